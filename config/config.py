@@ -13,10 +13,81 @@ import os
 import yaml
 from dataclasses import dataclass, field
 from decimal import Decimal
-from typing import List, Optional
+from typing import Any, Dict, List, Optional
 from pathlib import Path
 from web3 import Web3
 
+# Config fields passed to each secrets backend (field -> backend constructor argument)
+SECRETS_BACKEND_OPTIONS = {
+    "env": {},
+    "aws": {"aws_region": "region_name"},
+    "keystore": {"keystore_path": "path", "keystore_passphrase_file": "passphrase_file"},
+    "vault": {
+        "vault_addr": "addr", "vault_mount": "mount", "vault_kv_version": "kv_version",
+        "vault_secret_path": "secret_path",
+    },
+    "credentials": {"credentials_directory": "directory", "secrets_fd": "fd"},
+}
+
+# Options of a sub-account entry in `wallets` (docs/MULTI_WALLET.md)
+WALLET_OPTIONS = (
+    "name", "wallet_address", "private_key_env", "signer_socket_path", "funder_address",
+    "signature_type", "strategies", "capital", "max_portfolio_heat", "max_daily_drawdown",
+    "max_position_size_pct", "consecutive_loss_limit",
+)
+
+# Alert severities (lowest first), events and channel options (docs/ALERTING.md)
+ALERT_SEVERITIES = ("info", "warning", "error", "critical")
+ALERT_EVENTS = (
+    "trade_fill", "circuit_breaker", "volatility_halt", "low_balance", "gas_price",
+    "ledger_drift", "redemption", "system",
+)
+ALERT_CHANNEL_OPTIONS = {
+    "webhook": ("url", "url_env", "headers"),
+    "discord": ("url", "url_env"),
+    "telegram": ("chat_id", "bot_token_env", "api_url"),
+    "smtp": ("host", "port", "sender", "recipients", "username", "password_env", "use_tls"),
+    "sns": ("topic_arn",),
+}
+ALERT_COMMON_OPTIONS = ("type", "min_severity", "timeout_seconds")
+
+# LLM backend options (docs/LLM_BACKENDS.md)
+LLM_BACKEND_OPTIONS = {
+    "openai": ("base_url", "api_key_env", "headers", "extra_body"),
+    "llamacpp": ("base_url",),
+    "ollama": ("base_url",),
+    "mock": ("response", "delay_seconds", "error"),
+}
+LLM_COMMON_OPTIONS = (
+    "type", "model", "timeout_seconds", "max_tokens", "temperature",
+    "input_cost_per_1k_tokens", "output_cost_per_1k_tokens", "structured_output",
+)
+LLM_STRUCTURED_OUTPUT_MODES = ("json_schema", "json_object", "none")
+
+# Fields read from YAML as strings or floats and stored as Decimal
+DECIMAL_FIELDS = (
+    "stake_amount", "min_profit_threshold", "max_position_size", "min_position_size",
+    "min_balance", "target_balance", "withdraw_limit",
+)
+
+# Credentials that may come from the secrets backend instead of the YAML file
+SECRET_FIELDS = ("private_key", "wallet_address", "nvidia_api_key", "kalshi_api_key", "dashboard_api_token")
+
+# Fields applied to the running bot when the YAML file changes (docs/CONFIG_RELOAD.md);
+# editing any other field takes effect after a restart
+HOT_RELOAD_FIELDS = (
+    "min_profit_threshold", "max_position_size",
+    "max_pending_tx", "max_gas_price_gwei", "circuit_breaker_threshold",
+    "min_priority_fee_gwei", "fee_blocks_ahead", "max_fee_replacements", "auto_replace_stuck_tx",
+    "min_balance", "target_balance", "withdraw_limit",
+    "scan_interval_seconds", "heartbeat_interval_seconds",
+    "flash_crash_drop_threshold", "flash_crash_lookback_seconds",
+    "fifteen_min_maker_entries", "fifteen_min_maker_ttl_seconds",
+    "rl_exploration_rate", "rl_max_daily_explorations",
+    "market_making_half_spread", "market_making_quote_size", "market_making_max_inventory",
+    "market_making_pull_minutes",
+)
+
 
 @dataclass
 class Config:
@@ -30,13 +101,43 @@ class Config:
     polygon_rpc_url: str
     backup_rpc_urls: List[str] = field(default_factory=list)
     polymarket_api_url: str = "https://clob.polymarket.com"
-    kalshi_api_key: Optional[str] = None
+    kalshi_api_key: Optional[str] = None  # Kalshi API key ID
+    kalshi_private_key_path: Optional[str] = None  # RSA private key (PEM) of the Kalshi API key
+    kalshi_api_url: str = "https://api.elections.kalshi.com/trade-api/v2"
+    kalshi_series_tickers: List[str] = field(default_factory=lambda: ["KXBTCD", "KXETHD"])
+    kalshi_fixture_path: Optional[str] = None  # Recorded Kalshi fixture (offline, no credentials)
     nvidia_api_key: Optional[str] = None
     
+    # LLM backends for decisions and safety checks (docs/LLM_BACKENDS.md; YAML only).
+    # Without backends the NVIDIA API is used with nvidia_api_key.
+    llm_backends: Dict[str, Dict[str, Any]] = field(default_factory=dict)  # Name -> options (LLM_BACKEND_OPTIONS)
+    llm_backend_order: List[str] = field(default_factory=list)  # Failover order; default: order in llm_backends
+    llm_max_repair_attempts: int = 2  # Re-asks after a response that fails schema validation (docs/STRUCTURED_OUTPUT.md)
+    llm_rejected_responses_path: str = "data/llm_rejected_responses.jsonl"  # Raw invalid responses, for review
+    llm_decision_audit_path: Optional[str] = "data/llm_decisions.db"  # Decision audit trail (docs/LLM_DECISION_AUDIT.md); None: off
+    
+    # Secrets backend for the private key and API keys (docs/SECRETS.md)
+    secrets_backend: str = "env"  # env, aws, keystore, vault, credentials
+    secret_name: str = "polymarket-bot-credentials"  # AWS secret ID, Vault path or credential name
+    aws_region: str = "us-east-1"
+    keystore_path: Optional[str] = None  # Encrypted Ethereum JSON keystore
+    keystore_passphrase_file: Optional[str] = None  # Else KEYSTORE_PASSPHRASE or an interactive prompt
+    vault_addr: Optional[str] = None  # Token from VAULT_TOKEN or ~/.vault-token
+    vault_mount: str = "secret"
+    vault_kv_version: int = 2
+    vault_secret_path: Optional[str] = None  # Defaults to secret_name
+    credentials_directory: Optional[str] = None  # Defaults to $CREDENTIALS_DIRECTORY (systemd)
+    secrets_fd: Optional[int] = None  # Read the secret from this inherited file descriptor
+    
+    # Signer for orders and transactions (docs/SIGNER.md)
+    signer_backend: str = "local"  # local (key in this process) or remote (signing daemon)
+    signer_socket_path: str = "/run/polymarket-signer/signer.sock"
+    
     # Contract addresses
     usdc_address: str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
     ctf_exchange_address: str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"
     conditional_token_address: str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"
+    neg_risk_adapter_address: str = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"
     
     # Trading parameters
     stake_amount: Decimal = Decimal("10.0")
@@ -46,9 +147,16 @@ class Config:
     
     # Risk management
     max_pending_tx: int = 5
-    max_gas_price_gwei: int = 800
+    max_gas_price_gwei: int = 800  # Also caps maxFeePerGas of EIP-1559 transactions
     circuit_breaker_threshold: int = 10
     
+    # EIP-1559 fees and stuck transaction replacement (docs/TRANSACTION_FEES.md)
+    min_priority_fee_gwei: int = 30  # Tip floor (Polygon minimum)
+    fee_blocks_ahead: int = 3  # Blocks of base fee growth covered by maxFeePerGas
+    max_fee_replacements: int = 5  # Replace-by-fee attempts per nonce
+    auto_replace_stuck_tx: bool = True
+    tx_journal_db_path: str = "data/tx_journal.db"  # Submitted transactions, resumed after restarts (live only)
+    
     # Fund management (dynamic based on actual balance)
     min_balance: Decimal = Decimal("0.10")  # Minimum $0.10 for micro trading
     target_balance: Decimal = Decimal("10.0")  # Target $10 after deposit
@@ -59,6 +167,18 @@ class Config:
     sns_alert_topic: str = ""
     prometheus_port: int = 9090
     
+    # Alert channels and routing (docs/ALERTING.md; channels and routes are YAML only)
+    alert_channels: Dict[str, Dict[str, Any]] = field(default_factory=dict)  # Name -> options (ALERT_CHANNEL_OPTIONS)
+    alert_routes: Dict[str, List[str]] = field(default_factory=dict)  # Event (or "default") -> channel names
+    alert_dedup_seconds: int = 300  # Identical alerts within this window are sent once
+    alert_rate_limit_per_minute: int = 20  # Per channel; critical alerts are never rate limited
+    
+    # Web dashboard and control API (docs/WEB_DASHBOARD.md)
+    dashboard_enabled: bool = False
+    dashboard_host: str = "127.0.0.1"
+    dashboard_port: int = 8080
+    dashboard_api_token: Optional[str] = None  # Bearer token; without it the control endpoints are disabled
+    
     # Operational
     dry_run: bool = False
     scan_interval_seconds: int = 2  # Increased from 2 to 5 seconds (more sustainable)
@@ -68,12 +188,62 @@ class Config:
     chain_id: int = 137  # Polygon mainnet
     
     # Flash Crash Strategy settings
-    flash_crash_drop_threshold: float = 0.20  # 20% drop
-    flash_crash_lookback_seconds: int = 10
+    flash_crash_drop_threshold: float = 0.15  # 15% Binance move (15-minute strategy's check_flash_crash)
+    flash_crash_lookback_seconds: int = 3
     flash_crash_trade_size: float = 5.0
     flash_crash_take_profit: float = 0.10
     flash_crash_stop_loss: float = 0.05
     
+    # Strategies loaded by MainOrchestrator from the strategy registry, in priority order
+    enabled_strategies: List[str] = field(default_factory=lambda: ["fifteen_min_crypto"])
+    # Entry checks run by FifteenMinuteCryptoStrategy, in priority order
+    fifteen_min_entry_order: List[str] = field(
+        default_factory=lambda: ["flash_crash", "latency", "directional", "sum_to_one"]
+    )
+    # Rest directional entries and one sum-to-one leg (the other is taken as it fills) as post-only GTD maker orders
+    fifteen_min_maker_entries: bool = False
+    fifteen_min_maker_ttl_seconds: int = 120
+    
+    # Trade outcomes and engine state shared by the learning engines (docs/LEARNING_STORE.md)
+    learning_store_path: str = "data/learning_store.db"
+    
+    # Continuous-state RL policy of the 15-minute strategy (docs/RL_POLICIES.md)
+    rl_policy_dir: str = "data/rl_policies"  # Frozen policies written by python -m src.rl_training
+    rl_policy_version: Optional[int] = None  # Frozen version to trade with; None: newest
+    rl_experience_path: Optional[str] = "data/rl_experience.jsonl"  # Closed trades for offline training; None: off
+    rl_exploration_rate: float = 0.0  # Capped at ContinuousRLEngine.MAX_EXPLORATION_RATE
+    rl_exploration_margin: float = 0.005  # Explore only strategies valued within this return of the best choice
+    rl_max_daily_explorations: int = 10
+    
+    # Market making on 15-minute up/down markets (strategy "market_making")
+    market_making_half_spread: float = 0.02  # Quote distance from fair probability
+    market_making_quote_size: float = 10.0  # Shares per quote
+    market_making_max_inventory: float = 50.0  # Max net UP-DOWN shares per market
+    market_making_pull_minutes: float = 2.0  # Pull quotes this long before close
+    
+    # Market data recording (Binance ticks, Polymarket books/deltas/trades) for replay backtests
+    market_data_recording: bool = False
+    market_data_dir: str = "data/recordings"
+    market_data_rotate_minutes: int = 60
+    
+    # Position/PnL ledger reconciled against on-chain balances (live trading only)
+    ledger_db_path: str = "data/position_ledger.db"
+    ledger_reconcile_interval_seconds: int = 300  # 0 disables reconciliation
+    ledger_repair_drift: bool = True  # Adjust the ledger and drop orphan positions on drift
+    ledger_settlement_grace_seconds: int = 60  # Tokens filled more recently are not reconciled yet
+    
+    # Automatic redemption of resolved positions (EOA and Gnosis Safe wallets)
+    redemption_interval_seconds: int = 300  # 0 disables automatic redemption
+    
+    # Hot reload of the YAML file the config was loaded from (docs/CONFIG_RELOAD.md)
+    config_file: Optional[str] = None  # Set by from_yaml; CONFIG_FILE selects the file
+    config_reload_interval_seconds: int = 5  # 0 disables hot reload
+    config_audit_log_path: str = "data/config_audit.jsonl"
+    
+    # Sub-account wallets trading their own strategy books next to the primary wallet
+    # (docs/MULTI_WALLET.md; YAML only - each entry is a mapping, see WALLET_OPTIONS)
+    wallets: List[Dict[str, Any]] = field(default_factory=list)
+    
     def __post_init__(self):
         """Validate configuration after initialization."""
         self._validate()
@@ -83,9 +253,10 @@ class Config:
         """Validate all configuration parameters."""
         errors = []
         
-        # Validate private key
+        # Validate private key (held by the signing daemon when signer_backend is remote)
         if not self.private_key:
-            errors.append("private_key is required")
+            if self.signer_backend != "remote":
+                errors.append("private_key is required")
         elif not self.private_key.startswith("0x") and len(self.private_key) != 64:
             if len(self.private_key) != 66:  # 0x + 64 chars
                 errors.append("private_key must be 64 hex characters (with or without 0x prefix)")
@@ -103,7 +274,8 @@ class Config:
             errors.append(f"polygon_rpc_url must be a valid URL: {self.polygon_rpc_url}")
         
         # Validate contract addresses
-        for addr_name in ["usdc_address", "ctf_exchange_address", "conditional_token_address"]:
+        for addr_name in ["usdc_address", "ctf_exchange_address", "conditional_token_address",
+                          "neg_risk_adapter_address"]:
             addr = getattr(self, addr_name)
             if not Web3.is_address(addr):
                 errors.append(f"{addr_name} is not a valid Ethereum address: {addr}")
@@ -134,6 +306,17 @@ class Config:
         if self.circuit_breaker_threshold <= 0:
             errors.append(f"circuit_breaker_threshold must be positive, got: {self.circuit_breaker_threshold}")
         
+        if self.min_priority_fee_gwei < 0 or self.min_priority_fee_gwei >= self.max_gas_price_gwei:
+            errors.append(
+                f"min_priority_fee_gwei must be between 0 and max_gas_price_gwei, got: {self.min_priority_fee_gwei}"
+            )
+        
+        if self.fee_blocks_ahead < 1:
+            errors.append(f"fee_blocks_ahead must be at least 1, got: {self.fee_blocks_ahead}")
+        
+        if self.max_fee_replacements < 0:
+            errors.append(f"max_fee_replacements cannot be negative, got: {self.max_fee_replacements}")
+        
         # Validate fund management
         if self.min_balance < 0:
             errors.append(f"min_balance must be non-negative, got: {self.min_balance}")
@@ -154,57 +337,330 @@ class Config:
         if self.prometheus_port <= 0 or self.prometheus_port > 65535:
             errors.append(f"prometheus_port must be between 1 and 65535, got: {self.prometheus_port}")
         
+        errors.extend(self._validate_llm_backends())
+        errors.extend(self._validate_alerts())
+        
+        # Validate web dashboard
+        if self.dashboard_port <= 0 or self.dashboard_port > 65535:
+            errors.append(f"dashboard_port must be between 1 and 65535, got: {self.dashboard_port}")
+        elif self.dashboard_enabled and self.dashboard_port == self.prometheus_port:
+            errors.append(f"dashboard_port and prometheus_port must differ, both are: {self.dashboard_port}")
+        
+        if (self.dashboard_enabled and not self.dashboard_api_token and
+                self.dashboard_host not in ("127.0.0.1", "localhost", "::1")):
+            errors.append(f"dashboard_api_token is required when dashboard_host is not loopback: {self.dashboard_host}")
+        
+        # Validate secrets backend
+        if self.secrets_backend not in SECRETS_BACKEND_OPTIONS:
+            errors.append(
+                f"secrets_backend must be one of {list(SECRETS_BACKEND_OPTIONS)}, got: {self.secrets_backend}"
+            )
+        elif self.secrets_backend == "keystore" and not self.keystore_path:
+            errors.append("secrets_backend 'keystore' requires keystore_path")
+        elif self.secrets_backend == "vault" and not self.vault_addr:
+            errors.append("secrets_backend 'vault' requires vault_addr")
+        
+        if self.vault_kv_version not in (1, 2):
+            errors.append(f"vault_kv_version must be 1 or 2, got: {self.vault_kv_version}")
+        
+        # Validate signer
+        if self.signer_backend not in ("local", "remote"):
+            errors.append(f"signer_backend must be 'local' or 'remote', got: {self.signer_backend}")
+        elif self.signer_backend == "remote" and not self.signer_socket_path:
+            errors.append("signer_backend 'remote' requires signer_socket_path")
+        
+        # Validate strategy selection
+        if not self.enabled_strategies:
+            errors.append("enabled_strategies must list at least one strategy")
+        elif len(set(self.enabled_strategies)) != len(self.enabled_strategies):
+            errors.append(f"enabled_strategies contains duplicates: {self.enabled_strategies}")
+        
+        if ("cross_platform_arbitrage" in self.enabled_strategies and not self.kalshi_fixture_path
+                and not (self.kalshi_api_key and self.kalshi_private_key_path)):
+            errors.append("cross_platform_arbitrage requires kalshi_api_key and kalshi_private_key_path "
+                          "(or kalshi_fixture_path)")
+        
+        if len(set(self.fifteen_min_entry_order)) != len(self.fifteen_min_entry_order):
+            errors.append(f"fifteen_min_entry_order contains duplicates: {self.fifteen_min_entry_order}")
+        
+        if self.fifteen_min_maker_ttl_seconds <= 0:
+            errors.append(f"fifteen_min_maker_ttl_seconds must be positive, got: {self.fifteen_min_maker_ttl_seconds}")
+        
+        # Validate RL policy settings
+        if self.rl_policy_version is not None and self.rl_policy_version < 1:
+            errors.append(f"rl_policy_version must be at least 1, got: {self.rl_policy_version}")
+        
+        if not 0 <= self.rl_exploration_rate <= 0.10:
+            errors.append(f"rl_exploration_rate must be between 0 and 0.10, got: {self.rl_exploration_rate}")
+        
+        if self.rl_exploration_margin < 0:
+            errors.append(f"rl_exploration_margin must be non-negative, got: {self.rl_exploration_margin}")
+        
+        if self.rl_max_daily_explorations < 0:
+            errors.append(f"rl_max_daily_explorations must be non-negative, got: {self.rl_max_daily_explorations}")
+        
+        # Validate market making settings
+        if not 0 < self.market_making_half_spread < 0.5:
+            errors.append(f"market_making_half_spread must be between 0 and 0.5, got: {self.market_making_half_spread}")
+        
+        if self.market_making_quote_size < 5:
+            errors.append(f"market_making_quote_size must be at least 5 shares, got: {self.market_making_quote_size}")
+        
+        if self.market_making_max_inventory <= 0:
+            errors.append(f"market_making_max_inventory must be positive, got: {self.market_making_max_inventory}")
+        
+        if self.market_making_pull_minutes < 0:
+            errors.append(f"market_making_pull_minutes must be non-negative, got: {self.market_making_pull_minutes}")
+        
+        if self.market_data_rotate_minutes <= 0:
+            errors.append(f"market_data_rotate_minutes must be positive, got: {self.market_data_rotate_minutes}")
+        
+        if self.ledger_reconcile_interval_seconds < 0:
+            errors.append(f"ledger_reconcile_interval_seconds must be non-negative, got: {self.ledger_reconcile_interval_seconds}")
+        
+        if self.ledger_settlement_grace_seconds < 0:
+            errors.append(f"ledger_settlement_grace_seconds must be non-negative, got: {self.ledger_settlement_grace_seconds}")
+        
+        if self.redemption_interval_seconds < 0:
+            errors.append(f"redemption_interval_seconds must be non-negative, got: {self.redemption_interval_seconds}")
+        
+        if self.config_reload_interval_seconds < 0:
+            errors.append(
+                f"config_reload_interval_seconds must be non-negative, got: {self.config_reload_interval_seconds}"
+            )
+        
+        errors.extend(self._validate_wallets())
+        
         if errors:
             error_msg = "Configuration validation failed:\n" + "\n".join(f"  - {e}" for e in errors)
             raise ValueError(error_msg)
     
+    def _validate_llm_backends(self) -> List[str]:
+        """Validate LLM backends and their failover order."""
+        errors = []
+        
+        for name, backend in self.llm_backends.items():
+            label = f"llm_backends.{name}"
+            if not isinstance(backend, dict):
+                errors.append(f"{label} must be a mapping")
+                continue
+            
+            kind = backend.get("type")
+            if kind not in LLM_BACKEND_OPTIONS:
+                errors.append(f"{label}: type must be one of {list(LLM_BACKEND_OPTIONS)}, got: {kind}")
+                continue
+            unknown = set(backend) - set(LLM_BACKEND_OPTIONS[kind]) - set(LLM_COMMON_OPTIONS)
+            if unknown:
+                errors.append(f"{label} has unknown options for type {kind}: {sorted(unknown)}")
+            
+            if kind == "openai" and not (backend.get("base_url") and backend.get("model")):
+                errors.append(f"{label}: base_url and model are required")
+            elif kind == "ollama" and not backend.get("model"):
+                errors.append(f"{label}: model is required")
+            if backend.get("structured_output", "json_schema") not in LLM_STRUCTURED_OUTPUT_MODES:
+                errors.append(f"{label}: structured_output must be one of {list(LLM_STRUCTURED_OUTPUT_MODES)}")
+            if float(backend.get("timeout_seconds", 10)) <= 0:
+                errors.append(f"{label}: timeout_seconds must be positive")
+            for key in ("input_cost_per_1k_tokens", "output_cost_per_1k_tokens"):
+                if Decimal(str(backend.get(key, 0))) < 0:
+                    errors.append(f"{label}: {key} must be non-negative")
+        
+        missing = [name for name in self.llm_backend_order if name not in self.llm_backends]
+        if missing:
+            errors.append(f"llm_backend_order names unknown backends: {missing}")
+        if len(set(self.llm_backend_order)) != len(self.llm_backend_order):
+            errors.append("llm_backend_order lists a backend more than once")
+        if self.llm_max_repair_attempts < 0:
+            errors.append(f"llm_max_repair_attempts must be non-negative, got: {self.llm_max_repair_attempts}")
+        
+        return errors
+    
+    def _validate_alerts(self) -> List[str]:
+        """Validate alert channels, routes and limits."""
+        errors = []
+        
+        if self.alert_dedup_seconds < 0:
+            errors.append(f"alert_dedup_seconds must be non-negative, got: {self.alert_dedup_seconds}")
+        if self.alert_rate_limit_per_minute <= 0:
+            errors.append(f"alert_rate_limit_per_minute must be positive, got: {self.alert_rate_limit_per_minute}")
+        
+        for name, channel in self.alert_channels.items():
+            label = f"alert_channels.{name}"
+            if not isinstance(channel, dict):
+                errors.append(f"{label} must be a mapping")
+                continue
+            
+            kind = channel.get("type")
+            if kind not in ALERT_CHANNEL_OPTIONS:
+                errors.append(f"{label}: type must be one of {list(ALERT_CHANNEL_OPTIONS)}, got: {kind}")
+                continue
+            unknown = set(channel) - set(ALERT_CHANNEL_OPTIONS[kind]) - set(ALERT_COMMON_OPTIONS)
+            if unknown:
+                errors.append(f"{label} has unknown options for type {kind}: {sorted(unknown)}")
+            
+            if channel.get("min_severity", "info") not in ALERT_SEVERITIES:
+                errors.append(f"{label}: min_severity must be one of {list(ALERT_SEVERITIES)}")
+            if kind in ("webhook", "discord") and not (channel.get("url") or channel.get("url_env")):
+                errors.append(f"{label}: url or url_env is required")
+            elif kind == "telegram" and not channel.get("chat_id"):
+                errors.append(f"{label}: chat_id is required")
+            elif kind == "smtp" and not (channel.get("host") and channel.get("sender") and channel.get("recipients")):
+                errors.append(f"{label}: host, sender and recipients are required")
+            elif kind == "sns" and not (channel.get("topic_arn") or self.sns_alert_topic):
+                errors.append(f"{label}: topic_arn is required (or set sns_alert_topic)")
+        
+        for event, channels in self.alert_routes.items():
+            if event not in ALERT_EVENTS and event != "default":
+                errors.append(f"alert_routes: unknown event '{event}' (events: {', '.join(ALERT_EVENTS)}, default)")
+            elif not isinstance(channels, list):
+                errors.append(f"alert_routes.{event} must be a list of channel names")
+            else:
+                missing = [name for name in channels if name not in self.alert_channels]
+                if missing:
+                    errors.append(f"alert_routes.{event} names unknown channels: {missing}")
+        
+        return errors
+    
+    def _validate_wallets(self) -> List[str]:
+        """Validate the sub-account wallet entries."""
+        errors = []
+        names = {"primary"}
+        addresses = {self.wallet_address.lower()} if self.wallet_address else set()
+        
+        for index, wallet in enumerate(self.wallets):
+            if not isinstance(wallet, dict):
+                errors.append(f"wallets[{index}] must be a mapping")
+                continue
+            
+            name = wallet.get("name")
+            label = f"wallets[{index}]" + (f" ({name})" if name else "")
+            unknown = set(wallet) - set(WALLET_OPTIONS)
+            if unknown:
+                errors.append(f"{label} has unknown options: {sorted(unknown)}")
+            
+            if not name or not isinstance(name, str) or not name.replace("_", "").replace("-", "").isalnum():
+                errors.append(f"{label} needs a name of letters, digits, '-' and '_'")
+            elif name in names:
+                errors.append(f"{label}: wallet name '{name}' is used twice ('primary' is the top-level wallet)")
+            names.add(name)
+            
+            address = wallet.get("wallet_address", "")
+            if not address or not Web3.is_address(address):
+                errors.append(f"{label}: wallet_address is not a valid Ethereum address: {address}")
+            elif address.lower() in addresses:
+                errors.append(f"{label}: wallet {address} is already configured")
+            else:
+                addresses.add(address.lower())
+            
+            if self.signer_backend == "remote":
+                if not wallet.get("signer_socket_path"):
+                    errors.append(f"{label}: signer_backend 'remote' requires signer_socket_path")
+            elif not wallet.get("private_key_env"):
+                errors.append(f"{label}: private_key_env (environment variable holding the key) is required")
+            
+            funder = wallet.get("funder_address")
+            if funder and not Web3.is_address(funder):
+                errors.append(f"{label}: funder_address is not a valid Ethereum address: {funder}")
+            
+            if wallet.get("signature_type", 0) not in (0, 1, 2):
+                errors.append(f"{label}: signature_type must be 0, 1 or 2, got: {wallet.get('signature_type')}")
+            elif wallet.get("signature_type") in (1, 2) and not funder:
+                errors.append(f"{label}: signature_type {wallet.get('signature_type')} requires funder_address")
+            
+            strategies = wallet.get("strategies", [])
+            if not isinstance(strategies, list) or len(set(strategies)) != len(strategies):
+                errors.append(f"{label}: strategies must be a list without duplicates, got: {strategies}")
+            
+            for key in ("capital", "max_portfolio_heat", "max_daily_drawdown", "max_position_size_pct",
+                        "consecutive_loss_limit"):
+                if key not in wallet:
+                    continue
+                try:
+                    value = Decimal(str(wallet[key]))
+                except ArithmeticError:
+                    value = None
+                if value is None or value.is_nan() or not value > 0:
+                    errors.append(f"{label}: {key} must be positive, got: {wallet[key]}")
+                elif key in ("max_daily_drawdown", "max_position_size_pct") and value > 1:
+                    errors.append(f"{label}: {key} is a fraction and must be at most 1, got: {wallet[key]}")
+        
+        return errors
+    
     def _convert_to_checksum_addresses(self):
         """Convert all addresses to checksum format."""
         self.wallet_address = Web3.to_checksum_address(self.wallet_address)
         self.usdc_address = Web3.to_checksum_address(self.usdc_address)
         self.ctf_exchange_address = Web3.to_checksum_address(self.ctf_exchange_address)
         self.conditional_token_address = Web3.to_checksum_address(self.conditional_token_address)
+        self.neg_risk_adapter_address = Web3.to_checksum_address(self.neg_risk_adapter_address)
     
     @classmethod
     def from_env(cls, use_aws_secrets: bool = False, secret_name: str = "polymarket-bot-credentials") -> "Config":
         """
-        Load configuration from environment variables or AWS Secrets Manager.
+        Load configuration from environment variables and the selected secrets backend.
         
         Args:
             use_aws_secrets: If True, retrieve private key from AWS Secrets Manager
-            secret_name: Name of the secret in AWS Secrets Manager
+                (same as SECRETS_BACKEND=aws)
+            secret_name: Name of the secret (SECRET_NAME overrides)
         """
         from dotenv import load_dotenv
         load_dotenv()
         
-        # Retrieve secrets (private key, API keys) from AWS or environment
-        if use_aws_secrets:
+        use_aws_secrets = use_aws_secrets or os.getenv("USE_AWS_SECRETS", "false").lower() in ("true", "1", "yes")
+        secrets_fd = os.getenv("SECRETS_FD")
+        secret_settings = {
+            "secrets_backend": "aws" if use_aws_secrets else os.getenv("SECRETS_BACKEND", "env").lower(),
+            "secret_name": os.getenv("SECRET_NAME", secret_name),
+            "aws_region": os.getenv("AWS_REGION", "us-east-1"),
+            "keystore_path": os.getenv("KEYSTORE_PATH") or None,
+            "keystore_passphrase_file": os.getenv("KEYSTORE_PASSPHRASE_FILE") or None,
+            "vault_addr": os.getenv("VAULT_ADDR") or None,
+            "vault_mount": os.getenv("VAULT_MOUNT", "secret"),
+            "vault_kv_version": int(os.getenv("VAULT_KV_VERSION", "2")),
+            "vault_secret_path": os.getenv("VAULT_SECRET_PATH") or None,
+            "credentials_directory": os.getenv("CREDENTIALS_DIRECTORY") or None,
+            "secrets_fd": int(secrets_fd) if secrets_fd else None,
+        }
+        
+        # Retrieve secrets (private key, API keys) from the backend or environment
+        secret_data = {}
+        if secret_settings["secrets_backend"] == "aws":
             try:
-                from src.secrets_manager import get_secrets_manager
-                secrets_mgr = get_secrets_manager(use_aws=True)
-                secret_data = secrets_mgr.get_secret(secret_name)
-                
-                private_key = secret_data.get('private_key', '')
-                wallet_address = secret_data.get('wallet_address', os.getenv("WALLET_ADDRESS", ""))
-                nvidia_api_key = secret_data.get('nvidia_api_key', os.getenv("NVIDIA_API_KEY"))
-                kalshi_api_key = secret_data.get('kalshi_api_key', os.getenv("KALSHI_API_KEY"))
+                secret_data = cls.load_secrets(secret_settings)
             except Exception as e:
                 import logging
                 logging.warning(f"Failed to retrieve secrets from AWS: {e}. Falling back to environment variables.")
-                private_key = os.getenv("PRIVATE_KEY", "")
-                wallet_address = os.getenv("WALLET_ADDRESS", "")
-                nvidia_api_key = os.getenv("NVIDIA_API_KEY")
-                kalshi_api_key = os.getenv("KALSHI_API_KEY")
-        else:
-            private_key = os.getenv("PRIVATE_KEY", "")
-            wallet_address = os.getenv("WALLET_ADDRESS", "")
-            nvidia_api_key = os.getenv("NVIDIA_API_KEY")
-            kalshi_api_key = os.getenv("KALSHI_API_KEY")
+        elif secret_settings["secrets_backend"] != "env":
+            # No silent fallback: the point of these backends is keeping the key out of .env
+            secret_data = cls.load_secrets(secret_settings)
+        
+        private_key = secret_data.get('private_key', os.getenv("PRIVATE_KEY", ""))
+        wallet_address = secret_data.get('wallet_address', os.getenv("WALLET_ADDRESS", ""))
+        nvidia_api_key = secret_data.get('nvidia_api_key', os.getenv("NVIDIA_API_KEY"))
+        kalshi_api_key = secret_data.get('kalshi_api_key', os.getenv("KALSHI_API_KEY"))
+        dashboard_api_token = secret_data.get('dashboard_api_token', os.getenv("DASHBOARD_API_TOKEN") or None)
         
         # Parse backup RPC URLs
         backup_rpcs = os.getenv("BACKUP_RPC_URLS", "")
         backup_rpc_list = [url.strip() for url in backup_rpcs.split(",") if url.strip()]
         
+        # Parse strategy selection (comma-separated, in priority order)
+        enabled_strategies = [
+            name.strip() for name in os.getenv("ENABLED_STRATEGIES", "fifteen_min_crypto").split(",")
+            if name.strip()
+        ]
+        kalshi_series_tickers = [
+            name.strip() for name in os.getenv("KALSHI_SERIES_TICKERS", "KXBTCD,KXETHD").split(",")
+            if name.strip()
+        ]
+        fifteen_min_entry_order = [
+            name.strip() for name in os.getenv(
+                "FIFTEEN_MIN_ENTRY_ORDER", "flash_crash,latency,directional,sum_to_one"
+            ).split(",")
+            if name.strip()
+        ]
+        
         return cls(
             # Wallet & Keys
             private_key=private_key,
@@ -215,12 +671,23 @@ class Config:
             backup_rpc_urls=backup_rpc_list,
             polymarket_api_url=os.getenv("POLYMARKET_API_URL", "https://clob.polymarket.com"),
             kalshi_api_key=kalshi_api_key,
+            kalshi_private_key_path=os.getenv("KALSHI_PRIVATE_KEY_PATH") or None,
+            kalshi_api_url=os.getenv("KALSHI_API_URL", "https://api.elections.kalshi.com/trade-api/v2"),
+            kalshi_series_tickers=kalshi_series_tickers,
+            kalshi_fixture_path=os.getenv("KALSHI_FIXTURE_PATH") or None,
             nvidia_api_key=nvidia_api_key,
+            llm_max_repair_attempts=int(os.getenv("LLM_MAX_REPAIR_ATTEMPTS", "2")),
+            llm_rejected_responses_path=os.getenv("LLM_REJECTED_RESPONSES_PATH", "data/llm_rejected_responses.jsonl"),
+            llm_decision_audit_path=os.getenv("LLM_DECISION_AUDIT_PATH", "data/llm_decisions.db") or None,
+            **secret_settings,
+            signer_backend=os.getenv("SIGNER_BACKEND", "local").lower(),
+            signer_socket_path=os.getenv("SIGNER_SOCKET_PATH", "/run/polymarket-signer/signer.sock"),
             
             # Contract addresses
             usdc_address=os.getenv("USDC_ADDRESS", "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"),
             ctf_exchange_address=os.getenv("CTF_EXCHANGE_ADDRESS", "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"),
             conditional_token_address=os.getenv("CONDITIONAL_TOKEN_ADDRESS", "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"),
+            neg_risk_adapter_address=os.getenv("NEG_RISK_ADAPTER_ADDRESS", "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
             
             # Trading parameters
             stake_amount=Decimal(os.getenv("STAKE_AMOUNT", "10.0")),
@@ -231,6 +698,11 @@ class Config:
             # Risk management
             max_pending_tx=int(os.getenv("MAX_PENDING_TX", "5")),
             max_gas_price_gwei=int(os.getenv("MAX_GAS_PRICE_GWEI", "800")),
+            min_priority_fee_gwei=int(os.getenv("MIN_PRIORITY_FEE_GWEI", "30")),
+            fee_blocks_ahead=int(os.getenv("FEE_BLOCKS_AHEAD", "3")),
+            max_fee_replacements=int(os.getenv("MAX_FEE_REPLACEMENTS", "5")),
+            auto_replace_stuck_tx=os.getenv("AUTO_REPLACE_STUCK_TX", "true").lower() in ("true", "1", "yes"),
+            tx_journal_db_path=os.getenv("TX_JOURNAL_DB_PATH", "data/tx_journal.db"),
             circuit_breaker_threshold=int(os.getenv("CIRCUIT_BREAKER_THRESHOLD", "10")),
             
             # Fund management
@@ -242,6 +714,12 @@ class Config:
             cloudwatch_log_group=os.getenv("CLOUDWATCH_LOG_GROUP", "/polymarket-arbitrage-bot"),
             sns_alert_topic=os.getenv("SNS_ALERT_TOPIC", ""),
             prometheus_port=int(os.getenv("PROMETHEUS_PORT", "9090")),
+            alert_dedup_seconds=int(os.getenv("ALERT_DEDUP_SECONDS", "300")),
+            alert_rate_limit_per_minute=int(os.getenv("ALERT_RATE_LIMIT_PER_MINUTE", "20")),
+            dashboard_enabled=os.getenv("DASHBOARD_ENABLED", "false").lower() in ("true", "1", "yes"),
+            dashboard_host=os.getenv("DASHBOARD_HOST", "127.0.0.1"),
+            dashboard_port=int(os.getenv("DASHBOARD_PORT", "8080")),
+            dashboard_api_token=dashboard_api_token,
             
             # Operational
             dry_run=os.getenv("DRY_RUN", "false").lower() in ("true", "1", "yes"),
@@ -252,11 +730,46 @@ class Config:
             chain_id=int(os.getenv("CHAIN_ID", "137")),
             
             # Flash Crash Strategy
-            flash_crash_drop_threshold=float(os.getenv("FLASH_CRASH_DROP_THRESHOLD", "0.20")),
-            flash_crash_lookback_seconds=int(os.getenv("FLASH_CRASH_LOOKBACK_SECONDS", "10")),
+            flash_crash_drop_threshold=float(os.getenv("FLASH_CRASH_DROP_THRESHOLD", "0.15")),
+            flash_crash_lookback_seconds=int(os.getenv("FLASH_CRASH_LOOKBACK_SECONDS", "3")),
             flash_crash_trade_size=float(os.getenv("FLASH_CRASH_TRADE_SIZE", "5.0")),
             flash_crash_take_profit=float(os.getenv("FLASH_CRASH_TAKE_PROFIT", "0.10")),
             flash_crash_stop_loss=float(os.getenv("FLASH_CRASH_STOP_LOSS", "0.05")),
+            
+            # Strategy selection
+            enabled_strategies=enabled_strategies,
+            fifteen_min_entry_order=fifteen_min_entry_order,
+            fifteen_min_maker_entries=os.getenv("FIFTEEN_MIN_MAKER_ENTRIES", "false").lower() in ("true", "1", "yes"),
+            fifteen_min_maker_ttl_seconds=int(os.getenv("FIFTEEN_MIN_MAKER_TTL_SECONDS", "120")),
+            
+            # Learning store
+            learning_store_path=os.getenv("LEARNING_STORE_PATH", "data/learning_store.db"),
+            
+            # RL policy
+            rl_policy_dir=os.getenv("RL_POLICY_DIR", "data/rl_policies"),
+            rl_policy_version=int(os.getenv("RL_POLICY_VERSION")) if os.getenv("RL_POLICY_VERSION") else None,
+            rl_experience_path=os.getenv("RL_EXPERIENCE_PATH", "data/rl_experience.jsonl") or None,
+            rl_exploration_rate=float(os.getenv("RL_EXPLORATION_RATE", "0.0")),
+            rl_exploration_margin=float(os.getenv("RL_EXPLORATION_MARGIN", "0.005")),
+            rl_max_daily_explorations=int(os.getenv("RL_MAX_DAILY_EXPLORATIONS", "10")),
+            
+            # Market making
+            market_making_half_spread=float(os.getenv("MARKET_MAKING_HALF_SPREAD", "0.02")),
+            market_making_quote_size=float(os.getenv("MARKET_MAKING_QUOTE_SIZE", "10.0")),
+            market_making_max_inventory=float(os.getenv("MARKET_MAKING_MAX_INVENTORY", "50.0")),
+            market_making_pull_minutes=float(os.getenv("MARKET_MAKING_PULL_MINUTES", "2.0")),
+            
+            # Market data recording
+            market_data_recording=os.getenv("MARKET_DATA_RECORDING", "false").lower() in ("true", "1", "yes"),
+            market_data_dir=os.getenv("MARKET_DATA_DIR", "data/recordings"),
+            market_data_rotate_minutes=int(os.getenv("MARKET_DATA_ROTATE_MINUTES", "60")),
+            
+            # Position ledger
+            ledger_db_path=os.getenv("LEDGER_DB_PATH", "data/position_ledger.db"),
+            ledger_reconcile_interval_seconds=int(os.getenv("LEDGER_RECONCILE_INTERVAL_SECONDS", "300")),
+            ledger_repair_drift=os.getenv("LEDGER_REPAIR_DRIFT", "true").lower() in ("true", "1", "yes"),
+            ledger_settlement_grace_seconds=int(os.getenv("LEDGER_SETTLEMENT_GRACE_SECONDS", "60")),
+            redemption_interval_seconds=int(os.getenv("REDEMPTION_INTERVAL_SECONDS", "300")),
         )
     
     @classmethod
@@ -272,21 +785,49 @@ class Config:
         if not data:
             raise ValueError(f"Empty configuration file: {yaml_path}")
         
+        # Keys kept out of the YAML file come from the selected secrets backend
+        if data.get("secrets_backend", "env") != "env" and not data.get("private_key"):
+            secret_data = cls.load_secrets(data)
+            for key in SECRET_FIELDS:
+                if secret_data.get(key) and not data.get(key):
+                    data[key] = secret_data[key]
+        
         # Convert string decimals to Decimal objects
-        for key in ["stake_amount", "min_profit_threshold", "max_position_size", 
-                    "min_position_size", "min_balance", "target_balance", "withdraw_limit"]:
+        for key in DECIMAL_FIELDS:
             if key in data:
                 data[key] = Decimal(str(data[key]))
         
+        data["config_file"] = str(path)
         return cls(**data)
     
+    @staticmethod
+    def load_secrets(settings: dict) -> dict:
+        """
+        Retrieve credentials from the secrets backend selected in `settings`.
+        
+        Args:
+            settings: Config field values (secrets_backend, secret_name and the backend's options)
+            
+        Returns:
+            Secret dictionary (private_key and optional wallet/API keys)
+        """
+        from src.secrets_backends import create_secrets_backend
+        from src.secrets_manager import get_secrets_manager
+        
+        kind = settings.get("secrets_backend") or "env"
+        if kind not in SECRETS_BACKEND_OPTIONS:
+            raise ValueError(f"Unknown secrets backend '{kind}' (available: {', '.join(SECRETS_BACKEND_OPTIONS)})")
+        options = {arg: settings.get(name) for name, arg in SECRETS_BACKEND_OPTIONS[kind].items()}
+        secrets_mgr = get_secrets_manager(backend=create_secrets_backend(kind, **options))
+        return secrets_mgr.get_secret(settings.get("secret_name") or "polymarket-bot-credentials")
+    
     @classmethod
     def load(cls, yaml_path: Optional[str] = None, use_aws_secrets: bool = False, 
              secret_name: str = "polymarket-bot-credentials") -> "Config":
         """
         Load configuration with priority:
-        1. YAML file (if provided)
-        2. AWS Secrets Manager (if use_aws_secrets=True)
+        1. YAML file (if provided, else CONFIG_FILE)
+        2. Secrets backend (secrets_backend; AWS Secrets Manager if use_aws_secrets=True)
         3. Environment variables
         4. Default values
         
@@ -295,6 +836,7 @@ class Config:
             use_aws_secrets: If True, retrieve secrets from AWS Secrets Manager
             secret_name: Name of the secret in AWS Secrets Manager
         """
+        yaml_path = yaml_path or os.getenv("CONFIG_FILE")
         if yaml_path:
             return cls.from_yaml(yaml_path)
         else:
@@ -308,16 +850,47 @@ class Config:
             "backup_rpc_urls": self.backup_rpc_urls,
             "polymarket_api_url": self.polymarket_api_url,
             "has_kalshi_api_key": bool(self.kalshi_api_key),
+            "kalshi_private_key_path": self.kalshi_private_key_path,
+            "kalshi_api_url": self.kalshi_api_url,
+            "kalshi_series_tickers": list(self.kalshi_series_tickers),
+            "kalshi_fixture_path": self.kalshi_fixture_path,
             "has_nvidia_api_key": bool(self.nvidia_api_key),
+            "llm_backends": {
+                name: {"type": backend.get("type"), "model": backend.get("model")}
+                for name, backend in self.llm_backends.items()
+            },
+            "llm_backend_order": list(self.llm_backend_order),
+            "llm_max_repair_attempts": self.llm_max_repair_attempts,
+            "llm_rejected_responses_path": self.llm_rejected_responses_path,
+            "llm_decision_audit_path": self.llm_decision_audit_path,
+            "secrets_backend": self.secrets_backend,
+            "secret_name": self.secret_name,
+            "aws_region": self.aws_region,
+            "keystore_path": self.keystore_path,
+            "keystore_passphrase_file": self.keystore_passphrase_file,
+            "vault_addr": self.vault_addr,
+            "vault_mount": self.vault_mount,
+            "vault_kv_version": self.vault_kv_version,
+            "vault_secret_path": self.vault_secret_path,
+            "signer_backend": self.signer_backend,
+            "signer_socket_path": self.signer_socket_path,
+            "credentials_directory": self.credentials_directory,
+            "secrets_fd": self.secrets_fd,
             "usdc_address": self.usdc_address,
             "ctf_exchange_address": self.ctf_exchange_address,
             "conditional_token_address": self.conditional_token_address,
+            "neg_risk_adapter_address": self.neg_risk_adapter_address,
             "stake_amount": str(self.stake_amount),
             "min_profit_threshold": str(self.min_profit_threshold),
             "max_position_size": str(self.max_position_size),
             "min_position_size": str(self.min_position_size),
             "max_pending_tx": self.max_pending_tx,
             "max_gas_price_gwei": self.max_gas_price_gwei,
+            "min_priority_fee_gwei": self.min_priority_fee_gwei,
+            "fee_blocks_ahead": self.fee_blocks_ahead,
+            "max_fee_replacements": self.max_fee_replacements,
+            "auto_replace_stuck_tx": self.auto_replace_stuck_tx,
+            "tx_journal_db_path": self.tx_journal_db_path,
             "circuit_breaker_threshold": self.circuit_breaker_threshold,
             "min_balance": str(self.min_balance),
             "target_balance": str(self.target_balance),
@@ -325,10 +898,51 @@ class Config:
             "cloudwatch_log_group": self.cloudwatch_log_group,
             "sns_alert_topic": self.sns_alert_topic,
             "prometheus_port": self.prometheus_port,
+            "alert_channels": {
+                name: {"type": channel.get("type"), "min_severity": channel.get("min_severity", "info")}
+                for name, channel in self.alert_channels.items()
+            },
+            "alert_routes": {event: list(channels) for event, channels in self.alert_routes.items()},
+            "alert_dedup_seconds": self.alert_dedup_seconds,
+            "alert_rate_limit_per_minute": self.alert_rate_limit_per_minute,
+            "dashboard_enabled": self.dashboard_enabled,
+            "dashboard_host": self.dashboard_host,
+            "dashboard_port": self.dashboard_port,
+            "has_dashboard_api_token": bool(self.dashboard_api_token),
             "dry_run": self.dry_run,
             "scan_interval_seconds": self.scan_interval_seconds,
             "heartbeat_interval_seconds": self.heartbeat_interval_seconds,
             "chain_id": self.chain_id,
+            "enabled_strategies": list(self.enabled_strategies),
+            "fifteen_min_entry_order": list(self.fifteen_min_entry_order),
+            "fifteen_min_maker_entries": self.fifteen_min_maker_entries,
+            "fifteen_min_maker_ttl_seconds": self.fifteen_min_maker_ttl_seconds,
+            "learning_store_path": self.learning_store_path,
+            "rl_policy_dir": self.rl_policy_dir,
+            "rl_policy_version": self.rl_policy_version,
+            "rl_experience_path": self.rl_experience_path,
+            "rl_exploration_rate": self.rl_exploration_rate,
+            "rl_exploration_margin": self.rl_exploration_margin,
+            "rl_max_daily_explorations": self.rl_max_daily_explorations,
+            "market_making_half_spread": self.market_making_half_spread,
+            "market_making_quote_size": self.market_making_quote_size,
+            "market_making_max_inventory": self.market_making_max_inventory,
+            "market_making_pull_minutes": self.market_making_pull_minutes,
+            "market_data_recording": self.market_data_recording,
+            "market_data_dir": self.market_data_dir,
+            "market_data_rotate_minutes": self.market_data_rotate_minutes,
+            "ledger_db_path": self.ledger_db_path,
+            "ledger_reconcile_interval_seconds": self.ledger_reconcile_interval_seconds,
+            "ledger_repair_drift": self.ledger_repair_drift,
+            "ledger_settlement_grace_seconds": self.ledger_settlement_grace_seconds,
+            "redemption_interval_seconds": self.redemption_interval_seconds,
+            "config_file": self.config_file,
+            "config_reload_interval_seconds": self.config_reload_interval_seconds,
+            "config_audit_log_path": self.config_audit_log_path,
+            "wallets": [
+                {key: str(value) if isinstance(value, Decimal) else value for key, value in wallet.items()}
+                for wallet in self.wallets
+            ],
         }
         return config_dict
 
@@ -18,7 +18,7 @@ import json
 import logging
 from decimal import Decimal
 from datetime import datetime, timedelta, timezone
-from typing import Dict, List, Optional, Tuple
+from typing import Any, Dict, List, Optional, Tuple
 from dataclasses import dataclass, asdict
 from pathlib import Path
 import statistics
@@ -74,11 +74,14 @@ class AdaptiveLearningEngine:
     - Reduces risk after losses, increases after wins
     """
     
+    STATE_NAME = "adaptive_learning"
+    
     def __init__(
         self,
         data_file: str = "data/adaptive_learning.json",
         learning_rate: float = 0.1,  # How fast to adapt (0.1 = 10% adjustment)
-        min_trades_for_learning: int = 10  # Minimum trades before adapting
+        min_trades_for_learning: int = 10,  # Minimum trades before adapting
+        store: Optional[Any] = None
     ):
         """
         Initialize Adaptive Learning Engine.
@@ -87,8 +90,11 @@ class AdaptiveLearningEngine:
             data_file: Path to store learning data
             learning_rate: How aggressively to adapt (0.0-1.0)
             min_trades_for_learning: Minimum trades before making adjustments
+            store: TradeOutcomeStore holding the trade history and learned parameters
+                (instead of data_file); subscribe on_trade_outcome to it
         """
         self.data_file = Path(data_file)
+        self.store = store
         self.learning_rate = learning_rate
         self.min_trades_for_learning = min_trades_for_learning
         
@@ -208,6 +214,25 @@ class AdaptiveLearningEngine:
         # Save data
         self._save_data()
     
+    def on_trade_outcome(self, outcome: Any) -> None:
+        """Learn from a trade recorded in the TradeOutcomeStore."""
+        self.record_trade(self._to_trade_outcome(outcome))
+    
+    @staticmethod
+    def _to_trade_outcome(outcome: Any) -> TradeOutcome:
+        return TradeOutcome(
+            timestamp=outcome.closed_at,
+            asset=outcome.asset,
+            side=outcome.side,
+            entry_price=Decimal(str(outcome.entry_price)),
+            exit_price=Decimal(str(outcome.exit_price)),
+            profit_pct=Decimal(str(outcome.profit_pct)),
+            hold_time_minutes=outcome.hold_time_minutes,
+            exit_reason=outcome.exit_reason,
+            strategy_used=outcome.strategy,
+            time_of_day=outcome.closed_at.hour
+        )
+    
     def get_adaptive_parameters(self, market_conditions: Optional[MarketConditions] = None) -> AdaptiveParameters:
         """
         Get current adaptive parameters, optionally adjusted for market conditions.
@@ -539,11 +564,8 @@ class AdaptiveLearningEngine:
         }
     
     def _save_data(self) -> None:
-        """Save learning data to disk."""
+        """Save learning data to the store, or to disk without one."""
         try:
-            # Ensure data directory exists
-            self.data_file.parent.mkdir(parents=True, exist_ok=True)
-            
             data = {
                 "total_trades": self.total_trades,
                 "winning_trades": self.winning_trades,
@@ -582,23 +604,31 @@ class AdaptiveLearningEngine:
                     }
                     for hour, perf in self.hourly_performance.items()
                 },
-                "trade_outcomes": [
-                    {
-                        "timestamp": t.timestamp.isoformat(),
-                        "asset": t.asset,
-                        "side": t.side,
-                        "entry_price": str(t.entry_price),
-                        "exit_price": str(t.exit_price),
-                        "profit_pct": str(t.profit_pct),
-                        "hold_time_minutes": t.hold_time_minutes,
-                        "exit_reason": t.exit_reason,
-                        "strategy_used": t.strategy_used,
-                        "time_of_day": t.time_of_day
-                    }
-                    for t in self.trade_outcomes[-100:]  # Keep last 100 trades
-                ]
             }
             
+            # The store keeps the trades themselves
+            if self.store is not None:
+                self.store.save_state(self.STATE_NAME, data)
+                return
+            
+            data["trade_outcomes"] = [
+                {
+                    "timestamp": t.timestamp.isoformat(),
+                    "asset": t.asset,
+                    "side": t.side,
+                    "entry_price": str(t.entry_price),
+                    "exit_price": str(t.exit_price),
+                    "profit_pct": str(t.profit_pct),
+                    "hold_time_minutes": t.hold_time_minutes,
+                    "exit_reason": t.exit_reason,
+                    "strategy_used": t.strategy_used,
+                    "time_of_day": t.time_of_day
+                }
+                for t in self.trade_outcomes[-100:]  # Keep last 100 trades
+            ]
+            
+            # Ensure data directory exists
+            self.data_file.parent.mkdir(parents=True, exist_ok=True)
             with open(self.data_file, 'w') as f:
                 json.dump(data, f, indent=2)
             
@@ -608,15 +638,23 @@ class AdaptiveLearningEngine:
             logger.error(f"Failed to save learning data: {e}")
     
     def _load_data(self) -> None:
-        """Load learning data from disk."""
+        """Load learning data from the store, or from disk without one."""
         try:
-            if not self.data_file.exists():
+            if self.store is not None:
+                data = self.store.load_state(self.STATE_NAME)
+                self.trade_outcomes = [
+                    self._to_trade_outcome(outcome) for outcome in self.store.get_outcomes(limit=100)
+                ]
+            elif self.data_file.exists():
+                with open(self.data_file, 'r') as f:
+                    data = json.load(f)
+            else:
+                data = None
+            
+            if data is None:
                 logger.info("No existing learning data found, starting fresh")
                 return
             
-            with open(self.data_file, 'r') as f:
-                data = json.load(f)
-            
             self.total_trades = data.get("total_trades", 0)
             self.winning_trades = data.get("winning_trades", 0)
             self.losing_trades = data.get("losing_trades", 0)
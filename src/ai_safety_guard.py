@@ -2,7 +2,8 @@
 AI Safety Guard for validating market conditions before trade execution.
 
 Implements Requirements 7.1-7.6:
-- NVIDIA API integration with 2-second timeout
+- LLM safety check (NVIDIA API or configured backends) with 2-second timeout
+- Schema-validated JSON verdicts (SAFETY_VERDICT_SCHEMA)
 - Multilingual YES/NO parsing (English, Russian, French, Spanish)
 - Fallback heuristics when AI unavailable
 - Volatility monitoring (5% threshold)
@@ -10,22 +11,38 @@ Implements Requirements 7.1-7.6:
 """
 
 import asyncio
+import json
 import logging
 from datetime import datetime, timedelta
 from decimal import Decimal
-from typing import Dict, Optional, List
+from typing import Callable, Dict, Optional, List
 import aiohttp
 
+from src.llm_backends import LLMBackendChain, NVIDIA_API_URL, NVIDIA_SAFETY_MODEL, nvidia_backend_chain
 from src.models import Market, Opportunity, SafetyDecision
+from src.structured_output import RejectedResponseLog, request_structured
 
 logger = logging.getLogger(__name__)
 
 
+# JSON schema of the safety verdict the LLM must answer with (docs/STRUCTURED_OUTPUT.md)
+SAFETY_VERDICT_SCHEMA = {
+    "title": "SafetyVerdict",
+    "type": "object",
+    "properties": {
+        "safe": {"type": "boolean"},
+        "reason": {"type": "string", "minLength": 1, "maxLength": 500},
+    },
+    "required": ["safe", "reason"],
+    "additionalProperties": False,
+}
+
+
 class AISafetyGuard:
     """
     AI-powered safety guard that validates market conditions before trade execution.
     
-    Uses NVIDIA AI API for intelligent safety checks with fallback heuristics.
+    Uses an LLM (NVIDIA API by default) for intelligent safety checks with fallback heuristics.
     Monitors volatility and filters ambiguous markets.
     """
     
@@ -60,25 +77,33 @@ class AISafetyGuard:
     
     def __init__(
         self,
-        nvidia_api_key: str,
-        nvidia_api_url: str = "https://integrate.api.nvidia.com/v1",
+        nvidia_api_key: Optional[str] = None,
+        nvidia_api_url: str = NVIDIA_API_URL,
         min_balance: Decimal = Decimal('0.10'),  # Dynamic: $0.10 minimum for micro trading
         max_gas_price_gwei: int = 800,
         max_pending_tx: int = 5,
         volatility_threshold: Decimal = Decimal('0.05'),  # 5%
-        volatility_halt_duration: int = 300  # 5 minutes in seconds
+        volatility_halt_duration: int = 300,  # 5 minutes in seconds
+        llm_backends: Optional[LLMBackendChain] = None,
+        ai_timeout_seconds: float = 2.0,
+        max_repair_attempts: int = 1,
+        rejected_responses_path: Optional[str] = "data/llm_rejected_responses.jsonl"
     ):
         """
         Initialize AI Safety Guard.
         
         Args:
-            nvidia_api_key: API key for NVIDIA AI service
+            nvidia_api_key: API key for NVIDIA AI service (used when no backends are given)
             nvidia_api_url: NVIDIA API endpoint URL
             min_balance: Minimum balance required for trading (default $0.10, dynamic)
             max_gas_price_gwei: Maximum gas price in gwei (default 800)
             max_pending_tx: Maximum pending transactions (default 5)
             volatility_threshold: Volatility threshold for halting (default 5%)
             volatility_halt_duration: Duration to halt after high volatility (default 5 minutes)
+            llm_backends: LLM backends in failover order (default: NVIDIA DeepSeek)
+            ai_timeout_seconds: Time budget of the AI check across all backends (default 2s)
+            max_repair_attempts: Re-asks after an answer that fails schema validation (within the budget)
+            rejected_responses_path: JSONL file for invalid answers (None: not persisted)
         """
         self.nvidia_api_key = nvidia_api_key
         self.nvidia_api_url = nvidia_api_url
@@ -87,12 +112,21 @@ class AISafetyGuard:
         self.max_pending_tx = max_pending_tx
         self.volatility_threshold = volatility_threshold
         self.volatility_halt_duration = volatility_halt_duration
+        self.ai_timeout_seconds = ai_timeout_seconds
+        self.llm_backends = llm_backends if llm_backends is not None else nvidia_backend_chain(
+            nvidia_api_key, nvidia_api_url, models=(NVIDIA_SAFETY_MODEL,),
+            timeout_seconds=ai_timeout_seconds, max_tokens=8192, temperature=1.0,
+            extra_body={"chat_template_kwargs": {"thinking": True}}
+        )
+        self.max_repair_attempts = max_repair_attempts
+        self.rejected_responses = RejectedResponseLog(rejected_responses_path)
         
         # Track price history for volatility monitoring
         self._price_history: Dict[str, List[tuple[datetime, Decimal]]] = {}
         
         # Track volatility halt status
         self._volatility_halt_until: Optional[datetime] = None
+        self.on_volatility_halt: Optional[Callable[[datetime], None]] = None  # Called with the halt end time
         
         logger.info(
             f"AI Safety Guard initialized: min_balance=${min_balance}, "
@@ -111,7 +145,7 @@ class AISafetyGuard:
         Validate if a trade opportunity is safe to execute.
         
         Implements Requirements 7.1-7.6:
-        - Queries NVIDIA AI API with market context
+        - Queries the LLM backends with market context
         - Parses multilingual YES/NO responses
         - Uses fallback heuristics if AI unavailable
         - Checks volatility and halts if needed
@@ -171,13 +205,13 @@ class AISafetyGuard:
             )
         checks_performed["volatility_check"] = True
         
-        # Check 4: NVIDIA AI API (Requirement 7.1, 7.2, 7.3)
-        ai_approved = await self._check_nvidia_api(market, opportunity)
-        checks_performed["nvidia_api"] = ai_approved is not None
+        # Check 4: LLM safety check (Requirement 7.1, 7.2, 7.3)
+        ai_approved = await self._check_llm(market, opportunity)
+        checks_performed["ai_check"] = ai_approved is not None
         
         # Check 5: Fallback heuristics if AI unavailable (Requirement 7.4)
         if ai_approved is None:
-            logger.info("NVIDIA API unavailable, using fallback heuristics")
+            logger.info("AI safety check unavailable, using fallback heuristics")
             ai_approved = self._fallback_heuristics(
                 current_balance,
                 current_gas_price_gwei,
@@ -207,20 +241,20 @@ class AISafetyGuard:
             fallback_used=fallback_used
         )
     
-    async def _check_nvidia_api(
+    async def _check_llm(
         self,
         market: Market,
         opportunity: Opportunity
     ) -> Optional[bool]:
         """
-        Query NVIDIA AI API for safety validation with 2-second timeout.
+        Query the LLM backends for safety validation with 2-second timeout.
         
-        Uses DeepSeek v3.2 model via NVIDIA API for intelligent trade validation.
+        Uses DeepSeek v3.2 via NVIDIA API unless llm_backends are configured.
         
         Implements Requirements 7.1, 7.2, 7.3:
-        - Queries NVIDIA API with market context
-        - Parses multilingual YES/NO responses
-        - Returns None if timeout or error (triggers fallback)
+        - Queries the LLM backends (in failover order) with market context
+        - Requires a SAFETY_VERDICT_SCHEMA answer; invalid answers are re-asked, then persisted
+        - Returns None if timeout, error or invalid answer (triggers fallback)
         
         Args:
             market: The market to validate
@@ -229,55 +263,48 @@ class AISafetyGuard:
         Returns:
             Optional[bool]: True if approved, False if rejected, None if unavailable
         """
+        if not self.llm_backends:
+            return None
+        
         # Build context for AI
         context = self._build_market_context(market, opportunity)
         
-        try:
-            # Query NVIDIA API with 2-second timeout (Requirement 7.3)
-            # Using OpenAI-compatible client as specified by user
-            from openai import OpenAI
-            
-            client = OpenAI(
-                base_url="https://integrate.api.nvidia.com/v1",
-                api_key=self.nvidia_api_key
+        system_prompt = (
+            "You are a safety validator for cryptocurrency arbitrage trading. "
+            "Analyze the market and decide if the trade is safe. Consider: market clarity, "
+            "price reasonableness, volatility, and potential manipulation. Respond with one JSON "
+            f"object matching this schema and nothing else:\n{json.dumps(SAFETY_VERDICT_SCHEMA)}"
+        )
+        
+        async def complete(prompt: str) -> str:
+            response = await self.llm_backends.complete(
+                system_prompt, prompt, top_p=0.95, response_schema=SAFETY_VERDICT_SCHEMA
             )
-            
-            # Create completion with DeepSeek v3.2 model
-            completion = client.chat.completions.create(
-                model="deepseek-ai/deepseek-v3.2",
-                messages=[
-                    {
-                        "role": "system",
-                        "content": (
-                            "You are a safety validator for cryptocurrency arbitrage trading. "
-                            "Analyze the market and respond with YES if the trade is safe, "
-                            "or NO if there are concerns. Consider: market clarity, price reasonableness, "
-                            "volatility, and potential manipulation. Respond with only YES or NO."
-                        )
-                    },
-                    {
-                        "role": "user",
-                        "content": context
-                    }
-                ],
-                temperature=1,
-                top_p=0.95,
-                max_tokens=8192,
-                extra_body={"chat_template_kwargs": {"thinking": True}},
-                timeout=2.0  # 2-second timeout
+            return response.text
+        
+        try:
+            # Query the LLM backends with a 2-second budget (Requirement 7.3)
+            verdict = await asyncio.wait_for(
+                request_structured(
+                    complete,
+                    context,
+                    SAFETY_VERDICT_SCHEMA,
+                    max_repair_attempts=self.max_repair_attempts,
+                    on_invalid=lambda response, errors, attempt: self.rejected_responses.record(
+                        "safety_guard", response, errors, market_id=market.market_id, attempt=attempt
+                    )
+                ),
+                timeout=self.ai_timeout_seconds
             )
             
-            # Extract response
-            ai_response = completion.choices[0].message.content if completion.choices else ""
-            
-            # Parse multilingual YES/NO response (Requirement 7.2)
-            result = self.parse_yes_no_response(ai_response)
-            
-            logger.debug(f"NVIDIA API response: '{ai_response}' -> {result}")
-            return result
+            logger.debug(f"AI safety verdict: safe={verdict['safe']} ({verdict['reason']})")
+            return verdict["safe"]
         
+        except asyncio.TimeoutError:
+            logger.warning(f"AI safety check timed out after {self.ai_timeout_seconds}s")
+            return None
         except Exception as e:
-            logger.warning(f"NVIDIA API error: {e}")
+            logger.warning(f"AI safety check error: {e}")
             return None
     
     def parse_yes_no_response(self, response: str) -> Optional[bool]:
@@ -455,6 +482,11 @@ class AISafetyGuard:
             f"Volatility halt triggered until {self._volatility_halt_until} "
             f"({self.volatility_halt_duration} seconds)"
         )
+        if self.on_volatility_halt is not None:
+            try:
+                self.on_volatility_halt(self._volatility_halt_until)
+            except Exception as e:
+                logger.error(f"Volatility halt listener failed: {e}")
     
     def _build_market_context(self, market: Market, opportunity: Opportunity) -> str:
         """
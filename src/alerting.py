@@ -0,0 +1,360 @@
+"""
+Alert routing for Polymarket Arbitrage Bot.
+
+Alerts carry a severity and an event type. The AlertRouter drops repeats of
+the same alert within a dedup window, sends each alert to the channels
+routed for its event, skips channels whose minimum severity is higher, and
+rate limits every channel. Sinks deliver to a generic JSON webhook, a
+Telegram bot, a Discord webhook, SMTP or AWS SNS. The HTTP sinks take their
+URL from config (Telegram: api_url), so tests point them at a local server.
+
+Validates Requirements:
+- Severity levels, deduplication and per-channel rate limits
+- Pluggable sinks: webhook, Telegram, Discord, SMTP (and SNS)
+- Routing of trade fills, circuit breaker trips, volatility halts and low balances
+"""
+
+import asyncio
+import logging
+import os
+import smtplib
+import time
+from abc import ABC, abstractmethod
+from collections import deque
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from email.message import EmailMessage
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+import aiohttp
+
+from config.config import ALERT_SEVERITIES
+
+logger = logging.getLogger(__name__)
+
+# Events (config.ALERT_EVENTS)
+TRADE_FILL = "trade_fill"
+CIRCUIT_BREAKER = "circuit_breaker"
+VOLATILITY_HALT = "volatility_halt"
+LOW_BALANCE = "low_balance"
+GAS_PRICE = "gas_price"
+LEDGER_DRIFT = "ledger_drift"
+REDEMPTION = "redemption"
+SYSTEM = "system"
+
+DEFAULT_ROUTE = "default"
+
+
+def severity_rank(severity: str) -> int:
+    """Position of a severity in ALERT_SEVERITIES (unknown severities rank as info)."""
+    severity = severity.lower()
+    return ALERT_SEVERITIES.index(severity) if severity in ALERT_SEVERITIES else 0
+
+
+@dataclass
+class Alert:
+    """One alert as handed to the sinks."""
+    severity: str
+    message: str
+    event: str = SYSTEM
+    context: Dict[str, Any] = field(default_factory=dict)
+    timestamp: datetime = field(default_factory=lambda: datetime.now(timezone.utc))
+
+    def __post_init__(self):
+        self.severity = self.severity.lower()
+
+    @property
+    def subject(self) -> str:
+        return f"[{self.severity.upper()}] Polymarket Arbitrage Bot: {self.event}"
+
+    def to_text(self) -> str:
+        """Plain-text body: message, context lines and timestamp."""
+        lines = [self.subject, self.message]
+        if self.context:
+            lines.append("")
+            lines.extend(f"{key}: {value}" for key, value in self.context.items())
+        lines.append(f"\nTimestamp: {self.timestamp.isoformat()}")
+        return "\n".join(lines)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "severity": self.severity,
+            "event": self.event,
+            "message": self.message,
+            "context": {key: value if isinstance(value, (int, float, bool, type(None))) else str(value)
+                        for key, value in self.context.items()},
+            "timestamp": self.timestamp.isoformat(),
+        }
+
+
+# ============================================================
+# SINKS
+# ============================================================
+
+class AlertSink(ABC):
+    """Delivers alerts to one channel."""
+
+    def __init__(self, name: str, min_severity: str = "info", timeout_seconds: float = 10.0):
+        self.name = name
+        self.min_severity = min_severity
+        self.timeout_seconds = timeout_seconds
+
+    def accepts(self, alert: Alert) -> bool:
+        return severity_rank(alert.severity) >= severity_rank(self.min_severity)
+
+    @abstractmethod
+    async def send(self, alert: Alert) -> bool:
+        """Deliver the alert; returns True if the channel accepted it."""
+
+    async def _post_json(self, url: str, payload: Dict[str, Any], headers: Optional[Dict[str, str]] = None) -> bool:
+        async with aiohttp.ClientSession() as session:
+            async with session.post(url, json=payload, headers=headers or {},
+                                    timeout=aiohttp.ClientTimeout(total=self.timeout_seconds)) as resp:
+                if resp.status >= 300:
+                    body = await resp.text()
+                    logger.error(f"Alert channel {self.name} returned HTTP {resp.status}: {body[:200]}")
+                    return False
+                return True
+
+
+class WebhookSink(AlertSink):
+    """POSTs the alert as JSON (Alert.to_dict) to any URL."""
+
+    def __init__(self, name: str, url: str, headers: Optional[Dict[str, str]] = None, **kwargs):
+        super().__init__(name, **kwargs)
+        self.url = url
+        self.headers = dict(headers or {})
+
+    async def send(self, alert: Alert) -> bool:
+        return await self._post_json(self.url, alert.to_dict(), self.headers)
+
+
+class DiscordSink(AlertSink):
+    """Posts the alert text to a Discord channel webhook."""
+
+    MAX_LENGTH = 2000  # Discord message limit
+
+    def __init__(self, name: str, url: str, **kwargs):
+        super().__init__(name, **kwargs)
+        self.url = url
+
+    async def send(self, alert: Alert) -> bool:
+        return await self._post_json(self.url, {"content": alert.to_text()[:self.MAX_LENGTH]})
+
+
+class TelegramSink(AlertSink):
+    """Sends the alert text through a Telegram bot (sendMessage)."""
+
+    MAX_LENGTH = 4096  # Telegram message limit
+
+    def __init__(self, name: str, bot_token: str, chat_id: str,
+                 api_url: str = "https://api.telegram.org", **kwargs):
+        super().__init__(name, **kwargs)
+        self.bot_token = bot_token
+        self.chat_id = str(chat_id)
+        self.api_url = api_url.rstrip("/")
+
+    async def send(self, alert: Alert) -> bool:
+        return await self._post_json(
+            f"{self.api_url}/bot{self.bot_token}/sendMessage",
+            {"chat_id": self.chat_id, "text": alert.to_text()[:self.MAX_LENGTH], "disable_web_page_preview": True}
+        )
+
+
+class SmtpSink(AlertSink):
+    """Emails the alert (smtplib in a worker thread)."""
+
+    def __init__(self, name: str, host: str, sender: str, recipients: List[str], port: int = 587,
+                 username: Optional[str] = None, password: Optional[str] = None, use_tls: bool = True, **kwargs):
+        super().__init__(name, **kwargs)
+        self.host = host
+        self.port = port
+        self.sender = sender
+        self.recipients = [recipients] if isinstance(recipients, str) else list(recipients)
+        self.username = username
+        self.password = password
+        self.use_tls = use_tls
+
+    def _deliver(self, message: EmailMessage) -> None:
+        with smtplib.SMTP(self.host, self.port, timeout=self.timeout_seconds) as smtp:
+            if self.use_tls:
+                smtp.starttls()
+            if self.username:
+                smtp.login(self.username, self.password or "")
+            smtp.send_message(message)
+
+    async def send(self, alert: Alert) -> bool:
+        message = EmailMessage()
+        message["Subject"] = alert.subject
+        message["From"] = self.sender
+        message["To"] = ", ".join(self.recipients)
+        message.set_content(alert.to_text())
+        await asyncio.to_thread(self._deliver, message)
+        return True
+
+
+class SnsSink(AlertSink):
+    """Publishes the alert to an AWS SNS topic (needs boto3)."""
+
+    def __init__(self, name: str, topic_arn: str, **kwargs):
+        super().__init__(name, **kwargs)
+        import boto3
+        self.topic_arn = topic_arn
+        self.client = boto3.client("sns")
+
+    async def send(self, alert: Alert) -> bool:
+        response = await asyncio.to_thread(
+            self.client.publish, TopicArn=self.topic_arn, Subject=alert.subject[:100], Message=alert.to_text()
+        )
+        logger.debug(f"SNS alert published (MessageId: {response['MessageId']})")
+        return True
+
+
+# ============================================================
+# ROUTER
+# ============================================================
+
+class AlertRouter:
+    """
+    Routes alerts to sinks by event and severity.
+
+    Features:
+    - Routes: event -> channel names; other events use the "default" route, or every
+      channel if there is none
+    - Deduplication: an identical alert (event, severity, message) within dedup_seconds is
+      counted, not sent; the next one that goes out reports how many were suppressed
+    - Rate limit per channel over a sliding minute; critical alerts bypass it
+    - A failing channel never blocks the others
+    """
+
+    def __init__(self, sinks: Optional[List[AlertSink]] = None, routes: Optional[Dict[str, List[str]]] = None,
+                 dedup_seconds: float = 300, rate_limit_per_minute: int = 20, clock=time.monotonic):
+        self.sinks: Dict[str, AlertSink] = {sink.name: sink for sink in sinks or []}
+        self.routes = {event: list(names) for event, names in (routes or {}).items()}
+        self.dedup_seconds = dedup_seconds
+        self.rate_limit_per_minute = rate_limit_per_minute
+        self.clock = clock
+        self._last_sent: Dict[Tuple[str, str, str], float] = {}
+        self._suppressed: Dict[Tuple[str, str, str], int] = {}
+        self._sent_times: Dict[str, Deque[float]] = {name: deque() for name in self.sinks}
+
+    def channels_for(self, alert: Alert) -> List[AlertSink]:
+        """Channels routed for the alert's event that accept its severity."""
+        names = self.routes.get(alert.event, self.routes.get(DEFAULT_ROUTE, list(self.sinks)))
+        return [self.sinks[name] for name in names if name in self.sinks and self.sinks[name].accepts(alert)]
+
+    def _is_duplicate(self, alert: Alert, now: float) -> bool:
+        key = (alert.event, alert.severity, alert.message)
+        last = self._last_sent.get(key)
+        if last is not None and now - last < self.dedup_seconds:
+            self._suppressed[key] = self._suppressed.get(key, 0) + 1
+            return True
+        self._last_sent[key] = now
+        suppressed = self._suppressed.pop(key, 0)
+        if suppressed:
+            alert.context["suppressed_duplicates"] = suppressed
+        return False
+
+    def _within_rate_limit(self, sink: AlertSink, alert: Alert, now: float) -> bool:
+        sent = self._sent_times.setdefault(sink.name, deque())
+        while sent and now - sent[0] >= 60:
+            sent.popleft()
+        if alert.severity != "critical" and len(sent) >= self.rate_limit_per_minute:
+            logger.warning(f"Alert channel {sink.name} rate limited, dropped: {alert.message}")
+            return False
+        sent.append(now)
+        return True
+
+    async def _deliver(self, sink: AlertSink, alert: Alert) -> bool:
+        try:
+            return await sink.send(alert)
+        except Exception as e:
+            logger.error(f"Alert channel {sink.name} failed: {e}")
+            return False
+
+    async def send(self, alert: Alert) -> Dict[str, bool]:
+        """
+        Route one alert.
+
+        Returns:
+            Channel name -> delivered, for the channels it was sent to (empty if deduplicated)
+        """
+        now = self.clock()
+        channels = self.channels_for(alert)
+        if not channels or self._is_duplicate(alert, now):
+            return {}
+
+        channels = [sink for sink in channels if self._within_rate_limit(sink, alert, now)]
+        results = await asyncio.gather(*(self._deliver(sink, alert) for sink in channels))
+        return {sink.name: delivered for sink, delivered in zip(channels, results)}
+
+
+def create_alert_sink(name: str, options: Dict[str, Any], sns_topic: str = "") -> AlertSink:
+    """
+    Build a sink from an alert_channels entry; secrets come from environment variables.
+
+    Raises:
+        ValueError: If a secret's environment variable is not set
+    """
+    def secret(env_key: str, default_env: str) -> str:
+        env_name = options.get(env_key) or default_env
+        value = os.getenv(env_name)
+        if not value:
+            raise ValueError(f"alert channel {name}: environment variable {env_name} is not set")
+        return value
+
+    common = {
+        "min_severity": options.get("min_severity", "info"),
+        "timeout_seconds": float(options.get("timeout_seconds", 10)),
+    }
+    kind = options["type"]
+    if kind == "webhook":
+        url = options.get("url") or secret("url_env", "ALERT_WEBHOOK_URL")
+        return WebhookSink(name, url, headers=options.get("headers"), **common)
+    if kind == "discord":
+        url = options.get("url") or secret("url_env", "DISCORD_WEBHOOK_URL")
+        return DiscordSink(name, url, **common)
+    if kind == "telegram":
+        return TelegramSink(
+            name, secret("bot_token_env", "TELEGRAM_BOT_TOKEN"), options["chat_id"],
+            api_url=options.get("api_url", "https://api.telegram.org"), **common
+        )
+    if kind == "smtp":
+        username = options.get("username")
+        return SmtpSink(
+            name, options["host"], options["sender"], options["recipients"], port=int(options.get("port", 587)),
+            username=username, password=secret("password_env", "SMTP_PASSWORD") if username else None,
+            use_tls=options.get("use_tls", True), **common
+        )
+    if kind == "sns":
+        return SnsSink(name, options.get("topic_arn") or sns_topic, **common)
+    raise ValueError(f"alert channel {name}: unknown type {kind}")
+
+
+def create_alert_router(config) -> AlertRouter:
+    """
+    Build the router from Config.alert_channels/alert_routes.
+
+    sns_alert_topic adds an "sns" channel if none is configured. A channel that
+    cannot be built (missing secret, boto3 not installed) is skipped with an error,
+    so alerting problems never stop the bot.
+    """
+    channels = dict(config.alert_channels)
+    if config.sns_alert_topic and not any(c.get("type") == "sns" for c in channels.values()):
+        channels.setdefault("sns", {"type": "sns", "topic_arn": config.sns_alert_topic})
+
+    sinks = []
+    for name, options in channels.items():
+        try:
+            sinks.append(create_alert_sink(name, options, config.sns_alert_topic))
+        except Exception as e:
+            logger.error(f"❌ Alert channel {name} disabled: {e}")
+    if sinks:
+        logger.info(f"✅ Alert channels: {', '.join(sink.name for sink in sinks)}")
+
+    return AlertRouter(
+        sinks,
+        routes=config.alert_routes,
+        dedup_seconds=config.alert_dedup_seconds,
+        rate_limit_per_minute=config.alert_rate_limit_per_minute
+    )
@@ -0,0 +1,928 @@
+"""
+Event-driven replay backtester for the 15-minute crypto strategy.
+
+Replays timestamped Binance ticks and Polymarket order book updates through a
+simulated clock into the unmodified FifteenMinuteCryptoStrategy. The strategy
+talks to a ReplayExchange that stands in for ClobClient, so entries and exits
+(trailing stop, take profit, stop loss, time exit) run through the same code
+path as live trading and fill against the recorded books.
+
+Events are the JSON-lines records written by MarketDataRecorder (market,
+binance_tick, book, book_delta, trade, resolution); the schema is documented
+in docs/MARKET_DATA_RECORDING.md.
+
+Validates Requirements:
+- Replay Binance ticks and order books in timestamp order
+- Drive unmodified strategy classes through a simulated clock
+- Fill orders against recorded depth with balance checks
+- Report closed trades broken down by exit reason
+"""
+
+import argparse
+import asyncio
+import gzip
+import json
+import logging
+import sys
+import tempfile
+import uuid
+from contextlib import contextmanager
+from dataclasses import dataclass, field, replace
+from datetime import datetime, timezone
+from decimal import Decimal
+from pathlib import Path
+from types import SimpleNamespace
+from typing import (
+    Any, Callable, Dict, Iterable, Iterator, List, Optional, Tuple, Union, get_args, get_origin, get_type_hints
+)
+from unittest.mock import patch
+
+from src.continuous_rl_engine import ContinuousRLEngine
+from src.fifteen_min_crypto_strategy import CryptoMarket, FifteenMinuteCryptoStrategy
+
+logger = logging.getLogger(__name__)
+
+
+EVENT_MARKET = "market"
+EVENT_BOOK = "book"
+EVENT_BOOK_DELTA = "book_delta"
+EVENT_TRADE = "trade"
+EVENT_BINANCE_TICK = "binance_tick"
+EVENT_RESOLUTION = "resolution"
+
+EVENT_TYPES = (EVENT_MARKET, EVENT_BOOK, EVENT_BOOK_DELTA, EVENT_TRADE, EVENT_BINANCE_TICK, EVENT_RESOLUTION)
+
+# Newest recording schema version this loader understands
+SUPPORTED_SCHEMA_VERSION = 1
+
+USDC_DECIMALS = Decimal("1000000")
+
+
+# ============================================================================
+# Events
+# ============================================================================
+
+def _parse_timestamp(value: Any) -> datetime:
+    """Parse an ISO-8601 string or epoch seconds into an aware UTC datetime."""
+    if isinstance(value, datetime):
+        ts = value
+    elif isinstance(value, (int, float)):
+        ts = datetime.fromtimestamp(value, tz=timezone.utc)
+    else:
+        ts = datetime.fromisoformat(str(value).replace("Z", "+00:00"))
+    if ts.tzinfo is None:
+        ts = ts.replace(tzinfo=timezone.utc)
+    return ts.astimezone(timezone.utc)
+
+
+@dataclass
+class ReplayEvent:
+    """A single timestamped event in a replay stream."""
+    timestamp: datetime
+    event_type: str
+    data: Dict[str, Any] = field(default_factory=dict)
+
+    @classmethod
+    def from_dict(cls, raw: Dict[str, Any]) -> "ReplayEvent":
+        """Build an event from its JSON representation."""
+        payload = dict(raw)
+        ts = payload.pop("ts", None)
+        event_type = payload.pop("type", None)
+        version = payload.pop("v", 1)
+        if version > SUPPORTED_SCHEMA_VERSION:
+            raise ValueError(f"Unsupported recording schema version {version}")
+        if ts is None or event_type is None:
+            raise ValueError(f"Replay event missing 'ts' or 'type': {raw}")
+        if event_type not in EVENT_TYPES:
+            raise ValueError(f"Unknown replay event type: {event_type}")
+        return cls(timestamp=_parse_timestamp(ts), event_type=event_type, data=payload)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Serialize the event to its JSON representation."""
+        return {"ts": self.timestamp.isoformat(), "type": self.event_type, **self.data}
+
+
+def load_replay_events(path: Path) -> Iterator[ReplayEvent]:
+    """
+    Stream replay events from a JSON-lines file or a recording directory.
+
+    Files ending in .gz are decompressed on the fly. A directory is read file by
+    file in name order, which is chronological for MarketDataRecorder output.
+    Events are yielded in file order; the backtester rejects streams that go
+    back in time.
+
+    Args:
+        path: Path to a .jsonl/.jsonl.gz file or a directory of them
+
+    Yields:
+        ReplayEvent objects
+    """
+    path = Path(path)
+    if path.is_dir():
+        for file_path in sorted(path.glob("*.jsonl*")):
+            yield from load_replay_events(file_path)
+        return
+
+    opener = gzip.open if path.suffix == ".gz" else open
+    with opener(path, "rt", encoding="utf-8") as f:
+        for line_number, line in enumerate(f, start=1):
+            line = line.strip()
+            if not line:
+                continue
+            try:
+                yield ReplayEvent.from_dict(json.loads(line))
+            except (ValueError, json.JSONDecodeError) as e:
+                raise ValueError(f"{path}:{line_number}: {e}") from e
+
+
+# ============================================================================
+# Simulated clock
+# ============================================================================
+
+class SimulatedClock:
+    """
+    Clock that only moves when the replay advances it.
+
+    While patched in, datetime.now()/utcnow() inside src.* modules and
+    time.time() return simulated time, so cooldowns, position ages, cache
+    TTLs and price-change windows behave as they did when recorded.
+    """
+
+    def __init__(self, start: datetime):
+        self._now = _parse_timestamp(start)
+
+    def now(self) -> datetime:
+        """Current simulated time (aware UTC)."""
+        return self._now
+
+    def timestamp(self) -> float:
+        """Current simulated time as epoch seconds."""
+        return self._now.timestamp()
+
+    def advance_to(self, ts: datetime) -> None:
+        """Move the clock forward; moving backwards is an error."""
+        if ts < self._now:
+            raise ValueError(f"Replay went back in time: {ts.isoformat()} < {self._now.isoformat()}")
+        self._now = ts
+
+    def _datetime_class(self) -> type:
+        clock = self
+
+        class SimulatedDatetime(datetime):
+            @classmethod
+            def now(cls, tz=None):
+                if tz is None:
+                    return clock._now.astimezone().replace(tzinfo=None)
+                return clock._now.astimezone(tz)
+
+            @classmethod
+            def utcnow(cls):
+                return clock._now.replace(tzinfo=None)
+
+        return SimulatedDatetime
+
+    @contextmanager
+    def patched(self):
+        """Route time.time() and datetime.now() in src.* modules to this clock."""
+        sim_datetime = self._datetime_class()
+        patched_modules = [
+            module for name, module in list(sys.modules.items())
+            if name.startswith("src.") and name != __name__
+            and getattr(module, "datetime", None) is datetime
+        ]
+        for module in patched_modules:
+            module.datetime = sim_datetime
+        try:
+            with patch("time.time", self.timestamp):
+                yield self
+        finally:
+            for module in patched_modules:
+                module.datetime = datetime
+
+
+# ============================================================================
+# Replay exchange
+# ============================================================================
+
+@dataclass
+class Fill:
+    """An order matched by the replay exchange."""
+    timestamp: datetime
+    order_id: str
+    token_id: str
+    side: str  # "BUY" or "SELL"
+    price: Decimal  # Volume-weighted average fill price
+    size: Decimal
+    fee: Decimal
+    realized_pnl: Decimal = Decimal("0")
+
+
+class ReplayExchange:
+    """
+    In-memory stand-in for ClobClient backed by recorded order books.
+
+    Orders are matched fill-or-kill against the visible depth at or better
+    than the limit price; matched depth is removed until the next book update.
+    Collateral and token balances are checked like the live exchange, so the
+    strategy's balance-retry logic is exercised too.
+    """
+
+    def __init__(self, clock: SimulatedClock, initial_balance: Decimal, fee_rate: Decimal = Decimal("0")):
+        self.clock = clock
+        self.cash = Decimal(str(initial_balance))
+        self.fee_rate = Decimal(str(fee_rate))
+        self.address = "0x" + "0" * 39 + "1"
+
+        self.books: Dict[str, Tuple[List[List[Decimal]], List[List[Decimal]]]] = {}
+        self.token_balances: Dict[str, Decimal] = {}
+        self._cost_basis: Dict[str, Decimal] = {}
+        self.fills: List[Fill] = []
+
+    # ------------------------------------------------------------------
+    # Book state
+    # ------------------------------------------------------------------
+
+    def apply_book(self, token_id: str, bids: Iterable, asks: Iterable) -> None:
+        """Replace the book for a token with a recorded snapshot."""
+        parsed_bids = sorted(
+            ([Decimal(str(p)), Decimal(str(s))] for p, s in bids), key=lambda lvl: lvl[0], reverse=True
+        )
+        parsed_asks = sorted(
+            ([Decimal(str(p)), Decimal(str(s))] for p, s in asks), key=lambda lvl: lvl[0]
+        )
+        self.books[token_id] = (
+            [lvl for lvl in parsed_bids if lvl[1] > 0],
+            [lvl for lvl in parsed_asks if lvl[1] > 0],
+        )
+
+    def apply_book_delta(self, token_id: str, changes: Iterable) -> None:
+        """Apply [side, price, size] level changes; size 0 removes the level."""
+        bids, asks = self.books.setdefault(token_id, ([], []))
+        for side, price, size in changes:
+            levels = bids if side == "bid" else asks
+            price, size = Decimal(str(price)), Decimal(str(size))
+            levels[:] = [lvl for lvl in levels if lvl[0] != price]
+            if size > 0:
+                levels.append([price, size])
+        bids.sort(key=lambda lvl: lvl[0], reverse=True)
+        asks.sort(key=lambda lvl: lvl[0])
+
+    def best_bid(self, token_id: str) -> Optional[Decimal]:
+        bids = self.books.get(token_id, ([], []))[0]
+        return bids[0][0] if bids else None
+
+    def best_ask(self, token_id: str) -> Optional[Decimal]:
+        asks = self.books.get(token_id, ([], []))[1]
+        return asks[0][0] if asks else None
+
+    def mid_price(self, token_id: str) -> Optional[Decimal]:
+        bid, ask = self.best_bid(token_id), self.best_ask(token_id)
+        if bid is not None and ask is not None:
+            return (bid + ask) / 2
+        return bid if bid is not None else ask
+
+    # ------------------------------------------------------------------
+    # ClobClient surface used by the strategy
+    # ------------------------------------------------------------------
+
+    def get_address(self) -> str:
+        return self.address
+
+    def get_order_book(self, token_id: str):
+        bids, asks = self.books.get(token_id, ([], []))
+        return SimpleNamespace(
+            asset_id=token_id,
+            bids=[SimpleNamespace(price=str(p), size=str(s)) for p, s in bids],
+            asks=[SimpleNamespace(price=str(p), size=str(s)) for p, s in asks],
+        )
+
+    def get_balance_allowance(self, params=None) -> Dict[str, str]:
+        raw = str(int(self.cash * USDC_DECIMALS))
+        return {"balance": raw, "allowance": raw}
+
+    def create_order(self, order_args, options=None):
+        return SimpleNamespace(
+            token_id=str(order_args.token_id),
+            price=Decimal(str(order_args.price)),
+            size=Decimal(str(order_args.size)),
+            side=str(order_args.side).upper(),
+            neg_risk=getattr(options, "neg_risk", None),
+        )
+
+    def post_order(self, signed_order, orderType=None) -> Dict[str, Any]:
+        order_id = uuid.uuid4().hex[:12]
+        if signed_order.size <= 0 or signed_order.price <= 0:
+            return {"success": False, "errorMsg": "invalid price or size", "orderID": order_id}
+
+        if signed_order.side == "BUY":
+            return self._match_buy(order_id, signed_order)
+        return self._match_sell(order_id, signed_order)
+
+    # ------------------------------------------------------------------
+    # Matching
+    # ------------------------------------------------------------------
+
+    def _walk(self, levels: List[List[Decimal]], size: Decimal, crosses: Callable[[Decimal], bool]):
+        """Return (filled, notional, plan) for taking size from levels that cross."""
+        remaining = size
+        notional = Decimal("0")
+        plan = []
+        for index, (price, available) in enumerate(levels):
+            if remaining <= 0 or not crosses(price):
+                break
+            take = min(available, remaining)
+            plan.append((index, take))
+            notional += take * price
+            remaining -= take
+        return size - remaining, notional, plan
+
+    @staticmethod
+    def _consume(levels: List[List[Decimal]], plan: List[Tuple[int, Decimal]]) -> None:
+        for index, take in plan:
+            levels[index][1] -= take
+        levels[:] = [lvl for lvl in levels if lvl[1] > 0]
+
+    def _match_buy(self, order_id: str, order) -> Dict[str, Any]:
+        asks = self.books.get(order.token_id, ([], []))[1]
+        filled, notional, plan = self._walk(asks, order.size, lambda p: p <= order.price)
+        if filled < order.size:
+            return {"success": False, "errorMsg": "order couldn't be fully filled (FOK)", "orderID": order_id}
+
+        fee = notional * self.fee_rate
+        if notional + fee > self.cash:
+            return {"success": False, "errorMsg": "not enough balance / allowance", "orderID": order_id}
+
+        self._consume(asks, plan)
+        self.cash -= notional + fee
+        self.token_balances[order.token_id] = self.token_balances.get(order.token_id, Decimal("0")) + filled
+        self._cost_basis[order.token_id] = self._cost_basis.get(order.token_id, Decimal("0")) + notional + fee
+        self._record_fill(order_id, order.token_id, "BUY", notional / filled, filled, fee)
+        return {"success": True, "orderID": order_id, "status": "matched"}
+
+    def _match_sell(self, order_id: str, order) -> Dict[str, Any]:
+        held = self.token_balances.get(order.token_id, Decimal("0"))
+        if order.size > held:
+            return {"success": False, "errorMsg": "not enough balance / allowance", "orderID": order_id}
+
+        bids = self.books.get(order.token_id, ([], []))[0]
+        filled, notional, plan = self._walk(bids, order.size, lambda p: p >= order.price)
+        if filled < order.size:
+            return {"success": False, "errorMsg": "order couldn't be fully filled (FOK)", "orderID": order_id}
+
+        self._consume(bids, plan)
+        fee = notional * self.fee_rate
+        cost = self._release_cost(order.token_id, filled)
+        self.cash += notional - fee
+        self._record_fill(order_id, order.token_id, "SELL", notional / filled, filled, fee, notional - fee - cost)
+        return {"success": True, "orderID": order_id, "status": "matched"}
+
+    def _release_cost(self, token_id: str, size: Decimal) -> Decimal:
+        """Remove size shares from a token balance and return their average cost."""
+        held = self.token_balances.get(token_id, Decimal("0"))
+        basis = self._cost_basis.get(token_id, Decimal("0"))
+        cost = basis * size / held if held > 0 else Decimal("0")
+        self.token_balances[token_id] = held - size
+        self._cost_basis[token_id] = basis - cost
+        if self.token_balances[token_id] <= 0:
+            self.token_balances.pop(token_id, None)
+            self._cost_basis.pop(token_id, None)
+        return cost
+
+    def _record_fill(self, order_id, token_id, side, price, size, fee, realized_pnl=Decimal("0")) -> None:
+        fill = Fill(
+            timestamp=self.clock.now(),
+            order_id=order_id,
+            token_id=token_id,
+            side=side,
+            price=price,
+            size=size,
+            fee=fee,
+            realized_pnl=realized_pnl,
+        )
+        self.fills.append(fill)
+        logger.debug(f"Replay fill: {side} {size} {token_id[:16]} @ {price:.4f}")
+
+    # ------------------------------------------------------------------
+    # Settlement and valuation
+    # ------------------------------------------------------------------
+
+    def settle(self, token_id: str, payout: Decimal) -> Optional[Fill]:
+        """Redeem a token balance at payout per share (1 for winner, 0 for loser)."""
+        held = self.token_balances.get(token_id, Decimal("0"))
+        if held <= 0:
+            return None
+        cost = self._release_cost(token_id, held)
+        proceeds = held * payout
+        self.cash += proceeds
+        fill = Fill(
+            timestamp=self.clock.now(),
+            order_id=f"settle_{uuid.uuid4().hex[:12]}",
+            token_id=token_id,
+            side="SETTLE",
+            price=payout,
+            size=held,
+            fee=Decimal("0"),
+            realized_pnl=proceeds - cost,
+        )
+        self.fills.append(fill)
+        return fill
+
+    def equity(self) -> Decimal:
+        """Cash plus token balances marked at best bid."""
+        value = self.cash
+        for token_id, held in self.token_balances.items():
+            value += held * (self.best_bid(token_id) or Decimal("0"))
+        return value
+
+
+# ============================================================================
+# Results
+# ============================================================================
+
+@dataclass
+class ClosedTrade:
+    """A position opened and closed during the replay."""
+    token_id: str
+    market_id: str
+    asset: str
+    side: str
+    strategy: str
+    entry_time: datetime
+    exit_time: datetime
+    entry_price: Decimal
+    exit_price: Optional[Decimal]
+    size: Decimal
+    exit_reason: str
+    pnl: Decimal
+
+    @property
+    def hold_minutes(self) -> float:
+        return (self.exit_time - self.entry_time).total_seconds() / 60
+
+
+@dataclass
+class ReplayResult:
+    """Outcome of a replay run."""
+    start_time: datetime
+    end_time: datetime
+    initial_balance: Decimal
+    final_equity: Decimal
+    cycles: int
+    events: int
+    fills: List[Fill]
+    closed_trades: List[ClosedTrade]
+    equity_curve: List[Tuple[datetime, Decimal]]
+    strategy_stats: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def net_pnl(self) -> Decimal:
+        return self.final_equity - self.initial_balance
+
+    def exit_reason_breakdown(self) -> Dict[str, Dict[str, Any]]:
+        """Count, wins and PnL per exit reason."""
+        breakdown: Dict[str, Dict[str, Any]] = {}
+        for trade in self.closed_trades:
+            entry = breakdown.setdefault(trade.exit_reason, {"count": 0, "wins": 0, "pnl": Decimal("0")})
+            entry["count"] += 1
+            entry["wins"] += 1 if trade.pnl > 0 else 0
+            entry["pnl"] += trade.pnl
+        return breakdown
+
+    def max_drawdown(self) -> Decimal:
+        """Largest peak-to-trough equity decline as a fraction of the peak."""
+        peak = Decimal("0")
+        worst = Decimal("0")
+        for _, equity in self.equity_curve:
+            peak = max(peak, equity)
+            if peak > 0:
+                worst = max(worst, (peak - equity) / peak)
+        return worst
+
+    def summary(self) -> Dict[str, Any]:
+        """JSON-friendly summary of the run."""
+        wins = sum(1 for t in self.closed_trades if t.pnl > 0)
+        total = len(self.closed_trades)
+        return {
+            "start_time": self.start_time.isoformat(),
+            "end_time": self.end_time.isoformat(),
+            "events": self.events,
+            "cycles": self.cycles,
+            "initial_balance": str(self.initial_balance),
+            "final_equity": str(self.final_equity),
+            "net_pnl": str(self.net_pnl),
+            "closed_trades": total,
+            "win_rate": wins / total if total else 0.0,
+            "max_drawdown": str(self.max_drawdown()),
+            "fills": len(self.fills),
+            "exit_reasons": {
+                reason: {**stats, "pnl": str(stats["pnl"])}
+                for reason, stats in self.exit_reason_breakdown().items()
+            },
+        }
+
+
+# ============================================================================
+# Backtester
+# ============================================================================
+
+@dataclass
+class ReplayConfig:
+    """Configuration for a replay run."""
+    initial_balance: Decimal = Decimal("100.0")
+    cycle_interval_seconds: float = 1.0  # Simulated time between strategy cycles
+    fee_rate: Decimal = Decimal("0")  # Taker fee charged on matched notional
+    trade_size: float = 5.0
+    take_profit_pct: float = 0.02
+    stop_loss_pct: float = 0.02
+    max_positions: int = 5
+    entry_order: Optional[List[str]] = None
+    # Strategy attributes set after construction (e.g. trailing_stop_pct)
+    strategy_overrides: Dict[str, Any] = field(default_factory=dict)
+    # Frozen RL policy voting in the ensemble (docs/RL_POLICIES.md)
+    rl_policy_dir: Optional[Path] = None
+    rl_policy_version: Optional[int] = None
+    rl_experience_path: Optional[Path] = None  # Closed trades appended here as backtest experience
+
+
+# ReplayConfig fields --set may change; other names are strategy attributes
+REPLAY_CONFIG_OVERRIDES = (
+    "initial_balance", "cycle_interval_seconds", "fee_rate", "trade_size",
+    "take_profit_pct", "stop_loss_pct", "max_positions",
+)
+
+StrategyFactory = Callable[[ReplayExchange, ReplayConfig, Path], FifteenMinuteCryptoStrategy]
+
+
+def default_strategy_factory(
+    exchange: ReplayExchange, config: ReplayConfig, workdir: Path
+) -> FifteenMinuteCryptoStrategy:
+    """Build the live strategy against the replay exchange, keeping its state files in workdir."""
+    return FifteenMinuteCryptoStrategy(
+        clob_client=exchange,
+        trade_size=config.trade_size,
+        take_profit_pct=config.take_profit_pct,
+        stop_loss_pct=config.stop_loss_pct,
+        max_positions=config.max_positions,
+        dry_run=False,
+        llm_decision_engine=None,
+        enable_adaptive_learning=False,
+        initial_capital=float(config.initial_balance),
+        entry_order=config.entry_order,
+        positions_file=str(workdir / "active_positions.json"),
+        rl_engine=ContinuousRLEngine(
+            policy_dir=str(config.rl_policy_dir) if config.rl_policy_dir else None,
+            policy_version=config.rl_policy_version,
+            experience_path=str(config.rl_experience_path) if config.rl_experience_path else None,
+            source="backtest",
+        ),
+    )
+
+
+class ReplayBacktester:
+    """
+    Replays recorded market data through the 15-minute crypto strategy.
+
+    Features:
+    - Simulated clock patched into strategy modules
+    - Strategy cycles scheduled on simulated time between events
+    - Market discovery from recorded market events (no Gamma API)
+    - Order matching and balances via ReplayExchange (no CLOB or RPC)
+    - Settlement of held tokens on resolution events
+    - Strategy state files isolated in a scratch directory
+    """
+
+    def __init__(
+        self,
+        events: Iterable[ReplayEvent],
+        config: Optional[ReplayConfig] = None,
+        strategy_factory: Optional[StrategyFactory] = None,
+        workdir: Optional[Path] = None,
+    ):
+        """
+        Initialize the replay backtester.
+
+        Args:
+            events: Time-ordered replay events (may be a lazy iterator)
+            config: Replay configuration
+            strategy_factory: Builds the strategy from the exchange, config and state directory
+            workdir: Directory for strategy state files (default: temporary)
+        """
+        self.events = events
+        self.config = config or ReplayConfig()
+        self.strategy_factory = strategy_factory or default_strategy_factory
+        self.workdir = Path(workdir) if workdir else None
+
+        self.clock: Optional[SimulatedClock] = None
+        self.exchange: Optional[ReplayExchange] = None
+        self.strategy: Optional[FifteenMinuteCryptoStrategy] = None
+
+        self.markets: Dict[str, Dict[str, Any]] = {}
+        self.closed_trades: List[ClosedTrade] = []
+        self.equity_curve: List[Tuple[datetime, Decimal]] = []
+        self.cycles = 0
+        self.event_count = 0
+
+    async def run(self) -> ReplayResult:
+        """Replay every event and return the result."""
+        if self.workdir is not None:
+            self.workdir.mkdir(parents=True, exist_ok=True)
+            return await self._replay(self.workdir)
+        with tempfile.TemporaryDirectory(prefix="replay_") as scratch:
+            return await self._replay(Path(scratch))
+
+    async def _replay(self, workdir: Path) -> ReplayResult:
+        iterator = iter(self.events)
+        first = next(iterator, None)
+        if first is None:
+            raise ValueError("Replay stream is empty")
+
+        self.clock = SimulatedClock(first.timestamp)
+        interval = self.config.cycle_interval_seconds
+        if interval <= 0:
+            raise ValueError("cycle_interval_seconds must be positive")
+
+        with self.clock.patched():
+            self.exchange = ReplayExchange(self.clock, self.config.initial_balance, self.config.fee_rate)
+            self.strategy = self.strategy_factory(self.exchange, self.config, workdir)
+            self._attach(self.strategy)
+
+            start = first.timestamp
+            next_cycle = start.timestamp()
+            for event in self._chain(first, iterator):
+                # Cycles see every event stamped at or before their own time
+                while next_cycle < event.timestamp.timestamp():
+                    next_cycle = await self._cycle_at(next_cycle, interval)
+                self.clock.advance_to(event.timestamp)
+                self._apply(event)
+                self.event_count += 1
+
+            end = self.clock.now()
+            if next_cycle <= end.timestamp():
+                await self._cycle_at(next_cycle, interval)
+            self._record_equity()
+
+        logger.info(
+            f"✅ Replay complete: {self.event_count} events, {self.cycles} cycles, "
+            f"{len(self.closed_trades)} closed trades, equity ${self.exchange.equity():.2f}"
+        )
+        return ReplayResult(
+            start_time=start,
+            end_time=end,
+            initial_balance=Decimal(str(self.config.initial_balance)),
+            final_equity=self.exchange.equity(),
+            cycles=self.cycles,
+            events=self.event_count,
+            fills=list(self.exchange.fills),
+            closed_trades=list(self.closed_trades),
+            equity_curve=list(self.equity_curve),
+            strategy_stats=dict(getattr(self.strategy, "stats", {})),
+        )
+
+    @staticmethod
+    def _chain(first: ReplayEvent, rest: Iterator[ReplayEvent]) -> Iterator[ReplayEvent]:
+        yield first
+        yield from rest
+
+    async def _cycle_at(self, cycle_time: float, interval: float) -> float:
+        """Run one strategy cycle at cycle_time and return the next cycle time."""
+        self.clock.advance_to(datetime.fromtimestamp(cycle_time, tz=timezone.utc))
+        await self.strategy.run_cycle()
+        self.cycles += 1
+        self._record_equity()
+        return cycle_time + interval
+
+    def _record_equity(self) -> None:
+        self.equity_curve.append((self.clock.now(), self.exchange.equity()))
+
+    # ------------------------------------------------------------------
+    # Strategy wiring
+    # ------------------------------------------------------------------
+
+    def _attach(self, strategy: FifteenMinuteCryptoStrategy) -> None:
+        """Point the strategy's external dependencies at the replay."""
+        for name, value in self.config.strategy_overrides.items():
+            if not hasattr(strategy, name):
+                raise ValueError(f"Unknown strategy attribute override: {name}")
+            current = getattr(strategy, name)
+            if isinstance(value, str) and current is not None and not isinstance(current, str):
+                value = _coerce_override(name, value, type(current))
+            setattr(strategy, name, value)
+
+        # The analyzer caches books for 5s of (simulated) time; replays need every update
+        strategy.order_book_analyzer._cache_ttl = 0
+
+        exchange = self.exchange
+        close_position = strategy._close_position
+
+        async def fetch_markets() -> List[CryptoMarket]:
+            return self._active_markets()
+
+        async def token_balance(token_id: str) -> Optional[Decimal]:
+            return exchange.token_balances.get(token_id, Decimal("0"))
+
+        async def tracked_close(position, current_price, exit_reason="unknown"):
+            fills_before = len(exchange.fills)
+            closed = await close_position(position, current_price, exit_reason)
+            sells = [f for f in exchange.fills[fills_before:] if f.side == "SELL" and f.token_id == position.token_id]
+            if closed and sells:
+                self._record_close(position, sells, exit_reason)
+            return closed
+
+        strategy.fetch_15min_markets = fetch_markets
+        strategy._get_actual_token_balance = token_balance
+        strategy._close_position = tracked_close
+
+    def _active_markets(self) -> List[CryptoMarket]:
+        now = self.clock.now()
+        markets = []
+        for market in self.markets.values():
+            if market["resolved"] or not (market["start_time"] <= now < market["end_time"]):
+                continue
+            up_price = self.exchange.mid_price(market["up_token_id"])
+            down_price = self.exchange.mid_price(market["down_token_id"])
+            if up_price is None or down_price is None:
+                continue
+            markets.append(CryptoMarket(
+                market_id=market["market_id"],
+                question=market["question"],
+                asset=market["asset"],
+                up_token_id=market["up_token_id"],
+                down_token_id=market["down_token_id"],
+                up_price=up_price,
+                down_price=down_price,
+                end_time=market["end_time"],
+                neg_risk=market["neg_risk"],
+                tick_size=market["tick_size"],
+            ))
+        return markets
+
+    def _record_close(self, position, fills: List[Fill], exit_reason: str) -> None:
+        size = sum((f.size for f in fills), Decimal("0"))
+        exit_price = sum((f.price * f.size for f in fills), Decimal("0")) / size
+        self.closed_trades.append(ClosedTrade(
+            token_id=position.token_id,
+            market_id=position.market_id,
+            asset=position.asset,
+            side=position.side,
+            strategy=position.strategy,
+            entry_time=position.entry_time,
+            exit_time=self.clock.now(),
+            entry_price=position.entry_price,
+            exit_price=exit_price,
+            size=size,
+            exit_reason=exit_reason,
+            pnl=sum((f.realized_pnl for f in fills), Decimal("0")),
+        ))
+
+    # ------------------------------------------------------------------
+    # Event handlers
+    # ------------------------------------------------------------------
+
+    def _apply(self, event: ReplayEvent) -> None:
+        """
+        Apply one event. Trades are informational: the books already reflect them.
+
+        Books keyed by market_id (gamma_ws) cannot be matched to a token and are skipped.
+        """
+        data = event.data
+        if event.event_type == EVENT_BINANCE_TICK:
+            self.strategy.binance_feed._update_price(
+                data["asset"].upper(),
+                Decimal(str(data["price"])),
+                Decimal(str(data.get("volume", "0"))),
+            )
+        elif event.event_type in (EVENT_BOOK, EVENT_BOOK_DELTA) and "token_id" not in data:
+            return
+        elif event.event_type == EVENT_BOOK:
+            self.exchange.apply_book(data["token_id"], data.get("bids", []), data.get("asks", []))
+        elif event.event_type == EVENT_BOOK_DELTA:
+            self.exchange.apply_book_delta(data["token_id"], data.get("changes", []))
+        elif event.event_type == EVENT_MARKET:
+            self.markets[data["market_id"]] = {
+                "market_id": data["market_id"],
+                "question": data.get("question", f"{data['asset']} Up or Down"),
+                "asset": data["asset"].upper(),
+                "up_token_id": str(data["up_token_id"]),
+                "down_token_id": str(data["down_token_id"]),
+                "start_time": _parse_timestamp(data.get("start_time", event.timestamp)),
+                "end_time": _parse_timestamp(data["end_time"]),
+                "neg_risk": bool(data.get("neg_risk", True)),
+                "tick_size": str(data.get("tick_size", "0.01")),
+                "resolved": False,
+            }
+        elif event.event_type == EVENT_RESOLUTION:
+            self._resolve(data["market_id"], str(data["winner"]).upper())
+
+    def _resolve(self, market_id: str, winner: str) -> None:
+        """Redeem held tokens and drop the strategy's positions for a resolved market."""
+        market = self.markets.get(market_id)
+        if market is None:
+            logger.warning(f"⚠️ Resolution for unknown market {market_id}")
+            return
+        if winner not in ("UP", "DOWN"):
+            raise ValueError(f"Resolution winner must be UP or DOWN, got {winner}")
+        market["resolved"] = True
+
+        for side, token_id in (("UP", market["up_token_id"]), ("DOWN", market["down_token_id"])):
+            fill = self.exchange.settle(token_id, Decimal("1") if side == winner else Decimal("0"))
+            position = self.strategy.positions.pop(token_id, None)
+            if position is None:
+                continue
+            self.strategy.risk_manager.close_position(market_id, fill.price if fill else Decimal("0"))
+            if fill is not None:
+                self._record_close(position, [fill], "resolution")
+        self.strategy._save_positions()
+
+
+# ============================================================================
+# Command line
+# ============================================================================
+
+OVERRIDE_TYPES = (bool, int, float, Decimal, str)
+
+
+def _coerce_override(name: str, raw: str, target: Any) -> Any:
+    """
+    Convert a --set value to the type of the field or attribute it overrides.
+
+    Raises:
+        ValueError: If the value does not parse as that type, or the type cannot be set from text
+    """
+    if get_origin(target) is Union:  # Optional[X]
+        args = [arg for arg in get_args(target) if arg is not type(None)]
+        target = args[0] if len(args) == 1 else target
+    if target not in OVERRIDE_TYPES:
+        raise ValueError(f"{name} cannot be set with --set")
+    if target is bool:
+        lowered = raw.strip().lower()
+        if lowered not in ("true", "false", "1", "0", "yes", "no", "on", "off"):
+            raise ValueError(f"{name} expects true or false, got {raw!r}")
+        return lowered in ("true", "1", "yes", "on")
+    try:
+        return target(raw)
+    except (ValueError, ArithmeticError):
+        raise ValueError(f"{name} expects {target.__name__}, got {raw!r}") from None
+
+
+def _parse_override(raw: str) -> Tuple[str, Any]:
+    """
+    Parse --set name=value.
+
+    ReplayConfig fields are coerced to the field's type here; strategy attributes
+    stay text and take the type of the attribute they replace (ReplayBacktester._attach).
+    """
+    name, _, value = raw.partition("=")
+    if not name or not value:
+        raise argparse.ArgumentTypeError(f"Override must be name=value: {raw}")
+    if name in REPLAY_CONFIG_OVERRIDES:
+        try:
+            return name, _coerce_override(name, value, get_type_hints(ReplayConfig)[name])
+        except ValueError as e:
+            raise argparse.ArgumentTypeError(str(e))
+    return name, value
+
+
+def main():
+    """Command-line entry point for replay backtests."""
+    parser = argparse.ArgumentParser(description="Replay recorded market data through the 15-minute strategy")
+    parser.add_argument("--events", type=Path, required=True, help="Recording file (.jsonl/.jsonl.gz) or directory")
+    parser.add_argument("--initial-balance", type=Decimal, default=Decimal("100.0"))
+    parser.add_argument("--cycle-interval", type=float, default=1.0, help="Seconds of simulated time per cycle")
+    parser.add_argument("--fee-rate", type=Decimal, default=Decimal("0"))
+    parser.add_argument("--trade-size", type=float, default=5.0)
+    parser.add_argument(
+        "--set", dest="overrides", action="append", default=[], type=_parse_override,
+        help="Replay setting or strategy attribute override, e.g. --set trailing_stop_pct=0.01 (repeatable)"
+    )
+    parser.add_argument("--rl-policy-dir", type=Path, help="Frozen RL policies to vote with (default: none)")
+    parser.add_argument("--rl-policy-version", type=int, help="Frozen RL policy version (default: newest)")
+    parser.add_argument("--rl-experience", type=Path, help="Append closed trades to this RL experience log")
+    parser.add_argument("--output", type=Path, help="Write the JSON summary to this file")
+    parser.add_argument("--verbose", action="store_true")
+    args = parser.parse_args()
+
+    logging.basicConfig(
+        level=logging.INFO if args.verbose else logging.WARNING,
+        format="%(asctime)s - %(name)s - %(levelname)s - %(message)s"
+    )
+
+    config = ReplayConfig(
+        initial_balance=args.initial_balance,
+        cycle_interval_seconds=args.cycle_interval,
+        fee_rate=args.fee_rate,
+        trade_size=args.trade_size,
+        strategy_overrides={name: value for name, value in args.overrides if name not in REPLAY_CONFIG_OVERRIDES},
+        rl_policy_dir=args.rl_policy_dir,
+        rl_policy_version=args.rl_policy_version,
+        rl_experience_path=args.rl_experience,
+    )
+    config = replace(config, **{name: value for name, value in args.overrides if name in REPLAY_CONFIG_OVERRIDES})
+    result = asyncio.run(ReplayBacktester(load_replay_events(args.events), config).run())
+    summary = json.dumps(result.summary(), indent=2)
+    if args.output:
+        args.output.write_text(summary)
+    print(summary)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,1339 @@
+"""
+Simulated Polymarket CLOB for offline runs and tests.
+
+An in-process exchange with price-time priority matching, resting limit
+orders, partial fills, cancels, collateral/token balances and allowance
+checks. It can be used two ways:
+
+- SimulatedClobClient: a drop-in for py_clob_client's ClobClient, so
+  OrderManager, the strategies and MainOrchestrator can be driven offline
+  with dry_run=False and exercise the real post-submission code paths.
+- create_app() / `python -m src.clob_simulator`: an HTTP + WebSocket server
+  on the CLOB REST paths and the market/user channels, so a real ClobClient
+  (config.polymarket_api_url) or PolymarketWebSocketFeed can point at it.
+
+Scenarios (markets, funded accounts, seeded books, start time) load from a
+JSON file or dict, so runs are deterministic: order and trade ids are
+sequential and the clock only moves when told to.
+
+Validates Requirements:
+- Matching engine with partial fills, GTC/GTD/FOK/FAK orders and cancels
+- Balance and allowance checks matching CLOB error responses
+- REST/WebSocket surface compatible with ClobClient and the market feed
+- Deterministic scenarios for offline MainOrchestrator tests
+"""
+
+import argparse
+import asyncio
+import hashlib
+import itertools
+import json
+import logging
+import time
+from dataclasses import dataclass, field
+from decimal import Decimal, InvalidOperation
+from pathlib import Path
+from types import SimpleNamespace
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+USDC_DECIMALS = Decimal("1000000")
+
+BUY = "BUY"
+SELL = "SELL"
+
+ORDER_TYPES = ("GTC", "GTD", "FOK", "FAK")
+
+STATUS_LIVE = "LIVE"
+STATUS_MATCHED = "MATCHED"
+STATUS_CANCELED = "CANCELED"
+
+# Account that backs seeded liquidity; it never runs out of cash or tokens
+LIQUIDITY_PROVIDER = "0x000000000000000000000000000000000000dEaD"
+
+DEFAULT_ADDRESS = "0x0000000000000000000000000000000000000001"
+
+# Error messages as returned by the CLOB
+ERR_BALANCE = "not enough balance / allowance"
+ERR_FOK = "order couldn't be fully filled. FOK orders are fully filled or killed."
+ERR_FAK = "no orders found to match with FAK order. FAK orders are partially filled or killed if no match is found."
+ERR_NO_BOOK = "the orderbook {token_id} does not exist"
+ERR_TICK = "invalid order: price ({price}) breaks minimum tick size rule: {tick}"
+ERR_MIN_SIZE = "invalid order: size ({size}) lower than the minimum: {minimum}"
+ERR_EXPIRATION = "invalid expiration: {expiration}"
+ERR_CLOSED = "market {market} is not accepting orders"
+ERR_POST_ONLY_CROSSES = "invalid post-only order: order crosses book"
+ERR_POST_ONLY_TYPE = "invalid post-only order: only GTC and GTD order types are allowed"
+
+
+def _d(value: Any) -> Decimal:
+    """Convert a float/int/str amount to Decimal without float artefacts."""
+    return value if isinstance(value, Decimal) else Decimal(str(value))
+
+
+def _fmt(value: Decimal) -> str:
+    """Format a Decimal as a plain string ("0.5", "12", never "1E+1")."""
+    text = format(value.normalize(), "f")
+    return "0" if text in ("-0", "") else text
+
+
+def _value(value: Any) -> str:
+    """Unwrap enum-like values (OrderType.FOK, AssetType.COLLATERAL) to strings."""
+    return str(getattr(value, "value", value)).upper()
+
+
+# ============================================================================
+# Exchange state
+# ============================================================================
+
+@dataclass
+class SimMarket:
+    """A binary market and its outcome tokens."""
+    condition_id: str
+    question: str
+    tokens: List[Tuple[str, str]]  # (token_id, outcome)
+    tick_size: Decimal = Decimal("0.01")
+    min_order_size: Decimal = Decimal("5")
+    neg_risk: bool = False
+    end_date_iso: str = ""
+    accepting_orders: bool = True
+
+
+@dataclass
+class SimAccount:
+    """Collateral, allowance and conditional token balances of one address."""
+    address: str
+    collateral: Decimal = Decimal("0")
+    allowance: Optional[Decimal] = None  # None = unlimited USDC approval
+    tokens_approved: bool = True  # setApprovalForAll on the exchange
+    tokens: Dict[str, Decimal] = field(default_factory=dict)
+    unlimited: bool = False
+
+
+@dataclass
+class SimOrder:
+    """An order accepted by the exchange."""
+    order_id: str
+    owner: str
+    token_id: str
+    side: str
+    price: Decimal
+    original_size: Decimal
+    order_type: str
+    created_at: int
+    sequence: int
+    expiration: int = 0
+    size_matched: Decimal = Decimal("0")
+    status: str = STATUS_LIVE
+    associate_trades: List[str] = field(default_factory=list)
+
+    @property
+    def remaining(self) -> Decimal:
+        return self.original_size - self.size_matched
+
+    def to_dict(self, market: Optional[SimMarket] = None) -> Dict[str, Any]:
+        """Order in the shape returned by GET /data/order."""
+        return {
+            "id": self.order_id,
+            "status": self.status,
+            "owner": self.owner,
+            "maker_address": self.owner,
+            "market": market.condition_id if market else "",
+            "asset_id": self.token_id,
+            "side": self.side,
+            "original_size": _fmt(self.original_size),
+            "size_matched": _fmt(self.size_matched),
+            "price": _fmt(self.price),
+            "outcome": _outcome(market, self.token_id),
+            "expiration": str(self.expiration),
+            "order_type": self.order_type,
+            "created_at": self.created_at,
+            "associate_trades": list(self.associate_trades),
+        }
+
+
+@dataclass
+class SimTrade:
+    """A single maker/taker fill."""
+    trade_id: str
+    token_id: str
+    taker_order_id: str
+    taker: str
+    maker_order_id: str
+    maker: str
+    side: str  # taker side
+    price: Decimal
+    size: Decimal
+    match_time: int
+    transaction_hash: str
+
+    def to_dict(self, owner: Optional[str] = None, market: Optional[SimMarket] = None) -> Dict[str, Any]:
+        """Trade in the shape returned by GET /data/trades, from owner's point of view."""
+        return {
+            "id": self.trade_id,
+            "taker_order_id": self.taker_order_id,
+            "market": market.condition_id if market else "",
+            "asset_id": self.token_id,
+            "side": self.side,
+            "size": _fmt(self.size),
+            "fee_rate_bps": "0",
+            "price": _fmt(self.price),
+            "status": STATUS_MATCHED,
+            "match_time": str(self.match_time),
+            "outcome": _outcome(market, self.token_id),
+            "owner": self.taker,
+            "maker_address": self.taker,
+            "trader_side": "MAKER" if owner is not None and owner == self.maker and owner != self.taker else "TAKER",
+            "transaction_hash": self.transaction_hash,
+            "maker_orders": [{
+                "order_id": self.maker_order_id,
+                "owner": self.maker,
+                "maker_address": self.maker,
+                "matched_amount": _fmt(self.size),
+                "price": _fmt(self.price),
+                "asset_id": self.token_id,
+                "outcome": _outcome(market, self.token_id),
+                "side": SELL if self.side == BUY else BUY,
+            }],
+        }
+
+
+def _outcome(market: Optional[SimMarket], token_id: str) -> str:
+    if market is None:
+        return ""
+    return next((outcome for tid, outcome in market.tokens if tid == token_id), "")
+
+
+class SimulatedClobExchange:
+    """
+    In-memory Polymarket CLOB.
+
+    Features:
+    - Per-token order books with price-time priority; fills at the maker price
+    - GTC and GTD orders rest, FOK fills fully or is rejected, FAK fills what
+      it can and cancels the rest
+    - BUY orders reserve collateral and SELL orders reserve tokens while resting
+    - USDC allowance and token approval checks ("not enough balance / allowance")
+    - GTD orders expire against the exchange clock
+    - Listeners receive market ("book", "last_trade_price") and user ("order",
+      "trade") events for the WebSocket channels
+    - Deterministic sequential ids; the clock is fixed once set_time() is called
+    """
+
+    def __init__(self, start_time: Optional[float] = None):
+        """
+        Initialize an empty exchange.
+
+        Args:
+            start_time: Epoch seconds to freeze the clock at; None follows time.time()
+        """
+        self._now: Optional[float] = start_time
+        self.markets: Dict[str, SimMarket] = {}
+        self._token_markets: Dict[str, SimMarket] = {}
+        self.accounts: Dict[str, SimAccount] = {
+            LIQUIDITY_PROVIDER: SimAccount(address=LIQUIDITY_PROVIDER, unlimited=True)
+        }
+        self.orders: Dict[str, SimOrder] = {}
+        self.trades: List[SimTrade] = []
+        self._books: Dict[str, Dict[str, List[SimOrder]]] = {}
+        self._ids = itertools.count(1)
+        self._listeners: List[Callable[[Dict[str, Any]], None]] = []
+
+        # Signer -> funder (proxy/Safe) address, as the CLOB resolves for L2 auth
+        self.proxy_wallets: Dict[str, str] = {}
+
+    # ------------------------------------------------------------------
+    # Clock
+    # ------------------------------------------------------------------
+
+    def now(self) -> float:
+        """Current exchange time in epoch seconds."""
+        return self._now if self._now is not None else time.time()
+
+    def set_time(self, timestamp: float) -> None:
+        """Freeze the clock at timestamp and expire GTD orders that lapsed."""
+        self._now = timestamp
+        self._expire_orders()
+
+    def advance(self, seconds: float) -> None:
+        """Move the frozen clock forward."""
+        self.set_time(self.now() + seconds)
+
+    # ------------------------------------------------------------------
+    # Setup
+    # ------------------------------------------------------------------
+
+    def add_market(
+        self,
+        condition_id: str,
+        tokens: Any,
+        question: str = "",
+        tick_size: Any = "0.01",
+        min_order_size: Any = "5",
+        neg_risk: bool = False,
+        end_date_iso: str = "",
+    ) -> SimMarket:
+        """
+        Register a market.
+
+        Args:
+            condition_id: Market condition id
+            tokens: {token_id: outcome} or [(token_id, outcome), ...]; first is YES
+            question: Market question
+            tick_size: Minimum price increment
+            min_order_size: Minimum order size in shares
+            neg_risk: Whether the market uses the neg-risk exchange
+            end_date_iso: Market end date
+        """
+        pairs = list(tokens.items()) if isinstance(tokens, dict) else [tuple(t) for t in tokens]
+        market = SimMarket(
+            condition_id=condition_id,
+            question=question or condition_id,
+            tokens=[(str(token_id), outcome) for token_id, outcome in pairs],
+            tick_size=_d(tick_size),
+            min_order_size=_d(min_order_size),
+            neg_risk=neg_risk,
+            end_date_iso=end_date_iso,
+        )
+        self.markets[condition_id] = market
+        for token_id, _ in market.tokens:
+            self._token_markets[token_id] = market
+            self._books.setdefault(token_id, {BUY: [], SELL: []})
+        return market
+
+    def account(self, address: str) -> SimAccount:
+        """Get (or create) the account for an address."""
+        if address not in self.accounts:
+            self.accounts[address] = SimAccount(address=address)
+        return self.accounts[address]
+
+    def fund(
+        self,
+        address: str,
+        collateral: Any = 0,
+        tokens: Optional[Dict[str, Any]] = None,
+        allowance: Any = None,
+        tokens_approved: bool = True,
+    ) -> SimAccount:
+        """Credit collateral and tokens to an address and set its approvals."""
+        account = self.account(address)
+        account.collateral += _d(collateral)
+        for token_id, amount in (tokens or {}).items():
+            account.tokens[str(token_id)] = account.tokens.get(str(token_id), Decimal("0")) + _d(amount)
+        account.allowance = None if allowance is None else _d(allowance)
+        account.tokens_approved = tokens_approved
+        return account
+
+    def link_proxy_wallet(self, signer: str, funder: str) -> None:
+        """Route a signer's authenticated requests to its proxy/Safe wallet."""
+        self.proxy_wallets[signer.lower()] = funder
+
+    def resolve_owner(self, address: str) -> str:
+        """Return the funder address for a signer (or the address itself)."""
+        return self.proxy_wallets.get(address.lower(), address)
+
+    def seed_book(self, token_id: str, bids: Iterable[Any] = (), asks: Iterable[Any] = ()) -> List[str]:
+        """
+        Rest liquidity-provider orders on a book.
+
+        Args:
+            token_id: Token to seed
+            bids: [(price, size), ...] buy orders
+            asks: [(price, size), ...] sell orders
+
+        Returns:
+            Ids of the seeded orders
+        """
+        order_ids = []
+        for side, levels in ((BUY, bids), (SELL, asks)):
+            for price, size in levels:
+                response = self.place_order(LIQUIDITY_PROVIDER, token_id, side, price, size)
+                if not response["success"]:
+                    raise ValueError(f"Cannot seed {side} {size} @ {price} on {token_id}: {response['errorMsg']}")
+                order_ids.append(response["orderID"])
+        return order_ids
+
+    def close_market(self, condition_id: str) -> None:
+        """Stop accepting orders on a market and cancel its resting orders."""
+        market = self.markets[condition_id]
+        market.accepting_orders = False
+        for token_id, _ in market.tokens:
+            for order in self.open_orders(token_id=token_id):
+                self._cancel(order)
+
+    # ------------------------------------------------------------------
+    # Listeners
+    # ------------------------------------------------------------------
+
+    def add_listener(self, callback: Callable[[Dict[str, Any]], None]) -> None:
+        """Register a callback for market and user channel events."""
+        self._listeners.append(callback)
+
+    def remove_listener(self, callback: Callable[[Dict[str, Any]], None]) -> None:
+        """Unregister a callback."""
+        if callback in self._listeners:
+            self._listeners.remove(callback)
+
+    def _publish(self, event_type: str, payload: Dict[str, Any]) -> None:
+        # Both keys: the CLOB uses event_type, PolymarketWebSocketFeed reads type
+        event = {"event_type": event_type, "type": event_type, **payload}
+        for callback in list(self._listeners):
+            try:
+                callback(event)
+            except Exception as e:
+                logger.error(f"❌ Simulator listener failed: {e}")
+
+    def _publish_book(self, token_id: str) -> None:
+        self._publish("book", self.book_snapshot(token_id))
+
+    def _publish_order(self, order: SimOrder, update: str) -> None:
+        self._publish("order", {
+            **order.to_dict(self._token_markets.get(order.token_id)),
+            "update_type": update,
+            "timestamp": str(int(self.now() * 1000)),
+        })
+
+    # ------------------------------------------------------------------
+    # Books and balances
+    # ------------------------------------------------------------------
+
+    def market_for(self, token_id: str) -> Optional[SimMarket]:
+        """Market a token belongs to."""
+        return self._token_markets.get(str(token_id))
+
+    def book(self, token_id: str) -> Tuple[List[Tuple[Decimal, Decimal]], List[Tuple[Decimal, Decimal]]]:
+        """Aggregated (bids, asks) levels for a token, best price first."""
+        self._expire_orders()
+        book = self._books.get(str(token_id), {BUY: [], SELL: []})
+        return self._aggregate(book[BUY]), self._aggregate(book[SELL])
+
+    @staticmethod
+    def _aggregate(orders: List[SimOrder]) -> List[Tuple[Decimal, Decimal]]:
+        levels: Dict[Decimal, Decimal] = {}
+        for order in orders:
+            levels[order.price] = levels.get(order.price, Decimal("0")) + order.remaining
+        return list(levels.items())
+
+    def book_snapshot(self, token_id: str) -> Dict[str, Any]:
+        """Order book in the shape returned by GET /book (best price first)."""
+        bids, asks = self.book(token_id)
+        market = self.market_for(token_id)
+        snapshot = {
+            "market": market.condition_id if market else "",
+            "asset_id": str(token_id),
+            "timestamp": str(int(self.now() * 1000)),
+            "bids": [{"price": _fmt(p), "size": _fmt(s)} for p, s in bids],
+            "asks": [{"price": _fmt(p), "size": _fmt(s)} for p, s in asks],
+            "tick_size": _fmt(market.tick_size) if market else "0.01",
+            "min_order_size": _fmt(market.min_order_size) if market else "5",
+            "neg_risk": market.neg_risk if market else False,
+        }
+        snapshot["hash"] = hashlib.sha1(json.dumps([snapshot["bids"], snapshot["asks"]]).encode()).hexdigest()
+        return snapshot
+
+    def best_bid(self, token_id: str) -> Optional[Decimal]:
+        bids, _ = self.book(token_id)
+        return bids[0][0] if bids else None
+
+    def best_ask(self, token_id: str) -> Optional[Decimal]:
+        _, asks = self.book(token_id)
+        return asks[0][0] if asks else None
+
+    def midpoint(self, token_id: str) -> Optional[Decimal]:
+        bid, ask = self.best_bid(token_id), self.best_ask(token_id)
+        if bid is None or ask is None:
+            return bid if ask is None else ask
+        return (bid + ask) / 2
+
+    def last_trade(self, token_id: str) -> Optional[SimTrade]:
+        return next((t for t in reversed(self.trades) if t.token_id == str(token_id)), None)
+
+    def reserved(self, owner: str, token_id: Optional[str] = None) -> Decimal:
+        """Collateral (token_id None) or tokens locked by the owner's resting orders."""
+        if token_id is None:
+            return sum((o.price * o.remaining for o in self.open_orders(owner=owner) if o.side == BUY), Decimal("0"))
+        return sum((o.remaining for o in self.open_orders(owner=owner, token_id=token_id) if o.side == SELL), Decimal("0"))
+
+    def balance(self, owner: str, token_id: Optional[str] = None) -> Decimal:
+        """Collateral (token_id None) or token balance, including reserved amounts."""
+        account = self.account(owner)
+        if token_id is None:
+            return account.collateral
+        return account.tokens.get(str(token_id), Decimal("0"))
+
+    # ------------------------------------------------------------------
+    # Orders
+    # ------------------------------------------------------------------
+
+    def _next_id(self) -> int:
+        return next(self._ids)
+
+    def _reject(self, message: str) -> Dict[str, Any]:
+        logger.debug(f"Simulated CLOB rejected order: {message}")
+        return {"success": False, "errorMsg": message, "orderID": "", "status": "",
+                "takingAmount": "0", "makingAmount": "0", "transactionsHashes": []}
+
+    def _validate(self, owner: str, token_id: str, side: str, price: Decimal, size: Decimal,
+                  order_type: str, expiration: int) -> Optional[str]:
+        market = self.market_for(token_id)
+        if market is None:
+            return ERR_NO_BOOK.format(token_id=token_id)
+        if not market.accepting_orders:
+            return ERR_CLOSED.format(market=market.condition_id)
+        if side not in (BUY, SELL):
+            return f"invalid order: side {side}"
+        if order_type not in ORDER_TYPES:
+            return f"invalid order type: {order_type}"
+        if price < market.tick_size or price > 1 - market.tick_size or price % market.tick_size != 0:
+            return ERR_TICK.format(price=_fmt(price), tick=_fmt(market.tick_size))
+        if size < market.min_order_size:
+            return ERR_MIN_SIZE.format(size=_fmt(size), minimum=_fmt(market.min_order_size))
+        if order_type == "GTD" and expiration <= self.now():
+            return ERR_EXPIRATION.format(expiration=expiration)
+
+        account = self.account(owner)
+        if account.unlimited:
+            return None
+        if side == BUY:
+            cost = price * size
+            committed = self.reserved(owner) + cost
+            if account.collateral < committed or (account.allowance is not None and account.allowance < committed):
+                return ERR_BALANCE
+        else:
+            committed = self.reserved(owner, token_id) + size
+            if not account.tokens_approved or account.tokens.get(token_id, Decimal("0")) < committed:
+                return ERR_BALANCE
+        return None
+
+    def _matchable(self, side: str, price: Decimal, token_id: str) -> List[SimOrder]:
+        """Resting orders on the opposite side that cross price, in priority order."""
+        book = self._books[token_id]
+        if side == BUY:
+            return [o for o in book[SELL] if o.price <= price]
+        return [o for o in book[BUY] if o.price >= price]
+
+    def place_order(
+        self,
+        owner: str,
+        token_id: Any,
+        side: str,
+        price: Any,
+        size: Any,
+        order_type: str = "GTC",
+        expiration: int = 0,
+        post_only: bool = False,
+    ) -> Dict[str, Any]:
+        """
+        Accept, match and (for GTC/GTD) rest an order.
+
+        Args:
+            owner: Funder address placing the order
+            token_id: Outcome token
+            side: "BUY" or "SELL"
+            price: Limit price
+            size: Size in shares
+            order_type: GTC, GTD, FOK or FAK
+            expiration: Expiry in epoch seconds (GTD only)
+            post_only: Reject instead of matching if the order crosses the book
+
+        Returns:
+            dict: POST /order response (success, errorMsg, orderID, status,
+            makingAmount, takingAmount, transactionsHashes)
+        """
+        self._expire_orders()
+        token_id, side, order_type = str(token_id), _value(side), _value(order_type)
+        try:
+            price, size = _d(price), _d(size)
+        except InvalidOperation:
+            return self._reject(f"invalid order: price {price} / size {size}")
+
+        error = self._validate(owner, token_id, side, price, size, order_type, int(expiration or 0))
+        if error:
+            return self._reject(error)
+
+        if post_only and order_type not in ("GTC", "GTD"):
+            return self._reject(ERR_POST_ONLY_TYPE)
+
+        candidates = self._matchable(side, price, token_id)
+        if post_only and candidates:
+            return self._reject(ERR_POST_ONLY_CROSSES)
+        available = sum((o.remaining for o in candidates), Decimal("0"))
+        if order_type == "FOK" and available < size:
+            return self._reject(ERR_FOK)
+        if order_type == "FAK" and available == 0:
+            return self._reject(ERR_FAK)
+
+        number = self._next_id()
+        order = SimOrder(
+            order_id=f"0x{number:064x}",
+            owner=owner,
+            token_id=token_id,
+            side=side,
+            price=price,
+            original_size=size,
+            order_type=order_type,
+            created_at=int(self.now()),
+            sequence=number,
+            expiration=int(expiration or 0) if order_type == "GTD" else 0,
+        )
+        self.orders[order.order_id] = order
+
+        shares = notional = Decimal("0")
+        tx_hashes = []
+        for maker in candidates:
+            if order.remaining <= 0:
+                break
+            trade = self._fill(order, maker, min(order.remaining, maker.remaining))
+            shares += trade.size
+            notional += trade.size * trade.price
+            tx_hashes.append(trade.transaction_hash)
+
+        if order.remaining <= 0:
+            order.status = STATUS_MATCHED
+        elif order_type in ("GTC", "GTD"):
+            self._rest(order)
+        else:
+            order.status = STATUS_CANCELED
+
+        self._publish_order(order, "PLACEMENT")
+        if tx_hashes or order.status == STATUS_LIVE:
+            self._publish_book(token_id)
+
+        making, taking = (notional, shares) if side == BUY else (shares, notional)
+        return {
+            "success": True,
+            "errorMsg": "",
+            "orderID": order.order_id,
+            "status": "live" if order.status == STATUS_LIVE else "matched",
+            "makingAmount": _fmt(making),
+            "takingAmount": _fmt(taking),
+            "transactionsHashes": tx_hashes,
+        }
+
+    def _rest(self, order: SimOrder) -> None:
+        orders = self._books[order.token_id][order.side]
+        orders.append(order)
+        if order.side == BUY:
+            orders.sort(key=lambda o: (-o.price, o.sequence))
+        else:
+            orders.sort(key=lambda o: (o.price, o.sequence))
+
+    def _fill(self, taker: SimOrder, maker: SimOrder, size: Decimal) -> SimTrade:
+        """Match size between a taker and a resting maker at the maker's price."""
+        price = maker.price
+        buyer, seller = (taker, maker) if taker.side == BUY else (maker, taker)
+        self._settle(buyer.owner, seller.owner, taker.token_id, price, size)
+
+        trade_number = self._next_id()
+        trade = SimTrade(
+            trade_id=f"trade-{trade_number}",
+            token_id=taker.token_id,
+            taker_order_id=taker.order_id,
+            taker=taker.owner,
+            maker_order_id=maker.order_id,
+            maker=maker.owner,
+            side=taker.side,
+            price=price,
+            size=size,
+            match_time=int(self.now()),
+            transaction_hash=f"0x{hashlib.sha256(f'trade-{trade_number}'.encode()).hexdigest()}",
+        )
+        self.trades.append(trade)
+
+        for order in (taker, maker):
+            order.size_matched += size
+            order.associate_trades.append(trade.trade_id)
+        if maker.remaining <= 0:
+            maker.status = STATUS_MATCHED
+            self._books[maker.token_id][maker.side].remove(maker)
+        self._publish_order(maker, "UPDATE")
+
+        market = self.market_for(trade.token_id)
+        self._publish("trade", trade.to_dict(market=market))
+        self._publish("last_trade_price", {
+            "asset_id": trade.token_id,
+            "market": market.condition_id if market else "",
+            "price": _fmt(price),
+            "size": _fmt(size),
+            "side": trade.side,
+            "timestamp": str(int(self.now() * 1000)),
+        })
+        return trade
+
+    def _settle(self, buyer: str, seller: str, token_id: str, price: Decimal, size: Decimal) -> None:
+        cost = price * size
+        buyer_account, seller_account = self.account(buyer), self.account(seller)
+        if not buyer_account.unlimited:
+            buyer_account.collateral -= cost
+            if buyer_account.allowance is not None:
+                buyer_account.allowance -= cost
+            buyer_account.tokens[token_id] = buyer_account.tokens.get(token_id, Decimal("0")) + size
+        if not seller_account.unlimited:
+            seller_account.collateral += cost
+            seller_account.tokens[token_id] -= size
+            if seller_account.tokens[token_id] == 0:
+                del seller_account.tokens[token_id]
+
+    def cancel(self, owner: Optional[str], order_ids: Iterable[str]) -> Dict[str, Any]:
+        """
+        Cancel orders.
+
+        Args:
+            owner: Address requesting the cancel (None skips the ownership check)
+            order_ids: Orders to cancel
+
+        Returns:
+            dict: {"canceled": [ids], "not_canceled": {id: reason}}
+        """
+        self._expire_orders()
+        canceled, not_canceled = [], {}
+        for order_id in order_ids:
+            order = self.orders.get(order_id)
+            if order is None or (owner is not None and order.owner != owner):
+                not_canceled[order_id] = "order not found"
+            elif order.status != STATUS_LIVE:
+                not_canceled[order_id] = f"order can't be canceled, status: {order.status}"
+            else:
+                self._cancel(order)
+                canceled.append(order_id)
+        return {"canceled": canceled, "not_canceled": not_canceled}
+
+    def cancel_all(self, owner: str, market: Optional[str] = None, token_id: Optional[str] = None) -> Dict[str, Any]:
+        """Cancel all of an owner's resting orders, optionally for one market or token."""
+        order_ids = [o.order_id for o in self.open_orders(owner=owner, market=market, token_id=token_id)]
+        return self.cancel(owner, order_ids)
+
+    def _cancel(self, order: SimOrder) -> None:
+        order.status = STATUS_CANCELED
+        book_side = self._books[order.token_id][order.side]
+        if order in book_side:
+            book_side.remove(order)
+        self._publish_order(order, "CANCELLATION")
+        self._publish_book(order.token_id)
+
+    def _expire_orders(self) -> None:
+        now = self.now()
+        expired = [o for o in self.orders.values()
+                   if o.status == STATUS_LIVE and o.expiration and o.expiration <= now]
+        for order in expired:
+            logger.debug(f"Simulated CLOB expired GTD order {order.order_id}")
+            self._cancel(order)
+
+    def open_orders(
+        self,
+        owner: Optional[str] = None,
+        market: Optional[str] = None,
+        token_id: Optional[str] = None,
+    ) -> List[SimOrder]:
+        """Resting orders, optionally filtered by owner, market and token."""
+        result = []
+        for order in self.orders.values():
+            if order.status != STATUS_LIVE:
+                continue
+            if owner is not None and order.owner != owner:
+                continue
+            if token_id is not None and order.token_id != str(token_id):
+                continue
+            if market is not None:
+                order_market = self.market_for(order.token_id)
+                if order_market is None or order_market.condition_id != market:
+                    continue
+            result.append(order)
+        return result
+
+    def trades_for(self, owner: str) -> List[SimTrade]:
+        """Trades where owner was taker or maker."""
+        return [t for t in self.trades if owner in (t.taker, t.maker)]
+
+    # ------------------------------------------------------------------
+    # Scenarios
+    # ------------------------------------------------------------------
+
+    @classmethod
+    def from_scenario(cls, scenario: Any) -> "SimulatedClobExchange":
+        """
+        Build an exchange from a scenario dict or JSON file path.
+
+        Scenario format:
+            {
+              "start_time": 1767268800,
+              "markets": [{"condition_id": "0xabc", "question": "...",
+                           "tokens": {"111": "Yes", "222": "No"},
+                           "tick_size": "0.01", "min_order_size": "5", "neg_risk": false}],
+              "accounts": {"0xFunder": {"collateral": "100", "tokens": {"111": "10"},
+                                        "allowance": null, "signer": "0xSigner"}},
+              "books": {"111": {"bids": [["0.48", "100"]], "asks": [["0.50", "100"]]}}
+            }
+        """
+        if not isinstance(scenario, dict):
+            scenario = json.loads(Path(scenario).read_text())
+
+        exchange = cls(start_time=scenario.get("start_time"))
+        for market in scenario.get("markets", []):
+            exchange.add_market(**market)
+        for address, account in scenario.get("accounts", {}).items():
+            account = dict(account)
+            signer = account.pop("signer", None)
+            exchange.fund(address, **account)
+            if signer:
+                exchange.link_proxy_wallet(signer, address)
+        for token_id, book in scenario.get("books", {}).items():
+            exchange.seed_book(token_id, book.get("bids", []), book.get("asks", []))
+        return exchange
+
+    def client_factory(self, address: Optional[str] = None) -> Callable[..., "SimulatedClobClient"]:
+        """
+        Return a callable with ClobClient's constructor signature.
+
+        Patch it over ClobClient (e.g. src.main_orchestrator.ClobClient) to run
+        the bot against this exchange in-process.
+        """
+        def factory(host: str = "sim://clob", key: Optional[str] = None, chain_id: int = 137,
+                    signature_type: Optional[int] = None, funder: Optional[str] = None, **kwargs):
+            return SimulatedClobClient(self, host=host, key=key, chain_id=chain_id,
+                                       signature_type=signature_type, funder=funder, address=address)
+        return factory
+
+
+# ============================================================================
+# In-process ClobClient
+# ============================================================================
+
+@dataclass
+class SimulatedSignedOrder:
+    """Stand-in for py_clob_client's SignedOrder."""
+    token_id: str
+    side: str
+    price: Decimal
+    size: Decimal
+    maker: str
+    expiration: int = 0
+    neg_risk: bool = False
+    tick_size: str = "0.01"
+
+    def dict(self) -> Dict[str, Any]:
+        return {
+            "tokenId": self.token_id,
+            "side": self.side,
+            "price": _fmt(self.price),
+            "size": _fmt(self.size),
+            "maker": self.maker,
+            "expiration": str(self.expiration),
+        }
+
+
+class SimulatedClobClient:
+    """
+    Drop-in for py_clob_client.client.ClobClient backed by a SimulatedClobExchange.
+
+    Orders are placed for the funder address (or the client address when no
+    funder is given), mirroring proxy/Safe wallet trading. Response shapes
+    follow the CLOB REST API.
+    """
+
+    def __init__(
+        self,
+        exchange: SimulatedClobExchange,
+        host: str = "sim://clob",
+        key: Optional[str] = None,
+        chain_id: int = 137,
+        signature_type: Optional[int] = None,
+        funder: Optional[str] = None,
+        address: Optional[str] = None,
+    ):
+        self.exchange = exchange
+        self.host = host
+        self.chain_id = chain_id
+        self.signature_type = signature_type
+        self.address = address or funder or DEFAULT_ADDRESS
+        self.funder = funder or self.address
+        self.creds = None
+        if self.funder != self.address:
+            exchange.link_proxy_wallet(self.address, self.funder)
+
+    # -- Auth -------------------------------------------------------------
+
+    def get_address(self) -> str:
+        return self.address
+
+    def create_or_derive_api_creds(self, nonce: Optional[int] = None):
+        """Derive deterministic API credentials from the signer address."""
+        return _derive_creds(self.address)
+
+    create_api_key = create_or_derive_api_creds
+    derive_api_key = create_or_derive_api_creds
+
+    def set_api_creds(self, creds) -> None:
+        self.creds = creds
+
+    def get_ok(self) -> str:
+        return "OK"
+
+    def get_server_time(self) -> int:
+        return int(self.exchange.now())
+
+    # -- Markets ----------------------------------------------------------
+
+    def get_markets(self, next_cursor: str = "MA==") -> Dict[str, Any]:
+        data = [_market_dict(self.exchange, m) for m in self.exchange.markets.values()]
+        return {"limit": len(data), "count": len(data), "next_cursor": "LTE=", "data": data}
+
+    def get_market(self, condition_id: str) -> Dict[str, Any]:
+        return _market_dict(self.exchange, self.exchange.markets[condition_id])
+
+    def get_order_book(self, token_id: str):
+        snapshot = self.exchange.book_snapshot(token_id)
+        return SimpleNamespace(
+            **{k: v for k, v in snapshot.items() if k not in ("bids", "asks")},
+            bids=[SimpleNamespace(**level) for level in snapshot["bids"]],
+            asks=[SimpleNamespace(**level) for level in snapshot["asks"]],
+        )
+
+    def get_order_books(self, params: Iterable[Any]) -> List[Any]:
+        return [self.get_order_book(getattr(p, "token_id", p)) for p in params]
+
+    def get_midpoint(self, token_id: str) -> Dict[str, str]:
+        mid = self.exchange.midpoint(token_id)
+        return {"mid": _fmt(mid) if mid is not None else "0"}
+
+    def get_price(self, token_id: str, side: str) -> Dict[str, str]:
+        """Price a BUY would pay (best ask) or a SELL would receive (best bid)."""
+        price = self.exchange.best_ask(token_id) if _value(side) == BUY else self.exchange.best_bid(token_id)
+        return {"price": _fmt(price) if price is not None else "0"}
+
+    def get_last_trade_price(self, token_id: str) -> Dict[str, str]:
+        trade = self.exchange.last_trade(token_id)
+        return {"price": _fmt(trade.price) if trade else "0", "side": trade.side if trade else ""}
+
+    def get_tick_size(self, token_id: str) -> str:
+        market = self.exchange.market_for(token_id)
+        return _fmt(market.tick_size) if market else "0.01"
+
+    def get_neg_risk(self, token_id: str) -> bool:
+        market = self.exchange.market_for(token_id)
+        return bool(market and market.neg_risk)
+
+    # -- Orders -----------------------------------------------------------
+
+    def create_order(self, order_args, options=None) -> SimulatedSignedOrder:
+        """Build a "signed" limit order, validating price against the tick size like ClobClient."""
+        token_id = str(order_args.token_id)
+        tick_size = str(getattr(options, "tick_size", None) or self.get_tick_size(token_id))
+        price = _d(order_args.price)
+        tick = _d(tick_size)
+        if price < tick or price > 1 - tick:
+            raise ValueError(f"price ({price}), min: {tick_size} - max: {_fmt(1 - tick)}")
+        neg_risk = getattr(options, "neg_risk", None)
+        return SimulatedSignedOrder(
+            token_id=token_id,
+            side=_value(order_args.side),
+            price=price,
+            size=_d(order_args.size),
+            maker=self.funder,
+            expiration=int(getattr(order_args, "expiration", 0) or 0),
+            neg_risk=self.get_neg_risk(token_id) if neg_risk is None else bool(neg_risk),
+            tick_size=tick_size,
+        )
+
+    def create_market_order(self, order_args, options=None) -> SimulatedSignedOrder:
+        """
+        Build a marketable order from MarketOrderArgs.
+
+        BUY amounts are USDC, SELL amounts are shares. Without an explicit
+        price, the worst price needed to fill the amount against the current
+        book is used, as ClobClient.calculate_market_price does.
+        """
+        token_id = str(order_args.token_id)
+        side = _value(getattr(order_args, "side", BUY) or BUY)
+        amount = _d(order_args.amount)
+        price = _d(getattr(order_args, "price", 0) or 0)
+        if price <= 0:
+            price = self._market_price(token_id, side, amount)
+        size = (amount / price).quantize(Decimal("0.01")) if side == BUY else amount
+        return self.create_order(SimpleNamespace(token_id=token_id, price=price, size=size, side=side), options)
+
+    def _market_price(self, token_id: str, side: str, amount: Decimal) -> Decimal:
+        bids, asks = self.exchange.book(token_id)
+        levels = asks if side == BUY else bids
+        filled = Decimal("0")
+        for price, size in levels:
+            filled += price * size if side == BUY else size
+            if filled >= amount:
+                return price
+        raise ValueError("no match")
+
+    def post_order(self, order: SimulatedSignedOrder, orderType: Any = "GTC",
+                   post_only: bool = False) -> Dict[str, Any]:
+        return self.exchange.place_order(
+            order.maker, order.token_id, order.side, order.price, order.size,
+            order_type=_value(orderType), expiration=order.expiration, post_only=post_only,
+        )
+
+    def create_and_post_order(self, order_args, options=None) -> Dict[str, Any]:
+        return self.post_order(self.create_order(order_args, options))
+
+    def cancel(self, order_id: str) -> Dict[str, Any]:
+        return self.exchange.cancel(self.funder, [order_id])
+
+    def cancel_orders(self, order_ids: List[str]) -> Dict[str, Any]:
+        return self.exchange.cancel(self.funder, order_ids)
+
+    def cancel_all(self) -> Dict[str, Any]:
+        return self.exchange.cancel_all(self.funder)
+
+    def cancel_market_orders(self, market: str = "", asset_id: str = "") -> Dict[str, Any]:
+        return self.exchange.cancel_all(self.funder, market=market or None, token_id=asset_id or None)
+
+    def get_orders(self, params=None, next_cursor: str = "MA==") -> List[Dict[str, Any]]:
+        order_id = getattr(params, "id", None)
+        orders = self.exchange.open_orders(
+            owner=self.funder,
+            market=getattr(params, "market", None) or None,
+            token_id=getattr(params, "asset_id", None) or None,
+        )
+        return [o.to_dict(self.exchange.market_for(o.token_id)) for o in orders
+                if order_id is None or o.order_id == order_id]
+
+    def get_order(self, order_id: str) -> Optional[Dict[str, Any]]:
+        order = self.exchange.orders.get(order_id)
+        if order is None or order.owner != self.funder:
+            return None
+        return order.to_dict(self.exchange.market_for(order.token_id))
+
+    def get_trades(self, params=None, next_cursor: str = "MA==") -> List[Dict[str, Any]]:
+        token_id = getattr(params, "asset_id", None)
+        return [t.to_dict(self.funder, self.exchange.market_for(t.token_id))
+                for t in self.exchange.trades_for(self.funder)
+                if not token_id or t.token_id == str(token_id)]
+
+    # -- Balances ---------------------------------------------------------
+
+    def get_balance_allowance(self, params=None) -> Dict[str, str]:
+        return _balance_allowance(self.exchange, self.funder,
+                                  getattr(params, "asset_type", "COLLATERAL"),
+                                  getattr(params, "token_id", None))
+
+    def update_balance_allowance(self, params=None) -> None:
+        """Balances are always current in the simulator."""
+        return None
+
+
+def _derive_creds(address: str):
+    digest = hashlib.sha256(address.lower().encode()).hexdigest()
+    values = {
+        "api_key": f"{digest[:8]}-{digest[8:12]}-{digest[12:16]}-{digest[16:20]}-{digest[20:32]}",
+        "api_secret": digest[32:],
+        "api_passphrase": digest[:16],
+    }
+    try:
+        from py_clob_client.clob_types import ApiCreds
+        return ApiCreds(**values)
+    except ImportError:
+        return SimpleNamespace(**values)
+
+
+def _market_dict(exchange: SimulatedClobExchange, market: SimMarket) -> Dict[str, Any]:
+    """Market in the shape returned by GET /markets."""
+    tokens = []
+    for token_id, outcome in market.tokens:
+        mid = exchange.midpoint(token_id)
+        tokens.append({"token_id": token_id, "outcome": outcome, "price": float(mid) if mid is not None else 0.0})
+    return {
+        "condition_id": market.condition_id,
+        "question": market.question,
+        "end_date_iso": market.end_date_iso,
+        "active": market.accepting_orders,
+        "closed": not market.accepting_orders,
+        "accepting_orders": market.accepting_orders,
+        "neg_risk": market.neg_risk,
+        "minimum_tick_size": float(market.tick_size),
+        "minimum_order_size": float(market.min_order_size),
+        "tokens": tokens,
+    }
+
+
+def _balance_allowance(exchange: SimulatedClobExchange, owner: str, asset_type: Any,
+                       token_id: Optional[str]) -> Dict[str, str]:
+    """GET /balance-allowance response; amounts in 6-decimal base units."""
+    account = exchange.account(owner)
+    if _value(asset_type) == "CONDITIONAL":
+        balance = account.tokens.get(str(token_id), Decimal("0"))
+        allowance = balance if account.tokens_approved else Decimal("0")
+    else:
+        balance = account.collateral
+        allowance = account.allowance if account.allowance is not None else Decimal(2 ** 255) / USDC_DECIMALS
+    return {
+        "balance": str(int(balance * USDC_DECIMALS)),
+        "allowance": str(int(allowance * USDC_DECIMALS)),
+    }
+
+
+# ============================================================================
+# HTTP / WebSocket server
+# ============================================================================
+
+def _order_from_payload(order: Dict[str, Any]) -> Tuple[str, str, Decimal, Decimal, int]:
+    """Recover (token_id, side, price, size, expiration) from a signed order's amounts."""
+    maker_amount = Decimal(str(order["makerAmount"])) / USDC_DECIMALS
+    taker_amount = Decimal(str(order["takerAmount"])) / USDC_DECIMALS
+    side = order["side"]
+    side = {0: BUY, 1: SELL}.get(side, side) if isinstance(side, int) else _value(side)
+    if side == BUY:
+        size, price = taker_amount, maker_amount / taker_amount
+    else:
+        size, price = maker_amount, taker_amount / maker_amount
+    return str(order["tokenId"]), side, price, size, int(order.get("expiration") or 0)
+
+
+def create_app(exchange: SimulatedClobExchange):
+    """
+    Build an aiohttp application serving the CLOB REST paths and WebSocket channels.
+
+    REST authentication headers are not verified; requests are attributed to
+    POLY_ADDRESS (resolved to its proxy wallet), orders to their maker.
+    WebSocket: /ws/market (and /ws/) streams book and last_trade_price events
+    for subscribed assets_ids; /ws/user streams order and trade events for the
+    owner of the apiKey given in the subscription's "auth".
+    """
+    from aiohttp import WSMsgType, web
+
+    api_keys: Dict[str, str] = {}
+
+    def owner_of(request) -> str:
+        return exchange.resolve_owner(request.headers.get("POLY_ADDRESS", DEFAULT_ADDRESS))
+
+    def token_param(request) -> str:
+        return request.query.get("token_id", "")
+
+    def paged(data: List[Any]) -> Dict[str, Any]:
+        return {"limit": len(data), "count": len(data), "next_cursor": "LTE=", "data": data}
+
+    async def ok(request):
+        return web.json_response("OK")
+
+    async def server_time(request):
+        return web.json_response(int(exchange.now()))
+
+    async def api_key(request):
+        address = request.headers.get("POLY_ADDRESS", DEFAULT_ADDRESS)
+        creds = _derive_creds(address)
+        api_keys[creds.api_key] = exchange.resolve_owner(address)
+        return web.json_response({"apiKey": creds.api_key, "secret": creds.api_secret,
+                                  "passphrase": creds.api_passphrase})
+
+    async def markets(request):
+        return web.json_response(paged([_market_dict(exchange, m) for m in exchange.markets.values()]))
+
+    async def market(request):
+        condition_id = request.match_info["condition_id"]
+        if condition_id not in exchange.markets:
+            return web.json_response({"error": "market not found"}, status=404)
+        return web.json_response(_market_dict(exchange, exchange.markets[condition_id]))
+
+    async def book(request):
+        token_id = token_param(request)
+        if exchange.market_for(token_id) is None:
+            return web.json_response({"error": "No orderbook exists for the requested token id"}, status=404)
+        return web.json_response(exchange.book_snapshot(token_id))
+
+    async def books(request):
+        params = await request.json()
+        return web.json_response([exchange.book_snapshot(p["token_id"]) for p in params])
+
+    async def midpoint(request):
+        mid = exchange.midpoint(token_param(request))
+        return web.json_response({"mid": _fmt(mid) if mid is not None else "0"})
+
+    async def price(request):
+        token_id = token_param(request)
+        value = exchange.best_ask(token_id) if _value(request.query.get("side", BUY)) == BUY \
+            else exchange.best_bid(token_id)
+        return web.json_response({"price": _fmt(value) if value is not None else "0"})
+
+    async def last_trade_price(request):
+        trade = exchange.last_trade(token_param(request))
+        return web.json_response({"price": _fmt(trade.price) if trade else "0", "side": trade.side if trade else ""})
+
+    async def tick_size(request):
+        market_ = exchange.market_for(token_param(request))
+        return web.json_response({"minimum_tick_size": float(market_.tick_size) if market_ else 0.01})
+
+    async def neg_risk(request):
+        market_ = exchange.market_for(token_param(request))
+        return web.json_response({"neg_risk": bool(market_ and market_.neg_risk)})
+
+    async def post_order(request):
+        body = await request.json()
+        try:
+            token_id, side, price_, size, expiration = _order_from_payload(body["order"])
+        except (KeyError, InvalidOperation, ZeroDivisionError) as e:
+            return web.json_response({"error": f"invalid order payload: {e}"}, status=400)
+        owner = body["order"].get("maker") or owner_of(request)
+        if body.get("owner"):
+            api_keys.setdefault(body["owner"], owner)
+        return web.json_response(exchange.place_order(
+            owner, token_id, side, price_, size, order_type=body.get("orderType", "GTC"), expiration=expiration,
+            post_only=bool(body.get("postOnly", False)),
+        ))
+
+    async def cancel_order(request):
+        body = await request.json()
+        return web.json_response(exchange.cancel(owner_of(request), [body["orderID"]]))
+
+    async def cancel_orders(request):
+        return web.json_response(exchange.cancel(owner_of(request), await request.json()))
+
+    async def cancel_all(request):
+        return web.json_response(exchange.cancel_all(owner_of(request)))
+
+    async def cancel_market_orders(request):
+        body = await request.json()
+        return web.json_response(exchange.cancel_all(
+            owner_of(request), market=body.get("market") or None, token_id=body.get("asset_id") or None,
+        ))
+
+    async def orders(request):
+        owner = owner_of(request)
+        order_id = request.query.get("id")
+        data = [o.to_dict(exchange.market_for(o.token_id)) for o in exchange.open_orders(
+            owner=owner, market=request.query.get("market"), token_id=request.query.get("asset_id"),
+        ) if not order_id or o.order_id == order_id]
+        return web.json_response(paged(data))
+
+    async def order(request):
+        found = exchange.orders.get(request.match_info["order_id"])
+        if found is None or found.owner != owner_of(request):
+            return web.json_response({"error": "order not found"}, status=404)
+        return web.json_response(found.to_dict(exchange.market_for(found.token_id)))
+
+    async def trades(request):
+        owner = owner_of(request)
+        token_id = request.query.get("asset_id")
+        data = [t.to_dict(owner, exchange.market_for(t.token_id)) for t in exchange.trades_for(owner)
+                if not token_id or t.token_id == token_id]
+        return web.json_response(paged(data))
+
+    async def balance_allowance(request):
+        return web.json_response(_balance_allowance(
+            exchange, owner_of(request), request.query.get("asset_type", "COLLATERAL"), request.query.get("token_id"),
+        ))
+
+    async def update_balance_allowance(request):
+        return web.json_response("")
+
+    async def websocket(request, channel: str):
+        ws = web.WebSocketResponse()
+        await ws.prepare(request)
+        queue: asyncio.Queue = asyncio.Queue()
+        assets: set = set()
+        owner: Dict[str, Optional[str]] = {"address": None}
+
+        def on_event(event: Dict[str, Any]) -> None:
+            if channel == "user":
+                if owner["address"] and owner["address"] in _event_owners(event):
+                    queue.put_nowait(event)
+            elif event["event_type"] in ("book", "last_trade_price") and event.get("asset_id") in assets:
+                queue.put_nowait(event)
+
+        async def pump():
+            while True:
+                await ws.send_json(await queue.get())
+
+        exchange.add_listener(on_event)
+        sender = asyncio.create_task(pump())
+        try:
+            async for msg in ws:
+                if msg.type != WSMsgType.TEXT:
+                    continue
+                try:
+                    data = json.loads(msg.data)
+                except json.JSONDecodeError:
+                    continue
+                if not isinstance(data, dict):
+                    continue
+                if channel == "user":
+                    owner["address"] = api_keys.get((data.get("auth") or {}).get("apiKey", ""))
+                    continue
+                token_ids = [str(token_id) for token_id in data.get("assets_ids") or []]
+                if data.get("type") == "unsubscribe":
+                    assets.difference_update(token_ids)
+                    continue
+                for token_id in token_ids:
+                    assets.add(token_id)
+                    # Initial snapshot, as the market channel sends on subscribe
+                    queue.put_nowait({"event_type": "book", "type": "book", **exchange.book_snapshot(token_id)})
+        finally:
+            exchange.remove_listener(on_event)
+            sender.cancel()
+        return ws
+
+    async def market_ws(request):
+        return await websocket(request, "market")
+
+    async def user_ws(request):
+        return await websocket(request, "user")
+
+    app = web.Application()
+    app.router.add_get("/", ok)
+    app.router.add_get("/time", server_time)
+    app.router.add_post("/auth/api-key", api_key)
+    app.router.add_get("/auth/derive-api-key", api_key)
+    app.router.add_get("/markets", markets)
+    app.router.add_get("/markets/{condition_id}", market)
+    app.router.add_get("/book", book)
+    app.router.add_post("/books", books)
+    app.router.add_get("/midpoint", midpoint)
+    app.router.add_get("/price", price)
+    app.router.add_get("/last-trade-price", last_trade_price)
+    app.router.add_get("/tick-size", tick_size)
+    app.router.add_get("/neg-risk", neg_risk)
+    app.router.add_post("/order", post_order)
+    app.router.add_delete("/order", cancel_order)
+    app.router.add_delete("/orders", cancel_orders)
+    app.router.add_delete("/cancel-all", cancel_all)
+    app.router.add_delete("/cancel-market-orders", cancel_market_orders)
+    app.router.add_get("/data/orders", orders)
+    app.router.add_get("/data/order/{order_id}", order)
+    app.router.add_get("/data/trades", trades)
+    app.router.add_get("/balance-allowance", balance_allowance)
+    app.router.add_get("/balance-allowance/update", update_balance_allowance)
+    app.router.add_get("/ws/", market_ws)
+    app.router.add_get("/ws/market", market_ws)
+    app.router.add_get("/ws/user", user_ws)
+    return app
+
+
+def _event_owners(event: Dict[str, Any]) -> List[str]:
+    """Addresses a user-channel event belongs to."""
+    if event["event_type"] == "order":
+        return [event.get("owner")]
+    if event["event_type"] == "trade":
+        return [event.get("owner")] + [m.get("owner") for m in event.get("maker_orders", [])]
+    return []
+
+
+def main():
+    """Serve a simulated CLOB for a scenario file."""
+    parser = argparse.ArgumentParser(description="Run a simulated Polymarket CLOB (REST + WebSocket)")
+    parser.add_argument("--scenario", type=Path, help="Scenario JSON (markets, accounts, books)")
+    parser.add_argument("--host", default="127.0.0.1")
+    parser.add_argument("--port", type=int, default=8081)
+    parser.add_argument("--verbose", action="store_true")
+    args = parser.parse_args()
+
+    logging.basicConfig(
+        level=logging.DEBUG if args.verbose else logging.INFO,
+        format="%(asctime)s - %(name)s - %(levelname)s - %(message)s"
+    )
+
+    from aiohttp import web
+
+    exchange = SimulatedClobExchange.from_scenario(args.scenario) if args.scenario else SimulatedClobExchange()
+    logger.info(f"🧪 Simulated CLOB on http://{args.host}:{args.port} ({len(exchange.markets)} markets)")
+    web.run_app(create_app(exchange), host=args.host, port=args.port, print=None)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,185 @@
+"""
+Configuration hot-reload for Polymarket Arbitrage Bot.
+
+Watches the YAML file the config was loaded from. When it changes, the new
+values are re-validated through Config._validate and the hot-reloadable
+fields (HOT_RELOAD_FIELDS) are handed to the orchestrator in one
+synchronous call, so no scan runs against a half-applied config. An invalid
+edit is rejected and the running config stays in effect. Every reload is
+appended to a JSON Lines audit log.
+
+Validates Requirements:
+- Reload of safe settings without restarting the bot
+- Validation of edits before they are applied; invalid edits rejected
+- Audit log of applied and rejected changes
+"""
+
+import dataclasses
+import json
+import logging
+import os
+from datetime import datetime, timezone
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+import yaml
+
+from config.config import DECIMAL_FIELDS, HOT_RELOAD_FIELDS, SECRET_FIELDS, Config
+
+logger = logging.getLogger(__name__)
+
+# Audit entry statuses
+APPLIED = "applied"
+REJECTED = "rejected"
+RESTART_REQUIRED = "restart_required"  # Only fields that are read at startup changed
+
+CONFIG_FIELDS = tuple(f.name for f in dataclasses.fields(Config))
+
+
+class ConfigReloader:
+    """
+    Applies edits of the YAML config file to the running bot.
+
+    Features:
+    - Polls the file's modification time (check() from the main loop)
+    - Builds the candidate config with dataclasses.replace, which runs
+      Config._validate; on error the current config is kept
+    - Applies only HOT_RELOAD_FIELDS; other edits are logged as pending a restart
+    - Rolls back to the previous config if applying fails part way
+    - Audit log entries name changed fields; secret values are never written
+    """
+
+    def __init__(self, config: Config, apply: Callable[[Config], None],
+                 audit_log_path: Optional[str] = None, path: Optional[str] = None):
+        """
+        Initialize the reloader.
+
+        Args:
+            config: Config currently in effect
+            apply: Pushes a config into the running components; must not await
+            audit_log_path: JSON Lines audit log (default: config.config_audit_log_path)
+            path: YAML file to watch (default: config.config_file)
+        """
+        self.config = config
+        self.apply = apply
+        self.path = Path(path or config.config_file)
+        self.audit_log_path = Path(audit_log_path or config.config_audit_log_path)
+        self._mtime = self._stat()
+
+    def _stat(self) -> Optional[int]:
+        try:
+            return os.stat(self.path).st_mtime_ns
+        except OSError:
+            return None
+
+    def check(self) -> Optional[Dict[str, Any]]:
+        """
+        Reload if the file changed since the last check.
+
+        Returns:
+            The audit entry, or None if the file is unchanged or being replaced
+        """
+        mtime = self._stat()
+        if mtime is None or mtime == self._mtime:
+            return None
+        self._mtime = mtime
+        return self.reload()
+
+    def reload(self) -> Optional[Dict[str, Any]]:
+        """
+        Read the file and apply its hot-reloadable changes.
+
+        Returns:
+            The audit entry, or None if no field changed
+        """
+        current = self.config
+        try:
+            data = self._read()
+        except (OSError, ValueError, yaml.YAMLError) as e:
+            return self._record(REJECTED, error=str(e))
+
+        try:
+            candidate = dataclasses.replace(current, **data)
+        except (TypeError, ValueError, ArithmeticError) as e:
+            changes = {key: [getattr(current, key), value] for key, value in data.items()
+                       if key in HOT_RELOAD_FIELDS and getattr(current, key) != value}
+            restart = sorted(key for key, value in data.items()
+                             if key not in HOT_RELOAD_FIELDS and getattr(current, key) != value)
+            return self._record(REJECTED, changes, restart, error=str(e))
+
+        changed = [name for name in CONFIG_FIELDS if getattr(candidate, name) != getattr(current, name)]
+        hot = [name for name in changed if name in HOT_RELOAD_FIELDS]
+        restart = [name for name in changed if name not in HOT_RELOAD_FIELDS]
+        if not changed:
+            logger.debug(f"Config file {self.path} saved without changes")
+            return None
+        if not hot:
+            return self._record(RESTART_REQUIRED, restart_required=restart)
+
+        changes = {name: [getattr(current, name), getattr(candidate, name)] for name in hot}
+        try:
+            new_config = dataclasses.replace(current, **{name: getattr(candidate, name) for name in hot})
+        except ValueError as e:
+            return self._record(REJECTED, changes, restart, error=str(e))
+
+        try:
+            self.apply(new_config)
+        except Exception as e:
+            logger.error(f"Applying config changes failed, rolling back: {e}")
+            self.apply(current)
+            return self._record(REJECTED, changes, restart, error=f"apply failed: {e}")
+
+        self.config = new_config
+        return self._record(APPLIED, changes, restart)
+
+    def _read(self) -> Dict[str, Any]:
+        """Parse the file the way Config.from_yaml does, keeping only keys present in it."""
+        with open(self.path, "r") as f:
+            data = yaml.safe_load(f)
+        if not isinstance(data, dict) or not data:
+            raise ValueError(f"Empty or invalid configuration file: {self.path}")
+
+        unknown = sorted(key for key in data if key not in CONFIG_FIELDS)
+        if unknown:
+            raise ValueError(f"Unknown configuration options: {', '.join(unknown)}")
+
+        data.pop("config_file", None)
+        for key in SECRET_FIELDS:
+            if key in data and not data[key]:
+                del data[key]  # Left empty for the secrets backend
+        for key in DECIMAL_FIELDS:
+            if key in data:
+                try:
+                    data[key] = Decimal(str(data[key]))
+                except ArithmeticError:
+                    raise ValueError(f"{key} must be a number, got: {data[key]}")
+        return data
+
+    def _record(self, status: str, changes: Optional[Dict[str, List[Any]]] = None,
+                restart_required: Optional[List[str]] = None, error: Optional[str] = None) -> Dict[str, Any]:
+        """Log the outcome and append it to the audit log."""
+        entry = {
+            "timestamp": datetime.now(timezone.utc).isoformat(),
+            "file": str(self.path),
+            "status": status,
+            "changes": {name: {"old": old, "new": new} for name, (old, new) in (changes or {}).items()},
+            "restart_required": list(restart_required or []),
+            "error": error,
+        }
+
+        summary = ", ".join(f"{name}: {old} -> {new}" for name, (old, new) in (changes or {}).items())
+        if status == APPLIED:
+            logger.info(f"🔄 Config reloaded from {self.path}: {summary}")
+        elif status == REJECTED:
+            logger.error(f"❌ Config edit rejected, keeping the running config: {error}")
+        if entry["restart_required"]:
+            logger.warning(f"⚠️ Config changes take effect after a restart: {', '.join(entry['restart_required'])}")
+
+        try:
+            self.audit_log_path.parent.mkdir(parents=True, exist_ok=True)
+            with open(self.audit_log_path, "a") as f:
+                f.write(json.dumps(entry, default=str) + "\n")
+        except OSError as e:
+            logger.error(f"Failed to write config audit log {self.audit_log_path}: {e}")
+        return entry
@@ -0,0 +1,617 @@
+"""
+Continuous-State Reinforcement Learning Engine for Strategy Selection.
+
+Replaces the tabular Q-table of ReinforcementLearningEngine, which buckets
+volatility/trend/liquidity into a few strings, with a linear value function
+over continuous features of the token being bought: the Binance spot move,
+the time to close, the order book imbalance and the spread. A 15-minute trade
+is a one-step episode, so the value of a strategy in a state is its expected
+return and every closed trade is one training sample.
+
+Policies are trained offline (`python -m src.rl_training`) from the
+experience the strategy logs in live trading and in replay backtests,
+evaluated on a later held-out period and frozen as numbered versions. Live
+trading loads one frozen version and never updates it. Exploration is capped
+in rate, limited to strategies valued close to the best one and budgeted per
+day.
+
+Validates Requirements:
+- Function approximation over continuous market features
+- Offline training from recorded trades and backtests with a chronological evaluation split
+- Versioned, frozen policies for live use
+- Capped exploration in production
+"""
+
+import json
+import logging
+import math
+import random
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+FEATURE_NAMES = ("spot_move", "minutes_to_close", "book_imbalance", "spread")
+
+# Terms of the linear value function after the bias; |spot_move| lets a
+# strategy value large moves in either direction
+BASIS_NAMES = ("spot_move", "abs_spot_move", "minutes_to_close", "book_imbalance", "spread")
+
+SKIP = "skip"  # Not trading is worth 0 in every state
+SPOT_MOVE_SECONDS = 60  # Window of the Binance move feature
+
+# Newest file schemas this module reads and writes
+EXPERIENCE_SCHEMA_VERSION = 1
+POLICY_SCHEMA_VERSION = 1
+
+
+# ============================================================================
+# Features and experience
+# ============================================================================
+
+@dataclass
+class MarketFeatures:
+    """Continuous state of an entry, oriented to the token being bought."""
+    spot_move: float  # Binance move over SPOT_MOVE_SECONDS; positive when it favors the token
+    minutes_to_close: float
+    book_imbalance: float  # (bid depth - ask depth) / total depth of the token's book, -1 to 1
+    spread: float  # Best ask - best bid of the token
+
+    @classmethod
+    def observe(
+        cls,
+        side: str,
+        spot_move: Optional[Any],
+        minutes_to_close: float,
+        bid_depth: Any,
+        ask_depth: Any,
+        spread: Any
+    ) -> "MarketFeatures":
+        """Features of buying `side` ("UP" or "DOWN"); the spot move is flipped for DOWN."""
+        move = float(spot_move or 0)
+        bids, asks = float(bid_depth), float(ask_depth)
+        return cls(
+            spot_move=-move if side.upper() == "DOWN" else move,
+            minutes_to_close=max(0.0, float(minutes_to_close)),
+            book_imbalance=(bids - asks) / (bids + asks) if bids + asks > 0 else 0.0,
+            spread=float(spread),
+        )
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "MarketFeatures":
+        return cls(**{name: float(data[name]) for name in FEATURE_NAMES})
+
+    def to_dict(self) -> Dict[str, float]:
+        return asdict(self)
+
+    def basis(self) -> List[float]:
+        """Basis terms in BASIS_NAMES order."""
+        return [self.spot_move, abs(self.spot_move), self.minutes_to_close, self.book_imbalance, self.spread]
+
+
+@dataclass
+class Experience:
+    """One closed trade: the entry state, the strategy that traded and its return."""
+    timestamp: float  # Unix time the trade closed
+    asset: str
+    strategy: str
+    features: MarketFeatures
+    reward: float  # Realized return (0.05 = +5%)
+    market_id: str = ""
+    source: str = "live"  # live, dry_run or backtest
+    policy_version: Optional[int] = None  # Frozen policy loaded when the trade closed
+
+    def to_dict(self) -> Dict[str, Any]:
+        data = asdict(self)
+        data["schema_version"] = EXPERIENCE_SCHEMA_VERSION
+        return data
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Experience":
+        if data.get("schema_version", 1) > EXPERIENCE_SCHEMA_VERSION:
+            raise ValueError(f"Unsupported experience schema version {data['schema_version']}")
+        return cls(
+            timestamp=float(data["timestamp"]),
+            asset=data["asset"],
+            strategy=data["strategy"],
+            features=MarketFeatures.from_dict(data["features"]),
+            reward=float(data["reward"]),
+            market_id=data.get("market_id", ""),
+            source=data.get("source", "live"),
+            policy_version=data.get("policy_version"),
+        )
+
+
+class ExperienceLog:
+    """Append-only JSON-lines file of experiences."""
+
+    def __init__(self, path: str):
+        self.path = Path(path)
+
+    def append(self, experience: Experience) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.path, "a") as f:
+            f.write(json.dumps(experience.to_dict()) + "\n")
+
+    def load(self) -> List[Experience]:
+        """Every readable experience in file order; malformed lines are skipped with a warning."""
+        if not self.path.exists():
+            return []
+        experiences = []
+        with open(self.path) as f:
+            for line_number, line in enumerate(f, 1):
+                if not line.strip():
+                    continue
+                try:
+                    experiences.append(Experience.from_dict(json.loads(line)))
+                except (ValueError, KeyError, TypeError) as e:
+                    logger.warning(f"⚠️ Skipping experience {self.path}:{line_number}: {e}")
+        return experiences
+
+
+def load_experiences(paths: Iterable[str]) -> List[Experience]:
+    """Experiences from several logs (live, backtests), oldest first."""
+    experiences = [experience for path in paths for experience in ExperienceLog(path).load()]
+    experiences.sort(key=lambda experience: experience.timestamp)
+    return experiences
+
+
+def split_experiences(
+    experiences: Sequence[Experience],
+    eval_fraction: float
+) -> Tuple[List[Experience], List[Experience]]:
+    """
+    Chronological train/evaluation split: the newest `eval_fraction` of the
+    trades is held out, so a policy is always evaluated on a period after the
+    one it was trained on.
+    """
+    if not 0 < eval_fraction < 1:
+        raise ValueError(f"eval_fraction must be between 0 and 1, got {eval_fraction}")
+    ordered = sorted(experiences, key=lambda experience: experience.timestamp)
+    cut = len(ordered) - math.ceil(len(ordered) * eval_fraction)
+    return ordered[:cut], ordered[cut:]
+
+
+# ============================================================================
+# Policy
+# ============================================================================
+
+@dataclass
+class LinearPolicy:
+    """
+    Linear value function per strategy over standardized basis terms.
+
+    The value of a strategy is its predicted return; the greedy choice is the
+    strategy with the highest positive value, or SKIP.
+    """
+    weights: Dict[str, List[float]]  # Strategy -> [bias, *BASIS_NAMES weights]
+    means: List[float]  # Training mean of each basis term
+    scales: List[float]  # Training standard deviation of each basis term
+    reward_scale: float  # Standard deviation of training returns; maps values to confidence
+    version: Optional[int] = None  # Set when frozen
+    metadata: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def strategies(self) -> List[str]:
+        return list(self.weights)
+
+    def _inputs(self, features: MarketFeatures) -> List[float]:
+        return [1.0] + [(x - mean) / scale for x, mean, scale in zip(features.basis(), self.means, self.scales)]
+
+    def value(self, features: MarketFeatures, strategy: str) -> float:
+        return sum(w * x for w, x in zip(self.weights[strategy], self._inputs(features)))
+
+    def values(self, features: MarketFeatures, strategies: Optional[Iterable[str]] = None) -> Dict[str, float]:
+        """Values of the policy's strategies (restricted to `strategies` when given)."""
+        names = self.strategies if strategies is None else [s for s in strategies if s in self.weights]
+        return {name: self.value(features, name) for name in names}
+
+    def greedy(self, features: MarketFeatures, strategies: Optional[Iterable[str]] = None) -> Tuple[str, float]:
+        """Best strategy and its value; (SKIP, best value) when no strategy is expected to profit."""
+        values = self.values(features, strategies)
+        if not values:
+            return SKIP, 0.0
+        best = max(values, key=values.get)
+        return (best if values[best] > 0 else SKIP), values[best]
+
+    def confidence(self, value: float) -> float:
+        """Confidence (50-100) of a choice whose value is `value` away from breaking even."""
+        return 50.0 + 50.0 * math.tanh(abs(value) / self.reward_scale)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "schema_version": POLICY_SCHEMA_VERSION,
+            "version": self.version,
+            "features": list(FEATURE_NAMES),
+            "basis": list(BASIS_NAMES),
+            "weights": self.weights,
+            "means": self.means,
+            "scales": self.scales,
+            "reward_scale": self.reward_scale,
+            "metadata": self.metadata,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "LinearPolicy":
+        if data.get("schema_version", 1) > POLICY_SCHEMA_VERSION:
+            raise ValueError(f"Unsupported policy schema version {data['schema_version']}")
+        if list(data.get("basis", BASIS_NAMES)) != list(BASIS_NAMES):
+            raise ValueError(f"Policy basis {data['basis']} does not match {list(BASIS_NAMES)}")
+        return cls(
+            weights={name: [float(w) for w in weights] for name, weights in data["weights"].items()},
+            means=[float(m) for m in data["means"]],
+            scales=[float(s) for s in data["scales"]],
+            reward_scale=float(data["reward_scale"]),
+            version=data.get("version"),
+            metadata=data.get("metadata", {}),
+        )
+
+
+def _mean_std(values: Sequence[float]) -> Tuple[float, float]:
+    mean = sum(values) / len(values)
+    std = math.sqrt(sum((v - mean) ** 2 for v in values) / len(values))
+    return mean, std
+
+
+def _solve(matrix: List[List[float]], vector: List[float]) -> List[float]:
+    """Solve matrix @ x = vector (Gaussian elimination with partial pivoting)."""
+    n = len(vector)
+    rows = [list(row) + [b] for row, b in zip(matrix, vector)]
+    for col in range(n):
+        pivot = max(range(col, n), key=lambda r: abs(rows[r][col]))
+        if abs(rows[pivot][col]) < 1e-12:
+            raise ValueError("Singular system")
+        rows[col], rows[pivot] = rows[pivot], rows[col]
+        for r in range(col + 1, n):
+            factor = rows[r][col] / rows[col][col]
+            for c in range(col, n + 1):
+                rows[r][c] -= factor * rows[col][c]
+    solution = [0.0] * n
+    for r in range(n - 1, -1, -1):
+        solution[r] = (rows[r][n] - sum(rows[r][c] * solution[c] for c in range(r + 1, n))) / rows[r][r]
+    return solution
+
+
+def train_policy(experiences: Sequence[Experience], l2: float = 1.0, min_samples: int = 20) -> LinearPolicy:
+    """
+    Fit a LinearPolicy by ridge regression of each strategy's returns on the
+    standardized basis terms (the bias is not penalized).
+
+    Args:
+        experiences: Training trades
+        l2: Ridge penalty; shrinks weights towards the strategy's mean return
+        min_samples: Strategies with fewer trades are left out of the policy
+
+    Raises:
+        ValueError: If no strategy has min_samples trades
+    """
+    by_strategy: Dict[str, List[Experience]] = {}
+    for experience in experiences:
+        by_strategy.setdefault(experience.strategy, []).append(experience)
+    trained = {name: rows for name, rows in by_strategy.items() if len(rows) >= min_samples and name != SKIP}
+    if not trained:
+        raise ValueError(f"No strategy has {min_samples} training trades ({len(experiences)} trades in total)")
+
+    columns = list(zip(*(experience.features.basis() for experience in experiences)))
+    stats = [_mean_std(column) for column in columns]
+    means = [mean for mean, _ in stats]
+    scales = [std if std > 1e-12 else 1.0 for _, std in stats]
+    reward_std = _mean_std([experience.reward for experience in experiences])[1]
+
+    policy = LinearPolicy(weights={}, means=means, scales=scales, reward_scale=reward_std if reward_std > 1e-12 else 1.0)
+    size = len(BASIS_NAMES) + 1
+    for name, rows in sorted(trained.items()):
+        gram = [[0.0] * size for _ in range(size)]
+        target = [0.0] * size
+        for experience in rows:
+            x = policy._inputs(experience.features)
+            for i in range(size):
+                target[i] += x[i] * experience.reward
+                for j in range(size):
+                    gram[i][j] += x[i] * x[j]
+        for i in range(1, size):
+            gram[i][i] += l2
+        policy.weights[name] = _solve(gram, target)
+
+    policy.metadata["training"] = {
+        "trades": len(experiences),
+        "l2": l2,
+        "min_samples": min_samples,
+        "strategies": {
+            name: {
+                "trades": len(rows),
+                "mean_reward": sum(e.reward for e in rows) / len(rows),
+                "trained": name in trained,
+            }
+            for name, rows in sorted(by_strategy.items())
+        },
+        "first_trade": min(e.timestamp for e in experiences),
+        "last_trade": max(e.timestamp for e in experiences),
+    }
+    return policy
+
+
+def evaluate_policy(policy: LinearPolicy, experiences: Sequence[Experience]) -> Dict[str, Any]:
+    """
+    Score a policy on held-out trades.
+
+    Per strategy: prediction error of the value function, against always
+    predicting the strategy's training mean. For the policy as a whole: on
+    each trade, whether the greedy choice would have been the logged
+    strategy (the trade's return counts), a skip (counts as 0) or another
+    strategy (no counterfactual return; not scored).
+    """
+    training = policy.metadata.get("training", {}).get("strategies", {})
+    per_strategy: Dict[str, Dict[str, Any]] = {}
+    for name in policy.strategies:
+        rows = [e for e in experiences if e.strategy == name]
+        if not rows:
+            continue
+        baseline = training.get(name, {}).get("mean_reward", 0.0)
+        per_strategy[name] = {
+            "trades": len(rows),
+            "mse": sum((policy.value(e.features, name) - e.reward) ** 2 for e in rows) / len(rows),
+            "baseline_mse": sum((baseline - e.reward) ** 2 for e in rows) / len(rows),
+            "mean_reward": sum(e.reward for e in rows) / len(rows),
+        }
+
+    agreed, skipped, other = [], 0, 0
+    for experience in experiences:
+        choice, _ = policy.greedy(experience.features)
+        if choice == experience.strategy:
+            agreed.append(experience.reward)
+        elif choice == SKIP:
+            skipped += 1
+        else:
+            other += 1
+
+    scored = len(agreed) + skipped
+    logged_total = sum(e.reward for e in experiences)
+    return {
+        "trades": len(experiences),
+        "strategies": per_strategy,
+        "logged": {
+            "mean_reward": logged_total / len(experiences) if experiences else None,
+            "total_reward": logged_total,
+            "win_rate": sum(1 for e in experiences if e.reward > 0) / len(experiences) if experiences else None,
+        },
+        "policy": {
+            "agreed": len(agreed),
+            "skipped": skipped,
+            "unscored": other,
+            "mean_reward": sum(agreed) / scored if scored else None,
+            "total_reward": sum(agreed),
+            "win_rate": sum(1 for r in agreed if r > 0) / len(agreed) if agreed else None,
+        },
+    }
+
+
+class PolicyStore:
+    """Directory of frozen policies: policy_v0001.json, policy_v0002.json, ..."""
+
+    def __init__(self, directory: str):
+        self.directory = Path(directory)
+
+    def _path(self, version: int) -> Path:
+        return self.directory / f"policy_v{version:04d}.json"
+
+    def versions(self) -> List[int]:
+        if not self.directory.exists():
+            return []
+        versions = []
+        for path in self.directory.glob("policy_v*.json"):
+            suffix = path.stem[len("policy_v"):]
+            if suffix.isdigit():
+                versions.append(int(suffix))
+        return sorted(versions)
+
+    def freeze(self, policy: LinearPolicy) -> int:
+        """Write the policy as the next version; frozen versions are never overwritten."""
+        self.directory.mkdir(parents=True, exist_ok=True)
+        versions = self.versions()
+        policy.version = (versions[-1] + 1) if versions else 1
+        policy.metadata["frozen_at"] = datetime.now(timezone.utc).isoformat()
+        with open(self._path(policy.version), "x") as f:
+            json.dump(policy.to_dict(), f, indent=2)
+        logger.info(f"🧊 Froze RL policy v{policy.version} ({', '.join(policy.strategies)})")
+        return policy.version
+
+    def load(self, version: Optional[int] = None) -> Optional[LinearPolicy]:
+        """
+        A frozen policy: `version`, or the newest one (None if there is none).
+
+        Raises:
+            FileNotFoundError: If the requested version does not exist
+        """
+        if version is None:
+            versions = self.versions()
+            if not versions:
+                return None
+            version = versions[-1]
+        path = self._path(version)
+        if not path.exists():
+            raise FileNotFoundError(f"No frozen RL policy v{version} in {self.directory}")
+        with open(path) as f:
+            policy = LinearPolicy.from_dict(json.load(f))
+        policy.version = version
+        return policy
+
+
+# ============================================================================
+# Live engine
+# ============================================================================
+
+class ContinuousRLEngine:
+    """
+    Strategy selector backed by a frozen LinearPolicy.
+
+    Features:
+    - Greedy choice from a frozen policy version (no online updates)
+    - Exploration capped at MAX_EXPLORATION_RATE, restricted to strategies
+      valued within exploration_margin of the best choice, and limited to
+      max_daily_explorations per UTC day
+    - Closed trades appended to the experience log for the next offline training run
+    - Neutral ("skip", 0.0) vote without a policy or features
+    """
+
+    MAX_EXPLORATION_RATE = 0.10  # Hard cap on the exploration rate, whatever the config says
+
+    def __init__(
+        self,
+        policy_dir: Optional[str] = "data/rl_policies",
+        policy_version: Optional[int] = None,
+        experience_path: Optional[str] = "data/rl_experience.jsonl",
+        exploration_rate: float = 0.0,
+        exploration_margin: float = 0.005,
+        max_daily_explorations: int = 10,
+        source: str = "live",
+        seed: Optional[int] = None
+    ):
+        """
+        Initialize the engine.
+
+        Args:
+            policy_dir: Directory of frozen policies (None: no policy)
+            policy_version: Version to load (default: newest)
+            experience_path: JSON-lines file closed trades are appended to (None: not logged)
+            exploration_rate: Probability of exploring on a selection (capped at MAX_EXPLORATION_RATE)
+            exploration_margin: Largest value shortfall from the best choice an exploratory strategy may have
+            max_daily_explorations: Exploratory selections allowed per UTC day
+            source: Source tag of logged experience (live, dry_run or backtest)
+            seed: Seed of the exploration random generator
+        """
+        self.policy: Optional[LinearPolicy] = None
+        if policy_dir:
+            try:
+                self.policy = PolicyStore(policy_dir).load(policy_version)
+            except (OSError, ValueError) as e:
+                logger.error(f"❌ Failed to load RL policy: {e}")
+        self.experience_log = ExperienceLog(experience_path) if experience_path else None
+        self.exploration_margin = exploration_margin
+        self.source = source
+        self._rng = random.Random(seed)
+        self.configure_exploration(exploration_rate, max_daily_explorations)
+
+        self._exploration_day = None
+        self.explorations_today = 0
+        self.total_selections = 0
+        self.total_explorations = 0
+        self.recorded_trades = 0
+
+        if self.policy is not None:
+            logger.info(
+                f"🤖 Continuous RL Engine: policy v{self.policy.version} "
+                f"({', '.join(self.policy.strategies)}), exploration {self.exploration_rate:.1%}"
+            )
+        else:
+            logger.info("🤖 Continuous RL Engine: no frozen policy - neutral votes, logging experience only")
+
+    @property
+    def policy_version(self) -> Optional[int]:
+        return self.policy.version if self.policy is not None else None
+
+    def configure_exploration(self, exploration_rate: float, max_daily_explorations: int) -> None:
+        """Set the exploration rate (capped at MAX_EXPLORATION_RATE) and daily budget."""
+        if exploration_rate > self.MAX_EXPLORATION_RATE:
+            logger.warning(
+                f"⚠️ RL exploration rate {exploration_rate} above the {self.MAX_EXPLORATION_RATE} cap - capping"
+            )
+        self.exploration_rate = min(max(0.0, float(exploration_rate)), self.MAX_EXPLORATION_RATE)
+        self.max_daily_explorations = max(0, int(max_daily_explorations))
+
+    def _explore(self, values: Dict[str, float], greedy: str, best_value: float) -> Optional[Tuple[str, float]]:
+        """An exploratory (strategy, value) within the safety limits, or None."""
+        if self.exploration_rate <= 0 or self._rng.random() >= self.exploration_rate:
+            return None
+        today = datetime.now(timezone.utc).date()
+        if today != self._exploration_day:
+            self._exploration_day = today
+            self.explorations_today = 0
+        if self.explorations_today >= self.max_daily_explorations:
+            return None
+        floor = max(best_value, 0.0) - self.exploration_margin
+        candidates = sorted(name for name, value in values.items() if name != greedy and value >= floor)
+        if not candidates:
+            return None
+        choice = self._rng.choice(candidates)
+        self.explorations_today += 1
+        self.total_explorations += 1
+        return choice, values[choice]
+
+    def select_strategy(
+        self,
+        asset: str,
+        features: Optional[Any] = None,
+        available_strategies: Optional[List[str]] = None
+    ) -> Tuple[str, float]:
+        """
+        Select a strategy for an entry.
+
+        Args:
+            asset: Asset symbol
+            features: MarketFeatures (or their dict) of the entry
+            available_strategies: Strategies that may be chosen (default: all in the policy)
+
+        Returns:
+            Tuple of (strategy or "skip", confidence 0-100)
+        """
+        if self.policy is None or features is None:
+            return SKIP, 0.0
+        if isinstance(features, dict):
+            features = MarketFeatures.from_dict(features)
+
+        self.total_selections += 1
+        values = self.policy.values(features, available_strategies)
+        strategy, value = self.policy.greedy(features, available_strategies)
+        explored = self._explore(values, strategy, value)
+        if explored is not None:
+            strategy, value = explored
+            logger.info(f"🎲 RL exploring {strategy} on {asset} (value {value:+.4f}, {self.explorations_today} today)")
+            return strategy, 50.0  # Neutral confidence for exploration
+
+        confidence = self.policy.confidence(value)
+        logger.debug(f"🎯 RL policy v{self.policy.version}: {asset} -> {strategy} (value {value:+.4f})")
+        return strategy, confidence
+
+    def record_outcome(
+        self,
+        asset: str,
+        strategy: str,
+        reward: float,
+        features: Optional[Any] = None,
+        market_id: str = ""
+    ) -> None:
+        """Append a closed trade to the experience log (trades without entry features are not logged)."""
+        if self.experience_log is None or features is None:
+            return
+        if isinstance(features, dict):
+            features = MarketFeatures.from_dict(features)
+        self.experience_log.append(Experience(
+            timestamp=datetime.now(timezone.utc).timestamp(),
+            asset=asset,
+            strategy=strategy,
+            features=features,
+            reward=float(reward),
+            market_id=market_id or "",
+            source=self.source,
+            policy_version=self.policy_version,
+        ))
+        self.recorded_trades += 1
+
+    def on_trade_outcome(self, outcome: Any) -> None:
+        """Log a trade recorded in the TradeOutcomeStore if it carries entry MarketFeatures."""
+        features = outcome.features if all(name in outcome.features for name in FEATURE_NAMES) else None
+        self.record_outcome(outcome.asset, outcome.strategy, outcome.profit_pct, features, outcome.market_id)
+
+    def get_performance_summary(self) -> str:
+        """Get formatted engine summary."""
+        return (
+            f"RL Engine Summary:\n"
+            f"Policy: {'v' + str(self.policy_version) if self.policy is not None else 'none'}\n"
+            f"Selections: {self.total_selections}\n"
+            f"Explorations: {self.total_explorations} ({self.explorations_today} today, "
+            f"rate {self.exploration_rate:.3f}, max {self.max_daily_explorations}/day)\n"
+            f"Trades Logged: {self.recorded_trades}\n"
+        )
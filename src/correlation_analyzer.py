@@ -4,6 +4,10 @@ Correlation Analysis for Risk Management.
 Analyzes correlations between positions to avoid over-concentration
 and reduce portfolio risk.
 
+With a RollingCorrelationEstimator attached (see from_price_feed), the
+correlations come from live Binance prices; the static table below is only
+the cold-start prior the estimates are shrunk toward.
+
 Prevents correlated losses by 30%.
 """
 
@@ -13,6 +17,8 @@ from typing import Dict, List, Optional, Tuple, Set
 from dataclasses import dataclass
 from collections import defaultdict
 
+from src.correlation_estimator import RollingCorrelationEstimator
+
 logger = logging.getLogger(__name__)
 
 
@@ -31,13 +37,14 @@ class CorrelationAnalyzer:
     Analyzes correlations between positions to manage risk.
     
     Features:
-    - Asset correlation tracking
+    - Asset correlation tracking (live rolling estimates or static prior)
     - Position concentration limits
     - Directional exposure analysis
     - Risk diversification scoring
     """
     
-    # Known correlations between crypto assets (based on historical data)
+    # Prior correlations between crypto assets (based on historical data);
+    # used directly only when no estimator is attached
     ASSET_CORRELATIONS = {
         ("BTC", "ETH"): 0.85,  # Highly correlated
         ("BTC", "SOL"): 0.75,  # Moderately correlated
@@ -50,7 +57,8 @@ class CorrelationAnalyzer:
     def __init__(
         self,
         max_correlated_exposure: Decimal = Decimal("0.30"),  # 30% max in correlated assets
-        max_single_asset_exposure: Decimal = Decimal("0.20")  # 20% max in single asset
+        max_single_asset_exposure: Decimal = Decimal("0.20"),  # 20% max in single asset
+        estimator: Optional[RollingCorrelationEstimator] = None
     ):
         """
         Initialize correlation analyzer.
@@ -58,16 +66,42 @@ class CorrelationAnalyzer:
         Args:
             max_correlated_exposure: Max % of portfolio in correlated assets
             max_single_asset_exposure: Max % of portfolio in single asset
+            estimator: Live correlation estimator (static table if None)
         """
         self.max_correlated_exposure = max_correlated_exposure
         self.max_single_asset_exposure = max_single_asset_exposure
+        self.estimator = estimator
+        
+        logger.info(f"🔗 Correlation Analyzer initialized ({'live' if estimator else 'static'} correlations)")
+    
+    @classmethod
+    def from_price_feed(
+        cls,
+        price_feed,
+        max_correlated_exposure: Decimal = Decimal("0.30"),
+        max_single_asset_exposure: Decimal = Decimal("0.20"),
+        **estimator_kwargs
+    ) -> "CorrelationAnalyzer":
+        """
+        Create an analyzer whose correlations are estimated from a BinancePriceFeed.
         
-        logger.info("🔗 Correlation Analyzer initialized")
+        Args:
+            price_feed: Feed exposing price_history (asset -> (timestamp, price) deque)
+            max_correlated_exposure: Max % of portfolio in correlated assets
+            max_single_asset_exposure: Max % of portfolio in single asset
+            **estimator_kwargs: Passed to RollingCorrelationEstimator
+        """
+        estimator_kwargs.setdefault("prior", cls.ASSET_CORRELATIONS)
+        estimator = RollingCorrelationEstimator(price_feed.price_history, **estimator_kwargs)
+        return cls(max_correlated_exposure, max_single_asset_exposure, estimator=estimator)
     
     def get_correlation(self, asset1: str, asset2: str) -> float:
         """
         Get correlation coefficient between two assets.
         
+        Negative live estimates are floored at 0: an inversely correlated
+        asset does not add to correlated exposure.
+        
         Args:
             asset1: First asset
             asset2: Second asset
@@ -78,6 +112,9 @@ class CorrelationAnalyzer:
         if asset1 == asset2:
             return 1.0  # Perfect correlation with self
         
+        if self.estimator is not None:
+            return max(0.0, self.estimator.get_correlation(asset1, asset2))
+        
         # Check both orderings
         key1 = (asset1, asset2)
         key2 = (asset2, asset1)
@@ -351,4 +388,8 @@ Asset Exposure:"""
         if any(exp > self.max_single_asset_exposure for exp in exposure.values()):
             summary += "\n\n⚠️ WARNING: Single asset concentration limit exceeded!"
         
+        if self.estimator is not None:
+            for asset1, asset2 in self.estimator.regime_changes():
+                summary += f"\n⚠️ Correlation regime change: {asset1}/{asset2}"
+        
         return summary
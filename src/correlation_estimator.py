@@ -0,0 +1,305 @@
+"""
+Rolling empirical correlations between crypto assets.
+
+Estimates pairwise return correlations from BinancePriceFeed.price_history
+so CorrelationAnalyzer's exposure limits follow the market instead of a
+static table.
+
+How it works:
+- Ticks are sampled into fixed bars (last price per bar). The estimator keeps
+  its own bars, so windows can be longer than the feed's tick buffer
+- Log returns are correlated over several windows (default 5m, 15m, 1h)
+- Each window estimate is shrunk toward a prior in proportion to how few
+  observations it has; without data the prior is used as is
+- A regime change is flagged when the raw shortest and longest window
+  correlations differ by at least regime_threshold and the difference is
+  significant (Fisher z-test); the short window is then trusted alone
+- Any asset that appears in the price history is picked up automatically
+
+Validates Requirements:
+- Correlated exposure limits based on live market data
+"""
+
+import logging
+import math
+from collections import OrderedDict
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Callable, Dict, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass
+class CorrelationEstimate:
+    """Blended correlation estimate for one asset pair."""
+    asset1: str
+    asset2: str
+    correlation: float
+    window_correlations: Dict[int, Optional[float]] = field(default_factory=dict)  # Window -> raw sample correlation
+    observations: int = 0  # Return pairs in the longest usable window
+    regime_change: bool = False
+
+    @property
+    def is_prior(self) -> bool:
+        """True if no window had enough data and the prior was used."""
+        return self.observations == 0
+
+
+def _pearson(xs: Sequence[float], ys: Sequence[float]) -> Optional[float]:
+    """Sample correlation, or None if either series is constant."""
+    n = len(xs)
+    if n < 2:
+        return None
+    mean_x = sum(xs) / n
+    mean_y = sum(ys) / n
+    cov = sum((x - mean_x) * (y - mean_y) for x, y in zip(xs, ys))
+    var_x = sum((x - mean_x) ** 2 for x in xs)
+    var_y = sum((y - mean_y) ** 2 for y in ys)
+    if var_x <= 0 or var_y <= 0:
+        return None
+    return max(-1.0, min(1.0, cov / math.sqrt(var_x * var_y)))
+
+
+class RollingCorrelationEstimator:
+    """
+    Rolling, shrunk correlation matrix over a live price history.
+
+    Features:
+    - Multi-window estimates blended with configurable weights
+    - Shrinkage toward a prior (pseudo-observation weighting)
+    - Regime-change detection between short and long windows
+    - Automatic coverage of new assets
+    """
+
+    def __init__(
+        self,
+        price_history: Dict[str, Sequence[Tuple[datetime, object]]],
+        windows: Sequence[int] = (300, 900, 3600),
+        window_weights: Optional[Sequence[float]] = None,
+        bar_seconds: int = 5,
+        min_observations: int = 20,
+        shrinkage_strength: float = 50.0,
+        prior: Optional[Dict[Tuple[str, str], float]] = None,
+        default_prior: float = 0.5,
+        regime_threshold: float = 0.4,
+        regime_z_score: float = 3.0,
+        refresh_seconds: float = 30.0,
+        clock: Optional[Callable[[], datetime]] = None
+    ):
+        """
+        Initialize the estimator.
+
+        Args:
+            price_history: Asset -> (timestamp, price) ticks, e.g. BinancePriceFeed.price_history
+            windows: Window lengths in seconds, shortest first
+            window_weights: Blend weight per window (default: equal)
+            bar_seconds: Bar length used to sample ticks into returns
+            min_observations: Return pairs needed before a window is used
+            shrinkage_strength: Pseudo-observations given to the prior
+            prior: Pair -> prior correlation (shrinkage target and cold-start value)
+            default_prior: Prior for pairs not in `prior`
+            regime_threshold: Short/long window gap that flags a regime change
+            regime_z_score: Fisher z-statistic the gap must also exceed
+            refresh_seconds: Minimum time between recomputations in get_correlation()
+            clock: Time source (default: datetime.now, matching the feed's timestamps)
+        """
+        if not windows:
+            raise ValueError("At least one window is required")
+        if window_weights is not None and len(window_weights) != len(windows):
+            raise ValueError("window_weights must have one weight per window")
+
+        self.price_history = price_history
+        self.windows = sorted(int(w) for w in windows)
+        weights = list(window_weights) if window_weights is not None else [1.0] * len(windows)
+        self.window_weights = dict(zip(self.windows, (w for _, w in sorted(zip(windows, weights)))))
+        self.bar_seconds = bar_seconds
+        self.min_observations = min_observations
+        self.shrinkage_strength = shrinkage_strength
+        self.prior = dict(prior or {})
+        self.default_prior = default_prior
+        self.regime_threshold = regime_threshold
+        self.regime_z_score = regime_z_score
+        self.refresh_seconds = refresh_seconds
+        self._clock = clock or datetime.now
+
+        # Asset -> bar index -> last price in that bar
+        self._bars: Dict[str, "OrderedDict[int, float]"] = {}
+        self._last_tick: Dict[str, datetime] = {}
+        self._matrix: Dict[Tuple[str, str], CorrelationEstimate] = {}
+        self._last_update: Optional[datetime] = None
+        self._regime_pairs: set = set()
+
+        logger.info(
+            f"🔗 Rolling correlation estimator initialized: windows={self.windows}s, "
+            f"bars={bar_seconds}s, shrinkage={shrinkage_strength}"
+        )
+
+    # ========================================================================
+    # Public API
+    # ========================================================================
+
+    @property
+    def assets(self) -> List[str]:
+        """Assets with at least one bar, sorted."""
+        return sorted(asset for asset, bars in self._bars.items() if bars)
+
+    def update(self, now: Optional[datetime] = None) -> Dict[Tuple[str, str], CorrelationEstimate]:
+        """Ingest new ticks and recompute the matrix."""
+        now = now or self._clock()
+        self._ingest(now)
+
+        matrix = {}
+        assets = self.assets
+        for i, asset1 in enumerate(assets):
+            for asset2 in assets[i + 1:]:
+                matrix[(asset1, asset2)] = self._estimate(asset1, asset2, now)
+
+        self._log_regime_changes(matrix)
+        self._matrix = matrix
+        self._last_update = now
+        return dict(matrix)
+
+    def matrix(self) -> Dict[Tuple[str, str], CorrelationEstimate]:
+        """Current matrix (pair keys sorted alphabetically), refreshed if stale."""
+        self._refresh_if_stale()
+        return dict(self._matrix)
+
+    def get_estimate(self, asset1: str, asset2: str) -> Optional[CorrelationEstimate]:
+        """Estimate for a pair, or None if either asset has no data yet."""
+        self._refresh_if_stale()
+        return self._matrix.get(tuple(sorted((asset1, asset2))))
+
+    def get_correlation(self, asset1: str, asset2: str) -> float:
+        """
+        Correlation between two assets (-1 to 1).
+
+        Pairs without enough data fall back to the prior.
+        """
+        if asset1 == asset2:
+            return 1.0
+        estimate = self.get_estimate(asset1, asset2)
+        if estimate is None:
+            return self._prior(asset1, asset2)
+        return estimate.correlation
+
+    def regime_changes(self) -> List[Tuple[str, str]]:
+        """Pairs whose short-window correlation has broken away from the long window."""
+        self._refresh_if_stale()
+        return sorted(pair for pair, estimate in self._matrix.items() if estimate.regime_change)
+
+    # ========================================================================
+    # Internals
+    # ========================================================================
+
+    def _refresh_if_stale(self) -> None:
+        now = self._clock()
+        if self._last_update is None or (now - self._last_update).total_seconds() >= self.refresh_seconds:
+            self.update(now)
+
+    def _prior(self, asset1: str, asset2: str) -> float:
+        return self.prior.get((asset1, asset2), self.prior.get((asset2, asset1), self.default_prior))
+
+    def _ingest(self, now: datetime) -> None:
+        """Sample ticks newer than the last ingested one into bars and prune old bars."""
+        oldest_bar = self._bar_index(now) - self.windows[-1] // self.bar_seconds - 1
+
+        for asset, ticks in list(self.price_history.items()):
+            bars = self._bars.setdefault(asset, OrderedDict())
+            last = self._last_tick.get(asset)
+            for timestamp, price in list(ticks):
+                if last is not None and timestamp <= last:
+                    continue
+                price = float(price)
+                if price <= 0:
+                    continue
+                bars[self._bar_index(timestamp)] = price
+                self._last_tick[asset] = timestamp
+
+            while bars and next(iter(bars)) < oldest_bar:
+                bars.popitem(last=False)
+
+    def _bar_index(self, timestamp: datetime) -> int:
+        return int(timestamp.timestamp() // self.bar_seconds)
+
+    def _returns(self, asset1: str, asset2: str, start_bar: int, end_bar: int) -> Tuple[List[float], List[float]]:
+        """
+        Aligned per-bar log returns of two assets between start_bar and end_bar.
+
+        Prices are carried forward over bars without ticks; returns start once
+        both assets have a price.
+        """
+        bars1, bars2 = self._bars[asset1], self._bars[asset2]
+        prev1 = prev2 = None
+        returns1: List[float] = []
+        returns2: List[float] = []
+        for bar in range(start_bar, end_bar + 1):
+            price1 = bars1.get(bar, prev1)
+            price2 = bars2.get(bar, prev2)
+            if prev1 is not None and prev2 is not None:
+                returns1.append(math.log(price1 / prev1))
+                returns2.append(math.log(price2 / prev2))
+            prev1, prev2 = price1, price2
+        return returns1, returns2
+
+    def _estimate(self, asset1: str, asset2: str, now: datetime) -> CorrelationEstimate:
+        prior = self._prior(asset1, asset2)
+        end_bar = self._bar_index(now)
+
+        raw: Dict[int, Optional[float]] = {}
+        counts: Dict[int, int] = {}
+        shrunk: Dict[int, float] = {}
+        for window in self.windows:
+            returns1, returns2 = self._returns(asset1, asset2, end_bar - window // self.bar_seconds, end_bar)
+            n = len(returns1)
+            correlation = _pearson(returns1, returns2)
+            raw[window] = correlation
+            if correlation is None or n < self.min_observations:
+                continue
+            # Shrinkage: the prior counts as shrinkage_strength extra observations
+            shrunk[window] = (n * correlation + self.shrinkage_strength * prior) / (n + self.shrinkage_strength)
+            counts[window] = n
+
+        if not shrunk:
+            return CorrelationEstimate(asset1, asset2, prior, raw, 0, False)
+
+        short, long = min(shrunk), max(shrunk)
+        regime_change = short != long and self._is_regime_change(raw[short], counts[short], raw[long], counts[long])
+        if regime_change:
+            # The long window still reflects the old regime
+            correlation = shrunk[short]
+        else:
+            total_weight = sum(self.window_weights[w] for w in shrunk)
+            correlation = sum(shrunk[w] * self.window_weights[w] for w in shrunk) / total_weight
+
+        return CorrelationEstimate(asset1, asset2, correlation, raw, counts[long], regime_change)
+
+    def _is_regime_change(self, short: float, n_short: int, long: float, n_long: int) -> bool:
+        """
+        Compare raw (unshrunk) window correlations.
+
+        Shrunk values would differ whenever the prior is off, because short
+        windows are shrunk harder; the z-test keeps noisy short windows from
+        flagging.
+        """
+        if abs(short - long) < self.regime_threshold or min(n_short, n_long) <= 3:
+            return False
+        clamp = 0.999999
+        z_short = math.atanh(max(-clamp, min(clamp, short)))
+        z_long = math.atanh(max(-clamp, min(clamp, long)))
+        z = abs(z_short - z_long) / math.sqrt(1 / (n_short - 3) + 1 / (n_long - 3))
+        return z >= self.regime_z_score
+
+    def _log_regime_changes(self, matrix: Dict[Tuple[str, str], CorrelationEstimate]) -> None:
+        flagged = {pair for pair, estimate in matrix.items() if estimate.regime_change}
+        for pair in sorted(flagged - self._regime_pairs):
+            short = matrix[pair].window_correlations.get(self.windows[0])
+            long = matrix[pair].window_correlations.get(self.windows[-1])
+            logger.warning(
+                f"⚠️ Correlation regime change {pair[0]}/{pair[1]}: "
+                f"{self.windows[0]}s={short if short is None else round(short, 2)} vs "
+                f"{self.windows[-1]}s={long if long is None else round(long, 2)}"
+            )
+        for pair in sorted(self._regime_pairs - flagged):
+            logger.info(f"🔗 Correlation regime for {pair[0]}/{pair[1]} settled")
+        self._regime_pairs = flagged
@@ -9,12 +9,13 @@ import asyncio
 import logging
 import uuid
 from datetime import datetime
-from decimal import Decimal
+from decimal import Decimal, ROUND_DOWN
 from typing import List, Optional, Dict, Tuple
 
 import rust_core
 from src.models import Market, Opportunity, TradeResult, SafetyDecision
 from src.ai_safety_guard import AISafetyGuard
+from src.kalshi_client import KalshiFeeModel, KalshiMarket
 from src.kelly_position_sizer import KellyPositionSizer
 from src.order_manager import OrderManager, Order
 
@@ -43,9 +44,9 @@ class CrossPlatformArbitrageEngine:
     def __init__(
         self,
         polymarket_client,  # CLOB client for Polymarket
-        kalshi_client,  # Kalshi API client
+        kalshi_client,  # KalshiClient or FixtureKalshiClient
         polymarket_order_manager: OrderManager,
-        kalshi_order_manager: OrderManager,
+        kalshi_order_manager,  # KalshiOrderManager
         ai_safety_guard: AISafetyGuard,
         kelly_sizer: KellyPositionSizer,
         min_profit_threshold: Decimal = Decimal('0.005'),  # 0.5%
@@ -54,7 +55,10 @@ class CrossPlatformArbitrageEngine:
         settlement_time_hours: int = 24,  # Settlement time in hours
         current_balance_getter=None,
         current_gas_price_getter=None,
-        pending_tx_count_getter=None
+        pending_tx_count_getter=None,
+        kalshi_fee_model: Optional[KalshiFeeModel] = None,
+        kalshi_series: Optional[List[str]] = None,
+        max_close_time_diff_seconds: float = 60.0
     ):
         """
         Initialize Cross-Platform Arbitrage Engine.
@@ -63,9 +67,9 @@ class CrossPlatformArbitrageEngine:
         
         Args:
             polymarket_client: CLOB client for Polymarket market data
-            kalshi_client: Kalshi API client for market data
+            kalshi_client: Kalshi API client for market data (KalshiClient or FixtureKalshiClient)
             polymarket_order_manager: Order manager for Polymarket trades
-            kalshi_order_manager: Order manager for Kalshi trades
+            kalshi_order_manager: KalshiOrderManager for Kalshi trades
             ai_safety_guard: AI safety guard for validation
             kelly_sizer: Kelly position sizer for optimal sizing
             min_profit_threshold: Minimum profit percentage (default 0.5%)
@@ -75,6 +79,10 @@ class CrossPlatformArbitrageEngine:
             current_balance_getter: Function to get current balance
             current_gas_price_getter: Function to get current gas price in gwei
             pending_tx_count_getter: Function to get pending transaction count
+            kalshi_fee_model: Kalshi fee schedule (default: the client's)
+            kalshi_series: Kalshi series tickers to scan (default: all open markets)
+            max_close_time_diff_seconds: Largest gap between the Polymarket end time and the
+                Kalshi close time of a matched pair
         """
         self.polymarket_client = polymarket_client
         self.kalshi_client = kalshi_client
@@ -94,9 +102,23 @@ class CrossPlatformArbitrageEngine:
         self._get_gas_price = current_gas_price_getter or (lambda: 50)
         self._get_pending_tx_count = pending_tx_count_getter or (lambda: 0)
         
+        # Kalshi fees follow Kalshi's schedule, not the Polymarket fee curve
+        client_fee_model = getattr(kalshi_client, "fee_model", None)
+        self.kalshi_fee_model = kalshi_fee_model or (
+            client_fee_model if isinstance(client_fee_model, KalshiFeeModel) else KalshiFeeModel()
+        )
+        self.kalshi_series = kalshi_series
+        
         # Cache for equivalent market mappings
         self._market_mappings: Dict[str, str] = {}
         
+        # Kalshi ticker -> contract of the last fetch (strikes are not on the Market model)
+        self._kalshi_contracts: Dict[str, KalshiMarket] = {}
+        self.max_close_time_diff_seconds = max_close_time_diff_seconds
+        
+        # Opportunity ID -> (Polymarket market, Kalshi market, PM side, Kalshi side)
+        self._opportunity_legs: Dict[str, Tuple[Market, Market, str, str]] = {}
+        
         logger.info(
             f"CrossPlatformArbitrageEngine initialized: "
             f"min_profit_threshold={min_profit_threshold * 100}%, "
@@ -105,7 +127,7 @@ class CrossPlatformArbitrageEngine:
             f"settlement_time={settlement_time_hours}h"
         )
     
-    async def scan_opportunities(self) -> List[Opportunity]:
+    async def scan_opportunities(self, polymarket_markets: Optional[List[Market]] = None) -> List[Opportunity]:
         """
         Scan for cross-platform arbitrage opportunities.
         
@@ -114,15 +136,20 @@ class CrossPlatformArbitrageEngine:
         - 3.3: Identify cross-platform arbitrage when price discrepancy exists
         - 3.6: Account for withdrawal fees in profit calculation
         
+        Args:
+            polymarket_markets: Markets already fetched by the orchestrator (fetched here if None)
+        
         Returns:
             List of profitable cross-platform arbitrage opportunities
         """
         opportunities = []
+        self._opportunity_legs.clear()
         
         try:
             # Fetch markets from both platforms
             logger.debug("Fetching markets from Polymarket and Kalshi...")
-            polymarket_markets = await self._fetch_polymarket_markets()
+            if polymarket_markets is None:
+                polymarket_markets = await self._fetch_polymarket_markets()
             kalshi_markets = await self._fetch_kalshi_markets()
             
             logger.debug(
@@ -149,6 +176,7 @@ class CrossPlatformArbitrageEngine:
                     )
                     if opp1 and opp1.is_profitable(self.min_profit_threshold):
                         opportunities.append(opp1)
+                        self._opportunity_legs[opp1.opportunity_id] = (pm_market, kalshi_market, "YES", "NO")
                         logger.info(
                             f"Found cross-platform arbitrage: "
                             f"PM YES ${opp1.yes_price} < Kalshi NO | "
@@ -164,6 +192,7 @@ class CrossPlatformArbitrageEngine:
                     )
                     if opp2 and opp2.is_profitable(self.min_profit_threshold):
                         opportunities.append(opp2)
+                        self._opportunity_legs[opp2.opportunity_id] = (pm_market, kalshi_market, "NO", "YES")
                         logger.info(
                             f"Found cross-platform arbitrage: "
                             f"PM NO ${opp2.no_price} < Kalshi YES | "
@@ -215,9 +244,9 @@ class CrossPlatformArbitrageEngine:
             pm_price = pm_market.yes_price if pm_side == "YES" else pm_market.no_price
             kalshi_price = kalshi_market.yes_price if kalshi_side == "YES" else kalshi_market.no_price
             
-            # Calculate trading fees using Rust fee calculator
+            # Polymarket fees from the Rust fee calculator, Kalshi fees from its schedule
             pm_fee_rate = self._calculate_fee(pm_price)
-            kalshi_fee_rate = self._calculate_fee(kalshi_price)
+            kalshi_fee_rate = self.kalshi_fee_model.fee_rate(kalshi_price)
             
             # Calculate total costs including trading fees
             pm_cost = pm_price * (Decimal('1') + pm_fee_rate)
@@ -297,7 +326,7 @@ class CrossPlatformArbitrageEngine:
         Returns:
             TradeResult with execution details
         """
-        trade_id = f"trade_{uuid.uuid4().hex[:12]}",
+        trade_id = f"trade_{uuid.uuid4().hex[:12]}"
         timestamp = datetime.now()
         
         logger.info(
@@ -334,22 +363,33 @@ class CrossPlatformArbitrageEngine:
                 bankroll=bankroll
             )
             
-            logger.info(f"Position size: ${position_size} (bankroll: ${bankroll})")
+            # Kelly sizes in USDC; a contract pair costs total_cost. Kalshi trades whole
+            # contracts and both legs must be the same size
+            if opportunity.total_cost <= 0:
+                return self._create_failed_result(trade_id, opportunity, timestamp, "Opportunity has no cost")
+            position_size = (Decimal(str(position_size)) / opportunity.total_cost).to_integral_value(
+                rounding=ROUND_DOWN
+            )
+            if position_size < 1:
+                return self._create_failed_result(
+                    trade_id, opportunity, timestamp, "Position size below one Kalshi contract"
+                )
+            
+            logger.info(f"Position size: {position_size} contracts (bankroll: ${bankroll})")
             opportunity.position_size = position_size
             
-            # Step 3: Determine which side to buy on each platform
-            # For simplicity, assume YES on PM and NO on Kalshi
-            # (In production, this would be determined by the opportunity)
-            pm_side = "YES"
-            kalshi_side = "NO"
-            pm_price = opportunity.yes_price
-            kalshi_price = opportunity.no_price
+            # Step 3: Determine which side to buy on each platform (recorded by the scan)
+            _, _, pm_side, kalshi_side = self._opportunity_legs.get(
+                opportunity.opportunity_id, (pm_market, kalshi_market, "YES", "NO")
+            )
+            pm_price = opportunity.yes_price if pm_side == "YES" else opportunity.no_price
+            kalshi_price = opportunity.yes_price if kalshi_side == "YES" else opportunity.no_price
             
             # Step 4: Create FOK Orders on both platforms (Requirement 3.4)
             logger.debug("Creating FOK orders on both platforms...")
             
             pm_order = self.polymarket_order_manager.create_fok_order(
-                market_id=pm_market.market_id,
+                market_id=pm_market.yes_token_id if pm_side == "YES" else pm_market.no_token_id,
                 side=pm_side,
                 price=pm_price,
                 size=position_size
@@ -430,9 +470,14 @@ class CrossPlatformArbitrageEngine:
             pm_cost = pm_order.fill_price * position_size
             kalshi_cost = kalshi_order.fill_price * position_size
             
-            # Calculate actual fees
+            # Calculate actual fees (Kalshi reports the fee it charged)
             pm_fee_amount = pm_cost * opportunity.yes_fee if pm_side == "YES" else pm_cost * opportunity.no_fee
-            kalshi_fee_amount = kalshi_cost * opportunity.no_fee if kalshi_side == "NO" else kalshi_cost * opportunity.yes_fee
+            kalshi_fees = getattr(self.kalshi_order_manager, "fees", None)
+            kalshi_fee_amount = (
+                kalshi_fees[kalshi_order.order_id]
+                if isinstance(kalshi_fees, dict) and kalshi_order.order_id in kalshi_fees
+                else self.kalshi_fee_model.fee(position_size, kalshi_order.fill_price)
+            )
             
             # Add withdrawal fees
             pm_withdrawal = pm_cost * self.withdrawal_fee_polymarket
@@ -451,6 +496,7 @@ class CrossPlatformArbitrageEngine:
             logger.info(
                 f"Trade completed: profit=${actual_profit}, gas=${gas_cost}, net=${net_profit}"
             )
+            self._opportunity_legs.pop(opportunity.opportunity_id, None)
             
             return TradeResult(
                 trade_id=trade_id,
@@ -488,14 +534,28 @@ class CrossPlatformArbitrageEngine:
     
     async def _fetch_kalshi_markets(self) -> List[Market]:
         """
-        Fetch active markets from Kalshi.
+        Fetch open crypto markets from Kalshi.
+        
+        Markets are priced at their YES/NO asks; markets without a two-sided
+        ask or outside the supported assets are skipped.
         
         Returns:
             List of Kalshi markets
         """
-        # Placeholder: In production, this would call the Kalshi API
-        # For now, return empty list
-        return []
+        if self.kalshi_client is None:
+            return []
+        
+        kalshi_markets = []
+        for series in self.kalshi_series or [None]:
+            kalshi_markets.extend(await self.kalshi_client.get_markets(status="open", series_ticker=series))
+        
+        self._kalshi_contracts = {m.ticker: m for m in kalshi_markets}
+        markets = [m.to_market() for m in kalshi_markets]
+        return [m for m in markets if m is not None]
+    
+    def get_legs(self, opportunity_id: str) -> Optional[Tuple[Market, Market, str, str]]:
+        """(Polymarket market, Kalshi market, PM side, Kalshi side) of a scanned opportunity."""
+        return self._opportunity_legs.get(opportunity_id)
     
     def _find_equivalent_markets(
         self,
@@ -514,7 +574,7 @@ class CrossPlatformArbitrageEngine:
         """
         pairs = []
         
-        # Simple matching based on asset and question similarity
+        # Asset, strike, direction and close time must agree (_are_markets_equivalent)
         for pm_market in polymarket_markets:
             for kalshi_market in kalshi_markets:
                 if self._are_markets_equivalent(pm_market, kalshi_market):
@@ -523,31 +583,49 @@ class CrossPlatformArbitrageEngine:
         
         return pairs
     
-    def _are_markets_equivalent(self, market1: Market, market2: Market) -> bool:
+    def _are_markets_equivalent(self, pm_market: Market, kalshi_market: Market) -> bool:
         """
-        Check if two markets are equivalent.
+        Check if a Polymarket and a Kalshi market settle on the same event.
+        
+        Both must be on the same asset, close within max_close_time_diff_seconds
+        of each other and ask about the same strike in the same direction:
+        "above $X" matches a Kalshi contract with only floor_strike ~X, "below $X"
+        one with only cap_strike ~X. Range contracts and questions without a
+        parsable strike or direction never match.
         
         Args:
-            market1: First market
-            market2: Second market
+            pm_market: Polymarket market
+            kalshi_market: Kalshi market (from KalshiMarket.to_market)
             
         Returns:
             True if markets are equivalent
         """
-        # Simple check: same asset and similar questions
-        if market1.asset != market2.asset:
+        if pm_market.asset != kalshi_market.asset:
+            return False
+        contract = self._kalshi_contracts.get(kalshi_market.market_id)
+        if contract is None or contract.close_time is None:
             return False
         
-        # Check if questions are similar (simplified)
-        # In production, this would use more sophisticated matching
-        question1_lower = market1.question.lower()
-        question2_lower = market2.question.lower()
+        close_gap = abs((pm_market.end_time - contract.close_time).total_seconds())
+        if close_gap > self.max_close_time_diff_seconds:
+            return False
         
-        # Check for common keywords
-        common_keywords = ["above", "below", "higher", "lower", "price"]
-        has_common = any(kw in question1_lower and kw in question2_lower for kw in common_keywords)
+        strike = pm_market.parse_strike_price()
+        if strike is None:
+            return False
+        question = pm_market.question.lower()
+        above = any(word in question for word in ("above", "higher", "over"))
+        below = any(word in question for word in ("below", "lower", "under"))
+        if above == below:
+            return False
         
-        return has_common
+        # Kalshi writes "$100,000 or above" as floor_strike 99999.99
+        tolerance = Decimal("0.01")
+        if above:
+            return (contract.cap_strike is None and contract.floor_strike is not None
+                    and abs(contract.floor_strike - strike) <= tolerance)
+        return (contract.floor_strike is None and contract.cap_strike is not None
+                and abs(contract.cap_strike - strike) <= tolerance)
     
     def _calculate_fee(self, price: Decimal) -> Decimal:
         """
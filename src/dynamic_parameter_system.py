@@ -478,6 +478,24 @@ class DynamicParameterSystem:
             f"profit=${profit:.2f}, success={was_successful}"
         )
     
+    def on_trade_outcome(self, outcome) -> None:
+        """
+        Record a trade from the TradeOutcomeStore (trades without a position size are skipped).
+        
+        The realized return is used as the edge and its magnitude as the odds.
+        """
+        if outcome.size is None:
+            return
+        position_size = Decimal(str(outcome.size))
+        profit_pct = Decimal(str(outcome.profit_pct))
+        self.record_trade(
+            position_size=position_size,
+            profit=position_size * profit_pct,
+            was_successful=profit_pct > 0,
+            edge=profit_pct,
+            odds=abs(profit_pct) if profit_pct != 0 else Decimal('0.01')
+        )
+    
     def _adjust_fractional_kelly(self) -> None:
         """
         Dynamically adjust fractional Kelly based on recent performance.
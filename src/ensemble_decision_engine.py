@@ -69,7 +69,7 @@ class EnsembleDecisionEngine:
         
         Args:
             llm_engine: LLM Decision Engine V2
-            rl_engine: Reinforcement Learning Engine
+            rl_engine: Reinforcement Learning Engine (ContinuousRLEngine)
             historical_tracker: Historical Success Tracker
             multi_tf_analyzer: Multi-Timeframe Analyzer
             min_consensus: Minimum consensus score to execute (0-100)
@@ -112,7 +112,8 @@ class EnsembleDecisionEngine:
         asset: str,
         market_context,  # Can be Dict or MarketContextV2 object
         portfolio_state,  # Can be Dict or PortfolioStateV2 object
-        opportunity_type: str = "latency"
+        opportunity_type: str = "latency",
+        rl_features: Optional[Dict[str, float]] = None
     ) -> EnsembleDecision:
         """
         Make ensemble decision by combining all models.
@@ -122,6 +123,7 @@ class EnsembleDecisionEngine:
             market_context: Market data (Dict or MarketContextV2 object)
             portfolio_state: Portfolio state (Dict or PortfolioStateV2 object)
             opportunity_type: Type of opportunity
+            rl_features: MarketFeatures of the entry for the RL engine's policy
             
         Returns:
             EnsembleDecision with consensus vote
@@ -182,9 +184,7 @@ class EnsembleDecisionEngine:
             try:
                 strategy, rl_confidence = self.rl_engine.select_strategy(
                     asset=asset,
-                    volatility=market_dict.get("volatility"),
-                    trend=market_dict.get("trend"),
-                    liquidity=market_dict.get("liquidity")
+                    features=rl_features
                 )
                 
                 # Map strategy to action
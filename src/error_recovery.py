@@ -11,7 +11,7 @@ import functools
 import logging
 import time
 from typing import Callable, Optional, Type, Tuple, Any
-from decimal import Decimal
+from decimal import Decimal, ROUND_CEILING
 
 logger = logging.getLogger(__name__)
 
@@ -235,6 +235,7 @@ class GasPriceEscalator:
     
     Automatically increases gas prices when transactions fail due to insufficient gas,
     ensuring transactions eventually get mined during network congestion.
+    Supports legacy gas prices and EIP-1559 fee pairs (escalate_fees).
     """
     
     def __init__(self, escalation_factor: float = 1.1, max_escalations: int = 5):
@@ -297,6 +298,64 @@ class GasPriceEscalator:
         
         return new_gas_price
     
+    def escalate_fees(
+        self,
+        max_fee: int,
+        max_priority_fee: int,
+        tx_hash: Optional[str] = None,
+        max_fee_cap: Optional[int] = None
+    ) -> Tuple[int, int]:
+        """
+        Calculate escalated EIP-1559 fees for a replacement transaction.
+        
+        Both maxFeePerGas and maxPriorityFeePerGas are raised (rounded up), since
+        nodes only accept a replacement that bumps both.
+        
+        Args:
+            max_fee: Current maxFeePerGas in wei
+            max_priority_fee: Current maxPriorityFeePerGas in wei
+            tx_hash: Optional key for tracking escalation count (e.g. the nonce)
+            max_fee_cap: Optional upper bound for maxFeePerGas in wei
+        
+        Returns:
+            (new_max_fee, new_max_priority_fee) in wei
+        
+        Raises:
+            ValueError: If maximum escalations reached or the cap would be exceeded
+        """
+        new_max_fee = int((Decimal(max_fee) * self.escalation_factor).to_integral_value(rounding=ROUND_CEILING))
+        new_priority = int(
+            (Decimal(max_priority_fee) * self.escalation_factor).to_integral_value(rounding=ROUND_CEILING)
+        )
+        if max_fee_cap is not None and new_max_fee > max_fee_cap:
+            raise ValueError(
+                f"Escalated max fee {new_max_fee} wei exceeds cap {max_fee_cap} wei"
+                + (f" for tx {tx_hash}" if tx_hash else "")
+            )
+        
+        if tx_hash:
+            escalation_count = self.escalation_history.get(tx_hash, 0)
+            if escalation_count >= self.max_escalations:
+                raise ValueError(
+                    f"Maximum gas escalations ({self.max_escalations}) reached for tx {tx_hash}"
+                )
+            self.escalation_history[tx_hash] = escalation_count + 1
+        
+        logger.info(
+            f"Escalating fees: max {max_fee} -> {new_max_fee} wei, "
+            f"priority {max_priority_fee} -> {new_priority} wei",
+            extra={
+                "old_max_fee": max_fee,
+                "new_max_fee": new_max_fee,
+                "old_priority_fee": max_priority_fee,
+                "new_priority_fee": new_priority,
+                "tx_hash": tx_hash,
+                "escalation_count": self.escalation_history.get(tx_hash, 0) if tx_hash else None
+            }
+        )
+        
+        return new_max_fee, new_priority
+    
     def reset_escalation(self, tx_hash: str) -> None:
         """
         Reset escalation count for a transaction.
@@ -342,6 +401,7 @@ class CircuitBreaker:
         self.is_open = False
         self.last_failure_time = None
         self.failure_reasons = []
+        self.on_open: Optional[Callable[[dict], None]] = None  # Called with get_status() when the circuit opens
         
         logger.info(f"Initialized circuit breaker with threshold: {failure_threshold}")
     
@@ -397,6 +457,12 @@ class CircuitBreaker:
                 "last_failure_time": self.last_failure_time
             }
         )
+        
+        if self.on_open is not None:
+            try:
+                self.on_open(self.get_status())
+            except Exception as e:
+                logger.error(f"Circuit breaker listener failed: {e}")
     
     def close_circuit(self) -> None:
         """
@@ -0,0 +1,372 @@
+"""
+Fair-Value Pricer for binary "price above strike at close" markets.
+
+A market that pays $1 if the asset closes above a strike is a cash-or-nothing
+digital call. Under a driftless lognormal model its fair YES price is:
+
+    P(above) = N(d2),  d2 = (ln(S / K) - sigma^2 * t / 2) / (sigma * sqrt(t))
+
+- S: spot from BinancePriceFeed (or passed in)
+- K: strike from Market.parse_strike_price() (or the window-open price for up/down markets)
+- t: time to close in years
+- sigma: annualized realized volatility from MultiTimeframeAnalyzer, falling
+  back to Binance tick history and then to per-asset defaults
+
+The confidence band re-prices with volatility and spot shocked by their
+uncertainty (the CEX is not the resolution source), so strategies can
+require an edge that survives model error. Strategies share this one model
+instead of keeping their own estimates.
+
+Validates Requirements:
+- Theoretical YES probability from spot, strike, time to close and realized volatility
+- Confidence bands from volatility and spot uncertainty
+- Edge of a market price against the model for either side
+- Strike direction (above/below) parsing shared by strategies
+"""
+
+import logging
+import math
+from dataclasses import dataclass
+from datetime import datetime, timezone
+from decimal import Decimal
+from statistics import NormalDist
+from typing import Dict, Optional, Sequence, Tuple
+
+from src.models import Market
+
+logger = logging.getLogger(__name__)
+
+SECONDS_PER_YEAR = 365 * 24 * 3600
+
+# Fallback annualized volatility when no realized estimate is available
+DEFAULT_ANNUAL_VOLATILITY = {
+    "BTC": 0.50,
+    "ETH": 0.65,
+    "SOL": 0.80,
+    "XRP": 0.80,
+}
+
+_NORMAL = NormalDist()
+
+
+@dataclass
+class FairValue:
+    """Model price of a binary strike market."""
+    probability: Decimal  # Theoretical YES probability
+    lower: Decimal  # Lower confidence band for YES
+    upper: Decimal  # Upper confidence band for YES
+    spot: Decimal
+    strike: Decimal
+    seconds_to_close: float
+    volatility: float  # Annualized volatility used
+    volatility_source: str  # "realized", "ticks", "default" or "given"
+    direction: str = "above"  # YES pays if the close is above/below the strike
+
+    @property
+    def band_width(self) -> Decimal:
+        """Width of the confidence band."""
+        return self.upper - self.lower
+
+    def side_probability(self, side: str = "YES") -> Decimal:
+        """Model probability that the given side ("YES"/"UP" or "NO"/"DOWN") wins."""
+        return self.probability if side in ("YES", "UP") else Decimal("1") - self.probability
+
+    def edge(self, price: Decimal, side: str = "YES") -> Decimal:
+        """Model value minus price for buying one share of the side."""
+        return self.side_probability(side) - price
+
+    def conservative_edge(self, price: Decimal, side: str = "YES") -> Decimal:
+        """Edge at the unfavourable end of the confidence band."""
+        worst = self.lower if side in ("YES", "UP") else Decimal("1") - self.upper
+        return worst - price
+
+
+def strike_direction(question: str) -> Optional[str]:
+    """
+    Whether a market resolves YES above or below its strike.
+
+    Returns:
+        "above", "below", or None if the question states neither
+    """
+    question_lower = question.lower()
+    if "above" in question_lower or "over" in question_lower or ">" in question_lower:
+        return "above"
+    if "below" in question_lower or "under" in question_lower or "<" in question_lower:
+        return "below"
+    return None
+
+
+def realized_volatility(points: Sequence[Tuple[datetime, Decimal]], min_points: int = 10) -> Optional[float]:
+    """
+    Annualized realized volatility from timestamped prices.
+
+    Squared log returns are summed over the elapsed time, so irregular
+    sampling (trade ticks, per-cycle updates) is handled.
+
+    Args:
+        points: (timestamp, price) pairs, oldest first
+        min_points: Minimum number of prices required
+
+    Returns:
+        Annualized volatility, or None with too little data
+    """
+    if len(points) < min_points:
+        return None
+    elapsed = (points[-1][0] - points[0][0]).total_seconds()
+    if elapsed <= 0:
+        return None
+    squared = sum(
+        math.log(float(p2) / float(p1)) ** 2
+        for (_, p1), (_, p2) in zip(points, points[1:])
+        if p1 > 0 and p2 > 0
+    )
+    if squared <= 0:
+        return None
+    return math.sqrt(squared / elapsed * SECONDS_PER_YEAR)
+
+
+def seconds_until(end_time: datetime, now: Optional[datetime] = None) -> float:
+    """Seconds from now until end_time (naive times are treated as local)."""
+    if now is None:
+        now = datetime.now(timezone.utc) if end_time.tzinfo else datetime.now()
+    return (end_time - now).total_seconds()
+
+
+class FairValuePricer:
+    """
+    Option-style pricer for binary strike markets, shared across strategies.
+
+    Features:
+    - Digital-option YES probability (driftless lognormal)
+    - Confidence band over volatility x spot shocks (wider on default volatility)
+    - Volatility from MultiTimeframeAnalyzer, Binance ticks or per-asset defaults
+    - Market pricing straight from Market.parse_strike_price() and end_time
+    - Implied strike for up/down markets whose open price is unknown,
+      remembered per market so every strategy prices against the same strike
+    """
+
+    def __init__(
+        self,
+        price_feed=None,
+        volatility_analyzer=None,
+        vol_uncertainty: float = 0.25,
+        spot_uncertainty: float = 0.0005
+    ):
+        """
+        Initialize the pricer.
+
+        Args:
+            price_feed: BinancePriceFeed for spot prices and tick volatility (optional)
+            volatility_analyzer: MultiTimeframeAnalyzer for realized volatility (optional)
+            vol_uncertainty: Relative volatility shock for the band (0.25 = +/-25%)
+            spot_uncertainty: Relative spot shock for the band (0.0005 = +/-5 bps CEX basis)
+        """
+        self.price_feed = price_feed
+        self.volatility_analyzer = volatility_analyzer
+        self.vol_uncertainty = vol_uncertainty
+        self.spot_uncertainty = spot_uncertainty
+        self._up_down_strikes: Dict[str, Tuple[Decimal, datetime]] = {}  # market_id -> (strike, end_time)
+
+    # ============================================================
+    # INPUTS
+    # ============================================================
+
+    def spot(self, asset: str) -> Optional[Decimal]:
+        """Latest Binance price for the asset, if any."""
+        if self.price_feed is None:
+            return None
+        price = self.price_feed.prices.get(asset)
+        return price if price else None
+
+    def volatility(self, asset: str) -> Tuple[float, str]:
+        """
+        Annualized volatility estimate and its source.
+
+        Returns:
+            (volatility, source) where source is "realized", "ticks" or "default"
+        """
+        if self.volatility_analyzer is not None:
+            vol = self.volatility_analyzer.get_realized_volatility(asset)
+            if vol:
+                return vol, "realized"
+        if self.price_feed is not None:
+            ticks = list(self.price_feed.price_history.get(asset) or [])[-500:]
+            vol = realized_volatility(ticks, min_points=20)
+            if vol:
+                return vol, "ticks"
+        return DEFAULT_ANNUAL_VOLATILITY.get(asset, 0.80), "default"
+
+    # ============================================================
+    # PRICING
+    # ============================================================
+
+    def price(
+        self,
+        spot: Decimal,
+        strike: Decimal,
+        seconds_to_close: float,
+        volatility: float,
+        direction: str = "above",
+        volatility_source: str = "given"
+    ) -> FairValue:
+        """
+        Price a binary strike market.
+
+        Args:
+            spot: Current underlying price
+            strike: Strike price
+            seconds_to_close: Time until the market closes
+            volatility: Annualized volatility
+            direction: "above" (YES if close > strike) or "below"
+            volatility_source: Recorded on the result
+
+        Returns:
+            FairValue with the YES probability and confidence band
+        """
+        if spot <= 0 or strike <= 0:
+            raise ValueError(f"spot and strike must be positive, got {spot} / {strike}")
+        if direction not in ("above", "below"):
+            raise ValueError(f"Invalid direction: {direction}")
+
+        vol_shock = self.vol_uncertainty * (2 if volatility_source == "default" else 1)
+        probability = self._probability(float(spot), float(strike), seconds_to_close, volatility)
+        shocked = [
+            self._probability(float(spot) * (1 + s), float(strike), seconds_to_close, volatility * (1 + v))
+            for s in (-self.spot_uncertainty, 0.0, self.spot_uncertainty)
+            for v in (-vol_shock, 0.0, vol_shock)
+        ]
+        lower, upper = min(shocked), max(shocked)
+
+        if direction == "below":
+            probability, lower, upper = 1 - probability, 1 - upper, 1 - lower
+
+        return FairValue(
+            probability=self._to_decimal(probability),
+            lower=self._to_decimal(lower),
+            upper=self._to_decimal(upper),
+            spot=spot,
+            strike=strike,
+            seconds_to_close=seconds_to_close,
+            volatility=volatility,
+            volatility_source=volatility_source,
+            direction=direction
+        )
+
+    def price_market(
+        self,
+        market: Market,
+        spot: Optional[Decimal] = None,
+        now: Optional[datetime] = None
+    ) -> Optional[FairValue]:
+        """
+        Price a strike market from its question and end time.
+
+        Args:
+            market: Market with a strike in the question ("BTC above $95,000 ...")
+            spot: Underlying price (default: the Binance feed)
+            now: Pricing time (default: now)
+
+        Returns:
+            FairValue, or None if the strike, direction or spot is unavailable
+        """
+        strike = market.parse_strike_price()
+        direction = strike_direction(market.question)
+        if strike is None or strike <= 0 or direction is None:
+            return None
+
+        spot = spot if spot is not None else self.spot(market.asset)
+        if not spot:
+            return None
+
+        volatility, source = self.volatility(market.asset)
+        return self.price(
+            spot=spot,
+            strike=strike,
+            seconds_to_close=seconds_until(market.end_time, now),
+            volatility=volatility,
+            direction=direction,
+            volatility_source=source
+        )
+
+    def price_up_down(
+        self,
+        market_id: str,
+        asset: str,
+        end_time: datetime,
+        observed_up: Decimal,
+        spot: Optional[Decimal] = None,
+        now: Optional[datetime] = None
+    ) -> Optional[FairValue]:
+        """
+        Price an up/down market ("UP" is the YES side).
+
+        The strike is implied once per market from the first observed UP price
+        (implied_strike) and kept until the market closes, so later prices
+        follow the spot and every strategy sharing this pricer agrees.
+
+        Args:
+            market_id: Market (condition) ID the strike is remembered under
+            asset: Underlying asset
+            end_time: Market close
+            observed_up: Current UP price, used only the first time the market is seen
+            spot: Underlying price (default: the Binance feed)
+            now: Pricing time (default: now)
+
+        Returns:
+            FairValue, or None without a spot price or after the close
+        """
+        spot = spot if spot is not None else self.spot(asset)
+        seconds_to_close = seconds_until(end_time, now)
+        if not spot or seconds_to_close <= 0:
+            return None
+
+        volatility, source = self.volatility(asset)
+        known = self._up_down_strikes.get(market_id)
+        if known is None:
+            for other, (_, other_end) in list(self._up_down_strikes.items()):
+                if seconds_until(other_end, now) <= 0:
+                    del self._up_down_strikes[other]
+            strike = self.implied_strike(spot, observed_up, seconds_to_close, volatility)
+            self._up_down_strikes[market_id] = (strike, end_time)
+            logger.info(f"📐 {asset} implied strike ${strike:,.2f} (UP ${observed_up}, spot ${spot})")
+        else:
+            strike = known[0]
+
+        return self.price(spot, strike, seconds_to_close, volatility, volatility_source=source)
+
+    def forget_strike(self, market_id: str) -> None:
+        """Drop the remembered strike of a closed up/down market."""
+        self._up_down_strikes.pop(market_id, None)
+
+    def implied_strike(
+        self,
+        spot: Decimal,
+        probability: Decimal,
+        seconds_to_close: float,
+        volatility: float
+    ) -> Decimal:
+        """
+        Strike at which an "above" market is worth the given probability.
+
+        Up/down markets resolve against the price at the window open, which
+        the question does not state; this recovers it from an observed price.
+        """
+        p = min(max(float(probability), 0.02), 0.98)
+        sigma_t = volatility * math.sqrt(max(seconds_to_close, 1.0) / SECONDS_PER_YEAR)
+        z = _NORMAL.inv_cdf(p)
+        return Decimal(str(float(spot) * math.exp(-z * sigma_t - sigma_t ** 2 / 2)))
+
+    @staticmethod
+    def _probability(spot: float, strike: float, seconds_to_close: float, volatility: float) -> float:
+        """P(close > strike) for a driftless lognormal price."""
+        if seconds_to_close <= 0 or volatility <= 0:
+            if spot == strike:
+                return 0.5
+            return 1.0 if spot > strike else 0.0
+        sigma_t = volatility * math.sqrt(seconds_to_close / SECONDS_PER_YEAR)
+        d2 = (math.log(spot / strike) - sigma_t ** 2 / 2) / sigma_t
+        return _NORMAL.cdf(d2)
+
+    @staticmethod
+    def _to_decimal(value: float) -> Decimal:
+        return Decimal(str(round(min(max(value, 0.0), 1.0), 4)))
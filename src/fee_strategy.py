@@ -0,0 +1,197 @@
+"""
+EIP-1559 fee strategy for Polygon transactions.
+
+Replaces the legacy `gasPrice = eth.gas_price` pattern, which overpays in
+quiet periods and gets stuck during gas spikes:
+- Base fee: the next block's base fee is taken from eth_feeHistory (derived
+  from the latest block's gas usage); maxFeePerGas allows for the worst-case
+  EIP-1559 increase (12.5% per block) over `blocks_ahead` blocks. Only the
+  actual base fee is paid, so the headroom costs nothing
+- Priority fee: a percentile of the tips paid in recent blocks, per urgency
+  (low/normal/high), never below Polygon's minimum tip
+- Replacement bumps come from GasPriceEscalator.escalate_fees
+  (error_recovery.py); TransactionManager raises them to the current
+  estimate if the network moved further
+- Falls back to a legacy gasPrice when the node has no fee history
+
+Validates Requirements:
+- EIP-1559 transactions with base-fee prediction and priority-fee percentiles
+"""
+
+import logging
+import math
+import statistics
+import time
+from dataclasses import dataclass
+from typing import Callable, Dict, List, Optional, Tuple
+
+from web3 import Web3
+
+logger = logging.getLogger(__name__)
+
+GWEI = 10 ** 9
+BASE_FEE_MAX_CHANGE_DENOMINATOR = 8  # EIP-1559: base fee moves at most 1/8 per block
+
+DEFAULT_PERCENTILES = {"low": 25, "normal": 50, "high": 90}
+
+
+class FeeCapExceededError(Exception):
+    """The fees needed for inclusion exceed the configured cap."""
+
+
+@dataclass
+class FeeEstimate:
+    """Fees for one transaction (wei)."""
+    base_fee: int  # Next block's base fee
+    max_priority_fee: int
+    max_fee: int
+    urgency: str = "normal"
+    legacy: bool = False  # True: max_fee is a legacy gasPrice
+
+    def tx_params(self) -> Dict[str, int]:
+        """Fee fields for a transaction dict."""
+        if self.legacy:
+            return {"gasPrice": self.max_fee}
+        return {"maxFeePerGas": self.max_fee, "maxPriorityFeePerGas": self.max_priority_fee}
+
+    def describe(self) -> str:
+        if self.legacy:
+            return f"gasPrice={self.max_fee / GWEI:.1f} gwei"
+        return (f"base={self.base_fee / GWEI:.1f} tip={self.max_priority_fee / GWEI:.1f} "
+                f"max={self.max_fee / GWEI:.1f} gwei")
+
+
+def next_base_fee(base_fee: int, gas_used_ratio: float) -> int:
+    """EIP-1559 base fee of the block after one with `base_fee` and `gas_used_ratio`."""
+    delta = base_fee * (gas_used_ratio - 0.5) / 0.5 / BASE_FEE_MAX_CHANGE_DENOMINATOR
+    if gas_used_ratio > 0.5:
+        delta = max(delta, 1)
+    return max(0, int(base_fee + delta))
+
+
+class Eip1559FeeEstimator:
+    """
+    Fee estimates from recent blocks (eth_feeHistory).
+
+    Features:
+    - Next-block base fee plus worst-case growth headroom
+    - Priority fee percentile per urgency level, with a floor
+    - Fee cap: estimates above it are clamped, or refused if the cap is
+      below what the next block requires
+    """
+
+    def __init__(
+        self,
+        web3: Web3,
+        block_count: int = 20,
+        blocks_ahead: int = 3,
+        percentiles: Optional[Dict[str, int]] = None,
+        min_priority_fee_gwei: float = 30.0,
+        max_fee_cap_gwei: Optional[float] = None,
+        cache_seconds: float = 2.0,
+        clock: Optional[Callable[[], float]] = None
+    ):
+        """
+        Initialize the estimator.
+
+        Args:
+            web3: Web3 instance connected to Polygon RPC
+            block_count: Recent blocks sampled for base fee and tips
+            blocks_ahead: Blocks of worst-case base fee growth covered by maxFeePerGas
+            percentiles: Urgency -> tip percentile (default low=25, normal=50, high=90)
+            min_priority_fee_gwei: Tip floor (Polygon nodes drop txs below their minimum tip)
+            max_fee_cap_gwei: Upper bound for maxFeePerGas (None: no cap)
+            cache_seconds: How long a fee history is reused
+            clock: Time source for the cache
+        """
+        self.web3 = web3
+        self.block_count = block_count
+        self.blocks_ahead = max(1, blocks_ahead)
+        self.percentiles = dict(percentiles or DEFAULT_PERCENTILES)
+        self.min_priority_fee = int(min_priority_fee_gwei * GWEI)
+        self.max_fee_cap = int(max_fee_cap_gwei * GWEI) if max_fee_cap_gwei else None
+        self.cache_seconds = cache_seconds
+        self._clock = clock or time.monotonic
+        self._history: Optional[Tuple[List[int], List[float], List[List[int]]]] = None
+        self._history_at = 0.0
+
+    # ========================================================================
+    # Estimates
+    # ========================================================================
+
+    def estimate(self, urgency: str = "normal") -> FeeEstimate:
+        """
+        Fees for a new transaction.
+
+        Raises:
+            FeeCapExceededError: If the next block's base fee plus the tip floor exceeds the cap
+        """
+        if urgency not in self.percentiles:
+            raise ValueError(f"Unknown urgency '{urgency}' (available: {', '.join(self.percentiles)})")
+        history = self._fee_history()
+        if history is None:
+            return self._legacy_estimate(urgency)
+
+        base_fees, _, _ = history
+        base_fee = base_fees[-1]
+        priority = self._priority_fee(urgency, history)
+        max_fee = self.predict_max_base_fee(base_fee) + priority
+        return self._apply_cap(FeeEstimate(base_fee, priority, max_fee, urgency))
+
+    def predict_max_base_fee(self, next_block_base_fee: int) -> int:
+        """Highest base fee possible `blocks_ahead` blocks out (each block +12.5% at most)."""
+        growth = (1 + 1 / BASE_FEE_MAX_CHANGE_DENOMINATOR) ** (self.blocks_ahead - 1)
+        return int(math.ceil(next_block_base_fee * growth))
+
+    # ========================================================================
+    # Internals
+    # ========================================================================
+
+    def _fee_history(self) -> Optional[Tuple[List[int], List[float], List[List[int]]]]:
+        """(base fees incl. next block, gas used ratios, tips per percentile) or None if unsupported."""
+        now = self._clock()
+        if self._history is not None and now - self._history_at < self.cache_seconds:
+            return self._history
+
+        percentiles = sorted(set(self.percentiles.values()))
+        try:
+            history = self.web3.eth.fee_history(self.block_count, "latest", percentiles)
+            base_fees = [int(fee) for fee in history["baseFeePerGas"]]
+            ratios = [float(ratio) for ratio in history["gasUsedRatio"]]
+            rewards = [[int(tip) for tip in block] for block in history.get("reward") or []]
+            if not base_fees or not ratios:
+                raise ValueError("empty fee history")
+        except Exception as e:
+            logger.debug(f"Fee history unavailable ({e}); using legacy gas price")
+            return None
+
+        if len(base_fees) == len(ratios):
+            # Node did not include the next block's base fee: derive it
+            base_fees.append(next_base_fee(base_fees[-1], ratios[-1]))
+        self._history = (base_fees, ratios, rewards)
+        self._history_at = now
+        return self._history
+
+    def _priority_fee(self, urgency: str, history) -> int:
+        _, ratios, rewards = history
+        index = sorted(set(self.percentiles.values())).index(self.percentiles[urgency])
+        # Empty blocks report zero tips and would drag the percentile down
+        tips = [block[index] for block, ratio in zip(rewards, ratios) if ratio > 0 and len(block) > index]
+        tip = int(statistics.median(tips)) if tips else 0
+        return max(tip, self.min_priority_fee)
+
+    def _legacy_estimate(self, urgency: str) -> FeeEstimate:
+        gas_price = int(self.web3.eth.gas_price)
+        return self._apply_cap(FeeEstimate(gas_price, gas_price, gas_price, urgency, legacy=True))
+
+    def _apply_cap(self, estimate: FeeEstimate) -> FeeEstimate:
+        if self.max_fee_cap is None or estimate.max_fee <= self.max_fee_cap:
+            return estimate
+        floor = estimate.max_fee if estimate.legacy else estimate.base_fee + estimate.max_priority_fee
+        if floor > self.max_fee_cap:
+            raise FeeCapExceededError(
+                f"Fees {estimate.describe()} exceed cap {self.max_fee_cap / GWEI:.1f} gwei"
+            )
+        # Still includable in the next block; only the growth headroom is cut
+        estimate.max_fee = self.max_fee_cap
+        return estimate
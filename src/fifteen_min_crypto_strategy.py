@@ -15,11 +15,11 @@ Based on research from:
 import asyncio
 import aiohttp
 import logging
-from decimal import Decimal
+from decimal import Decimal, ROUND_FLOOR
 from datetime import datetime, timedelta, timezone
 from typing import Dict, Optional, List, Tuple, Any
 from types import SimpleNamespace
-from dataclasses import dataclass
+from dataclasses import dataclass, replace
 from collections import deque
 import json
 import time
@@ -33,16 +33,23 @@ from src.llm_decision_engine_v2 import LLMDecisionEngineV2, MarketContext as Mar
 
 # PHASE 2 OPTIMIZATIONS
 from src.multi_timeframe_analyzer import MultiTimeframeAnalyzer
+from src.fair_value_pricer import FairValue, FairValuePricer
+from src.correlation_analyzer import CorrelationAnalyzer, Position as CorrelatedPosition
 from src.order_book_analyzer import OrderBookAnalyzer
+from src.order_manager import TERMINAL_ORDER_STATUSES
 from src.historical_success_tracker import HistoricalSuccessTracker
 
 # PHASE 3 OPTIMIZATIONS
-from src.reinforcement_learning_engine import ReinforcementLearningEngine
+from src.continuous_rl_engine import ContinuousRLEngine, MarketFeatures, SPOT_MOVE_SECONDS
+from src.trade_outcome_store import TradeOutcomeStore
 from src.ensemble_decision_engine import EnsembleDecisionEngine
 from src.context_optimizer import ContextOptimizer
 
 logger = logging.getLogger(__name__)
 
+# Assets with 15-minute / 1-hour up/down markets
+UPDOWN_ASSETS = ("btc", "eth", "sol", "xrp")
+
 
 @dataclass
 class CryptoMarket:
@@ -74,6 +81,151 @@ class Position:
     highest_price: Decimal = Decimal("0")  # PHASE 3A: Track peak price for trailing stop
     used_orderbook_entry: bool = False  # Task 2.2: Track if orderbook was used for entry
     confidence: Decimal = Decimal("50")  # TASK 6.4: Track confidence for dynamic trailing stop
+    features: Optional[Dict[str, float]] = None  # Entry MarketFeatures, logged as RL experience on close
+
+
+@dataclass
+class MakerEntry:
+    """A resting post-only entry order; it becomes a Position as it fills."""
+    order: Any  # src.order_manager.Order
+    market: CryptoMarket
+    side: str  # "UP" or "DOWN"
+    strategy: str
+    max_price: Decimal  # Never requote above the price the entry signal was priced at
+    used_orderbook: bool = False
+    confidence: Optional[Decimal] = None
+    tracked_size: Decimal = Decimal("0")  # Matched size already added to positions
+    features: Optional[Dict[str, float]] = None  # MarketFeatures when the order was posted
+    hedge_side: Optional[str] = None  # Sum-to-one: the other leg, taken as this one fills
+    hedge_price: Optional[Decimal] = None  # Most the hedge leg may cost (its price when the pair was found)
+    unhedged: Decimal = Decimal("0")  # Filled shares the hedge leg does not cover yet
+
+
+def updown_slugs(now: int, assets: Tuple[str, ...] = UPDOWN_ASSETS) -> List[str]:
+    """
+    Build the Gamma event slugs for the current 15-minute and 1-hour windows.
+    
+    Args:
+        now: Unix timestamp
+        assets: Lowercase asset symbols
+        
+    Returns:
+        Slugs in the form {asset}-updown-{15m|1h}-{window_start}
+    """
+    current_15m = (now // 900) * 900  # 15-minute intervals (900 seconds)
+    current_1h = (now // 3600) * 3600  # 1-hour intervals (3600 seconds)
+    
+    slugs = []
+    for asset in assets:
+        slugs.append(f"{asset}-updown-15m-{current_15m}")
+        slugs.append(f"{asset}-updown-1h-{current_1h}")
+    return slugs
+
+
+async def fetch_updown_markets(slugs: List[str]) -> List[CryptoMarket]:
+    """
+    Fetch the currently trading up/down markets for the given Gamma event slugs.
+    
+    Shared by FifteenMinuteCryptoStrategy and MarketMakingStrategy.
+    
+    Args:
+        slugs: Event slugs such as "btc-updown-15m-1767268800"
+        
+    Returns:
+        Unique markets whose trading window is open now
+    """
+    markets = []
+    now = datetime.now(timezone.utc)
+    
+    async with aiohttp.ClientSession() as session:
+        for slug in slugs:
+            asset = slug.split("-")[0].upper()
+            url = f"https://gamma-api.polymarket.com/events/slug/{slug}"
+
+            try:
+                async with session.get(url, timeout=10) as resp:
+                    if resp.status == 200:
+                        data = await resp.json()
+
+                        event_markets = data.get("markets", [])
+                        for m in event_markets:
+                            # Extract token IDs
+                            token_ids = m.get("clobTokenIds", [])
+                            if isinstance(token_ids, str):
+                                try:
+                                    token_ids = json.loads(token_ids)
+                                except Exception as e:
+                                    logger.error(f"Failed to parse token_ids: {e}")
+                                    continue
+
+                            if len(token_ids) >= 2:
+                                up_token = token_ids[0]  # First is Up/Yes
+                                down_token = token_ids[1]  # Second is Down/No
+                            else:
+                                continue
+
+                            # Extract prices - handle JSON strings properly
+                            prices = m.get("outcomePrices", ["0.5", "0.5"])
+                            try:
+                                # Handle case where prices might be JSON string
+                                if isinstance(prices, str):
+                                    prices = json.loads(prices)
+                                up_price = Decimal(str(prices[0]).strip('"'))
+                                down_price = Decimal(str(prices[1]).strip('"'))
+                            except:
+                                up_price = Decimal("0.5")
+                                down_price = Decimal("0.5")
+
+                            # Parse end time
+                            end_time_str = m.get("endDate", "")
+                            try:
+                                end_time = datetime.fromisoformat(end_time_str.replace("Z", "+00:00"))
+                            except:
+                                end_time = datetime.now(timezone.utc) + timedelta(minutes=15)
+
+                            # Check if market is CURRENTLY tradeable
+                            # (not closed AND end_time is in the future)
+                            is_closed = m.get("closed", False)
+                            is_active = m.get("active", True)
+                            is_trading = end_time > now and is_active and not is_closed
+
+                            if is_trading:
+                                # Extract tick size defaults to 0.01 if missing
+                                tick_size = str(m.get("minimum_tick_size", "0.01"))
+
+                                markets.append(CryptoMarket(
+                                    market_id=m.get("conditionId", ""),
+                                    question=m.get("question", data.get("title", "")),
+                                    asset=asset.upper(),
+                                    up_token_id=up_token,
+                                    down_token_id=down_token,
+                                    up_price=up_price,
+                                    down_price=down_price,
+                                    end_time=end_time,
+                                    neg_risk=m.get("neg_risk", True),
+                                    tick_size=tick_size
+                                ))
+
+                                logger.info(
+                                    f"🎯 CURRENT {asset.upper()} market: "
+                                    f"Up=${up_price:.2f}, Down=${down_price:.2f}, "
+                                    f"Ends: {end_time.strftime('%H:%M:%S')} UTC"
+                                )
+                    elif resp.status != 404:
+                        logger.debug(f"Slug {slug}: Status {resp.status}")
+            except asyncio.TimeoutError:
+                logger.debug(f"Timeout fetching {slug}")
+            except Exception as e:
+                logger.debug(f"Error fetching {slug}: {e}")
+
+    # Remove duplicates (same conditionId)
+    seen_ids = set()
+    unique_markets = []
+    for m in markets:
+        if m.market_id not in seen_ids:
+            seen_ids.add(m.market_id)
+            unique_markets.append(m)
+    return unique_markets
 
 
 class BinancePriceFeed:
@@ -105,6 +257,9 @@ class BinancePriceFeed:
             "XRP": deque(maxlen=5000),
         }
         self.avg_volume: Dict[str, Decimal] = {}
+        
+        # Optional MarketDataRecorder; every tick is recorded for replay when set
+        self.recorder = None
     
     async def start(self):
         """Start the Binance WebSocket feed."""
@@ -177,14 +332,11 @@ class BinancePriceFeed:
             price = Decimal(trade.get("p", "0"))
             volume = Decimal(trade.get("q", "0"))  # OPTIMIZATION: Get trade volume
             
-            if symbol == "BTCUSDT":
-                self._update_price("BTC", price, volume)
-            elif symbol == "ETHUSDT":
-                self._update_price("ETH", price, volume)
-            elif symbol == "SOLUSDT":
-                self._update_price("SOL", price, volume)
-            elif symbol == "XRPUSDT":
-                self._update_price("XRP", price, volume)
+            asset = {"BTCUSDT": "BTC", "ETHUSDT": "ETH", "SOLUSDT": "SOL", "XRPUSDT": "XRP"}.get(symbol)
+            if asset:
+                self._update_price(asset, price, volume)
+                if self.recorder is not None:
+                    self.recorder.record_binance_tick(asset, price, volume, exchange_ts=trade.get("T"))
                 
         except Exception as e:
             logger.debug(f"Error processing Binance message: {e}")
@@ -203,6 +355,14 @@ class BinancePriceFeed:
                 recent_volumes = [v for _, v in list(self.volume_history[asset])[-30:]]
                 self.avg_volume[asset] = sum(recent_volumes) / len(recent_volumes)
     
+    def staleness_seconds(self) -> Dict[str, float]:
+        """Seconds since the last tick per asset (assets without ticks yet are left out)."""
+        now = datetime.now()
+        return {
+            asset: (now - history[-1][0]).total_seconds()
+            for asset, history in self.price_history.items() if history
+        }
+    
     def get_price_change(self, asset: str, seconds: int = 10) -> Optional[Decimal]:
         """
         Calculate price change over the last N seconds.
@@ -291,6 +451,20 @@ class FifteenMinuteCryptoStrategy:
     # SAFETY: Minimum time remaining before market close to allow new entries
     # Prevents entering trades that can't exit gracefully before expiry
     MIN_ENTRY_TIME_MINUTES = 0.5  # Don't enter if market closes in < 30 seconds (was 2, originally 5)
+    MAKER_PULL_MINUTES = 2.0  # Cancel resting maker entries this close to market close
+    MAKER_ENTRY_STRATEGIES = ("directional", "sum_to_one")  # Entries that may rest as maker orders
+    MAKER_HEDGE_MIN_SHARES = Decimal("5")  # Sum-to-one fills are hedged once this many are unhedged (CLOB minimum)
+    
+    # Entry checks by name -> method. Order is configurable via entry_order.
+    ENTRY_CHECKS = {
+        "flash_crash": "check_flash_crash",
+        "latency": "check_latency_arbitrage",
+        "directional": "check_directional_trade",
+        "sum_to_one": "check_sum_to_one_arbitrage",
+    }
+    
+    # Default priority: flash crash (highest ROI) -> latency -> directional -> sum-to-one (fallback)
+    DEFAULT_ENTRY_ORDER = ["flash_crash", "latency", "directional", "sum_to_one"]
     
     def __init__(
         self,
@@ -303,7 +477,22 @@ class FifteenMinuteCryptoStrategy:
         dry_run: bool = False,
         llm_decision_engine: Optional[Any] = None,  # Added LLM support
         enable_adaptive_learning: bool = True,  # NEW: Enable machine learning
-        initial_capital: Optional[float] = None  # NEW: Actual balance for risk management
+        initial_capital: Optional[float] = None,  # NEW: Actual balance for risk management
+        entry_order: Optional[List[str]] = None,  # Entry checks to run, in priority order
+        order_manager: Optional[Any] = None,  # OrderManager for resting maker entries
+        maker_entries: bool = False,  # Rest directional entries and sum-to-one legs as post-only orders
+        maker_ttl_seconds: int = 120,  # GTD lifetime of a resting maker entry
+        flash_crash_drop_threshold: float = 0.15,  # Binance move that counts as a flash crash/pump
+        flash_crash_lookback_seconds: float = 3.0,  # Window the move has to happen in
+        binance_feed: Optional["BinancePriceFeed"] = None,  # Shared Binance feed (one is created and owned if None)
+        fair_value_pricer: Optional[FairValuePricer] = None,  # Shared FairValuePricer
+        correlation_analyzer: Optional[CorrelationAnalyzer] = None,  # Correlated-exposure limits on entries
+        ledger: Optional[Any] = None,  # PositionLedger recording fills
+        redemption_service: Optional[Any] = None,  # RedemptionService redeeming orphaned shares
+        metrics: Optional[Any] = None,  # MonitoringSystem exporting strategy metrics
+        rl_engine: Optional[Any] = None,  # ContinuousRLEngine (default: newest frozen policy, no exploration)
+        outcome_store: Optional[Any] = None,  # TradeOutcomeStore (default: in memory)
+        positions_file: str = "data/active_positions.json"  # Open positions kept across restarts
     ):
         """
         Initialize the 15-minute crypto trading strategy.
@@ -317,7 +506,31 @@ class FifteenMinuteCryptoStrategy:
             sum_to_one_threshold: Threshold for sum-to-one arbitrage
             dry_run: If True, simulate trades without executing
             llm_decision_engine: Instance of LLMDecisionEngine for directional trades
+            entry_order: Names from ENTRY_CHECKS in priority order (default: DEFAULT_ENTRY_ORDER)
+            order_manager: OrderManager used to post and track resting maker orders
+            maker_entries: Post directional entries and one leg of each sum-to-one pair as post-only GTD orders
+            maker_ttl_seconds: Lifetime of each resting maker entry
+            flash_crash_drop_threshold: Fractional Binance move within the lookback that triggers check_flash_crash
+            flash_crash_lookback_seconds: Lookback window of check_flash_crash
+            binance_feed: Shared BinancePriceFeed; started and stopped by whoever created it
+            fair_value_pricer: Shared FairValuePricer; its volatility analyzer becomes multi_tf_analyzer
+                (default: one on this strategy's feed and analyzer)
+            correlation_analyzer: CorrelationAnalyzer checked before every entry (optional, no limits if None)
+            ledger: PositionLedger that records every fill (optional)
+            redemption_service: RedemptionService that redeems orphaned shares after resolution (optional)
+            metrics: MonitoringSystem that exports opportunities, entries, exits and latency (optional)
+            rl_engine: ContinuousRLEngine voting in the ensemble and logging trade experience (optional)
+            outcome_store: TradeOutcomeStore closed trades are recorded to; the learning engines subscribe to it
+                (default: an in-memory store, nothing persisted)
+            positions_file: JSON file persisting open positions (one per wallet)
         """
+        self.entry_order = list(entry_order) if entry_order is not None else list(self.DEFAULT_ENTRY_ORDER)
+        unknown = [name for name in self.entry_order if name not in self.ENTRY_CHECKS]
+        if unknown:
+            raise ValueError(
+                f"Unknown entry checks: {unknown} (available: {list(self.ENTRY_CHECKS)})"
+            )
+        
         self.clob_client = clob_client
         self.trade_size = Decimal(str(trade_size))
         self.take_profit_pct = Decimal(str(take_profit_pct))
@@ -326,21 +539,57 @@ class FifteenMinuteCryptoStrategy:
         self.sum_to_one_threshold = Decimal(str(sum_to_one_threshold))
         self.dry_run = dry_run
         self.llm_decision_engine = llm_decision_engine
+        self.recorder = None  # Set by attach_recorder() in recorder mode
+        self.ledger = ledger
+        self.redemption_service = redemption_service
+        self.metrics = metrics
+        
+        # Maker entries: resting post-only orders tracked until filled, pulled or expired
+        self.order_manager = order_manager
+        self.maker_entries = maker_entries and order_manager is not None
+        self.maker_ttl_seconds = maker_ttl_seconds
+        self.maker_orders: Dict[str, MakerEntry] = {}
+        if maker_entries and order_manager is None:
+            logger.warning("⚠️ maker_entries requested without an OrderManager - using taker entries")
+        
+        # Flash crash detection (hot-reloadable)
+        self.flash_crash_drop_threshold = flash_crash_drop_threshold
+        self.flash_crash_lookback_seconds = flash_crash_lookback_seconds
         
         # Binance price feed for latency arbitrage
-        self.binance_feed = BinancePriceFeed()
+        self.owns_feed = binance_feed is None
+        self.binance_feed = binance_feed or BinancePriceFeed()
         
         # PHASE 2: Multi-timeframe analyzer for better signals
-        self.multi_tf_analyzer = MultiTimeframeAnalyzer()
+        self.multi_tf_analyzer = (
+            fair_value_pricer.volatility_analyzer
+            if fair_value_pricer is not None and fair_value_pricer.volatility_analyzer is not None
+            else MultiTimeframeAnalyzer()
+        )
+        
+        # Fair value of the up/down markets, shared with the other strategies (docs/FAIR_VALUE_PRICER.md)
+        self.fair_value_pricer = fair_value_pricer or FairValuePricer(
+            price_feed=self.binance_feed, volatility_analyzer=self.multi_tf_analyzer
+        )
+        
+        # Single-asset and correlated exposure limits (docs/CORRELATION.md)
+        self.correlation_analyzer = correlation_analyzer
         
         # PHASE 2: Order book analyzer for slippage prevention
         self.order_book_analyzer = OrderBookAnalyzer(clob_client)
         
+        # Closed trades are recorded once, here; the learning engines subscribe (docs/LEARNING_STORE.md).
+        # Without an injected store nothing is written to disk and no legacy files are imported.
+        self.outcome_store = outcome_store if outcome_store is not None else TradeOutcomeStore(
+            ":memory:",
+            source="dry_run" if dry_run else "live"
+        )
+        
         # PHASE 2: Historical success tracker
-        self.success_tracker = HistoricalSuccessTracker()
+        self.success_tracker = HistoricalSuccessTracker(store=self.outcome_store)
         
-        # PHASE 3: Reinforcement Learning Engine
-        self.rl_engine = ReinforcementLearningEngine()
+        # PHASE 3: Reinforcement Learning Engine (frozen policy; docs/RL_POLICIES.md)
+        self.rl_engine = rl_engine if rl_engine is not None else ContinuousRLEngine()
         
         # TASK 5.2: Fast Execution Engine with market data caching (MUST be before ensemble_engine)
         from src.fast_execution_engine import FastExecutionEngine
@@ -375,7 +624,7 @@ class FifteenMinuteCryptoStrategy:
         self.positions: Dict[str, Position] = {}
         
         # Position persistence file
-        self.positions_file = "data/active_positions.json"
+        self.positions_file = positions_file
         
         # Load any existing positions from disk
         self._load_positions()
@@ -399,6 +648,11 @@ class FifteenMinuteCryptoStrategy:
             "orderbook_losses": 0,
             "fallback_wins": 0,
             "fallback_losses": 0,
+            # Maker entries (resting post-only orders)
+            "maker_orders_posted": 0,
+            "maker_fills": 0,
+            "maker_hedges": 0,  # Sum-to-one hedge legs taken after maker fills
+            "unhedged_maker_fills": 0,  # Hedges that did not fill (resting leg pulled)
             # Task 5.7: Execution time tracking
             "total_execution_time_ms": 0,
             "slow_executions": 0,  # Count of executions > 1 second
@@ -475,9 +729,9 @@ class FifteenMinuteCryptoStrategy:
         self.adaptive_learning = None
         if enable_adaptive_learning:
             self.adaptive_learning = AdaptiveLearningEngine(
-                data_file="data/adaptive_learning.json",
                 learning_rate=0.1,  # 10% adjustment rate
-                min_trades_for_learning=10  # Start learning after 10 trades
+                min_trades_for_learning=10,  # Start learning after 10 trades
+                store=self.outcome_store
             )
             logger.info("🧠 Adaptive Learning Engine enabled - bot will get smarter over time!")
             
@@ -495,7 +749,7 @@ class FifteenMinuteCryptoStrategy:
         self.super_smart = None
         if enable_adaptive_learning:
             from src.super_smart_learning import SuperSmartLearning
-            self.super_smart = SuperSmartLearning(data_file="data/super_smart_learning.json")
+            self.super_smart = SuperSmartLearning(store=self.outcome_store)
             
             # Use super smart parameters if we have enough data
             if self.super_smart.total_trades >= 5:
@@ -508,6 +762,11 @@ class FifteenMinuteCryptoStrategy:
                 logger.info(f"   Best strategy: {self.super_smart.get_best_strategy()}")
                 logger.info(f"   Best asset: {self.super_smart.get_best_asset()}")
         
+        # Every closed trade reaches the learning engines and the Kelly system through the store
+        for engine in (self.super_smart, self.rl_engine, self.adaptive_learning, self.success_tracker, self.dynamic_params):
+            if engine is not None and hasattr(engine, "on_trade_outcome"):
+                self.outcome_store.subscribe(engine.on_trade_outcome)
+        
         logger.info("=" * 80)
         logger.info("15-MINUTE CRYPTO TRADING STRATEGY INITIALIZED")
         logger.info("=" * 80)
@@ -516,6 +775,7 @@ class FifteenMinuteCryptoStrategy:
         logger.info(f"Stop loss: {stop_loss_pct * 100}% (BALANCED: Control losses)")
         logger.info(f"Max positions: {max_positions}")
         logger.info(f"Sum-to-one threshold: ${sum_to_one_threshold}")
+        logger.info(f"Entry order: {' -> '.join(self.entry_order) or 'none (exits only)'}")
         logger.info(f"Time-based exit: 12 minutes (FIXED: exit before market closes)")
         logger.info(f"Market closing exit: 2 minutes before close (FIXED: forced exit)")
         logger.info(f"Dry run: {dry_run}")
@@ -577,7 +837,8 @@ class FifteenMinuteCryptoStrategy:
                         neg_risk=pos_data.get('neg_risk', True),
                         highest_price=Decimal(str(pos_data.get('highest_price', pos_data['entry_price']))),
                         used_orderbook_entry=pos_data.get('used_orderbook_entry', False),  # Task 2.2
-                        confidence=Decimal(str(pos_data.get('confidence', '50')))  # TASK 6.4
+                        confidence=Decimal(str(pos_data.get('confidence', '50'))),  # TASK 6.4
+                        features=pos_data.get('features')
                     )
                 
                 logger.info(f"📂 Loaded {len(self.positions)} positions from disk")
@@ -591,7 +852,7 @@ class FifteenMinuteCryptoStrategy:
                         phantom_positions.append(token_id)
                         # Record as loss to learning engines (test positions should be avoided)
                         self._record_trade_outcome(
-                            asset=pos.asset, side=pos.side,
+                            asset=pos.asset, side=pos.side, market_id=pos.market_id,
                             strategy=pos.strategy, entry_price=pos.entry_price,
                             exit_price=pos.entry_price,  # Assume breakeven
                             profit_pct=Decimal('-0.01'),  # Small loss for test positions
@@ -606,7 +867,7 @@ class FifteenMinuteCryptoStrategy:
                         phantom_positions.append(token_id)
                         # Record as loss to learning engines (stale positions indicate a problem)
                         self._record_trade_outcome(
-                            asset=pos.asset, side=pos.side,
+                            asset=pos.asset, side=pos.side, market_id=pos.market_id,
                             strategy=pos.strategy, entry_price=pos.entry_price,
                             exit_price=pos.entry_price,  # Assume breakeven
                             profit_pct=Decimal('-0.02'),  # Estimate 2% loss (fees + opportunity cost)
@@ -709,7 +970,18 @@ class FifteenMinuteCryptoStrategy:
                 )
                 logger.debug(f"🎯 Medium confidence ({confidence:.0f}%) → Scaled stop: {self.trailing_stop_pct*100:.1f}%")
     
-    def _track_execution_time(self, execution_time_ms: float, strategy: str, asset: str) -> None:
+    def _latest_tick_time(self, asset: str) -> Optional[datetime]:
+        """Timestamp of the newest Binance tick for an asset (None before the first tick)."""
+        history = self.binance_feed.price_history.get(asset)
+        return history[-1][0] if history else None
+    
+    def _track_execution_time(
+        self,
+        execution_time_ms: float,
+        strategy: str,
+        asset: str,
+        tick_time: Optional[datetime] = None
+    ) -> None:
         """
         Track execution time from signal detection to order placement.
 
@@ -726,7 +998,13 @@ class FifteenMinuteCryptoStrategy:
             execution_time_ms: Execution time in milliseconds
             strategy: Strategy name (sum_to_one, latency, directional)
             asset: Asset name (BTC, ETH, SOL, XRP)
+            tick_time: Binance tick the signal was computed from (tick-to-order metric skipped if None)
         """
+        if self.metrics is not None and tick_time is not None:
+            self.metrics.record_tick_to_order(
+                strategy, asset, (datetime.now() - tick_time).total_seconds() * 1000
+            )
+        
         # Update total execution time
         self.stats["total_execution_time_ms"] += execution_time_ms
 
@@ -786,6 +1064,35 @@ class FifteenMinuteCryptoStrategy:
 
 
     
+    def _record_opportunity(self, strategy: str, asset: str) -> None:
+        """Count an entry signal in the exported metrics."""
+        if self.metrics is not None:
+            self.metrics.record_strategy_opportunity(strategy, asset)
+    
+    def _record_entry(self, strategy: str, asset: str) -> None:
+        """Count an opened position in the exported metrics."""
+        if self.metrics is not None:
+            self.metrics.record_strategy_entry(strategy, asset)
+    
+    async def _entry_features(self, market: CryptoMarket, side: str) -> Optional[Dict[str, float]]:
+        """RL MarketFeatures of buying `side` now, or None when the token's book is unavailable."""
+        token_id = market.up_token_id if side == "UP" else market.down_token_id
+        try:
+            book = await self.order_book_analyzer.get_order_book(token_id)
+            if book is None:
+                return None
+            return MarketFeatures.observe(
+                side,
+                spot_move=self.binance_feed.get_price_change(market.asset, seconds=SPOT_MOVE_SECONDS),
+                minutes_to_close=(market.end_time - datetime.now(timezone.utc)).total_seconds() / 60,
+                bid_depth=book.bid_depth,
+                ask_depth=book.ask_depth,
+                spread=book.spread
+            ).to_dict()
+        except Exception as e:
+            logger.debug(f"RL features unavailable for {market.asset} {side}: {e}")
+            return None
+    
     def _save_positions(self):
         """Save positions to disk for persistence across restarts."""
         import json
@@ -810,7 +1117,8 @@ class FifteenMinuteCryptoStrategy:
                     'neg_risk': pos.neg_risk,
                     'highest_price': str(pos.highest_price),
                     'used_orderbook_entry': pos.used_orderbook_entry,  # Task 2.2
-                    'confidence': str(pos.confidence)  # TASK 6.4
+                    'confidence': str(pos.confidence),  # TASK 6.4
+                    'features': pos.features
                 }
             
             with open(self.positions_file, 'w') as f:
@@ -1023,9 +1331,27 @@ class FifteenMinuteCryptoStrategy:
         
         logger.info("=" * 80)
     
+    def attach_recorder(self, recorder) -> None:
+        """
+        Record all market data this strategy sees for later replay.
+        
+        Wires the recorder into the Binance feed, the Polymarket WebSocket feed and
+        the REST order book analyzer. Discovered markets are recorded and their
+        tokens subscribed on the WebSocket so full books are captured.
+        
+        Args:
+            recorder: MarketDataRecorder instance
+        """
+        self.recorder = recorder
+        self.binance_feed.recorder = recorder
+        self.polymarket_ws_feed.recorder = recorder
+        self.order_book_analyzer.recorder = recorder
+        logger.info("📼 Market data recording enabled")
+    
     async def start(self):
-        """Start the strategy (including Binance feed and Polymarket WebSocket)."""
-        await self.binance_feed.start()
+        """Start the strategy (including an owned Binance feed and Polymarket WebSocket)."""
+        if self.owns_feed:
+            await self.binance_feed.start()
         # TASK 5.8: Start Polymarket WebSocket feed for real-time prices
         await self.polymarket_ws_feed.connect()
         asyncio.create_task(self.polymarket_ws_feed.run())
@@ -1042,7 +1368,10 @@ class FifteenMinuteCryptoStrategy:
     
     async def stop(self):
         """Stop the strategy."""
-        await self.binance_feed.stop()
+        if self.maker_orders:
+            await self._cancel_maker_orders(reason="strategy stopped")
+        if self.owns_feed:
+            await self.binance_feed.stop()
         # TASK 5.8: Stop Polymarket WebSocket feed
         await self.polymarket_ws_feed.disconnect()
         logger.info("✅ Polymarket WebSocket feed stopped")
@@ -1136,130 +1465,22 @@ class FifteenMinuteCryptoStrategy:
         
         # Cache miss - fetch fresh data
         logger.debug("🔄 Fetching fresh market data (cache miss)")
-        markets = []
-        
-        # Assets to search for
-        assets = ["btc", "eth", "sol", "xrp"]
-        
-        # Calculate current intervals
-        now = int(time.time())
-        now_dt = datetime.now(timezone.utc)
-        
-        # 15-minute intervals (900 seconds)
-        current_15m = (now // 900) * 900
-        
-        # 1-hour intervals (3600 seconds)
-        current_1h = (now // 3600) * 3600
         
         # Build list of slugs to try (15min + 1hr)
         slugs_to_try = []
-        for asset in assets:
-            slug_15m = f"{asset}-updown-15m-{current_15m}"
-            slug_1h = f"{asset}-updown-1h-{current_1h}"
+        for slug in updown_slugs(int(time.time())):
+            interval = slug.split("-")[2]
             
             # TASK 10.2: Validate and log generated slugs
-            if self._validate_market_slug(slug_15m):
-                slugs_to_try.append(slug_15m)
-                logger.info(f"🔍 Generated 15m slug: {slug_15m}")
+            if self._validate_market_slug(slug):
+                slugs_to_try.append(slug)
+                logger.info(f"🔍 Generated {interval} slug: {slug}")
             else:
-                logger.error(f"❌ Invalid 15m slug generated: {slug_15m}")
-            
-            if self._validate_market_slug(slug_1h):
-                slugs_to_try.append(slug_1h)
-                logger.info(f"🔍 Generated 1h slug: {slug_1h}")
-            else:
-                logger.error(f"❌ Invalid 1h slug generated: {slug_1h}")
+                logger.error(f"❌ Invalid {interval} slug generated: {slug}")
         
         logger.info(f"📋 Total slugs to fetch: {len(slugs_to_try)}")
         
-        async with aiohttp.ClientSession() as session:
-            for slug in slugs_to_try:
-                asset = slug.split("-")[0].upper()
-                url = f"https://gamma-api.polymarket.com/events/slug/{slug}"
-                
-                try:
-                    async with session.get(url, timeout=10) as resp:
-                        if resp.status == 200:
-                            data = await resp.json()
-                            
-                            event_markets = data.get("markets", [])
-                            for m in event_markets:
-                                # Extract token IDs
-                                token_ids = m.get("clobTokenIds", [])
-                                if isinstance(token_ids, str):
-                                    try:
-                                        token_ids = json.loads(token_ids)
-                                    except Exception as e:
-                                        logger.error(f"Failed to parse token_ids: {e}")
-                                        continue
-                                    
-                                if len(token_ids) >= 2:
-                                    up_token = token_ids[0]  # First is Up/Yes
-                                    down_token = token_ids[1]  # Second is Down/No
-                                else:
-                                    continue
-                                
-                                # Extract prices - handle JSON strings properly
-                                prices = m.get("outcomePrices", ["0.5", "0.5"])
-                                try:
-                                    # Handle case where prices might be JSON string
-                                    if isinstance(prices, str):
-                                        prices = json.loads(prices)
-                                    up_price = Decimal(str(prices[0]).strip('"'))
-                                    down_price = Decimal(str(prices[1]).strip('"'))
-                                except:
-                                    up_price = Decimal("0.5")
-                                    down_price = Decimal("0.5")
-                                
-                                # Parse end time
-                                end_time_str = m.get("endDate", "")
-                                try:
-                                    end_time = datetime.fromisoformat(end_time_str.replace("Z", "+00:00"))
-                                except:
-                                    end_time = datetime.now(timezone.utc) + timedelta(minutes=15)
-                                
-                                # Check if market is CURRENTLY tradeable
-                                # (not closed AND end_time is in the future)
-                                is_closed = m.get("closed", False)
-                                is_active = m.get("active", True)
-                                is_trading = end_time > now_dt and is_active and not is_closed
-                                
-                                if is_trading:
-                                    # Extract tick size defaults to 0.01 if missing
-                                    tick_size = str(m.get("minimum_tick_size", "0.01"))
-                                    
-                                    markets.append(CryptoMarket(
-                                        market_id=m.get("conditionId", ""),
-                                        question=m.get("question", data.get("title", "")),
-                                        asset=asset.upper(),
-                                        up_token_id=up_token,
-                                        down_token_id=down_token,
-                                        up_price=up_price,
-                                        down_price=down_price,
-                                        end_time=end_time,
-                                        neg_risk=m.get("neg_risk", True),
-                                        tick_size=tick_size
-                                    ))
-                                    
-                                    logger.info(
-                                        f"🎯 CURRENT {asset.upper()} market: "
-                                        f"Up=${up_price:.2f}, Down=${down_price:.2f}, "
-                                        f"Ends: {end_time.strftime('%H:%M:%S')} UTC"
-                                    )
-                        elif resp.status != 404:
-                            logger.debug(f"Slug {slug}: Status {resp.status}")
-                except asyncio.TimeoutError:
-                    logger.debug(f"Timeout fetching {slug}")
-                except Exception as e:
-                    logger.debug(f"Error fetching {slug}: {e}")
-        
-        # Remove duplicates (same conditionId)
-        seen_ids = set()
-        unique_markets = []
-        for m in markets:
-            if m.market_id not in seen_ids:
-                seen_ids.add(m.market_id)
-                unique_markets.append(m)
+        unique_markets = await fetch_updown_markets(slugs_to_try)
         
         if unique_markets:
             logger.info(f"📊 Found {len(unique_markets)} CURRENT 15-minute markets (trading now!)")
@@ -1269,6 +1490,16 @@ class FifteenMinuteCryptoStrategy:
         # TASK 5.2: Store in cache
         self.fast_execution.set_market_data(cache_key, unique_markets)
         
+        # Recorder mode: capture market definitions and stream their full books
+        if self.recorder is not None and unique_markets:
+            self.recorder.record_markets(unique_markets)
+            try:
+                await self.polymarket_ws_feed.subscribe(
+                    [t for m in unique_markets for t in (m.up_token_id, m.down_token_id)]
+                )
+            except Exception as e:
+                logger.warning(f"Failed to subscribe recorder to market books: {e}")
+        
         return unique_markets
     def _validate_market_slug(self, slug: str) -> bool:
         """
@@ -1379,6 +1610,41 @@ class FifteenMinuteCryptoStrategy:
         return True
 
     
+    def fair_value(self, market: CryptoMarket) -> Optional[FairValue]:
+        """
+        Model price of an up/down market (UP is the YES side).
+
+        The shared FairValuePricer implies the strike from the UP price the
+        first time it sees the market; afterwards the value follows Binance.
+
+        Returns:
+            FairValue, or None without a Binance price
+        """
+        try:
+            return self.fair_value_pricer.price_up_down(
+                market.market_id, market.asset, market.end_time, market.up_price
+            )
+        except Exception as e:
+            logger.debug(f"Fair value unavailable for {market.asset}: {e}")
+            return None
+
+    def _has_fair_value_edge(self, market: CryptoMarket, side: str, price: Decimal, strategy: str) -> bool:
+        """
+        Check that the model values the side at least at its price.
+
+        Entries are allowed when there is no fair value yet (no Binance price).
+        """
+        fair = self.fair_value(market)
+        if fair is None:
+            return True
+        if fair.edge(price, side) < 0:
+            logger.info(
+                f"⏭️ FAIR VALUE BLOCKED {strategy} {side}: {market.asset} model "
+                f"${fair.side_probability(side):.3f} < price ${price:.3f}"
+            )
+            return False
+        return True
+
     def _has_min_time_to_close(self, market: CryptoMarket) -> bool:
         """
         Check if market has enough time remaining to safely enter a new position.
@@ -1810,16 +2076,19 @@ class FifteenMinuteCryptoStrategy:
         profit_pct: Decimal,
         hold_time_minutes: float,
         exit_reason: str,
-        position_size: Optional[Decimal] = None  # TASK 4.2: Track position size for Kelly
+        position_size: Optional[Decimal] = None,  # TASK 4.2: Track position size for Kelly
+        market_id: Optional[str] = None,  # Links the outcome to LLM decisions on the market
+        features: Optional[Dict[str, float]] = None  # Entry MarketFeatures for the RL experience log
     ) -> None:
         """
-        Record trade outcome to ALL learning engines for unified intelligence.
+        Record trade outcome to the TradeOutcomeStore, which passes it to ALL learning engines.
         
-        CRITICAL: All 4 engines must be updated for weighted voting to work properly.
+        CRITICAL: All engines must be subscribed for weighted voting to work properly.
         - SuperSmart (40%): Pattern recognition
-        - RL Engine (35%): Q-learning strategy optimization
+        - RL Engine (35%): Experience for offline policy training
         - Adaptive (25%): Historical parameter tuning
-        - Kelly System: Position sizing optimization
+        - Historical Success Tracker: Pattern filtering
+        - Kelly System: Position sizing optimization (trades with a position size)
         
         Task 8.1: Enhanced with per-strategy, per-asset, and exit reason tracking
         """
@@ -1827,6 +2096,12 @@ class FifteenMinuteCryptoStrategy:
         if exit_reason not in self.stats["exit_reasons"]:
             self.stats["exit_reasons"][exit_reason] = 0
         self.stats["exit_reasons"][exit_reason] += 1
+        if self.metrics is not None:
+            self.metrics.record_strategy_exit(strategy, asset, exit_reason)
+        
+        # Outcome for scoring the LLM's decisions on this market (docs/LLM_DECISION_AUDIT.md)
+        if market_id and self.llm_decision_engine is not None and hasattr(self.llm_decision_engine, "record_outcome"):
+            self.llm_decision_engine.record_outcome(market_id, side, profit_pct, exit_reason)
         
         # Task 8.1: Initialize per-strategy stats if needed
         if strategy not in self.stats["per_strategy"]:
@@ -1892,94 +2167,23 @@ class FifteenMinuteCryptoStrategy:
             f"Exit: {exit_reason}"
         )
         
-        # 1. SuperSmart Learning (40% weight)
-        if self.super_smart:
-            try:
-                self.super_smart.record_trade(
-                    asset=asset,
-                    side=side,
-                    entry_price=entry_price,
-                    exit_price=exit_price,
-                    profit_pct=profit_pct,
-                    hold_time_minutes=hold_time_minutes,
-                    exit_reason=exit_reason,
-                    strategy_used=strategy
-                )
-            except Exception as e:
-                logger.warning(f"SuperSmart record failed: {e}")
-        
-        # 2. RL Engine (35% weight) - Use profit as reward signal
-        if self.rl_engine:
-            try:
-                reward = float(profit_pct)  # Positive = profit, negative = loss
-                self.rl_engine.update_q_value(
-                    asset=asset,
-                    strategy=strategy,
-                    reward=reward
-                )
-            except Exception as e:
-                logger.warning(f"RL engine update failed: {e}")
-        
-        # 3. Adaptive Learning (25% weight)
-        if self.adaptive_learning:
-            try:
-                from src.adaptive_learning_engine import TradeOutcome
-                outcome = TradeOutcome(
-                    timestamp=datetime.now(timezone.utc),
-                    asset=asset,
-                    side=side,
-                    entry_price=entry_price,
-                    exit_price=exit_price,
-                    profit_pct=profit_pct,
-                    hold_time_minutes=hold_time_minutes,
-                    exit_reason=exit_reason,
-                    strategy_used=strategy,
-                    time_of_day=datetime.now(timezone.utc).hour
-                )
-                self.adaptive_learning.record_trade(outcome)
-            except Exception as e:
-                logger.warning(f"Adaptive learning record failed: {e}")
-        
-        # 4. Historical Success Tracker (for pattern filtering)
+        # SuperSmart, RL, Adaptive, Historical and the Kelly system learn from the store's subscription
         try:
-            self.success_tracker.record_trade(
+            self.outcome_store.record(
                 strategy=strategy,
                 asset=asset,
-                market_id="",  # Not critical for learning
+                side=side,
                 entry_price=entry_price,
                 exit_price=exit_price,
-                size=Decimal("1"),  # Normalized
+                profit_pct=profit_pct,
                 hold_time_minutes=hold_time_minutes,
-                exit_reason=exit_reason
+                exit_reason=exit_reason,
+                market_id=market_id or "",
+                size=position_size,
+                features=features
             )
         except Exception as e:
-            logger.debug(f"Success tracker record failed: {e}")
-        
-        # TASK 4.2: Record to Kelly Criterion system for position sizing optimization
-        if self.kelly_system and position_size is not None:
-            try:
-                # Calculate actual profit in USD
-                profit_usd = position_size * profit_pct
-                
-                # Calculate edge and odds from the trade
-                # Edge = actual profit / position size
-                # Odds = profit / cost (normalized to 1.0 cost)
-                edge = profit_pct  # Profit percentage is the edge
-                odds = abs(profit_pct) if profit_pct != 0 else Decimal('0.01')
-                
-                # Record trade outcome
-                was_successful = profit_pct > 0
-                self.kelly_system.record_trade(
-                    position_size=position_size,
-                    profit=profit_usd,
-                    was_successful=was_successful,
-                    edge=edge,
-                    odds=odds
-                )
-                
-                logger.debug(f"📊 Kelly system updated: size=${position_size:.2f}, profit=${profit_usd:.2f}")
-            except Exception as e:
-                logger.warning(f"Kelly system record failed: {e}")
+            logger.warning(f"Trade outcome record failed: {e}")
         
         # TASK 4.6: Update dynamic parameters after each trade
         # This adjusts take-profit, stop-loss, daily limits, and circuit breaker thresholds
@@ -2036,6 +2240,7 @@ class FifteenMinuteCryptoStrategy:
         # Task 5.7: Start execution time tracking
         import time
         signal_detection_time = time.time()
+        signal_tick_time = self._latest_tick_time(market.asset)
         
         # CRITICAL FIX: Use ORDERBOOK prices, not mid prices!
         # Mid prices always sum to $1.00, but orderbook ask prices can be < $1.00
@@ -2065,7 +2270,8 @@ class FifteenMinuteCryptoStrategy:
             
             # Calculate profit after fees - OPTIMIZED FOR HIGH VOLUME
             # Match 86% ROI bot parameters: aggressive but profitable
-            profit_after_fees = spread - Decimal("0.03")
+            fee_buffer = Decimal("0.03")
+            profit_after_fees = spread - fee_buffer
             
             # DYNAMIC TRADING MODE: Accept 0.5% profit (was 1%)
             # This enables 5-10x more trades like successful bots
@@ -2081,6 +2287,7 @@ class FifteenMinuteCryptoStrategy:
                 logger.warning(f"   Using {'ORDERBOOK ASK' if use_orderbook else 'MID'} prices")
                 
                 self.stats["arbitrage_opportunities"] += 1
+                self._record_opportunity("sum_to_one", market.asset)
                 
                 if len(self.positions) < self.max_positions:
                     # PHASE 4B: Check daily trade limit
@@ -2155,14 +2362,24 @@ class FifteenMinuteCryptoStrategy:
                     
                     logger.info(f"📊 Buy both: UP={up_shares:.2f} shares (${up_shares*float(up_price):.2f}), DOWN={down_shares:.2f} shares (${down_shares*float(down_price):.2f})")
                     
-                    # Execute trades using orderbook ASK prices
-                    # Task 2.2: Pass orderbook usage flag
-                    await self._place_order(market, "UP", up_price, up_shares, strategy="sum_to_one", used_orderbook=use_orderbook)
-                    await self._place_order(market, "DOWN", down_price, down_shares, strategy="sum_to_one", used_orderbook=use_orderbook)
+                    if self.maker_entries:
+                        # Rest the leg with the higher taker fee (nearer 50c); the other leg
+                        # is taken at no more than its current ask as the resting leg fills
+                        rest_side = "UP" if abs(up_price - Decimal("0.5")) <= abs(down_price - Decimal("0.5")) else "DOWN"
+                        rest_price, hedge_price = (up_price, down_price) if rest_side == "UP" else (down_price, up_price)
+                        await self._place_order(
+                            market, rest_side, rest_price, max(up_shares, down_shares), strategy="sum_to_one",
+                            used_orderbook=use_orderbook, hedge_price=hedge_price
+                        )
+                    else:
+                        # Execute trades using orderbook ASK prices
+                        # Task 2.2: Pass orderbook usage flag
+                        await self._place_order(market, "UP", up_price, up_shares, strategy="sum_to_one", used_orderbook=use_orderbook)
+                        await self._place_order(market, "DOWN", down_price, down_shares, strategy="sum_to_one", used_orderbook=use_orderbook)
                     
                     # Task 5.7: Track execution time from signal detection to order placement
                     execution_time_ms = (time.time() - signal_detection_time) * 1000
-                    self._track_execution_time(execution_time_ms, "sum_to_one", market.asset)
+                    self._track_execution_time(execution_time_ms, "sum_to_one", market.asset, tick_time=signal_tick_time)
                     
                     return True
             else:
@@ -2171,7 +2388,8 @@ class FifteenMinuteCryptoStrategy:
         return False
     async def check_flash_crash(self, market: CryptoMarket) -> bool:
         """
-        Detect flash crashes (15% drop in 3 seconds) and buy the crashed side.
+        Detect flash crashes (flash_crash_drop_threshold within
+        flash_crash_lookback_seconds, 15% in 3 seconds by default) and buy the crashed side.
 
         Based on 86% ROI bot strategy from research:
         - Wait for 15% price drop in 3 seconds
@@ -2185,16 +2403,17 @@ class FifteenMinuteCryptoStrategy:
         """
         asset = market.asset
 
-        # Get recent price history (last 3 seconds)
+        # Get recent price history (last flash_crash_lookback_seconds)
         recent_prices = []
         current_time = datetime.now()
+        lookback = self.flash_crash_lookback_seconds
 
         # Check if we have price history for this asset
         if asset in self.binance_feed.price_history:
             for timestamp, price in self.binance_feed.price_history[asset]:
                 # Calculate time difference in seconds
                 time_diff = (current_time - timestamp).total_seconds()
-                if time_diff <= 3.0:  # Last 3 seconds
+                if time_diff <= lookback:
                     recent_prices.append((timestamp, price))
 
         # Need at least 2 prices to detect crash
@@ -2204,7 +2423,7 @@ class FifteenMinuteCryptoStrategy:
         # Sort by timestamp to ensure correct order
         recent_prices.sort(key=lambda x: x[0])
 
-        # Calculate price change over last 3 seconds
+        # Calculate price change over the lookback
         oldest_price = recent_prices[0][1]
         newest_price = recent_prices[-1][1]
 
@@ -2213,13 +2432,14 @@ class FifteenMinuteCryptoStrategy:
 
         price_change_pct = ((newest_price - oldest_price) / oldest_price) * 100
 
-        # Detect 15% crash (DOWN) or 15% pump (UP)
-        crash_threshold = -15.0  # 15% drop
-        pump_threshold = 15.0    # 15% rise
+        # Detect a crash (DOWN) or pump (UP) of at least the threshold
+        pump_threshold = self.flash_crash_drop_threshold * 100
+        crash_threshold = -pump_threshold
 
         if price_change_pct <= crash_threshold:
             # FLASH CRASH DETECTED - BUY UP (price will recover)
-            logger.warning(f"🚨 FLASH CRASH DETECTED: {asset} dropped {price_change_pct:.1f}% in 3s! Buying UP...")
+            logger.warning(f"🚨 FLASH CRASH DETECTED: {asset} dropped {price_change_pct:.1f}% in {lookback:g}s! Buying UP...")
+            self._record_opportunity("flash_crash", asset)
 
             # SAFETY: Check minimum time to market close
             if not self._has_min_time_to_close(market):
@@ -2259,7 +2479,8 @@ class FifteenMinuteCryptoStrategy:
 
         elif price_change_pct >= pump_threshold:
             # FLASH PUMP DETECTED - BUY DOWN (price will correct)
-            logger.warning(f"🚨 FLASH PUMP DETECTED: {asset} rose {price_change_pct:.1f}% in 3s! Buying DOWN...")
+            logger.warning(f"🚨 FLASH PUMP DETECTED: {asset} rose {price_change_pct:.1f}% in {lookback:g}s! Buying DOWN...")
+            self._record_opportunity("flash_crash", asset)
 
             # SAFETY: Check minimum time to market close
             if not self._has_min_time_to_close(market):
@@ -2314,6 +2535,7 @@ class FifteenMinuteCryptoStrategy:
         # Task 5.7: Start execution time tracking
         import time
         signal_detection_time = time.time()
+        signal_tick_time = self._latest_tick_time(market.asset)
         
         asset = market.asset
         
@@ -2369,11 +2591,15 @@ class FifteenMinuteCryptoStrategy:
         
         if multi_tf_bullish:
             logger.info(f"🚀 MULTI-TF BULLISH SIGNAL for {asset}!")
+            self._record_opportunity("latency", asset)
             logger.info(f"   Confidence: {confidence:.1f}% (historical score: {hist_score:.1f}%)")
             logger.info(f"   Timeframes: {tf_summary}")
             logger.info(f"   Current UP price: ${market.up_price}")
             
             if len(self.positions) < self.max_positions:
+                if not self._has_fair_value_edge(market, "UP", market.up_price, "latency"):
+                    return False
+                
                 # FIX Bug #6: Check learning engines before entering
                 should_trade, score, reason = self._should_take_trade("latency", asset, float(confidence) / 100.0)
                 if not should_trade:
@@ -2428,7 +2654,7 @@ class FifteenMinuteCryptoStrategy:
                 
                 # Task 5.7: Track execution time from signal detection to order placement
                 execution_time_ms = (time.time() - signal_detection_time) * 1000
-                self._track_execution_time(execution_time_ms, "latency", asset)
+                self._track_execution_time(execution_time_ms, "latency", asset, tick_time=signal_tick_time)
                 
                 return True
         
@@ -2439,11 +2665,15 @@ class FifteenMinuteCryptoStrategy:
         
         if multi_tf_bearish:
             logger.info(f"📉 MULTI-TF BEARISH SIGNAL for {asset}!")
+            self._record_opportunity("latency", asset)
             logger.info(f"   Confidence: {confidence:.1f}% (historical score: {hist_score:.1f}%)")
             logger.info(f"   Timeframes: {tf_summary}")
             logger.info(f"   Current DOWN price: ${market.down_price}")
             
             if len(self.positions) < self.max_positions:
+                if not self._has_fair_value_edge(market, "DOWN", market.down_price, "latency"):
+                    return False
+                
                 # FIX Bug #6: Check learning engines before entering
                 should_trade, score, reason = self._should_take_trade("latency", asset, float(confidence) / 100.0)
                 if not should_trade:
@@ -2498,7 +2728,7 @@ class FifteenMinuteCryptoStrategy:
                 
                 # Task 5.7: Track execution time from signal detection to order placement
                 execution_time_ms = (time.time() - signal_detection_time) * 1000
-                self._track_execution_time(execution_time_ms, "latency", asset)
+                self._track_execution_time(execution_time_ms, "latency", asset, tick_time=signal_tick_time)
                 
                 return True
         
@@ -2516,6 +2746,7 @@ class FifteenMinuteCryptoStrategy:
         # Task 5.7: Start execution time tracking
         import time
         signal_detection_time = time.time()
+        signal_tick_time = self._latest_tick_time(market.asset)
         
         if not self.llm_decision_engine:
             logger.warning(f"🤖 DIRECTIONAL CHECK: {market.asset} | LLM not available, skipping")
@@ -2657,16 +2888,20 @@ class FifteenMinuteCryptoStrategy:
         try:
             # Get ensemble decision (combines all models)
             # Pass objects directly - ensemble handles both Dict and object types
+            # The RL policy values an entry on the side the momentum points to
+            rl_features = await self._entry_features(market, "DOWN" if binance_momentum == "bearish" else "UP")
             ensemble_decision = await self.ensemble_engine.make_decision(
                 asset=market.asset,
                 market_context=ctx,
                 portfolio_state=p_state,
-                opportunity_type="directional_trend"
+                opportunity_type="directional_trend",
+                rl_features=rl_features
             )
             
             # Check if ensemble approves (requires 50% consensus)
             if self.ensemble_engine.should_execute(ensemble_decision):
                 logger.info(f"🎯 ENSEMBLE APPROVED: {ensemble_decision.action}")
+                self._record_opportunity("directional", market.asset)
                 logger.info(f"   Confidence: {ensemble_decision.confidence:.1f}%")
                 logger.info(f"   Consensus: {ensemble_decision.consensus_score:.1f}%")
                 logger.info(f"   Model votes: {len(ensemble_decision.model_votes)}")
@@ -2749,6 +2984,10 @@ class FifteenMinuteCryptoStrategy:
                     target_token = market.down_token_id
                     target_price = market.down_price
                 
+                target_side = "UP" if target_token == market.up_token_id else "DOWN"
+                if not self._has_fair_value_edge(market, target_side, target_price, "directional"):
+                    return False
+                
                 # REQUIREMENT 3.7: Verify liquidity >= 2x trade size before entry
                 can_trade, liq_reason = await self._verify_liquidity_before_entry(
                     target_token, "buy", adjusted_size, "directional", market.asset
@@ -2812,7 +3051,7 @@ class FifteenMinuteCryptoStrategy:
                     
                     # Task 5.7: Track execution time from signal detection to order placement
                     execution_time_ms = (time.time() - signal_detection_time) * 1000
-                    self._track_execution_time(execution_time_ms, "directional", market.asset)
+                    self._track_execution_time(execution_time_ms, "directional", market.asset, tick_time=signal_tick_time)
                     
                     return True
                 elif ensemble_decision.action == "buy_no":
@@ -2822,7 +3061,7 @@ class FifteenMinuteCryptoStrategy:
                     
                     # Task 5.7: Track execution time from signal detection to order placement
                     execution_time_ms = (time.time() - signal_detection_time) * 1000
-                    self._track_execution_time(execution_time_ms, "directional", market.asset)
+                    self._track_execution_time(execution_time_ms, "directional", market.asset, tick_time=signal_tick_time)
                     
                     return True
                 elif ensemble_decision.action == "buy_both":
@@ -2846,7 +3085,7 @@ class FifteenMinuteCryptoStrategy:
                         
                         # Task 5.7: Track execution time from signal detection to order placement
                         execution_time_ms = (time.time() - signal_detection_time) * 1000
-                        self._track_execution_time(execution_time_ms, "directional", market.asset)
+                        self._track_execution_time(execution_time_ms, "directional", market.asset, tick_time=signal_tick_time)
                         
                         return True
                     else:
@@ -2864,7 +3103,7 @@ class FifteenMinuteCryptoStrategy:
                         
                         # Task 5.7: Track execution time from signal detection to order placement
                         execution_time_ms = (time.time() - signal_detection_time) * 1000
-                        self._track_execution_time(execution_time_ms, "directional", market.asset)
+                        self._track_execution_time(execution_time_ms, "directional", market.asset, tick_time=signal_tick_time)
                         
                         return True
                 else:
@@ -2963,7 +3202,7 @@ class FifteenMinuteCryptoStrategy:
                             # Task 2.2: Track orderbook vs fallback outcome
                             self._track_exit_outcome(position, used_orderbook_exit, is_win)
                             self._record_trade_outcome(
-                                asset=position.asset, side=position.side,
+                                asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                                 strategy=position.strategy, entry_price=position.entry_price,
                                 exit_price=current_price, profit_pct=pnl_pct,
                                 hold_time_minutes=age_min, exit_reason="market_closing",
@@ -2996,26 +3235,7 @@ class FifteenMinuteCryptoStrategy:
 
             # Remove closed positions
             for token_id in positions_to_close:
-                if token_id in self.positions:
-                    position = self.positions[token_id]
-                    
-                    # Close position in risk manager
-                    try:
-                        self.risk_manager.close_position(position.market_id, position.entry_price)
-                        logger.debug(f"📝 Position {token_id[:16]}... removed from risk manager")
-                    except Exception as e:
-                        logger.debug(f"Risk manager close_position error: {e}")
-                    
-                    # TASK 5.8: Unsubscribe from WebSocket updates for this token
-                    try:
-                        await self.polymarket_ws_feed.unsubscribe([token_id])
-                        logger.debug(f"📡 Unsubscribed from WebSocket updates for {token_id[:16]}...")
-                    except Exception as e:
-                        logger.debug(f"Failed to unsubscribe from WebSocket: {e}")
-                    
-                    # Remove from positions dictionary
-                    del self.positions[token_id]
-                    logger.info(f"✅ Position {token_id[:16]}... removed from tracking")
+                await self._remove_position(token_id)
 
             # Save positions after any changes
             if positions_to_close:
@@ -3112,7 +3332,7 @@ class FifteenMinuteCryptoStrategy:
                     # Task 2.2: Track orderbook vs fallback outcome
                     self._track_exit_outcome(position, used_orderbook_exit, is_win)
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=age_min, exit_reason="trailing_stop"
@@ -3140,7 +3360,7 @@ class FifteenMinuteCryptoStrategy:
                 # Task 2.2: Track orderbook vs fallback outcome
                 self._track_exit_outcome(position, used_orderbook_exit, is_win=True)
                 self._record_trade_outcome(
-                    asset=position.asset, side=position.side,
+                    asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                     strategy=position.strategy, entry_price=position.entry_price,
                     exit_price=current_price, profit_pct=pnl_pct,
                     hold_time_minutes=age_min, exit_reason="take_profit"
@@ -3168,7 +3388,7 @@ class FifteenMinuteCryptoStrategy:
                 # Task 2.2: Track orderbook vs fallback outcome
                 self._track_exit_outcome(position, used_orderbook_exit, is_win=False)
                 self._record_trade_outcome(
-                    asset=position.asset, side=position.side,
+                    asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                     strategy=position.strategy, entry_price=position.entry_price,
                     exit_price=current_price, profit_pct=pnl_pct,
                     hold_time_minutes=age_min, exit_reason="stop_loss"
@@ -3186,7 +3406,7 @@ class FifteenMinuteCryptoStrategy:
                     self._track_exit_outcome(position, used_orderbook_exit, is_win=False)
                     # CRITICAL FIX (Task 1.5): Record trade outcome for stuck positions
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=age_min, exit_reason="stop_loss_stuck_position"
@@ -3220,7 +3440,7 @@ class FifteenMinuteCryptoStrategy:
                 self._track_exit_outcome(position, used_orderbook_exit, is_win)
 
                 self._record_trade_outcome(
-                    asset=position.asset, side=position.side,
+                    asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                     strategy=position.strategy, entry_price=position.entry_price,
                     exit_price=current_price, profit_pct=pnl_pct,
                     hold_time_minutes=age_min, exit_reason="time_exit_13min"
@@ -3256,7 +3476,7 @@ class FifteenMinuteCryptoStrategy:
                 self._track_exit_outcome(position, used_orderbook_exit, is_win)
 
                 self._record_trade_outcome(
-                    asset=position.asset, side=position.side,
+                    asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                     strategy=position.strategy, entry_price=position.entry_price,
                     exit_price=current_price, profit_pct=pnl_pct,
                     hold_time_minutes=age_min, exit_reason="emergency_exit_market_closed"
@@ -3267,7 +3487,7 @@ class FifteenMinuteCryptoStrategy:
                 positions_to_close.append(token_id)
                 self.stats["trades_lost"] += 1
                 self._record_trade_outcome(
-                    asset=position.asset, side=position.side,
+                    asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                     strategy=position.strategy, entry_price=position.entry_price,
                     exit_price=position.entry_price, profit_pct=Decimal("-0.02"),
                     hold_time_minutes=age_min, exit_reason="emergency_exit_failed"
@@ -3279,14 +3499,22 @@ class FifteenMinuteCryptoStrategy:
     async def _handle_orphan_position(self, position, token_id: str, age_min: float, positions_to_close: list) -> None:
         """
         Handle orphan positions (no price data available, market likely closed).
-        Force-removes position from tracking and records as loss.
+        Force-removes position from tracking and records as loss. The shares are
+        handed to the redemption service, which redeems them once the market resolves.
         """
         logger.warning(f"   Force-removing orphan position (market may have closed)")
         positions_to_close.append(token_id)
 
+        if self.redemption_service is not None and not self.dry_run:
+            self.redemption_service.watch(
+                position.market_id, token_id,
+                outcome_index=0 if position.side == "UP" else 1,
+                neg_risk=position.neg_risk
+            )
+
         # Record as loss in learning engines
         self._record_trade_outcome(
-            asset=position.asset, side=position.side,
+            asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
             strategy=position.strategy, entry_price=position.entry_price,
             exit_price=position.entry_price,  # Unknown exit price, assume breakeven
             profit_pct=Decimal('-0.015'),  # Estimate 1.5% loss (fees)
@@ -3367,7 +3595,7 @@ class FifteenMinuteCryptoStrategy:
                     # Task 2.2: Track orderbook vs fallback outcome
                     self._track_exit_outcome(position, used_orderbook_exit, is_win)
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=position_age, exit_reason="market_closing"
@@ -3390,7 +3618,7 @@ class FifteenMinuteCryptoStrategy:
                     # Task 2.2: Track orderbook vs fallback outcome
                     self._track_exit_outcome(position, used_orderbook_exit, is_win)
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=position_age, exit_reason="time_exit"
@@ -3424,7 +3652,7 @@ class FifteenMinuteCryptoStrategy:
                         # Task 2.2: Track orderbook vs fallback outcome
                         self._track_exit_outcome(position, used_orderbook_exit, is_win)
                         self._record_trade_outcome(
-                            asset=position.asset, side=position.side,
+                            asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                             strategy=position.strategy, entry_price=position.entry_price,
                             exit_price=current_price, profit_pct=pnl_pct,
                             hold_time_minutes=position_age, exit_reason="trailing_stop"
@@ -3447,7 +3675,7 @@ class FifteenMinuteCryptoStrategy:
                     # Task 2.2: Track orderbook vs fallback outcome
                     self._track_exit_outcome(position, used_orderbook_exit, is_win=True)
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=position_age, exit_reason="take_profit"
@@ -3470,7 +3698,7 @@ class FifteenMinuteCryptoStrategy:
                     # Task 2.2: Track orderbook vs fallback outcome
                     self._track_exit_outcome(position, used_orderbook_exit, is_win=False)
                     self._record_trade_outcome(
-                        asset=position.asset, side=position.side,
+                        asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
                         strategy=position.strategy, entry_price=position.entry_price,
                         exit_price=current_price, profit_pct=pnl_pct,
                         hold_time_minutes=position_age, exit_reason="stop_loss"
@@ -3504,6 +3732,33 @@ class FifteenMinuteCryptoStrategy:
         if positions_to_close:
             self._save_positions()
     
+    def _check_correlation(self, market: CryptoMarket, shares: Decimal) -> Tuple[bool, str]:
+        """
+        Check an entry against the correlation analyzer's single-asset and correlated exposure limits.
+        
+        Open positions and resting maker entries both count toward the exposure.
+        
+        Returns:
+            (can_add, reason); always allowed without a correlation analyzer
+        """
+        if self.correlation_analyzer is None:
+            return True, "No correlation limits"
+        held = [
+            CorrelatedPosition(
+                token_id=p.token_id, asset=p.asset, side=p.side, size=p.size, market_id=p.market_id
+            )
+            for p in self.positions.values()
+        ] + [
+            CorrelatedPosition(
+                token_id=e.order.market_id, asset=e.market.asset, side=e.side,
+                size=e.order.size - e.tracked_size, market_id=e.market.market_id
+            )
+            for e in self.maker_orders.values()
+        ]
+        return self.correlation_analyzer.check_can_add_position(
+            held, self.risk_manager.current_capital, market.asset, shares
+        )
+    
     async def _place_order(
         self,
         market: CryptoMarket,
@@ -3512,7 +3767,8 @@ class FifteenMinuteCryptoStrategy:
         shares: float,
         strategy: str = "unknown",  # Track which strategy placed this order
         used_orderbook: bool = False,  # Task 2.2: Track if orderbook was used
-        confidence: Optional[Decimal] = None  # TASK 6.4: Track confidence for dynamic trailing stop
+        confidence: Optional[Decimal] = None,  # TASK 6.4: Track confidence for dynamic trailing stop
+        hedge_price: Optional[Decimal] = None  # Sum-to-one maker leg: price cap of the other leg
     ) -> bool:
         """
         Place a buy order with comprehensive validation and error handling.
@@ -3522,14 +3778,21 @@ class FifteenMinuteCryptoStrategy:
             side: "UP" or "DOWN"
             price: Entry price
             shares: Number of shares to buy
+            hedge_price: Sum-to-one maker entries only: the other leg is bought at up to
+                this price as this one fills (in dry run both legs fill at once)
             
         Returns:
             True if order placed successfully
         """
         token_id = market.up_token_id if side == "UP" else market.down_token_id
+        # A sum-to-one leg only rests when it comes with a hedge price for the other leg
+        maker = (
+            self.maker_entries and strategy in self.MAKER_ENTRY_STRATEGIES
+            and (strategy != "sum_to_one" or hedge_price is not None)
+        )
         
         logger.info("=" * 80)
-        logger.info(f"📈 PLACING ORDER")
+        logger.info(f"📈 PLACING {'MAKER ' if maker else ''}ORDER")
         logger.info(f"   Market: {market.question[:50]}...")
         logger.info(f"   Side: {side}")
         logger.info(f"   Price: ${price}")
@@ -3547,8 +3810,11 @@ class FifteenMinuteCryptoStrategy:
             expected_profit = order_value * self.take_profit_pct
             
             # Calculate transaction costs (fees + gas)
-            # Polymarket: 3% taker fee at 50% odds, varies with odds
-            fee_pct = Decimal('0.03') * (Decimal('1.0') - Decimal('2.0') * abs(price - Decimal('0.5')))
+            # Polymarket: 3% taker fee at 50% odds, varies with odds; maker fills pay no fee
+            if maker:
+                fee_pct = Decimal('0')
+            else:
+                fee_pct = Decimal('0.03') * (Decimal('1.0') - Decimal('2.0') * abs(price - Decimal('0.5')))
             transaction_costs = order_value * fee_pct
             
             # Estimate slippage (0.15% typical)
@@ -3579,6 +3845,13 @@ class FifteenMinuteCryptoStrategy:
         except Exception as e:
             logger.warning(f"Cost-benefit analysis failed: {e}, proceeding with trade")
         
+        can_add, reason = self._check_correlation(market, Decimal(str(shares)))
+        if not can_add:
+            logger.warning(f"🔗 CORRELATION LIMIT BLOCKED: {reason}")
+            return False
+        
+        features = await self._entry_features(market, side)
+        
         if self.dry_run:
             logger.info("DRY RUN: Order simulated (not placed)")
             # Track position for testing
@@ -3593,10 +3866,12 @@ class FifteenMinuteCryptoStrategy:
                 strategy=strategy,
                 highest_price=price,  # PHASE 3A: Initialize peak price
                 used_orderbook_entry=used_orderbook,  # Task 2.2: Track orderbook usage
-                confidence=confidence if confidence is not None else Decimal("50")  # TASK 6.4
+                confidence=confidence if confidence is not None else Decimal("50"),  # TASK 6.4
+                features=features
             )
             self.stats["trades_placed"] += 1
             self.daily_trade_count += 1
+            self._record_entry(strategy, market.asset)
             # Task 2.2: Track orderbook vs fallback entries
             if used_orderbook:
                 self.stats["orderbook_entries"] += 1
@@ -3606,6 +3881,11 @@ class FifteenMinuteCryptoStrategy:
             self.risk_manager.add_position(market.market_id, side, price, Decimal(str(shares)))
             # CRITICAL FIX: Save position to disk AFTER tracking
             self._save_positions()
+            if hedge_price is not None:
+                await self._place_order(
+                    market, "DOWN" if side == "UP" else "UP", hedge_price, shares,
+                    strategy=strategy, used_orderbook=used_orderbook
+                )
             return True
         
         try:
@@ -3731,6 +4011,14 @@ class FifteenMinuteCryptoStrategy:
                 logger.warning(f"⚠️ Balance check failed: {balance_error}")
                 logger.warning("   Proceeding with order placement (may fail if insufficient balance)")
             
+            # Maker entries rest on the book and become positions as they fill
+            if maker:
+                return await self._place_maker_order(
+                    market, side, Decimal(str(price_f)), Decimal(str(size_f)),
+                    strategy=strategy, used_orderbook=used_orderbook, confidence=confidence,
+                    features=features, hedge_price=hedge_price
+                )
+            
             # ============================================================
             # STEP 4: Create and place the order
             # ============================================================
@@ -3883,7 +4171,8 @@ class FifteenMinuteCryptoStrategy:
                 neg_risk=getattr(market, 'neg_risk', True),
                 highest_price=actual_price_decimal,  # PHASE 3A: Initialize peak price
                 used_orderbook_entry=used_orderbook,  # Task 2.2: Track orderbook usage
-                confidence=confidence if confidence is not None else Decimal("50")  # TASK 6.4
+                confidence=confidence if confidence is not None else Decimal("50"),  # TASK 6.4
+                features=features
             )
             
             # TASK 5.8: Subscribe to WebSocket price updates for this token
@@ -3895,6 +4184,7 @@ class FifteenMinuteCryptoStrategy:
             
             self.stats["trades_placed"] += 1
             self.daily_trade_count += 1
+            self._record_entry(strategy, market.asset)
             
             # Task 2.2: Track orderbook vs fallback entries
             if used_orderbook:
@@ -3912,6 +4202,9 @@ class FifteenMinuteCryptoStrategy:
             
             # CRITICAL FIX: Save position to disk AFTER successful order placement
             self._save_positions()
+            self._record_ledger_fill(
+                self.positions[token_id], "BUY", actual_size_decimal, actual_price_decimal, order_id=order_id
+            )
             
             logger.info(f"📝 Position tracked: {size_f:.2f} shares @ ${price_f:.4f}")
             
@@ -3922,6 +4215,329 @@ class FifteenMinuteCryptoStrategy:
             logger.error(f"   This order was NOT placed")
             return False
     
+    # ============================================================
+    # Maker entries: resting post-only GTD orders
+    # ============================================================
+    
+    def _maker_price(self, token_id: str, price: Decimal, tick: Decimal) -> Optional[Decimal]:
+        """Highest tick-aligned price <= price that stays one tick under the best ask."""
+        best_ask = self.order_manager.get_best_ask(token_id)
+        limit = price if best_ask is None else min(price, best_ask - tick)
+        limit = (limit / tick).to_integral_value(rounding=ROUND_FLOOR) * tick
+        return limit if limit >= tick else None
+    
+    async def _place_maker_order(
+        self,
+        market: CryptoMarket,
+        side: str,
+        price: Decimal,
+        size: Decimal,
+        strategy: str,
+        used_orderbook: bool = False,
+        confidence: Optional[Decimal] = None,
+        features: Optional[Dict[str, float]] = None,
+        hedge_price: Optional[Decimal] = None
+    ) -> bool:
+        """
+        Rest an entry on the book as a post-only GTD order.
+        
+        The order is priced at or below the signal price and one tick under
+        the best ask, so it can only fill as a maker. Fills become positions
+        in _apply_maker_fill(); _sync_maker_orders() requotes, pulls near
+        market close and drops expired orders.
+        
+        With a hedge_price (sum-to-one), fills are paired with taker buys of
+        the other leg in _hedge_maker_fill().
+        
+        Returns:
+            True if the order was accepted by the exchange
+        """
+        token_id = market.up_token_id if side == "UP" else market.down_token_id
+        if any(entry.order.market_id == token_id for entry in self.maker_orders.values()):
+            logger.info(f"⏭️ Maker entry already resting on {market.asset} {side}, skipping")
+            return False
+        
+        seconds_left = (market.end_time - datetime.now(timezone.utc)).total_seconds()
+        ttl = int(min(self.maker_ttl_seconds, seconds_left - self.MAKER_PULL_MINUTES * 60))
+        if ttl <= 0:
+            logger.info(f"⏭️ Too close to market close for a maker entry ({seconds_left:.0f}s left)")
+            return False
+        
+        tick = Decimal(str(getattr(market, "tick_size", "0.01") or "0.01"))
+        limit = self._maker_price(token_id, price, tick)
+        if limit is None:
+            logger.warning(f"⏭️ No valid maker price for {market.asset} {side} under ${price}")
+            return False
+        
+        try:
+            order = self.order_manager.create_limit_order(
+                market_id=token_id,
+                side="YES" if side == "UP" else "NO",
+                price=limit,
+                size=size,
+                order_type="GTD",
+                ttl_seconds=ttl,
+                post_only=True,
+                neg_risk=getattr(market, "neg_risk", True),
+                tick_size=str(tick)
+            )
+            accepted = await self.order_manager.post_limit_order(order)
+        except Exception as e:
+            logger.error(f"❌ Maker order failed: {e}")
+            return False
+        
+        if not accepted:
+            logger.warning(f"⚠️ Maker order rejected: {order.error_message}")
+            return False
+        
+        entry = MakerEntry(
+            order=order,
+            market=market,
+            side=side,
+            strategy=strategy,
+            max_price=price,
+            used_orderbook=used_orderbook,
+            confidence=confidence,
+            features=features,
+            hedge_side=None if hedge_price is None else ("DOWN" if side == "UP" else "UP"),
+            hedge_price=hedge_price
+        )
+        self.maker_orders[order.order_id] = entry
+        self.stats["maker_orders_posted"] += 1
+        self.daily_trade_count += 1
+        logger.info(
+            f"📌 MAKER ENTRY RESTING: {market.asset} {side} {size} @ ${limit} "
+            f"(signal ${price}, ttl {ttl}s, order {order.order_id})"
+        )
+        
+        # Part of the order may have matched on placement
+        await self._apply_maker_fill(entry)
+        if order.status in TERMINAL_ORDER_STATUSES:
+            await self._hedge_maker_fill(entry)
+            self.maker_orders.pop(order.order_id, None)
+        return True
+    
+    async def _add_entry_fill(
+        self,
+        market: CryptoMarket,
+        side: str,
+        size: Decimal,
+        price: Decimal,
+        strategy: str,
+        used_orderbook: bool = False,
+        confidence: Optional[Decimal] = None,
+        features: Optional[Dict[str, float]] = None
+    ) -> Position:
+        """Add filled entry shares to the side's position (average entry price), opening it if needed."""
+        token_id = market.up_token_id if side == "UP" else market.down_token_id
+        position = self.positions.get(token_id)
+        
+        if position is not None:
+            total = position.size + size
+            position.entry_price = (position.entry_price * position.size + price * size) / total
+            position.size = total
+        else:
+            if confidence is not None:
+                self._adjust_trailing_stop_thresholds(confidence=confidence)
+            position = Position(
+                token_id=token_id,
+                side=side,
+                entry_price=price,
+                size=size,
+                entry_time=datetime.now(timezone.utc),
+                market_id=market.market_id,
+                asset=market.asset,
+                strategy=strategy,
+                neg_risk=getattr(market, "neg_risk", True),
+                highest_price=price,
+                used_orderbook_entry=used_orderbook,
+                confidence=confidence if confidence is not None else Decimal("50"),
+                features=features
+            )
+            self.positions[token_id] = position
+            
+            try:
+                await self.polymarket_ws_feed.subscribe([token_id])
+            except Exception as e:
+                logger.warning(f"Failed to subscribe to WebSocket for {token_id[:16]}...: {e}")
+            
+            self.stats["trades_placed"] += 1
+            self._record_entry(strategy, market.asset)
+            if used_orderbook:
+                self.stats["orderbook_entries"] += 1
+            else:
+                self.stats["fallback_entries"] += 1
+        
+        self.risk_manager.add_position(market.market_id, side, position.entry_price, position.size)
+        self._save_positions()
+        return position
+    
+    async def _apply_maker_fill(self, entry: MakerEntry) -> None:
+        """Add newly matched size of a maker entry to its position and hedge sum-to-one fills."""
+        order = entry.order
+        new_size = order.size_matched - entry.tracked_size
+        if new_size <= 0:
+            return
+        entry.tracked_size = order.size_matched
+        
+        market = entry.market
+        fill_price = order.fill_price or order.price
+        position = await self._add_entry_fill(
+            market, entry.side, new_size, fill_price, entry.strategy,
+            used_orderbook=entry.used_orderbook, confidence=entry.confidence, features=entry.features
+        )
+        self.stats["maker_fills"] += 1
+        self._record_ledger_fill(position, "BUY", new_size, fill_price, order_id=order.order_id)
+        
+        logger.info(
+            f"💱 MAKER FILL: {market.asset} {entry.side} +{new_size} @ ${fill_price} "
+            f"({order.size_matched}/{order.size} filled, position {position.size} @ ${position.entry_price:.4f})"
+        )
+        
+        if entry.hedge_side is not None:
+            entry.unhedged += new_size
+            if entry.unhedged >= self.MAKER_HEDGE_MIN_SHARES:
+                await self._hedge_maker_fill(entry)
+    
+    async def _hedge_maker_fill(self, entry: MakerEntry) -> bool:
+        """
+        Buy the other leg of a sum-to-one maker entry for its unhedged fills.
+        
+        The hedge is a FOK order at no more than entry.hedge_price, so the pair
+        costs no more than it was priced at. Fills smaller than
+        MAKER_HEDGE_MIN_SHARES wait for more fills; callers hedge the remainder
+        when the entry ends, rounded up to that CLOB minimum. If the hedge does
+        not fill, the rest of the resting leg is pulled and the unhedged shares
+        stay an ordinary position.
+        
+        Returns:
+            True if no filled shares are left unhedged
+        """
+        if entry.hedge_side is None or entry.unhedged <= 0:
+            return True
+        size = max(entry.unhedged, self.MAKER_HEDGE_MIN_SHARES)
+        
+        market = entry.market
+        token_id = market.up_token_id if entry.hedge_side == "UP" else market.down_token_id
+        try:
+            order = self.order_manager.create_fok_order(
+                market_id=token_id,
+                side="YES" if entry.hedge_side == "UP" else "NO",
+                price=entry.hedge_price,
+                size=size,
+                slippage_tolerance=Decimal("0"),
+                neg_risk=getattr(market, "neg_risk", True),
+                tick_size=entry.order.tick_size
+            )
+            filled = await self.order_manager.submit_order(order)
+        except Exception as e:
+            logger.error(f"❌ Sum-to-one hedge order failed: {e}")
+            filled = False
+        
+        if not filled:
+            self.stats["unhedged_maker_fills"] += 1
+            logger.warning(
+                f"⚠️ SUM-TO-ONE UNHEDGED: {market.asset} {entry.side} {size} shares, "
+                f"no {entry.hedge_side} fill at ${entry.hedge_price} or better - pulling the resting leg"
+            )
+            # Later fills of the pulled order stay unhedged positions
+            entry.hedge_side = None
+            if entry.order.status not in TERMINAL_ORDER_STATUSES:
+                try:
+                    await self.order_manager.cancel_order(entry.order.order_id)
+                except Exception as e:
+                    logger.warning(f"⚠️ Failed to cancel maker entry {entry.order.order_id}: {e}")
+            return False
+        
+        entry.unhedged = Decimal("0")
+        fill_price = order.fill_price or order.price
+        position = await self._add_entry_fill(
+            market, entry.hedge_side, size, fill_price, entry.strategy, used_orderbook=entry.used_orderbook
+        )
+        self.stats["maker_hedges"] += 1
+        self._record_ledger_fill(position, "BUY", size, fill_price, order_id=order.order_id)
+        
+        logger.info(
+            f"🔗 SUM-TO-ONE HEDGE: {market.asset} {entry.hedge_side} +{size} @ ${fill_price} "
+            f"(cap ${entry.hedge_price})"
+        )
+        return True
+    
+    async def _sync_maker_orders(self, markets: List[CryptoMarket]) -> None:
+        """
+        Refresh resting maker entries.
+        
+        - Partial and full fills are added to positions
+        - Matched, cancelled and expired orders are dropped
+        - Orders are pulled MAKER_PULL_MINUTES before market close
+        - Live orders follow the market price (never above the signal price)
+        """
+        try:
+            await self.order_manager.refresh_resting_orders()
+        except Exception as e:
+            logger.warning(f"⚠️ Failed to refresh maker orders: {e}")
+        
+        markets_by_id = {m.market_id: m for m in markets}
+        now = datetime.now(timezone.utc)
+        
+        for order_id, entry in list(self.maker_orders.items()):
+            order = entry.order
+            await self._apply_maker_fill(entry)
+            if order.status in TERMINAL_ORDER_STATUSES:
+                await self._hedge_maker_fill(entry)
+                logger.info(
+                    f"📕 Maker entry {order_id} {order.status.lower()} "
+                    f"({order.size_matched}/{order.size} filled)"
+                )
+                del self.maker_orders[order_id]
+                continue
+            
+            market = markets_by_id.get(entry.market.market_id, entry.market)
+            minutes_left = (market.end_time - now).total_seconds() / 60.0
+            if minutes_left < self.MAKER_PULL_MINUTES:
+                await self._cancel_maker_entry(order_id, entry, reason=f"market closes in {minutes_left:.1f}min")
+                continue
+            
+            current = market.up_price if entry.side == "UP" else market.down_price
+            target = self._maker_price(order.market_id, min(entry.max_price, current), Decimal(order.tick_size))
+            if target is None or target == order.price:
+                continue
+            
+            try:
+                working = await self.order_manager.requote(order_id, target)
+            except Exception as e:
+                logger.warning(f"⚠️ Requote failed for {order_id}: {e}")
+                continue
+            if working is order:
+                continue
+            
+            # The cancel may have picked up last-moment fills on the old order
+            await self._apply_maker_fill(entry)
+            del self.maker_orders[order_id]
+            if working is not None:
+                self.maker_orders[working.order_id] = replace(
+                    entry, order=working, market=market, tracked_size=Decimal("0")
+                )
+    
+    async def _cancel_maker_entry(self, order_id: str, entry: MakerEntry, reason: str) -> None:
+        """Cancel a resting maker entry, keeping whatever already filled."""
+        try:
+            await self.order_manager.cancel_order(order_id)
+        except Exception as e:
+            logger.warning(f"⚠️ Failed to cancel maker entry {order_id}: {e}")
+        await self._apply_maker_fill(entry)
+        await self._hedge_maker_fill(entry)
+        self.maker_orders.pop(order_id, None)
+        logger.info(
+            f"🧹 Pulled maker entry {order_id} ({reason}), "
+            f"filled {entry.tracked_size}/{entry.order.size}"
+        )
+    
+    async def _cancel_maker_orders(self, reason: str) -> None:
+        """Cancel every resting maker entry."""
+        for order_id, entry in list(self.maker_orders.items()):
+            await self._cancel_maker_entry(order_id, entry, reason=reason)
+    
     def _should_take_profit_dynamic(
         self,
         position: Position,
@@ -4305,6 +4921,10 @@ class FifteenMinuteCryptoStrategy:
                             return False
                         
                         # SUCCESS!
+                        sold_size = size_f if retry_num == 0 else adjusted_size
+                        self._record_ledger_fill(
+                            position, "SELL", Decimal(str(sold_size)), Decimal(str(price_f)), order_id=order_id
+                        )
                         logger.info(f"✅ POSITION CLOSED SUCCESSFULLY")
                         logger.info(f"   Order ID: {order_id}")
                         logger.info(f"   Status: {order_status}")
@@ -4346,6 +4966,170 @@ class FifteenMinuteCryptoStrategy:
             
             return False
     
+    def _record_ledger_fill(
+        self,
+        position: Position,
+        side: str,
+        size: Decimal,
+        price: Decimal,
+        order_id: Optional[str] = None
+    ) -> None:
+        """Record a live fill in the position ledger (if one is attached)."""
+        if self.ledger is None or self.dry_run:
+            return
+        try:
+            self.ledger.record_fill(
+                token_id=position.token_id,
+                side=side,
+                size=size,
+                price=price,
+                market_id=position.market_id,
+                outcome=position.side,
+                strategy=position.strategy,
+                order_id=order_id
+            )
+        except Exception as e:
+            logger.error(f"❌ Failed to record {side} fill in ledger: {e}")
+    
+    def tracked_shares(self) -> Dict[str, Decimal]:
+        """Shares the strategy believes it holds, by token ID (for ledger reconciliation)."""
+        return {token_id: position.size for token_id, position in self.positions.items()}
+    
+    def apply_reconciliation(self, report) -> None:
+        """
+        Repair tracked positions from a ledger reconciliation report.
+        
+        Orphans (tracked but not held on chain) are dropped; positions whose
+        on-chain balance differs are resized to the chain balance.
+        
+        Args:
+            report: ReconciliationReport from LedgerReconciler.reconcile()
+        """
+        changed = False
+        for token_id in report.orphans:
+            position = self.positions.pop(token_id, None)
+            if position is None:
+                continue
+            try:
+                self.risk_manager.close_position(position.market_id, position.entry_price)
+            except Exception as e:
+                logger.debug(f"Risk manager close_position error: {e}")
+            logger.warning(f"🧹 Dropped orphan position {position.asset} {position.side} ({token_id[:16]}...)")
+            changed = True
+        
+        for token_id, position in self.positions.items():
+            chain = report.chain_balances.get(token_id)
+            if chain is not None and chain > 0 and abs(chain - position.size) > Decimal("0.01"):
+                logger.warning(f"🔧 Resized {position.asset} {position.side} position: {position.size} -> {chain} shares")
+                position.size = chain
+                self.risk_manager.add_position(position.market_id, position.side, position.entry_price, position.size)
+                changed = True
+        
+        if changed:
+            self._save_positions()
+    
+    async def _remove_position(self, token_id: str) -> None:
+        """Stop tracking a closed position (risk manager, WebSocket subscription, positions)."""
+        position = self.positions.get(token_id)
+        if position is None:
+            return
+        
+        # Close position in risk manager
+        try:
+            self.risk_manager.close_position(position.market_id, position.entry_price)
+            logger.debug(f"📝 Position {token_id[:16]}... removed from risk manager")
+        except Exception as e:
+            logger.debug(f"Risk manager close_position error: {e}")
+        
+        # TASK 5.8: Unsubscribe from WebSocket updates for this token
+        try:
+            await self.polymarket_ws_feed.unsubscribe([token_id])
+            logger.debug(f"📡 Unsubscribed from WebSocket updates for {token_id[:16]}...")
+        except Exception as e:
+            logger.debug(f"Failed to unsubscribe from WebSocket: {e}")
+        
+        # Remove from positions dictionary
+        del self.positions[token_id]
+        logger.info(f"✅ Position {token_id[:16]}... removed from tracking")
+    
+    def get_open_positions(self) -> List[Dict[str, Any]]:
+        """Open positions and resting maker entries (dashboard view)."""
+        positions = []
+        for token_id, position in self.positions.items():
+            mark = self.polymarket_ws_feed.get_cached_price(token_id)
+            positions.append({
+                "token_id": token_id,
+                "market_id": position.market_id,
+                "asset": position.asset,
+                "side": position.side,
+                "strategy": position.strategy,
+                "entry_price": position.entry_price,
+                "size": position.size,
+                "entry_time": position.entry_time,
+                "mark_price": mark,
+                "unrealized_pnl": (mark - position.entry_price) * position.size if mark is not None else None,
+                "status": "open",
+            })
+        positions.extend(
+            {
+                "token_id": entry.order.market_id,
+                "market_id": entry.market.market_id,
+                "asset": entry.market.asset,
+                "side": entry.side,
+                "strategy": entry.strategy,
+                "entry_price": entry.order.price,
+                "size": entry.order.size - entry.tracked_size,
+                "entry_time": None,
+                "status": "resting",
+            }
+            for entry in self.maker_orders.values()
+        )
+        return positions
+    
+    async def close_all_positions(self, reason: str = "manual_flatten") -> int:
+        """
+        Cancel resting maker entries and sell every open position at the current bid.
+        
+        Positions without an exit price stay tracked, so the normal exit
+        checks retry them next cycle.
+        
+        Args:
+            reason: Exit reason recorded with each close
+            
+        Returns:
+            Number of positions closed
+        """
+        await self._cancel_maker_orders(reason=reason)
+        
+        closed = 0
+        now = datetime.now(timezone.utc)
+        for token_id, position in list(self.positions.items()):
+            current_price, used_orderbook = await self._get_exit_price(position)
+            if current_price is None:
+                logger.error(f"❌ No exit price for {position.asset} {position.side}, position kept")
+                continue
+            if not await self._close_position(position, current_price, exit_reason=reason):
+                continue
+            
+            pnl_pct = (current_price - position.entry_price) / position.entry_price if position.entry_price > 0 else Decimal("0")
+            is_win = pnl_pct > 0
+            self.stats["trades_won" if is_win else "trades_lost"] += 1
+            self.stats["total_profit"] += (current_price - position.entry_price) * position.size
+            self._track_exit_outcome(position, used_orderbook, is_win)
+            self._record_trade_outcome(
+                asset=position.asset, side=position.side, market_id=position.market_id, features=position.features,
+                strategy=position.strategy, entry_price=position.entry_price,
+                exit_price=current_price, profit_pct=pnl_pct,
+                hold_time_minutes=(now - position.entry_time).total_seconds() / 60, exit_reason=reason,
+                position_size=position.size * position.entry_price
+            )
+            await self._remove_position(token_id)
+            closed += 1
+        
+        if closed:
+            self._save_positions()
+        return closed
+    
     async def _get_actual_token_balance(self, token_id: str) -> Optional[Decimal]:
         """
         Query the ACTUAL token balance from the blockchain.
@@ -4459,27 +5243,19 @@ class FifteenMinuteCryptoStrategy:
             market: The market to process
         """
         try:
+            # Implies the market's strike on first sight, before any entry signal
+            self.fair_value(market)
+            
             # Check exit conditions with fresh market data
             await self.check_exit_conditions(market)
             
             # If we have capacity for new positions...
             if len(self.positions) < self.max_positions:
-                # Priority 0: Flash Crash Detection (HIGHEST PROFIT - 86% ROI bot strategy)
-                # Detect 15% moves in 3 seconds and trade the reversal
-                if await self.check_flash_crash(market):
-                    return
-                
-                # Priority 1: Latency Arbitrage (High probability, bigger profits)
-                if await self.check_latency_arbitrage(market):
-                    return
-                    
-                # Priority 2: Directional / LLM (Speculative, BIGGEST profits)
-                if await self.check_directional_trade(market):
-                    return
-                    
-                # Priority 3: Sum-to-one Arbitrage (Guaranteed but tiny profits)
-                # Only use as fallback when no better opportunities
-                await self.check_sum_to_one_arbitrage(market)
+                # Run entry checks in configured priority order; stop at first trade
+                for name in self.entry_order:
+                    check = getattr(self, self.ENTRY_CHECKS[name])
+                    if await check(market):
+                        return
         
         except Exception as e:
             # Log error but don't propagate (handled by gather)
@@ -4553,6 +5329,10 @@ class FifteenMinuteCryptoStrategy:
             # 1. Fetch markets
             markets = await self.fetch_15min_markets()
             
+            # Track fills on resting maker entries, requote or pull them
+            if self.maker_orders:
+                await self._sync_maker_orders(markets)
+            
             # 2. TASK 5.4: Process markets concurrently with limit of 10 at a time
             await self._process_markets_concurrently(markets, max_concurrent=10)
                     
@@ -86,7 +86,8 @@ class FundManager:
         target_balance: Decimal,
         withdraw_limit: Decimal,
         dry_run: bool = False,
-        oneinch_api_key: Optional[str] = None
+        oneinch_api_key: Optional[str] = None,
+        clob_client=None
     ):
         """
         Initialize Fund Manager.
@@ -101,6 +102,8 @@ class FundManager:
             withdraw_limit: Balance threshold for auto-withdrawal (dynamic, default $100)
             dry_run: If True, log actions but don't execute transactions
             oneinch_api_key: API key for 1inch aggregator (optional)
+            clob_client: Authenticated ClobClient for balance queries (optional;
+                otherwise one is created from PRIVATE_KEY)
         """
         self.web3 = web3
         self.wallet = wallet
@@ -111,6 +114,7 @@ class FundManager:
         self.withdraw_limit = withdraw_limit
         self.dry_run = dry_run
         self.oneinch_api_key = oneinch_api_key
+        self.clob_client = clob_client
         
         # Flag to show proxy wallet warning only once
         self._proxy_warning_shown = False
@@ -549,6 +553,11 @@ class FundManager:
             from py_clob_client.client import ClobClient
             from py_clob_client.clob_types import BalanceAllowanceParams, AssetType
             
+            if self.clob_client is not None:
+                params = BalanceAllowanceParams(asset_type=AssetType.COLLATERAL)
+                balance_info = self.clob_client.get_balance_allowance(params)
+                return Decimal(str(float(balance_info.get('balance', 0)))) / Decimal('1000000')
+            
             # Get private key from environment
             private_key = os.getenv("PRIVATE_KEY", "")
             wallet_address = self.wallet.address
@@ -10,8 +10,8 @@ Improves trade selection by 35%.
 import logging
 import json
 from decimal import Decimal
-from datetime import datetime, timedelta
-from typing import Dict, List, Optional, Tuple
+from datetime import datetime, timedelta, timezone
+from typing import Any, Dict, List, Optional, Tuple
 from dataclasses import dataclass, asdict
 from pathlib import Path
 from collections import defaultdict
@@ -48,14 +48,17 @@ class HistoricalSuccessTracker:
     - Success rate calculation
     """
     
-    def __init__(self, data_file: str = "data/historical_success.json"):
+    def __init__(self, data_file: str = "data/historical_success.json", store: Optional[Any] = None):
         """
         Initialize historical success tracker.
         
         Args:
             data_file: Path to JSON file for persistence
+            store: TradeOutcomeStore to read trades from instead of data_file;
+                subscribe on_trade_outcome to it
         """
         self.data_file = Path(data_file)
+        self.store = store
         self.trades: List[TradeRecord] = []
         
         # Performance metrics
@@ -86,8 +89,34 @@ class HistoricalSuccessTracker:
         
         logger.info(f"📊 Historical Success Tracker initialized ({len(self.trades)} trades loaded)")
     
+    @staticmethod
+    def _to_trade_record(outcome: Any) -> TradeRecord:
+        """TradeRecord of a stored trade (profit_usd normalized to a size of 1, like record_trade)."""
+        return TradeRecord(
+            timestamp=outcome.closed_at.isoformat(),
+            strategy=outcome.strategy,
+            asset=outcome.asset,
+            market_id=outcome.market_id,
+            entry_price=outcome.entry_price,
+            exit_price=outcome.exit_price,
+            size=1.0,
+            profit_pct=outcome.profit_pct,
+            profit_usd=outcome.exit_price - outcome.entry_price,
+            hold_time_minutes=outcome.hold_time_minutes,
+            exit_reason=outcome.exit_reason,
+            conditions=outcome.features
+        )
+    
     def _load_data(self):
-        """Load historical data from file."""
+        """Load historical data from the store, or from file without one."""
+        if self.store is not None:
+            try:
+                self.trades = [self._to_trade_record(outcome) for outcome in self.store.get_outcomes()]
+                self._recalculate_stats()
+            except Exception as e:
+                logger.error(f"Failed to load historical data: {e}")
+            return
+        
         if not self.data_file.exists():
             logger.info("No historical data found, starting fresh")
             return
@@ -230,6 +259,11 @@ class HistoricalSuccessTracker:
             f"profit={profit_pct*100:.2f}% (${profit_usd:.2f})"
         )
     
+    def on_trade_outcome(self, outcome: Any) -> None:
+        """Add a trade recorded in the TradeOutcomeStore (which persists it)."""
+        self.trades.append(self._to_trade_record(outcome))
+        self._recalculate_stats()
+    
     def get_strategy_score(self, strategy: str) -> float:
         """
         Get performance score for a strategy (0-100).
@@ -293,7 +327,8 @@ class HistoricalSuccessTracker:
             Score from 0-100 (higher is better)
         """
         if hour is None:
-            hour = datetime.now().hour
+            # Stored trades are timestamped in UTC, legacy file trades in local time
+            hour = datetime.now(timezone.utc).hour if self.store is not None else datetime.now().hour
         
         if hour not in self.hour_stats:
             return 50.0  # Neutral score
@@ -50,6 +50,7 @@ class InternalArbitrageEngine:
         kelly_sizer: KellyPositionSizer,
         dynamic_sizer: Optional[DynamicPositionSizer] = None,
         min_profit_threshold: Decimal = Decimal('0.005'),  # 0.5%
+        max_position_size: Decimal = Decimal('5.0'),  # USDC per trade
         current_balance_getter=None,  # Callable to get current balance
         current_gas_price_getter=None,  # Callable to get current gas price
         pending_tx_count_getter=None  # Callable to get pending TX count
@@ -65,6 +66,7 @@ class InternalArbitrageEngine:
             kelly_sizer: Kelly position sizer for optimal sizing
             dynamic_sizer: Dynamic position sizer (optional, recommended)
             min_profit_threshold: Minimum profit percentage (default 0.5%)
+            max_position_size: Cap on the sized position in USDC (hot-reloadable)
             current_balance_getter: Function to get current balance
             current_gas_price_getter: Function to get current gas price in gwei
             pending_tx_count_getter: Function to get pending transaction count
@@ -76,6 +78,7 @@ class InternalArbitrageEngine:
         self.kelly_sizer = kelly_sizer
         self.dynamic_sizer = dynamic_sizer or DynamicPositionSizer()
         self.min_profit_threshold = min_profit_threshold
+        self.max_position_size = max_position_size
         
         # Getters for safety checks
         self._get_balance = current_balance_getter or (lambda: Decimal('100.0'))
@@ -335,6 +338,8 @@ class InternalArbitrageEngine:
                 )
                 logger.info(f"Kelly position size: ${position_size} (bankroll: ${bankroll})")
             
+            position_size = min(position_size, self.max_position_size)
+            
             # Validate position size
             if position_size <= 0:
                 logger.warning("Position size is zero or negative, skipping trade")
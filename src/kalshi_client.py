@@ -0,0 +1,768 @@
+"""
+Kalshi REST/WebSocket client for cross-platform arbitrage.
+
+Implements the parts of the Kalshi Trade API v2 the bot needs:
+- RSA-PSS request signing (KALSHI-ACCESS-KEY / -TIMESTAMP / -SIGNATURE headers)
+- Market listing with cursor pagination, single markets and order books
+- Limit order placement (fill-or-kill by default), cancellation and lookup
+- Portfolio balance
+- Order book WebSocket feed (snapshots + deltas)
+- Kalshi's trading fee schedule
+
+Prices on the wire are integer cents; everything returned here is in dollars
+(Decimal), matching the rest of the bot.
+
+Validates Requirements:
+- 3.2: Connect to Kalshi and compare prices for equivalent markets
+- 3.4: Submit FOK orders on Kalshi
+- 3.6: Account for Kalshi trading fees
+"""
+
+import asyncio
+import base64
+import json
+import logging
+import time
+import uuid
+from dataclasses import dataclass, field
+from datetime import datetime
+from decimal import Decimal, ROUND_CEILING, ROUND_DOWN
+from typing import Any, Callable, Dict, List, Optional, Tuple
+from urllib.parse import urlparse
+
+import aiohttp
+
+from src.models import Market
+from src.order_manager import Order
+
+logger = logging.getLogger(__name__)
+
+KALSHI_API_URL = "https://api.elections.kalshi.com/trade-api/v2"
+KALSHI_WS_URL = "wss://api.elections.kalshi.com/trade-api/ws/v2"
+KALSHI_DEMO_API_URL = "https://demo-api.kalshi.co/trade-api/v2"
+
+CENT = Decimal("0.01")
+
+# Series ticker prefixes of the crypto markets that have Polymarket equivalents
+ASSET_PREFIXES = {"KXBTC": "BTC", "KXETH": "ETH", "KXSOL": "SOL", "KXXRP": "XRP"}
+
+
+class KalshiError(Exception):
+    """Base exception for Kalshi API errors."""
+
+    def __init__(self, message: str, status: Optional[int] = None, code: Optional[str] = None):
+        super().__init__(message)
+        self.status = status
+        self.code = code
+
+
+class KalshiAuthError(KalshiError):
+    """Raised on missing credentials or a rejected signature (401/403)."""
+    pass
+
+
+class KalshiOrderError(KalshiError):
+    """Raised when an order is rejected."""
+    pass
+
+
+def cents_to_dollars(value: Any) -> Optional[Decimal]:
+    """Integer cents from the API to dollars (None stays None)."""
+    if value is None:
+        return None
+    return Decimal(str(value)) / 100
+
+
+def dollars_to_cents(price: Decimal) -> int:
+    """Dollar price to integer cents (must be a whole cent between 1 and 99)."""
+    cents = Decimal(str(price)) * 100
+    if cents != cents.to_integral_value() or not 1 <= cents <= 99:
+        raise ValueError(f"Kalshi prices are whole cents between $0.01 and $0.99, got: {price}")
+    return int(cents)
+
+
+def _parse_time(value: Optional[str]) -> Optional[datetime]:
+    if not value:
+        return None
+    return datetime.fromisoformat(value.replace("Z", "+00:00"))
+
+
+def asset_from_ticker(ticker: str) -> Optional[str]:
+    """Crypto asset of a Kalshi ticker (KXBTCD-25OCT1617-T95000 -> BTC)."""
+    for prefix, asset in ASSET_PREFIXES.items():
+        if ticker.upper().startswith(prefix):
+            return asset
+    return None
+
+
+# ============================================================================
+# Fees
+# ============================================================================
+
+@dataclass
+class KalshiFeeModel:
+    """
+    Kalshi trading fees.
+
+    fee = round_up(rate * contracts * P * (1 - P)) to the next cent, per order.
+    Takers pay 7%. Makers pay nothing on most markets and 1.75% on the
+    series that charge maker fees.
+    """
+    taker_rate: Decimal = Decimal("0.07")
+    maker_rate: Decimal = Decimal("0")
+
+    def fee(self, contracts: Any, price: Decimal, maker: bool = False) -> Decimal:
+        """Fee in dollars for one order of `contracts` at `price`."""
+        rate = self.maker_rate if maker else self.taker_rate
+        raw = rate * Decimal(str(contracts)) * price * (1 - price)
+        return raw.quantize(CENT, rounding=ROUND_CEILING)
+
+    def fee_rate(self, price: Decimal, maker: bool = False) -> Decimal:
+        """Fee as a fraction of the cost of one contract, before rounding."""
+        rate = self.maker_rate if maker else self.taker_rate
+        return rate * (1 - price)
+
+
+# ============================================================================
+# Authentication
+# ============================================================================
+
+class KalshiSigner:
+    """
+    Signs requests with the API key's RSA private key.
+
+    The signature is RSA-PSS (SHA-256, digest-length salt) over
+    timestamp_ms + METHOD + path, where path excludes the query string.
+    """
+
+    def __init__(self, key_id: str, private_key_pem: str):
+        from cryptography.hazmat.primitives import serialization
+
+        self.key_id = key_id
+        self._private_key = serialization.load_pem_private_key(private_key_pem.encode(), password=None)
+
+    @classmethod
+    def from_file(cls, key_id: str, path: str) -> "KalshiSigner":
+        with open(path, "r") as f:
+            return cls(key_id, f.read())
+
+    def sign(self, message: str) -> str:
+        from cryptography.hazmat.primitives import hashes
+        from cryptography.hazmat.primitives.asymmetric import padding
+
+        signature = self._private_key.sign(
+            message.encode(),
+            padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=padding.PSS.DIGEST_LENGTH),
+            hashes.SHA256()
+        )
+        return base64.b64encode(signature).decode()
+
+    def headers(self, method: str, path: str, timestamp_ms: Optional[int] = None) -> Dict[str, str]:
+        """Authentication headers for one request."""
+        timestamp = str(timestamp_ms if timestamp_ms is not None else int(time.time() * 1000))
+        return {
+            "KALSHI-ACCESS-KEY": self.key_id,
+            "KALSHI-ACCESS-TIMESTAMP": timestamp,
+            "KALSHI-ACCESS-SIGNATURE": self.sign(timestamp + method.upper() + path.split("?")[0]),
+        }
+
+
+# ============================================================================
+# Data
+# ============================================================================
+
+@dataclass
+class KalshiMarket:
+    """A Kalshi binary market (prices in dollars)."""
+    ticker: str
+    event_ticker: str
+    title: str
+    yes_sub_title: str = ""
+    status: str = ""
+    yes_bid: Optional[Decimal] = None
+    yes_ask: Optional[Decimal] = None
+    no_bid: Optional[Decimal] = None
+    no_ask: Optional[Decimal] = None
+    last_price: Optional[Decimal] = None
+    volume: int = 0
+    liquidity: Decimal = Decimal("0")
+    close_time: Optional[datetime] = None
+    floor_strike: Optional[Decimal] = None
+    cap_strike: Optional[Decimal] = None
+
+    @classmethod
+    def from_api(cls, data: Dict[str, Any]) -> "KalshiMarket":
+        return cls(
+            ticker=data["ticker"],
+            event_ticker=data.get("event_ticker", ""),
+            title=data.get("title", ""),
+            yes_sub_title=data.get("yes_sub_title") or data.get("subtitle") or "",
+            status=data.get("status", ""),
+            yes_bid=cents_to_dollars(data.get("yes_bid")),
+            yes_ask=cents_to_dollars(data.get("yes_ask")),
+            no_bid=cents_to_dollars(data.get("no_bid")),
+            no_ask=cents_to_dollars(data.get("no_ask")),
+            last_price=cents_to_dollars(data.get("last_price")),
+            volume=int(data.get("volume") or 0),
+            liquidity=cents_to_dollars(data.get("liquidity")) or Decimal("0"),
+            close_time=_parse_time(data.get("close_time")),
+            floor_strike=Decimal(str(data["floor_strike"])) if data.get("floor_strike") is not None else None,
+            cap_strike=Decimal(str(data["cap_strike"])) if data.get("cap_strike") is not None else None,
+        )
+
+    @property
+    def is_tradeable(self) -> bool:
+        """Open, with a two-sided YES/NO ask to buy into."""
+        return (
+            self.status in ("active", "open")
+            and self.yes_ask is not None and 0 < self.yes_ask < 1
+            and self.no_ask is not None and 0 < self.no_ask < 1
+        )
+
+    def to_market(self) -> Optional[Market]:
+        """
+        The bot's Market model for this contract, priced at the asks.
+
+        Both token IDs are the ticker: a Kalshi order names the ticker and a side.
+        Returns None for non-crypto markets and markets without a close time.
+        """
+        asset = asset_from_ticker(self.ticker)
+        if asset is None or self.close_time is None or not self.is_tradeable:
+            return None
+        return Market(
+            market_id=self.ticker,
+            question=f"{self.title} {self.yes_sub_title}".strip(),
+            asset=asset,
+            outcomes=["YES", "NO"],
+            yes_price=self.yes_ask,
+            no_price=self.no_ask,
+            yes_token_id=self.ticker,
+            no_token_id=self.ticker,
+            volume=Decimal(self.volume),
+            liquidity=self.liquidity,
+            end_time=self.close_time,
+            resolution_source="kalshi",
+        )
+
+
+@dataclass
+class KalshiOrderBook:
+    """
+    Kalshi order book: YES bids and NO bids, (price, contracts) ascending.
+
+    Kalshi only lists bids. Buying YES at p takes NO bids at 1 - p, so the YES
+    ask ladder is the NO bid ladder mirrored (and vice versa).
+    """
+    ticker: str
+    yes: List[Tuple[Decimal, int]] = field(default_factory=list)
+    no: List[Tuple[Decimal, int]] = field(default_factory=list)
+
+    @classmethod
+    def from_api(cls, ticker: str, data: Dict[str, Any]) -> "KalshiOrderBook":
+        book = data.get("orderbook", data) or {}
+        return cls(
+            ticker=ticker,
+            yes=sorted((cents_to_dollars(p), int(q)) for p, q in (book.get("yes") or [])),
+            no=sorted((cents_to_dollars(p), int(q)) for p, q in (book.get("no") or [])),
+        )
+
+    def bids(self, side: str) -> List[Tuple[Decimal, int]]:
+        return self.yes if side.lower() == "yes" else self.no
+
+    def asks(self, side: str) -> List[Tuple[Decimal, int]]:
+        """Ask ladder for buying `side`, best (lowest) first."""
+        opposite = self.no if side.lower() == "yes" else self.yes
+        return [(1 - price, qty) for price, qty in reversed(opposite)]
+
+    def best_bid(self, side: str) -> Optional[Decimal]:
+        bids = self.bids(side)
+        return bids[-1][0] if bids else None
+
+    def best_ask(self, side: str) -> Optional[Decimal]:
+        asks = self.asks(side)
+        return asks[0][0] if asks else None
+
+    def fillable(self, side: str, limit_price: Decimal) -> int:
+        """Contracts of `side` that can be bought at or below limit_price."""
+        return sum(qty for price, qty in self.asks(side) if price <= limit_price)
+
+    def apply_delta(self, side: str, price: Decimal, delta: int) -> None:
+        """Apply a WebSocket orderbook_delta to one price level."""
+        levels = dict(self.bids(side))
+        quantity = levels.get(price, 0) + delta
+        if quantity > 0:
+            levels[price] = quantity
+        else:
+            levels.pop(price, None)
+        if side.lower() == "yes":
+            self.yes = sorted(levels.items())
+        else:
+            self.no = sorted(levels.items())
+
+
+@dataclass
+class KalshiOrder:
+    """A Kalshi order as returned by the API."""
+    order_id: str
+    ticker: str
+    side: str  # "yes" or "no"
+    action: str  # "buy" or "sell"
+    status: str  # "resting", "canceled", "executed"
+    price: Decimal
+    count: int
+    fill_count: int = 0
+    remaining_count: int = 0
+    fill_cost: Decimal = Decimal("0")
+    fees: Decimal = Decimal("0")
+    client_order_id: str = ""
+
+    @classmethod
+    def from_api(cls, data: Dict[str, Any]) -> "KalshiOrder":
+        order = data.get("order", data)
+        side = order.get("side", "yes")
+        price = cents_to_dollars(order.get("yes_price") if side == "yes" else order.get("no_price"))
+        fill_count = int(order.get("fill_count", order.get("taker_fill_count", 0)) or 0)
+        remaining = int(order.get("remaining_count", 0) or 0)
+        return cls(
+            order_id=order["order_id"],
+            ticker=order.get("ticker", ""),
+            side=side,
+            action=order.get("action", "buy"),
+            status=order.get("status", ""),
+            price=price or Decimal("0"),
+            count=int(order.get("initial_count", fill_count + remaining) or 0),
+            fill_count=fill_count,
+            remaining_count=remaining,
+            fill_cost=cents_to_dollars(
+                (order.get("taker_fill_cost") or 0) + (order.get("maker_fill_cost") or 0)
+            ),
+            fees=cents_to_dollars((order.get("taker_fees") or 0) + (order.get("maker_fees") or 0)),
+            client_order_id=order.get("client_order_id", ""),
+        )
+
+    @property
+    def average_fill_price(self) -> Optional[Decimal]:
+        if self.fill_count == 0:
+            return None
+        if self.fill_cost > 0:
+            return self.fill_cost / self.fill_count
+        return self.price
+
+
+# ============================================================================
+# REST client
+# ============================================================================
+
+class KalshiClient:
+    """
+    Async Kalshi Trade API v2 client.
+
+    Features:
+    - Signed requests (public market data works without credentials)
+    - Cursor-paginated market listing
+    - Order books, order placement/cancellation, balance
+    - Errors mapped to KalshiError / KalshiAuthError / KalshiOrderError
+    """
+
+    def __init__(
+        self,
+        key_id: Optional[str] = None,
+        private_key_path: Optional[str] = None,
+        private_key_pem: Optional[str] = None,
+        base_url: str = KALSHI_API_URL,
+        fee_model: Optional[KalshiFeeModel] = None,
+        timeout: float = 10.0
+    ):
+        """
+        Initialize the Kalshi client.
+
+        Args:
+            key_id: API key ID
+            private_key_path: Path to the API key's RSA private key (PEM)
+            private_key_pem: The private key itself (instead of a path)
+            base_url: API base URL (production or KALSHI_DEMO_API_URL)
+            fee_model: Fee schedule (default: standard taker fees)
+            timeout: Request timeout in seconds
+        """
+        self.base_url = base_url.rstrip("/")
+        self._path_prefix = urlparse(self.base_url).path
+        self.fee_model = fee_model or KalshiFeeModel()
+        self.timeout = timeout
+        self._session: Optional[aiohttp.ClientSession] = None
+
+        self.signer: Optional[KalshiSigner] = None
+        if key_id and (private_key_path or private_key_pem):
+            self.signer = (
+                KalshiSigner(key_id, private_key_pem) if private_key_pem
+                else KalshiSigner.from_file(key_id, private_key_path)
+            )
+
+        logger.info(f"KalshiClient initialized: {self.base_url} (authenticated={self.signer is not None})")
+
+    async def close(self) -> None:
+        if self._session is not None:
+            await self._session.close()
+            self._session = None
+
+    async def _request(
+        self,
+        method: str,
+        path: str,
+        params: Optional[Dict[str, Any]] = None,
+        body: Optional[Dict[str, Any]] = None,
+        auth: bool = False
+    ) -> Dict[str, Any]:
+        """Send one API request and return the decoded JSON body."""
+        headers = {"Content-Type": "application/json"}
+        if self.signer is not None:
+            headers.update(self.signer.headers(method, self._path_prefix + path))
+        elif auth:
+            raise KalshiAuthError("Kalshi credentials (key ID and private key) are required")
+
+        if self._session is None:
+            self._session = aiohttp.ClientSession()
+
+        params = {k: v for k, v in (params or {}).items() if v is not None}
+        async with self._session.request(
+            method, self.base_url + path, params=params, json=body, headers=headers,
+            timeout=aiohttp.ClientTimeout(total=self.timeout)
+        ) as resp:
+            try:
+                data = await resp.json(content_type=None)
+            except Exception:
+                data = {}
+            if resp.status >= 400:
+                error = (data or {}).get("error") or {}
+                message = error.get("message") or f"HTTP {resp.status}"
+                error_cls = KalshiAuthError if resp.status in (401, 403) else KalshiError
+                raise error_cls(f"Kalshi {method} {path}: {message}", resp.status, error.get("code"))
+            return data or {}
+
+    # ------------------------------------------------------------------------
+    # Market data
+    # ------------------------------------------------------------------------
+
+    async def get_markets(
+        self,
+        status: str = "open",
+        series_ticker: Optional[str] = None,
+        event_ticker: Optional[str] = None,
+        limit: int = 200,
+        max_pages: int = 10
+    ) -> List[KalshiMarket]:
+        """List markets, following the cursor for up to max_pages pages."""
+        markets: List[KalshiMarket] = []
+        cursor = None
+        for _ in range(max_pages):
+            data = await self._request("GET", "/markets", params={
+                "status": status, "series_ticker": series_ticker, "event_ticker": event_ticker,
+                "limit": limit, "cursor": cursor
+            })
+            markets.extend(KalshiMarket.from_api(m) for m in data.get("markets", []))
+            cursor = data.get("cursor")
+            if not cursor:
+                break
+        return markets
+
+    async def get_market(self, ticker: str) -> KalshiMarket:
+        data = await self._request("GET", f"/markets/{ticker}")
+        return KalshiMarket.from_api(data["market"])
+
+    async def get_orderbook(self, ticker: str, depth: Optional[int] = None) -> KalshiOrderBook:
+        data = await self._request("GET", f"/markets/{ticker}/orderbook", params={"depth": depth})
+        return KalshiOrderBook.from_api(ticker, data)
+
+    # ------------------------------------------------------------------------
+    # Portfolio and orders
+    # ------------------------------------------------------------------------
+
+    async def get_balance(self) -> Decimal:
+        """Available cash balance in dollars."""
+        data = await self._request("GET", "/portfolio/balance", auth=True)
+        return cents_to_dollars(data.get("balance", 0))
+
+    async def place_order(
+        self,
+        ticker: str,
+        side: str,
+        count: int,
+        price: Decimal,
+        action: str = "buy",
+        time_in_force: str = "fill_or_kill",
+        client_order_id: Optional[str] = None,
+        post_only: bool = False
+    ) -> KalshiOrder:
+        """
+        Place a limit order.
+
+        Args:
+            ticker: Market ticker
+            side: "yes" or "no"
+            count: Number of contracts
+            price: Limit price in dollars for the given side (whole cents)
+            action: "buy" or "sell"
+            time_in_force: "fill_or_kill", "immediate_or_cancel" or "good_till_canceled"
+            client_order_id: Idempotency key (generated if omitted)
+            post_only: Reject instead of taking liquidity
+
+        Returns:
+            KalshiOrder with the fill details
+
+        Raises:
+            KalshiOrderError: If the order is rejected
+        """
+        side = side.lower()
+        if side not in ("yes", "no"):
+            raise ValueError(f"Invalid Kalshi side: {side}")
+        if count < 1:
+            raise ValueError(f"Kalshi orders need at least one contract, got: {count}")
+
+        body = {
+            "ticker": ticker,
+            "action": action,
+            "side": side,
+            "count": int(count),
+            "type": "limit",
+            f"{side}_price": dollars_to_cents(price),
+            "client_order_id": client_order_id or uuid.uuid4().hex,
+            "time_in_force": time_in_force,
+        }
+        if post_only:
+            body["post_only"] = True
+
+        try:
+            data = await self._request("POST", "/portfolio/orders", body=body, auth=True)
+        except KalshiAuthError:
+            raise
+        except KalshiError as e:
+            raise KalshiOrderError(str(e), e.status, e.code)
+        return KalshiOrder.from_api(data)
+
+    async def cancel_order(self, order_id: str) -> KalshiOrder:
+        data = await self._request("DELETE", f"/portfolio/orders/{order_id}", auth=True)
+        return KalshiOrder.from_api(data)
+
+    async def get_order(self, order_id: str) -> KalshiOrder:
+        data = await self._request("GET", f"/portfolio/orders/{order_id}", auth=True)
+        return KalshiOrder.from_api(data)
+
+
+# ============================================================================
+# WebSocket order book feed
+# ============================================================================
+
+class KalshiWebSocketFeed:
+    """
+    Real-time Kalshi order books over the authenticated WebSocket.
+
+    Features:
+    - orderbook_delta channel: snapshot, then per-level deltas
+    - Sequence gap detection (resubscribes to get a fresh snapshot)
+    - Auto-reconnect with exponential backoff
+    """
+
+    def __init__(
+        self,
+        signer: KalshiSigner,
+        ws_url: str = KALSHI_WS_URL,
+        on_book_update: Optional[Callable[[KalshiOrderBook], None]] = None,
+        initial_reconnect_delay: float = 1.0,
+        max_reconnect_delay: float = 60.0
+    ):
+        self.signer = signer
+        self.ws_url = ws_url
+        self.on_book_update = on_book_update
+        self.initial_reconnect_delay = initial_reconnect_delay
+        self.max_reconnect_delay = max_reconnect_delay
+
+        self.books: Dict[str, KalshiOrderBook] = {}
+        self._tickers: List[str] = []
+        self._seq: Dict[int, int] = {}
+        self._next_id = 1
+        self._ws: Optional[aiohttp.ClientWebSocketResponse] = None
+        self._session: Optional[aiohttp.ClientSession] = None
+        self._running = False
+        self._task: Optional[asyncio.Task] = None
+
+    def get_orderbook(self, ticker: str) -> Optional[KalshiOrderBook]:
+        return self.books.get(ticker)
+
+    async def start(self, tickers: List[str]) -> None:
+        self._tickers = list(tickers)
+        self._running = True
+        self._task = asyncio.create_task(self._run())
+
+    async def stop(self) -> None:
+        self._running = False
+        if self._task:
+            self._task.cancel()
+        if self._ws is not None:
+            await self._ws.close()
+        if self._session is not None:
+            await self._session.close()
+            self._session = None
+
+    async def _subscribe(self) -> None:
+        await self._ws.send_json({
+            "id": self._next_id,
+            "cmd": "subscribe",
+            "params": {"channels": ["orderbook_delta"], "market_tickers": self._tickers},
+        })
+        self._next_id += 1
+
+    async def _run(self) -> None:
+        delay = self.initial_reconnect_delay
+        while self._running:
+            try:
+                if self._session is None:
+                    self._session = aiohttp.ClientSession()
+                headers = self.signer.headers("GET", urlparse(self.ws_url).path)
+                self._ws = await self._session.ws_connect(self.ws_url, headers=headers, heartbeat=30)
+                logger.info(f"✅ Connected to Kalshi WebSocket ({len(self._tickers)} markets)")
+                delay = self.initial_reconnect_delay
+                self._seq.clear()
+                await self._subscribe()
+
+                async for msg in self._ws:
+                    if msg.type == aiohttp.WSMsgType.TEXT:
+                        if not self.handle_message(json.loads(msg.data)):
+                            await self._subscribe()  # Sequence gap: get a fresh snapshot
+                    elif msg.type in (aiohttp.WSMsgType.CLOSED, aiohttp.WSMsgType.ERROR):
+                        break
+            except asyncio.CancelledError:
+                break
+            except Exception as e:
+                logger.warning(f"Kalshi WebSocket error: {e}")
+
+            if self._running:
+                await asyncio.sleep(delay)
+                delay = min(delay * 2, self.max_reconnect_delay)
+
+    def handle_message(self, data: Dict[str, Any]) -> bool:
+        """
+        Apply one WebSocket message to the books.
+
+        Returns:
+            False if a sequence gap was detected (the caller resubscribes)
+        """
+        msg_type = data.get("type")
+        msg = data.get("msg") or {}
+
+        sid, seq = data.get("sid"), data.get("seq")
+        if sid is not None and seq is not None:
+            last = self._seq.get(sid)
+            self._seq[sid] = seq
+            if last is not None and msg_type == "orderbook_delta" and seq != last + 1:
+                logger.warning(f"Kalshi sequence gap on sid {sid}: {last} -> {seq}")
+                return False
+
+        if msg_type == "orderbook_snapshot":
+            ticker = msg["market_ticker"]
+            self.books[ticker] = KalshiOrderBook.from_api(ticker, msg)
+        elif msg_type == "orderbook_delta":
+            ticker = msg["market_ticker"]
+            book = self.books.setdefault(ticker, KalshiOrderBook(ticker))
+            book.apply_delta(msg["side"], cents_to_dollars(msg["price"]), int(msg["delta"]))
+        elif msg_type == "error":
+            logger.error(f"Kalshi WebSocket error message: {msg}")
+            return True
+        else:
+            return True
+
+        if self.on_book_update:
+            self.on_book_update(self.books[ticker])
+        return True
+
+
+# ============================================================================
+# Order manager
+# ============================================================================
+
+class KalshiOrderManager:
+    """
+    Kalshi orders behind the OrderManager interface used by
+    CrossPlatformArbitrageEngine (create_fok_order / submit_order / cancel_order).
+
+    Order.market_id is the Kalshi ticker and Order.size the number of
+    contracts (rounded down to a whole contract).
+    """
+
+    def __init__(self, client: Any, dry_run: bool = False, default_slippage: Decimal = Decimal("0.001")):
+        self.client = client
+        self.dry_run = dry_run
+        self.default_slippage = default_slippage
+        self._active_orders: Dict[str, Order] = {}
+        self.fees: Dict[str, Decimal] = {}  # Order ID -> fees charged
+
+    def create_fok_order(
+        self,
+        market_id: str,
+        side: str,
+        price: Decimal,
+        size: Decimal,
+        slippage_tolerance: Optional[Decimal] = None,
+        **kwargs
+    ) -> Order:
+        """Create a fill-or-kill order for `size` contracts of `side` on ticker `market_id`."""
+        if side not in ["YES", "NO"]:
+            raise ValueError(f"Invalid side: {side}")
+        dollars_to_cents(price)  # Validates the price
+        contracts = Decimal(str(size)).to_integral_value(rounding=ROUND_DOWN)
+        if contracts < 1:
+            raise ValueError(f"Invalid size: {size} (at least one contract)")
+
+        order = Order(
+            order_id=f"kalshi_{uuid.uuid4().hex[:12]}",
+            market_id=market_id,
+            side=side,
+            price=price,
+            size=contracts,
+            order_type="FOK",
+            slippage_tolerance=slippage_tolerance if slippage_tolerance is not None else self.default_slippage,
+            created_at=datetime.now(),
+            neg_risk=False
+        )
+        self._active_orders[order.order_id] = order
+        return order
+
+    async def submit_order(self, order: Order) -> bool:
+        """Submit the order; True if it filled completely."""
+        if self.dry_run:
+            logger.info(f"DRY RUN: Simulating Kalshi order {order.order_id}")
+            order.filled = True
+            order.fill_price = order.price
+            self.fees[order.order_id] = self.client.fee_model.fee(order.size, order.price)
+            return True
+
+        try:
+            result = await self.client.place_order(
+                ticker=order.market_id, side=order.side.lower(), count=int(order.size),
+                price=order.price, client_order_id=order.order_id
+            )
+        except KalshiError as e:
+            logger.warning(f"⚠️ Kalshi order rejected: {e}")
+            order.error_message = str(e)
+            return False
+
+        self._active_orders.pop(order.order_id, None)
+        order.order_id = result.order_id
+        self._active_orders[order.order_id] = order
+        order.status = result.status
+        order.size_matched = Decimal(result.fill_count)
+        self.fees[order.order_id] = result.fees
+
+        if result.fill_count < int(order.size):
+            logger.warning(f"⚠️ Kalshi order not filled: {result.order_id} ({result.fill_count}/{int(order.size)})")
+            return False
+
+        order.filled = True
+        order.fill_price = result.average_fill_price
+        return True
+
+    async def cancel_order(self, order_id: str) -> bool:
+        try:
+            await self.client.cancel_order(order_id)
+            return True
+        except KalshiError as e:
+            logger.warning(f"Failed to cancel Kalshi order {order_id}: {e}")
+            return False
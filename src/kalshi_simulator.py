@@ -0,0 +1,247 @@
+"""
+Recorded-fixture Kalshi client for offline tests and dry runs.
+
+FixtureKalshiClient answers with API responses recorded from a live
+KalshiClient (record_fixture) and parses them with the same code, so the
+cross-platform arbitrage engine runs end to end without network access.
+
+Orders fill against the recorded order books:
+- Buying YES at p takes NO bids at 1 - p (and vice versa), best price first
+- Fill-or-kill orders fill completely or are rejected
+- Fees follow KalshiFeeModel; the balance is debited cost plus fees
+
+Validates Requirements:
+- 3.2, 3.4: Offline Kalshi market data and FOK execution for tests
+"""
+
+import json
+import logging
+from dataclasses import dataclass
+from decimal import Decimal
+from typing import Any, Dict, List, Optional
+
+from src.kalshi_client import (
+    KalshiFeeModel,
+    KalshiMarket,
+    KalshiOrder,
+    KalshiOrderBook,
+    KalshiOrderError,
+    cents_to_dollars,
+    dollars_to_cents,
+)
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass
+class FixtureFill:
+    """One fill against the recorded book."""
+    order_id: str
+    ticker: str
+    side: str
+    action: str
+    price: Decimal
+    count: int
+    fee: Decimal
+
+
+class FixtureKalshiClient:
+    """
+    Offline stand-in for KalshiClient backed by a recorded fixture.
+
+    Fixture format (raw API payloads, prices in cents):
+        {
+          "markets": [<GET /markets "markets" entries>],
+          "orderbooks": {"<ticker>": <GET /markets/<ticker>/orderbook body>},
+          "balance": <GET /portfolio/balance "balance">
+        }
+    """
+
+    def __init__(self, fixture: Dict[str, Any], fee_model: Optional[KalshiFeeModel] = None):
+        self.fee_model = fee_model or KalshiFeeModel()
+        self._markets = [dict(m) for m in fixture.get("markets", [])]
+        self.books: Dict[str, KalshiOrderBook] = {
+            ticker: KalshiOrderBook.from_api(ticker, data)
+            for ticker, data in fixture.get("orderbooks", {}).items()
+        }
+        self.balance = cents_to_dollars(fixture.get("balance", 0))
+        self.positions: Dict[str, Dict[str, int]] = {}  # ticker -> {"yes": n, "no": n}
+        self.orders: Dict[str, KalshiOrder] = {}
+        self.fills: List[FixtureFill] = []
+        self._next_order = 1
+
+    @classmethod
+    def from_fixture(cls, fixture: Any, fee_model: Optional[KalshiFeeModel] = None) -> "FixtureKalshiClient":
+        """Load from a dict or a JSON file path."""
+        if isinstance(fixture, str):
+            with open(fixture, "r") as f:
+                fixture = json.load(f)
+        return cls(fixture, fee_model)
+
+    async def close(self) -> None:
+        return None
+
+    # ------------------------------------------------------------------------
+    # Market data
+    # ------------------------------------------------------------------------
+
+    async def get_markets(
+        self,
+        status: str = "open",
+        series_ticker: Optional[str] = None,
+        event_ticker: Optional[str] = None,
+        **kwargs
+    ) -> List[KalshiMarket]:
+        markets = [KalshiMarket.from_api(m) for m in self._markets]
+        if status == "open":
+            markets = [m for m in markets if m.status in ("active", "open")]
+        if series_ticker:
+            markets = [m for m in markets if m.ticker.startswith(series_ticker + "-")]
+        if event_ticker:
+            markets = [m for m in markets if m.event_ticker == event_ticker]
+        return markets
+
+    async def get_market(self, ticker: str) -> KalshiMarket:
+        for market in self._markets:
+            if market["ticker"] == ticker:
+                return KalshiMarket.from_api(market)
+        raise KalshiOrderError(f"market not found: {ticker}", 404, "not_found")
+
+    async def get_orderbook(self, ticker: str, depth: Optional[int] = None) -> KalshiOrderBook:
+        book = self.books.get(ticker, KalshiOrderBook(ticker))
+        return KalshiOrderBook(ticker, list(book.yes), list(book.no))
+
+    async def get_balance(self) -> Decimal:
+        return self.balance
+
+    # ------------------------------------------------------------------------
+    # Orders
+    # ------------------------------------------------------------------------
+
+    async def place_order(
+        self,
+        ticker: str,
+        side: str,
+        count: int,
+        price: Decimal,
+        action: str = "buy",
+        time_in_force: str = "fill_or_kill",
+        client_order_id: Optional[str] = None,
+        post_only: bool = False
+    ) -> KalshiOrder:
+        side = side.lower()
+        limit_cents = dollars_to_cents(price)
+        book = self.books.get(ticker)
+        if book is None:
+            raise KalshiOrderError(f"market not found: {ticker}", 404, "not_found")
+        if action != "buy":
+            raise KalshiOrderError("fixture client only supports buy orders", 400, "invalid_order")
+
+        # Levels this order can take, best first
+        levels = [(p, q) for p, q in book.asks(side) if p <= price]
+        available = sum(q for _, q in levels)
+        if post_only and available:
+            raise KalshiOrderError("post only order would cross", 409, "post_only_cross")
+        if time_in_force == "fill_or_kill" and available < count:
+            raise KalshiOrderError(
+                f"fill or kill order could not be filled: {available}/{count} available",
+                409, "fill_or_kill_insufficient_resting_volume"
+            )
+
+        fills = []
+        remaining = count
+        for level_price, quantity in levels:
+            take = min(quantity, remaining)
+            if take == 0:
+                break
+            fills.append((level_price, take))
+            remaining -= take
+
+        filled = count - remaining
+        cost = sum((p * q for p, q in fills), Decimal("0"))
+        fee = sum((self.fee_model.fee(q, p) for p, q in fills), Decimal("0"))
+        if cost + fee > self.balance:
+            raise KalshiOrderError("insufficient balance", 400, "insufficient_balance")
+
+        # Consume the opposite side's bids
+        for level_price, take in fills:
+            book.apply_delta("no" if side == "yes" else "yes", 1 - level_price, -take)
+        self.balance -= cost + fee
+        position = self.positions.setdefault(ticker, {"yes": 0, "no": 0})
+        position[side] += filled
+
+        order_id = f"fixture-{self._next_order}"
+        self._next_order += 1
+        for level_price, take in fills:
+            self.fills.append(FixtureFill(order_id, ticker, side, action, level_price, take, self.fee_model.fee(take, level_price)))
+
+        resting = remaining if time_in_force == "good_till_canceled" else 0
+        order = KalshiOrder.from_api({
+            "order_id": order_id,
+            "ticker": ticker,
+            "side": side,
+            "action": action,
+            "status": "resting" if resting else ("executed" if filled else "canceled"),
+            f"{side}_price": limit_cents,
+            "initial_count": count,
+            "fill_count": filled,
+            "remaining_count": resting,
+            "taker_fill_cost": int(cost * 100),
+            "taker_fees": int(fee * 100),
+            "client_order_id": client_order_id or "",
+        })
+        self.orders[order_id] = order
+        logger.info(f"[FIXTURE] Kalshi {action} {filled}/{count} {side.upper()} {ticker} for ${cost} + ${fee} fees")
+        return order
+
+    async def cancel_order(self, order_id: str) -> KalshiOrder:
+        order = self.orders.get(order_id)
+        if order is None:
+            raise KalshiOrderError(f"order not found: {order_id}", 404, "not_found")
+        order.status = "canceled"
+        order.remaining_count = 0
+        return order
+
+    async def get_order(self, order_id: str) -> KalshiOrder:
+        if order_id not in self.orders:
+            raise KalshiOrderError(f"order not found: {order_id}", 404, "not_found")
+        return self.orders[order_id]
+
+
+async def record_fixture(
+    client: Any,
+    path: str,
+    series_tickers: Optional[List[str]] = None,
+    max_books: int = 50
+) -> Dict[str, Any]:
+    """
+    Record live Kalshi responses into a fixture file for FixtureKalshiClient.
+
+    Args:
+        client: Live KalshiClient (credentials needed for the balance)
+        path: JSON file to write
+        series_tickers: Series to record (default: every open market)
+        max_books: Maximum number of order books to record
+
+    Returns:
+        The recorded fixture
+    """
+    markets = []
+    for series in series_tickers or [None]:
+        data = await client._request("GET", "/markets", params={"status": "open", "series_ticker": series, "limit": 200})
+        markets.extend(data.get("markets", []))
+
+    orderbooks = {}
+    for market in markets[:max_books]:
+        ticker = market["ticker"]
+        orderbooks[ticker] = await client._request("GET", f"/markets/{ticker}/orderbook")
+
+    balance = 0
+    if client.signer is not None:
+        balance = (await client._request("GET", "/portfolio/balance", auth=True)).get("balance", 0)
+
+    fixture = {"markets": markets, "orderbooks": orderbooks, "balance": balance}
+    with open(path, "w") as f:
+        json.dump(fixture, f, indent=2)
+    logger.info(f"Recorded Kalshi fixture: {len(markets)} markets, {len(orderbooks)} books -> {path}")
+    return fixture
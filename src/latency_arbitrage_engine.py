@@ -12,12 +12,13 @@ from collections import deque
 from dataclasses import dataclass
 from datetime import datetime, timedelta
 from decimal import Decimal
-from typing import List, Optional, Dict, AsyncIterator
+from typing import List, Optional, Dict, AsyncIterator, Tuple
 
 from src.models import Market, Opportunity, TradeResult
 from src.ai_safety_guard import AISafetyGuard
 from src.kelly_position_sizer import KellyPositionSizer
 from src.order_manager import OrderManager
+from src.fair_value_pricer import FairValuePricer
 
 logger = logging.getLogger(__name__)
 
@@ -78,12 +79,18 @@ class LatencyArbitrageEngine:
     # Minimum lag percentage to identify opportunity (Requirement 4.3)
     MIN_LAG_PERCENTAGE = Decimal('0.01')  # 1%
     
+    # Model probability (confidence band included) required to expect a catch-up
+    DECISIVE_PROBABILITY = Decimal('0.90')
+    
     # Maximum volatility before skipping (Requirement 4.6)
     MAX_VOLATILITY = Decimal('0.05')  # 5%
     
     # Target execution latency (Requirement 4.4)
     TARGET_LATENCY_MS = 150
     
+    # Signal tick times kept for opportunities awaiting execution
+    MAX_TRACKED_SIGNALS = 100
+    
     def __init__(
         self,
         cex_feeds: Dict[str, 'WebSocketFeed'],  # Type hint as string to avoid import
@@ -94,7 +101,9 @@ class LatencyArbitrageEngine:
         min_profit_threshold: Decimal = Decimal('0.005'),  # 0.5%
         current_balance_getter=None,
         current_gas_price_getter=None,
-        pending_tx_count_getter=None
+        pending_tx_count_getter=None,
+        fair_value_pricer: Optional[FairValuePricer] = None,
+        metrics=None
     ):
         """
         Initialize Latency Arbitrage Engine.
@@ -109,6 +118,9 @@ class LatencyArbitrageEngine:
             current_balance_getter: Function to get current balance
             current_gas_price_getter: Function to get current gas price in gwei
             pending_tx_count_getter: Function to get pending transaction count
+            fair_value_pricer: Shared FairValuePricer on the Binance feed (without one, the
+                model has no realized volatility and uses per-asset defaults)
+            metrics: MonitoringSystem exporting opportunities, entries and tick-to-order latency (optional)
         """
         self.cex_feeds = cex_feeds
         self.clob_client = clob_client
@@ -116,6 +128,9 @@ class LatencyArbitrageEngine:
         self.ai_safety_guard = ai_safety_guard
         self.kelly_sizer = kelly_sizer
         self.min_profit_threshold = min_profit_threshold
+        if fair_value_pricer is None:
+            logger.warning("LatencyArbitrageEngine without a shared FairValuePricer - using default volatilities")
+        self.fair_value_pricer = fair_value_pricer or FairValuePricer()
         
         # Getters for safety checks
         self._get_balance = current_balance_getter or (lambda: Decimal('100.0'))
@@ -133,6 +148,10 @@ class LatencyArbitrageEngine:
         # Last known prices for movement detection
         self._last_prices: Dict[str, Decimal] = {}
         
+        # Asset and CEX tick time behind each opportunity, for tick-to-order latency
+        self.metrics = metrics
+        self._signal_ticks: Dict[str, Tuple[str, datetime]] = {}
+        
         logger.info(
             f"LatencyArbitrageEngine initialized: "
             f"min_profit_threshold={min_profit_threshold * 100}%, "
@@ -401,6 +420,7 @@ class LatencyArbitrageEngine:
                     # CEX went down, expect NO price to rise
                     current_price = market.no_price
                     trade_side = "NO"
+                    expected_price = Decimal('1') - expected_price
                 
                 # Calculate lag percentage (Requirement 4.3)
                 if expected_price == 0:
@@ -469,6 +489,11 @@ class LatencyArbitrageEngine:
                         f"Latency {latency_ms:.0f}ms exceeds target {self.TARGET_LATENCY_MS}ms"
                     )
                 
+                if self.metrics is not None:
+                    self.metrics.record_strategy_opportunity("latency_arbitrage", movement.asset)
+                self._signal_ticks[opportunity.opportunity_id] = (movement.asset, movement.timestamp)
+                if len(self._signal_ticks) > self.MAX_TRACKED_SIGNALS:  # Opportunities never executed
+                    del self._signal_ticks[next(iter(self._signal_ticks))]
                 return opportunity
             
             return None
@@ -557,6 +582,15 @@ class LatencyArbitrageEngine:
             latency_ms = (datetime.now() - start_time).total_seconds() * 1000
             logger.info(f"Execution latency: {latency_ms:.0f}ms")
             
+            signal = self._signal_ticks.pop(opportunity.opportunity_id, None)
+            if self.metrics is not None and signal is not None:
+                asset, tick_time = signal
+                self.metrics.record_tick_to_order(
+                    "latency_arbitrage", asset, (datetime.now() - tick_time).total_seconds() * 1000
+                )
+                if filled:
+                    self.metrics.record_strategy_entry("latency_arbitrage", asset)
+            
             if not filled:
                 logger.warning("FOK order failed to fill")
                 return self._create_failed_result(
@@ -683,10 +717,13 @@ class LatencyArbitrageEngine:
         market: Market
     ) -> Optional[Decimal]:
         """
-        Calculate expected Polymarket price based on CEX movement.
+        Calculate expected Polymarket YES price based on CEX movement.
         
         Validates Requirement 4.3: Calculate expected market direction
         
+        The expected price is the FairValuePricer YES probability at the new
+        CEX price, returned only when the model is decisive.
+        
         Args:
             movement: CEX price movement
             market: Polymarket market
@@ -695,42 +732,19 @@ class LatencyArbitrageEngine:
             Expected price, or None if cannot calculate
         """
         try:
-            # Parse strike price from market question
-            strike_price = market.parse_strike_price()
-            
-            if strike_price is None:
+            fair_value = self.fair_value_pricer.price_market(market, spot=movement.new_price)
+            if fair_value is None:
+                # No strike or above/below direction in the question
                 return None
             
-            # Determine market type (above/below)
-            question_lower = market.question.lower()
-            
-            # Use a small threshold to handle floating point comparison
-            threshold = Decimal('0.01')  # $0.01 threshold
-            
-            if "above" in question_lower:
-                # Market resolves YES if price > strike
-                if movement.new_price > strike_price + threshold:
-                    # Price clearly above strike, YES should be high
-                    return Decimal('0.95')  # Expect 95% probability
-                elif movement.new_price < strike_price - threshold:
-                    # Price clearly below strike, NO should be high
-                    return Decimal('0.05')  # Expect 5% probability for YES
-                else:
-                    # Price too close to strike, uncertain
-                    return None
-                    
-            elif "below" in question_lower:
-                # Market resolves YES if price < strike
-                if movement.new_price < strike_price - threshold:
-                    # Price clearly below strike, YES should be high
-                    return Decimal('0.95')
-                elif movement.new_price > strike_price + threshold:
-                    # Price clearly above strike, NO should be high
-                    return Decimal('0.05')
-                else:
-                    # Price too close to strike, uncertain
-                    return None
+            # Only expect a catch-up once the model is confident in the outcome,
+            # across its whole confidence band
+            if fair_value.lower >= self.DECISIVE_PROBABILITY:
+                return fair_value.probability
+            if fair_value.upper <= Decimal('1') - self.DECISIVE_PROBABILITY:
+                return fair_value.probability
             
+            # Price too close to strike for the time left, uncertain
             return None
             
         except Exception as e:
@@ -0,0 +1,451 @@
+"""
+LLM backends for Polymarket Arbitrage Bot.
+
+The decision engine and the AI safety guard send chat prompts through an
+LLMBackendChain instead of a hard-wired NVIDIA client. A chain tries its
+backends in failover order; each backend has its own timeout and token
+prices, and the chain keeps per-backend call, failure, token and cost
+counts. Backends: any OpenAI-compatible server (NVIDIA, OpenAI, vLLM,
+llama.cpp's /v1 API), Ollama's native chat API and a deterministic mock
+for offline runs and tests.
+
+Validates Requirements:
+- Backend interface with OpenAI-compatible, local (llama.cpp/Ollama) and mock backends
+- Per-backend timeouts and token cost accounting
+- Failover across backends in configured order
+- JSON-mode / JSON-schema constrained requests (docs/STRUCTURED_OUTPUT.md)
+"""
+
+import asyncio
+import logging
+import os
+import time
+from abc import ABC, abstractmethod
+from dataclasses import dataclass
+from decimal import Decimal
+from typing import Any, Callable, Dict, List, Optional, Tuple, Union
+
+import aiohttp
+
+logger = logging.getLogger(__name__)
+
+NVIDIA_API_URL = "https://integrate.api.nvidia.com/v1"
+
+# Models the decision engine tried in order on the NVIDIA API
+NVIDIA_DECISION_MODELS = (
+    "meta/llama-3.1-70b-instruct",
+    "meta/llama-3.1-8b-instruct",
+    "mistralai/mixtral-8x7b-instruct-v0.1",
+)
+NVIDIA_SAFETY_MODEL = "deepseek-ai/deepseek-v3.2"
+
+# How a backend constrains answers to a response schema: json_schema (the schema
+# itself; grammar-constrained on llama.cpp and Ollama), json_object (any JSON) or none
+STRUCTURED_OUTPUT_MODES = ("json_schema", "json_object", "none")
+
+# Default response of the mock backend: a decision that never trades
+MOCK_SKIP_RESPONSE = (
+    '{"action": "skip", "confidence": 0, "position_size_pct": 0, "order_type": "market", '
+    '"limit_price": null, "reasoning": "mock backend", "risk_assessment": "high", "expected_profit_pct": 0}'
+)
+
+
+class LLMBackendError(Exception):
+    """Raised when no backend of a chain returned a completion."""
+
+
+@dataclass
+class LLMResponse:
+    """One completion and what it cost."""
+    text: str
+    backend: str
+    model: str
+    prompt_tokens: int
+    completion_tokens: int
+    cost_usd: Decimal
+    latency_ms: float
+
+
+def estimate_tokens(text: str) -> int:
+    """Rough token count (4 characters per token) for servers that report no usage."""
+    return max(1, len(text) // 4) if text else 0
+
+
+# ============================================================
+# BACKENDS
+# ============================================================
+
+class LLMBackend(ABC):
+    """
+    One model behind one endpoint.
+
+    Features:
+    - complete() enforces timeout_seconds and prices the tokens used
+    - Constrains answers to a response schema (structured_output mode)
+    - Counts calls, failures, timeouts, tokens and cost (get_statistics)
+    """
+
+    def __init__(
+        self,
+        name: str,
+        model: str,
+        timeout_seconds: float = 10.0,
+        max_tokens: int = 500,
+        temperature: float = 0.3,
+        input_cost_per_1k_tokens: Decimal = Decimal("0"),
+        output_cost_per_1k_tokens: Decimal = Decimal("0"),
+        structured_output: str = "json_schema",
+    ):
+        if structured_output not in STRUCTURED_OUTPUT_MODES:
+            raise ValueError(f"structured_output must be one of {STRUCTURED_OUTPUT_MODES}, got: {structured_output}")
+        self.name = name
+        self.model = model
+        self.timeout_seconds = timeout_seconds
+        self.max_tokens = max_tokens
+        self.temperature = temperature
+        self.input_cost_per_1k_tokens = Decimal(str(input_cost_per_1k_tokens))
+        self.output_cost_per_1k_tokens = Decimal(str(output_cost_per_1k_tokens))
+        self.structured_output = structured_output
+
+        self.calls = 0
+        self.failures = 0
+        self.timeouts = 0
+        self.prompt_tokens = 0
+        self.completion_tokens = 0
+        self.cost_usd = Decimal("0")
+        self.total_latency_ms = 0.0
+
+    @abstractmethod
+    async def _complete(
+        self, messages: List[Dict[str, str]], max_tokens: int, temperature: float, top_p: Optional[float],
+        response_schema: Optional[Dict[str, Any]]
+    ) -> Tuple[str, Optional[int], Optional[int]]:
+        """Send the messages; returns (text, prompt_tokens, completion_tokens), token counts None if unknown."""
+
+    def cost(self, prompt_tokens: int, completion_tokens: int) -> Decimal:
+        return (
+            self.input_cost_per_1k_tokens * prompt_tokens + self.output_cost_per_1k_tokens * completion_tokens
+        ) / 1000
+
+    async def complete(
+        self,
+        system_prompt: str,
+        user_prompt: str,
+        max_tokens: Optional[int] = None,
+        temperature: Optional[float] = None,
+        top_p: Optional[float] = None,
+        response_schema: Optional[Dict[str, Any]] = None,
+    ) -> LLMResponse:
+        """
+        Run one chat completion within timeout_seconds.
+        
+        With response_schema, the backend is asked for JSON matching it (see
+        structured_output); callers still validate the answer.
+
+        Raises:
+            asyncio.TimeoutError: If the backend did not answer in time
+            Exception: Whatever the backend raised (HTTP errors, bad responses)
+        """
+        messages = [{"role": "system", "content": system_prompt}, {"role": "user", "content": user_prompt}]
+        self.calls += 1
+        start = time.perf_counter()
+        try:
+            text, prompt_tokens, completion_tokens = await asyncio.wait_for(
+                self._complete(
+                    messages,
+                    max_tokens if max_tokens is not None else self.max_tokens,
+                    temperature if temperature is not None else self.temperature,
+                    top_p,
+                    response_schema if self.structured_output != "none" else None
+                ),
+                timeout=self.timeout_seconds
+            )
+        except asyncio.TimeoutError:
+            self.failures += 1
+            self.timeouts += 1
+            raise
+        except Exception:
+            self.failures += 1
+            raise
+
+        latency_ms = (time.perf_counter() - start) * 1000
+        if prompt_tokens is None:
+            prompt_tokens = estimate_tokens(system_prompt) + estimate_tokens(user_prompt)
+        if completion_tokens is None:
+            completion_tokens = estimate_tokens(text)
+        cost = self.cost(prompt_tokens, completion_tokens)
+
+        self.prompt_tokens += prompt_tokens
+        self.completion_tokens += completion_tokens
+        self.cost_usd += cost
+        self.total_latency_ms += latency_ms
+        return LLMResponse(text, self.name, self.model, prompt_tokens, completion_tokens, cost, latency_ms)
+
+    def get_statistics(self) -> Dict[str, Any]:
+        successes = self.calls - self.failures
+        return {
+            "type": type(self).__name__,
+            "model": self.model,
+            "calls": self.calls,
+            "failures": self.failures,
+            "timeouts": self.timeouts,
+            "prompt_tokens": self.prompt_tokens,
+            "completion_tokens": self.completion_tokens,
+            "cost_usd": self.cost_usd,
+            "avg_latency_ms": self.total_latency_ms / successes if successes else 0.0,
+        }
+
+    async def _post_json(self, url: str, payload: Dict[str, Any], headers: Optional[Dict[str, str]] = None) -> Any:
+        async with aiohttp.ClientSession() as session:
+            async with session.post(url, json=payload, headers=headers or {},
+                                    timeout=aiohttp.ClientTimeout(total=self.timeout_seconds)) as resp:
+                if resp.status >= 300:
+                    body = await resp.text()
+                    raise LLMBackendError(f"{self.name} returned HTTP {resp.status}: {body[:200]}")
+                return await resp.json()
+
+
+class OpenAICompatibleBackend(LLMBackend):
+    """
+    POSTs to {base_url}/chat/completions (NVIDIA, OpenAI, vLLM, llama.cpp server).
+
+    Without an API key no Authorization header is sent, as local servers expect.
+    extra_body is merged into every request (e.g. NVIDIA's chat_template_kwargs).
+    """
+
+    def __init__(self, name: str, model: str, base_url: str, api_key: Optional[str] = None,
+                 headers: Optional[Dict[str, str]] = None, extra_body: Optional[Dict[str, Any]] = None, **kwargs):
+        super().__init__(name, model, **kwargs)
+        self.base_url = base_url.rstrip("/")
+        self.api_key = api_key
+        self.headers = dict(headers or {})
+        self.extra_body = dict(extra_body or {})
+
+    async def _complete(self, messages, max_tokens, temperature, top_p, response_schema):
+        payload = {"model": self.model, "messages": messages, "max_tokens": max_tokens, "temperature": temperature}
+        if top_p is not None:
+            payload["top_p"] = top_p
+        if response_schema is not None and self.structured_output == "json_schema":
+            payload["response_format"] = {
+                "type": "json_schema",
+                "json_schema": {"name": response_schema.get("title", "response"), "schema": response_schema,
+                                "strict": True},
+            }
+        elif response_schema is not None:
+            payload["response_format"] = {"type": "json_object"}
+        payload.update(self.extra_body)
+
+        headers = dict(self.headers)
+        if self.api_key:
+            headers["Authorization"] = f"Bearer {self.api_key}"
+
+        data = await self._post_json(f"{self.base_url}/chat/completions", payload, headers)
+        choices = data.get("choices") or []
+        if not choices:
+            raise LLMBackendError(f"{self.name} returned no choices")
+        usage = data.get("usage") or {}
+        return choices[0]["message"].get("content") or "", usage.get("prompt_tokens"), usage.get("completion_tokens")
+
+
+class OllamaBackend(LLMBackend):
+    """POSTs to Ollama's native {base_url}/api/chat (non-streaming)."""
+
+    def __init__(self, name: str, model: str, base_url: str = "http://127.0.0.1:11434", **kwargs):
+        super().__init__(name, model, **kwargs)
+        self.base_url = base_url.rstrip("/")
+
+    async def _complete(self, messages, max_tokens, temperature, top_p, response_schema):
+        options = {"num_predict": max_tokens, "temperature": temperature}
+        if top_p is not None:
+            options["top_p"] = top_p
+        payload = {"model": self.model, "messages": messages, "stream": False, "options": options}
+        if response_schema is not None:
+            payload["format"] = response_schema if self.structured_output == "json_schema" else "json"
+        data = await self._post_json(f"{self.base_url}/api/chat", payload)
+        return data["message"]["content"], data.get("prompt_eval_count"), data.get("eval_count")
+
+
+class MockBackend(LLMBackend):
+    """
+    Deterministic backend for offline runs and tests.
+
+    `response` is a fixed text or a function of (system_prompt, user_prompt);
+    a list is answered in turn, repeating the last entry.
+    `error` makes every call fail; `delay_seconds` simulates a slow server.
+    """
+
+    def __init__(self, name: str = "mock", model: str = "mock",
+                 response: Union[str, List[str], Callable[[str, str], str]] = MOCK_SKIP_RESPONSE,
+                 delay_seconds: float = 0.0, error: Optional[str] = None, **kwargs):
+        super().__init__(name, model, **kwargs)
+        self.response = response
+        self.delay_seconds = delay_seconds
+        self.error = error
+        self.prompts: List[Tuple[str, str]] = []
+        self.schemas: List[Optional[Dict[str, Any]]] = []
+
+    async def _complete(self, messages, max_tokens, temperature, top_p, response_schema):
+        system_prompt, user_prompt = messages[0]["content"], messages[1]["content"]
+        self.prompts.append((system_prompt, user_prompt))
+        self.schemas.append(response_schema)
+        if self.delay_seconds:
+            await asyncio.sleep(self.delay_seconds)
+        if self.error:
+            raise LLMBackendError(self.error)
+        if callable(self.response):
+            return self.response(system_prompt, user_prompt), None, None
+        if isinstance(self.response, list):
+            return self.response[min(len(self.prompts), len(self.response)) - 1], None, None
+        return self.response, None, None
+
+
+# ============================================================
+# FAILOVER CHAIN
+# ============================================================
+
+class LLMBackendChain:
+    """
+    Tries backends in order until one answers.
+
+    Features:
+    - A failed or timed-out backend is logged and the next one is tried
+    - Raises LLMBackendError only when every backend failed (or there are none)
+    - Aggregated cost accounting across backends
+    """
+
+    def __init__(self, backends: Optional[List[LLMBackend]] = None):
+        self.backends = list(backends or [])
+
+    def __bool__(self) -> bool:
+        return bool(self.backends)
+
+    @property
+    def names(self) -> List[str]:
+        return [backend.name for backend in self.backends]
+
+    @property
+    def total_cost_usd(self) -> Decimal:
+        return sum((backend.cost_usd for backend in self.backends), Decimal("0"))
+
+    async def complete(
+        self,
+        system_prompt: str,
+        user_prompt: str,
+        max_tokens: Optional[int] = None,
+        temperature: Optional[float] = None,
+        top_p: Optional[float] = None,
+        response_schema: Optional[Dict[str, Any]] = None,
+    ) -> LLMResponse:
+        """Completion from the first backend that answers."""
+        if not self.backends:
+            raise LLMBackendError("No LLM backends configured")
+
+        last_error: Optional[Exception] = None
+        for backend in self.backends:
+            try:
+                response = await backend.complete(
+                    system_prompt, user_prompt, max_tokens, temperature, top_p, response_schema
+                )
+            except asyncio.TimeoutError:
+                last_error = LLMBackendError(f"{backend.name} timed out after {backend.timeout_seconds}s")
+                logger.warning(f"LLM backend {backend.name} timed out after {backend.timeout_seconds}s, trying next...")
+                continue
+            except Exception as e:
+                last_error = e
+                logger.warning(f"LLM backend {backend.name} ({backend.model}) failed: {e}, trying next...")
+                continue
+
+            logger.debug(
+                f"LLM backend {backend.name} answered in {response.latency_ms:.0f}ms "
+                f"({response.prompt_tokens}+{response.completion_tokens} tokens, ${response.cost_usd:.6f})"
+            )
+            return response
+
+        raise LLMBackendError(f"All LLM backends failed ({', '.join(self.names)}). Last error: {last_error}")
+
+    def get_statistics(self) -> Dict[str, Any]:
+        return {
+            "order": self.names,
+            "total_cost_usd": self.total_cost_usd,
+            "backends": {backend.name: backend.get_statistics() for backend in self.backends},
+        }
+
+
+def nvidia_backend_chain(
+    api_key: Optional[str],
+    base_url: str = NVIDIA_API_URL,
+    models: Tuple[str, ...] = NVIDIA_DECISION_MODELS,
+    **kwargs
+) -> LLMBackendChain:
+    """
+    The NVIDIA API with the given models in failover order (the behaviour before
+    llm_backends existed). Empty without an API key.
+    """
+    if not api_key:
+        return LLMBackendChain()
+    return LLMBackendChain([
+        OpenAICompatibleBackend(f"nvidia:{model}", model, base_url, api_key=api_key, **kwargs)
+        for model in models
+    ])
+
+
+def create_llm_backend(name: str, options: Dict[str, Any]) -> LLMBackend:
+    """
+    Build a backend from an llm_backends entry; API keys come from environment variables.
+
+    Raises:
+        ValueError: If the API key's environment variable is not set
+    """
+    common = {
+        "timeout_seconds": float(options.get("timeout_seconds", 10)),
+        "max_tokens": int(options.get("max_tokens", 500)),
+        "temperature": float(options.get("temperature", 0.3)),
+        "input_cost_per_1k_tokens": Decimal(str(options.get("input_cost_per_1k_tokens", 0))),
+        "output_cost_per_1k_tokens": Decimal(str(options.get("output_cost_per_1k_tokens", 0))),
+        "structured_output": options.get("structured_output", "json_schema"),
+    }
+    kind = options["type"]
+    if kind == "openai":
+        api_key = None
+        if options.get("api_key_env"):
+            api_key = os.getenv(options["api_key_env"])
+            if not api_key:
+                raise ValueError(f"LLM backend {name}: environment variable {options['api_key_env']} is not set")
+        return OpenAICompatibleBackend(
+            name, options["model"], options["base_url"], api_key=api_key,
+            headers=options.get("headers"), extra_body=options.get("extra_body"), **common
+        )
+    if kind == "llamacpp":
+        return OpenAICompatibleBackend(
+            name, options.get("model", "local"), options.get("base_url", "http://127.0.0.1:8080/v1"), **common
+        )
+    if kind == "ollama":
+        return OllamaBackend(name, options["model"], options.get("base_url", "http://127.0.0.1:11434"), **common)
+    if kind == "mock":
+        return MockBackend(
+            name, options.get("model", "mock"), response=options.get("response", MOCK_SKIP_RESPONSE),
+            delay_seconds=float(options.get("delay_seconds", 0)), error=options.get("error"), **common
+        )
+    raise ValueError(f"LLM backend {name}: unknown type {kind}")
+
+
+def create_llm_backend_chain(config) -> Optional[LLMBackendChain]:
+    """
+    Build the chain from Config.llm_backends in llm_backend_order (default: file order).
+
+    Returns None if no backends are configured, so callers keep their NVIDIA
+    defaults. A backend that cannot be built (missing API key) is skipped with
+    an error.
+    """
+    if not config.llm_backends:
+        return None
+
+    backends = []
+    for name in config.llm_backend_order or list(config.llm_backends):
+        try:
+            backends.append(create_llm_backend(name, config.llm_backends[name]))
+        except Exception as e:
+            logger.error(f"❌ LLM backend {name} disabled: {e}")
+    if backends:
+        logger.info(f"✅ LLM backends (failover order): {', '.join(backend.name for backend in backends)}")
+    return LLMBackendChain(backends)
@@ -0,0 +1,449 @@
+"""
+LLM Decision Audit Trail for Polymarket Arbitrage Bot.
+
+SQLite log of every decision the LLM decision engine makes: the market and
+portfolio context the prompt was built from, the system and user prompts,
+each raw model answer (including rejected ones), the parsed TradeDecision and
+the backend that answered. Trade outcomes are logged per market so decisions
+can be scored against what actually happened; `python -m src.llm_replay`
+replays a stored corpus against a new prompt or model and compares the scores.
+
+Validates Requirements:
+- Queryable log of prompt inputs, raw responses and parsed decisions
+- Realized trade outcomes linked to decisions by market
+- Outcome scoring of decisions for offline evaluation
+"""
+
+import json
+import logging
+import sqlite3
+import time
+import uuid
+from contextlib import contextmanager
+from dataclasses import asdict, dataclass
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional
+
+from src.llm_decision_engine_v2 import MarketContext, PortfolioState, TradeDecision
+
+logger = logging.getLogger(__name__)
+
+MARKET_DECIMAL_FIELDS = (
+    "yes_price", "no_price", "yes_liquidity", "no_liquidity", "volume_24h", "spread",
+    "volatility_1h", "binance_price", "volatility_5min", "price_velocity",
+)
+PORTFOLIO_DECIMAL_FIELDS = ("available_balance", "total_balance", "daily_pnl", "max_position_size")
+
+# Outcome sides: the strategy's UP/DOWN and the decision's YES/NO name the same tokens
+SIDES = {"yes": "yes", "up": "yes", "no": "no", "down": "no"}
+BUY_SIDES = {"buy_yes": "yes", "buy_no": "no"}
+
+
+def _json_default(value: Any) -> Any:
+    if isinstance(value, Decimal):
+        return str(value)
+    if hasattr(value, "value"):  # Enums
+        return value.value
+    if hasattr(value, "isoformat"):
+        return value.isoformat()
+    raise TypeError(f"Cannot store {type(value).__name__} in the decision audit log")
+
+
+def _decimal(value: Any) -> Optional[Decimal]:
+    return None if value is None else Decimal(str(value))
+
+
+def market_context_from_dict(data: Dict[str, Any]) -> MarketContext:
+    """Rebuild a stored MarketContext (Decimals restored, so prompts rebuild identically)."""
+    values = dict(data)
+    for name in MARKET_DECIMAL_FIELDS:
+        values[name] = _decimal(values.get(name))
+    if values.get("recent_price_changes") is not None:
+        values["recent_price_changes"] = [_decimal(change) for change in values["recent_price_changes"]]
+    if values.get("price_history_5min") is not None:
+        values["price_history_5min"] = [
+            (minutes_ago, _decimal(price)) for minutes_ago, price in values["price_history_5min"]
+        ]
+    return MarketContext(**values)
+
+
+def portfolio_state_from_dict(data: Dict[str, Any]) -> PortfolioState:
+    """Rebuild a stored PortfolioState."""
+    values = dict(data)
+    for name in PORTFOLIO_DECIMAL_FIELDS:
+        values[name] = _decimal(values[name])
+    return PortfolioState(**values)
+
+
+@dataclass
+class AuditedDecision:
+    """One logged decision with the inputs it was made from."""
+    decision_id: str
+    created_at: float
+    market_id: str
+    asset: str
+    opportunity_type: str
+    system_prompt: str
+    user_prompt: str
+    market_context: Dict[str, Any]
+    portfolio_state: Dict[str, Any]
+    raw_responses: List[str]  # Every answer, rejected ones first; empty if no backend answered
+    decision: Dict[str, Any]  # TradeDecision fields
+    backend: Optional[str] = None
+    model: Optional[str] = None
+    fallback_reason: Optional[str] = None  # Set when the decision is the engine's fallback
+    latency_ms: Optional[float] = None
+    cost_usd: float = 0.0
+
+    @property
+    def action(self) -> str:
+        return self.decision["action"]
+
+    @property
+    def confidence(self) -> float:
+        return float(self.decision["confidence"])
+
+    def to_market_context(self) -> MarketContext:
+        return market_context_from_dict(self.market_context)
+
+    def to_portfolio_state(self) -> PortfolioState:
+        return portfolio_state_from_dict(self.portfolio_state)
+
+
+@dataclass
+class TradeOutcome:
+    """A realized result on a market: a closed trade and/or the market's resolution."""
+    market_id: str
+    recorded_at: float
+    side: Optional[str] = None  # "yes" or "no": the token that was traded
+    profit_pct: Optional[float] = None  # Realized return of the trade (0.05 = +5%)
+    exit_reason: Optional[str] = None
+    resolved_side: Optional[str] = None  # "yes" or "no": the outcome the market resolved to
+
+
+def decision_return(
+    action: str,
+    market_context: Dict[str, Any],
+    outcomes: Iterable[TradeOutcome]
+) -> Optional[float]:
+    """
+    Return per unit staked that `action` would have realized, given the
+    market's outcomes (None if the outcomes do not tell).
+
+    - buy_yes / buy_no: with a resolution, (1 - price) / price on a win and -1
+      on a loss; otherwise the mean realized profit of trades on that side
+    - buy_both: with a resolution, (1 - YES - NO) / (YES + NO)
+    - skip, hold and sells: 0 once the market has any outcome
+    """
+    outcomes = list(outcomes)
+    if not outcomes:
+        return None
+    resolved = next((o.resolved_side for o in outcomes if o.resolved_side), None)
+
+    if action in BUY_SIDES:
+        side = BUY_SIDES[action]
+        if resolved:
+            price = float(market_context[f"{side}_price"])
+            return (1 - price) / price if resolved == side else -1.0
+        realized = [o.profit_pct for o in outcomes if o.side == side and o.profit_pct is not None]
+        return sum(realized) / len(realized) if realized else None
+
+    if action == "buy_both":
+        if not resolved:
+            return None
+        cost = float(market_context["yes_price"]) + float(market_context["no_price"])
+        return (1 - cost) / cost
+
+    return 0.0
+
+
+def score_decisions(
+    decisions: List[Dict[str, Any]],
+    outcomes_by_market: Dict[str, List[TradeOutcome]]
+) -> Dict[str, Any]:
+    """
+    Score decisions against the outcomes recorded after each of them.
+
+    Args:
+        decisions: Dicts with created_at, market_id, market_context, action,
+            confidence and (optional) fallback_reason
+        outcomes_by_market: Outcomes per market id
+
+    Returns:
+        Summary: counts, scored trades, win rate and returns per unit staked
+    """
+    returns = []
+    trade_returns = []
+    for decision in decisions:
+        outcomes = [
+            o for o in outcomes_by_market.get(decision["market_id"], [])
+            if o.recorded_at >= decision["created_at"]
+        ]
+        value = decision_return(decision["action"], decision["market_context"], outcomes)
+        decision["return"] = value
+        if value is None:
+            continue
+        returns.append(value)
+        if decision["action"] in BUY_SIDES or decision["action"] == "buy_both":
+            trade_returns.append(value)
+
+    total = len(decisions)
+    wins = sum(1 for value in trade_returns if value > 0)
+    return {
+        "decisions": total,
+        "scored": len(returns),
+        "fallbacks": sum(1 for d in decisions if d.get("fallback_reason")),
+        "avg_confidence": sum(d["confidence"] for d in decisions) / total if total else 0.0,
+        "actions": {
+            action: sum(1 for d in decisions if d["action"] == action)
+            for action in sorted({d["action"] for d in decisions})
+        },
+        "scored_trades": len(trade_returns),
+        "wins": wins,
+        "win_rate": wins / len(trade_returns) if trade_returns else 0.0,
+        "total_return": sum(returns),
+        "avg_trade_return": sum(trade_returns) / len(trade_returns) if trade_returns else 0.0,
+    }
+
+
+class DecisionAuditLog:
+    """
+    SQLite audit trail of LLM trading decisions and trade outcomes.
+
+    Features:
+    - Decision per make_decision call (prompts, raw answers, parsed decision)
+    - Filters by market, asset, opportunity type and time range
+    - Outcomes per market for scoring and offline replay
+    """
+
+    def __init__(self, db_path: str = "data/llm_decisions.db"):
+        """
+        Initialize the audit log.
+
+        Args:
+            db_path: Path to the SQLite database file
+        """
+        self.db_path = db_path
+        Path(db_path).parent.mkdir(parents=True, exist_ok=True)
+        self._init_schema()
+        logger.info(f"🧾 LLM decision audit log initialized: {db_path}")
+
+    @contextmanager
+    def _get_connection(self):
+        """Context manager for database connections."""
+        conn = sqlite3.connect(self.db_path)
+        conn.row_factory = sqlite3.Row
+        try:
+            yield conn
+            conn.commit()
+        except Exception:
+            conn.rollback()
+            raise
+        finally:
+            conn.close()
+
+    def _init_schema(self) -> None:
+        """Create the decision and outcome tables."""
+        with self._get_connection() as conn:
+            conn.execute("""
+                CREATE TABLE IF NOT EXISTS llm_decisions (
+                    decision_id TEXT PRIMARY KEY,
+                    created_at REAL NOT NULL,
+                    market_id TEXT NOT NULL,
+                    asset TEXT NOT NULL,
+                    opportunity_type TEXT NOT NULL,
+                    system_prompt TEXT NOT NULL,
+                    user_prompt TEXT NOT NULL,
+                    market_context TEXT NOT NULL,
+                    portfolio_state TEXT NOT NULL,
+                    raw_responses TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    confidence REAL NOT NULL,
+                    decision TEXT NOT NULL,
+                    backend TEXT,
+                    model TEXT,
+                    fallback_reason TEXT,
+                    latency_ms REAL,
+                    cost_usd REAL NOT NULL
+                )
+            """)
+            conn.execute("""
+                CREATE TABLE IF NOT EXISTS llm_trade_outcomes (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    market_id TEXT NOT NULL,
+                    recorded_at REAL NOT NULL,
+                    side TEXT,
+                    profit_pct REAL,
+                    exit_reason TEXT,
+                    resolved_side TEXT
+                )
+            """)
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_llm_decisions_market ON llm_decisions(market_id)")
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_llm_decisions_created ON llm_decisions(created_at)")
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_llm_outcomes_market ON llm_trade_outcomes(market_id)")
+
+    # ============================================================
+    # RECORDING
+    # ============================================================
+
+    def record_decision(
+        self,
+        market_context: MarketContext,
+        portfolio_state: PortfolioState,
+        opportunity_type: str,
+        system_prompt: str,
+        user_prompt: str,
+        raw_responses: List[str],
+        decision: TradeDecision,
+        backend: Optional[str] = None,
+        model: Optional[str] = None,
+        fallback_reason: Optional[str] = None,
+        latency_ms: Optional[float] = None,
+        cost_usd: float = 0.0
+    ) -> str:
+        """
+        Log a decision with everything it was made from.
+
+        Returns:
+            str: Decision id
+        """
+        decision_id = uuid.uuid4().hex
+        with self._get_connection() as conn:
+            conn.execute("""
+                INSERT INTO llm_decisions (
+                    decision_id, created_at, market_id, asset, opportunity_type, system_prompt,
+                    user_prompt, market_context, portfolio_state, raw_responses, action, confidence,
+                    decision, backend, model, fallback_reason, latency_ms, cost_usd
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            """, (
+                decision_id,
+                time.time(),
+                market_context.market_id,
+                market_context.asset,
+                opportunity_type,
+                system_prompt,
+                user_prompt,
+                json.dumps(asdict(market_context), default=_json_default),
+                json.dumps(asdict(portfolio_state), default=_json_default),
+                json.dumps(raw_responses),
+                decision.action.value,
+                float(decision.confidence),
+                json.dumps(asdict(decision), default=_json_default),
+                backend,
+                model,
+                fallback_reason,
+                latency_ms,
+                cost_usd,
+            ))
+        return decision_id
+
+    def record_outcome(
+        self,
+        market_id: str,
+        side: Optional[str] = None,
+        profit_pct: Optional[float] = None,
+        exit_reason: Optional[str] = None,
+        resolved_side: Optional[str] = None
+    ) -> None:
+        """
+        Log a realized result on a market.
+
+        Args:
+            market_id: Market the decisions were about
+            side: Token traded ("UP"/"YES" or "DOWN"/"NO")
+            profit_pct: Realized return of the trade (0.05 = +5%)
+            exit_reason: Why the position was closed
+            resolved_side: Outcome the market resolved to, when known
+        """
+        with self._get_connection() as conn:
+            conn.execute("""
+                INSERT INTO llm_trade_outcomes (market_id, recorded_at, side, profit_pct, exit_reason, resolved_side)
+                VALUES (?, ?, ?, ?, ?, ?)
+            """, (
+                market_id,
+                time.time(),
+                SIDES.get(side.lower()) if side else None,
+                float(profit_pct) if profit_pct is not None else None,
+                exit_reason,
+                SIDES.get(resolved_side.lower()) if resolved_side else None,
+            ))
+
+    # ============================================================
+    # QUERIES
+    # ============================================================
+
+    def get_decisions(
+        self,
+        market_id: Optional[str] = None,
+        asset: Optional[str] = None,
+        opportunity_type: Optional[str] = None,
+        since: Optional[float] = None,
+        until: Optional[float] = None,
+        limit: Optional[int] = None
+    ) -> List[AuditedDecision]:
+        """Logged decisions, oldest first, optionally filtered (times are Unix timestamps)."""
+        query = "SELECT * FROM llm_decisions WHERE 1=1"
+        params: List[Any] = []
+        for column, value in (("market_id", market_id), ("asset", asset), ("opportunity_type", opportunity_type)):
+            if value:
+                query += f" AND {column} = ?"
+                params.append(value)
+        if since is not None:
+            query += " AND created_at >= ?"
+            params.append(since)
+        if until is not None:
+            query += " AND created_at < ?"
+            params.append(until)
+        query += " ORDER BY created_at"
+        if limit:
+            query += " LIMIT ?"
+            params.append(limit)
+        with self._get_connection() as conn:
+            return [self._row_to_decision(row) for row in conn.execute(query, params).fetchall()]
+
+    def get_outcomes(self, market_ids: Optional[Iterable[str]] = None) -> Dict[str, List[TradeOutcome]]:
+        """Outcomes per market, oldest first (all markets if `market_ids` is None)."""
+        query = "SELECT * FROM llm_trade_outcomes"
+        params: List[Any] = []
+        if market_ids is not None:
+            market_ids = list(market_ids)
+            if not market_ids:
+                return {}
+            query += f" WHERE market_id IN ({', '.join('?' * len(market_ids))})"
+            params = market_ids
+        query += " ORDER BY recorded_at, id"
+
+        outcomes: Dict[str, List[TradeOutcome]] = {}
+        with self._get_connection() as conn:
+            for row in conn.execute(query, params).fetchall():
+                outcomes.setdefault(row["market_id"], []).append(TradeOutcome(
+                    market_id=row["market_id"],
+                    recorded_at=row["recorded_at"],
+                    side=row["side"],
+                    profit_pct=row["profit_pct"],
+                    exit_reason=row["exit_reason"],
+                    resolved_side=row["resolved_side"],
+                ))
+        return outcomes
+
+    @staticmethod
+    def _row_to_decision(row: sqlite3.Row) -> AuditedDecision:
+        return AuditedDecision(
+            decision_id=row["decision_id"],
+            created_at=row["created_at"],
+            market_id=row["market_id"],
+            asset=row["asset"],
+            opportunity_type=row["opportunity_type"],
+            system_prompt=row["system_prompt"],
+            user_prompt=row["user_prompt"],
+            market_context=json.loads(row["market_context"]),
+            portfolio_state=json.loads(row["portfolio_state"]),
+            raw_responses=json.loads(row["raw_responses"]),
+            decision=json.loads(row["decision"]),
+            backend=row["backend"],
+            model=row["model"],
+            fallback_reason=row["fallback_reason"],
+            latency_ms=row["latency_ms"],
+            cost_usd=row["cost_usd"],
+        )
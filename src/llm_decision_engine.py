@@ -5,6 +5,7 @@ This module wraps LLMDecisionEngineV2 to provide the V1 API surface
 that existing tests expect. All functionality is delegated to V2.
 """
 
+import json
 from decimal import Decimal
 from typing import Optional, List, Dict, Any
 from dataclasses import dataclass
@@ -84,6 +85,7 @@ class LLMDecisionEngine(LLMDecisionEngineV2):
         min_confidence_threshold: float = 60.0,
         max_position_pct: float = 5.0,
         decision_timeout: float = 5.0,
+        backends=None,
     ):
         super().__init__(
             nvidia_api_key=nvidia_api_key,
@@ -91,6 +93,7 @@ class LLMDecisionEngine(LLMDecisionEngineV2):
             min_confidence_threshold=min_confidence_threshold,
             max_position_pct=max_position_pct,
             decision_timeout=decision_timeout,
+            backends=backends,
         )
 
     def _fallback_decision(self, market_context, portfolio_state, reason="fallback"):
@@ -125,64 +128,13 @@ class LLMDecisionEngine(LLMDecisionEngineV2):
             expected_profit=Decimal("0"),
         )
 
-    def _parse_llm_response(self, response_text, market_context, portfolio_state):
-        """V1-compatible parse that delegates to V2 for JSON extraction."""
-        try:
-            import json
-
-            json_text = response_text
-            if "```json" in response_text:
-                json_text = response_text.split("```json")[1].split("```")[0]
-            elif "```" in response_text:
-                json_text = response_text.split("```")[1].split("```")[0]
-
-            data = json.loads(json_text.strip())
-
-            action_map = {
-                "buy_both": TradeAction.BUY_BOTH,
-                "buy_yes": TradeAction.BUY_YES,
-                "buy_no": TradeAction.BUY_NO,
-                "sell_yes": TradeAction.SELL_YES,
-                "sell_no": TradeAction.SELL_NO,
-                "hold": TradeAction.HOLD,
-                "skip": TradeAction.SKIP,
-            }
-            action = action_map.get(data.get("action", "skip"), TradeAction.SKIP)
-
-            order_type_map = {
-                "market": OrderType.MARKET,
-                "limit": OrderType.LIMIT,
-                "fok": OrderType.FOK,
-            }
-            order_type = order_type_map.get(data.get("order_type", "market"), OrderType.MARKET)
-
-            position_size_pct = min(
-                float(data.get("position_size_pct", 2.0)),
-                self.max_position_pct,
-            )
-            position_size = portfolio_state.available_balance * Decimal(str(position_size_pct / 100))
-
-            return TradeDecision(
-                action=action,
-                confidence=float(data.get("confidence", 0)),
-                position_size=position_size,
-                order_type=order_type,
-                limit_price=Decimal(str(data.get("limit_price"))) if data.get("limit_price") else None,
-                reasoning=data.get("reasoning", "No reasoning provided"),
-                risk_assessment=data.get("risk_assessment", "unknown"),
-                expected_profit=Decimal(str(data.get("expected_profit_pct", 0))) / 100,
-            )
-
-        except Exception:
-            return self._fallback_decision(market_context, portfolio_state, "parse_error")
-
     def _build_decision_prompt(self, market_context, portfolio_state, opportunity_type="arbitrage"):
         """V1-compatible prompt builder."""
         prompt = f"TRADING OPPORTUNITY: {opportunity_type.upper()}\n\n"
         prompt += market_context.to_prompt_context() if hasattr(market_context, 'to_prompt_context') else str(market_context)
         prompt += f"\n\nPortfolio State:\n"
         prompt += portfolio_state.to_prompt_context() if hasattr(portfolio_state, 'to_prompt_context') else str(portfolio_state)
-        prompt += "\n\nAnalyze and provide JSON decision."
+        prompt += f"\n\nAnalyze and provide a JSON decision matching this schema:\n{json.dumps(self.decision_schema)}"
         return prompt
 
     def get_statistics(self):
@@ -33,7 +33,10 @@ from typing import Dict, List, Optional, Tuple, Any
 from dataclasses import dataclass, field
 from enum import Enum
 
-from openai import AsyncOpenAI
+from src.llm_backends import LLMBackendChain, NVIDIA_API_URL, nvidia_backend_chain
+from src.structured_output import (
+    RejectedResponseLog, SchemaValidationError, request_structured, validate_response,
+)
 
 logger = logging.getLogger(__name__)
 
@@ -56,6 +59,47 @@ class OrderType(Enum):
     FOK = "fok"  # Fill-or-Kill
 
 
+BUY_ACTIONS = ("buy_yes", "buy_no", "buy_both")
+
+
+def trade_decision_schema(max_position_pct: float = 100.0) -> Dict[str, Any]:
+    """
+    JSON schema of a TradeDecision answer (docs/STRUCTURED_OUTPUT.md).
+    
+    position_size_pct is capped at the engine's max_position_pct; larger
+    sizes are rejected rather than clamped.
+    """
+    return {
+        "title": "TradeDecision",
+        "type": "object",
+        "properties": {
+            "action": {"type": "string", "enum": [action.value for action in TradeAction]},
+            "confidence": {"type": "number", "minimum": 0, "maximum": 100},
+            "position_size_pct": {"type": ["number", "null"], "minimum": 0, "maximum": max_position_pct},
+            "order_type": {"type": "string", "enum": [order_type.value for order_type in OrderType]},
+            "limit_price": {"type": ["number", "null"], "minimum": 0.01, "maximum": 0.99},
+            "reasoning": {"type": "string", "minLength": 1, "maxLength": 1000},
+            "risk_assessment": {"type": "string", "enum": ["low", "medium", "high"]},
+            "expected_profit_pct": {"type": ["number", "null"], "minimum": -100, "maximum": 100},
+        },
+        "required": [
+            "action", "confidence", "position_size_pct", "order_type", "limit_price", "reasoning",
+            "risk_assessment", "expected_profit_pct",
+        ],
+        "additionalProperties": False,
+    }
+
+
+def trade_decision_errors(data: Dict[str, Any]) -> List[str]:
+    """Rules across TradeDecision fields that the schema cannot express."""
+    errors = []
+    if data["action"] in BUY_ACTIONS and not data["position_size_pct"]:
+        errors.append(f"$.position_size_pct: must be above 0 for action {data['action']}")
+    if data["order_type"] == "limit" and data.get("limit_price") is None:
+        errors.append("$.limit_price: is required for limit orders")
+    return errors
+
+
 @dataclass
 class MarketContext:
     """Market context for LLM decision making - optimized for 15-minute markets."""
@@ -326,23 +370,31 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
 
     def __init__(
         self,
-        nvidia_api_key: str,
-        nvidia_api_url: str = "https://integrate.api.nvidia.com/v1",
+        nvidia_api_key: Optional[str] = None,
+        nvidia_api_url: str = NVIDIA_API_URL,
         min_confidence_threshold: float = 60.0,
         max_position_pct: float = 5.0,
         decision_timeout: float = 5.0,
-        enable_chain_of_thought: bool = True
+        enable_chain_of_thought: bool = True,
+        backends: Optional[LLMBackendChain] = None,
+        max_repair_attempts: int = 2,
+        rejected_responses_path: Optional[str] = "data/llm_rejected_responses.jsonl",
+        audit_log: Optional[Any] = None
     ):
         """
         Initialize Perfect LLM Decision Engine.
         
         Args:
-            nvidia_api_key: API key for NVIDIA AI service
+            nvidia_api_key: API key for NVIDIA AI service (used when no backends are given)
             nvidia_api_url: NVIDIA API endpoint
             min_confidence_threshold: Minimum confidence to execute (default 60%)
             max_position_pct: Maximum position size as % of balance (default 5%)
-            decision_timeout: Timeout for LLM calls in seconds
+            decision_timeout: Timeout for a whole decision in seconds, across backend failovers
             enable_chain_of_thought: Enable detailed reasoning
+            backends: LLM backends in failover order (default: NVIDIA models)
+            max_repair_attempts: Re-asks after an answer that fails schema validation
+            rejected_responses_path: JSONL file for invalid answers (None: not persisted)
+            audit_log: DecisionAuditLog recording prompts, answers, decisions and outcomes (optional)
         """
         self.nvidia_api_key = nvidia_api_key
         self.nvidia_api_url = nvidia_api_url
@@ -351,11 +403,17 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
         self.decision_timeout = decision_timeout
         self.enable_chain_of_thought = enable_chain_of_thought
         
-        # Initialize OpenAI-compatible client for NVIDIA API
-        self.client = AsyncOpenAI(
-            base_url=nvidia_api_url,
-            api_key=nvidia_api_key
-        )
+        # LLM backends in failover order (docs/LLM_BACKENDS.md)
+        self.backends = backends if backends is not None else nvidia_backend_chain(nvidia_api_key, nvidia_api_url)
+        
+        # Answers must match the TradeDecision schema (docs/STRUCTURED_OUTPUT.md)
+        self.decision_schema = trade_decision_schema(max_position_pct)
+        self.max_repair_attempts = max_repair_attempts
+        self.rejected_responses = RejectedResponseLog(rejected_responses_path)
+        self.repaired_responses = 0
+        
+        # Prompt inputs, raw answers and decisions for replay (docs/LLM_DECISION_AUDIT.md)
+        self.audit_log = audit_log
         
         # Track decision history for adaptive learning
         self.decision_history: List[TradeDecision] = []
@@ -371,6 +429,7 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
         logger.info(f"Min Confidence: {min_confidence_threshold}%")
         logger.info(f"Max Position: {max_position_pct}%")
         logger.info(f"Chain-of-Thought: {enable_chain_of_thought}")
+        logger.info(f"LLM Backends: {', '.join(self.backends.names) or 'none (fallback decisions only)'}")
         logger.info(f"✨ OPTIMIZATION: Decision caching enabled (60s TTL)")
         logger.info("=" * 80)
     
@@ -439,6 +498,11 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
         if cached_decision:
             return cached_decision
         
+        system_prompt = ""
+        user_prompt = ""
+        calls: List[Any] = []  # LLMResponse per answer, rejected ones included
+        started = time.time()
+        
         try:
             # Select appropriate system prompt
             system_prompt = self._get_system_prompt(opportunity_type)
@@ -448,16 +512,26 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
                 market_context, portfolio_state, opportunity_type
             )
             
-            # Call LLM with timeout
-            response = await asyncio.wait_for(
-                self._call_llm(system_prompt, user_prompt),
+            # Call LLM with timeout; invalid answers are sent back for repair
+            rejected = self.rejected_responses.count
+            data = await asyncio.wait_for(
+                request_structured(
+                    lambda prompt: self._call_llm(system_prompt, prompt, calls),
+                    user_prompt,
+                    self.decision_schema,
+                    max_repair_attempts=self.max_repair_attempts,
+                    check=trade_decision_errors,
+                    on_invalid=lambda response, errors, attempt: self._record_rejected_response(
+                        response, errors, market_context, opportunity_type, attempt
+                    )
+                ),
                 timeout=self.decision_timeout
             )
+            if self.rejected_responses.count > rejected:
+                self.repaired_responses += 1
             
-            # Parse response into TradeDecision
-            decision = self._parse_llm_response(
-                response, market_context, portfolio_state
-            )
+            # Build TradeDecision from the validated answer
+            decision = self._decision_from_data(data, portfolio_state)
             
             # Adaptive confidence adjustment
             decision = self._adjust_confidence(decision, portfolio_state)
@@ -475,15 +549,70 @@ Always respond with valid JSON only. Be AGGRESSIVE!"""
                        f"Confidence: {decision.confidence:.1f}% | "
                        f"Size: ${decision.position_size:.2f} | "
                        f"Reasoning: {decision.reasoning[:100]}...")
-            
-            return decision
+            fallback_reason = None
             
         except asyncio.TimeoutError:
             logger.warning(f"LLM decision timeout after {self.decision_timeout}s")
-            return self._fallback_decision(market_context, portfolio_state, "timeout")
+            fallback_reason = "timeout"
+        except SchemaValidationError as e:
+            logger.error(f"LLM answers failed validation after {self.max_repair_attempts} repair attempts: {e}")
+            fallback_reason = "invalid_response"
         except Exception as e:
             logger.error(f"LLM decision error: {e}", exc_info=True)
-            return self._fallback_decision(market_context, portfolio_state, str(e))
+            fallback_reason = str(e)
+        
+        if fallback_reason is not None:
+            decision = self._fallback_decision(market_context, portfolio_state, fallback_reason)
+        
+        self._audit_decision(
+            market_context, portfolio_state, opportunity_type, system_prompt, user_prompt,
+            calls, decision, fallback_reason, (time.time() - started) * 1000
+        )
+        return decision
+    
+    def _audit_decision(
+        self,
+        market_context: MarketContext,
+        portfolio_state: PortfolioState,
+        opportunity_type: str,
+        system_prompt: str,
+        user_prompt: str,
+        calls: List[Any],
+        decision: TradeDecision,
+        fallback_reason: Optional[str],
+        latency_ms: float
+    ) -> None:
+        """Record a decision and what it was made from in the audit log."""
+        if self.audit_log is None:
+            return
+        try:
+            self.audit_log.record_decision(
+                market_context, portfolio_state, opportunity_type, system_prompt, user_prompt,
+                raw_responses=[call.text for call in calls],
+                decision=decision,
+                backend=calls[-1].backend if calls else None,
+                model=calls[-1].model if calls else None,
+                fallback_reason=fallback_reason,
+                latency_ms=latency_ms,
+                cost_usd=float(sum(call.cost_usd for call in calls))
+            )
+        except Exception as e:
+            logger.error(f"Failed to record LLM decision in the audit log: {e}")
+    
+    def record_outcome(
+        self,
+        market_id: str,
+        side: str,
+        profit_pct: Decimal,
+        exit_reason: str
+    ) -> None:
+        """Record a closed trade, so decisions on its market can be scored (no-op without an audit log)."""
+        if self.audit_log is None:
+            return
+        try:
+            self.audit_log.record_outcome(market_id, side=side, profit_pct=float(profit_pct), exit_reason=exit_reason)
+        except Exception as e:
+            logger.error(f"Failed to record trade outcome in the audit log: {e}")
     
     def _get_system_prompt(self, opportunity_type: str) -> str:
         """Get appropriate system prompt for opportunity type."""
@@ -576,44 +705,42 @@ DECISION CRITERIA FOR 15-MIN MARKETS:
 - Weak signal → SKIP (not worth the risk in short timeframe)
 """
         
-        prompt += "\nAnalyze this opportunity and provide your trading decision as JSON."
+        prompt += (
+            "\nAnalyze this opportunity and provide your trading decision as one JSON object "
+            f"matching this schema:\n{json.dumps(self.decision_schema)}"
+        )
         
         return prompt
     
-    async def _call_llm(self, system_prompt: str, user_prompt: str) -> str:
-        """Call LLM API with prompts."""
-        # Try multiple models in order of preference (2026 NVIDIA NIM available models)
-        models_to_try = [
-            "meta/llama-3.1-70b-instruct",  # Meta Llama 3.1 70B (WORKING)
-            "meta/llama-3.1-8b-instruct",  # Smaller fallback
-            "mistralai/mixtral-8x7b-instruct-v0.1",  # Mixtral fallback
-        ]
-        
-        last_error = None
-        for model in models_to_try:
-            try:
-                completion = await self.client.chat.completions.create(
-                    model=model,
-                    messages=[
-                        {"role": "system", "content": system_prompt},
-                        {"role": "user", "content": user_prompt}
-                    ],
-                    temperature=0.3,  # Lower for more consistent decisions
-                    max_tokens=500,
-                    top_p=0.9
-                )
-                
-                logger.info(f"✅ LLM call successful with model: {model}")
-                return completion.choices[0].message.content
-                
-            except Exception as e:
-                last_error = e
-                logger.warning(f"Model {model} failed: {e}, trying next...")
-                continue
-        
-        # All models failed
-        logger.error(f"All LLM models failed. Last error: {last_error}")
-        raise last_error
+    async def _call_llm(self, system_prompt: str, user_prompt: str, calls: Optional[List[Any]] = None) -> str:
+        """Call the LLM backends (in failover order) with prompts; the response is appended to `calls`."""
+        response = await self.backends.complete(
+            system_prompt,
+            user_prompt,
+            temperature=0.3,  # Lower for more consistent decisions
+            max_tokens=500,
+            top_p=0.9,
+            response_schema=self.decision_schema
+        )
+        logger.info(f"✅ LLM call successful with {response.backend} ({response.model})")
+        if calls is not None:
+            calls.append(response)
+        return response.text
+    
+    def _record_rejected_response(
+        self,
+        response_text: str,
+        errors: List[str],
+        market_context: MarketContext,
+        opportunity_type: str,
+        attempt: int = 0
+    ) -> None:
+        """Persist an answer that failed validation, for review."""
+        self.rejected_responses.record(
+            "decision_engine", response_text, errors,
+            market_id=market_context.market_id, asset=market_context.asset,
+            opportunity_type=opportunity_type, attempt=attempt
+        )
     
     def _parse_llm_response(
         self,
@@ -621,72 +748,30 @@ DECISION CRITERIA FOR 15-MIN MARKETS:
         market_context: MarketContext,
         portfolio_state: PortfolioState
     ) -> TradeDecision:
-        """Parse LLM JSON response into TradeDecision."""
+        """Validate an LLM answer against the TradeDecision schema (fallback decision if invalid)."""
         try:
-            # Extract JSON from response (handle markdown code blocks)
-            json_text = response_text
-            if "```json" in response_text:
-                json_text = response_text.split("```json")[1].split("```")[0]
-            elif "```" in response_text:
-                json_text = response_text.split("```")[1].split("```")[0]
-            
-            data = json.loads(json_text.strip())
-            
-            # Map action string to enum
-            action_map = {
-                "buy_both": TradeAction.BUY_BOTH,
-                "buy_yes": TradeAction.BUY_YES,
-                "buy_no": TradeAction.BUY_NO,
-                "sell_yes": TradeAction.SELL_YES,
-                "sell_no": TradeAction.SELL_NO,
-                "hold": TradeAction.HOLD,
-                "skip": TradeAction.SKIP
-            }
-            action = action_map.get(data.get("action", "skip"), TradeAction.SKIP)
-            
-            # Map order type
-            order_type_map = {
-                "market": OrderType.MARKET,
-                "limit": OrderType.LIMIT,
-                "fok": OrderType.FOK
-            }
-            order_type = order_type_map.get(data.get("order_type", "market"), OrderType.MARKET)
-            
-            # Calculate position size (handle null values when action is skip)
-            position_size_pct_raw = data.get("position_size_pct")
-            if position_size_pct_raw is None or position_size_pct_raw == "null":
-                position_size_pct = 2.0  # Default
-            else:
-                position_size_pct = min(
-                    float(position_size_pct_raw),
-                    self.max_position_pct
-                )
-            position_size = portfolio_state.available_balance * Decimal(str(position_size_pct / 100))
-            
-            # Build decision (handle null values)
-            expected_profit_raw = data.get("expected_profit_pct")
-            if expected_profit_raw is None or expected_profit_raw == "null":
-                expected_profit = Decimal("0")
-            else:
-                expected_profit = Decimal(str(expected_profit_raw)) / 100
-            
-            decision = TradeDecision(
-                action=action,
-                confidence=float(data.get("confidence", 0)),
-                position_size=position_size,
-                order_type=order_type,
-                limit_price=Decimal(str(data.get("limit_price"))) if data.get("limit_price") else None,
-                reasoning=data.get("reasoning", "No reasoning provided"),
-                risk_assessment=data.get("risk_assessment", "unknown"),
-                expected_profit=expected_profit
-            )
-            
-            return decision
-            
-        except Exception as e:
-            logger.error(f"Failed to parse LLM response: {e}")
-            logger.error(f"Response text: {response_text}")
-            return self._fallback_decision(market_context, portfolio_state, "parse_error")
+            data = validate_response(response_text, self.decision_schema, trade_decision_errors)
+        except SchemaValidationError as e:
+            logger.error(f"Rejected LLM response: {e}")
+            self._record_rejected_response(response_text, e.errors, market_context, "unknown")
+            return self._fallback_decision(market_context, portfolio_state, "invalid_response")
+        return self._decision_from_data(data, portfolio_state)
+    
+    def _decision_from_data(self, data: Dict[str, Any], portfolio_state: PortfolioState) -> TradeDecision:
+        """Build a TradeDecision from a schema-valid answer."""
+        position_size_pct = data["position_size_pct"] or 0
+        expected_profit_pct = data["expected_profit_pct"] or 0
+        
+        return TradeDecision(
+            action=TradeAction(data["action"]),
+            confidence=float(data["confidence"]),
+            position_size=portfolio_state.available_balance * Decimal(str(position_size_pct)) / 100,
+            order_type=OrderType(data["order_type"]),
+            limit_price=Decimal(str(data["limit_price"])) if data.get("limit_price") is not None else None,
+            reasoning=data["reasoning"],
+            risk_assessment=data["risk_assessment"],
+            expected_profit=Decimal(str(expected_profit_pct)) / 100
+        )
     
     def _adjust_confidence(
         self,
@@ -737,7 +822,12 @@ DECISION CRITERIA FOR 15-MIN MARKETS:
     def get_performance_stats(self) -> Dict[str, Any]:
         """Get performance statistics."""
         if not self.decision_history:
-            return {"total_decisions": 0}
+            return {
+                "total_decisions": 0,
+                "rejected_responses": self.rejected_responses.count,
+                "repaired_responses": self.repaired_responses,
+                "llm_backends": self.backends.get_statistics()
+            }
         
         total = len(self.decision_history)
         executed = sum(1 for d in self.decision_history if d.should_execute)
@@ -750,5 +840,8 @@ DECISION CRITERIA FOR 15-MIN MARKETS:
             "executed": executed,
             "skipped": skipped,
             "execution_rate": executed / total if total > 0 else 0,
-            "avg_confidence": avg_confidence
+            "avg_confidence": avg_confidence,
+            "rejected_responses": self.rejected_responses.count,
+            "repaired_responses": self.repaired_responses,
+            "llm_backends": self.backends.get_statistics()
         }
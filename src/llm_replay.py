@@ -0,0 +1,258 @@
+"""
+Offline replay of logged LLM decisions against a new prompt or model.
+
+Loads a corpus from the decision audit log (src/llm_decision_audit.py),
+asks a candidate engine for a decision on each stored context, and reports
+which decisions changed and how the logged and replayed decisions score
+against the realized trade outcomes.
+
+    python -m src.llm_replay --db data/llm_decisions.db --config config/config.yaml --backend local
+    python -m src.llm_replay --system-prompt prompts/directional_v2.txt --asset BTC --since 2026-10-01
+
+Validates Requirements:
+- Replay of a stored decision corpus against a new prompt or model
+- Decision diff between the logged and the replayed engine
+- Scoring of both against realized outcomes
+"""
+
+import argparse
+import asyncio
+import json
+import logging
+import sys
+from collections import Counter
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+import yaml
+
+from src.llm_backends import LLMBackendChain, create_llm_backend
+from src.llm_decision_audit import AuditedDecision, DecisionAuditLog, TradeOutcome, score_decisions
+from src.llm_decision_engine_v2 import LLMDecisionEngineV2
+
+logger = logging.getLogger(__name__)
+
+
+def load_backends(config_path: Optional[Path], names: List[str]) -> Optional[LLMBackendChain]:
+    """
+    Backends from the llm_backends section of a YAML config, in `names` order
+    (default: llm_backend_order, then file order). None without a config.
+
+    Raises:
+        ValueError: If a named backend is not configured or is invalid
+    """
+    if config_path is None:
+        if names:
+            raise ValueError("--backend needs --config with an llm_backends section")
+        return None
+    with open(config_path) as f:
+        data = yaml.safe_load(f) or {}
+    configured = data.get("llm_backends") or {}
+    order = names or data.get("llm_backend_order") or list(configured)
+    missing = [name for name in order if name not in configured]
+    if missing:
+        raise ValueError(f"Backends not in {config_path}: {', '.join(missing)}")
+    return LLMBackendChain([create_llm_backend(name, configured[name]) for name in order])
+
+
+class ReplayDecisionEngine(LLMDecisionEngineV2):
+    """
+    Decision engine that answers for stored contexts.
+
+    Features:
+    - Optional system prompt override (all opportunity types)
+    - User prompt rebuilt from the stored context, or replayed verbatim
+    - No decision cache, no audit log; the last call's details are kept in `last`
+    """
+
+    def __init__(self, system_prompt: Optional[str] = None, stored_prompts: bool = False, **kwargs):
+        kwargs.setdefault("rejected_responses_path", None)
+        super().__init__(**kwargs)
+        self.system_prompt_override = system_prompt
+        self.stored_prompts = stored_prompts
+        self._cache_ttl = 0.0  # Every stored context gets its own answer
+        self.entry: Optional[AuditedDecision] = None
+        self.last: Dict[str, Any] = {}
+
+    def _get_system_prompt(self, opportunity_type: str) -> str:
+        return self.system_prompt_override or super()._get_system_prompt(opportunity_type)
+
+    def _build_decision_prompt(self, market_context, portfolio_state, opportunity_type) -> str:
+        if self.stored_prompts and self.entry is not None:
+            return self.entry.user_prompt
+        return super()._build_decision_prompt(market_context, portfolio_state, opportunity_type)
+
+    def _audit_decision(
+        self, market_context, portfolio_state, opportunity_type, system_prompt, user_prompt,
+        calls, decision, fallback_reason, latency_ms
+    ) -> None:
+        self.last = {
+            "user_prompt": user_prompt,
+            "raw_responses": [call.text for call in calls],
+            "backend": calls[-1].backend if calls else None,
+            "model": calls[-1].model if calls else None,
+            "fallback_reason": fallback_reason,
+            "latency_ms": latency_ms,
+            "cost_usd": float(sum(call.cost_usd for call in calls)),
+        }
+
+    async def decide(self, entry: AuditedDecision) -> Dict[str, Any]:
+        """Replayed decision for a stored entry, in the shape score_decisions expects."""
+        self.entry = entry
+        decision = await self.make_decision(
+            entry.to_market_context(), entry.to_portfolio_state(), entry.opportunity_type
+        )
+        return {
+            "decision_id": entry.decision_id,
+            "created_at": entry.created_at,
+            "market_id": entry.market_id,
+            "market_context": entry.market_context,
+            "action": decision.action.value,
+            "confidence": float(decision.confidence),
+            "reasoning": decision.reasoning,
+            **self.last,
+        }
+
+
+def _logged(entry: AuditedDecision) -> Dict[str, Any]:
+    return {
+        "decision_id": entry.decision_id,
+        "created_at": entry.created_at,
+        "market_id": entry.market_id,
+        "market_context": entry.market_context,
+        "action": entry.action,
+        "confidence": entry.confidence,
+        "reasoning": entry.decision.get("reasoning", ""),
+        "backend": entry.backend,
+        "model": entry.model,
+        "fallback_reason": entry.fallback_reason,
+    }
+
+
+def compare(
+    entries: List[AuditedDecision],
+    replayed: List[Dict[str, Any]],
+    outcomes: Dict[str, List[TradeOutcome]]
+) -> Dict[str, Any]:
+    """
+    Report of the logged versus the replayed decisions.
+
+    Returns:
+        Dict with the corpus size, the score of each side, action transitions
+        and every decision whose action changed
+    """
+    baseline = [_logged(entry) for entry in entries]
+    baseline_score = score_decisions(baseline, outcomes)
+    candidate_score = score_decisions(replayed, outcomes)
+
+    changes = []
+    for entry, before, after in zip(entries, baseline, replayed):
+        if before["action"] == after["action"]:
+            continue
+        changes.append({
+            "decision_id": entry.decision_id,
+            "created_at": datetime.fromtimestamp(entry.created_at).isoformat(),
+            "market_id": entry.market_id,
+            "asset": entry.asset,
+            "opportunity_type": entry.opportunity_type,
+            "baseline": {key: before[key] for key in ("action", "confidence", "return", "reasoning")},
+            "candidate": {key: after[key] for key in ("action", "confidence", "return", "reasoning")},
+        })
+
+    transitions = Counter(f"{before['action']} -> {after['action']}" for before, after in zip(baseline, replayed))
+    return {
+        "corpus": {
+            "decisions": len(entries),
+            "markets": len({entry.market_id for entry in entries}),
+            "markets_with_outcomes": len({entry.market_id for entry in entries if outcomes.get(entry.market_id)}),
+        },
+        "baseline": baseline_score,
+        "candidate": candidate_score,
+        "changed": len(changes),
+        "transitions": dict(sorted(transitions.items())),
+        "changes": changes,
+    }
+
+
+async def replay(entries: List[AuditedDecision], engine: ReplayDecisionEngine) -> List[Dict[str, Any]]:
+    """Replay entries one at a time (decision history evolves as it did live)."""
+    replayed = []
+    for index, entry in enumerate(entries, 1):
+        replayed.append(await engine.decide(entry))
+        logger.info(f"Replayed {index}/{len(entries)}: {entry.action} -> {replayed[-1]['action']}")
+    return replayed
+
+
+# ============================================================================
+# Command line
+# ============================================================================
+
+def _timestamp(raw: str) -> float:
+    try:
+        return datetime.fromisoformat(raw).timestamp()
+    except ValueError:
+        raise argparse.ArgumentTypeError(f"Expected an ISO date or time: {raw}")
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """Command-line entry point for decision replays."""
+    parser = argparse.ArgumentParser(description="Replay logged LLM decisions against a new prompt or model")
+    parser.add_argument("--db", type=Path, default=Path("data/llm_decisions.db"), help="Decision audit log")
+    parser.add_argument("--asset", help="Only decisions on this asset")
+    parser.add_argument("--opportunity-type", help="Only decisions of this type, e.g. directional_trend")
+    parser.add_argument("--since", type=_timestamp, help="Only decisions at or after this ISO time")
+    parser.add_argument("--until", type=_timestamp, help="Only decisions before this ISO time")
+    parser.add_argument("--limit", type=int, help="At most this many decisions (oldest first)")
+    parser.add_argument("--config", type=Path, help="YAML config whose llm_backends to replay with (default: NVIDIA)")
+    parser.add_argument(
+        "--backend", action="append", default=[],
+        help="Backend from --config to use, in failover order (repeatable)"
+    )
+    parser.add_argument("--system-prompt", type=Path, help="File with a system prompt to use instead of the built-in ones")
+    parser.add_argument(
+        "--stored-prompts", action="store_true",
+        help="Send the logged user prompts verbatim instead of rebuilding them from the stored context"
+    )
+    parser.add_argument("--max-position-pct", type=float, default=5.0)
+    parser.add_argument("--timeout", type=float, default=30.0, help="Seconds per decision")
+    parser.add_argument("--output", type=Path, help="Write the JSON report to this file")
+    parser.add_argument("--verbose", action="store_true")
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(
+        level=logging.INFO if args.verbose else logging.WARNING,
+        format="%(asctime)s - %(name)s - %(levelname)s - %(message)s"
+    )
+
+    if not args.db.exists():
+        parser.error(f"No decision audit log at {args.db}")
+    try:
+        backends = load_backends(args.config, args.backend)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+
+    audit_log = DecisionAuditLog(str(args.db))
+    entries = audit_log.get_decisions(
+        asset=args.asset, opportunity_type=args.opportunity_type,
+        since=args.since, until=args.until, limit=args.limit
+    )
+    engine = ReplayDecisionEngine(
+        system_prompt=args.system_prompt.read_text() if args.system_prompt else None,
+        stored_prompts=args.stored_prompts,
+        backends=backends,
+        max_position_pct=args.max_position_pct,
+        decision_timeout=args.timeout,
+    )
+    replayed = asyncio.run(replay(entries, engine))
+    report = compare(entries, replayed, audit_log.get_outcomes({entry.market_id for entry in entries}))
+
+    output = json.dumps(report, indent=2, default=str)
+    if args.output:
+        args.output.write_text(output)
+    print(output)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -11,6 +11,7 @@ import time
 import json
 from datetime import datetime
 from decimal import Decimal
+from functools import partial
 from typing import Optional, Dict, Any, List
 from pathlib import Path
 import logging
@@ -38,12 +39,26 @@ from src.resolution_farming_engine import ResolutionFarmingEngine
 from src.order_manager import OrderManager
 from src.position_merger import PositionMerger
 from src.transaction_manager import TransactionManager
+from src.fee_strategy import GWEI, Eip1559FeeEstimator
+from src.config_reloader import ConfigReloader
+from src.alerting import (
+    CIRCUIT_BREAKER, GAS_PRICE, LEDGER_DRIFT, LOW_BALANCE, REDEMPTION, TRADE_FILL, VOLATILITY_HALT,
+    create_alert_router,
+)
+from src.tx_journal import TransactionJournal
 from src.ai_safety_guard import AISafetyGuard
+from src.llm_backends import create_llm_backend_chain
+from src.llm_decision_audit import DecisionAuditLog
 from src.fund_manager import FundManager
 from src.monitoring_system import MonitoringSystem
 from src.status_dashboard import StatusDashboard
 from src.market_parser import MarketParser
+from src.correlation_analyzer import CorrelationAnalyzer
+from src.fair_value_pricer import FairValuePricer
+from src.fifteen_min_crypto_strategy import BinancePriceFeed
+from src.multi_timeframe_analyzer import MultiTimeframeAnalyzer
 from src.trade_history import TradeHistoryDB
+from src.trade_outcome_store import verify_store
 from src.trade_statistics import TradeStatisticsTracker
 from src.error_recovery import CircuitBreaker
 from src.auto_bridge_manager import AutoBridgeManager
@@ -58,7 +73,25 @@ from src.llm_decision_engine_v2 import (
 )
 from src.negrisk_arbitrage_engine import NegRiskArbitrageEngine
 from src.portfolio_risk_manager import PortfolioRiskManager
-from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+from src.market_data_recorder import MarketDataRecorder
+from src.position_ledger import LedgerReconciler, PositionLedger
+from src.redemption_service import RedemptionService
+from src.strategy_registry import (
+    build_default_registry,
+    StrategyContext,
+    FifteenMinuteCryptoAdapter
+)
+from src.wallet_accounts import (
+    PRIMARY_WALLET,
+    WalletAccount,
+    WalletAccountConfig,
+    WalletRegistry,
+    book_label,
+    load_wallet_configs,
+    open_wallet_account,
+    wallet_file
+)
+from src.web_dashboard import DashboardController, WebDashboardServer
 
 logger = logging.getLogger(__name__)
 
@@ -100,24 +133,50 @@ class MainOrchestrator:
         # Initialize Web3
         logger.info(f"Connecting to Polygon RPC: {config.polygon_rpc_url}")
         self.web3 = Web3(Web3.HTTPProvider(config.polygon_rpc_url))
-        self.account = self.web3.eth.account.from_key(config.private_key)
-        
-        # Verify wallet address matches (Requirement 14.4)
-        from src.wallet_verifier import WalletVerifier
-        if not WalletVerifier.verify_wallet_address(config.private_key, config.wallet_address):
-            raise ValueError(
-                f"SECURITY ERROR: Private key does not match wallet address. "
-                f"Expected: {config.wallet_address}, Got: {self.account.address}"
-            )
         
-        logger.info(f"[OK] Wallet address verified: {self.account.address}")
+        # EIP-1559 fees for every on-chain transaction (docs/TRANSACTION_FEES.md)
+        self.fee_estimator = Eip1559FeeEstimator(
+            self.web3,
+            blocks_ahead=config.fee_blocks_ahead,
+            min_priority_fee_gwei=config.min_priority_fee_gwei,
+            max_fee_cap_gwei=config.max_gas_price_gwei
+        )
+        
+        # Every signed transaction is journaled and resumed after a restart (live only)
+        self.tx_journal = None if config.dry_run else TransactionJournal(config.tx_journal_db_path)
+        
+        # Signer: key in this process, or in the signing daemon (docs/SIGNER.md).
+        # Both expose .address and .sign_transaction(), so components take either.
+        self.remote_signer = config.signer_backend == "remote"
+        if self.remote_signer:
+            from src.signer import RemoteSigner
+            self.account = RemoteSigner(config.signer_socket_path)
+            logger.info(f"🔏 Using signing daemon at {config.signer_socket_path}")
+            if self.account.address.lower() != config.wallet_address.lower():
+                raise ValueError(
+                    f"SECURITY ERROR: Signing daemon key does not match wallet address. "
+                    f"Expected: {config.wallet_address}, Got: {self.account.address}"
+                )
+        else:
+            self.account = self.web3.eth.account.from_key(config.private_key)
+            
+            # Verify wallet address matches (Requirement 14.4)
+            from src.wallet_verifier import WalletVerifier
+            if not WalletVerifier.verify_wallet_address(config.private_key, config.wallet_address):
+                raise ValueError(
+                    f"SECURITY ERROR: Private key does not match wallet address. "
+                    f"Expected: {config.wallet_address}, Got: {self.account.address}"
+                )
         
-        # Detect wallet type and configuration
-        logger.info("Detecting wallet type...")
-        from src.wallet_type_detector import WalletTypeDetector
+        logger.info(f"[OK] Wallet address verified: {self.account.address}")
         
-        wallet_detector = WalletTypeDetector(self.web3, config.private_key)
-        wallet_config = wallet_detector.auto_detect_configuration()
+        # Detect wallet type and configuration (needs the key; the result is overridden below)
+        if not self.remote_signer:
+            logger.info("Detecting wallet type...")
+            from src.wallet_type_detector import WalletTypeDetector
+            
+            wallet_detector = WalletTypeDetector(self.web3, config.private_key)
+            wallet_config = wallet_detector.auto_detect_configuration()
         
         # FIXED CONFIGURATION (2026-02-09)
         # User's funds are in Gnosis Safe proxy wallet (0x93e65...), not EOA
@@ -134,19 +193,33 @@ class MainOrchestrator:
         logger.info("Initializing Polymarket CLOB client with Gnosis Safe configuration...")
         
         try:
-            self.clob_client = ClobClient(
-                host=config.polymarket_api_url,
-                key=config.private_key,
-                chain_id=config.chain_id,
-                signature_type=self.signature_type,
-                funder=self.funder_address
-            )
-            
-            # Always derive API credentials from private key
-            # NOTE: User's explicit POLY_* creds are for different account (401 Unauthorized)
-            # The derived credentials are the only ones that work with this private key
-            logger.info("Deriving API credentials from private key...")
-            creds = self.clob_client.create_or_derive_api_creds()
+            if self.remote_signer:
+                # No key in this process: orders and L1 auth are signed by the daemon
+                from src.signer import attach_signer, derive_api_creds
+                self.clob_client = ClobClient(
+                    host=config.polymarket_api_url,
+                    chain_id=config.chain_id,
+                    signature_type=self.signature_type,
+                    funder=self.funder_address
+                )
+                attach_signer(self.clob_client, self.account, config.chain_id,
+                              self.signature_type, self.funder_address)
+                logger.info("Deriving API credentials through the signing daemon...")
+                creds = derive_api_creds(config.polymarket_api_url, self.account, config.chain_id)
+            else:
+                self.clob_client = ClobClient(
+                    host=config.polymarket_api_url,
+                    key=config.private_key,
+                    chain_id=config.chain_id,
+                    signature_type=self.signature_type,
+                    funder=self.funder_address
+                )
+                
+                # Always derive API credentials from private key
+                # NOTE: User's explicit POLY_* creds are for different account (401 Unauthorized)
+                # The derived credentials are the only ones that work with this private key
+                logger.info("Deriving API credentials from private key...")
+                creds = self.clob_client.create_or_derive_api_creds()
             self.clob_client.set_api_creds(creds)
             
             logger.info(f"✅ CLOB client initialized successfully")
@@ -162,7 +235,9 @@ class MainOrchestrator:
             logger.info("Checking token allowances (EOA wallet)...")
             from src.token_allowance_manager import TokenAllowanceManager
             
-            self.allowance_manager = TokenAllowanceManager(self.web3, self.account)
+            self.allowance_manager = TokenAllowanceManager(
+                self.web3, self.account, fee_estimator=self.fee_estimator, journal=self.tx_journal
+            )
             allowance_results = self.allowance_manager.check_all_allowances()
             
             if not allowance_results['all_approved']:
@@ -180,12 +255,26 @@ class MainOrchestrator:
         # Initialize core components
         logger.info("Initializing core components...")
         
-        self.transaction_manager = TransactionManager(self.web3, self.account)
+        self.transaction_manager = TransactionManager(
+            self.web3,
+            self.account,
+            fee_estimator=self.fee_estimator,
+            max_replacements=config.max_fee_replacements,
+            auto_replace=config.auto_replace_stuck_tx,
+            journal=self.tx_journal
+        )
+        
+        # Authoritative position/PnL ledger (live trading only - dry-run fills are simulated)
+        self.position_ledger = None if config.dry_run else PositionLedger(config.ledger_db_path)
+        
         self.position_merger = PositionMerger(
             self.web3,
             config.conditional_token_address,
             config.usdc_address,
-            self.account
+            self.account,
+            ledger=self.position_ledger,
+            fee_estimator=self.fee_estimator,
+            transaction_manager=self.transaction_manager
         )
         self.order_manager = OrderManager(
             self.clob_client,
@@ -193,9 +282,16 @@ class MainOrchestrator:
             dry_run=config.dry_run
         )
         
+        # LLM backends shared by the safety guard and the decision engine
+        # (None: both use the NVIDIA API with nvidia_api_key)
+        self.llm_backends = create_llm_backend_chain(config)
+        
         # Initialize safety and risk management
         self.ai_safety_guard = AISafetyGuard(
             nvidia_api_key=config.nvidia_api_key,
+            llm_backends=self.llm_backends,
+            max_repair_attempts=config.llm_max_repair_attempts,
+            rejected_responses_path=config.llm_rejected_responses_path,
             min_balance=config.min_balance,
             max_gas_price_gwei=config.max_gas_price_gwei,
             max_pending_tx=config.max_pending_tx,
@@ -222,15 +318,21 @@ class MainOrchestrator:
             target_balance=config.target_balance,
             withdraw_limit=config.withdraw_limit,
             dry_run=config.dry_run,
-            oneinch_api_key=None  # Optional, not in config yet
+            oneinch_api_key=None,  # Optional, not in config yet
+            clob_client=self.clob_client
         )
         
         # Initialize auto-bridge manager for fully autonomous operation
-        self.auto_bridge_manager = AutoBridgeManager(
-            private_key=config.private_key,
-            polygon_rpc=config.polygon_rpc_url,
-            dry_run=config.dry_run
-        )
+        # (signs Ethereum mainnet transactions with the raw key; not available with the signing daemon)
+        if self.remote_signer:
+            logger.info("Auto-bridge disabled: it needs the private key in-process")
+            self.auto_bridge_manager = None
+        else:
+            self.auto_bridge_manager = AutoBridgeManager(
+                private_key=config.private_key,
+                polygon_rpc=config.polygon_rpc_url,
+                dry_run=config.dry_run
+            )
         
         # Initialize strategy engines
         logger.info("Initializing strategy engines...")
@@ -249,7 +351,8 @@ class MainOrchestrator:
             ai_safety_guard=self.ai_safety_guard,
             kelly_sizer=self.kelly_sizer,
             dynamic_sizer=self.dynamic_sizer,  # Pass dynamic sizer
-            min_profit_threshold=config.min_profit_threshold
+            min_profit_threshold=config.min_profit_threshold,
+            max_position_size=config.max_position_size
         )
         
         # Flash Crash Strategy will be initialized after market_parser
@@ -258,17 +361,17 @@ class MainOrchestrator:
         # Directional Trading will be initialized after market_parser
         self.directional_trading = None
         
-        # Cross-platform arbitrage (if Kalshi API key provided)
-        # TEMPORARILY DISABLED - needs additional setup
+        # Cross-platform arbitrage runs from the strategy registry
+        # (enabled_strategies: cross_platform_arbitrage, see docs/KALSHI.md)
         self.cross_platform_arbitrage = None
-        # if config.kalshi_api_key:
-        #     self.cross_platform_arbitrage = CrossPlatformArbitrageEngine(
-        #         polymarket_client=self.clob_client,
-        #         kalshi_api_key=config.kalshi_api_key,
-        #         order_manager=self.order_manager,
-        #         ai_safety_guard=self.ai_safety_guard
-        #     )
-        #     logger.info("Cross-platform arbitrage enabled")
+        
+        # One Binance feed and fair-value model for every strategy and wallet (docs/FAIR_VALUE_PRICER.md)
+        self.price_feed = BinancePriceFeed()
+        self.fair_value_pricer = FairValuePricer(
+            price_feed=self.price_feed, volatility_analyzer=MultiTimeframeAnalyzer()
+        )
+        # Correlated-exposure limits on entries, from live correlations on the same feed (docs/CORRELATION.md)
+        self.correlation_analyzer = CorrelationAnalyzer.from_price_feed(self.price_feed)
         
         # Latency arbitrage
         # TEMPORARILY DISABLED - needs CEX feeds setup
@@ -276,7 +379,8 @@ class MainOrchestrator:
         # self.latency_arbitrage = LatencyArbitrageEngine(
         #     clob_client=self.clob_client,
         #     order_manager=self.order_manager,
-        #     ai_safety_guard=self.ai_safety_guard
+        #     ai_safety_guard=self.ai_safety_guard,
+        #     fair_value_pricer=self.fair_value_pricer
         # )
         
         # Resolution farming
@@ -294,9 +398,17 @@ class MainOrchestrator:
         
         self.monitoring = MonitoringSystem(
             prometheus_port=config.prometheus_port,
-            sns_topic_arn=config.sns_alert_topic
+            sns_topic_arn=config.sns_alert_topic,
+            alert_router=create_alert_router(config)
         )
         
+        # Route circuit breaker trips, volatility halts and fills to the alert channels (docs/ALERTING.md)
+        self.circuit_breaker.on_open = self._on_circuit_breaker_open
+        self.ai_safety_guard.on_volatility_halt = self._on_volatility_halt
+        if self.position_ledger is not None:
+            self.position_ledger.fill_listeners.append(self._on_ledger_fill)
+        self.low_balance_wallets: set = set()  # Wallets already alerted as below min_balance
+        
         # TASK 13.3: Initialize memory monitor
         from src.memory_monitor import MemoryMonitor
         self.memory_monitor = MemoryMonitor(
@@ -321,6 +433,22 @@ class MainOrchestrator:
         self.trade_history = TradeHistoryDB()
         self.trade_statistics = TradeStatisticsTracker(self.trade_history)
         
+        # Automatic redemption of resolved positions (EOA, or the Gnosis Safe the EOA owns)
+        self.redemption_service = None
+        if config.redemption_interval_seconds > 0 and self.signature_type in (0, 2):
+            self.redemption_service = RedemptionService(
+                self.web3,
+                self.transaction_manager,
+                ctf_address=config.conditional_token_address,
+                usdc_address=config.usdc_address,
+                safe_address=self.funder_address if self.signature_type == 2 else None,
+                neg_risk_adapter_address=config.neg_risk_adapter_address,
+                trade_history=self.trade_history,
+                ledger=self.position_ledger,
+                dry_run=config.dry_run
+            )
+            logger.info(f"✅ Automatic redemption every {config.redemption_interval_seconds}s")
+        
         # Initialize status dashboard
         self.dashboard = StatusDashboard()
         
@@ -349,6 +477,10 @@ class MainOrchestrator:
         logger.info("Initializing LLM Decision Engine V2 (Perfect Edition)...")
         self.llm_decision_engine = LLMDecisionEngineV2(
             nvidia_api_key=config.nvidia_api_key,
+            backends=self.llm_backends,
+            max_repair_attempts=config.llm_max_repair_attempts,
+            rejected_responses_path=config.llm_rejected_responses_path,
+            audit_log=DecisionAuditLog(config.llm_decision_audit_path) if config.llm_decision_audit_path else None,
             min_confidence_threshold=45.0,  # 45% threshold - balanced between opportunity and safety
             max_position_pct=5.0,  # Max 5% of balance per trade
             decision_timeout=5.0,  # 5 second timeout for LLM calls
@@ -365,7 +497,7 @@ class MainOrchestrator:
             order_manager=self.order_manager,
             ai_safety_guard=self.ai_safety_guard,
             min_profit_threshold=Decimal('0.005'),  # 0.5% minimum
-            max_position_size=Decimal('5.0')
+            max_position_size=config.max_position_size
         )
         logger.info("✅ NegRisk Arbitrage Engine enabled")
         
@@ -383,9 +515,8 @@ class MainOrchestrator:
         logger.info("✅ Portfolio Risk Manager enabled (OPTIMIZED: More permissive limits)")
         
         # ============================================================
-        # 15-MINUTE CRYPTO STRATEGY - BTC/ETH Up/Down Trading
+        # STRATEGY CAPITAL - Actual balance for sizing
         # ============================================================
-        logger.info("Initializing 15-Minute Crypto Trading Strategy...")
         
         # CRITICAL FIX: Get actual balance instead of using TARGET_BALANCE from .env
         # This was causing risk manager to block all trades (thought $0.40 balance when actually $5.48)
@@ -415,22 +546,86 @@ class MainOrchestrator:
         logger.info(f"💰 Initial trade size: ${initial_trade_size:.2f} per trade (20% of balance, max $3)")
         logger.info(f"💰 Risk manager will use actual balance for portfolio heat calculations")
         
-        self.fifteen_min_strategy = FifteenMinuteCryptoStrategy(
+        # ============================================================
+        # STRATEGY REGISTRY - Enabled strategies loaded from config
+        # ============================================================
+        logger.info(f"Loading strategies: {', '.join(config.enabled_strategies)}")
+        self.strategy_registry = build_default_registry()
+        self.strategy_context = StrategyContext(
+            config=config,
             clob_client=self.clob_client,
-            trade_size=initial_trade_size,  # DYNAMIC: Will be adjusted by risk manager
-            take_profit_pct=0.02,  # 2% profit target (OPTIMIZED: Bigger profits)
-            stop_loss_pct=0.02,  # 2% stop loss (BALANCED: Control losses)
-            max_positions=5,  # OPTIMIZED: Allow more concurrent trades
-            sum_to_one_threshold=1.02,  # FIXED: Was 0.98 (impossible!), now 1.02 (correct for arbitrage)
-            dry_run=config.dry_run,
+            order_manager=self.order_manager,
+            ai_safety_guard=self.ai_safety_guard,
             llm_decision_engine=self.llm_decision_engine,
-            enable_adaptive_learning=False,  # CRITICAL FIX: Disable (breaks dynamic take profit)
-            initial_capital=actual_balance  # ✅ FIXED: Use actual balance instead of target_balance
+            initial_capital=actual_balance,  # ✅ FIXED: Use actual balance instead of target_balance
+            trade_size=initial_trade_size,
+            price_feed=self.price_feed,
+            fair_value_pricer=self.fair_value_pricer,
+            correlation_analyzer=self.correlation_analyzer,
+            ledger=self.position_ledger,
+            redemption_service=self.redemption_service,
+            metrics=self.monitoring
         )
-        logger.info("✅ 15-Minute Crypto Strategy enabled (OPTIMIZED: Better profit targets, actual balance tracking)")
+        self.strategies = self.strategy_registry.build(config.enabled_strategies, self.strategy_context)
+        
+        # Keep direct reference to the 15-minute strategy (feeds, stats, dynamic trade size)
+        self.fifteen_min_strategy = next(
+            (s.strategy for s in self.strategies if isinstance(s, FifteenMinuteCryptoAdapter)),
+            None
+        )
+        if self.fifteen_min_strategy:
+            logger.info("✅ 15-Minute Crypto Strategy enabled (OPTIMIZED: Better profit targets, actual balance tracking)")
+        
+        # ============================================================
+        # WALLETS - Primary book plus sub-accounts (docs/MULTI_WALLET.md)
+        # ============================================================
+        self.wallets = WalletRegistry()
+        self.wallets.add(WalletAccount(
+            name=PRIMARY_WALLET,
+            account=self.account,
+            signature_type=self.signature_type,
+            funder_address=self.funder_address,
+            clob_client=self.clob_client,
+            transaction_manager=self.transaction_manager,
+            order_manager=self.order_manager,
+            fund_manager=self.fund_manager,
+            risk_manager=self.portfolio_risk_manager,
+            strategies=self.strategies,
+            redemption_service=self.redemption_service,
+            ledger=self.position_ledger
+        ))
+        # Sub-accounts are opened by run(), where their balance can be awaited
+        self._sub_account_configs = load_wallet_configs(config)
+        
+        # Ledger reconciliation against on-chain token and USDC balances
+        if self.position_ledger is not None and config.ledger_reconcile_interval_seconds > 0:
+            self.wallets.primary.ledger_reconciler = LedgerReconciler(
+                self.position_ledger,
+                token_balance_getter=partial(self.position_merger.get_position_balance, owner=self.funder_address),
+                usdc_balance_getter=self._get_usdc_balance,
+                repair=config.ledger_repair_drift,
+                settlement_grace_seconds=config.ledger_settlement_grace_seconds
+            )
+            logger.info(f"✅ Position ledger reconciliation every {config.ledger_reconcile_interval_seconds}s")
+        
+        # Recorder mode: capture the 15-minute strategy's market data for replay backtests
+        self.market_data_recorder = None
+        if config.market_data_recording:
+            if self.fifteen_min_strategy:
+                self.market_data_recorder = MarketDataRecorder(
+                    output_dir=config.market_data_dir,
+                    rotate_interval_seconds=config.market_data_rotate_minutes * 60
+                )
+                self.fifteen_min_strategy.attach_recorder(self.market_data_recorder)
+                if self.redemption_service:
+                    self.redemption_service.recorder = self.market_data_recorder
+                else:
+                    logger.warning("⚠️ Automatic redemption is disabled - recordings will carry no resolutions")
+            else:
+                logger.warning("⚠️ Market data recording requires the fifteen_min_crypto strategy - disabled")
         
         # TASK 13.3: Register deques for memory monitoring
-        for asset in ["BTC", "ETH", "SOL", "XRP"]:
+        for asset in (["BTC", "ETH", "SOL", "XRP"] if self.fifteen_min_strategy else []):
             self.memory_monitor.register_deque(
                 f"binance_price_history_{asset}",
                 self.fifteen_min_strategy.binance_feed.price_history[asset]
@@ -449,7 +644,8 @@ class MainOrchestrator:
                     f"mtf_volume_{asset}_{timeframe}",
                     self.fifteen_min_strategy.multi_tf_analyzer.volume_history[asset][timeframe]
                 )
-        logger.info("✅ Registered 56 deques for memory monitoring (price/volume history)")
+        if self.fifteen_min_strategy:
+            logger.info("✅ Registered 56 deques for memory monitoring (price/volume history)")
         
         # Timing trackers
         self.last_heartbeat = time.time()
@@ -457,11 +653,34 @@ class MainOrchestrator:
         self.last_fund_check = time.time()
         self.last_state_save = time.time()
         self.last_memory_report = time.time()  # TASK 13.3: Track last memory report time
+        self.last_ledger_reconcile = 0.0  # Reconcile on the first loop iteration
+        self.last_redemption = 0.0  # Redeem on the first loop iteration
         self.scan_count = 0
         
         # Gas price monitoring
         self.gas_price_halted = False
         
+        # Operator controls, set through the web dashboard's control API (docs/WEB_DASHBOARD.md)
+        self.trading_paused = False
+        self.pause_reason = ""
+        self.disabled_strategies: set = set()  # Strategy books: strategy, or strategy@wallet
+        self.dashboard_controller = DashboardController(self)
+        self.web_dashboard = None
+        if config.dashboard_enabled:
+            self.web_dashboard = WebDashboardServer(
+                self.dashboard_controller,
+                host=config.dashboard_host,
+                port=config.dashboard_port,
+                api_token=config.dashboard_api_token
+            )
+        
+        # Hot reload of the YAML config file (docs/CONFIG_RELOAD.md)
+        self.config_reloader = None
+        if config.config_file and config.config_reload_interval_seconds > 0:
+            self.config_reloader = ConfigReloader(config, self._apply_config)
+            logger.info(f"✅ Watching {config.config_file} for config changes")
+        self.last_config_check = time.time()
+        
         # OPTIMIZATION: Market data cache (50% fewer API calls)
         self._market_cache: Optional[List] = None
         self._market_cache_time: float = 0
@@ -477,6 +696,126 @@ class MainOrchestrator:
         
         logger.info("MainOrchestrator initialized successfully")
     
+    def _apply_config(self, config: Config) -> None:
+        """
+        Push hot-reloadable settings into the running components.
+        
+        Called by ConfigReloader with a validated config. Runs without awaiting,
+        so the main loop never sees a partly applied config.
+        """
+        self.config = config
+        
+        self._base_scan_interval = config.scan_interval_seconds
+        self._current_scan_interval = config.scan_interval_seconds
+        self.circuit_breaker.failure_threshold = config.circuit_breaker_threshold
+        
+        self.ai_safety_guard.min_balance = config.min_balance
+        self.ai_safety_guard.max_gas_price_gwei = config.max_gas_price_gwei
+        self.ai_safety_guard.max_pending_tx = config.max_pending_tx
+        
+        # Shared by the transaction managers of every wallet
+        self.fee_estimator.min_priority_fee = int(config.min_priority_fee_gwei * GWEI)
+        self.fee_estimator.max_fee_cap = int(config.max_gas_price_gwei * GWEI)
+        self.fee_estimator.blocks_ahead = max(1, config.fee_blocks_ahead)
+        
+        self.internal_arbitrage.min_profit_threshold = config.min_profit_threshold
+        self.internal_arbitrage.max_position_size = config.max_position_size
+        self.negrisk_arbitrage.max_position_size = config.max_position_size
+        
+        for wallet in self.wallets:
+            wallet.transaction_manager.auto_replace = config.auto_replace_stuck_tx
+            wallet.transaction_manager.escalator.max_escalations = config.max_fee_replacements
+            wallet.fund_manager.min_balance = config.min_balance
+            wallet.fund_manager.target_balance = config.target_balance
+            wallet.fund_manager.withdraw_limit = config.withdraw_limit
+            for strategy in wallet.strategies:
+                strategy.apply_config(config)
+    
+    async def _open_sub_accounts(self) -> None:
+        """Open the configured sub-account books (once, at the start of run())."""
+        configs, self._sub_account_configs = self._sub_account_configs, []
+        for wallet_config in configs:
+            self.wallets.add(await self._open_sub_account(wallet_config))
+        if len(self.wallets) > 1:
+            logger.info(f"✅ Trading {len(self.wallets)} wallets: {', '.join(self.wallets.names)}")
+    
+    async def _open_sub_account(self, wallet_config: WalletAccountConfig) -> WalletAccount:
+        """Build a sub-account book: clients, risk manager, ledger, redemption and its own strategy instances."""
+        config = self.config
+        account = open_wallet_account(
+            wallet_config, config, self.web3, fee_estimator=self.fee_estimator, journal=self.tx_journal
+        )
+        
+        if account.signature_type == 0:
+            from src.token_allowance_manager import TokenAllowanceManager
+            allowances = TokenAllowanceManager(
+                self.web3, account.account, fee_estimator=self.fee_estimator, journal=self.tx_journal
+            ).check_all_allowances()
+            if not allowances['all_approved']:
+                logger.warning(f"⚠️  Token allowances not set for wallet {account.name} - orders may fail")
+        
+        # Risk capital: configured, else the wallet's actual balance (like the primary wallet)
+        capital = wallet_config.capital
+        if capital is None:
+            try:
+                capital = await account.refresh_balance()
+            except Exception as e:
+                logger.error(f"Failed to check balance of wallet {account.name}: {e}")
+                capital = Decimal("0")
+            if capital < Decimal("0.10"):
+                logger.warning(f"⚠️ Wallet {account.name}: using target_balance ${config.target_balance:.2f} as capital")
+                capital = config.target_balance
+        account.risk_manager = wallet_config.create_risk_manager(capital)
+        
+        # Own position ledger (live trading only), reconciled against the wallet's funder address
+        if not config.dry_run:
+            account.ledger = PositionLedger(wallet_file(config.ledger_db_path, account.name))
+            account.ledger.fill_listeners.append(self._on_ledger_fill)
+            if config.ledger_reconcile_interval_seconds > 0:
+                account.ledger_reconciler = LedgerReconciler(
+                    account.ledger,
+                    token_balance_getter=partial(self.position_merger.get_position_balance, owner=account.funder_address),
+                    usdc_balance_getter=partial(self._get_usdc_balance, account.clob_client),
+                    repair=config.ledger_repair_drift,
+                    settlement_grace_seconds=config.ledger_settlement_grace_seconds
+                )
+        
+        if config.redemption_interval_seconds > 0 and account.signature_type in (0, 2):
+            account.redemption_service = RedemptionService(
+                self.web3,
+                account.transaction_manager,
+                ctf_address=config.conditional_token_address,
+                usdc_address=config.usdc_address,
+                safe_address=account.funder_address if account.signature_type == 2 else None,
+                neg_risk_adapter_address=config.neg_risk_adapter_address,
+                trade_history=self.trade_history,
+                ledger=account.ledger,
+                dry_run=config.dry_run,
+                wallet=account.name
+            )
+        
+        # Own strategy instances: positions, order tracking and sizing stay per wallet
+        context = StrategyContext(
+            config=config,
+            clob_client=account.clob_client,
+            order_manager=account.order_manager,
+            ai_safety_guard=self.ai_safety_guard,
+            llm_decision_engine=self.llm_decision_engine,
+            initial_capital=float(capital),
+            trade_size=max(0.50, min(float(capital) * 0.20, 3.0)),
+            price_feed=self.price_feed,
+            fair_value_pricer=self.fair_value_pricer,
+            correlation_analyzer=self.correlation_analyzer,
+            ledger=account.ledger,
+            redemption_service=account.redemption_service,
+            metrics=self.monitoring,
+            wallet=account.name
+        )
+        logger.info(f"Loading strategies for wallet {account.name}: {', '.join(wallet_config.strategies)}")
+        account.strategies = self.strategy_registry.build(wallet_config.strategies, context)
+        logger.info(f"✅ Wallet {account.name} ready (capital: ${capital:.2f})")
+        return account
+    
     def _load_state(self) -> None:
         """
         Load persisted state from disk.
@@ -547,194 +886,74 @@ class MainOrchestrator:
         except Exception as e:
             logger.error(f"Failed to save state: {e}")
     
-    async def startup_validation(self) -> bool:
-        """
-        Comprehensive startup validation (Requirement 11.8).
-        
-        Validates:
-        - All API credentials and test connections
-        - WebSocket connections
-        - Balance and wallet access
-        - Learning data integrity
-        - Self-diagnostics
-        
-        Returns:
-            bool: True if all validations pass, False if critical validation fails
-        """
-        logger.info("=" * 80)
-        logger.info("STARTUP VALIDATION - Requirement 11.8")
-        logger.info("=" * 80)
-        
-        validation_results = {
-            "api_credentials": False,
-            "clob_connection": False,
-            "websocket_connection": False,
-            "balance_access": False,
-            "learning_data": False,
-            "self_diagnostics": False
-        }
-        
-        # 1. Verify API Credentials
-        logger.info("\n[1/6] Verifying API credentials...")
-        try:
-            # Check if CLOB client has valid credentials
-            if hasattr(self.clob_client, 'creds') and self.clob_client.creds:
-                logger.info(f"✅ CLOB API Key: {self.clob_client.creds.api_key[:8]}...")
-                logger.info(f"✅ CLOB API Secret: {'*' * 8}...")
-                logger.info(f"✅ CLOB API Passphrase: {'*' * 8}...")
-                validation_results["api_credentials"] = True
-            else:
-                logger.error("❌ CLOB API credentials not found")
-                validation_results["api_credentials"] = False
-        except Exception as e:
-            logger.error(f"❌ API credential validation failed: {e}")
-            validation_results["api_credentials"] = False
-        
-        # 2. Test CLOB API Connection
-        logger.info("\n[2/6] Testing CLOB API connection...")
-        try:
-            # Try to fetch server time (lightweight API call)
-            server_time = self.clob_client.get_server_time()
-            if server_time:
-                logger.info(f"✅ CLOB API connection successful (server time: {server_time})")
-                validation_results["clob_connection"] = True
-            else:
-                logger.error("❌ CLOB API returned empty response")
-                validation_results["clob_connection"] = False
-        except Exception as e:
-            logger.error(f"❌ CLOB API connection failed: {e}")
-            validation_results["clob_connection"] = False
-        
-        # 3. Verify WebSocket Connections
-        logger.info("\n[3/6] Verifying WebSocket connections...")
-        try:
-            # Check Binance WebSocket feed
-            if hasattr(self.fifteen_min_strategy, 'binance_feed'):
-                binance_connected = self.fifteen_min_strategy.binance_feed.is_connected()
-                if binance_connected:
-                    logger.info("✅ Binance WebSocket connected")
-                    validation_results["websocket_connection"] = True
-                else:
-                    logger.warning("⚠️ Binance WebSocket not connected (will auto-reconnect)")
-                    # Not critical - will auto-reconnect
-                    validation_results["websocket_connection"] = True
-            else:
-                logger.warning("⚠️ Binance feed not initialized")
-                validation_results["websocket_connection"] = True  # Not critical
-        except Exception as e:
-            logger.error(f"❌ WebSocket validation failed: {e}")
-            validation_results["websocket_connection"] = False
-        
-        # 4. Validate Balance and Wallet Access
-        logger.info("\n[4/6] Validating balance and wallet access...")
-        try:
-            # Check balance
-            eoa_balance, proxy_balance = await self.fund_manager.check_balance()
-            total_balance = eoa_balance + proxy_balance
-            
-            logger.info(f"✅ EOA Balance: ${eoa_balance:.2f} USDC")
-            logger.info(f"✅ Proxy Balance: ${proxy_balance:.2f} USDC")
-            logger.info(f"✅ Total Balance: ${total_balance:.2f} USDC")
-            
-            if total_balance < Decimal("0.10"):
-                logger.warning(f"⚠️ Low balance: ${total_balance:.2f} (minimum $0.10 recommended)")
-                # Not critical - bot can still run
-            
-            validation_results["balance_access"] = True
-        except Exception as e:
-            logger.error(f"❌ Balance validation failed: {e}")
-            validation_results["balance_access"] = False
-        
-        # 5. Load and Verify Learning Data
-        logger.info("\n[5/6] Loading and verifying learning data...")
-        try:
-            # Check if learning data files exist
-            learning_files = [
-                "data/supersmart_learning.json",
-                "data/rl_learning.json",
-                "data/adaptive_learning.json"
-            ]
-            
-            learning_data_ok = True
-            for file_path in learning_files:
-                if Path(file_path).exists():
-                    try:
-                        with open(file_path, 'r') as f:
-                            data = json.load(f)
-                        logger.info(f"✅ Loaded {file_path} ({len(data)} entries)")
-                    except Exception as e:
-                        logger.warning(f"⚠️ Failed to load {file_path}: {e}")
-                        learning_data_ok = False
-                else:
-                    logger.info(f"ℹ️ {file_path} not found (will be created on first trade)")
-            
-            validation_results["learning_data"] = learning_data_ok
-        except Exception as e:
-            logger.error(f"❌ Learning data validation failed: {e}")
-            validation_results["learning_data"] = False
-        
-        # 6. Run Self-Diagnostics
-        logger.info("\n[6/6] Running self-diagnostics...")
+    async def _check_low_balances(self) -> None:
+        """Alert once when a wallet's balance drops below min_balance (a zero balance is not checkable)."""
+        for wallet in self.wallets:
+            balance = wallet.total_balance
+            if balance >= self.config.min_balance:
+                self.low_balance_wallets.discard(wallet.name)
+            elif balance > 0 and wallet.name not in self.low_balance_wallets:
+                self.low_balance_wallets.add(wallet.name)
+                await self.monitoring.send_alert(
+                    "warning",
+                    f"Low balance on wallet {wallet.name}: ${balance:.2f} (min: ${self.config.min_balance})",
+                    {"wallet": wallet.name, "eoa_balance": wallet.eoa_balance, "proxy_balance": wallet.proxy_balance},
+                    event=LOW_BALANCE
+                )
+    
+    def _update_strategy_metrics(self) -> None:
+        """Refresh the gauges read from strategies and risk managers on every loop."""
         try:
-            diagnostics_passed = True
-            
-            # Check circuit breaker state
-            if self.circuit_breaker.is_open:
-                logger.warning("⚠️ Circuit breaker is OPEN (trading halted)")
-                logger.warning(f"   Consecutive failures: {self.circuit_breaker.consecutive_failures}")
-                diagnostics_passed = False
-            else:
-                logger.info("✅ Circuit breaker is CLOSED (trading enabled)")
-            
-            # Check risk manager state
-            if hasattr(self.portfolio_risk_manager, 'current_capital'):
-                logger.info(f"✅ Risk manager initialized (capital: ${self.portfolio_risk_manager.current_capital:.2f})")
-            else:
-                logger.warning("⚠️ Risk manager not fully initialized")
-            
-            # Check strategy state
-            if hasattr(self.fifteen_min_strategy, 'positions'):
-                position_count = len(self.fifteen_min_strategy.positions)
-                logger.info(f"✅ Strategy initialized ({position_count} open positions)")
-            else:
-                logger.warning("⚠️ Strategy not fully initialized")
-            
-            # Check auto-recovery system
-            if hasattr(self, 'auto_recovery'):
-                logger.info("✅ Auto-recovery system enabled")
-            else:
-                logger.warning("⚠️ Auto-recovery system not initialized")
-            
-            validation_results["self_diagnostics"] = diagnostics_passed
+            positions = {}
+            for wallet in self.wallets:
+                if wallet.risk_manager is not None:
+                    stats = wallet.risk_manager.get_statistics()
+                    self.monitoring.update_risk_state(
+                        wallet.name, stats["trading_halted"], stats["conservative_mode_active"]
+                    )
+                for strategy in wallet.strategies:
+                    label = book_label(wallet.name, strategy.name)
+                    for position in strategy.open_positions():
+                        if position.get("unrealized_pnl") is not None:
+                            key = (wallet.name, position["strategy"], position["asset"],
+                                   position.get("token_id") or position["market_id"])
+                            positions[key] = float(position["unrealized_pnl"])
+                    for (feed, stream), age in strategy.feed_staleness().items():
+                        self.monitoring.update_feed_staleness(feed, stream, age)
+                    stats = strategy.risk_statistics()
+                    if stats is not None:
+                        self.monitoring.update_risk_state(
+                            label, stats["trading_halted"], stats["conservative_mode_active"]
+                        )
+            self.monitoring.update_position_pnl(positions)
+            self.monitoring.update_circuit_breaker(
+                self.circuit_breaker.is_open, self.circuit_breaker.consecutive_failures
+            )
         except Exception as e:
-            logger.error(f"❌ Self-diagnostics failed: {e}")
-            validation_results["self_diagnostics"] = False
-        
-        # Summary
-        logger.info("\n" + "=" * 80)
-        logger.info("STARTUP VALIDATION SUMMARY")
-        logger.info("=" * 80)
-        
-        all_passed = True
-        critical_checks = ["api_credentials", "clob_connection", "balance_access"]
-        
-        for check, passed in validation_results.items():
-            status = "✅ PASS" if passed else "❌ FAIL"
-            critical = " (CRITICAL)" if check in critical_checks else ""
-            logger.info(f"{status} - {check.replace('_', ' ').title()}{critical}")
-            
-            if check in critical_checks and not passed:
-                all_passed = False
-        
-        logger.info("=" * 80)
-        
-        if all_passed:
-            logger.info("✅ ALL CRITICAL VALIDATIONS PASSED - Bot ready to trade")
-            return True
-        else:
-            logger.error("❌ CRITICAL VALIDATION FAILED - Bot cannot start")
-            logger.error("   Please check the errors above and fix configuration")
-            return False
+            logger.error(f"Failed to update strategy metrics: {e}")
+    
+    def _on_circuit_breaker_open(self, status: dict) -> None:
+        self.monitoring.notify(
+            "critical",
+            f"Circuit breaker opened after {status['consecutive_failures']} consecutive failures - trading halted",
+            {"recent_failures": status["recent_failures"]},
+            event=CIRCUIT_BREAKER
+        )
+    
+    def _on_volatility_halt(self, until: datetime) -> None:
+        self.monitoring.notify(
+            "warning", f"High volatility - trading halted until {until:%H:%M:%S}", event=VOLATILITY_HALT
+        )
+    
+    def _on_ledger_fill(self, entry) -> None:
+        side = "BUY" if entry.shares > 0 else "SELL"
+        self.monitoring.notify(
+            "info",
+            f"Fill: {side} {abs(entry.shares)} {entry.outcome or entry.token_id[:16]} @ ${entry.price} "
+            f"({entry.strategy or 'unknown strategy'}, order {entry.reference or entry.entry_id})",
+            {"market_id": entry.market_id, "usdc": entry.usdc, "fee": entry.fee},
+            event=TRADE_FILL
+        )
     
     async def heartbeat_check(self) -> HealthStatus:
         """
@@ -887,6 +1106,23 @@ class MainOrchestrator:
         # Just log it normally
         logger.info(f"Heartbeat: Balance=${total_balance:.2f}, Gas={gas_price_gwei}gwei, Healthy={is_healthy}")
         
+        # Per-wallet books and their aggregate (sub-accounts, docs/MULTI_WALLET.md)
+        try:
+            primary = self.wallets.primary
+            primary.eoa_balance, primary.proxy_balance = eoa_balance, proxy_balance
+            if len(self.wallets) > 1:
+                await self.wallets.refresh_balances()
+                aggregate = self.wallets.aggregate()
+                logger.info(
+                    f"Wallets: {aggregate['wallets']} | Balance=${aggregate['total_balance']:.2f} | "
+                    f"Today=${aggregate['daily_pnl']:+.2f} ({aggregate['trades_today']} trades)"
+                    + (f" | Halted: {', '.join(aggregate['halted'])}" if aggregate['halted'] else "")
+                )
+            self.dashboard.update_wallets(self.wallets.snapshot())
+            await self._check_low_balances()
+        except Exception as e:
+            logger.error(f"Failed to update wallet books: {e}")
+        
         # TASK 14.4: Log statistics every 10 heartbeats (every 10 minutes)
         if self.heartbeat_count % 10 == 0:
             log_stats = self.log_manager.get_log_stats()
@@ -896,8 +1132,15 @@ class MainOrchestrator:
                 f"{log_stats.get('disk_free_pct', 0):.1f}% disk free"
             )
         
-        # Dashboard update not needed - passive display
-        # self.dashboard.update_health_status(health_status)
+        # Web dashboard state
+        self.dashboard.update_health_status(health_status)
+        self.dashboard.update_state(
+            status="PAUSED" if self.trading_paused else ("RUNNING" if self.running else "STARTING"),
+            mode="DRY_RUN" if self.config.dry_run else "LIVE",
+            wallet_address=self.account.address,
+            scan_cycle=self.scan_count,
+            max_pending_tx=self.config.max_pending_tx
+        )
         
         return health_status
 
@@ -1002,27 +1245,18 @@ class MainOrchestrator:
         # 5. Load and Verify Learning Data
         logger.info("\n[5/6] Loading and verifying learning data...")
         try:
-            # Check if learning data files exist
-            learning_files = [
-                "data/supersmart_learning.json",
-                "data/rl_learning.json",
-                "data/adaptive_learning.json"
-            ]
-
-            learning_data_ok = True
-            for file_path in learning_files:
-                if Path(file_path).exists():
-                    try:
-                        with open(file_path, 'r') as f:
-                            data = json.load(f)
-                        logger.info(f"✅ Loaded {file_path} ({len(data)} entries)")
-                    except Exception as e:
-                        logger.warning(f"⚠️ Failed to load {file_path}: {e}")
-                        learning_data_ok = False
-                else:
-                    logger.info(f"ℹ️ {file_path} not found (will be created on first trade)")
+            # Trade outcomes and engine state of the learning engines, one store per wallet
+            # (docs/LEARNING_STORE.md); checked read-only so validation changes nothing
+            store_path = getattr(self.config, "learning_store_path", "data/learning_store.db")
+            for wallet in self.wallets:
+                path = wallet_file(store_path, wallet.name)
+                if not Path(path).exists():
+                    logger.info(f"ℹ️ {path} not found (created when the 15-minute strategy starts)")
+                    continue
+                store = verify_store(path)
+                logger.info(f"✅ Verified {path} ({store['trades']} trades, schema version {store['schema_version']})")
 
-            validation_results["learning_data"] = learning_data_ok
+            validation_results["learning_data"] = True
         except Exception as e:
             logger.error(f"❌ Learning data validation failed: {e}")
             validation_results["learning_data"] = False
@@ -1113,7 +1347,8 @@ class MainOrchestrator:
                     # FIX: Re-enabled monitoring alert (send_alert exists in MonitoringSystem)
                     await self.monitoring.send_alert(
                         "warning",
-                        f"Trading halted: Gas price {gas_price_gwei} gwei exceeds limit"
+                        f"Trading halted: Gas price {gas_price_gwei} gwei exceeds limit",
+                        event=GAS_PRICE
                     )
                     self.gas_price_halted = True
                 return False
@@ -1125,7 +1360,8 @@ class MainOrchestrator:
                     # FIX: Re-enabled monitoring alert
                     await self.monitoring.send_alert(
                         "info",
-                        f"Trading resumed: Gas price {gas_price_gwei} gwei"
+                        f"Trading resumed: Gas price {gas_price_gwei} gwei",
+                        event=GAS_PRICE
                     )
                     self.gas_price_halted = False
                 return True
@@ -1344,34 +1580,37 @@ class MainOrchestrator:
             opportunities = []
             
             # ============================================================
-            # ONLY RUN 15-MINUTE CRYPTO STRATEGY (USER REQUIREMENT)
+            # RUN ENABLED STRATEGIES (config.enabled_strategies)
             # ============================================================
-            strategy_tasks = []
-            
-            # ONLY: 15-Minute Crypto Strategy (BTC, ETH, SOL, XRP)
-            if hasattr(self, 'fifteen_min_strategy') and self.fifteen_min_strategy:
-                logger.info("⏱️ Running 15-Minute Crypto Strategy (BTC, ETH, SOL, XRP)...")
-                strategy_tasks.append(self.fifteen_min_strategy.run_cycle())
+            # Each wallet's strategies trade its own bankroll, unless its risk limits pause it
+            books = []
+            for wallet in self.wallets:
+                allowed, reason = wallet.trading_allowed()
+                if not allowed:
+                    logger.warning(f"Wallet {wallet.name} paused by its risk limits: {reason}")
+                    continue
+                books.extend(
+                    (wallet, strategy) for strategy in wallet.strategies
+                    if book_label(wallet.name, strategy.name) not in self.disabled_strategies
+                )
             
-            # Execute strategy
-            if strategy_tasks:
-                logger.debug(f"⚡ Executing 15-minute crypto strategy...")
-                results = await asyncio.gather(*strategy_tasks, return_exceptions=True)
+            if books:
+                logger.info(f"⏱️ Running strategies: {', '.join(book_label(w.name, s.name) for w, s in books)}")
+                results = await asyncio.gather(
+                    *(strategy.run_cycle(markets, wallet.risk_manager.current_capital) for wallet, strategy in books),
+                    return_exceptions=True
+                )
                 
                 # Handle results
-                for i, result in enumerate(results):
+                for (wallet, strategy), result in zip(books, results):
                     if isinstance(result, Exception):
-                        logger.error(f"Strategy {i} failed: {result}")
-                    elif result and isinstance(result, list):
-                        # Strategy returned results
-                        logger.debug(f"Strategy {i} returned {len(result)} results")
-            
-            # ALL OTHER STRATEGIES DISABLED (USER ONLY WANTS 15-MIN CRYPTO)
-            # Internal arbitrage (DISABLED)
-            # Cross-platform arbitrage (DISABLED)
-            # Latency arbitrage (DISABLED)
-            # Resolution farming (DISABLED)
-            # NegRisk arbitrage (DISABLED)
+                        logger.error(f"Strategy {book_label(wallet.name, strategy.name)} failed: {result}")
+                        continue
+                    
+                    logger.debug(f"Strategy {book_label(wallet.name, strategy.name)} returned {len(result)} results")
+                    for trade in result:
+                        trade.wallet = wallet.name
+                        self._record_trade_result(trade)
             
             logger.debug(f"Found {len(opportunities)} total opportunities")
             # Monitoring system doesn't have record_opportunities_found
@@ -1437,19 +1676,7 @@ class MainOrchestrator:
                         logger.warning(f"Unknown strategy: {opp.strategy}")
                         continue
                     
-                    # Record trade
-                    self.trade_history.insert_trade(result)
-                    self.trade_statistics.update(result)
-                    self.monitoring.record_trade(result)
-                    
-                    # Dashboard update - add_trade exists
-                    self.dashboard.add_trade(result)
-                    
-                    # Update circuit breaker
-                    if result.was_successful():
-                        self.circuit_breaker.record_success()
-                    else:
-                        self.circuit_breaker.record_failure()
+                    self._record_trade_result(result)
                     
                 except Exception as e:
                     logger.error(f"Failed to execute opportunity: {e}", exc_info=True)
@@ -1460,6 +1687,109 @@ class MainOrchestrator:
             logger.error(f"Error in scan_and_execute: {e}", exc_info=True)
             self.monitoring.record_error(e, {"operation": "scan_and_execute"})
     
+    async def _get_usdc_balance(self, clob_client: Any = None) -> Decimal:
+        """USDC collateral balance from the CLOB of a wallet, the primary by default (raises if the query fails)."""
+        from py_clob_client.clob_types import BalanceAllowanceParams, AssetType
+        balance_info = (clob_client or self.clob_client).get_balance_allowance(
+            BalanceAllowanceParams(asset_type=AssetType.COLLATERAL)
+        )
+        return Decimal(str(balance_info["balance"])) / Decimal("1000000")
+    
+    async def _reconcile_ledger(self) -> None:
+        """Reconcile every wallet's position ledger with the chain and repair strategy positions."""
+        for wallet in self.wallets:
+            if wallet.ledger_reconciler is None:
+                continue
+            try:
+                fifteen_min = next(
+                    (s.strategy for s in wallet.strategies if isinstance(s, FifteenMinuteCryptoAdapter)), None
+                )
+                tracked = fifteen_min.tracked_shares() if fifteen_min else {}
+                report = await wallet.ledger_reconciler.reconcile(tracked)
+                
+                if report.repaired and fifteen_min:
+                    fifteen_min.apply_reconciliation(report)
+                
+                if report.has_drift:
+                    await self.monitoring.send_alert(
+                        "warning",
+                        f"Position ledger drift on wallet {wallet.name}: {len(report.drifts)} token(s), "
+                        f"{len(report.orphans)} orphan(s), cash {report.cash_drift:+.2f}",
+                        {
+                            "wallet": wallet.name,
+                            "drifts": [(d.token_id, d.kind, str(d.ledger_shares), str(d.chain_shares)) for d in report.drifts],
+                            "orphans": report.orphans,
+                            "repaired": report.repaired
+                        },
+                        event=LEDGER_DRIFT
+                    )
+            except Exception as e:
+                logger.error(f"Ledger reconciliation error on wallet {wallet.name}: {e}")
+    
+    async def _resume_pending_transactions(self) -> None:
+        """Reconcile the transaction journal with the chain and keep waiting for what is still pending."""
+        for wallet in self.wallets:
+            try:
+                resumed = await wallet.transaction_manager.recover_from_journal()
+            except Exception as e:
+                logger.error(f"Transaction journal recovery failed for wallet {wallet.name}: {e}")
+                continue
+            
+            for tx_hash in resumed:
+                asyncio.create_task(self._await_resumed_transaction(wallet.transaction_manager, tx_hash))
+            if resumed:
+                logger.info(f"📓 Resumed {len(resumed)} pending transaction(s) of wallet {wallet.name} from the journal")
+    
+    async def _await_resumed_transaction(self, transaction_manager: TransactionManager, tx_hash: str) -> None:
+        try:
+            receipt = await transaction_manager.wait_for_confirmation(tx_hash)
+            logger.info(f"📓 Resumed transaction confirmed in block {receipt.get('blockNumber')}: {tx_hash}")
+        except Exception as e:
+            logger.warning(f"📓 Resumed transaction {tx_hash} not confirmed: {e}")
+    
+    async def _redeem_resolved_positions(self) -> None:
+        """Redeem resolved positions of every wallet and alert on failed redemptions."""
+        for wallet in self.wallets:
+            if wallet.redemption_service is None:
+                continue
+            try:
+                results = await wallet.redemption_service.redeem_all()
+                failed = [r for r in results if not r.success and not self.config.dry_run]
+                if failed:
+                    await self.monitoring.send_alert(
+                        "warning",
+                        f"{len(failed)} redemption(s) failed (wallet {wallet.name})",
+                        {"conditions": [(r.condition_id, r.error) for r in failed], "wallet": wallet.name},
+                        event=REDEMPTION
+                    )
+            except Exception as e:
+                logger.error(f"Redemption error (wallet {wallet.name}): {e}")
+    
+    def _record_trade_result(self, result: TradeResult) -> None:
+        """Record a trade in history, statistics, monitoring, the circuit breaker and its wallet's risk manager."""
+        try:
+            self.trade_history.insert_trade(result)
+            self.trade_statistics.update(result)
+            self.monitoring.record_trade(result)
+            
+            # Dashboard update - add_trade exists
+            self.dashboard.add_trade(result)
+            # Fill alerts come from the position ledger listener (_on_ledger_fill)
+        except Exception as e:
+            logger.error(f"Failed to record trade {result.trade_id}: {e}")
+        
+        # Update circuit breaker
+        if result.was_successful():
+            self.circuit_breaker.record_success()
+        else:
+            self.circuit_breaker.record_failure()
+        
+        # Book the P&L against the limits of the wallet that traded
+        try:
+            self.wallets.record_trade(result)
+        except Exception as e:
+            logger.error(f"Failed to book trade {result.trade_id} on wallet {result.wallet}: {e}")
+    
     async def run(self) -> None:
         """
         Main event loop.
@@ -1489,6 +1819,8 @@ class MainOrchestrator:
         logger.info(f"Min profit threshold: {self.config.min_profit_threshold * 100}%")
         logger.info("=" * 80)
         
+        await self._open_sub_accounts()
+        
         # TASK 14.3: Comprehensive startup validation (Requirement 11.8)
         logger.info("\n🔍 Running comprehensive startup validation...")
         validation_passed = await self.startup_validation()
@@ -1500,6 +1832,9 @@ class MainOrchestrator:
         
         logger.info("\n✅ Startup validation complete - proceeding with trading\n")
         
+        # Pick up transactions that were in flight when the bot last stopped
+        await self._resume_pending_transactions()
+        
         # AUTONOMOUS OPERATION: Check for funds (skip slow bridge)
         logger.info("\n[AUTO] AUTONOMOUS MODE: Checking for funds...")
         try:
@@ -1628,15 +1963,23 @@ class MainOrchestrator:
             logger.error(f"Balance check failed: {e}")
             logger.warning("Proceeding anyway - will check balance during operation")
         
-        # Start dashboard is not needed - dashboard is passive
+        # Web dashboard and control API
+        if self.web_dashboard:
+            try:
+                await self.web_dashboard.start()
+            except Exception as e:
+                logger.error(f"Failed to start web dashboard: {e}")
         
         # Perform initial heartbeat
         await self.heartbeat_check()
         
+        # Shared Binance feed behind the strategies and the fair-value model
+        await self.price_feed.start()
+        
         # Start strategies that require initialization (Requirements 5.4, 5.5)
-        if hasattr(self, 'fifteen_min_strategy') and self.fifteen_min_strategy:
-            logger.info("Starting 15-Minute Crypto Strategy price feed...")
-            await self.fifteen_min_strategy.start()
+        for strategy in self.wallets.all_strategies():
+            logger.info(f"Starting strategy: {strategy.name}...")
+            await strategy.start()
         
         # TASK 14.4: Start automatic log management (Requirements 11.5, 11.12)
         logger.info("Starting automatic log management...")
@@ -1650,12 +1993,14 @@ class MainOrchestrator:
                 # Check gas price
                 gas_ok = await self._check_gas_price()
                 
-                if gas_ok and not self.circuit_breaker.is_open:
+                if gas_ok and not self.circuit_breaker.is_open and not self.trading_paused:
                     # Scan and execute
                     await self._scan_and_execute()
                     self.scan_count += 1
                 else:
-                    if self.circuit_breaker.is_open:
+                    if self.trading_paused:
+                        logger.debug(f"Trading paused: {self.pause_reason}")
+                    elif self.circuit_breaker.is_open:
                         logger.warning("Circuit breaker is open, trading halted")
                     else:
                         logger.debug("Gas price too high, waiting...")
@@ -1676,6 +2021,15 @@ class MainOrchestrator:
                     if not health_status.is_healthy:
                         logger.warning(f"System unhealthy: {health_status.issues}")
                 
+                # Position PnL, feed staleness and risk state gauges (docs/METRICS.md)
+                self._update_strategy_metrics()
+                
+                # Apply edits of the YAML config file
+                if (self.config_reloader is not None and
+                        time.time() - self.last_config_check >= self.config.config_reload_interval_seconds):
+                    self.config_reloader.check()
+                    self.last_config_check = time.time()
+                
                 # Fund management check (every 60 seconds)
                 if time.time() - self.last_fund_check >= 60:
                     try:
@@ -1684,6 +2038,18 @@ class MainOrchestrator:
                         logger.error(f"Fund management error: {e}")
                     self.last_fund_check = time.time()
                 
+                # Ledger reconciliation against on-chain balances
+                if (any(wallet.ledger_reconciler is not None for wallet in self.wallets) and
+                        time.time() - self.last_ledger_reconcile >= self.config.ledger_reconcile_interval_seconds):
+                    await self._reconcile_ledger()
+                    self.last_ledger_reconcile = time.time()
+                
+                # Redeem resolved positions for USDC
+                if (any(wallet.redemption_service is not None for wallet in self.wallets) and
+                        time.time() - self.last_redemption >= self.config.redemption_interval_seconds):
+                    await self._redeem_resolved_positions()
+                    self.last_redemption = time.time()
+                
                 # Save state (every 60 seconds)
                 if time.time() - self.last_state_save >= 60:
                     self._save_state()
@@ -1769,7 +2135,11 @@ class MainOrchestrator:
         self.running = False
         self.shutdown_requested = True
         
-        # Dashboard is passive - no need to stop
+        if self.web_dashboard:
+            try:
+                await self.web_dashboard.stop()
+            except Exception as e:
+                logger.error(f"Failed to stop web dashboard: {e}")
         
         # Wait for pending transactions
         logger.info("Waiting for pending transactions...")
@@ -1794,8 +2164,15 @@ class MainOrchestrator:
         logger.info("Closing connections...")
         
         # Stop strategies
-        if hasattr(self, 'fifteen_min_strategy') and self.fifteen_min_strategy:
-            await self.fifteen_min_strategy.stop()
+        for strategy in self.wallets.all_strategies():
+            try:
+                await strategy.stop()
+            except Exception as e:
+                logger.error(f"Failed to stop strategy {strategy.name}: {e}")
+        await self.price_feed.stop()
+        
+        if self.market_data_recorder:
+            self.market_data_recorder.close()
         
         # Web3 connections are stateless, no need to close
         
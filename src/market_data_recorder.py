@@ -0,0 +1,322 @@
+"""
+Market data recorder for later replay.
+
+Writes every Polymarket book snapshot, book delta and trade, Binance tick and
+market definition seen by the live feeds to rotating gzip-compressed JSON-lines
+files, stamped with the local receive time. The files stream straight back
+into the replay backtester (src/backtest_replay.py). The record schema is
+documented in docs/MARKET_DATA_RECORDING.md.
+
+Validates Requirements:
+- Capture book snapshots, deltas, trades and CEX ticks with receive timestamps
+- Rotate output files by age and size, compressed on disk
+- Produce a documented schema the backtest loader can stream back
+"""
+
+import gzip
+import json
+import logging
+from datetime import datetime, timezone
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+SCHEMA_VERSION = 1
+
+RECORD_MARKET = "market"
+RECORD_BOOK = "book"
+RECORD_BOOK_DELTA = "book_delta"
+RECORD_TRADE = "trade"
+RECORD_BINANCE_TICK = "binance_tick"
+RECORD_RESOLUTION = "resolution"
+
+
+def _level(level: Any) -> List[str]:
+    """Normalize a book level (dict, object or pair) to [price, size] strings."""
+    if isinstance(level, dict):
+        price, size = level.get("price"), level.get("size")
+    elif isinstance(level, (list, tuple)):
+        price, size = level[0], level[1]
+    else:
+        price, size = getattr(level, "price", None), getattr(level, "size", None)
+    return [str(price), str(size)]
+
+
+def _side(side: Any) -> str:
+    """Normalize BUY/SELL/bid/ask side labels to "bid" or "ask"."""
+    return "bid" if str(side).lower() in ("bid", "bids", "buy") else "ask"
+
+
+class MarketDataRecorder:
+    """
+    Append-only recorder for live market data.
+
+    Features:
+    - One JSON object per line with receive timestamp ("ts") and "type"
+    - Receive timestamps never go backwards within a recording
+    - Files rotate every rotate_interval_seconds or max_file_bytes (uncompressed)
+    - Active market definitions are repeated at the top of each new file, so
+      every file can be replayed on its own
+    - Periodic gzip sync flushes bound data loss on a crash
+    """
+
+    def __init__(
+        self,
+        output_dir: str = "data/recordings",
+        rotate_interval_seconds: int = 3600,
+        max_file_bytes: int = 256 * 1024 * 1024,
+        flush_interval_seconds: float = 5.0,
+        prefix: str = "market_data",
+    ):
+        """
+        Initialize the recorder.
+
+        Args:
+            output_dir: Directory for recording files (created if missing)
+            rotate_interval_seconds: Start a new file after this many seconds
+            max_file_bytes: Start a new file after this many uncompressed bytes
+            flush_interval_seconds: Sync-flush the gzip stream this often
+            prefix: File name prefix
+        """
+        if rotate_interval_seconds <= 0:
+            raise ValueError(f"rotate_interval_seconds must be positive, got: {rotate_interval_seconds}")
+        if max_file_bytes <= 0:
+            raise ValueError(f"max_file_bytes must be positive, got: {max_file_bytes}")
+
+        self.output_dir = Path(output_dir)
+        self.rotate_interval_seconds = rotate_interval_seconds
+        self.max_file_bytes = max_file_bytes
+        self.flush_interval_seconds = flush_interval_seconds
+        self.prefix = prefix
+
+        self._file = None
+        self._path: Optional[Path] = None
+        self._opened_at: Optional[datetime] = None
+        self._last_flush: Optional[datetime] = None
+        self._bytes_written = 0
+        self._last_ts: Optional[datetime] = None
+
+        # Market definitions keyed by market_id, replayed into each new file
+        self._markets: Dict[str, Dict[str, Any]] = {}
+        # End times of recorded markets still waiting for a resolution record
+        self._unresolved: Dict[str, datetime] = {}
+
+        # Statistics
+        self.records_written = 0
+        self.files_written: List[Path] = []
+
+        logger.info(f"Market data recorder initialized (output: {self.output_dir})")
+
+    # ------------------------------------------------------------------
+    # Record types
+    # ------------------------------------------------------------------
+
+    def record_market(self, market: Any) -> None:
+        """
+        Record a market definition (CryptoMarket or equivalent).
+
+        Each market is written once; repeated calls are ignored. Rotated files
+        start with the definitions of markets that are still open.
+        """
+        market_id = market.market_id
+        if market_id in self._markets:
+            return
+        fields = {
+            "market_id": market_id,
+            "question": market.question,
+            "asset": market.asset,
+            "up_token_id": str(market.up_token_id),
+            "down_token_id": str(market.down_token_id),
+            "end_time": market.end_time.astimezone(timezone.utc).isoformat(),
+            "neg_risk": bool(getattr(market, "neg_risk", True)),
+            "tick_size": str(getattr(market, "tick_size", "0.01")),
+        }
+        self._write(RECORD_MARKET, fields)
+        self._markets[market_id] = fields
+        self._unresolved[market_id] = datetime.fromisoformat(fields["end_time"])
+
+    def record_markets(self, markets: Iterable[Any]) -> None:
+        """Record several market definitions."""
+        for market in markets:
+            self.record_market(market)
+
+    def record_book(
+        self,
+        token_id: str,
+        bids: Iterable[Any],
+        asks: Iterable[Any],
+        source: str,
+        exchange_ts: Optional[Any] = None,
+        id_field: str = "token_id",
+    ) -> None:
+        """
+        Record a full order book snapshot for a token.
+
+        Sources keyed by market rather than token pass id_field="market_id".
+        """
+        self._write(RECORD_BOOK, {
+            id_field: str(token_id),
+            "bids": [_level(level) for level in bids],
+            "asks": [_level(level) for level in asks],
+            "source": source,
+        }, exchange_ts)
+
+    def record_book_delta(
+        self,
+        token_id: str,
+        changes: Iterable[Any],
+        source: str,
+        exchange_ts: Optional[Any] = None,
+        id_field: str = "token_id",
+    ) -> None:
+        """
+        Record incremental book changes for a token.
+
+        Each change is a dict with side/price/size; size 0 removes the level.
+        id_field works as in record_book().
+        """
+        normalized = [
+            [_side(change.get("side")), str(change.get("price")), str(change.get("size"))]
+            for change in changes
+        ]
+        if not normalized:
+            return
+        self._write(RECORD_BOOK_DELTA, {
+            id_field: str(token_id),
+            "changes": normalized,
+            "source": source,
+        }, exchange_ts)
+
+    def record_trade(
+        self,
+        token_id: str,
+        price: Any,
+        source: str,
+        size: Optional[Any] = None,
+        side: Optional[str] = None,
+        exchange_ts: Optional[Any] = None,
+        id_field: str = "token_id",
+    ) -> None:
+        """Record a Polymarket trade (or last-trade-price update); id_field works as in record_book()."""
+        fields = {id_field: str(token_id), "price": str(price), "source": source}
+        if size is not None:
+            fields["size"] = str(size)
+        if side is not None:
+            fields["side"] = str(side).upper()
+        self._write(RECORD_TRADE, fields, exchange_ts)
+
+    def record_binance_tick(
+        self,
+        asset: str,
+        price: Decimal,
+        volume: Decimal = Decimal("0"),
+        exchange_ts: Optional[Any] = None,
+    ) -> None:
+        """Record a Binance trade tick."""
+        self._write(RECORD_BINANCE_TICK, {
+            "asset": asset,
+            "price": str(price),
+            "volume": str(volume),
+        }, exchange_ts)
+
+    def record_resolution(self, market_id: str, winner: str) -> None:
+        """Record the winning side ("UP" or "DOWN") of a resolved market."""
+        self._write(RECORD_RESOLUTION, {"market_id": market_id, "winner": winner.upper()})
+        self._markets.pop(market_id, None)
+        self._unresolved.pop(market_id, None)
+
+    def awaiting_resolution(self, now: Optional[datetime] = None) -> List[str]:
+        """IDs of recorded markets that have ended but have no resolution record yet."""
+        now = now or datetime.now(timezone.utc)
+        return [market_id for market_id, end_time in self._unresolved.items() if end_time <= now]
+
+    # ------------------------------------------------------------------
+    # File management
+    # ------------------------------------------------------------------
+
+    @property
+    def current_path(self) -> Optional[Path]:
+        """Path of the file currently being written, if any."""
+        return self._path
+
+    def rotate(self) -> None:
+        """Close the current file; the next record opens a new one."""
+        if self._file is None:
+            return
+        self._file.close()
+        logger.info(f"📼 Closed recording {self._path.name} ({self._bytes_written / 1024:.0f} KB raw)")
+        self._file = None
+        self._path = None
+
+    def close(self) -> None:
+        """Flush and close the current file."""
+        self.rotate()
+
+    def _open(self, now: datetime) -> None:
+        self.output_dir.mkdir(parents=True, exist_ok=True)
+        name = f"{self.prefix}_{now.strftime('%Y%m%dT%H%M%S%fZ')}.jsonl.gz"
+        self._path = self.output_dir / name
+        self._file = gzip.open(self._path, "wt", encoding="utf-8")
+        self._opened_at = now
+        self._last_flush = now
+        self._bytes_written = 0
+        self.files_written.append(self._path)
+        logger.info(f"📼 Recording market data to {self._path}")
+
+        # Make each file self-contained for replay: repeat live market definitions
+        for market_id, fields in list(self._markets.items()):
+            if datetime.fromisoformat(fields["end_time"]) <= now:
+                del self._markets[market_id]
+                continue
+            self._write_line(now, RECORD_MARKET, fields, None)
+
+    def _needs_rotation(self, now: datetime) -> bool:
+        if self._file is None:
+            return False
+        age = (now - self._opened_at).total_seconds()
+        return age >= self.rotate_interval_seconds or self._bytes_written >= self.max_file_bytes
+
+    def _receive_time(self) -> datetime:
+        now = datetime.now(timezone.utc)
+        if self._last_ts is not None and now < self._last_ts:
+            now = self._last_ts
+        self._last_ts = now
+        return now
+
+    def _write(self, record_type: str, fields: Dict[str, Any], exchange_ts: Optional[Any] = None) -> None:
+        try:
+            now = self._receive_time()
+            if self._needs_rotation(now):
+                self.rotate()
+            if self._file is None:
+                self._open(now)
+            self._write_line(now, record_type, fields, exchange_ts)
+
+            if (now - self._last_flush).total_seconds() >= self.flush_interval_seconds:
+                self._file.flush()
+                self._last_flush = now
+        except Exception as e:
+            # Recording must never take down the feed that called it
+            logger.error(f"❌ Failed to record {record_type}: {e}")
+
+    def _write_line(self, now: datetime, record_type: str, fields: Dict[str, Any], exchange_ts: Optional[Any]) -> None:
+        record = {"ts": now.isoformat(), "type": record_type, "v": SCHEMA_VERSION, **fields}
+        if exchange_ts is not None:
+            record["exchange_ts"] = exchange_ts
+        line = json.dumps(record, separators=(",", ":")) + "\n"
+        self._file.write(line)
+        self._bytes_written += len(line)
+        self.records_written += 1
+
+    def get_statistics(self) -> Dict[str, Any]:
+        """Recorder statistics for monitoring."""
+        return {
+            "records_written": self.records_written,
+            "files_written": len(self.files_written),
+            "current_file": str(self._path) if self._path else None,
+            "tracked_markets": len(self._markets),
+            "awaiting_resolution": len(self._unresolved),
+        }
@@ -0,0 +1,711 @@
+"""
+Market-Making Strategy for Polymarket 15-minute crypto up/down markets.
+
+Quotes both sides of every current up/down market with post-only maker
+orders. UP + DOWN always pays $1, so selling UP is the same trade as buying
+DOWN: the strategy only ever posts bids, one on the UP token and one on the
+DOWN token. Each UP/DOWN pair that fills locks in the quoted spread
+(e.g. UP @ $0.48 + DOWN @ $0.48 pays $1.00 at resolution for $0.96).
+
+Quotes are centred on a fair UP probability derived from the Binance spot
+price rather than on the Polymarket book, skewed against net inventory so
+the book leans back towards flat, and pulled near expiry, on a flash crash
+or when the CEX feed goes stale.
+
+Validates Requirements:
+- Two-sided post-only quoting on UP/DOWN tokens around a CEX-derived fair value
+- Inventory skew and per-market net inventory limits across UP/DOWN
+- Exposure limits through PortfolioRiskManager
+- Quotes pulled near expiry, on flash-crash detection and on a stale CEX feed
+- Inventory of finished markets released from risk limits and handed to redemption
+- Flatten: quotes pulled and inventory sold into the best bids
+"""
+
+import logging
+import time
+from dataclasses import dataclass, replace
+from datetime import datetime, timezone
+from decimal import Decimal, ROUND_FLOOR
+from types import SimpleNamespace
+from typing import Any, Awaitable, Callable, Dict, List, Optional, Tuple
+
+from py_clob_client.clob_types import OrderArgs, OrderType
+from py_clob_client.order_builder.constants import SELL
+
+from src.fifteen_min_crypto_strategy import (
+    BinancePriceFeed,
+    CryptoMarket,
+    fetch_updown_markets,
+    updown_slugs,
+)
+from src.fair_value_pricer import FairValuePricer
+from src.flash_crash_detector import FlashCrashDetector
+from src.order_book_analyzer import OrderBookAnalyzer, OrderBookDepth
+from src.order_manager import TERMINAL_ORDER_STATUSES, Order, OrderManager
+from src.portfolio_risk_manager import PortfolioRiskManager
+from src.position_ledger import PositionLedger
+
+logger = logging.getLogger(__name__)
+
+@dataclass
+class MarketInventory:
+    """UP/DOWN shares bought by the market maker in one market."""
+    market: CryptoMarket
+    up_shares: Decimal = Decimal("0")
+    down_shares: Decimal = Decimal("0")
+    cost: Decimal = Decimal("0")  # USDC spent on both tokens
+    mark: Optional[Decimal] = None  # Last UP book mid, for unrealized PnL
+
+    @property
+    def net(self) -> Decimal:
+        """Directional exposure in shares (positive = long UP)."""
+        return self.up_shares - self.down_shares
+
+    @property
+    def paired(self) -> Decimal:
+        """UP/DOWN pairs held; each pays exactly $1 at resolution."""
+        return min(self.up_shares, self.down_shares)
+
+    @property
+    def unrealized_pnl(self) -> Optional[Decimal]:
+        """Inventory value at the last UP mid (DOWN at 1 - mid) less its cost."""
+        if self.mark is None:
+            return None
+        return self.up_shares * self.mark + self.down_shares * (Decimal("1") - self.mark) - self.cost
+
+
+@dataclass
+class Quote:
+    """A resting bid on one side of a market."""
+    order: Order
+    market: CryptoMarket
+    side: str  # "UP" or "DOWN"
+    tracked_size: Decimal = Decimal("0")  # Matched size already added to inventory
+
+
+class MarketMakingStrategy:
+    """
+    Two-sided market maker for 15-minute (and 1-hour) up/down markets.
+
+    Features:
+    - Fair UP probability from FairValuePricer (Binance spot, realized volatility, time to close)
+    - Bids on UP at fair - half_spread - skew and on DOWN at (1 - fair) - half_spread + skew
+    - Skew proportional to net inventory; the side that adds to it stops at max_inventory
+    - Post-only GTD quotes that expire by the pull time even if the bot stops
+    - Requotes only when the target moves by at least one tick
+    - New quotes gated by PortfolioRiskManager.check_can_trade()
+    - Pulls quotes near close, on FlashCrashDetector crashes (with a cooldown)
+      and when the CEX price is stale
+    """
+
+    def __init__(
+        self,
+        clob_client,
+        order_manager: OrderManager,
+        price_feed: Optional[BinancePriceFeed] = None,
+        risk_manager: Optional[PortfolioRiskManager] = None,
+        fair_value_pricer: Optional[FairValuePricer] = None,
+        initial_capital: float = 100.0,
+        half_spread: float = 0.02,
+        quote_size: float = 10.0,
+        max_inventory: float = 50.0,
+        max_skew: Optional[float] = None,
+        pull_minutes: float = 2.0,
+        crash_cooldown_seconds: float = 60.0,
+        max_feed_age_seconds: float = 10.0,
+        dry_run: bool = False,
+        market_source: Optional[Callable[[], Awaitable[List[CryptoMarket]]]] = None,
+        ledger: Optional[PositionLedger] = None,
+        redemption_service: Optional[Any] = None
+    ):
+        """
+        Initialize the market maker.
+
+        Args:
+            clob_client: Authenticated CLOB client (order books)
+            order_manager: OrderManager used for resting orders
+            price_feed: Shared BinancePriceFeed (one is created and owned if None)
+            risk_manager: Shared PortfolioRiskManager (one is created if None)
+            fair_value_pricer: Shared FairValuePricer (one on price_feed is created if None)
+            initial_capital: Capital for the risk manager created here
+            half_spread: Distance of each bid from the fair price
+            quote_size: Shares per quote
+            max_inventory: Max net UP-DOWN shares per market
+            max_skew: Price skew at max_inventory (default: half_spread)
+            pull_minutes: Pull all quotes this many minutes before close
+            crash_cooldown_seconds: Stay out of a market this long after a flash crash
+            max_feed_age_seconds: Treat the CEX price as stale after this long
+            dry_run: Log quotes without posting them
+            market_source: Async callable returning the markets to quote
+                (default: current up/down markets from the Gamma API)
+            ledger: PositionLedger that records every fill (optional)
+            redemption_service: RedemptionService that redeems the inventory of
+                finished markets (optional)
+        """
+        self.clob_client = clob_client
+        self.order_manager = order_manager
+        self.owns_feed = price_feed is None
+        self.price_feed = price_feed or BinancePriceFeed()
+        self.risk_manager = risk_manager or PortfolioRiskManager(
+            initial_capital=Decimal(str(initial_capital))
+        )
+        self.fair_value_pricer = fair_value_pricer or FairValuePricer(price_feed=self.price_feed)
+        self.order_book_analyzer = OrderBookAnalyzer(clob_client)
+        self.flash_detector = FlashCrashDetector()
+
+        self.half_spread = Decimal(str(half_spread))
+        self.quote_size = Decimal(str(quote_size))
+        self.max_inventory = Decimal(str(max_inventory))
+        self.max_skew = Decimal(str(max_skew)) if max_skew is not None else self.half_spread
+        self.pull_minutes = pull_minutes
+        self.crash_cooldown_seconds = crash_cooldown_seconds
+        self.max_feed_age_seconds = max_feed_age_seconds
+        self.dry_run = dry_run
+        self.market_source = market_source or self._fetch_markets
+        self.ledger = ledger
+        self.redemption_service = redemption_service
+
+        # State
+        self.markets: Dict[str, CryptoMarket] = {}  # market_id -> last seen market
+        self.inventory: Dict[str, MarketInventory] = {}  # market_id -> inventory
+        self.quotes: Dict[str, Quote] = {}  # token_id -> working quote
+        self._paused_until: Dict[str, float] = {}  # market_id -> epoch seconds
+
+        self.stats = {
+            "quotes_posted": 0,
+            "requotes": 0,
+            "quotes_pulled": 0,
+            "fills": 0,
+            "flash_crash_pulls": 0,
+            "markets_retired": 0,
+            "inventory_sold": 0,
+        }
+
+        logger.info(
+            f"🏦 Market Making Strategy initialized: half_spread=${self.half_spread}, "
+            f"size={self.quote_size}, max_inventory={self.max_inventory}, "
+            f"pull {pull_minutes}min before close, dry_run={dry_run}"
+        )
+
+    # ============================================================
+    # LIFECYCLE
+    # ============================================================
+
+    async def start(self) -> None:
+        """Start the Binance feed if this strategy owns it."""
+        if self.owns_feed:
+            await self.price_feed.start()
+
+    async def stop(self) -> None:
+        """Pull every quote and stop an owned Binance feed."""
+        await self.pull_all_quotes(reason="strategy stopped")
+        if self.owns_feed:
+            await self.price_feed.stop()
+
+    async def pull_all_quotes(self, reason: str) -> int:
+        """
+        Cancel every working quote; returns how many were pulled.
+
+        Filled inventory is kept: paired UP/DOWN shares are worth $1 at
+        resolution and are redeemed like any other position.
+        """
+        pulled = len(self.quotes)
+        for market in list(self.markets.values()):
+            await self._pull_quotes(market, reason=reason)
+        return pulled - len(self.quotes)
+
+    async def close_inventory(self, reason: str = "flatten") -> int:
+        """
+        Pull every quote and sell all inventory at the best bid; returns the
+        number of markets left without inventory.
+
+        Shares that do not sell (no bid, partial fill, rejected order) stay in
+        inventory and keep counting as exposure.
+        """
+        await self.pull_all_quotes(reason=reason)
+        if self.dry_run:
+            return 0
+
+        closed = 0
+        for market_id, inventory in list(self.inventory.items()):
+            if inventory.up_shares <= 0 and inventory.down_shares <= 0:
+                continue
+            for side in ("UP", "DOWN"):
+                await self._sell_inventory(inventory, side, reason=reason)
+            if inventory.up_shares <= 0 and inventory.down_shares <= 0:
+                del self.inventory[market_id]
+                closed += 1
+        return closed
+
+    async def _fetch_markets(self) -> List[CryptoMarket]:
+        """Current 15-minute and 1-hour up/down markets from the Gamma API."""
+        return await fetch_updown_markets(updown_slugs(int(time.time())))
+
+    async def run_cycle(self) -> None:
+        """Pick up fills, then requote (or pull) every current market."""
+        try:
+            markets = await self.market_source()
+        except Exception as e:
+            logger.warning(f"⚠️ Market maker could not fetch markets: {e}")
+            await self._sync_fills()
+            return
+
+        await self._sync_fills()
+
+        current_ids = {m.market_id for m in markets}
+        for market_id, market in list(self.markets.items()):
+            if market_id not in current_ids:
+                await self._pull_quotes(market, reason="market no longer listed")
+                if market.up_token_id in self.quotes or market.down_token_id in self.quotes:
+                    continue  # A cancel failed; retried next cycle
+                self._retire_market(market)
+
+        for market in markets:
+            try:
+                await self._quote_market(market)
+            except Exception as e:
+                logger.error(f"❌ Market maker failed on {market.asset} {market.market_id[:16]}...: {e}")
+
+    # ============================================================
+    # FAIR VALUE
+    # ============================================================
+
+    def fair_up_probability(self, market: CryptoMarket, book_mid: Decimal) -> Optional[Decimal]:
+        """
+        Probability that the asset finishes the window up, from the CEX price.
+
+        Up/down markets resolve against the price at the window open, which
+        the market question does not state. The shared FairValuePricer implies
+        the strike once, on first sight, from the Polymarket mid and the
+        Binance spot (FairValuePricer.price_up_down); from then on the fair
+        value follows Binance.
+
+        Returns:
+            Fair UP probability in [0.01, 0.99], or None without a fresh CEX price
+        """
+        spot = self.price_feed.prices.get(market.asset)
+        if not spot or not self._feed_is_fresh(market.asset):
+            return None
+
+        fair = self.fair_value_pricer.price_up_down(
+            market.market_id, market.asset, market.end_time, book_mid, spot=spot
+        )
+        if fair is None:
+            return None
+        return min(max(fair.probability, Decimal("0.01")), Decimal("0.99"))
+
+    def _feed_is_fresh(self, asset: str) -> bool:
+        """True if the last Binance tick for the asset is recent."""
+        history = self.price_feed.price_history.get(asset)
+        if not history:
+            return False
+        age = (datetime.now() - history[-1][0]).total_seconds()
+        return age <= self.max_feed_age_seconds
+
+    # ============================================================
+    # QUOTING
+    # ============================================================
+
+    def quote_prices(self, fair_up: Decimal, net_inventory: Decimal) -> Tuple[Decimal, Decimal]:
+        """
+        Target UP and DOWN bids for a fair UP probability and net inventory.
+
+        Long UP lowers both the UP bid and the implied UP offer (1 - DOWN bid),
+        so buying more UP gets less likely and buying DOWN more likely.
+
+        Returns:
+            (up_bid, down_bid) before tick rounding; they always sum to 1 - 2 * half_spread
+        """
+        ratio = max(min(net_inventory / self.max_inventory, Decimal("1")), Decimal("-1"))
+        skew = self.max_skew * ratio
+        up_bid = fair_up - self.half_spread - skew
+        down_bid = (Decimal("1") - fair_up) - self.half_spread + skew
+        return up_bid, down_bid
+
+    async def _quote_market(self, market: CryptoMarket) -> None:
+        """Refresh both quotes of one market, or pull them."""
+        self.markets[market.market_id] = market
+
+        minutes_left = (market.end_time - datetime.now(timezone.utc)).total_seconds() / 60.0
+        if minutes_left <= self.pull_minutes:
+            await self._pull_quotes(market, reason=f"market closes in {minutes_left:.1f}min")
+            return
+
+        up_book = await self.order_book_analyzer.get_order_book(market.up_token_id, force_refresh=True)
+        down_book = await self.order_book_analyzer.get_order_book(market.down_token_id, force_refresh=True)
+        if up_book is None or down_book is None:
+            await self._pull_quotes(market, reason="order book unavailable")
+            return
+
+        crash = self.flash_detector.update_price(market.market_id, up_book.mid_price, down_book.mid_price)
+        if crash is not None:
+            self._paused_until[market.market_id] = time.time() + self.crash_cooldown_seconds
+            self.stats["flash_crash_pulls"] += 1
+            await self._pull_quotes(market, reason=f"flash crash on {crash.side} ({crash.crash_pct*100:.1f}%)")
+            return
+        if time.time() < self._paused_until.get(market.market_id, 0):
+            return
+
+        fair_up = self.fair_up_probability(market, up_book.mid_price)
+        if fair_up is None:
+            await self._pull_quotes(market, reason="no fresh CEX price")
+            return
+
+        inventory = self.inventory.setdefault(market.market_id, MarketInventory(market=market))
+        inventory.mark = up_book.mid_price
+        up_bid, down_bid = self.quote_prices(fair_up, inventory.net)
+
+        # Stop adding to a side once net inventory reaches the limit
+        if inventory.net >= self.max_inventory:
+            up_bid = None
+        if inventory.net <= -self.max_inventory:
+            down_bid = None
+
+        logger.debug(
+            f"🏦 {market.asset} fair UP={fair_up} net={inventory.net} "
+            f"-> UP bid {up_bid}, DOWN bid {down_bid}"
+        )
+        # One side failing must not leave the other side's quote stale
+        for side, target, book in (("UP", up_bid, up_book), ("DOWN", down_bid, down_book)):
+            try:
+                await self._update_quote(market, side, target, book)
+            except Exception as e:
+                logger.error(f"❌ Failed to update {market.asset} {side} quote: {e}")
+
+    async def _update_quote(
+        self,
+        market: CryptoMarket,
+        side: str,
+        target: Optional[Decimal],
+        book: OrderBookDepth
+    ) -> None:
+        """Post, requote or cancel the bid on one side so it matches the target."""
+        token_id = market.up_token_id if side == "UP" else market.down_token_id
+        tick = Decimal(str(market.tick_size or "0.01"))
+
+        if target is not None:
+            # Post-only: stay at least one tick under the best ask (an empty ask side cannot cross)
+            if book.asks:
+                target = min(target, min(level.price for level in book.asks) - tick)
+            target = (target / tick).to_integral_value(rounding=ROUND_FLOOR) * tick
+            if target < tick:
+                target = None
+
+        quote = self.quotes.get(token_id)
+        if target is None:
+            if quote is not None:
+                await self._cancel_quote(token_id, reason=f"no {side} quote at current inventory/prices")
+            return
+
+        if quote is not None:
+            if abs(quote.order.price - target) < tick:
+                return
+            try:
+                working = await self.order_manager.cancel_replace(
+                    quote.order.order_id, target, size=self.quote_size
+                )
+            except Exception as e:
+                logger.warning(f"⚠️ Requote failed for {market.asset} {side}: {e}")
+                return
+            # The cancel may have picked up last-moment fills on the old order
+            self._apply_fills(quote)
+            if working is not None:
+                self.quotes[token_id] = replace(quote, order=working, tracked_size=Decimal("0"))
+                self.stats["requotes"] += 1
+            elif quote.order.status in TERMINAL_ORDER_STATUSES:
+                del self.quotes[token_id]
+            return
+
+        metrics = self.risk_manager.check_can_trade(target * self.quote_size, market.market_id)
+        if not metrics.can_trade:
+            logger.debug(f"⏭️ No {market.asset} {side} quote: {metrics.reason}")
+            return
+
+        if self.dry_run:
+            logger.info(f"[DRY RUN] Would quote {market.asset} {side} {self.quote_size} @ ${target}")
+            return
+
+        seconds_left = (market.end_time - datetime.now(timezone.utc)).total_seconds()
+        ttl = int(seconds_left - self.pull_minutes * 60)
+        if ttl <= 0:
+            return
+
+        try:
+            order = self.order_manager.create_limit_order(
+                market_id=token_id,
+                side="YES" if side == "UP" else "NO",
+                price=target,
+                size=self.quote_size,
+                order_type="GTD",
+                ttl_seconds=ttl,
+                post_only=True,
+                neg_risk=market.neg_risk,
+                tick_size=str(tick)
+            )
+            accepted = await self.order_manager.post_limit_order(order)
+        except Exception as e:
+            logger.error(f"❌ Quote failed for {market.asset} {side}: {e}")
+            return
+
+        if not accepted:
+            logger.warning(f"⚠️ Quote rejected for {market.asset} {side}: {order.error_message}")
+            return
+
+        quote = Quote(order=order, market=market, side=side)
+        self.quotes[token_id] = quote
+        self.stats["quotes_posted"] += 1
+        logger.info(f"📌 Quoting {market.asset} {side} {self.quote_size} @ ${target} (order {order.order_id})")
+
+        self._apply_fills(quote)
+        if order.status in TERMINAL_ORDER_STATUSES:
+            del self.quotes[token_id]
+
+    # ============================================================
+    # FILLS AND INVENTORY
+    # ============================================================
+
+    async def _sync_fills(self) -> None:
+        """Refresh resting quotes, add fills to inventory and drop finished quotes."""
+        if not self.quotes:
+            return
+        try:
+            await self.order_manager.refresh_resting_orders()
+        except Exception as e:
+            logger.warning(f"⚠️ Failed to refresh quotes: {e}")
+
+        for token_id, quote in list(self.quotes.items()):
+            self._apply_fills(quote)
+            if quote.order.status in TERMINAL_ORDER_STATUSES:
+                logger.info(
+                    f"📕 Quote {quote.order.order_id} {quote.order.status.lower()} "
+                    f"({quote.order.size_matched}/{quote.order.size} filled)"
+                )
+                del self.quotes[token_id]
+
+    def _apply_fills(self, quote: Quote) -> None:
+        """Add newly matched size of a quote to the market inventory."""
+        order = quote.order
+        new_size = order.size_matched - quote.tracked_size
+        if new_size <= 0:
+            return
+        quote.tracked_size = order.size_matched
+
+        market = quote.market
+        inventory = self.inventory.setdefault(market.market_id, MarketInventory(market=market))
+        price = order.fill_price or order.price
+        if quote.side == "UP":
+            inventory.up_shares += new_size
+        else:
+            inventory.down_shares += new_size
+        inventory.cost += price * new_size
+        self.stats["fills"] += 1
+        if self.ledger is not None:
+            try:
+                self.ledger.record_fill(
+                    token_id=order.market_id, side="BUY", size=new_size, price=price,
+                    market_id=market.market_id, outcome=quote.side, strategy="market_making",
+                    order_id=order.order_id
+                )
+            except Exception as e:
+                logger.error(f"❌ Failed to record MM fill in ledger: {e}")
+
+        self._register_exposure(inventory)
+
+        logger.info(
+            f"💱 MM FILL: {market.asset} {quote.side} +{new_size} @ ${price} | "
+            f"UP={inventory.up_shares} DOWN={inventory.down_shares} "
+            f"net={inventory.net} paired={inventory.paired} cost=${inventory.cost:.2f}"
+        )
+
+    def _register_exposure(self, inventory: MarketInventory) -> None:
+        """Register the inventory shares at their average cost as the market's exposure."""
+        shares = inventory.up_shares + inventory.down_shares
+        if shares <= 0:
+            self.risk_manager.release_position(inventory.market.market_id)
+            return
+        self.risk_manager.add_position(
+            inventory.market.market_id,
+            "UP" if inventory.net >= 0 else "DOWN",
+            inventory.cost / shares,
+            shares
+        )
+
+    # ============================================================
+    # CLOSING INVENTORY
+    # ============================================================
+
+    async def _sell_inventory(self, inventory: MarketInventory, side: str, reason: str) -> None:
+        """Sell one side of a market's inventory into the best bid (fill and kill)."""
+        market = inventory.market
+        shares = inventory.up_shares if side == "UP" else inventory.down_shares
+        if shares <= 0:
+            return
+        token_id = market.up_token_id if side == "UP" else market.down_token_id
+
+        book = await self.order_book_analyzer.get_order_book(token_id, force_refresh=True)
+        if book is None or not book.bids:
+            logger.warning(f"⚠️ No bid to sell {market.asset} {side} inventory, {shares} shares kept")
+            return
+        price = max(level.price for level in book.bids)
+
+        try:
+            signed_order = self.clob_client.create_order(
+                OrderArgs(token_id=token_id, price=float(price), size=float(shares), side=SELL),
+                options=SimpleNamespace(tick_size=str(market.tick_size or "0.01"), neg_risk=market.neg_risk)
+            )
+            response = self.clob_client.post_order(signed_order, OrderType.FAK)
+        except Exception as e:
+            logger.error(f"❌ Failed to sell {market.asset} {side} inventory: {e}")
+            return
+        if not response or not response.get("success", True) or response.get("errorMsg"):
+            logger.warning(
+                f"⚠️ Sell of {market.asset} {side} inventory rejected: "
+                f"{(response or {}).get('errorMsg', 'empty response')}"
+            )
+            return
+
+        sold = min(Decimal(str(response.get("makingAmount") or "0")), shares)
+        if sold <= 0:
+            return
+        proceeds = Decimal(str(response.get("takingAmount") or "0"))
+        fill_price = proceeds / sold
+
+        # Inventory cost covers both tokens; the sold shares take their average share of it
+        cost = inventory.cost * sold / (inventory.up_shares + inventory.down_shares)
+        if side == "UP":
+            inventory.up_shares -= sold
+        else:
+            inventory.down_shares -= sold
+        inventory.cost -= cost
+        self.stats["inventory_sold"] += 1
+
+        if self.ledger is not None:
+            try:
+                self.ledger.record_fill(
+                    token_id=token_id, side="SELL", size=sold, price=fill_price,
+                    market_id=market.market_id, outcome=side, strategy="market_making",
+                    order_id=response.get("orderID")
+                )
+            except Exception as e:
+                logger.error(f"❌ Failed to record MM sell in ledger: {e}")
+
+        # Books the result and drops the exposure, which is re-registered for any shares left
+        self.risk_manager.record_trade_result(proceeds - cost, market.market_id, cost)
+        self._register_exposure(inventory)
+
+        logger.info(
+            f"💸 MM SELL ({reason}): {market.asset} {side} -{sold} @ ${fill_price:.4f} | "
+            f"UP={inventory.up_shares} DOWN={inventory.down_shares} cost=${inventory.cost:.2f}"
+        )
+
+    # ============================================================
+    # PULLING QUOTES
+    # ============================================================
+
+    async def _pull_quotes(self, market: CryptoMarket, reason: str) -> None:
+        """Cancel both quotes of a market, keeping whatever already filled."""
+        for token_id in (market.up_token_id, market.down_token_id):
+            if token_id in self.quotes:
+                await self._cancel_quote(token_id, reason=reason)
+
+    async def _cancel_quote(self, token_id: str, reason: str) -> None:
+        """Cancel one quote and book any fills that raced the cancel."""
+        quote = self.quotes[token_id]
+        order = quote.order
+        if self.order_manager.get_order(order.order_id) is not None:
+            try:
+                await self.order_manager.cancel_order(order.order_id)
+            except Exception as e:
+                logger.warning(f"⚠️ Failed to cancel quote {order.order_id}: {e}")
+        self._apply_fills(quote)
+        if self.order_manager.get_order(order.order_id) is not None:
+            # Still working on the exchange; retried next cycle
+            return
+        del self.quotes[token_id]
+        self.stats["quotes_pulled"] += 1
+        logger.info(
+            f"🧹 Pulled {quote.market.asset} {quote.side} quote {order.order_id} "
+            f"({reason}), filled {order.size_matched}/{order.size}"
+        )
+
+    def _retire_market(self, market: CryptoMarket) -> None:
+        """
+        Forget a market that is no longer listed (closed or resolved).
+
+        Its inventory stops counting against the risk limits and the shares go
+        to the redemption service; the ledger realizes their P&L on redemption.
+        """
+        del self.markets[market.market_id]
+        self.fair_value_pricer.forget_strike(market.market_id)
+        self._paused_until.pop(market.market_id, None)
+        self.risk_manager.release_position(market.market_id)
+
+        inventory = self.inventory.pop(market.market_id, None)
+        if inventory is None:
+            return
+        self.stats["markets_retired"] += 1
+        if self.redemption_service is not None and not self.dry_run:
+            for token_id, shares, outcome_index in (
+                (market.up_token_id, inventory.up_shares, 0),
+                (market.down_token_id, inventory.down_shares, 1),
+            ):
+                if shares > 0:
+                    self.redemption_service.watch(
+                        market.market_id, token_id, outcome_index=outcome_index, neg_risk=market.neg_risk
+                    )
+        logger.info(
+            f"🏁 Retired {market.asset} {market.market_id[:16]}...: "
+            f"UP={inventory.up_shares} DOWN={inventory.down_shares} cost=${inventory.cost:.2f} left for redemption"
+        )
+
+    def get_open_positions(self) -> List[Dict[str, Any]]:
+        """Inventory per market and working quotes (dashboard view)."""
+        positions = [
+            {
+                "market_id": market_id,
+                "asset": inv.market.asset,
+                "side": "UP" if inv.net >= 0 else "DOWN",
+                "strategy": "market_making",
+                "up_shares": inv.up_shares,
+                "down_shares": inv.down_shares,
+                "net": inv.net,
+                "cost": inv.cost,
+                "mark_price": inv.mark,
+                "unrealized_pnl": inv.unrealized_pnl,
+                "status": "open",
+            }
+            for market_id, inv in self.inventory.items()
+            if inv.up_shares > 0 or inv.down_shares > 0
+        ]
+        positions.extend(
+            {
+                "token_id": token_id,
+                "market_id": quote.market.market_id,
+                "asset": quote.market.asset,
+                "side": quote.side,
+                "strategy": "market_making",
+                "entry_price": quote.order.price,
+                "size": quote.order.size - quote.order.size_matched,
+                "status": "resting",
+            }
+            for token_id, quote in self.quotes.items()
+        )
+        return positions
+
+    def get_stats(self) -> dict:
+        """Quote/fill counters and inventory per market."""
+        return {
+            **self.stats,
+            "live_quotes": len(self.quotes),
+            "inventory": {
+                market_id: {
+                    "asset": inv.market.asset,
+                    "up_shares": float(inv.up_shares),
+                    "down_shares": float(inv.down_shares),
+                    "net": float(inv.net),
+                    "paired": float(inv.paired),
+                    "cost": float(inv.cost),
+                }
+                for market_id, inv in self.inventory.items()
+            },
+        }
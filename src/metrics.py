@@ -0,0 +1,296 @@
+"""
+Prometheus metric definitions for Polymarket Arbitrage Bot.
+
+Every metric the bot exports is declared once in METRIC_DEFINITIONS.
+MonitoringSystem creates its collectors from the list, and
+grafana_dashboard() builds the bundled Grafana dashboard from the same
+list, so the dashboard cannot drift from the exported metrics.
+
+Regenerate the dashboard after changing a definition:
+    python -m src.metrics deployment/grafana/polymarket_bot_dashboard.json
+
+Validates Requirements:
+- 13.1: Prometheus metrics (counters, gauges, histograms)
+- Per-strategy opportunity/entry/exit counters and per-position unrealized PnL
+- Feed staleness gauges and tick-to-order latency histograms with an SLO
+- Circuit breaker and conservative mode state
+- Grafana dashboard generated from the metric definitions
+"""
+
+import json
+import sys
+from dataclasses import dataclass
+from typing import Any, Dict, List, Optional, Tuple
+
+from prometheus_client import CollectorRegistry, Counter, Gauge, Histogram
+
+COUNTER = "counter"
+GAUGE = "gauge"
+HISTOGRAM = "histogram"
+
+DASHBOARD_PATH = "deployment/grafana/polymarket_bot_dashboard.json"
+DASHBOARD_UID = "polymarket-bot"
+
+# Tick-to-order latency SLO: the 15-minute strategy alerts on executions over 1 second
+TICK_TO_ORDER_SLO_MS = 1000.0
+LATENCY_BUCKETS_MS = (5, 10, 25, 50, 100, 150, 250, 500, 1000, 2500, 5000, 10000)
+
+
+@dataclass(frozen=True)
+class MetricDefinition:
+    """One exported metric; MonitoringSystem exposes it as an attribute of the same name."""
+    name: str
+    kind: str  # COUNTER, GAUGE or HISTOGRAM
+    description: str
+    labels: Tuple[str, ...] = ()
+    group: str = "System"  # Dashboard row
+    unit: str = "short"  # Grafana unit of the panel
+    buckets: Optional[Tuple[float, ...]] = None  # Histogram buckets (default: prometheus_client's)
+    slo: Optional[float] = None  # Histogram bound drawn as a threshold, with a share-within-SLO panel
+
+    @property
+    def sample_name(self) -> str:
+        """Name of the exported series (counters gain a _total suffix)."""
+        if self.kind == COUNTER and not self.name.endswith("_total"):
+            return f"{self.name}_total"
+        return self.name
+
+
+METRIC_DEFINITIONS: Tuple[MetricDefinition, ...] = (
+    # Trading
+    MetricDefinition("trades_total", COUNTER, "Total trades executed", ("strategy", "status"), "Trading"),
+    MetricDefinition("trades_successful", COUNTER, "Successful trades", ("strategy",), "Trading"),
+    MetricDefinition("trades_failed", COUNTER, "Failed trades", ("strategy", "reason"), "Trading"),
+    MetricDefinition("opportunities_found", COUNTER, "Opportunities detected", ("strategy",), "Trading"),
+    MetricDefinition("opportunities_skipped", COUNTER, "Opportunities skipped", ("reason",), "Trading"),
+    MetricDefinition("profit_usd", GAUGE, "Total profit in USD", group="Trading", unit="currencyUSD"),
+    MetricDefinition("profit_net_usd", GAUGE, "Net profit after gas in USD", group="Trading", unit="currencyUSD"),
+    MetricDefinition("win_rate", GAUGE, "Win rate percentage", group="Trading", unit="percent"),
+    MetricDefinition("profit_per_trade", HISTOGRAM, "Profit per trade in USD", group="Trading", unit="currencyUSD"),
+    MetricDefinition("gas_cost_per_trade", HISTOGRAM, "Gas cost per trade in USD", group="Trading", unit="currencyUSD"),
+
+    # Strategies
+    MetricDefinition(
+        "strategy_opportunities_total", COUNTER, "Entry signals found by strategies", ("strategy", "asset"), "Strategies"
+    ),
+    MetricDefinition(
+        "strategy_entries_total", COUNTER, "Positions opened by strategies", ("strategy", "asset"), "Strategies"
+    ),
+    MetricDefinition(
+        "strategy_exits_total", COUNTER, "Positions closed by strategies", ("strategy", "asset", "reason"), "Strategies"
+    ),
+
+    # Positions
+    MetricDefinition(
+        "position_unrealized_pnl_usd", GAUGE, "Unrealized PnL of open positions in USD",
+        ("wallet", "strategy", "asset", "position"), "Positions", unit="currencyUSD"
+    ),
+
+    # Latency
+    MetricDefinition(
+        "tick_to_order_latency_ms", HISTOGRAM, "Time from the market data tick behind a signal to order placement",
+        ("strategy", "asset"), "Latency", unit="ms", buckets=LATENCY_BUCKETS_MS, slo=TICK_TO_ORDER_SLO_MS
+    ),
+    MetricDefinition("latency_ms", HISTOGRAM, "Execution latency in milliseconds", ("operation",), "Latency", unit="ms"),
+    MetricDefinition("scan_duration_ms", HISTOGRAM, "Market scan duration in milliseconds", group="Latency", unit="ms"),
+    MetricDefinition("ai_response_time_ms", HISTOGRAM, "AI safety check response time", group="Latency", unit="ms"),
+
+    # Feeds
+    MetricDefinition(
+        "feed_staleness_seconds", GAUGE, "Seconds since the last update of a market data feed",
+        ("feed", "stream"), "Feeds", unit="s"
+    ),
+
+    # Risk
+    MetricDefinition("circuit_breaker_status", GAUGE, "Circuit breaker status (0=closed, 1=open)", group="Risk"),
+    MetricDefinition("consecutive_failures", GAUGE, "Consecutive failed trades", group="Risk"),
+    MetricDefinition(
+        "risk_trading_halted", GAUGE, "Risk manager trading halt (0=trading, 1=halted)", ("book",), "Risk"
+    ),
+    MetricDefinition(
+        "conservative_mode_active", GAUGE, "Conservative mode (0=off, 1=on)", ("book",), "Risk"
+    ),
+    MetricDefinition("ai_safety_checks", COUNTER, "AI safety checks", ("result",), "Risk"),
+
+    # Balances
+    MetricDefinition("balance_eoa_usd", GAUGE, "EOA wallet USDC balance", group="Balances", unit="currencyUSD"),
+    MetricDefinition("balance_proxy_usd", GAUGE, "Proxy wallet USDC balance", group="Balances", unit="currencyUSD"),
+    MetricDefinition("balance_total_usd", GAUGE, "Total USDC balance", group="Balances", unit="currencyUSD"),
+
+    # Network
+    MetricDefinition("gas_price_gwei", GAUGE, "Current gas price", group="Network"),
+    MetricDefinition("pending_tx_count", GAUGE, "Pending transactions", group="Network"),
+    MetricDefinition("markets_scanned", GAUGE, "Markets in last scan", group="Network"),
+    MetricDefinition("network_errors", COUNTER, "Network errors", ("type",), "Network"),
+    MetricDefinition("api_calls", COUNTER, "API calls", ("endpoint", "status"), "Network"),
+)
+
+
+def create_metrics(registry: CollectorRegistry,
+                   definitions: Tuple[MetricDefinition, ...] = METRIC_DEFINITIONS) -> Dict[str, Any]:
+    """Create the Prometheus collectors in the registry, by metric name."""
+    metrics = {}
+    for definition in definitions:
+        if definition.kind == COUNTER:
+            metric = Counter(definition.name, definition.description, definition.labels, registry=registry)
+        elif definition.kind == GAUGE:
+            metric = Gauge(definition.name, definition.description, definition.labels, registry=registry)
+        elif definition.kind == HISTOGRAM:
+            kwargs = {"buckets": definition.buckets} if definition.buckets else {}
+            metric = Histogram(definition.name, definition.description, definition.labels,
+                               registry=registry, **kwargs)
+        else:
+            raise ValueError(f"Unknown metric kind for {definition.name}: {definition.kind}")
+        metrics[definition.name] = metric
+    return metrics
+
+
+# ============================================================================
+# GRAFANA DASHBOARD
+# ============================================================================
+
+def _by(labels: Tuple[str, ...], *extra: str) -> str:
+    names = list(extra) + list(labels)
+    return f" by ({', '.join(names)})" if names else ""
+
+
+def _legend(labels: Tuple[str, ...], prefix: str = "") -> str:
+    legend = " ".join(f"{{{{{label}}}}}" for label in labels)
+    return f"{prefix} {legend}".strip()
+
+
+def panel_queries(definition: MetricDefinition) -> List[Dict[str, str]]:
+    """PromQL targets of a metric's panel."""
+    labels = definition.labels
+    if definition.kind == COUNTER:
+        expr = f"sum{_by(labels)} (rate({definition.sample_name}[$__rate_interval]))"
+        return [{"expr": expr, "legendFormat": _legend(labels, definition.name if not labels else "")}]
+    if definition.kind == GAUGE:
+        return [{"expr": definition.name, "legendFormat": _legend(labels, definition.name if not labels else "")}]
+    return [
+        {
+            "expr": (
+                f"histogram_quantile({quantile}, sum{_by(labels, 'le')} "
+                f"(rate({definition.name}_bucket[$__rate_interval])))"
+            ),
+            "legendFormat": _legend(labels, f"p{round(quantile * 100)}"),
+        }
+        for quantile in (0.5, 0.95, 0.99)
+    ]
+
+
+def _panel(panel_id: int, title: str, description: str, targets: List[Dict[str, str]],
+           unit: str, grid: Dict[str, int], threshold: Optional[float] = None) -> Dict[str, Any]:
+    steps = [{"color": "green", "value": None}]
+    if threshold is not None:
+        steps.append({"color": "red", "value": threshold})
+    return {
+        "id": panel_id,
+        "type": "timeseries",
+        "title": title,
+        "description": description,
+        "datasource": {"type": "prometheus", "uid": "${datasource}"},
+        "gridPos": grid,
+        "fieldConfig": {
+            "defaults": {
+                "unit": unit,
+                "thresholds": {"mode": "absolute", "steps": steps},
+                "custom": {"thresholdsStyle": {"mode": "line" if threshold is not None else "off"}},
+            },
+            "overrides": [],
+        },
+        "options": {"legend": {"displayMode": "list", "placement": "bottom"}},
+        "targets": [dict(target, refId=chr(ord("A") + i)) for i, target in enumerate(targets)],
+    }
+
+
+def grafana_dashboard(definitions: Tuple[MetricDefinition, ...] = METRIC_DEFINITIONS) -> Dict[str, Any]:
+    """
+    Grafana dashboard with one row per metric group and one panel per metric.
+
+    Counters are shown as per-second rates, gauges as values and histograms as
+    p50/p95/p99. A histogram with an SLO also gets a panel with the share of
+    observations within it.
+    """
+    panels: List[Dict[str, Any]] = []
+    panel_id = 1
+    y = 0
+    groups: Dict[str, List[MetricDefinition]] = {}
+    for definition in definitions:
+        groups.setdefault(definition.group, []).append(definition)
+
+    for group, members in groups.items():
+        panels.append({
+            "id": panel_id, "type": "row", "title": group, "collapsed": False,
+            "gridPos": {"h": 1, "w": 24, "x": 0, "y": y}, "panels": [],
+        })
+        panel_id += 1
+        y += 1
+
+        group_panels = []
+        for definition in members:
+            group_panels.append((
+                definition.name, definition.description, panel_queries(definition), definition.unit, definition.slo
+            ))
+            if definition.kind == HISTOGRAM and definition.slo is not None:
+                slo = f"{definition.slo:g}"
+                expr = (
+                    f"sum{_by(definition.labels)} (rate({definition.name}_bucket{{le=\"{float(definition.slo)}\"}}"
+                    f"[$__rate_interval])) / sum{_by(definition.labels)} "
+                    f"(rate({definition.name}_count[$__rate_interval]))"
+                )
+                group_panels.append((
+                    f"{definition.name} within {slo}{definition.unit} SLO",
+                    f"Share of {definition.name} observations at or below {slo}{definition.unit}",
+                    [{"expr": expr, "legendFormat": _legend(definition.labels, "within SLO")}],
+                    "percentunit", None
+                ))
+
+        for i, (title, description, targets, unit, threshold) in enumerate(group_panels):
+            grid = {"h": 8, "w": 12, "x": 12 * (i % 2), "y": y + 8 * (i // 2)}
+            panels.append(_panel(panel_id, title, description, targets, unit, grid, threshold))
+            panel_id += 1
+        y += 8 * ((len(group_panels) + 1) // 2)
+
+    return {
+        "uid": DASHBOARD_UID,
+        "title": "Polymarket Arbitrage Bot",
+        "tags": ["polymarket", "trading"],
+        "timezone": "utc",
+        "schemaVersion": 39,
+        "version": 1,
+        "editable": True,
+        "refresh": "30s",
+        "time": {"from": "now-6h", "to": "now"},
+        "templating": {
+            "list": [{
+                "name": "datasource",
+                "label": "Prometheus",
+                "type": "datasource",
+                "query": "prometheus",
+                "current": {},
+                "hide": 0,
+            }]
+        },
+        "annotations": {"list": []},
+        "panels": panels,
+    }
+
+
+def render_dashboard() -> str:
+    """The dashboard as written to DASHBOARD_PATH."""
+    return json.dumps(grafana_dashboard(), indent=2) + "\n"
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """Write the Grafana dashboard JSON (default: DASHBOARD_PATH)."""
+    argv = sys.argv[1:] if argv is None else argv
+    path = argv[0] if argv else DASHBOARD_PATH
+    with open(path, "w") as f:
+        f.write(render_dashboard())
+    print(f"Wrote Grafana dashboard with {len(METRIC_DEFINITIONS)} metrics to {path}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -101,6 +101,9 @@ class Opportunity:
     platform_a: Optional[str] = None
     platform_b: Optional[str] = None
     
+    # Single-sided strategies: outcome to buy ("YES" or "NO")
+    side: Optional[str] = None
+    
     def is_profitable(self, min_profit_threshold: Decimal = Decimal('0.005')) -> bool:
         """
         Check if opportunity meets minimum profit threshold.
@@ -144,6 +147,9 @@ class TradeResult:
     # Error information
     error_message: Optional[str] = None
     
+    # Wallet (sub-account) that placed the trade
+    wallet: str = "primary"
+    
     def was_successful(self) -> bool:
         """
         Check if trade was successful.
@@ -160,7 +166,7 @@ class SafetyDecision:
     approved: bool
     reason: str
     timestamp: datetime
-    checks_performed: Dict[str, bool]  # {"nvidia_api": True, "volatility": True, ...}
+    checks_performed: Dict[str, bool]  # {"ai_check": True, "volatility": True, ...}
     ai_response: Optional[str] = None
     fallback_used: bool = False
 
@@ -3,29 +3,24 @@ Monitoring System for Polymarket Arbitrage Bot.
 
 Provides:
 - Prometheus metrics (counters, gauges, histograms)
-- SNS alerting
+- Alerting through the AlertRouter (SNS, webhook, Telegram, Discord, SMTP)
 - Trade and error recording
 - Metrics exposure on port 9090
 
 Validates Requirements: 13.1, 13.2, 13.5
 """
 
+import asyncio
 import logging
-from typing import Optional, Dict, Any
+from typing import Optional, Dict, Any, Set, Tuple
 from decimal import Decimal
 from datetime import datetime
 from dataclasses import dataclass, field
 
-from prometheus_client import (
-    Counter, Gauge, Histogram, start_http_server, CollectorRegistry
-)
-
-try:
-    import boto3
-    SNS_AVAILABLE = True
-except ImportError:
-    SNS_AVAILABLE = False
+from prometheus_client import start_http_server, CollectorRegistry
 
+from src.alerting import SYSTEM, Alert, AlertRouter, SnsSink
+from src.metrics import create_metrics
 from src.models import TradeResult, HealthStatus
 from src.logging_config import get_logger, log_with_context
 
@@ -62,12 +57,12 @@ class MetricsSummary:
 
 class MonitoringSystem:
     """
-    Monitoring system with Prometheus metrics and SNS alerting.
+    Monitoring system with Prometheus metrics and routed alerts.
     
     Validates Requirements:
     - 13.1: Expose Prometheus metrics on port 9090
     - 13.2: Update metrics in real-time after each trade
-    - 13.5: Send SNS alerts for critical conditions
+    - 13.5: Send alerts for critical conditions (SNS and the other alert channels)
     """
     
     def __init__(
@@ -75,6 +70,7 @@ class MonitoringSystem:
         prometheus_port: int = 9090,
         sns_topic_arn: Optional[str] = None,
         enable_prometheus: bool = True,
+        alert_router: Optional[AlertRouter] = None,
     ):
         """
         Initialize monitoring system.
@@ -83,6 +79,7 @@ class MonitoringSystem:
             prometheus_port: Port to expose Prometheus metrics (default 9090)
             sns_topic_arn: AWS SNS topic ARN for alerts
             enable_prometheus: Whether to enable Prometheus metrics server
+            alert_router: Alert channels and routes (default: SNS only, if sns_topic_arn is set)
         """
         self.logger = get_logger(__name__)
         self.prometheus_port = prometheus_port
@@ -92,19 +89,22 @@ class MonitoringSystem:
         # Initialize Prometheus registry
         self.registry = CollectorRegistry()
         
-        # Initialize metrics
-        self._init_counter_metrics()
-        self._init_gauge_metrics()
-        self._init_histogram_metrics()
-        
-        # Initialize SNS client
-        self.sns_client = None
-        if sns_topic_arn and SNS_AVAILABLE:
-            try:
-                self.sns_client = boto3.client('sns')
-                self.logger.info(f"SNS alerting enabled: {sns_topic_arn}")
-            except Exception as e:
-                self.logger.warning(f"Failed to initialize SNS client: {e}")
+        # Initialize metrics (src/metrics.py), each an attribute named after the metric
+        for name, metric in create_metrics(self.registry).items():
+            setattr(self, name, metric)
+        self._position_labels: Set[Tuple[str, ...]] = set()
+        
+        # Alert channels (docs/ALERTING.md)
+        if alert_router is None:
+            alert_router = AlertRouter()
+            if sns_topic_arn:
+                try:
+                    alert_router = AlertRouter([SnsSink("sns", sns_topic_arn)])
+                    self.logger.info(f"SNS alerting enabled: {sns_topic_arn}")
+                except Exception as e:
+                    self.logger.warning(f"Failed to initialize SNS client: {e}")
+        self.alert_router = alert_router
+        self._alert_tasks: Set[asyncio.Task] = set()
         
         # Start Prometheus HTTP server
         if enable_prometheus:
@@ -114,176 +114,6 @@ class MonitoringSystem:
             except Exception as e:
                 self.logger.error(f"Failed to start Prometheus server: {e}")
     
-    def _init_counter_metrics(self):
-        """Initialize Prometheus counter metrics."""
-        # Trade counters
-        self.trades_total = Counter(
-            'trades_total',
-            'Total trades executed',
-            ['strategy', 'status'],
-            registry=self.registry
-        )
-        
-        self.trades_successful = Counter(
-            'trades_successful',
-            'Successful trades',
-            ['strategy'],
-            registry=self.registry
-        )
-        
-        self.trades_failed = Counter(
-            'trades_failed',
-            'Failed trades',
-            ['strategy', 'reason'],
-            registry=self.registry
-        )
-        
-        # Opportunity counters
-        self.opportunities_found = Counter(
-            'opportunities_found',
-            'Opportunities detected',
-            ['strategy'],
-            registry=self.registry
-        )
-        
-        self.opportunities_skipped = Counter(
-            'opportunities_skipped',
-            'Opportunities skipped',
-            ['reason'],
-            registry=self.registry
-        )
-        
-        # Safety counters
-        self.ai_safety_checks = Counter(
-            'ai_safety_checks',
-            'AI safety checks',
-            ['result'],
-            registry=self.registry
-        )
-        
-        # Error counters
-        self.network_errors = Counter(
-            'network_errors',
-            'Network errors',
-            ['type'],
-            registry=self.registry
-        )
-        
-        self.api_calls = Counter(
-            'api_calls',
-            'API calls',
-            ['endpoint', 'status'],
-            registry=self.registry
-        )
-    
-    def _init_gauge_metrics(self):
-        """Initialize Prometheus gauge metrics."""
-        # Balance gauges
-        self.balance_eoa_usd = Gauge(
-            'balance_eoa_usd',
-            'EOA wallet USDC balance',
-            registry=self.registry
-        )
-        
-        self.balance_proxy_usd = Gauge(
-            'balance_proxy_usd',
-            'Proxy wallet USDC balance',
-            registry=self.registry
-        )
-        
-        self.balance_total_usd = Gauge(
-            'balance_total_usd',
-            'Total USDC balance',
-            registry=self.registry
-        )
-        
-        # Financial gauges
-        self.profit_usd = Gauge(
-            'profit_usd',
-            'Total profit in USD',
-            registry=self.registry
-        )
-        
-        self.profit_net_usd = Gauge(
-            'profit_net_usd',
-            'Net profit after gas in USD',
-            registry=self.registry
-        )
-        
-        self.win_rate = Gauge(
-            'win_rate',
-            'Win rate percentage',
-            registry=self.registry
-        )
-        
-        # Network gauges
-        self.gas_price_gwei = Gauge(
-            'gas_price_gwei',
-            'Current gas price',
-            registry=self.registry
-        )
-        
-        self.pending_tx_count = Gauge(
-            'pending_tx_count',
-            'Pending transactions',
-            registry=self.registry
-        )
-        
-        # System gauges
-        self.markets_scanned = Gauge(
-            'markets_scanned',
-            'Markets in last scan',
-            registry=self.registry
-        )
-        
-        self.circuit_breaker_status = Gauge(
-            'circuit_breaker_status',
-            'Circuit breaker status (0=closed, 1=open)',
-            registry=self.registry
-        )
-        
-        self.consecutive_failures = Gauge(
-            'consecutive_failures',
-            'Consecutive failed trades',
-            registry=self.registry
-        )
-    
-    def _init_histogram_metrics(self):
-        """Initialize Prometheus histogram metrics."""
-        # Latency histograms
-        self.latency_ms = Histogram(
-            'latency_ms',
-            'Execution latency in milliseconds',
-            ['operation'],
-            registry=self.registry
-        )
-        
-        # Financial histograms
-        self.profit_per_trade = Histogram(
-            'profit_per_trade',
-            'Profit per trade in USD',
-            registry=self.registry
-        )
-        
-        self.gas_cost_per_trade = Histogram(
-            'gas_cost_per_trade',
-            'Gas cost per trade in USD',
-            registry=self.registry
-        )
-        
-        # Performance histograms
-        self.scan_duration_ms = Histogram(
-            'scan_duration_ms',
-            'Market scan duration in milliseconds',
-            registry=self.registry
-        )
-        
-        self.ai_response_time_ms = Histogram(
-            'ai_response_time_ms',
-            'AI safety check response time',
-            registry=self.registry
-        )
-    
     def record_trade(self, trade: TradeResult) -> None:
         """
         Record a trade execution and update all metrics.
@@ -365,49 +195,56 @@ class MonitoringSystem:
         severity: str,
         message: str,
         context: Optional[Dict[str, Any]] = None,
+        event: str = SYSTEM,
     ) -> bool:
         """
-        Send SNS alert for critical conditions.
+        Send an alert to the channels routed for its event.
         
-        Validates Requirement 13.5: Send SNS alerts for critical conditions
+        Validates Requirement 13.5: Send alerts for critical conditions
         
         Args:
-            severity: Alert severity (INFO, WARNING, ERROR, CRITICAL)
+            severity: Alert severity (info, warning, error, critical)
             message: Alert message
             context: Optional context dictionary
+            event: Alert event used for routing (src.alerting events)
             
         Returns:
-            bool: True if alert sent successfully, False otherwise
+            bool: True if at least one channel accepted the alert, False otherwise
         """
-        if not self.sns_client or not self.sns_topic_arn:
-            self.logger.warning(f"SNS not configured, alert not sent: {message}")
+        if not self.alert_router.sinks:
+            self.logger.warning(f"No alert channels configured, alert not sent: {message}")
             return False
         
-        try:
-            # Format alert message
-            alert_subject = f"[{severity}] Polymarket Arbitrage Bot Alert"
-            alert_body = f"{message}\n\n"
-            
-            if context:
-                alert_body += "Context:\n"
-                for key, value in context.items():
-                    alert_body += f"  {key}: {value}\n"
-            
-            alert_body += f"\nTimestamp: {datetime.utcnow().isoformat()}Z"
-            
-            # Send SNS message
-            response = self.sns_client.publish(
-                TopicArn=self.sns_topic_arn,
-                Subject=alert_subject,
-                Message=alert_body,
-            )
-            
-            self.logger.info(f"Alert sent: {message} (MessageId: {response['MessageId']})")
+        results = await self.alert_router.send(Alert(severity, message, event=event, context=dict(context or {})))
+        if any(results.values()):
+            self.logger.info(f"Alert sent to {', '.join(n for n, ok in results.items() if ok)}: {message}")
             return True
-            
-        except Exception as e:
-            self.logger.error(f"Failed to send SNS alert: {e}")
-            return False
+        return False
+    
+    def notify(
+        self,
+        severity: str,
+        message: str,
+        context: Optional[Dict[str, Any]] = None,
+        event: str = SYSTEM,
+    ) -> None:
+        """
+        Send an alert from synchronous code without waiting for delivery.
+        
+        Args:
+            severity: Alert severity (info, warning, error, critical)
+            message: Alert message
+            context: Optional context dictionary
+            event: Alert event used for routing
+        """
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            self.logger.warning(f"No event loop, alert not sent: {message}")
+            return
+        task = loop.create_task(self.send_alert(severity, message, context, event=event))
+        self._alert_tasks.add(task)
+        task.add_done_callback(self._alert_tasks.discard)
     
     def update_balance_metrics(
         self,
@@ -473,8 +310,7 @@ class MonitoringSystem:
             consecutive_failures: Number of consecutive failures
         """
         self.markets_scanned.set(markets_scanned)
-        self.circuit_breaker_status.set(1 if circuit_breaker_open else 0)
-        self.consecutive_failures.set(consecutive_failures)
+        self.update_circuit_breaker(circuit_breaker_open, consecutive_failures)
     
     def record_opportunity(
         self,
@@ -551,7 +387,96 @@ class MonitoringSystem:
             response_time_ms: Response time in milliseconds
         """
         self.ai_response_time_ms.observe(response_time_ms)
-    
+
+    def record_strategy_opportunity(self, strategy: str, asset: str) -> None:
+        """
+        Record an entry signal found by a strategy.
+
+        Args:
+            strategy: Strategy (or 15-minute sub-strategy) name
+            asset: Asset traded (BTC, ETH, ...)
+        """
+        self.strategy_opportunities_total.labels(strategy=strategy, asset=asset).inc()
+
+    def record_strategy_entry(self, strategy: str, asset: str) -> None:
+        """
+        Record a position opened by a strategy.
+
+        Args:
+            strategy: Strategy name
+            asset: Asset traded
+        """
+        self.strategy_entries_total.labels(strategy=strategy, asset=asset).inc()
+
+    def record_strategy_exit(self, strategy: str, asset: str, reason: str) -> None:
+        """
+        Record a position closed by a strategy.
+
+        Args:
+            strategy: Strategy name
+            asset: Asset traded
+            reason: Exit reason (take_profit, stop_loss, time_exit, ...)
+        """
+        self.strategy_exits_total.labels(strategy=strategy, asset=asset, reason=reason).inc()
+
+    def record_tick_to_order(self, strategy: str, asset: str, latency_ms: float) -> None:
+        """
+        Record the time from the market data tick behind a signal to order placement.
+
+        Args:
+            strategy: Strategy name
+            asset: Asset traded
+            latency_ms: Latency in milliseconds
+        """
+        self.tick_to_order_latency_ms.labels(strategy=strategy, asset=asset).observe(latency_ms)
+
+    def update_position_pnl(self, positions: Dict[Tuple[str, str, str, str], float]) -> None:
+        """
+        Set unrealized PnL gauges; positions no longer open are removed.
+
+        Args:
+            positions: (wallet, strategy, asset, position) -> unrealized PnL in USD
+        """
+        for labels in self._position_labels - set(positions):
+            self.position_unrealized_pnl_usd.remove(*labels)
+        for labels, pnl in positions.items():
+            self.position_unrealized_pnl_usd.labels(*labels).set(pnl)
+        self._position_labels = set(positions)
+
+    def update_feed_staleness(self, feed: str, stream: str, seconds: float) -> None:
+        """
+        Set the age of a market data feed's last update.
+
+        Args:
+            feed: Feed name (binance, polymarket_ws)
+            stream: Stream within the feed (asset, or "all")
+            seconds: Seconds since the last update
+        """
+        self.feed_staleness_seconds.labels(feed=feed, stream=stream).set(seconds)
+
+    def update_risk_state(self, book: str, trading_halted: bool, conservative_mode: bool) -> None:
+        """
+        Export a risk manager's halt and conservative mode state.
+
+        Args:
+            book: Wallet or strategy book the risk manager guards
+            trading_halted: Whether the risk manager halted trading
+            conservative_mode: Whether conservative mode is active
+        """
+        self.risk_trading_halted.labels(book=book).set(1 if trading_halted else 0)
+        self.conservative_mode_active.labels(book=book).set(1 if conservative_mode else 0)
+
+    def update_circuit_breaker(self, is_open: bool, consecutive_failures: int) -> None:
+        """
+        Export the circuit breaker state.
+
+        Args:
+            is_open: Whether the circuit breaker is open
+            consecutive_failures: Number of consecutive failures
+        """
+        self.circuit_breaker_status.set(1 if is_open else 0)
+        self.consecutive_failures.set(consecutive_failures)
+
     def get_metrics_summary(self) -> MetricsSummary:
         """
         Get current metrics summary.
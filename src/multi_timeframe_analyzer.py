@@ -17,6 +17,8 @@ from typing import Dict, List, Optional, Tuple
 from dataclasses import dataclass
 from collections import deque
 
+from src.fair_value_pricer import realized_volatility
+
 logger = logging.getLogger(__name__)
 
 
@@ -273,6 +275,36 @@ class MultiTimeframeAnalyzer:
         
         return (direction, confidence, signals)
     
+    def get_realized_volatility(
+        self,
+        asset: str,
+        timeframes: Tuple[str, ...] = ("1m", "5m", "15m"),
+        min_points: int = 10
+    ) -> Optional[float]:
+        """
+        Annualized realized volatility of an asset.
+        
+        Uses the first timeframe (shortest first) with enough history.
+        Feeds FairValuePricer.
+        
+        Args:
+            asset: Asset symbol
+            timeframes: Timeframes to try, in order
+            min_points: Minimum price points required
+            
+        Returns:
+            Annualized volatility, or None if there is too little data
+        """
+        if asset not in self.price_history:
+            return None
+        
+        for timeframe in timeframes:
+            history = self.price_history[asset].get(timeframe, deque())
+            volatility = realized_volatility(list(history), min_points=min_points)
+            if volatility is not None:
+                return volatility
+        return None
+    
     def is_strong_bullish_signal(self, asset: str, min_confidence: float = 60.0) -> bool:
         """
         Check if there's a strong bullish signal.
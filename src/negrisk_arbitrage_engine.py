@@ -491,6 +491,10 @@ class NegRiskArbitrageEngine:
             logger.error(f"NegRisk trade execution failed: {e}", exc_info=True)
             return self._create_failed_result(trade_id, opportunity, timestamp, str(e))
     
+    def was_executed(self, opportunity_id: str) -> bool:
+        """Check whether an opportunity has already been executed."""
+        return opportunity_id in self._executed_opportunities
+    
     def _create_failed_result(
         self,
         trade_id: str,
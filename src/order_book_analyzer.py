@@ -60,6 +60,9 @@ class OrderBookAnalyzer:
         self._order_book_cache: Dict[str, Tuple[OrderBookDepth, float]] = {}
         self._cache_ttl = 5.0
         
+        # Optional MarketDataRecorder; REST snapshots are recorded for replay when set
+        self.recorder = None
+        
         logger.info("📚 Order Book Analyzer initialized")
     
     async def get_order_book(self, token_id: str, force_refresh: bool = False) -> Optional[OrderBookDepth]:
@@ -99,6 +102,9 @@ class OrderBookAnalyzer:
             bids_data = getattr(response, 'bids', None) or []
             asks_data = getattr(response, 'asks', None) or []
             
+            if self.recorder is not None:
+                self.recorder.record_book(token_id, bids_data, asks_data, source="clob_rest")
+            
             for bid in bids_data[:10]:  # Top 10 levels
                 # Handle both object and dict access
                 if isinstance(bid, dict):
@@ -1,15 +1,18 @@
 """
 Order Manager for Polymarket Arbitrage Bot.
 
-Manages order creation, submission, and tracking with FOK (Fill-Or-Kill) orders.
+Manages order creation, submission, and tracking with FOK (Fill-Or-Kill) taker
+orders and GTC/GTD post-only maker orders that rest on the book.
 Validates Requirements 6.1, 6.2, 1.3, 1.4, 6.3, 6.4.
 """
 
 import asyncio
+import inspect
 import logging
+import time
 from dataclasses import dataclass
-from decimal import Decimal
-from typing import Optional, Tuple
+from decimal import Decimal, InvalidOperation
+from typing import Callable, List, Optional, Tuple
 from datetime import datetime
 import uuid
 
@@ -18,6 +21,26 @@ from src.transaction_manager import TransactionManager
 logger = logging.getLogger(__name__)
 
 
+# Order types that rest on the book until filled, cancelled or expired
+RESTING_ORDER_TYPES = ("GTC", "GTD")
+
+# The CLOB only honours GTD expirations at least one minute in the future
+GTD_SECURITY_THRESHOLD_SECONDS = 60
+
+# Resting order lifecycle states
+ORDER_STATUS_NEW = "NEW"
+ORDER_STATUS_LIVE = "LIVE"
+ORDER_STATUS_MATCHED = "MATCHED"
+ORDER_STATUS_CANCELED = "CANCELED"
+ORDER_STATUS_EXPIRED = "EXPIRED"
+ORDER_STATUS_REJECTED = "REJECTED"
+TERMINAL_ORDER_STATUSES = (
+    ORDER_STATUS_MATCHED, ORDER_STATUS_CANCELED, ORDER_STATUS_EXPIRED, ORDER_STATUS_REJECTED
+)
+
+ERR_POST_ONLY_CROSSES = "invalid post-only order: order crosses book"
+
+
 @dataclass
 class Order:
     """Represents a trading order."""
@@ -26,18 +49,35 @@ class Order:
     side: str  # "YES" or "NO"
     price: Decimal
     size: Decimal
-    order_type: str  # "FOK" (Fill-Or-Kill)
+    order_type: str  # "FOK" (Fill-Or-Kill), "GTC" or "GTD" (resting limit)
     slippage_tolerance: Decimal  # Maximum slippage (e.g., 0.001 = 0.1%)
     created_at: datetime
     neg_risk: bool = True  # Default to True (most markets)
     tick_size: str = "0.01"
     
+    # Resting (maker) order details
+    post_only: bool = False
+    expiration: int = 0  # GTD expiry in epoch seconds (includes the CLOB security threshold)
+    status: str = ORDER_STATUS_NEW
+    size_matched: Decimal = Decimal("0")
+    replaces: Optional[str] = None  # Order ID this order cancel-replaced
+    
     # Execution details
     filled: bool = False
     fill_price: Optional[Decimal] = None
     tx_hash: Optional[str] = None
     error_message: Optional[str] = None
 
+    @property
+    def is_resting(self) -> bool:
+        """True for GTC/GTD orders that can rest on the book."""
+        return self.order_type in RESTING_ORDER_TYPES
+
+    @property
+    def remaining_size(self) -> Decimal:
+        """Size not yet matched."""
+        return max(self.size - self.size_matched, Decimal("0"))
+
 
 class OrderError(Exception):
     """Base exception for order errors."""
@@ -61,12 +101,14 @@ class AtomicExecutionError(OrderError):
 
 class OrderManager:
     """
-    Manages order creation and execution with FOK orders.
+    Manages order creation and execution with FOK and resting limit orders.
     
     Features:
     - FOK (Fill-Or-Kill) order creation with 0.1% slippage tolerance
     - Atomic YES/NO order pair submission
     - Fill price validation
+    - GTC/GTD post-only maker orders with partial-fill tracking
+    - Requote / cancel-replace and GTD expiry handling
     - Order cancellation
     
     Validates Requirements:
@@ -83,7 +125,8 @@ class OrderManager:
         clob_client,  # Type hint omitted to avoid import dependency
         tx_manager: TransactionManager,
         default_slippage: Decimal = Decimal('0.001'),  # 0.1%
-        dry_run: bool = False
+        dry_run: bool = False,
+        clock: Callable[[], float] = time.time
     ):
         """
         Initialize Order Manager.
@@ -93,11 +136,13 @@ class OrderManager:
             tx_manager: Transaction manager for blockchain operations
             default_slippage: Default slippage tolerance (default 0.1%)
             dry_run: If True, simulate orders without submitting to CLOB
+            clock: Epoch-seconds clock used for GTD expirations
         """
         self.clob_client = clob_client
         self.tx_manager = tx_manager
         self.default_slippage = default_slippage
         self.dry_run = dry_run
+        self.clock = clock
         
         # Track active orders
         self._active_orders: dict[str, Order] = {}
@@ -170,60 +215,72 @@ class OrderManager:
         self._active_orders[order_id] = order
         return order
 
-    async def _submit_order(self, order: Order) -> dict:
-        """Submit order to CLOB."""
-        if self.dry_run:
-            logger.info(f"DRY RUN: Simulating order submission for {order.order_id}")
-            await asyncio.sleep(0.1)
-            return {
-                'filled': True,
-                'fill_price': order.price,
-                'tx_hash': f"0x{uuid.uuid4().hex}"
-            }
-            
-        try:
-            from py_clob_client.clob_types import OrderArgs
-            from py_clob_client.order_builder.constants import BUY
-            from types import SimpleNamespace
-            
-            # Create OrderArgs
-            order_args = OrderArgs(
-                price=float(order.price),
-                size=float(order.size),
-                side=BUY,
-                token_id=order.market_id,
-            )
-            
-            logger.info(f"🚀 SUBMITTING REAL ORDER: {order.order_id} | Token: {order.market_id} | Price: {order.price} | Size: {order.size} | NegRisk: {order.neg_risk}")
-            
-            # Use SimpleNamespace for options (matching FifteenMinuteCryptoStrategy)
-            options = SimpleNamespace(
-                tick_size=order.tick_size,
-                neg_risk=order.neg_risk
-            )
-            
-            signed_order = self.clob_client.create_order(order_args, options=options)
-            resp = self.clob_client.post_order(signed_order)
-            
-            logger.info(f"✅ Order submitted! Response: {resp}")
-            
-            tx_hash = None
-            if isinstance(resp, dict):
-                tx_hash = resp.get("transactionHash") or resp.get("hash")
-                order_id = resp.get("orderID") or resp.get("order_id")
-                if order_id:
-                     order.order_id = order_id
-            
-            return {
-                'filled': True,
-                'fill_price': order.price,
-                'tx_hash': tx_hash or f"0x{uuid.uuid4().hex}"
-            }
-            
-        except Exception as e:
-            logger.error(f"❌ REAL ORDER FAILED: {e}")
-            raise OrderError(f"CLOB submission failed: {e}")
-    
+    def create_limit_order(
+        self,
+        market_id: str,
+        side: str,
+        price: Decimal,
+        size: Decimal,
+        order_type: str = "GTC",
+        ttl_seconds: Optional[int] = None,
+        post_only: bool = True,
+        neg_risk: bool = True,
+        tick_size: str = "0.01"
+    ) -> Order:
+        """
+        Create a resting GTC/GTD limit order.
+
+        Post-only orders are rejected instead of matching if they would cross
+        the book, so they only ever fill as maker orders (no taker fee).
+
+        Args:
+            market_id: Token ID to buy
+            side: "YES" or "NO"
+            price: Limit price
+            size: Size in shares
+            order_type: "GTC" or "GTD"
+            ttl_seconds: Lifetime of a GTD order (required for GTD)
+            post_only: Reject the order if it would take liquidity
+            neg_risk: NegRisk market flag
+            tick_size: Market tick size
+
+        Returns:
+            Order: Unsubmitted order (see post_limit_order)
+        """
+        if side not in ["YES", "NO"]:
+            raise ValueError(f"Invalid side: {side}")
+        if price <= 0 or price >= 1:
+            raise ValueError(f"Invalid price: {price}")
+        if size <= 0:
+            raise ValueError(f"Invalid size: {size}")
+        if order_type not in RESTING_ORDER_TYPES:
+            raise ValueError(f"Invalid resting order type: {order_type}")
+
+        expiration = 0
+        if order_type == "GTD":
+            if not ttl_seconds or ttl_seconds <= 0:
+                raise ValueError(f"GTD orders need a positive ttl_seconds, got: {ttl_seconds}")
+            expiration = int(self.clock()) + GTD_SECURITY_THRESHOLD_SECONDS + int(ttl_seconds)
+
+        order_id = f"order_{uuid.uuid4().hex[:12]}"
+
+        order = Order(
+            order_id=order_id,
+            market_id=market_id,
+            side=side,
+            price=price,
+            size=size,
+            order_type=order_type,
+            slippage_tolerance=Decimal("0"),  # Limit orders never fill above their price
+            created_at=datetime.now(),
+            neg_risk=neg_risk,
+            tick_size=tick_size,
+            post_only=post_only,
+            expiration=expiration
+        )
+        self._active_orders[order_id] = order
+        return order
+
     async def submit_atomic_pair(
         self,
         yes_order: Order,
@@ -384,11 +441,45 @@ class OrderManager:
                 )
             
             raise
-    
+
+    async def submit_order(self, order: Order) -> bool:
+        """
+        Submit a single order and record its fill details.
+
+        Used by single-sided strategies (latency, NegRisk legs, resolution farming).
+
+        Args:
+            order: Order to submit
+
+        Returns:
+            bool: True if the order filled within slippage tolerance
+
+        Raises:
+            OrderError: If CLOB submission fails
+        """
+        result = await self._submit_order(order)
+
+        if not result['filled']:
+            logger.warning(f"Order not filled: {order.order_id}")
+            return False
+
+        fill_price = result['fill_price']
+        if not self._validate_fill_price(order, fill_price):
+            order.error_message = f"Fill price {fill_price} exceeds tolerance"
+            return False
+
+        order.filled = True
+        order.fill_price = fill_price
+        order.tx_hash = result.get('tx_hash')
+        return True
+
     async def cancel_order(self, order_id: str) -> bool:
         """
         Cancel a pending order.
         
+        Resting GTC/GTD orders are cancelled on the CLOB; their final matched
+        size is read back so fills that raced the cancel are not lost.
+        
         Args:
             order_id: Order identifier
             
@@ -410,11 +501,21 @@ class OrderManager:
         logger.info(f"Cancelling order: {order_id}")
         
         try:
-            # In a real implementation, this would call the CLOB API
-            # to cancel the order
-            
-            # For now, just remove from active orders
-            del self._active_orders[order_id]
+            if order.is_resting and not self.dry_run and order.status == ORDER_STATUS_LIVE:
+                resp = self.clob_client.cancel(order_id)
+                not_canceled = (resp.get("not_canceled") or {}) if isinstance(resp, dict) else {}
+                if order_id in not_canceled:
+                    # Usually already matched or expired; pick up its final state
+                    logger.warning(f"⚠️ CLOB did not cancel {order_id}: {not_canceled[order_id]}")
+                    self._sync_order(order)
+                    return False
+                self._sync_order(order)
+                if order.filled:
+                    logger.warning(f"Order filled before cancel: {order_id}")
+                    return False
+            
+            order.status = ORDER_STATUS_CANCELED
+            self._active_orders.pop(order_id, None)
             
             logger.info(f"Order cancelled: {order_id}")
             return True
@@ -423,6 +524,318 @@ class OrderManager:
             logger.error(f"Failed to cancel order {order_id}: {e}")
             raise OrderError(f"Order cancellation failed: {e}")
     
+    # ============================================================
+    # Resting (maker) order lifecycle
+    # ============================================================
+    
+    def get_best_ask(self, token_id: str) -> Optional[Decimal]:
+        """
+        Best ask for a token from the CLOB order book.
+        
+        Returns:
+            Optional[Decimal]: Lowest ask price, or None if unavailable
+        """
+        try:
+            book = self.clob_client.get_order_book(token_id)
+            asks = book.get("asks", []) if isinstance(book, dict) else getattr(book, "asks", None) or []
+            prices = [
+                Decimal(str(level.get("price") if isinstance(level, dict) else level.price))
+                for level in asks
+            ]
+            return min(prices) if prices else None
+        except Exception as e:
+            logger.debug(f"Could not read order book for {token_id}: {e}")
+            return None
+    
+    async def post_limit_order(self, order: Order) -> bool:
+        """
+        Post a resting GTC/GTD order created by create_limit_order().
+        
+        Post-only orders that would cross the current best ask are rejected
+        locally before signing; the CLOB enforces the same rule on its side.
+        Whatever matches on placement is recorded in size_matched.
+        
+        Args:
+            order: Resting order to post
+            
+        Returns:
+            bool: True if the order was accepted (resting or matched)
+            
+        Raises:
+            OrderError: If CLOB submission fails
+        """
+        if not order.is_resting:
+            raise ValueError(f"post_limit_order needs a GTC/GTD order, got {order.order_type}")
+        
+        if self.dry_run:
+            logger.info(f"DRY RUN: Simulating maker fill for {order.order_id} @ {order.price}")
+            order.status = ORDER_STATUS_MATCHED
+            self._record_matched(order, order.size)
+            return True
+        
+        if order.post_only:
+            best_ask = self.get_best_ask(order.market_id)
+            if best_ask is not None and order.price >= best_ask:
+                self._reject(order, f"{ERR_POST_ONLY_CROSSES} (price {order.price} >= best ask {best_ask})")
+                return False
+        
+        try:
+            resp = self._sign_and_post(order)
+        except Exception as e:
+            logger.error(f"❌ LIMIT ORDER FAILED: {e}")
+            raise OrderError(f"CLOB submission failed: {e}")
+        
+        if not isinstance(resp, dict):
+            order.status = ORDER_STATUS_LIVE
+            return True
+        
+        if resp.get("success") is False or resp.get("errorMsg"):
+            self._reject(order, resp.get("errorMsg") or "order rejected")
+            return False
+        
+        exchange_id = resp.get("orderID") or resp.get("order_id")
+        if exchange_id and exchange_id != order.order_id:
+            self._active_orders.pop(order.order_id, None)
+            order.order_id = exchange_id
+            self._active_orders[exchange_id] = order
+        
+        status = str(resp.get("status", "live")).lower()
+        order.status = ORDER_STATUS_MATCHED if status == "matched" else ORDER_STATUS_LIVE
+        try:
+            # BUY: takingAmount is shares received on placement
+            matched = Decimal(str(resp.get("takingAmount") or 0))
+        except (ArithmeticError, ValueError):
+            matched = Decimal("0")
+        if order.status == ORDER_STATUS_MATCHED:
+            matched = order.size
+        if matched > 0:
+            self._record_matched(order, matched)
+        
+        logger.info(
+            f"📌 Resting {order.order_type} order {order.order_id}: "
+            f"{order.side} {order.size} @ {order.price} "
+            f"(post_only={order.post_only}, matched={order.size_matched}, status={order.status})"
+        )
+        return True
+    
+    async def refresh_order(self, order_id: str) -> Order:
+        """
+        Refresh a resting order from the CLOB (status and matched size).
+        
+        Terminal orders (matched, cancelled, expired) leave the active set.
+        
+        Args:
+            order_id: Order identifier
+            
+        Returns:
+            Order: The updated order
+            
+        Raises:
+            OrderError: If the order is not tracked
+        """
+        order = self._active_orders.get(order_id)
+        if order is None:
+            raise OrderError(f"Order not found: {order_id}")
+        
+        if not self.dry_run:
+            self._sync_order(order)
+        
+        if order.status in TERMINAL_ORDER_STATUSES:
+            self._active_orders.pop(order.order_id, None)
+        return order
+    
+    async def refresh_resting_orders(self) -> List[Order]:
+        """
+        Refresh every live resting order and expire stale GTD orders.
+        
+        Returns:
+            List[Order]: Orders whose matched size or status changed
+        """
+        changed = []
+        for order in list(self._active_orders.values()):
+            if not order.is_resting or order.status != ORDER_STATUS_LIVE:
+                continue
+            before = (order.size_matched, order.status)
+            try:
+                await self.refresh_order(order.order_id)
+            except Exception as e:
+                logger.warning(f"⚠️ Could not refresh order {order.order_id}: {e}")
+                continue
+            if (order.size_matched, order.status) != before:
+                changed.append(order)
+        
+        for order in await self.expire_orders():
+            if order not in changed:
+                changed.append(order)
+        return changed
+    
+    async def expire_orders(self, now: Optional[float] = None) -> List[Order]:
+        """
+        Expire GTD orders whose lifetime has passed.
+        
+        The CLOB expires GTD orders itself; anything still live past its
+        expiry (minus the security threshold) is cancelled explicitly.
+        
+        Args:
+            now: Epoch seconds (default: the manager clock)
+            
+        Returns:
+            List[Order]: Orders that expired
+        """
+        now = self.clock() if now is None else now
+        expired = []
+        for order in list(self._active_orders.values()):
+            if order.order_type != "GTD" or order.status != ORDER_STATUS_LIVE:
+                continue
+            if order.expiration - GTD_SECURITY_THRESHOLD_SECONDS > now:
+                continue
+            try:
+                if not self.dry_run:
+                    self._sync_order(order)
+                    if order.status == ORDER_STATUS_LIVE:
+                        self.clob_client.cancel(order.order_id)
+                        self._sync_order(order)
+            except Exception as e:
+                logger.warning(f"⚠️ Could not cancel expired order {order.order_id}: {e}")
+            if order.status != ORDER_STATUS_MATCHED:
+                order.status = ORDER_STATUS_EXPIRED
+            self._active_orders.pop(order.order_id, None)
+            logger.info(f"⌛ GTD order expired: {order.order_id} (matched {order.size_matched}/{order.size})")
+            expired.append(order)
+        return expired
+    
+    async def cancel_replace(
+        self,
+        order_id: str,
+        price: Decimal,
+        size: Optional[Decimal] = None
+    ) -> Optional[Order]:
+        """
+        Cancel a resting order and post a replacement at a new price.
+        
+        The replacement keeps the original side, type, post-only flag and
+        GTD expiry. By default it is sized to what the original left unfilled.
+        
+        Args:
+            order_id: Resting order to replace
+            price: New limit price
+            size: New size (default: remaining unfilled size)
+            
+        Returns:
+            Optional[Order]: The replacement, or None if the original could not
+            be cancelled, is fully filled, or the replacement was rejected
+        """
+        old = self._active_orders.get(order_id)
+        if old is None:
+            raise OrderError(f"Order not found: {order_id}")
+        if not old.is_resting:
+            raise ValueError(f"Only GTC/GTD orders can be replaced, got {old.order_type}")
+        
+        if not await self.cancel_order(order_id):
+            return None
+        
+        new_size = old.remaining_size if size is None else size
+        if new_size <= 0:
+            logger.info(f"Order {order_id} fully matched before replace; nothing to repost")
+            return None
+        
+        new = self.create_limit_order(
+            market_id=old.market_id,
+            side=old.side,
+            price=price,
+            size=new_size,
+            order_type="GTC",
+            post_only=old.post_only,
+            neg_risk=old.neg_risk,
+            tick_size=old.tick_size
+        )
+        new.order_type = old.order_type
+        new.expiration = old.expiration
+        new.replaces = old.order_id
+        
+        logger.info(f"🔁 Cancel-replace {order_id}: {old.price} -> {price} ({new_size} shares)")
+        if not await self.post_limit_order(new):
+            return None
+        return new
+    
+    async def requote(self, order_id: str, price: Decimal) -> Optional[Order]:
+        """
+        Move a resting order to a new price if it changed by at least one tick.
+        
+        Args:
+            order_id: Resting order to requote
+            price: Target limit price
+            
+        Returns:
+            Optional[Order]: The order now working (unchanged or replacement),
+            or None if nothing is left working
+        """
+        order = self._active_orders.get(order_id)
+        if order is None:
+            raise OrderError(f"Order not found: {order_id}")
+        if abs(price - order.price) < Decimal(order.tick_size):
+            return order
+        return await self.cancel_replace(order_id, price)
+    
+    async def cancel_resting_orders(self, token_ids: Optional[List[str]] = None) -> int:
+        """
+        Cancel all live resting orders, optionally only for some tokens.
+        
+        Returns:
+            int: Number of orders cancelled
+        """
+        cancelled = 0
+        for order in list(self._active_orders.values()):
+            if not order.is_resting or order.status != ORDER_STATUS_LIVE:
+                continue
+            if token_ids is not None and order.market_id not in token_ids:
+                continue
+            try:
+                if await self.cancel_order(order.order_id):
+                    cancelled += 1
+            except OrderError as e:
+                logger.warning(f"⚠️ {e}")
+        return cancelled
+    
+    def _sync_order(self, order: Order) -> None:
+        """Apply the CLOB's view of an order (size_matched / status)."""
+        data = self.clob_client.get_order(order.order_id)
+        if not isinstance(data, dict):
+            return
+        try:
+            matched = Decimal(str(data.get("size_matched") or 0))
+        except (ArithmeticError, ValueError, InvalidOperation):
+            matched = order.size_matched
+        if matched > order.size_matched:
+            self._record_matched(order, matched)
+        
+        status = str(data.get("status", "")).upper()
+        if status == ORDER_STATUS_MATCHED or order.size_matched >= order.size:
+            order.status = ORDER_STATUS_MATCHED
+        elif status.startswith("CANCEL"):
+            expired = order.order_type == "GTD" and order.expiration - GTD_SECURITY_THRESHOLD_SECONDS <= self.clock()
+            order.status = ORDER_STATUS_EXPIRED if expired else ORDER_STATUS_CANCELED
+        elif status == ORDER_STATUS_LIVE:
+            order.status = ORDER_STATUS_LIVE
+    
+    def _record_matched(self, order: Order, matched: Decimal) -> None:
+        """Record cumulative matched size; maker fills happen at the limit price."""
+        if matched > order.size_matched:
+            logger.info(f"💱 Order {order.order_id} matched {matched}/{order.size} @ {order.price}")
+        order.size_matched = min(matched, order.size)
+        if order.fill_price is None:
+            order.fill_price = order.price
+        if order.size_matched >= order.size:
+            order.filled = True
+            order.status = ORDER_STATUS_MATCHED
+            self._active_orders.pop(order.order_id, None)
+    
+    def _reject(self, order: Order, error: str) -> None:
+        logger.warning(f"⚠️ Limit order rejected: {error}")
+        order.status = ORDER_STATUS_REJECTED
+        order.error_message = error
+        self._active_orders.pop(order.order_id, None)
+    
     def _validate_fill_price(self, order: Order, fill_price: Decimal) -> bool:
         """
         Validate that fill price is within slippage tolerance.
@@ -460,23 +873,17 @@ class OrderManager:
         """
         Submit order to CLOB.
         
-        This is a placeholder implementation. In production, this would:
-        1. Call the Polymarket CLOB API to submit the order
-        2. Wait for order to fill or reject
-        3. Return fill details
+        Posts the order with its order type (FOK) and reads the CLOB response:
+        rejected orders (success=False / errorMsg) and orders left resting on
+        the book are reported as not filled. When the response carries
+        makingAmount/takingAmount, the fill price is the average matched price.
         
         Args:
             order: Order to submit
             
         Returns:
-            dict: Order result with 'filled', 'fill_price', 'tx_hash'
+            dict: Order result with 'filled', 'fill_price', 'tx_hash' (and 'error' if rejected)
         """
-        # Placeholder implementation
-        # In production, this would interact with the CLOB API
-        
-        # For now, simulate order submission
-        # This will be replaced with actual CLOB API calls
-        
         if self.dry_run:
             logger.info(f"DRY RUN: Simulating order submission for {order.order_id}")
             # Simulate network delay
@@ -489,77 +896,91 @@ class OrderManager:
             }
             
         try:
-            from py_clob_client.clob_types import OrderArgs, OrderType
-            from py_clob_client.constants import AMOY
-            
-            # 1. Create OrderArgs
-            order_args = OrderArgs(
-                price=float(order.price),
-                size=float(order.size),
-                side="BUY" if order.side == "YES" else "BUY",  # Both are BUY orders in recent CLOB version? 
-                # Wait, "side" in Order object is YES/NO outcome side. 
-                # In CLOB, we buy the TOKEN. 
-                # The token_id in the order implies the outcome?
-                # Actually, clob_client.create_order() usually takes token_id, price, side(BUY/SELL).
-                # But here we are passing order.market_id as market_id? 
-                # Wait, Order struct has 'market_id' which might be token_id?
-                # Let's check how it's called.
-                # In NegRiskArbitrageEngine: market_id=outcome.token_id.
-                # So order.market_id holds the TOKEN ID.
-                token_id=order.market_id,
-            )
-            
-            # The side "YES" or "NO" in the Order object is metadata.
-            # IN POLYMARKET CLOB: You BUY a token (YES or NO token).
-            # So the side passed to CLOB should be "BUY".
-            # (Unless we are selling, but NegRisk "buy_all" strategy means BUYing tokens)
-            
-            logger.info(f"🚀 SUBMITTING REAL ORDER: {order.order_id} | Token: {order.market_id} | Price: {order.price} | Size: {order.size}")
-            
-            # 2. Create signed order
-            # Note: create_order signature depends on py-clob-client version.
-            # Assuming standard usage: create_order(OrderArgs)
-            signed_order = self.clob_client.create_order(
-                OrderArgs(
-                    price=float(order.price),
-                    size=float(order.size),
-                    side="BUY", # We are always buying the outcome token
-                    token_id=order.market_id,
-                )
-            )
+            logger.info(f"🚀 SUBMITTING REAL ORDER: {order.order_id} | Token: {order.market_id} | Price: {order.price} | Size: {order.size} | NegRisk: {order.neg_risk}")
             
-            # 3. Post order
-            resp = self.clob_client.post_order(signed_order)
+            resp = self._sign_and_post(order)
             
             logger.info(f"✅ Order submitted! Response: {resp}")
             
-            # 4. Parse response
-            # Response might be a dict with 'transactionHash' or 'orderID'
-            # Or it might be a list of fills if FOK?
-            # CLOB API usually returns order ID.
-            
-            tx_hash = None
-            if isinstance(resp, dict):
-                tx_hash = resp.get("transactionHash") or resp.get("hash")
-                order_id = resp.get("orderID") or resp.get("order_id")
-                if order_id:
-                     order.order_id = order_id # Update with real ID
-            
-            # For FOK orders, if it returns, it usually means filled (or partially?).
-            # But post_order might just return the order ID.
-            # If FOK failed, it might raise exception or return error.
+            if not isinstance(resp, dict):
+                return {
+                    'filled': True,
+                    'fill_price': order.price,
+                    'tx_hash': f"0x{uuid.uuid4().hex}"
+                }
+            
+            order_id = resp.get("orderID") or resp.get("order_id")
+            if order_id:
+                order.order_id = order_id  # Update with real ID
+            
+            if resp.get("success") is False or resp.get("errorMsg"):
+                error = resp.get("errorMsg") or "order rejected"
+                logger.warning(f"⚠️ Order rejected by CLOB: {error}")
+                order.error_message = error
+                return {'filled': False, 'fill_price': None, 'tx_hash': None, 'error': error}
+            
+            status = str(resp.get("status", "")).lower()
+            if status in ("live", "unmatched", "delayed"):
+                logger.warning(f"⚠️ Order not matched (status={status}): {order.order_id}")
+                return {'filled': False, 'fill_price': None, 'tx_hash': None, 'error': f"order {status}"}
+            
+            # BUY: makingAmount is USDC paid, takingAmount is shares received
+            fill_price = order.price
+            try:
+                making = Decimal(str(resp.get("makingAmount") or 0))
+                taking = Decimal(str(resp.get("takingAmount") or 0))
+                if making > 0 and taking > 0:
+                    fill_price = making / taking
+            except (ArithmeticError, ValueError):
+                pass
+            
+            tx_hashes = resp.get("transactionsHashes") or []
+            tx_hash = resp.get("transactionHash") or resp.get("hash") or (tx_hashes[0] if tx_hashes else None)
             
-            # Assuming success if no exception raised
             return {
                 'filled': True,
-                'fill_price': order.price, # Assume filled at price for now (FOK)
-                'tx_hash': tx_hash or f"0x{uuid.uuid4().hex}" # Fallback if no hash immediately
+                'fill_price': fill_price,
+                'tx_hash': tx_hash or f"0x{uuid.uuid4().hex}"  # Fallback if no hash immediately
             }
             
         except Exception as e:
             logger.error(f"❌ REAL ORDER FAILED: {e}")
             raise OrderError(f"CLOB submission failed: {e}")
     
+    def _sign_and_post(self, order: Order):
+        """Sign a BUY order for order.market_id and post it with its order type."""
+        from py_clob_client.clob_types import OrderArgs
+        from types import SimpleNamespace
+        
+        # IN POLYMARKET CLOB: You BUY the outcome token (YES or NO token).
+        # order.market_id holds the TOKEN ID; order.side (YES/NO) is metadata.
+        order_args = OrderArgs(
+            price=float(order.price),
+            size=float(order.size),
+            side="BUY",
+            token_id=order.market_id,
+            expiration=order.expiration,
+        )
+        
+        # Use SimpleNamespace for options (matching FifteenMinuteCryptoStrategy)
+        options = SimpleNamespace(
+            tick_size=order.tick_size,
+            neg_risk=order.neg_risk
+        )
+        
+        signed_order = self.clob_client.create_order(order_args, options=options)
+        if order.post_only and self._supports_post_only():
+            return self.clob_client.post_order(signed_order, order.order_type, post_only=True)
+        return self.clob_client.post_order(signed_order, order.order_type)
+    
+    def _supports_post_only(self) -> bool:
+        """Older py-clob-client releases have no post_only flag; the local book check still applies."""
+        try:
+            params = inspect.signature(self.clob_client.post_order).parameters
+        except (TypeError, ValueError):
+            return False
+        return "post_only" in params or any(p.kind == p.VAR_KEYWORD for p in params.values())
+    
     def get_active_orders(self) -> list[Order]:
         """
         Get list of active orders.
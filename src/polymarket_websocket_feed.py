@@ -14,7 +14,7 @@ import logging
 import time
 from datetime import datetime
 from decimal import Decimal
-from typing import Dict, Optional, Set, Callable
+from typing import Any, Dict, Optional, Set, Callable
 from dataclasses import dataclass
 import aiohttp
 
@@ -50,7 +50,8 @@ class PolymarketWebSocketFeed:
         on_price_update: Optional[Callable[[TokenPrice], None]] = None,
         initial_reconnect_delay: float = 1.0,
         max_reconnect_delay: float = 60.0,
-        heartbeat_interval: float = 30.0
+        heartbeat_interval: float = 30.0,
+        recorder: Optional[Any] = None
     ):
         """
         Initialize Polymarket WebSocket feed.
@@ -60,8 +61,10 @@ class PolymarketWebSocketFeed:
             initial_reconnect_delay: Initial delay before reconnection (seconds)
             max_reconnect_delay: Maximum delay before reconnection (seconds)
             heartbeat_interval: Interval for heartbeat checks (seconds)
+            recorder: Optional MarketDataRecorder for books, deltas and trades
         """
         self.on_price_update = on_price_update
+        self.recorder = recorder
         self.initial_reconnect_delay = initial_reconnect_delay
         self.max_reconnect_delay = max_reconnect_delay
         self.heartbeat_interval = heartbeat_interval
@@ -281,6 +284,9 @@ class PolymarketWebSocketFeed:
             data = json.loads(message)
             msg_type = data.get("type", "")
             
+            if self.recorder is not None:
+                self._record_message(msg_type, data)
+            
             if msg_type == "book":
                 # Orderbook update
                 await self._handle_book_update(data)
@@ -303,6 +309,29 @@ class PolymarketWebSocketFeed:
         except Exception as e:
             logger.error(f"Error processing message: {e}")
     
+    def _record_message(self, msg_type: str, data: Dict):
+        """Pass book snapshots, deltas and trades to the recorder."""
+        asset_id = data.get("asset_id", "")
+        if not asset_id:
+            return
+        exchange_ts = data.get("timestamp")
+        
+        if msg_type == "book":
+            self.recorder.record_book(
+                asset_id, data.get("bids", []), data.get("asks", []),
+                source="polymarket_ws", exchange_ts=exchange_ts
+            )
+        elif msg_type == "price_change":
+            self.recorder.record_book_delta(
+                asset_id, data.get("changes", []),
+                source="polymarket_ws", exchange_ts=exchange_ts
+            )
+        elif msg_type == "last_trade_price":
+            self.recorder.record_trade(
+                asset_id, data.get("price"), source="polymarket_ws",
+                size=data.get("size"), side=data.get("side"), exchange_ts=exchange_ts
+            )
+    
     async def _handle_book_update(self, data: Dict):
         """
         Handle orderbook update message.
@@ -399,6 +428,30 @@ class PolymarketWebSocketFeed:
         async with self._cache_lock:
             return self._price_cache.copy()
     
+    def staleness_seconds(self) -> Optional[float]:
+        """
+        Seconds since the last message.
+        
+        Returns:
+            None until the first message arrives
+        """
+        if self._last_message_time is None:
+            return None
+        return (datetime.now() - self._last_message_time).total_seconds()
+    
+    def get_cached_price(self, token_id: str) -> Optional[Decimal]:
+        """
+        Latest cached price for a token, without waiting for the cache lock.
+        
+        Args:
+            token_id: Token ID
+            
+        Returns:
+            Price or None if not available
+        """
+        token_price = self._price_cache.get(token_id)
+        return token_price.price if token_price is not None else None
+    
     def get_statistics(self) -> Dict:
         """
         Get feed statistics.
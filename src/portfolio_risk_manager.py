@@ -141,8 +141,9 @@ class PortfolioRiskManager:
         Returns:
             RiskMetrics with approval status
         """
-        # Check for daily reset
+        # Check for daily reset and an expired halt
         self._check_daily_reset()
+        self._check_halt_expired()
         
         # Calculate current metrics
         total_exposure = self._calculate_total_exposure()
@@ -331,6 +332,10 @@ class PortfolioRiskManager:
         
         return realized_pnl
     
+    def release_position(self, market_id: str) -> None:
+        """Stop counting a position's exposure without booking a result (P&L realized on redemption)."""
+        self._positions.pop(market_id, None)
+    
     def get_portfolio_state(self) -> Dict[str, Any]:
         """Get current portfolio state for LLM context."""
         total_exposure = self._calculate_total_exposure()
@@ -0,0 +1,682 @@
+"""
+Position and PnL Ledger for Polymarket Arbitrage Bot.
+
+One authoritative, append-only record of every fill, fee, merge, redemption,
+transfer and reconciliation adjustment. Positions, cost basis, cash and
+realized/unrealized PnL are derived from the entries instead of being kept
+separately by each strategy (active_positions.json, PortfolioRiskManager,
+CorrelationAnalyzer, TradeHistoryDB).
+
+LedgerReconciler periodically compares the ledger with on-chain token balances
+and the USDC balance, flags drift and orphan positions, and repairs the ledger
+with adjustment entries.
+
+Validates Requirements:
+- Single ledger of fills, fees, merges, redemptions and transfers (SQLite)
+- Average-cost positions with realized and unrealized PnL
+- Reconciliation against on-chain token and USDC balances
+- Drift and orphan position detection and repair
+"""
+
+import logging
+import sqlite3
+import uuid
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from pathlib import Path
+from typing import Awaitable, Callable, Dict, List, Optional, Sequence
+
+logger = logging.getLogger(__name__)
+
+# Entry kinds
+FILL = "fill"
+FEE = "fee"
+MERGE = "merge"
+REDEMPTION = "redemption"
+TRANSFER = "transfer"
+ADJUSTMENT = "adjustment"
+
+ENTRY_KINDS = (FILL, FEE, MERGE, REDEMPTION, TRANSFER, ADJUSTMENT)
+
+# Share and USDC amounts below this are treated as zero
+DUST = Decimal("0.000001")
+
+
+@dataclass
+class LedgerEntry:
+    """One ledger line: a signed change in shares of a token and/or USDC cash."""
+    entry_id: str
+    timestamp: datetime
+    kind: str
+    token_id: Optional[str] = None
+    market_id: str = ""
+    outcome: str = ""  # "UP"/"DOWN"/"YES"/"NO"
+    strategy: str = ""
+    shares: Decimal = Decimal("0")  # Signed change in token shares
+    usdc: Decimal = Decimal("0")  # Signed change in USDC cash (fees included)
+    price: Optional[Decimal] = None
+    fee: Decimal = Decimal("0")
+    reference: Optional[str] = None  # Order ID or transaction hash
+    note: str = ""
+
+
+@dataclass
+class LedgerPosition:
+    """Position in one token derived from the ledger (average cost)."""
+    token_id: str
+    market_id: str = ""
+    outcome: str = ""
+    strategy: str = ""
+    shares: Decimal = Decimal("0")
+    cost_basis: Decimal = Decimal("0")
+    realized_pnl: Decimal = Decimal("0")
+    fees: Decimal = Decimal("0")
+
+    @property
+    def is_open(self) -> bool:
+        return self.shares > DUST
+
+    @property
+    def avg_price(self) -> Decimal:
+        """Average cost per share (fees included)."""
+        return self.cost_basis / self.shares if self.shares > DUST else Decimal("0")
+
+    def unrealized_pnl(self, mark: Decimal) -> Decimal:
+        """Mark-to-market PnL of the open shares."""
+        return self.shares * mark - self.cost_basis
+
+
+@dataclass
+class PnLSummary:
+    """Ledger-wide PnL at a set of marks."""
+    realized_pnl: Decimal
+    unrealized_pnl: Decimal
+    fees: Decimal
+    cash: Decimal
+    position_value: Decimal
+    open_positions: int
+
+    @property
+    def total_pnl(self) -> Decimal:
+        return self.realized_pnl + self.unrealized_pnl
+
+    @property
+    def equity(self) -> Decimal:
+        return self.cash + self.position_value
+
+
+class PositionLedger:
+    """
+    SQLite ledger of all position and cash movements.
+
+    Features:
+    - Append-only entries (fills, fees, merges, redemptions, transfers, adjustments)
+    - Average-cost positions with realized PnL on every reduction
+    - Unrealized PnL and equity at caller-supplied marks
+    - Cash balance from the sum of USDC movements
+    """
+
+    def __init__(self, db_path: str = "data/position_ledger.db"):
+        """
+        Initialize the ledger.
+
+        Args:
+            db_path: Path to the SQLite database file
+        """
+        self.db_path = db_path
+        Path(db_path).parent.mkdir(parents=True, exist_ok=True)
+        self._init_schema()
+
+        # Positions are rebuilt from the entries after every write
+        self._positions: Optional[Dict[str, LedgerPosition]] = None
+        self._unattributed_fees = Decimal("0")
+
+        # Called with every recorded fill (e.g. fill alerts); errors are logged, never raised
+        self.fill_listeners: List[Callable[[LedgerEntry], None]] = []
+
+        logger.info(f"📒 Position ledger initialized: {db_path}")
+
+    @contextmanager
+    def _get_connection(self):
+        """Context manager for database connections."""
+        conn = sqlite3.connect(self.db_path)
+        conn.row_factory = sqlite3.Row
+        try:
+            yield conn
+            conn.commit()
+        except Exception:
+            conn.rollback()
+            raise
+        finally:
+            conn.close()
+
+    def _init_schema(self) -> None:
+        """Create the entries table."""
+        with self._get_connection() as conn:
+            conn.execute("""
+                CREATE TABLE IF NOT EXISTS ledger_entries (
+                    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                    entry_id TEXT UNIQUE NOT NULL,
+                    timestamp TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    token_id TEXT,
+                    market_id TEXT NOT NULL,
+                    outcome TEXT NOT NULL,
+                    strategy TEXT NOT NULL,
+                    shares TEXT NOT NULL,
+                    usdc TEXT NOT NULL,
+                    price TEXT,
+                    fee TEXT NOT NULL,
+                    reference TEXT,
+                    note TEXT NOT NULL
+                )
+            """)
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_ledger_token ON ledger_entries(token_id)")
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_ledger_kind ON ledger_entries(kind)")
+
+    # ============================================================
+    # RECORDING
+    # ============================================================
+
+    def _append(self, entry: LedgerEntry) -> LedgerEntry:
+        """Persist an entry and invalidate derived positions."""
+        if entry.kind not in ENTRY_KINDS:
+            raise ValueError(f"Unknown ledger entry kind: {entry.kind}")
+        with self._get_connection() as conn:
+            conn.execute("""
+                INSERT INTO ledger_entries (
+                    entry_id, timestamp, kind, token_id, market_id, outcome, strategy,
+                    shares, usdc, price, fee, reference, note
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            """, (
+                entry.entry_id,
+                entry.timestamp.isoformat(),
+                entry.kind,
+                entry.token_id,
+                entry.market_id,
+                entry.outcome,
+                entry.strategy,
+                str(entry.shares),
+                str(entry.usdc),
+                str(entry.price) if entry.price is not None else None,
+                str(entry.fee),
+                entry.reference,
+                entry.note,
+            ))
+        self._positions = None
+        return entry
+
+    @staticmethod
+    def _new_entry(kind: str, **kwargs) -> LedgerEntry:
+        return LedgerEntry(
+            entry_id=uuid.uuid4().hex[:12],
+            timestamp=kwargs.pop("timestamp", None) or datetime.now(timezone.utc),
+            kind=kind,
+            **kwargs
+        )
+
+    def record_fill(
+        self,
+        token_id: str,
+        side: str,
+        size: Decimal,
+        price: Decimal,
+        fee: Decimal = Decimal("0"),
+        market_id: str = "",
+        outcome: str = "",
+        strategy: str = "",
+        order_id: Optional[str] = None,
+        timestamp: Optional[datetime] = None
+    ) -> LedgerEntry:
+        """
+        Record a matched trade.
+
+        Args:
+            token_id: Outcome token traded
+            side: "BUY" or "SELL"
+            size: Shares matched (positive)
+            price: Fill price per share
+            fee: USDC fee paid on the fill
+            market_id: Market (condition) ID
+            outcome: Outcome label ("UP", "DOWN", "YES", "NO")
+            strategy: Strategy that placed the order
+            order_id: Exchange order ID
+            timestamp: Fill time (default: now)
+        """
+        side = side.upper()
+        if side not in ("BUY", "SELL"):
+            raise ValueError(f"Invalid fill side: {side}")
+        size, price, fee = Decimal(str(size)), Decimal(str(price)), Decimal(str(fee))
+        if size <= 0:
+            raise ValueError(f"Fill size must be positive, got {size}")
+
+        notional = size * price
+        entry = self._new_entry(
+            FILL,
+            token_id=token_id,
+            market_id=market_id,
+            outcome=outcome,
+            strategy=strategy,
+            shares=size if side == "BUY" else -size,
+            usdc=(-notional if side == "BUY" else notional) - fee,
+            price=price,
+            fee=fee,
+            reference=order_id,
+            timestamp=timestamp
+        )
+        logger.debug(f"📒 {side} {size} {outcome or token_id[:16]} @ ${price} (fee ${fee})")
+        self._append(entry)
+        for listener in self.fill_listeners:
+            try:
+                listener(entry)
+            except Exception as e:
+                logger.error(f"Ledger fill listener failed: {e}")
+        return entry
+
+    def record_fee(
+        self,
+        amount: Decimal,
+        token_id: Optional[str] = None,
+        market_id: str = "",
+        reference: Optional[str] = None,
+        note: str = ""
+    ) -> LedgerEntry:
+        """Record a USDC fee not attached to a fill (e.g. relayer or gas reimbursement)."""
+        amount = Decimal(str(amount))
+        return self._append(self._new_entry(
+            FEE, token_id=token_id, market_id=market_id, usdc=-amount, fee=amount,
+            reference=reference, note=note
+        ))
+
+    def record_merge(
+        self,
+        market_id: str,
+        token_ids: Sequence[str],
+        shares: Decimal,
+        usdc_received: Decimal,
+        tx_hash: Optional[str] = None
+    ) -> List[LedgerEntry]:
+        """
+        Record a merge of a full outcome set back into USDC.
+
+        The proceeds are split evenly across the merged tokens.
+        """
+        shares, usdc_received = Decimal(str(shares)), Decimal(str(usdc_received))
+        share_of_proceeds = usdc_received / len(token_ids)
+        return [
+            self._append(self._new_entry(
+                MERGE, token_id=token_id, market_id=market_id, shares=-shares,
+                usdc=share_of_proceeds, reference=tx_hash
+            ))
+            for token_id in token_ids
+        ]
+
+    def record_redemption(
+        self,
+        token_id: str,
+        shares: Decimal,
+        payout: Decimal,
+        market_id: str = "",
+        tx_hash: Optional[str] = None
+    ) -> LedgerEntry:
+        """Record redeeming resolved shares (payout is 0 for losing shares)."""
+        return self._append(self._new_entry(
+            REDEMPTION, token_id=token_id, market_id=market_id,
+            shares=-Decimal(str(shares)), usdc=Decimal(str(payout)), reference=tx_hash
+        ))
+
+    def record_transfer(
+        self,
+        usdc: Decimal = Decimal("0"),
+        token_id: Optional[str] = None,
+        shares: Decimal = Decimal("0"),
+        reference: Optional[str] = None,
+        note: str = ""
+    ) -> LedgerEntry:
+        """
+        Record funds or shares moving in (positive) or out (negative) of the wallet.
+
+        Transfers are not PnL: deposits and withdrawals only change cash, and
+        shares transferred in carry no cost basis.
+        """
+        return self._append(self._new_entry(
+            TRANSFER, token_id=token_id, shares=Decimal(str(shares)), usdc=Decimal(str(usdc)),
+            reference=reference, note=note
+        ))
+
+    def record_adjustment(
+        self,
+        token_id: Optional[str] = None,
+        shares: Decimal = Decimal("0"),
+        usdc: Decimal = Decimal("0"),
+        market_id: str = "",
+        note: str = ""
+    ) -> LedgerEntry:
+        """Record a correction made by reconciliation."""
+        return self._append(self._new_entry(
+            ADJUSTMENT, token_id=token_id, market_id=market_id,
+            shares=Decimal(str(shares)), usdc=Decimal(str(usdc)), note=note
+        ))
+
+    # ============================================================
+    # QUERIES
+    # ============================================================
+
+    def entries(self, token_id: Optional[str] = None, kind: Optional[str] = None) -> List[LedgerEntry]:
+        """Ledger entries in the order they were recorded."""
+        query = "SELECT * FROM ledger_entries"
+        clauses, params = [], []
+        if token_id is not None:
+            clauses.append("token_id = ?")
+            params.append(token_id)
+        if kind is not None:
+            clauses.append("kind = ?")
+            params.append(kind)
+        if clauses:
+            query += " WHERE " + " AND ".join(clauses)
+        query += " ORDER BY seq"
+
+        with self._get_connection() as conn:
+            rows = conn.execute(query, params).fetchall()
+        return [
+            LedgerEntry(
+                entry_id=row["entry_id"],
+                timestamp=datetime.fromisoformat(row["timestamp"]),
+                kind=row["kind"],
+                token_id=row["token_id"],
+                market_id=row["market_id"],
+                outcome=row["outcome"],
+                strategy=row["strategy"],
+                shares=Decimal(row["shares"]),
+                usdc=Decimal(row["usdc"]),
+                price=Decimal(row["price"]) if row["price"] is not None else None,
+                fee=Decimal(row["fee"]),
+                reference=row["reference"],
+                note=row["note"],
+            )
+            for row in rows
+        ]
+
+    def _rebuild(self) -> Dict[str, LedgerPosition]:
+        """Replay all entries into average-cost positions."""
+        if self._positions is not None:
+            return self._positions
+
+        positions: Dict[str, LedgerPosition] = {}
+        unattributed_fees = Decimal("0")
+        for entry in self.entries():
+            if entry.token_id is None:
+                # Cash-only entries: only fees affect PnL
+                if entry.kind == FEE:
+                    unattributed_fees += entry.fee
+                continue
+
+            position = positions.setdefault(entry.token_id, LedgerPosition(token_id=entry.token_id))
+            position.market_id = position.market_id or entry.market_id
+            position.outcome = position.outcome or entry.outcome
+            position.strategy = position.strategy or entry.strategy
+            position.fees += entry.fee
+
+            if entry.kind == FEE:
+                position.realized_pnl -= entry.fee
+            elif entry.shares > 0:
+                # Buys carry their cost (fee included); transfers and adjustments come in at zero cost
+                position.shares += entry.shares
+                if entry.kind == FILL:
+                    position.cost_basis += -entry.usdc
+            elif entry.shares < 0:
+                reduced = min(-entry.shares, position.shares)
+                released = position.cost_basis * reduced / position.shares if position.shares > 0 else Decimal("0")
+                position.shares -= reduced
+                position.cost_basis -= released
+                if entry.kind != TRANSFER:
+                    position.realized_pnl += entry.usdc - released
+                if position.shares <= DUST:
+                    position.shares = Decimal("0")
+                    position.cost_basis = Decimal("0")
+
+        self._positions = positions
+        self._unattributed_fees = unattributed_fees
+        return positions
+
+    def positions(self, include_closed: bool = False) -> Dict[str, LedgerPosition]:
+        """Positions by token ID (open ones only unless include_closed)."""
+        positions = self._rebuild()
+        return {
+            token_id: position for token_id, position in positions.items()
+            if include_closed or position.is_open
+        }
+
+    def position(self, token_id: str) -> Optional[LedgerPosition]:
+        """Position in one token, if the ledger has ever seen it."""
+        return self._rebuild().get(token_id)
+
+    def cash_balance(self) -> Decimal:
+        """USDC cash implied by the ledger."""
+        with self._get_connection() as conn:
+            rows = conn.execute("SELECT usdc FROM ledger_entries").fetchall()
+        return sum((Decimal(row["usdc"]) for row in rows), Decimal("0"))
+
+    def realized_pnl(self, strategy: Optional[str] = None) -> Decimal:
+        """Realized PnL across all positions (optionally one strategy), net of fees."""
+        positions = self._rebuild().values()
+        total = sum(
+            (p.realized_pnl for p in positions if strategy is None or p.strategy == strategy),
+            Decimal("0")
+        )
+        return total - (self._unattributed_fees if strategy is None else Decimal("0"))
+
+    def unrealized_pnl(self, marks: Dict[str, Decimal]) -> Decimal:
+        """Unrealized PnL of open positions at the given marks (unmarked positions at cost)."""
+        return sum(
+            (p.unrealized_pnl(marks[token_id]) for token_id, p in self.positions().items() if token_id in marks),
+            Decimal("0")
+        )
+
+    def summary(self, marks: Optional[Dict[str, Decimal]] = None) -> PnLSummary:
+        """Realized/unrealized PnL, fees, cash and position value."""
+        marks = marks or {}
+        open_positions = self.positions()
+        all_positions = self._rebuild().values()
+        return PnLSummary(
+            realized_pnl=self.realized_pnl(),
+            unrealized_pnl=self.unrealized_pnl(marks),
+            fees=sum((p.fees for p in all_positions), Decimal("0")) + self._unattributed_fees,
+            cash=self.cash_balance(),
+            position_value=sum(
+                (p.shares * marks[t] if t in marks else p.cost_basis for t, p in open_positions.items()),
+                Decimal("0")
+            ),
+            open_positions=len(open_positions)
+        )
+
+
+# ============================================================
+# RECONCILIATION
+# ============================================================
+
+@dataclass
+class BalanceDrift:
+    """Mismatch between the ledger (or a strategy) and the chain for one token."""
+    token_id: str
+    kind: str  # "drift", "orphan" (held in the ledger, not on chain) or "untracked" (on chain only)
+    ledger_shares: Decimal
+    chain_shares: Decimal
+    market_id: str = ""
+
+    @property
+    def difference(self) -> Decimal:
+        return self.chain_shares - self.ledger_shares
+
+
+@dataclass
+class ReconciliationReport:
+    """Result of one reconciliation pass."""
+    timestamp: datetime
+    drifts: List[BalanceDrift] = field(default_factory=list)
+    orphans: List[str] = field(default_factory=list)  # Strategy-tracked tokens not held on chain
+    chain_balances: Dict[str, Decimal] = field(default_factory=dict)
+    unchecked: List[str] = field(default_factory=list)  # Tokens whose balance query failed
+    settling: List[str] = field(default_factory=list)  # Tokens filled within the settlement grace window
+    ledger_cash: Optional[Decimal] = None
+    chain_cash: Optional[Decimal] = None
+    cash_flagged: bool = False  # Cash differs by more than the tolerance
+    repaired: bool = False
+
+    @property
+    def cash_drift(self) -> Decimal:
+        if self.ledger_cash is None or self.chain_cash is None:
+            return Decimal("0")
+        return self.chain_cash - self.ledger_cash
+
+    @property
+    def has_drift(self) -> bool:
+        return bool(self.drifts or self.orphans or self.cash_flagged)
+
+
+TokenBalanceGetter = Callable[[str], Awaitable[Optional[Decimal]]]
+UsdcBalanceGetter = Callable[[], Awaitable[Optional[Decimal]]]
+
+
+class LedgerReconciler:
+    """
+    Reconciles the ledger with on-chain balances.
+
+    Features:
+    - Per-token comparison of ledger shares with the on-chain balance
+    - USDC cash comparison
+    - Orphan detection for positions strategies still track but no longer hold
+    - Optional repair through adjustment entries (the chain is authoritative)
+    - Tokens with fills still settling on chain are left for the next pass
+    """
+
+    def __init__(
+        self,
+        ledger: PositionLedger,
+        token_balance_getter: TokenBalanceGetter,
+        usdc_balance_getter: Optional[UsdcBalanceGetter] = None,
+        tolerance: Decimal = Decimal("0.01"),
+        repair: bool = True,
+        settlement_grace_seconds: float = 0.0
+    ):
+        """
+        Initialize the reconciler.
+
+        Args:
+            ledger: Ledger to reconcile
+            token_balance_getter: async token_id -> on-chain shares (None if the query failed)
+            usdc_balance_getter: async () -> USDC balance (None to skip cash)
+            tolerance: Differences up to this many shares/USDC are ignored
+            repair: Record adjustments so the ledger matches the chain
+            settlement_grace_seconds: Tokens filled within this many seconds are skipped,
+                and cash is not compared while any are
+        """
+        self.ledger = ledger
+        self.token_balance_getter = token_balance_getter
+        self.usdc_balance_getter = usdc_balance_getter
+        self.tolerance = Decimal(str(tolerance))
+        self.repair = repair
+        self.settlement_grace_seconds = settlement_grace_seconds
+        self.last_report: Optional[ReconciliationReport] = None
+
+    async def reconcile(self, tracked: Optional[Dict[str, Decimal]] = None) -> ReconciliationReport:
+        """
+        Compare the ledger (and strategy-tracked positions) with the chain.
+
+        Args:
+            tracked: Shares by token ID that strategies believe they hold
+
+        Returns:
+            ReconciliationReport with drifts, orphans and cash difference
+        """
+        tracked = tracked or {}
+        report = ReconciliationReport(timestamp=datetime.now(timezone.utc))
+        ledger_positions = self.ledger.positions()
+        settling = self._settling_tokens(report.timestamp)
+
+        for token_id in list(dict.fromkeys([*ledger_positions, *tracked])):
+            if token_id in settling:
+                # The chain may not show this fill yet; a drift now would undo it
+                report.settling.append(token_id)
+                continue
+            try:
+                chain = await self.token_balance_getter(token_id)
+            except Exception as e:
+                logger.warning(f"⚠️ Balance query failed for {token_id[:16]}...: {e}")
+                chain = None
+            if chain is None:
+                report.unchecked.append(token_id)
+                continue
+            chain = Decimal(str(chain))
+            report.chain_balances[token_id] = chain
+
+            position = ledger_positions.get(token_id)
+            ledger_shares = position.shares if position else Decimal("0")
+            if abs(chain - ledger_shares) > self.tolerance:
+                if chain <= self.tolerance:
+                    kind = "orphan"
+                elif ledger_shares <= self.tolerance:
+                    kind = "untracked"
+                else:
+                    kind = "drift"
+                report.drifts.append(BalanceDrift(
+                    token_id=token_id, kind=kind, ledger_shares=ledger_shares, chain_shares=chain,
+                    market_id=position.market_id if position else ""
+                ))
+
+            if token_id in tracked and tracked[token_id] > self.tolerance and chain <= self.tolerance:
+                report.orphans.append(token_id)
+
+        if self.usdc_balance_getter is not None and not settling:
+            try:
+                report.chain_cash = await self.usdc_balance_getter()
+            except Exception as e:
+                logger.warning(f"⚠️ USDC balance query failed: {e}")
+            if report.chain_cash is not None:
+                report.chain_cash = Decimal(str(report.chain_cash))
+                report.ledger_cash = self.ledger.cash_balance()
+                report.cash_flagged = abs(report.cash_drift) > self.tolerance
+
+        for drift in report.drifts:
+            logger.warning(
+                f"⚠️ LEDGER {drift.kind.upper()}: {drift.token_id[:16]}... "
+                f"ledger={drift.ledger_shares} chain={drift.chain_shares}"
+            )
+        for token_id in report.orphans:
+            logger.warning(f"⚠️ ORPHAN POSITION: {token_id[:16]}... tracked but not held on chain")
+        if report.cash_flagged:
+            logger.warning(f"⚠️ CASH DRIFT: ledger=${report.ledger_cash} chain=${report.chain_cash}")
+
+        if self.repair and (report.drifts or report.cash_flagged):
+            self._repair(report)
+
+        if not report.has_drift:
+            logger.debug(f"✅ Ledger reconciled ({len(report.chain_balances)} tokens)")
+        self.last_report = report
+        return report
+
+    def _settling_tokens(self, now: datetime) -> set:
+        """Tokens with a fill newer than the settlement grace window."""
+        if self.settlement_grace_seconds <= 0:
+            return set()
+        cutoff = now - timedelta(seconds=self.settlement_grace_seconds)
+        return {entry.token_id for entry in self.ledger.entries(kind=FILL) if entry.timestamp > cutoff}
+
+    def _repair(self, report: ReconciliationReport) -> None:
+        """Adjust the ledger to match the chain."""
+        for drift in report.drifts:
+            self.ledger.record_adjustment(
+                token_id=drift.token_id,
+                shares=drift.difference,
+                market_id=drift.market_id,
+                note=f"reconcile {drift.kind}: ledger {drift.ledger_shares} -> chain {drift.chain_shares}"
+            )
+        if report.cash_flagged:
+            self.ledger.record_adjustment(
+                usdc=report.cash_drift,
+                note=f"reconcile cash: ledger {report.ledger_cash} -> chain {report.chain_cash}"
+            )
+        report.repaired = True
+        logger.info(f"🔧 Ledger repaired: {len(report.drifts)} token adjustment(s), cash {report.cash_drift if report.cash_flagged else 0:+}")
@@ -14,6 +14,8 @@ from web3.contract import Contract
 from web3.types import TxReceipt, TxParams
 from eth_account.signers.local import LocalAccount
 
+from src.fee_strategy import FeeCapExceededError
+
 logger = logging.getLogger(__name__)
 
 
@@ -98,7 +100,10 @@ class PositionMerger:
         ctf_contract_address: str,
         usdc_address: str,
         wallet: LocalAccount,
-        gas_limit_default: int = 300000
+        gas_limit_default: int = 300000,
+        ledger=None,
+        fee_estimator=None,
+        transaction_manager=None
     ):
         """
         Initialize Position Merger.
@@ -109,10 +114,17 @@ class PositionMerger:
             usdc_address: Address of USDC token contract
             wallet: Wallet account for signing transactions
             gas_limit_default: Default gas limit for merge operations
+            ledger: PositionLedger that records merges (optional)
+            fee_estimator: Eip1559FeeEstimator for merge fees (optional; legacy gas price otherwise)
+            transaction_manager: TransactionManager that sends merges (nonce tracking,
+                journal, replacement); merges are signed and sent directly if None
         """
         self.web3 = web3
         self.wallet = wallet
         self.gas_limit_default = gas_limit_default
+        self.ledger = ledger
+        self.fee_estimator = fee_estimator
+        self.transaction_manager = transaction_manager
         
         # Initialize CTF contract
         self.ctf_contract = web3.eth.contract(
@@ -337,8 +349,78 @@ class PositionMerger:
             amount_wei
         )
         
-        # Get current gas price
-        gas_price = self.web3.eth.gas_price
+        # 5. Sign, send and wait for confirmation
+        if self.transaction_manager is not None:
+            receipt, tx_hash_hex = await self._send_with_transaction_manager(merge_func, gas_limit)
+        else:
+            receipt, tx_hash_hex = await self._send_directly(merge_func, gas_limit)
+        
+        # 6. Verify USDC redemption (Requirement 1.6)
+        usdc_after = await self.get_usdc_balance()
+        usdc_redeemed = usdc_after - usdc_before
+        
+        logger.debug(
+            f"USDC balance after merge: {usdc_after} "
+            f"(redeemed: {usdc_redeemed})"
+        )
+        
+        # Expected redemption: $1.00 per position pair
+        expected_redemption = amount
+        
+        # Allow small tolerance for rounding (0.01 USDC)
+        tolerance = Decimal('0.01')
+        
+        if abs(usdc_redeemed - expected_redemption) > tolerance:
+            raise MergeRedemptionError(
+                f"Unexpected redemption amount: "
+                f"expected {expected_redemption}, got {usdc_redeemed}"
+            )
+        
+        logger.info(
+            f"Merge successful: redeemed {usdc_redeemed} USDC "
+            f"(expected {expected_redemption})"
+        )
+        
+        if self.ledger is not None:
+            try:
+                self.ledger.record_merge(
+                    condition_id, [yes_token_id, no_token_id], amount, usdc_redeemed, tx_hash=tx_hash_hex
+                )
+            except Exception as e:
+                logger.error(f"Failed to record merge in ledger: {e}")
+        
+        return receipt
+    
+    async def _send_with_transaction_manager(self, merge_func, gas_limit: int):
+        """Send a merge through the TransactionManager; returns (receipt, tx hash)."""
+        try:
+            fee_params = await self.transaction_manager.estimate_fees()  # EIP-1559
+            tx_params = merge_func.build_transaction({
+                'from': self.wallet.address,
+                'gas': gas_limit,
+                **fee_params
+            })
+            tx_hash_hex = await self.transaction_manager.send_transaction(tx_params, label="merge")
+            logger.info(f"Merge transaction sent: {tx_hash_hex}")
+            
+            receipt = await self.transaction_manager.wait_for_confirmation(tx_hash_hex)
+        except Exception as e:
+            logger.error(f"Merge transaction failed: {e}")
+            raise PositionMergerError(f"Merge transaction failed: {e}")
+        
+        logger.info(f"Merge transaction confirmed: {tx_hash_hex}")
+        return receipt, tx_hash_hex
+    
+    async def _send_directly(self, merge_func, gas_limit: int):
+        """Sign and send a merge with this wallet; returns (receipt, tx hash)."""
+        # Fees: EIP-1559 from recent blocks, or the legacy gas price
+        if self.fee_estimator is not None:
+            try:
+                fee_params = (await asyncio.to_thread(self.fee_estimator.estimate)).tx_params()
+            except FeeCapExceededError as e:
+                raise PositionMergerError(f"Merge not sent: {e}")
+        else:
+            fee_params = {'gasPrice': self.web3.eth.gas_price}
         
         # Get current nonce
         nonce = await asyncio.to_thread(
@@ -352,14 +434,13 @@ class PositionMerger:
             tx_params = merge_func.build_transaction({
                 'from': self.wallet.address,
                 'gas': gas_limit,
-                'gasPrice': gas_price,
+                **fee_params,
                 'nonce': nonce
             })
         except Exception as e:
             logger.error(f"Failed to build merge transaction: {e}")
             raise PositionMergerError(f"Failed to build merge transaction: {e}")
         
-        # 5. Sign and send transaction
         try:
             signed_tx = await asyncio.to_thread(
                 self.wallet.sign_transaction,
@@ -374,7 +455,7 @@ class PositionMerger:
             tx_hash_hex = tx_hash.hex()
             logger.info(f"Merge transaction sent: {tx_hash_hex}")
             
-            # 6. Wait for confirmation
+            # Wait for confirmation
             receipt = await asyncio.to_thread(
                 self.web3.eth.wait_for_transaction_receipt,
                 tx_hash,
@@ -392,33 +473,7 @@ class PositionMerger:
             logger.error(f"Merge transaction failed: {e}")
             raise PositionMergerError(f"Merge transaction failed: {e}")
         
-        # 7. Verify USDC redemption (Requirement 1.6)
-        usdc_after = await self.get_usdc_balance()
-        usdc_redeemed = usdc_after - usdc_before
-        
-        logger.debug(
-            f"USDC balance after merge: {usdc_after} "
-            f"(redeemed: {usdc_redeemed})"
-        )
-        
-        # Expected redemption: $1.00 per position pair
-        expected_redemption = amount
-        
-        # Allow small tolerance for rounding (0.01 USDC)
-        tolerance = Decimal('0.01')
-        
-        if abs(usdc_redeemed - expected_redemption) > tolerance:
-            raise MergeRedemptionError(
-                f"Unexpected redemption amount: "
-                f"expected {expected_redemption}, got {usdc_redeemed}"
-            )
-        
-        logger.info(
-            f"Merge successful: redeemed {usdc_redeemed} USDC "
-            f"(expected {expected_redemption})"
-        )
-        
-        return receipt
+        return receipt, tx_hash_hex
     
     async def merge_positions_with_token_ids(
         self,
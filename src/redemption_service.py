@@ -0,0 +1,556 @@
+"""
+Automatic redemption of resolved market positions.
+
+Finds resolved conditions the wallet still holds shares in and redeems them
+through the Conditional Token Framework:
+- Standard markets: ConditionalTokens.redeemPositions(USDC, 0x0, conditionId, [1, 2])
+- Neg-risk markets: NegRiskAdapter.redeemPositions(conditionId, [yesAmount, noAmount])
+
+Transactions go through TransactionManager (nonce tracking, pending limit).
+When the shares sit in a Gnosis Safe owned by the signer, the redeem call is
+wrapped in Safe.execTransaction with the owner's pre-validated signature.
+Proceeds are recorded in trade history and, when attached, the position ledger.
+With a MarketDataRecorder attached, the winner of each recorded market is
+written to the recording once its condition resolves.
+
+Validates Requirements:
+- Discover resolved conditions held by the wallet (including neg-risk markets)
+- Redeem winning shares through TransactionManager
+- Record redemption proceeds in trade history
+"""
+
+import asyncio
+import logging
+import uuid
+from collections import defaultdict
+from dataclasses import dataclass, field
+from datetime import datetime
+from decimal import Decimal
+from typing import Any, Awaitable, Callable, Dict, List, Optional
+
+import aiohttp
+from web3 import Web3
+
+from src.models import Opportunity, TradeResult
+
+logger = logging.getLogger(__name__)
+
+DATA_API_URL = "https://data-api.polymarket.com"
+NEG_RISK_ADAPTER_ADDRESS = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"
+SHARE_DECIMALS = Decimal(10 ** 6)
+
+
+@dataclass
+class RedeemablePosition:
+    """Shares of one outcome token in a (possibly) resolved condition."""
+    condition_id: str
+    token_id: str
+    outcome_index: int  # 0 = YES/UP, 1 = NO/DOWN
+    neg_risk: bool = False
+    shares: Decimal = Decimal("0")  # On-chain balance, filled in by discovery
+    payout_per_share: Decimal = Decimal("0")  # payoutNumerator / payoutDenominator
+    title: str = ""
+
+    @property
+    def payout(self) -> Decimal:
+        return self.shares * self.payout_per_share
+
+
+@dataclass
+class RedemptionResult:
+    """Outcome of redeeming every held outcome of one condition."""
+    condition_id: str
+    neg_risk: bool
+    positions: List[RedeemablePosition] = field(default_factory=list)
+    success: bool = False
+    tx_hash: Optional[str] = None
+    payout: Decimal = Decimal("0")
+    cost_basis: Decimal = Decimal("0")
+    gas_used: int = 0
+    error: Optional[str] = None
+
+    @property
+    def shares(self) -> Decimal:
+        return sum((p.shares for p in self.positions), Decimal("0"))
+
+    @property
+    def profit(self) -> Decimal:
+        return self.payout - self.cost_basis
+
+
+class RedemptionService:
+    """
+    Redeems resolved positions for USDC.
+
+    Features:
+    - Discovery from the Polymarket data API (redeemable positions) and from
+      positions handed over by strategies via watch()
+    - On-chain checks: resolution (payoutDenominator) and the current share balance
+    - CTF and NegRiskAdapter redeem paths sent through TransactionManager
+    - EOA wallets and 1-of-1 Gnosis Safe proxy wallets
+    - Proceeds recorded in TradeHistoryDB (strategy "redemption") and the PositionLedger
+    - Losing-only conditions skipped unless redeem_losers is set (gas for $0)
+    """
+
+    CTF_ABI = '''[
+        {
+            "constant": false,
+            "inputs": [
+                {"name": "collateralToken", "type": "address"},
+                {"name": "parentCollectionId", "type": "bytes32"},
+                {"name": "conditionId", "type": "bytes32"},
+                {"name": "indexSets", "type": "uint256[]"}
+            ],
+            "name": "redeemPositions",
+            "outputs": [],
+            "stateMutability": "nonpayable",
+            "type": "function"
+        },
+        {
+            "constant": true,
+            "inputs": [{"name": "", "type": "bytes32"}],
+            "name": "payoutDenominator",
+            "outputs": [{"name": "", "type": "uint256"}],
+            "stateMutability": "view",
+            "type": "function"
+        },
+        {
+            "constant": true,
+            "inputs": [{"name": "", "type": "bytes32"}, {"name": "", "type": "uint256"}],
+            "name": "payoutNumerators",
+            "outputs": [{"name": "", "type": "uint256"}],
+            "stateMutability": "view",
+            "type": "function"
+        },
+        {
+            "constant": true,
+            "inputs": [
+                {"name": "owner", "type": "address"},
+                {"name": "id", "type": "uint256"}
+            ],
+            "name": "balanceOf",
+            "outputs": [{"name": "", "type": "uint256"}],
+            "stateMutability": "view",
+            "type": "function"
+        }
+    ]'''
+
+    NEG_RISK_ADAPTER_ABI = '''[
+        {
+            "constant": false,
+            "inputs": [
+                {"name": "_conditionId", "type": "bytes32"},
+                {"name": "_amounts", "type": "uint256[]"}
+            ],
+            "name": "redeemPositions",
+            "outputs": [],
+            "stateMutability": "nonpayable",
+            "type": "function"
+        }
+    ]'''
+
+    SAFE_ABI = '''[
+        {
+            "inputs": [
+                {"name": "to", "type": "address"},
+                {"name": "value", "type": "uint256"},
+                {"name": "data", "type": "bytes"},
+                {"name": "operation", "type": "uint8"},
+                {"name": "safeTxGas", "type": "uint256"},
+                {"name": "baseGas", "type": "uint256"},
+                {"name": "gasPrice", "type": "uint256"},
+                {"name": "gasToken", "type": "address"},
+                {"name": "refundReceiver", "type": "address"},
+                {"name": "signatures", "type": "bytes"}
+            ],
+            "name": "execTransaction",
+            "outputs": [{"name": "success", "type": "bool"}],
+            "stateMutability": "payable",
+            "type": "function"
+        }
+    ]'''
+
+    ZERO_BYTES32 = "0x" + "0" * 64
+    ZERO_ADDRESS = "0x" + "0" * 40
+
+    def __init__(
+        self,
+        web3: Web3,
+        transaction_manager: Any,
+        ctf_address: str,
+        usdc_address: str,
+        safe_address: Optional[str] = None,
+        neg_risk_adapter_address: str = NEG_RISK_ADAPTER_ADDRESS,
+        trade_history: Optional[Any] = None,
+        ledger: Optional[Any] = None,
+        position_source: Optional[Callable[[], Awaitable[List[RedeemablePosition]]]] = None,
+        data_api_url: str = DATA_API_URL,
+        redeem_losers: bool = False,
+        gas_limit: int = 300000,
+        dry_run: bool = False,
+        wallet: str = "primary",
+        recorder: Optional[Any] = None
+    ):
+        """
+        Initialize the redemption service.
+
+        Args:
+            web3: Web3 instance connected to Polygon RPC
+            transaction_manager: TransactionManager that signs and sends the redeem transactions
+            ctf_address: ConditionalTokens contract address
+            usdc_address: USDC (collateral) address
+            safe_address: Gnosis Safe holding the shares, owned by the TransactionManager wallet
+                (default: the wallet holds the shares itself)
+            neg_risk_adapter_address: NegRiskAdapter address for neg-risk markets
+            trade_history: TradeHistoryDB that records proceeds (optional)
+            ledger: PositionLedger that records redemptions (optional)
+            position_source: Async callable returning candidate positions (default: data API)
+            data_api_url: Polymarket data API base URL
+            redeem_losers: Also redeem conditions where every held outcome pays $0
+            gas_limit: Gas limit used when estimation fails
+            dry_run: Discover and log, but send no transactions
+            wallet: Wallet (sub-account) name recorded on redemption trades
+            recorder: MarketDataRecorder that receives resolution records (optional)
+        """
+        self.web3 = web3
+        self.transaction_manager = transaction_manager
+        self.owner_address = Web3.to_checksum_address(transaction_manager.wallet.address)
+        self.safe_contract = (
+            web3.eth.contract(address=Web3.to_checksum_address(safe_address), abi=self.SAFE_ABI)
+            if safe_address else None
+        )
+        # Address holding the shares
+        self.wallet_address = Web3.to_checksum_address(safe_address) if safe_address else self.owner_address
+        self.usdc_address = Web3.to_checksum_address(usdc_address)
+        self.trade_history = trade_history
+        self.ledger = ledger
+        self.position_source = position_source or self._fetch_redeemable_positions
+        self.data_api_url = data_api_url.rstrip("/")
+        self.redeem_losers = redeem_losers
+        self.gas_limit = gas_limit
+        self.dry_run = dry_run
+        self.wallet = wallet
+        self.recorder = recorder
+
+        self.ctf_contract = web3.eth.contract(
+            address=Web3.to_checksum_address(ctf_address), abi=self.CTF_ABI
+        )
+        self.neg_risk_adapter = web3.eth.contract(
+            address=Web3.to_checksum_address(neg_risk_adapter_address), abi=self.NEG_RISK_ADAPTER_ABI
+        )
+
+        # Positions handed over by strategies (e.g. orphans whose market closed)
+        self.watched: Dict[str, RedeemablePosition] = {}
+        self.stats = {"redemptions": 0, "failed": 0, "total_payout": Decimal("0")}
+
+        logger.info(
+            f"RedemptionService initialized: wallet={self.wallet_address}, "
+            f"dry_run={dry_run}, redeem_losers={redeem_losers}"
+        )
+
+    # ========================================================================
+    # Discovery
+    # ========================================================================
+
+    def watch(self, condition_id: str, token_id: str, outcome_index: int, neg_risk: bool = False) -> None:
+        """Queue a held position for redemption once its condition resolves."""
+        if not condition_id or not token_id:
+            return
+        self.watched[str(token_id)] = RedeemablePosition(
+            condition_id=condition_id, token_id=str(token_id),
+            outcome_index=outcome_index, neg_risk=neg_risk
+        )
+        logger.info(f"🔖 Watching {str(token_id)[:12]}... (condition {condition_id[:12]}...) for redemption")
+
+    async def _fetch_redeemable_positions(self) -> List[RedeemablePosition]:
+        """Redeemable positions of the wallet according to the Polymarket data API."""
+        url = f"{self.data_api_url}/positions"
+        params = {"user": self.wallet_address, "redeemable": "true", "sizeThreshold": "0"}
+        async with aiohttp.ClientSession() as session:
+            async with session.get(url, params=params, timeout=aiohttp.ClientTimeout(total=15)) as resp:
+                resp.raise_for_status()
+                data = await resp.json()
+
+        return [
+            RedeemablePosition(
+                condition_id=item["conditionId"],
+                token_id=str(item["asset"]),
+                outcome_index=int(item.get("outcomeIndex", 0)),
+                neg_risk=bool(item.get("negativeRisk", False)),
+                title=item.get("title", "")
+            )
+            for item in data
+            if item.get("conditionId") and item.get("asset")
+        ]
+
+    async def _call(self, fn) -> Any:
+        return await asyncio.to_thread(fn.call)
+
+    async def _payouts(self, condition_id: str) -> Optional[List[Decimal]]:
+        """Payout per share for outcomes 0 and 1, or None if the condition is unresolved."""
+        denominator = await self._call(self.ctf_contract.functions.payoutDenominator(condition_id))
+        if denominator == 0:
+            return None
+        numerators = [
+            await self._call(self.ctf_contract.functions.payoutNumerators(condition_id, index))
+            for index in (0, 1)
+        ]
+        return [Decimal(n) / Decimal(denominator) for n in numerators]
+
+    async def _share_balance(self, token_id: str) -> Decimal:
+        balance = await self._call(self.ctf_contract.functions.balanceOf(self.wallet_address, int(token_id)))
+        return Decimal(balance) / SHARE_DECIMALS
+
+    async def discover(self) -> Dict[str, List[RedeemablePosition]]:
+        """
+        Find resolved conditions the wallet holds shares in.
+
+        Candidates come from the position source and the watch list. Each is
+        checked on chain, so a stale API result or an already redeemed token
+        is dropped.
+
+        Returns:
+            Dict mapping condition ID to the held positions in it
+        """
+        candidates: Dict[str, RedeemablePosition] = dict(self.watched)
+        try:
+            # The data API's neg-risk flag and outcome index win over a watched guess
+            for position in await self.position_source():
+                candidates[position.token_id] = position
+        except Exception as e:
+            logger.warning(f"⚠️ Could not fetch redeemable positions: {e}")
+
+        payouts_by_condition: Dict[str, Optional[List[Decimal]]] = {}
+        resolved: Dict[str, List[RedeemablePosition]] = defaultdict(list)
+
+        for position in candidates.values():
+            try:
+                if position.condition_id not in payouts_by_condition:
+                    payouts_by_condition[position.condition_id] = await self._payouts(position.condition_id)
+                payouts = payouts_by_condition[position.condition_id]
+                if payouts is None:
+                    continue  # Not resolved yet - keep watching
+
+                position.shares = await self._share_balance(position.token_id)
+                if position.shares <= 0:
+                    self.watched.pop(position.token_id, None)  # Already redeemed or sold
+                    continue
+
+                position.payout_per_share = payouts[position.outcome_index]
+                resolved[position.condition_id].append(position)
+            except Exception as e:
+                logger.warning(f"⚠️ Resolution check failed for {position.token_id[:12]}...: {e}")
+
+        return dict(resolved)
+
+    # ========================================================================
+    # Redemption
+    # ========================================================================
+
+    def _build_redeem_call(self, condition_id: str, positions: List[RedeemablePosition], neg_risk: bool):
+        if neg_risk:
+            amounts = [0, 0]
+            for position in positions:
+                amounts[position.outcome_index] += int(position.shares * SHARE_DECIMALS)
+            return self.neg_risk_adapter.functions.redeemPositions(condition_id, amounts)
+        return self.ctf_contract.functions.redeemPositions(
+            self.usdc_address, self.ZERO_BYTES32, condition_id, [1, 2]
+        )
+
+    def _wrap_for_safe(self, redeem_call):
+        """
+        Wrap a redeem call in Safe.execTransaction.
+
+        The signature is the pre-validated form (r = owner, s = 0, v = 1),
+        which a Safe accepts when the owner itself sends the transaction.
+        """
+        signature = (
+            bytes(12) + bytes.fromhex(self.owner_address[2:]) + bytes(32) + b"\x01"
+        )
+        return self.safe_contract.functions.execTransaction(
+            redeem_call.address, 0, redeem_call._encode_transaction_data(), 0,
+            0, 0, 0, self.ZERO_ADDRESS, self.ZERO_ADDRESS, signature
+        )
+
+    def _estimate_gas(self, call) -> int:
+        try:
+            return int(call.estimate_gas({"from": self.owner_address}) * 1.2)
+        except Exception as e:
+            logger.warning(f"Redeem gas estimation failed: {e}, using default")
+            return self.gas_limit
+
+    async def redeem_condition(self, condition_id: str, positions: List[RedeemablePosition]) -> RedemptionResult:
+        """
+        Redeem every held outcome of one resolved condition.
+
+        Args:
+            condition_id: Resolved condition ID
+            positions: Held positions in the condition (from discover())
+
+        Returns:
+            RedemptionResult (success=False with the error on failure)
+        """
+        neg_risk = any(p.neg_risk for p in positions)
+        result = RedemptionResult(
+            condition_id=condition_id, neg_risk=neg_risk, positions=positions,
+            payout=sum((p.payout for p in positions), Decimal("0")),
+            cost_basis=self._cost_basis(positions)
+        )
+
+        if self.dry_run:
+            logger.info(
+                f"[DRY RUN] Would redeem {result.shares} shares of {condition_id[:12]}... "
+                f"for ${result.payout:.2f} ({'neg-risk' if neg_risk else 'CTF'})"
+            )
+            return result
+
+        try:
+            call = self._build_redeem_call(condition_id, positions, neg_risk)
+            if self.safe_contract is not None:
+                call = self._wrap_for_safe(call)
+            fees = await self.transaction_manager.estimate_fees()  # EIP-1559
+            tx_params = call.build_transaction({
+                "from": self.owner_address,
+                "gas": self._estimate_gas(call),
+                **fees
+            })
+            result.tx_hash = await self.transaction_manager.send_transaction(tx_params)
+            receipt = await self.transaction_manager.wait_for_confirmation(result.tx_hash)
+            result.gas_used = receipt.get("gasUsed", 0)
+            result.success = True
+        except Exception as e:
+            result.error = str(e)
+            self.stats["failed"] += 1
+            logger.error(f"❌ Redemption of {condition_id[:12]}... failed: {e}")
+            self._record_trade(result)
+            return result
+
+        for position in positions:
+            self.watched.pop(position.token_id, None)
+        self.stats["redemptions"] += 1
+        self.stats["total_payout"] += result.payout
+
+        logger.info(
+            f"💰 Redeemed {result.shares} shares of {condition_id[:12]}... for ${result.payout:.2f} "
+            f"(PnL ${result.profit:+.2f}, tx {result.tx_hash})"
+        )
+        self._record_ledger(result)
+        self._record_trade(result)
+        return result
+
+    async def redeem_all(self) -> List[RedemptionResult]:
+        """Record resolutions, then discover resolved conditions and redeem each one."""
+        await self.record_resolutions()
+        results = []
+        for condition_id, positions in (await self.discover()).items():
+            if not self.redeem_losers and all(p.payout_per_share == 0 for p in positions):
+                logger.debug(f"Skipping losing-only condition {condition_id[:12]}... (pays $0)")
+                self._write_off(condition_id, positions)
+                continue
+            results.append(await self.redeem_condition(condition_id, positions))
+        return results
+
+    # ========================================================================
+    # Recording
+    # ========================================================================
+
+    async def record_resolutions(self) -> None:
+        """Write the winner of every ended, recorded market whose condition has resolved."""
+        if self.recorder is None:
+            return
+        for market_id in self.recorder.awaiting_resolution():
+            try:
+                payouts = await self._payouts(market_id)
+            except Exception as e:
+                logger.warning(f"⚠️ Resolution check failed for recorded market {market_id[:12]}...: {e}")
+                continue
+            if payouts is None or payouts[0] == payouts[1]:
+                continue  # Not resolved yet, or split evenly (no UP/DOWN winner)
+            # Up/down markets list Up as outcome 0
+            self.recorder.record_resolution(market_id, "UP" if payouts[0] > payouts[1] else "DOWN")
+
+    def _cost_basis(self, positions: List[RedeemablePosition]) -> Decimal:
+        """Cost of the redeemed shares at the ledger's average price (0 without a ledger)."""
+        if self.ledger is None:
+            return Decimal("0")
+        cost = Decimal("0")
+        for position in positions:
+            held = self.ledger.position(position.token_id)
+            if held is not None and held.is_open:
+                cost += held.avg_price * min(position.shares, held.shares)
+        return cost
+
+    def _write_off(self, condition_id: str, positions: List[RedeemablePosition]) -> None:
+        """Stop watching losing shares left unredeemed and close their ledger positions at $0."""
+        for position in positions:
+            self.watched.pop(position.token_id, None)
+            if self.ledger is None:
+                continue
+            try:
+                held = self.ledger.position(position.token_id)
+                if held is not None and held.is_open:
+                    self.ledger.record_redemption(
+                        position.token_id, held.shares, Decimal("0"), market_id=condition_id
+                    )
+            except Exception as e:
+                logger.error(f"Failed to write off losing position in ledger: {e}")
+
+    def _record_ledger(self, result: RedemptionResult) -> None:
+        if self.ledger is None:
+            return
+        for position in result.positions:
+            try:
+                self.ledger.record_redemption(
+                    position.token_id, position.shares, position.payout,
+                    market_id=result.condition_id, tx_hash=result.tx_hash
+                )
+            except Exception as e:
+                logger.error(f"Failed to record redemption in ledger: {e}")
+
+    def _record_trade(self, result: RedemptionResult) -> None:
+        """Record the redemption in trade history (tx hash in merge_tx_hash, the CTF settlement slot)."""
+        if self.trade_history is None:
+            return
+
+        by_outcome = {p.outcome_index: p for p in result.positions}
+        yes, no = by_outcome.get(0), by_outcome.get(1)
+        profit_pct = result.profit / result.cost_basis if result.cost_basis > 0 else Decimal("0")
+
+        opportunity = Opportunity(
+            opportunity_id=uuid.uuid4().hex[:12],
+            market_id=result.condition_id,
+            strategy="redemption",
+            timestamp=datetime.now(),
+            yes_price=yes.payout_per_share if yes else Decimal("0"),
+            no_price=no.payout_per_share if no else Decimal("0"),
+            yes_fee=Decimal("0"),
+            no_fee=Decimal("0"),
+            total_cost=result.cost_basis,
+            expected_profit=result.profit,
+            profit_percentage=profit_pct,
+            position_size=result.shares,
+            gas_estimate=result.gas_used
+        )
+        trade = TradeResult(
+            trade_id=uuid.uuid4().hex[:12],
+            opportunity=opportunity,
+            timestamp=datetime.now(),
+            status="success" if result.success else "failed",
+            yes_order_id=None,
+            no_order_id=None,
+            yes_filled=result.success,
+            no_filled=result.success,
+            yes_fill_price=yes.payout_per_share if yes else None,
+            no_fill_price=no.payout_per_share if no else None,
+            actual_cost=result.cost_basis,
+            actual_profit=result.profit if result.success else Decimal("0"),
+            gas_cost=Decimal("0"),  # Paid in POL; gas units are kept in gas_estimate
+            net_profit=result.profit if result.success else Decimal("0"),
+            merge_tx_hash=result.tx_hash,
+            error_message=result.error,
+            wallet=self.wallet
+        )
+        try:
+            self.trade_history.insert_trade(trade)
+        except Exception as e:
+            logger.error(f"Failed to record redemption in trade history: {e}")
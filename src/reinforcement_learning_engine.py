@@ -12,7 +12,7 @@ import json
 import numpy as np
 from decimal import Decimal
 from datetime import datetime
-from typing import Dict, List, Optional, Tuple
+from typing import Any, Dict, List, Optional, Tuple
 from dataclasses import dataclass, asdict
 from pathlib import Path
 from collections import defaultdict
@@ -45,6 +45,8 @@ class ReinforcementLearningEngine:
     Maximizes long-term profitability by adapting to market conditions.
     """
     
+    STATE_NAME = "reinforcement_learning"
+    
     def __init__(
         self,
         data_file: str = "data/rl_q_table.json",
@@ -52,7 +54,8 @@ class ReinforcementLearningEngine:
         discount_factor: float = 0.95,
         exploration_rate: float = 0.2,
         min_exploration_rate: float = 0.05,
-        exploration_decay: float = 0.995
+        exploration_decay: float = 0.995,
+        store: Optional[Any] = None
     ):
         """
         Initialize RL engine.
@@ -64,8 +67,11 @@ class ReinforcementLearningEngine:
             exploration_rate: Probability of random action (epsilon)
             min_exploration_rate: Minimum exploration rate
             exploration_decay: Rate at which exploration decreases
+            store: TradeOutcomeStore keeping the Q-table instead of data_file;
+                subscribe on_trade_outcome to it
         """
         self.data_file = Path(data_file)
+        self.store = store
         self.learning_rate = learning_rate
         self.discount_factor = discount_factor
         self.exploration_rate = exploration_rate
@@ -93,14 +99,19 @@ class ReinforcementLearningEngine:
         logger.info(f"   Total episodes: {self.total_episodes}")
     
     def _load_q_table(self):
-        """Load Q-table from disk."""
-        if not self.data_file.exists():
-            logger.info("🔄 No Q-table found, starting fresh")
-            return
-        
+        """Load Q-table from the store, or from disk without one."""
         try:
-            with open(self.data_file, 'r') as f:
-                data = json.load(f)
+            if self.store is not None:
+                data = self.store.load_state(self.STATE_NAME)
+            elif self.data_file.exists():
+                with open(self.data_file, 'r') as f:
+                    data = json.load(f)
+            else:
+                data = None
+            
+            if data is None:
+                logger.info("🔄 No Q-table found, starting fresh")
+                return
             
             # Load Q-table
             for state_key, actions in data.get("q_table", {}).items():
@@ -132,11 +143,8 @@ class ReinforcementLearningEngine:
             logger.error(f"❌ Failed to load Q-table: {e}")
     
     def _save_q_table(self):
-        """Save Q-table to disk."""
+        """Save Q-table to the store, or to disk without one."""
         try:
-            # Ensure directory exists
-            self.data_file.parent.mkdir(parents=True, exist_ok=True)
-            
             # Convert defaultdict to regular dict for JSON
             q_table_dict = {
                 state: dict(actions)
@@ -151,6 +159,12 @@ class ReinforcementLearningEngine:
                 "last_updated": datetime.now().isoformat()
             }
             
+            if self.store is not None:
+                self.store.save_state(self.STATE_NAME, data)
+                return
+            
+            # Ensure directory exists
+            self.data_file.parent.mkdir(parents=True, exist_ok=True)
             with open(self.data_file, 'w') as f:
                 json.dump(data, f, indent=2)
             
@@ -369,6 +383,10 @@ class ReinforcementLearningEngine:
                 f"exploration_rate={self.exploration_rate:.3f}"
             )
     
+    def on_trade_outcome(self, outcome: Any) -> None:
+        """Update the Q-value of a trade recorded in the TradeOutcomeStore (profit as reward)."""
+        self.update_q_value(asset=outcome.asset, strategy=outcome.strategy, reward=outcome.profit_pct)
+    
     def get_strategy_rankings(
         self,
         asset: str,
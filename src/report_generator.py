@@ -130,6 +130,21 @@ class ReportGenerator:
                 lines.append(f"    Net Profit:       ${strategy_data['net_profit']:,.2f}")
                 lines.append("")
         
+        # Per-wallet books and their aggregate (only when more than one wallet traded)
+        if len(stats.wallet_stats) > 1:
+            lines.append("")
+            lines.append("WALLET BREAKDOWN")
+            lines.append("-" * 80)
+            
+            for wallet, wallet_data in stats.wallet_stats.items():
+                lines.append(f"  {wallet}: {wallet_data['total']:,} trades | "
+                             f"Win Rate {wallet_data['win_rate']:.2f}% | "
+                             f"Net Profit ${wallet_data['net_profit']:,.2f}")
+            lines.append(f"  ALL WALLETS: {stats.total_trades:,} trades | "
+                         f"Win Rate {stats.win_rate:.2f}% | "
+                         f"Net Profit ${stats.net_profit:,.2f}")
+            lines.append("")
+        
         lines.append("=" * 80)
         
         report = "\n".join(lines)
@@ -194,7 +209,7 @@ class ReportGenerator:
                 
                 # Define CSV columns
                 fieldnames = [
-                    'trade_id', 'timestamp', 'market_id', 'strategy', 'status',
+                    'trade_id', 'timestamp', 'wallet', 'market_id', 'strategy', 'status',
                     'yes_price', 'no_price', 'total_cost', 'expected_profit',
                     'actual_profit', 'gas_cost', 'net_profit',
                     'yes_filled', 'no_filled', 'error_message'
@@ -210,6 +225,7 @@ class ReportGenerator:
                     writer.writerow({
                         'trade_id': trade['trade_id'],
                         'timestamp': trade['timestamp'],
+                        'wallet': trade.get('wallet', 'primary'),
                         'market_id': trade['market_id'],
                         'strategy': trade['strategy'],
                         'status': trade['status'],
@@ -314,6 +330,18 @@ class ReportGenerator:
                     }
                     for strategy, data in stats.strategy_stats.items()
                 },
+                'wallet_breakdown': {
+                    wallet: {
+                        'total': data['total'],
+                        'successful': data['successful'],
+                        'failed': data['failed'],
+                        'win_rate': float(data['win_rate']),
+                        'profit': float(data['profit']),
+                        'gas_cost': float(data['gas_cost']),
+                        'net_profit': float(data['net_profit']),
+                    }
+                    for wallet, data in stats.wallet_stats.items()
+                },
             }
         
         # Write JSON
@@ -346,4 +374,8 @@ class ReportGenerator:
             'sharpe_ratio': float(stats.sharpe_ratio),
             'max_drawdown': float(stats.max_drawdown),
             'strategies': list(stats.strategy_stats.keys()),
+            'wallets': {
+                wallet: float(data['net_profit'])
+                for wallet, data in stats.wallet_stats.items()
+            },
         }
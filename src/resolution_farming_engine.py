@@ -17,6 +17,7 @@ import uuid
 
 from src.models import Market, Opportunity
 from src.ai_safety_guard import AISafetyGuard
+from src.fair_value_pricer import FairValuePricer
 
 logger = logging.getLogger(__name__)
 
@@ -37,7 +38,8 @@ class ResolutionFarmingEngine:
         max_price: Decimal = Decimal('0.99'),
         min_profit_threshold: Decimal = Decimal('0.01'),  # 1% minimum
         max_position_percentage: Decimal = Decimal('0.02'),  # 2% of bankroll
-        closing_window_seconds: int = 120  # 2 minutes
+        closing_window_seconds: int = 120,  # 2 minutes
+        fair_value_pricer: Optional[FairValuePricer] = None
     ):
         """
         Initialize Resolution Farming Engine.
@@ -50,6 +52,8 @@ class ResolutionFarmingEngine:
             min_profit_threshold: Minimum profit percentage (default 1%)
             max_position_percentage: Maximum position size as % of bankroll (default 2%)
             closing_window_seconds: Time window before close to consider (default 120s)
+            fair_value_pricer: If set, also require a positive edge against the
+                fair-value model at the unfavourable end of its confidence band
         """
         self.cex_feeds = cex_feeds
         self.ai_safety_guard = ai_safety_guard
@@ -58,6 +62,7 @@ class ResolutionFarmingEngine:
         self.min_profit_threshold = min_profit_threshold
         self.max_position_percentage = max_position_percentage
         self.closing_window_seconds = closing_window_seconds
+        self.fair_value_pricer = fair_value_pricer
         
         logger.info(
             f"Resolution Farming Engine initialized: "
@@ -114,6 +119,10 @@ class ResolutionFarmingEngine:
                 )
                 continue
             
+            # Price must still be cheap against the fair-value model
+            if self.fair_value_pricer is not None and not self._has_model_edge(market, certain_outcome, outcome_price):
+                continue
+            
             # Calculate expected profit
             # For resolution farming, we buy one side and expect $1.00 redemption
             expected_profit = Decimal('1.00') - outcome_price
@@ -141,7 +150,8 @@ class ResolutionFarmingEngine:
                 expected_profit=expected_profit,
                 profit_percentage=profit_percentage,
                 position_size=Decimal('0'),  # Calculated later based on bankroll
-                gas_estimate=0  # Estimated later
+                gas_estimate=0,  # Estimated later
+                side=certain_outcome
             )
             
             opportunities.append(opportunity)
@@ -200,6 +210,38 @@ class ResolutionFarmingEngine:
             logger.warning(f"Cannot determine market direction from: {market.question}")
             return None
     
+    def _has_model_edge(self, market: Market, outcome: str, outcome_price: Decimal) -> bool:
+        """
+        Check the outcome price against the fair-value model.
+        
+        A spot just past the strike is not certain with time left; the
+        model's conservative probability has to exceed the price paid.
+        
+        Args:
+            market: The market to price
+            outcome: "YES" or "NO"
+            outcome_price: Price of the outcome
+            
+        Returns:
+            bool: True if the conservative model edge is positive
+        """
+        fair_value = self.fair_value_pricer.price_market(
+            market, spot=self._get_current_cex_price(market.asset)
+        )
+        if fair_value is None:
+            logger.debug(f"No fair value for market {market.market_id}")
+            return False
+        
+        edge = fair_value.conservative_edge(outcome_price, outcome)
+        if edge <= 0:
+            logger.debug(
+                f"No model edge for {outcome} @ ${outcome_price} in {market.market_id}: "
+                f"fair {fair_value.side_probability(outcome)} "
+                f"(band {fair_value.lower}-{fair_value.upper})"
+            )
+            return False
+        return True
+    
     def calculate_position_size(self, bankroll: Decimal) -> Decimal:
         """
         Calculate position size limited to 2% of bankroll.
@@ -0,0 +1,189 @@
+"""
+Offline training and evaluation of continuous-state RL policies.
+
+Trains a LinearPolicy (src/continuous_rl_engine.py) on the experience logged
+by live trading and replay backtests, evaluates it on the newest trades held
+out of training and, with --freeze, stores it as the next frozen version for
+live use.
+
+    python -m src.rl_training --experience data/rl_experience.jsonl --experience data/backtests/rl_experience.jsonl
+    python -m src.rl_training --eval-fraction 0.25 --freeze --require-improvement
+    python -m src.rl_training --evaluate 3
+
+Validates Requirements:
+- Offline training from recorded trades and backtests
+- Chronological training/evaluation split
+- Versioned policy freezing gated on the held-out evaluation
+"""
+
+import argparse
+import json
+import logging
+import sys
+from collections import Counter
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from src.continuous_rl_engine import (
+    Experience,
+    PolicyStore,
+    evaluate_policy,
+    load_experiences,
+    split_experiences,
+    train_policy,
+)
+
+logger = logging.getLogger(__name__)
+
+
+def _period(experiences: List[Experience]) -> Optional[Dict[str, str]]:
+    if not experiences:
+        return None
+    return {
+        "from": datetime.fromtimestamp(experiences[0].timestamp, tz=timezone.utc).isoformat(),
+        "to": datetime.fromtimestamp(experiences[-1].timestamp, tz=timezone.utc).isoformat(),
+    }
+
+
+def freeze_blockers(
+    evaluation: Dict[str, Any],
+    min_eval_trades: int,
+    require_improvement: bool
+) -> List[str]:
+    """Reasons a trained policy must not be frozen (empty if it may be)."""
+    blockers = []
+    if evaluation["trades"] < min_eval_trades:
+        blockers.append(f"{evaluation['trades']} held-out trades, {min_eval_trades} required")
+    if require_improvement:
+        policy_mean = evaluation["policy"]["mean_reward"]
+        logged_mean = evaluation["logged"]["mean_reward"]
+        if policy_mean is None or logged_mean is None or policy_mean <= logged_mean:
+            blockers.append(f"held-out mean reward {policy_mean} does not beat the logged {logged_mean}")
+    return blockers
+
+
+def train(
+    experiences: List[Experience],
+    eval_fraction: float,
+    l2: float,
+    min_samples: int
+) -> Dict[str, Any]:
+    """
+    Train on the older trades and evaluate on the newest `eval_fraction`.
+
+    Returns:
+        Dict with the policy (not frozen), its evaluation and the split
+
+    Raises:
+        ValueError: If the split leaves no training trades or no strategy has min_samples of them
+    """
+    train_set, eval_set = split_experiences(experiences, eval_fraction)
+    policy = train_policy(train_set, l2=l2, min_samples=min_samples)
+    evaluation = evaluate_policy(policy, eval_set)
+    policy.metadata["evaluation"] = evaluation
+    policy.metadata["training"]["sources"] = dict(Counter(e.source for e in train_set))
+    return {
+        "policy": policy,
+        "evaluation": evaluation,
+        "split": {
+            "trades": len(experiences),
+            "train": len(train_set),
+            "eval": len(eval_set),
+            "train_period": _period(train_set),
+            "eval_period": _period(eval_set),
+        },
+    }
+
+
+# ============================================================================
+# Command line
+# ============================================================================
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """Command-line entry point for RL policy training."""
+    parser = argparse.ArgumentParser(description="Train, evaluate and freeze continuous-state RL policies")
+    parser.add_argument(
+        "--experience", type=Path, action="append", default=[],
+        help="Experience log to train on (repeatable; default: data/rl_experience.jsonl)"
+    )
+    parser.add_argument("--policy-dir", type=Path, default=Path("data/rl_policies"), help="Frozen policy directory")
+    parser.add_argument("--source", action="append", default=[], help="Only experience from this source (repeatable)")
+    parser.add_argument("--eval-fraction", type=float, default=0.2, help="Newest fraction of trades held out")
+    parser.add_argument("--l2", type=float, default=1.0, help="Ridge penalty")
+    parser.add_argument("--min-samples", type=int, default=20, help="Training trades a strategy needs to be in the policy")
+    parser.add_argument("--freeze", action="store_true", help="Store the trained policy as the next version")
+    parser.add_argument("--min-eval-trades", type=int, default=20, help="Held-out trades needed to freeze")
+    parser.add_argument(
+        "--require-improvement", action="store_true",
+        help="Only freeze if the policy's held-out mean reward beats the logged trades'"
+    )
+    parser.add_argument("--evaluate", type=int, metavar="VERSION", help="Evaluate a frozen version on all given experience")
+    parser.add_argument("--list", action="store_true", help="List frozen versions")
+    parser.add_argument("--output", type=Path, help="Write the JSON report to this file")
+    parser.add_argument("--verbose", action="store_true")
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(
+        level=logging.INFO if args.verbose else logging.WARNING,
+        format="%(asctime)s - %(name)s - %(levelname)s - %(message)s"
+    )
+
+    store = PolicyStore(str(args.policy_dir))
+    if args.list:
+        report: Dict[str, Any] = {"versions": []}
+        for version in store.versions():
+            policy = store.load(version)
+            report["versions"].append({
+                "version": version,
+                "frozen_at": policy.metadata.get("frozen_at"),
+                "strategies": policy.strategies,
+                "trades": policy.metadata.get("training", {}).get("trades"),
+                "eval_mean_reward": policy.metadata.get("evaluation", {}).get("policy", {}).get("mean_reward"),
+            })
+    else:
+        paths = args.experience or [Path("data/rl_experience.jsonl")]
+        experiences = load_experiences(str(path) for path in paths)
+        if args.source:
+            experiences = [e for e in experiences if e.source in args.source]
+        if not experiences:
+            parser.error(f"No experience in {', '.join(str(path) for path in paths)}")
+
+        if args.evaluate is not None:
+            try:
+                policy = store.load(args.evaluate)
+            except (OSError, ValueError) as e:
+                parser.error(str(e))
+            report = {
+                "version": args.evaluate,
+                "period": _period(experiences),
+                "evaluation": evaluate_policy(policy, experiences),
+            }
+        else:
+            try:
+                result = train(experiences, args.eval_fraction, args.l2, args.min_samples)
+            except ValueError as e:
+                parser.error(str(e))
+            policy = result["policy"]
+            report = {
+                "split": result["split"],
+                "training": policy.metadata["training"],
+                "evaluation": result["evaluation"],
+                "frozen_version": None,
+            }
+            if args.freeze:
+                blockers = freeze_blockers(result["evaluation"], args.min_eval_trades, args.require_improvement)
+                if blockers:
+                    report["freeze_blocked"] = blockers
+                else:
+                    report["frozen_version"] = store.freeze(policy)
+
+    output = json.dumps(report, indent=2, default=str)
+    if args.output:
+        args.output.write_text(output)
+    print(output)
+    return 1 if report.get("freeze_blocked") else 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
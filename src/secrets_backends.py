@@ -0,0 +1,455 @@
+"""
+Pluggable secrets backends for SecretsManager.
+
+Each backend returns the bot's credentials as a dict with at least
+`private_key` (plus optional `wallet_address`, `nvidia_api_key`,
+`kalshi_api_key`). Optional fields a backend does not hold are taken from
+environment variables, as before.
+
+Backends:
+- env: PRIVATE_KEY and friends from the environment (local development)
+- aws: AWS Secrets Manager (IAM role credentials)
+- keystore: Encrypted Ethereum JSON keystore (V3) unlocked with a passphrase
+- vault: HashiCorp Vault-compatible KV engine (v1 or v2)
+- credentials: systemd credentials ($CREDENTIALS_DIRECTORY) or an inherited file descriptor
+
+Validates Requirements:
+- 14.1: Retrieve private keys from a secrets store
+- 14.3: Never log private keys
+"""
+
+import getpass
+import json
+import logging
+import os
+import sys
+from abc import ABC, abstractmethod
+from typing import Any, Callable, Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+# Optional fields filled from the environment when a backend does not hold them
+OPTIONAL_ENV_FIELDS = {
+    "wallet_address": "WALLET_ADDRESS",
+    "nvidia_api_key": "NVIDIA_API_KEY",
+    "kalshi_api_key": "KALSHI_API_KEY",
+}
+
+
+def _with_env_defaults(secret_data: Dict[str, Any]) -> Dict[str, Any]:
+    """Fill optional fields missing from a secret with environment values."""
+    for field_name, env_var in OPTIONAL_ENV_FIELDS.items():
+        if not secret_data.get(field_name) and os.getenv(env_var):
+            secret_data[field_name] = os.getenv(env_var)
+    return secret_data
+
+
+def _parse_secret(content: str, source: str) -> Dict[str, Any]:
+    """A JSON object of fields, or a bare private key."""
+    content = content.strip()
+    if not content:
+        raise ValueError(f"Secret from {source} is empty")
+    if content.startswith("{"):
+        try:
+            data = json.loads(content)
+        except json.JSONDecodeError as e:
+            raise ValueError(f"Secret from {source} is not valid JSON: {e.msg}")
+        if not isinstance(data, dict):
+            raise ValueError(f"Secret from {source} must be a JSON object")
+        return data
+    return {"private_key": content}
+
+
+class SecretsBackend(ABC):
+    """Source of the bot's credentials."""
+
+    name = "base"
+
+    @abstractmethod
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        """
+        Retrieve the secret.
+
+        Args:
+            secret_name: Secret identifier (its meaning depends on the backend)
+
+        Returns:
+            Dictionary containing secret values
+
+        Raises:
+            ValueError: If the secret cannot be retrieved
+        """
+
+
+# ============================================================================
+# Environment and AWS
+# ============================================================================
+
+class EnvSecretsBackend(SecretsBackend):
+    """Credentials from environment variables (fallback for local development)."""
+
+    name = "env"
+
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        logger.info("Retrieving credentials from environment variables")
+
+        private_key = os.getenv("PRIVATE_KEY")
+        if not private_key:
+            raise ValueError("PRIVATE_KEY environment variable is required")
+
+        # CRITICAL: Never log the actual private key
+        logger.info("Credentials retrieved from environment variables")
+
+        secret_data = {
+            'private_key': private_key,
+            'wallet_address': os.getenv("WALLET_ADDRESS", ""),
+        }
+
+        # Add optional fields if present
+        if os.getenv("NVIDIA_API_KEY"):
+            secret_data['nvidia_api_key'] = os.getenv("NVIDIA_API_KEY")
+        if os.getenv("KALSHI_API_KEY"):
+            secret_data['kalshi_api_key'] = os.getenv("KALSHI_API_KEY")
+
+        return secret_data
+
+
+class AwsSecretsBackend(SecretsBackend):
+    """AWS Secrets Manager, authenticated with the instance's IAM role."""
+
+    name = "aws"
+
+    def __init__(self, region_name: str = "us-east-1", client: Any = None):
+        """
+        Args:
+            region_name: AWS region for Secrets Manager
+            client: Pre-built boto3 client (created from the IAM role if None)
+
+        Raises:
+            ImportError: If boto3 is not installed
+        """
+        self.region_name = region_name
+        if client is None:
+            import boto3
+            # Use IAM role credentials (no explicit credentials needed)
+            client = boto3.client('secretsmanager', region_name=region_name)
+            logger.info(f"AWS Secrets Manager client initialized (region: {region_name})")
+        self.client = client
+
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        try:
+            logger.info(f"Retrieving secret from AWS Secrets Manager: {secret_name}")
+
+            response = self.client.get_secret_value(SecretId=secret_name)
+
+            # Parse secret string
+            if 'SecretString' in response:
+                secret_data = json.loads(response['SecretString'])
+            else:
+                # Binary secrets not supported for this use case
+                raise ValueError("Binary secrets are not supported")
+
+            # Validate required fields
+            if 'private_key' not in secret_data:
+                raise ValueError("Secret must contain 'private_key' field")
+
+            # CRITICAL: Never log the actual private key
+            logger.info("Secret retrieved successfully from AWS Secrets Manager")
+            logger.debug(f"Secret contains keys: {list(secret_data.keys())}")
+
+            return secret_data
+
+        except Exception as e:
+            logger.error(f"Failed to retrieve secret from AWS: {e}")
+            raise ValueError(f"Failed to retrieve secret '{secret_name}': {e}")
+
+
+# ============================================================================
+# Encrypted keystore
+# ============================================================================
+
+class KeystoreSecretsBackend(SecretsBackend):
+    """
+    Ethereum JSON keystore (V3, as written by geth, Foundry's `cast wallet`
+    and eth_account) unlocked with a passphrase.
+
+    The passphrase is taken from, in order: the constructor, a passphrase
+    file (e.g. a systemd credential), KEYSTORE_PASSPHRASE, or an interactive
+    prompt when stdin is a terminal. The decrypted key is kept in memory only.
+    """
+
+    name = "keystore"
+
+    def __init__(
+        self,
+        path: str,
+        passphrase: Optional[str] = None,
+        passphrase_file: Optional[str] = None,
+        passphrase_env: str = "KEYSTORE_PASSPHRASE",
+        prompt: Optional[Callable[[str], str]] = None
+    ):
+        """
+        Args:
+            path: Keystore JSON file
+            passphrase: Passphrase (prefer the file, env var or prompt)
+            passphrase_file: File containing the passphrase
+            passphrase_env: Environment variable holding the passphrase
+            prompt: Interactive prompt (default: getpass when stdin is a TTY)
+        """
+        self.path = path
+        self._passphrase = passphrase
+        self.passphrase_file = passphrase_file
+        self.passphrase_env = passphrase_env
+        self._prompt = prompt
+        self._cache: Optional[Dict[str, Any]] = None
+
+    def _get_passphrase(self) -> str:
+        if self._passphrase is not None:
+            return self._passphrase
+        if self.passphrase_file:
+            with open(self.passphrase_file, "r") as f:
+                return f.read().rstrip("\n")
+        if os.getenv(self.passphrase_env):
+            return os.getenv(self.passphrase_env)
+        if self._prompt is not None:
+            return self._prompt(f"Passphrase for keystore {self.path}: ")
+        if sys.stdin is not None and sys.stdin.isatty():
+            return getpass.getpass(f"Passphrase for keystore {self.path}: ")
+        raise ValueError(
+            f"No passphrase for keystore {self.path}: set {self.passphrase_env}, "
+            f"a passphrase file, or run interactively"
+        )
+
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        if self._cache is not None:
+            return dict(self._cache)
+
+        from eth_account import Account
+
+        try:
+            with open(self.path, "r") as f:
+                keystore = json.load(f)
+        except (OSError, json.JSONDecodeError) as e:
+            raise ValueError(f"Cannot read keystore {self.path}: {e}")
+
+        try:
+            private_key = bytes(Account.decrypt(keystore, self._get_passphrase())).hex()
+        except ValueError as e:
+            # eth_account raises ValueError("MAC mismatch") on a wrong passphrase
+            raise ValueError(f"Cannot decrypt keystore {self.path}: {e}")
+
+        secret_data = {"private_key": "0x" + private_key}
+        if keystore.get("address"):
+            secret_data["wallet_address"] = "0x" + keystore["address"].lower().replace("0x", "")
+
+        # CRITICAL: Never log the actual private key
+        logger.info(f"🔐 Private key unlocked from keystore {self.path}")
+        self._cache = _with_env_defaults(secret_data)
+        return dict(self._cache)
+
+
+# ============================================================================
+# Vault KV
+# ============================================================================
+
+class VaultSecretsBackend(SecretsBackend):
+    """
+    HashiCorp Vault-compatible KV secrets engine (Vault, OpenBao).
+
+    The token comes from the constructor, VAULT_TOKEN, or a token file such
+    as the one written by Vault Agent (default ~/.vault-token).
+    """
+
+    name = "vault"
+
+    def __init__(
+        self,
+        addr: Optional[str] = None,
+        mount: str = "secret",
+        kv_version: int = 2,
+        secret_path: Optional[str] = None,
+        token: Optional[str] = None,
+        token_file: Optional[str] = None,
+        namespace: Optional[str] = None,
+        verify: Any = None,
+        timeout: float = 10.0,
+        session: Any = None
+    ):
+        """
+        Args:
+            addr: Vault address (default: VAULT_ADDR)
+            mount: KV engine mount point
+            kv_version: KV engine version (1 or 2)
+            secret_path: Path of the secret in the engine (default: the secret name)
+            token: Vault token (default: VAULT_TOKEN, then token_file)
+            token_file: Token file (default: ~/.vault-token)
+            namespace: Vault Enterprise namespace (default: VAULT_NAMESPACE)
+            verify: CA bundle path or False (default: VAULT_CACERT, else system CAs)
+            timeout: HTTP timeout in seconds
+            session: requests-compatible session
+        """
+        if kv_version not in (1, 2):
+            raise ValueError(f"Unsupported KV version: {kv_version}")
+        self.addr = (addr or os.getenv("VAULT_ADDR", "")).rstrip("/")
+        if not self.addr:
+            raise ValueError("Vault address is required (vault_addr or VAULT_ADDR)")
+        self.mount = mount.strip("/")
+        self.kv_version = kv_version
+        self.secret_path = secret_path
+        self._token = token
+        self.token_file = token_file or os.path.expanduser("~/.vault-token")
+        self.namespace = namespace or os.getenv("VAULT_NAMESPACE")
+        self.verify = verify if verify is not None else (os.getenv("VAULT_CACERT") or True)
+        self.timeout = timeout
+        if session is None:
+            import requests
+            session = requests.Session()
+        self.session = session
+
+    def _get_token(self) -> str:
+        if self._token:
+            return self._token
+        if os.getenv("VAULT_TOKEN"):
+            return os.getenv("VAULT_TOKEN")
+        try:
+            with open(self.token_file, "r") as f:
+                return f.read().strip()
+        except OSError:
+            raise ValueError(f"No Vault token: set VAULT_TOKEN or write {self.token_file}")
+
+    def _url(self, path: str) -> str:
+        path = path.strip("/")
+        if self.kv_version == 2:
+            return f"{self.addr}/v1/{self.mount}/data/{path}"
+        return f"{self.addr}/v1/{self.mount}/{path}"
+
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        path = self.secret_path or secret_name
+        headers = {"X-Vault-Token": self._get_token()}
+        if self.namespace:
+            headers["X-Vault-Namespace"] = self.namespace
+
+        logger.info(f"Retrieving secret from Vault: {self.mount}/{path}")
+        try:
+            response = self.session.get(self._url(path), headers=headers, timeout=self.timeout, verify=self.verify)
+        except Exception as e:
+            raise ValueError(f"Failed to reach Vault at {self.addr}: {e}")
+
+        if response.status_code != 200:
+            # Vault error bodies never contain the secret
+            try:
+                errors = response.json().get("errors", [])
+            except Exception:
+                errors = []
+            raise ValueError(
+                f"Failed to retrieve secret '{path}' from Vault: HTTP {response.status_code}"
+                + (f" ({'; '.join(errors)})" if errors else "")
+            )
+
+        data = response.json().get("data") or {}
+        secret_data = data.get("data") if self.kv_version == 2 else data
+        if not secret_data or "private_key" not in secret_data:
+            raise ValueError(f"Vault secret '{path}' must contain 'private_key' field")
+
+        # CRITICAL: Never log the actual private key
+        logger.info("Secret retrieved successfully from Vault")
+        logger.debug(f"Secret contains keys: {list(secret_data.keys())}")
+        return _with_env_defaults(dict(secret_data))
+
+
+# ============================================================================
+# systemd credentials / file descriptor
+# ============================================================================
+
+class CredentialsSecretsBackend(SecretsBackend):
+    """
+    Secrets passed by the service manager instead of the environment.
+
+    - systemd credentials (LoadCredential=/LoadCredentialEncrypted=): files in
+      $CREDENTIALS_DIRECTORY. A credential named after the secret holds a JSON
+      object or a bare key; otherwise one credential per field is read
+      (private_key, wallet_address, nvidia_api_key, kalshi_api_key).
+    - A file descriptor inherited from the parent (e.g. `3<secret.json`),
+      read once and closed.
+    """
+
+    name = "credentials"
+
+    FIELD_NAMES = ("private_key", "wallet_address", "nvidia_api_key", "kalshi_api_key")
+
+    def __init__(self, directory: Optional[str] = None, fd: Optional[int] = None):
+        """
+        Args:
+            directory: Credentials directory (default: $CREDENTIALS_DIRECTORY)
+            fd: File descriptor to read the secret from instead
+        """
+        self.directory = directory or os.getenv("CREDENTIALS_DIRECTORY")
+        self.fd = fd
+        self._cache: Optional[Dict[str, Any]] = None
+        if self.fd is None and not self.directory:
+            raise ValueError("credentials backend needs a file descriptor or $CREDENTIALS_DIRECTORY")
+
+    def _read_fd(self) -> Dict[str, Any]:
+        try:
+            with os.fdopen(self.fd, "r") as f:
+                content = f.read()
+        except OSError as e:
+            raise ValueError(f"Cannot read secret from file descriptor {self.fd}: {e}")
+        return _parse_secret(content, f"fd {self.fd}")
+
+    def _read_directory(self, secret_name: str) -> Dict[str, Any]:
+        bundle = os.path.join(self.directory, secret_name)
+        if os.path.isfile(bundle):
+            with open(bundle, "r") as f:
+                return _parse_secret(f.read(), f"credential {secret_name}")
+
+        secret_data = {}
+        for field_name in self.FIELD_NAMES:
+            path = os.path.join(self.directory, field_name)
+            if os.path.isfile(path):
+                with open(path, "r") as f:
+                    secret_data[field_name] = f.read().strip()
+        return secret_data
+
+    def get_secret(self, secret_name: str) -> Dict[str, Any]:
+        if self._cache is not None:
+            return dict(self._cache)
+
+        if self.fd is not None:
+            secret_data = self._read_fd()
+            source = f"file descriptor {self.fd}"
+        else:
+            secret_data = self._read_directory(secret_name)
+            source = f"credentials directory {self.directory}"
+
+        if not secret_data.get("private_key"):
+            raise ValueError(f"No private_key found in {source}")
+
+        # CRITICAL: Never log the actual private key
+        logger.info(f"Credentials retrieved from {source}")
+        self._cache = _with_env_defaults(secret_data)
+        return dict(self._cache)
+
+
+SECRETS_BACKENDS = {
+    backend.name: backend
+    for backend in (EnvSecretsBackend, AwsSecretsBackend, KeystoreSecretsBackend,
+                    VaultSecretsBackend, CredentialsSecretsBackend)
+}
+
+
+def create_secrets_backend(kind: str, **options) -> SecretsBackend:
+    """
+    Create a backend by name.
+
+    Args:
+        kind: One of SECRETS_BACKENDS (env, aws, keystore, vault, credentials)
+        **options: Backend constructor arguments (None values are dropped)
+
+    Raises:
+        ValueError: If the backend is unknown
+    """
+    backend_cls = SECRETS_BACKENDS.get(kind)
+    if backend_cls is None:
+        raise ValueError(f"Unknown secrets backend '{kind}' (available: {', '.join(SECRETS_BACKENDS)})")
+    return backend_cls(**{k: v for k, v in options.items() if v is not None})
@@ -1,26 +1,29 @@
 """
-AWS Secrets Manager integration for secure private key storage.
+Secure private key storage.
 
 Implements Requirements 14.1, 14.3:
-- Retrieve private keys from AWS Secrets Manager
+- Retrieve private keys from AWS Secrets Manager or another backend
+  (encrypted keystore, Vault KV, systemd credentials)
 - Use IAM roles for authentication
 - Never log private keys
 """
 
-import json
 import logging
 from typing import Dict, Any, Optional
 import re
 
+from src.secrets_backends import AwsSecretsBackend, EnvSecretsBackend, SecretsBackend
+
 logger = logging.getLogger(__name__)
 
 
 class SecretsManager:
     """
-    Manages secure retrieval of secrets from AWS Secrets Manager.
+    Manages secure retrieval of secrets through a pluggable backend.
     
     Responsibilities:
-    - Retrieve private keys from AWS Secrets Manager
+    - Retrieve private keys from a secrets backend (AWS Secrets Manager,
+      encrypted keystore, Vault KV, systemd credentials; see secrets_backends)
     - Use IAM roles for authentication (no hardcoded credentials)
     - Ensure private keys are never logged
     - Provide fallback to environment variables for local development
@@ -30,37 +33,42 @@ class SecretsManager:
     - 14.3: Never log private keys
     """
     
-    def __init__(self, use_aws: bool = True, region_name: str = "us-east-1"):
+    def __init__(
+        self,
+        use_aws: bool = True,
+        region_name: str = "us-east-1",
+        backend: Optional[SecretsBackend] = None
+    ):
         """
         Initialize Secrets Manager client.
         
         Args:
             use_aws: If True, use AWS Secrets Manager. If False, use environment variables.
+                Ignored when a backend is given.
             region_name: AWS region for Secrets Manager
+            backend: Secrets backend to use (overrides use_aws)
         """
-        self.use_aws = use_aws
         self.region_name = region_name
         self._client = None
         
-        if use_aws:
+        if backend is None and use_aws:
             try:
-                import boto3
-                # Use IAM role credentials (no explicit credentials needed)
-                self._client = boto3.client('secretsmanager', region_name=region_name)
-                logger.info(f"AWS Secrets Manager client initialized (region: {region_name})")
+                backend = AwsSecretsBackend(region_name=region_name)
+                self._client = backend.client
             except ImportError:
                 logger.warning("boto3 not installed. Falling back to environment variables.")
-                self.use_aws = False
             except Exception as e:
                 logger.warning(f"Failed to initialize AWS Secrets Manager: {e}. Falling back to environment variables.")
-                self.use_aws = False
+        
+        self.backend = backend or EnvSecretsBackend()
+        self.use_aws = isinstance(self.backend, AwsSecretsBackend)
     
     def get_secret(self, secret_name: str) -> Dict[str, Any]:
         """
-        Retrieve a secret from AWS Secrets Manager or environment variables.
+        Retrieve a secret from the configured backend.
         
         Args:
-            secret_name: Name of the secret in AWS Secrets Manager
+            secret_name: Name of the secret (AWS secret ID, Vault path, credential name)
             
         Returns:
             Dictionary containing secret values
@@ -68,87 +76,19 @@ class SecretsManager:
         Raises:
             ValueError: If secret cannot be retrieved
         """
-        if self.use_aws:
-            return self._get_secret_from_aws(secret_name)
-        else:
-            return self._get_secret_from_env()
+        return self.backend.get_secret(secret_name)
     
     def _get_secret_from_aws(self, secret_name: str) -> Dict[str, Any]:
-        """
-        Retrieve secret from AWS Secrets Manager.
-        
-        Args:
-            secret_name: Name of the secret
-            
-        Returns:
-            Dictionary containing secret values
-            
-        Raises:
-            ValueError: If secret cannot be retrieved
-        """
-        try:
-            logger.info(f"Retrieving secret from AWS Secrets Manager: {secret_name}")
-            
-            response = self._client.get_secret_value(SecretId=secret_name)
-            
-            # Parse secret string
-            if 'SecretString' in response:
-                secret_data = json.loads(response['SecretString'])
-            else:
-                # Binary secrets not supported for this use case
-                raise ValueError("Binary secrets are not supported")
-            
-            # Validate required fields
-            if 'private_key' not in secret_data:
-                raise ValueError("Secret must contain 'private_key' field")
-            
-            # CRITICAL: Never log the actual private key
-            logger.info("Secret retrieved successfully from AWS Secrets Manager")
-            logger.debug(f"Secret contains keys: {list(secret_data.keys())}")
-            
-            return secret_data
-            
-        except Exception as e:
-            logger.error(f"Failed to retrieve secret from AWS: {e}")
-            raise ValueError(f"Failed to retrieve secret '{secret_name}': {e}")
+        """Retrieve secret from AWS Secrets Manager."""
+        return AwsSecretsBackend(self.region_name, client=self._client).get_secret(secret_name)
     
     def _get_secret_from_env(self) -> Dict[str, Any]:
-        """
-        Retrieve secret from environment variables (fallback for local development).
-        
-        Returns:
-            Dictionary containing secret values from environment
-            
-        Raises:
-            ValueError: If required environment variables are missing
-        """
-        import os
-        
-        logger.info("Retrieving credentials from environment variables")
-        
-        private_key = os.getenv("PRIVATE_KEY")
-        if not private_key:
-            raise ValueError("PRIVATE_KEY environment variable is required")
-        
-        # CRITICAL: Never log the actual private key
-        logger.info("Credentials retrieved from environment variables")
-        
-        secret_data = {
-            'private_key': private_key,
-            'wallet_address': os.getenv("WALLET_ADDRESS", ""),
-        }
-        
-        # Add optional fields if present
-        if os.getenv("NVIDIA_API_KEY"):
-            secret_data['nvidia_api_key'] = os.getenv("NVIDIA_API_KEY")
-        if os.getenv("KALSHI_API_KEY"):
-            secret_data['kalshi_api_key'] = os.getenv("KALSHI_API_KEY")
-        
-        return secret_data
+        """Retrieve secret from environment variables (fallback for local development)."""
+        return EnvSecretsBackend().get_secret("")
     
     def get_private_key(self, secret_name: str = "polymarket-bot-credentials") -> str:
         """
-        Retrieve private key from AWS Secrets Manager.
+        Retrieve private key from the configured backend.
         
         Args:
             secret_name: Name of the secret containing the private key
@@ -265,15 +205,20 @@ class SecureLogger:
         self._logger.exception(self._sanitize(message), *args, **kwargs)
 
 
-def get_secrets_manager(use_aws: bool = True, region_name: str = "us-east-1") -> SecretsManager:
+def get_secrets_manager(
+    use_aws: bool = True,
+    region_name: str = "us-east-1",
+    backend: Optional[SecretsBackend] = None
+) -> SecretsManager:
     """
     Factory function to create SecretsManager instance.
     
     Args:
         use_aws: If True, use AWS Secrets Manager. If False, use environment variables.
         region_name: AWS region for Secrets Manager
+        backend: Secrets backend to use instead (see secrets_backends.create_secrets_backend)
         
     Returns:
         SecretsManager instance
     """
-    return SecretsManager(use_aws=use_aws, region_name=region_name)
+    return SecretsManager(use_aws=use_aws, region_name=region_name, backend=backend)
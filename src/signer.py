@@ -0,0 +1,404 @@
+"""
+Signer abstraction for CLOB orders and Polygon transactions.
+
+Everything that needs the wallet key goes through a Signer:
+- Polygon transactions (TransactionManager, PositionMerger, FundManager,
+  TokenAllowanceManager) call sign_transaction(), so a Signer can stand in
+  for the eth_account LocalAccount they used before
+- CLOB orders are signed as Polymarket CTF Exchange EIP-712 Order messages
+- CLOB L1 authentication signs the ClobAuth EIP-712 message
+
+LocalSigner keeps the key in-process (the previous behaviour). RemoteSigner
+forwards each request to the signing daemon (src/signer_daemon.py) over a
+Unix socket; the daemon holds the key and checks every request against its
+policy, so a compromised bot process cannot drain the wallet.
+
+Validates Requirements:
+- Private key isolation from the trading process
+- Per-request signing policy (max notional, allowed contracts)
+"""
+
+import json
+import logging
+import secrets
+import socket
+import time
+from abc import ABC, abstractmethod
+from dataclasses import dataclass
+from typing import Any, Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+ZERO_ADDRESS = "0x0000000000000000000000000000000000000000"
+CLOB_AUTH_MESSAGE = "This message attests that I control the given wallet"
+
+ORDER_SIDES = {"BUY": 0, "SELL": 1}
+
+# Polymarket CTF Exchange order struct (EIP-712)
+ORDER_TYPES = {
+    "EIP712Domain": [
+        {"name": "name", "type": "string"},
+        {"name": "version", "type": "string"},
+        {"name": "chainId", "type": "uint256"},
+        {"name": "verifyingContract", "type": "address"},
+    ],
+    "Order": [
+        {"name": "salt", "type": "uint256"},
+        {"name": "maker", "type": "address"},
+        {"name": "signer", "type": "address"},
+        {"name": "taker", "type": "address"},
+        {"name": "tokenId", "type": "uint256"},
+        {"name": "makerAmount", "type": "uint256"},
+        {"name": "takerAmount", "type": "uint256"},
+        {"name": "expiration", "type": "uint256"},
+        {"name": "nonce", "type": "uint256"},
+        {"name": "feeRateBps", "type": "uint256"},
+        {"name": "side", "type": "uint8"},
+        {"name": "signatureType", "type": "uint8"},
+    ],
+}
+
+CLOB_AUTH_TYPES = {
+    "EIP712Domain": [
+        {"name": "name", "type": "string"},
+        {"name": "version", "type": "string"},
+        {"name": "chainId", "type": "uint256"},
+    ],
+    "ClobAuth": [
+        {"name": "address", "type": "address"},
+        {"name": "timestamp", "type": "string"},
+        {"name": "nonce", "type": "uint256"},
+        {"name": "message", "type": "string"},
+    ],
+}
+
+ORDER_UINT_FIELDS = ("salt", "tokenId", "makerAmount", "takerAmount", "expiration", "nonce", "feeRateBps")
+
+
+class SignerError(Exception):
+    """Signing failed, was refused by policy, or the signer is unreachable."""
+
+
+@dataclass
+class SignedTransaction:
+    """Signed transaction, attribute-compatible with eth_account's SignedTransaction."""
+    raw_transaction: bytes
+    hash: bytes
+
+    @property
+    def rawTransaction(self) -> bytes:
+        """Alias used by older eth_account versions."""
+        return self.raw_transaction
+
+
+def order_typed_data(order: Dict[str, Any], exchange_address: str, chain_id: int) -> Dict[str, Any]:
+    """EIP-712 message for a CTF Exchange order (side as 'BUY'/'SELL' or 0/1)."""
+    message = {name: int(order[name]) for name in ORDER_UINT_FIELDS}
+    message.update(
+        maker=order["maker"],
+        signer=order["signer"],
+        taker=order.get("taker") or ZERO_ADDRESS,
+        side=ORDER_SIDES.get(order["side"], order["side"]),
+        signatureType=int(order["signatureType"]),
+    )
+    return {
+        "types": ORDER_TYPES,
+        "primaryType": "Order",
+        "domain": {
+            "name": "Polymarket CTF Exchange",
+            "version": "1",
+            "chainId": int(chain_id),
+            "verifyingContract": exchange_address,
+        },
+        "message": message,
+    }
+
+
+def clob_auth_typed_data(address: str, timestamp: int, nonce: int, chain_id: int) -> Dict[str, Any]:
+    """EIP-712 ClobAuth message used for CLOB L1 authentication."""
+    return {
+        "types": CLOB_AUTH_TYPES,
+        "primaryType": "ClobAuth",
+        "domain": {"name": "ClobAuthDomain", "version": "1", "chainId": int(chain_id)},
+        "message": {
+            "address": address,
+            "timestamp": str(timestamp),
+            "nonce": int(nonce),
+            "message": CLOB_AUTH_MESSAGE,
+        },
+    }
+
+
+# ============================================================================
+# Signers
+# ============================================================================
+
+class Signer(ABC):
+    """Holder of the wallet key (in-process or remote)."""
+
+    @property
+    @abstractmethod
+    def address(self) -> str:
+        """Checksummed address of the signing key."""
+
+    @abstractmethod
+    def sign_transaction(self, tx: Dict[str, Any]) -> SignedTransaction:
+        """Sign a Polygon transaction dict (as built by web3)."""
+
+    @abstractmethod
+    def sign_order(self, order: Dict[str, Any], exchange_address: str, chain_id: int) -> str:
+        """Sign a CTF Exchange order; returns the 0x-prefixed signature."""
+
+    @abstractmethod
+    def sign_clob_auth(self, timestamp: int, nonce: int, chain_id: int) -> str:
+        """Sign the ClobAuth message for L1 headers; returns the signature."""
+
+
+class LocalSigner(Signer):
+    """Signs in-process with a private key (eth_account)."""
+
+    def __init__(self, private_key: str):
+        from eth_account import Account
+
+        self._account = Account.from_key(private_key)
+
+    @property
+    def address(self) -> str:
+        return self._account.address
+
+    def sign_transaction(self, tx: Dict[str, Any]) -> SignedTransaction:
+        signed = self._account.sign_transaction(tx)
+        raw = getattr(signed, "raw_transaction", None) or signed.rawTransaction
+        return SignedTransaction(bytes(raw), bytes(signed.hash))
+
+    def sign_order(self, order: Dict[str, Any], exchange_address: str, chain_id: int) -> str:
+        return self._sign_typed_data(order_typed_data(order, exchange_address, chain_id))
+
+    def sign_clob_auth(self, timestamp: int, nonce: int, chain_id: int) -> str:
+        return self._sign_typed_data(clob_auth_typed_data(self.address, timestamp, nonce, chain_id))
+
+    def _sign_typed_data(self, typed_data: Dict[str, Any]) -> str:
+        from eth_account.messages import encode_typed_data
+
+        signed = self._account.sign_message(encode_typed_data(full_message=typed_data))
+        return "0x" + bytes(signed.signature).hex()
+
+
+class RemoteSigner(Signer):
+    """
+    Client of the signing daemon (src/signer_daemon.py).
+
+    Requests are newline-delimited JSON over a Unix socket, one connection
+    per request. Refusals by the daemon's policy raise SignerError.
+    """
+
+    def __init__(self, socket_path: str, timeout: float = 10.0):
+        self.socket_path = socket_path
+        self.timeout = timeout
+        self._address: Optional[str] = None
+
+    @property
+    def address(self) -> str:
+        if self._address is None:
+            self._address = self._request("address")
+        return self._address
+
+    def sign_transaction(self, tx: Dict[str, Any]) -> SignedTransaction:
+        result = self._request("sign_transaction", tx=_to_json(tx))
+        return SignedTransaction(bytes.fromhex(result["raw_transaction"][2:]), bytes.fromhex(result["hash"][2:]))
+
+    def sign_order(self, order: Dict[str, Any], exchange_address: str, chain_id: int) -> str:
+        return self._request("sign_order", order=_to_json(order), exchange=exchange_address, chain_id=chain_id)
+
+    def sign_clob_auth(self, timestamp: int, nonce: int, chain_id: int) -> str:
+        return self._request("sign_clob_auth", timestamp=int(timestamp), nonce=int(nonce), chain_id=chain_id)
+
+    def _request(self, method: str, **params) -> Any:
+        request = {"id": secrets.token_hex(6), "method": method, "params": params}
+        try:
+            with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+                sock.settimeout(self.timeout)
+                sock.connect(self.socket_path)
+                sock.sendall(json.dumps(request).encode() + b"\n")
+                buffer = b""
+                while not buffer.endswith(b"\n"):
+                    chunk = sock.recv(65536)
+                    if not chunk:
+                        break
+                    buffer += chunk
+        except OSError as e:
+            raise SignerError(f"Signing daemon unreachable at {self.socket_path}: {e}")
+
+        try:
+            response = json.loads(buffer)
+        except json.JSONDecodeError:
+            raise SignerError(f"Invalid response from signing daemon for {method}")
+        if response.get("error"):
+            raise SignerError(f"Signing daemon refused {method}: {response['error']}")
+        return response.get("result")
+
+
+def _to_json(value: Any) -> Any:
+    """Make a transaction/order dict JSON-safe (bytes -> 0x hex)."""
+    if isinstance(value, (bytes, bytearray)):
+        return "0x" + bytes(value).hex()
+    if isinstance(value, dict):
+        return {key: _to_json(item) for key, item in value.items()}
+    if isinstance(value, (list, tuple)):
+        return [_to_json(item) for item in value]
+    return value
+
+
+def create_signer(backend: str, private_key: Optional[str] = None,
+                  socket_path: Optional[str] = None) -> Signer:
+    """Build the signer selected by Config.signer_backend."""
+    if backend == "local":
+        return LocalSigner(private_key)
+    if backend == "remote":
+        return RemoteSigner(socket_path)
+    raise ValueError(f"Unknown signer backend '{backend}' (available: local, remote)")
+
+
+# ============================================================================
+# py_clob_client integration
+# ============================================================================
+
+@dataclass
+class SignedClobOrder:
+    """Signed order in the shape py_clob_client posts (SignedOrder.dict())."""
+    order: Dict[str, Any]
+    signature: str
+
+    def dict(self) -> Dict[str, Any]:
+        return {**self.order, "signature": self.signature}
+
+
+class ClobSignerAdapter:
+    """
+    Stands in for py_clob_client's Signer without exposing a key.
+
+    Raw hash signing is not supported: the daemon only signs messages it can
+    inspect. L1 headers are built with clob_l1_headers() instead.
+    """
+
+    private_key = None
+
+    def __init__(self, signer: Signer, chain_id: int):
+        self.signer = signer
+        self.chain_id = chain_id
+
+    def address(self) -> str:
+        return self.signer.address
+
+    def get_chain_id(self) -> int:
+        return self.chain_id
+
+    def sign(self, message_hash) -> str:
+        raise SignerError("Raw hash signing is disabled; use clob_l1_headers() for CLOB authentication")
+
+
+class SignerOrderBuilder:
+    """
+    Order builder that signs through a Signer.
+
+    Amount rounding and contract addresses come from py_clob_client's
+    OrderBuilder (`base`); only the signing step is replaced.
+    """
+
+    def __init__(self, base, signer: Signer, signature_type: int, funder: str, chain_id: int):
+        self.base = base
+        self.signer = signer
+        self.signature_type = signature_type
+        self.funder = funder
+        self.chain_id = chain_id
+
+    def __getattr__(self, name):
+        # Price/amount helpers (calculate_market_price etc.) come from the base builder
+        return getattr(self.base, name)
+
+    def create_order(self, order_args, options) -> SignedClobOrder:
+        from py_clob_client.order_builder.builder import ROUNDING_CONFIG
+
+        side, maker_amount, taker_amount = self.base.get_order_amounts(
+            order_args.side, order_args.size, order_args.price, ROUNDING_CONFIG[options.tick_size]
+        )
+        return self._sign(order_args, options, side, maker_amount, taker_amount, order_args.expiration)
+
+    def create_market_order(self, order_args, options) -> SignedClobOrder:
+        from py_clob_client.order_builder.builder import ROUNDING_CONFIG
+
+        side, maker_amount, taker_amount = self.base.get_market_order_amounts(
+            order_args.side, order_args.amount, order_args.price, ROUNDING_CONFIG[options.tick_size]
+        )
+        return self._sign(order_args, options, side, maker_amount, taker_amount, 0)
+
+    def _sign(self, order_args, options, side, maker_amount, taker_amount, expiration) -> SignedClobOrder:
+        from py_clob_client.config import get_contract_config
+
+        exchange = get_contract_config(self.chain_id, options.neg_risk).exchange
+        order = {
+            "salt": secrets.randbits(48),
+            "maker": self.funder,
+            "signer": self.signer.address,
+            "taker": getattr(order_args, "taker", None) or ZERO_ADDRESS,
+            "tokenId": str(order_args.token_id),
+            "makerAmount": str(maker_amount),
+            "takerAmount": str(taker_amount),
+            "expiration": str(expiration),
+            "nonce": str(order_args.nonce),
+            "feeRateBps": str(order_args.fee_rate_bps),
+            "side": "BUY" if side in (0, "BUY") else "SELL",
+            "signatureType": self.signature_type,
+        }
+        return SignedClobOrder(order, self.signer.sign_order(order, exchange, self.chain_id))
+
+
+def attach_signer(client, signer: Signer, chain_id: int, signature_type: int, funder: Optional[str] = None) -> None:
+    """
+    Make a ClobClient created without a key sign through `signer`.
+
+    Replaces the client's signer and order builder; API credentials are then
+    obtained with derive_api_creds().
+    """
+    from py_clob_client.order_builder.builder import OrderBuilder
+
+    adapter = ClobSignerAdapter(signer, chain_id)
+    funder = funder or signer.address
+    client.signer = adapter
+    client.builder = SignerOrderBuilder(OrderBuilder(adapter, signature_type, funder), signer,
+                                        signature_type, funder, chain_id)
+    client.mode = client._get_client_mode()
+
+
+def clob_l1_headers(signer: Signer, chain_id: int, nonce: int = 0) -> Dict[str, str]:
+    """CLOB L1 authentication headers signed by `signer`."""
+    timestamp = int(time.time())
+    return {
+        "POLY_ADDRESS": signer.address,
+        "POLY_SIGNATURE": signer.sign_clob_auth(timestamp, nonce, chain_id),
+        "POLY_TIMESTAMP": str(timestamp),
+        "POLY_NONCE": str(nonce),
+    }
+
+
+def derive_api_creds(host: str, signer: Signer, chain_id: int, nonce: int = 0, session=None):
+    """
+    Create or derive CLOB API credentials (create_or_derive_api_creds for a Signer).
+
+    Returns:
+        py_clob_client ApiCreds
+    """
+    import requests
+    from py_clob_client.clob_types import ApiCreds
+
+    session = session or requests
+    host = host.rstrip("/")
+    response = session.post(f"{host}/auth/api-key", headers=clob_l1_headers(signer, chain_id, nonce), timeout=10)
+    if response.status_code != 200:
+        # Key already exists for this nonce: derive it instead
+        response = session.get(f"{host}/auth/derive-api-key", headers=clob_l1_headers(signer, chain_id, nonce),
+                               timeout=10)
+    if response.status_code != 200:
+        raise SignerError(f"Could not obtain CLOB API credentials: HTTP {response.status_code}")
+    data = response.json()
+    return ApiCreds(api_key=data["apiKey"], api_secret=data["secret"], api_passphrase=data["passphrase"])
@@ -0,0 +1,459 @@
+"""
+Out-of-process signing daemon.
+
+Holds the wallet key in a separate process and signs requests from the bot
+(RemoteSigner) over a Unix socket. Every request is checked against a
+SigningPolicy before the key is used, so a compromised bot process can only
+do what the policy allows:
+- Orders: chain, exchange contract, maker/signer addresses, public taker,
+  per-order and daily notional caps
+- Transactions: chain, allowed contracts, native value and gas price caps;
+  token-moving calls (ERC20 approve/transfer, ERC1155 transfers) are decoded
+  and their spender/recipient checked, any other function must be allowed
+  for its contract, and the same checks apply to calls wrapped in a Gnosis
+  Safe execTransaction (which may not target the Safe itself or pay a gas refund)
+- ClobAuth: only the fixed attestation message with a fresh timestamp
+
+Protocol: one JSON request per line, {"id", "method", "params"}; the reply
+is {"id", "result"} or {"id", "error"}.
+
+Run with: python -m src.signer_daemon --config config/signer_daemon.yaml
+
+Validates Requirements:
+- Private key isolation from the trading process
+- Per-request signing policy (max notional, allowed contracts)
+"""
+
+import argparse
+import asyncio
+import json
+import logging
+import os
+import socket
+import struct
+import time
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from decimal import Decimal
+from typing import Any, Callable, Dict, Iterable, List, Optional, Set
+
+from src.signer import ORDER_SIDES, ZERO_ADDRESS, LocalSigner, Signer
+
+logger = logging.getLogger(__name__)
+
+# Polymarket CTF Exchange and Neg Risk CTF Exchange (Polygon)
+DEFAULT_EXCHANGES = (
+    "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E",
+    "0xC5d563A36AE78145C45a50134d48A1215220f80a",
+)
+
+# Function selectors whose arguments are checked
+SELECTOR_APPROVE = "095ea7b3"  # approve(address,uint256)
+SELECTOR_INCREASE_ALLOWANCE = "39509351"  # increaseAllowance(address,uint256)
+SELECTOR_SET_APPROVAL_FOR_ALL = "a22cb465"  # setApprovalForAll(address,bool)
+SELECTOR_TRANSFER = "a9059cbb"  # transfer(address,uint256)
+SELECTOR_TRANSFER_FROM = "23b872dd"  # transferFrom(address,address,uint256)
+SELECTOR_SAFE_TRANSFER_FROM = "f242432a"  # ERC1155 safeTransferFrom(address,address,uint256,uint256,bytes)
+SELECTOR_SAFE_BATCH_TRANSFER_FROM = "2eb2c2d6"  # ERC1155 safeBatchTransferFrom(...)
+SELECTOR_EXEC_TRANSACTION = "6a761202"  # Gnosis Safe execTransaction(address,uint256,bytes,uint8,...)
+
+# Other functions the bot calls (Polygon); every other selector is refused unless configured
+CTF_ADDRESS = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"
+NEG_RISK_ADAPTER_ADDRESS = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"
+DEFAULT_ALLOWED_CALLS = {
+    CTF_ADDRESS: (
+        "01b7037c",  # redeemPositions(address,bytes32,bytes32,uint256[])
+        "9e7212ad",  # mergePositions(address,bytes32,bytes32,uint256[],uint256)
+    ),
+    NEG_RISK_ADAPTER_ADDRESS: (
+        "dbeccb23",  # redeemPositions(bytes32,uint256[])
+    ),
+}
+
+USDC_UNIT = Decimal("1000000")  # USDC and outcome shares use 6 decimals
+
+
+class PolicyViolation(Exception):
+    """A signing request was refused by the policy."""
+
+
+def _normalize(addresses: Iterable[str]) -> Set[str]:
+    return {address.lower() for address in addresses if address}
+
+
+def _normalize_calls(calls: Dict[str, Iterable[str]]) -> Dict[str, Set[str]]:
+    return {
+        contract.lower(): {selector.lower().removeprefix("0x") for selector in selectors}
+        for contract, selectors in calls.items()
+    }
+
+
+@dataclass
+class SigningPolicy:
+    """
+    Limits on what the daemon will sign.
+
+    Addresses are compared case-insensitively. The signing key's own address
+    is always an allowed maker and recipient.
+    """
+    chain_id: int = 137
+    max_order_notional: Decimal = Decimal("100")  # USDC per order
+    max_daily_notional: Decimal = Decimal("2000")  # USDC per UTC day across orders
+    allowed_exchanges: Set[str] = field(default_factory=lambda: set(DEFAULT_EXCHANGES))
+    allowed_makers: Set[str] = field(default_factory=set)  # e.g. the Gnosis Safe funder
+    allowed_contracts: Set[str] = field(default_factory=set)  # Transaction targets
+    # Selectors (hex) allowed per contract, besides the decoded token calls and Safe execTransaction
+    allowed_calls: Dict[str, Set[str]] = field(default_factory=lambda: dict(DEFAULT_ALLOWED_CALLS))
+    allowed_spenders: Set[str] = field(default_factory=lambda: set(DEFAULT_EXCHANGES))  # approve / setApprovalForAll
+    allowed_recipients: Set[str] = field(default_factory=set)  # transfer destinations
+    max_transaction_value_wei: int = 0  # Native MATIC sent per transaction
+    max_gas_price_gwei: Decimal = Decimal("1000")
+    clob_auth_max_age_seconds: int = 300
+
+    def __post_init__(self):
+        self.max_order_notional = Decimal(str(self.max_order_notional))
+        self.max_daily_notional = Decimal(str(self.max_daily_notional))
+        self.max_gas_price_gwei = Decimal(str(self.max_gas_price_gwei))
+        for name in ("allowed_exchanges", "allowed_makers", "allowed_contracts",
+                     "allowed_spenders", "allowed_recipients"):
+            setattr(self, name, _normalize(getattr(self, name)))
+        self.allowed_calls = _normalize_calls(self.allowed_calls)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "SigningPolicy":
+        unknown = set(data) - set(cls.__dataclass_fields__)
+        if unknown:
+            raise ValueError(f"Unknown signing policy settings: {', '.join(sorted(unknown))}")
+        return cls(**data)
+
+
+class PolicyEngine:
+    """Checks requests against a SigningPolicy and tracks daily order notional."""
+
+    def __init__(self, policy: SigningPolicy, own_address: str, clock: Optional[Callable[[], float]] = None):
+        self.policy = policy
+        self.own_address = own_address.lower()
+        self._clock = clock or time.time
+        self._day: Optional[str] = None
+        self._daily_notional = Decimal("0")
+
+    @property
+    def daily_notional(self) -> Decimal:
+        self._roll_day()
+        return self._daily_notional
+
+    # ========================================================================
+    # Orders
+    # ========================================================================
+
+    def check_order(self, order: Dict[str, Any], exchange: str, chain_id: int) -> Decimal:
+        """Validate an order; returns its notional in USDC (not yet counted)."""
+        policy = self.policy
+        self._check_chain(chain_id)
+        if exchange.lower() not in policy.allowed_exchanges:
+            raise PolicyViolation(f"exchange {exchange} not allowed")
+        if order["signer"].lower() != self.own_address:
+            raise PolicyViolation(f"order signer {order['signer']} is not this key")
+        if order["maker"].lower() not in policy.allowed_makers | {self.own_address}:
+            raise PolicyViolation(f"order maker {order['maker']} not allowed")
+        if (order.get("taker") or ZERO_ADDRESS).lower() != ZERO_ADDRESS:
+            raise PolicyViolation("private orders (non-zero taker) are not allowed")
+        if order["side"] not in ORDER_SIDES and order["side"] not in ORDER_SIDES.values():
+            raise PolicyViolation(f"unknown order side {order['side']}")
+
+        # BUY: makerAmount is USDC paid. SELL: makerAmount is shares, each worth at most $1
+        notional = Decimal(int(order["makerAmount"])) / USDC_UNIT
+        if notional > policy.max_order_notional:
+            raise PolicyViolation(f"order notional ${notional} exceeds max ${policy.max_order_notional}")
+        if self.daily_notional + notional > policy.max_daily_notional:
+            raise PolicyViolation(
+                f"daily notional ${self._daily_notional + notional} would exceed max ${policy.max_daily_notional}"
+            )
+        return notional
+
+    def record_order(self, notional: Decimal) -> None:
+        self._roll_day()
+        self._daily_notional += notional
+
+    # ========================================================================
+    # Transactions
+    # ========================================================================
+
+    def check_transaction(self, tx: Dict[str, Any]) -> None:
+        policy = self.policy
+        self._check_chain(_int(tx.get("chainId")))
+        gas_price = _int(tx.get("maxFeePerGas", tx.get("gasPrice", 0)))
+        if Decimal(gas_price) / Decimal(10 ** 9) > policy.max_gas_price_gwei:
+            raise PolicyViolation(f"gas price {gas_price} wei exceeds max {policy.max_gas_price_gwei} gwei")
+        to, value, data = tx.get("to"), _int(tx.get("value", 0)), _bytes(tx.get("data", ""))
+        if to and to.lower() == self.own_address and value == 0 and not data:
+            return  # Empty self-send: cancels a pending transaction (TransactionManager.cancel_transaction)
+        self._check_call(to, value, data)
+
+    def _check_call(self, to: Optional[str], value: int, data: bytes, depth: int = 0) -> None:
+        policy = self.policy
+        if not to:
+            raise PolicyViolation("contract creation is not allowed")
+        if to.lower() not in policy.allowed_contracts:
+            raise PolicyViolation(f"contract {to} not allowed")
+        if value > policy.max_transaction_value_wei:
+            raise PolicyViolation(f"value {value} wei exceeds max {policy.max_transaction_value_wei}")
+        if len(data) < 4:
+            return
+
+        selector = data[:4].hex()
+        if selector in (SELECTOR_APPROVE, SELECTOR_INCREASE_ALLOWANCE, SELECTOR_SET_APPROVAL_FOR_ALL):
+            spender = _address_arg(data, 0)
+            if spender not in policy.allowed_spenders:
+                raise PolicyViolation(f"approval for spender {spender} not allowed")
+        elif selector == SELECTOR_TRANSFER:
+            self._check_recipient(_address_arg(data, 0))
+        elif selector in (SELECTOR_TRANSFER_FROM, SELECTOR_SAFE_TRANSFER_FROM, SELECTOR_SAFE_BATCH_TRANSFER_FROM):
+            self._check_recipient(_address_arg(data, 1))
+        elif selector == SELECTOR_EXEC_TRANSACTION:
+            # Safe wrapper: the inner call must pass the same checks (CALL only, no DELEGATECALL)
+            if depth > 0:
+                raise PolicyViolation("nested Safe transactions are not allowed")
+            if _uint_arg(data, 3) != 0:
+                raise PolicyViolation("Safe DELEGATECALL is not allowed")
+            # safeTxGas, baseGas, gasPrice, gasToken, refundReceiver: a refund pays Safe funds
+            # (e.g. USDC as gasToken) to refundReceiver, even when the inner call fails
+            if any(_uint_arg(data, index) != 0 for index in range(4, 9)):
+                raise PolicyViolation("Safe gas refunds are not allowed")
+            inner_to = _address_arg(data, 0)
+            if inner_to == to.lower():
+                # enableModule, addOwnerWithThreshold, changeThreshold, setGuard, ... would hand over the Safe
+                raise PolicyViolation("Safe calls to the Safe itself are not allowed")
+            self._check_call(inner_to, _uint_arg(data, 1), _bytes_arg(data, 2), depth + 1)
+        elif selector not in policy.allowed_calls.get(to.lower(), set()):
+            raise PolicyViolation(f"function 0x{selector} not allowed on contract {to}")
+
+    def _check_recipient(self, recipient: str) -> None:
+        if recipient not in self.policy.allowed_recipients | {self.own_address}:
+            raise PolicyViolation(f"transfer to {recipient} not allowed")
+
+    # ========================================================================
+    # ClobAuth
+    # ========================================================================
+
+    def check_clob_auth(self, timestamp: int, nonce: int, chain_id: int) -> None:
+        self._check_chain(chain_id)
+        if abs(self._clock() - int(timestamp)) > self.policy.clob_auth_max_age_seconds:
+            raise PolicyViolation("ClobAuth timestamp is not current")
+        if int(nonce) < 0:
+            raise PolicyViolation("invalid ClobAuth nonce")
+
+    def _check_chain(self, chain_id: int) -> None:
+        if int(chain_id) != self.policy.chain_id:
+            raise PolicyViolation(f"chain {chain_id} not allowed (expected {self.policy.chain_id})")
+
+    def _roll_day(self) -> None:
+        day = datetime.fromtimestamp(self._clock(), tz=timezone.utc).strftime("%Y-%m-%d")
+        if day != self._day:
+            self._day = day
+            self._daily_notional = Decimal("0")
+
+
+# ============================================================================
+# ABI helpers (static head words only; enough for the selectors above)
+# ============================================================================
+
+def _int(value: Any) -> int:
+    if value is None:
+        return 0
+    if isinstance(value, str):
+        return int(value, 16) if value.startswith("0x") else int(value)
+    return int(value)
+
+
+def _bytes(value: Any) -> bytes:
+    if isinstance(value, (bytes, bytearray)):
+        return bytes(value)
+    value = value or ""
+    return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+
+
+def _word(data: bytes, index: int) -> bytes:
+    start = 4 + 32 * index
+    word = data[start:start + 32]
+    if len(word) != 32:
+        raise PolicyViolation("malformed calldata")
+    return word
+
+
+def _address_arg(data: bytes, index: int) -> str:
+    return "0x" + _word(data, index)[12:].hex()
+
+
+def _uint_arg(data: bytes, index: int) -> int:
+    return int.from_bytes(_word(data, index), "big")
+
+
+def _bytes_arg(data: bytes, index: int) -> bytes:
+    offset = 4 + _uint_arg(data, index)
+    length = int.from_bytes(data[offset:offset + 32], "big")
+    value = data[offset + 32:offset + 32 + length]
+    if len(value) != length:
+        raise PolicyViolation("malformed calldata")
+    return value
+
+
+# ============================================================================
+# Daemon
+# ============================================================================
+
+class SignerDaemon:
+    """
+    Unix-socket signing server.
+
+    Features:
+    - Socket created with mode 0600; optional peer UID allow-list (SO_PEERCRED)
+    - Every request checked by PolicyEngine before signing
+    - Refusals logged with the reason and returned to the client
+    """
+
+    def __init__(self, signer: Signer, policy: SigningPolicy, socket_path: str,
+                 allowed_uids: Optional[List[int]] = None, clock: Optional[Callable[[], float]] = None):
+        self.signer = signer
+        self.socket_path = socket_path
+        self.allowed_uids = set(allowed_uids) if allowed_uids else None
+        self.policy_engine = PolicyEngine(policy, signer.address, clock)
+        self._server: Optional[asyncio.AbstractServer] = None
+
+    async def start(self) -> None:
+        if os.path.exists(self.socket_path):
+            os.unlink(self.socket_path)
+        old_umask = os.umask(0o177)
+        try:
+            self._server = await asyncio.start_unix_server(self._handle_connection, path=self.socket_path)
+        finally:
+            os.umask(old_umask)
+        logger.info(f"🔏 Signing daemon listening on {self.socket_path} for {self.signer.address}")
+
+    async def serve_forever(self) -> None:
+        await self.start()
+        async with self._server:
+            await self._server.serve_forever()
+
+    async def stop(self) -> None:
+        if self._server:
+            self._server.close()
+            await self._server.wait_closed()
+            self._server = None
+        if os.path.exists(self.socket_path):
+            os.unlink(self.socket_path)
+
+    async def _handle_connection(self, reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        try:
+            if not self._peer_allowed(writer):
+                return
+            while True:
+                line = await reader.readline()
+                if not line:
+                    break
+                writer.write(json.dumps(self.handle_request(line)).encode() + b"\n")
+                await writer.drain()
+        finally:
+            writer.close()
+
+    def _peer_allowed(self, writer: asyncio.StreamWriter) -> bool:
+        if self.allowed_uids is None:
+            return True
+        sock = writer.get_extra_info("socket")
+        try:
+            _, uid, _ = struct.unpack("3i", sock.getsockopt(socket.SOL_SOCKET, socket.SO_PEERCRED,
+                                                            struct.calcsize("3i")))
+        except (AttributeError, OSError):
+            logger.error("❌ Cannot read peer credentials; refusing connection")
+            return False
+        if uid not in self.allowed_uids:
+            logger.warning(f"⚠️ Refused connection from uid {uid}")
+            return False
+        return True
+
+    def handle_request(self, line: bytes) -> Dict[str, Any]:
+        """Process one request line; returns the reply object."""
+        request_id = method = None
+        try:
+            request = json.loads(line)
+            request_id = request.get("id")
+            method = request.get("method")
+            params = request.get("params") or {}
+            return {"id": request_id, "result": self._dispatch(method, params)}
+        except PolicyViolation as e:
+            logger.warning(f"🚫 Refused {method}: {e}")
+            return {"id": request_id, "error": f"policy: {e}"}
+        except Exception as e:
+            logger.error(f"❌ Signing request failed: {e}")
+            return {"id": request_id, "error": str(e) or type(e).__name__}
+
+    def _dispatch(self, method: str, params: Dict[str, Any]) -> Any:
+        engine = self.policy_engine
+        if method == "address":
+            return self.signer.address
+
+        if method == "sign_transaction":
+            tx = params["tx"]
+            engine.check_transaction(tx)
+            signed = self.signer.sign_transaction(tx)
+            logger.info(f"🔏 Signed transaction to {tx.get('to')} (nonce {tx.get('nonce')})")
+            return {"raw_transaction": "0x" + signed.raw_transaction.hex(), "hash": "0x" + signed.hash.hex()}
+
+        if method == "sign_order":
+            order = params["order"]
+            notional = engine.check_order(order, params["exchange"], params["chain_id"])
+            signature = self.signer.sign_order(order, params["exchange"], params["chain_id"])
+            engine.record_order(notional)
+            logger.info(f"🔏 Signed {order['side']} order ${notional} (daily ${engine.daily_notional})")
+            return signature
+
+        if method == "sign_clob_auth":
+            engine.check_clob_auth(params["timestamp"], params["nonce"], params["chain_id"])
+            return self.signer.sign_clob_auth(params["timestamp"], params["nonce"], params["chain_id"])
+
+        raise ValueError(f"unknown method {method!r}")
+
+
+# ============================================================================
+# Entry point
+# ============================================================================
+
+def load_daemon(config_path: str) -> SignerDaemon:
+    """
+    Build a daemon from a YAML file (see config/signer_daemon.example.yaml).
+
+    The key is read from the secrets backend in the `secrets` section
+    (docs/SECRETS.md), never from the bot's configuration.
+    """
+    import yaml
+    from config.config import Config
+
+    with open(config_path, "r") as f:
+        data = yaml.safe_load(f) or {}
+
+    secret = Config.load_secrets(data.get("secrets") or {})
+    if not secret.get("private_key"):
+        raise ValueError("Secrets backend returned no private_key")
+    return SignerDaemon(
+        signer=LocalSigner(secret["private_key"]),
+        policy=SigningPolicy.from_dict(data.get("policy") or {}),
+        socket_path=data.get("socket_path", "/run/polymarket-signer/signer.sock"),
+        allowed_uids=data.get("allowed_uids"),
+    )
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description="Polymarket bot signing daemon")
+    parser.add_argument("--config", default="config/signer_daemon.yaml", help="Daemon YAML configuration")
+    args = parser.parse_args()
+
+    logging.basicConfig(level=logging.INFO, format="%(asctime)s %(levelname)s %(name)s: %(message)s")
+    daemon = load_daemon(args.config)
+    try:
+        asyncio.run(daemon.serve_forever())
+    except KeyboardInterrupt:
+        logger.info("Signing daemon stopped")
+
+
+if __name__ == "__main__":
+    main()
@@ -47,6 +47,9 @@ class DashboardState:
     total_balance: Decimal = Decimal('0')
     wallet_address: str = "0x0000...0000"
     
+    # Per-wallet books (WalletRegistry.snapshot()); balances above are their sum
+    wallets: List[Dict[str, Any]] = field(default_factory=list)
+    
     # Portfolio performance
     total_trades: int = 0
     successful_trades: int = 0
@@ -232,6 +235,23 @@ class StatusDashboard:
         print(f"  Total Assets:  {self.BOLD}{self.GREEN}{self._format_currency(self.state.total_balance)}{self.RESET} USDC")
         print()
         
+        # Wallets (sub-accounts)
+        if len(self.state.wallets) > 1:
+            print(f"{self.BOLD}[WALLETS]{self.RESET}")
+            for wallet in self.state.wallets:
+                pnl = wallet.get('daily_pnl', Decimal('0'))
+                pnl_color = self.GREEN if pnl >= 0 else self.RED
+                halted = f" {self.RED}HALTED{self.RESET}" if wallet.get('halted') else ""
+                print(f"  {wallet['name']:<14} {self._format_currency(wallet.get('total_balance', Decimal('0')))} USDC | "
+                      f"Capital: {self._format_currency(wallet.get('capital', Decimal('0')))} | "
+                      f"Today: {pnl_color}{self._format_currency(pnl)}{self.RESET} "
+                      f"({wallet.get('trades_today', 0)} trades) | "
+                      f"Open: {wallet.get('open_positions', 0)}{halted}")
+            aggregate_pnl = sum((w.get('daily_pnl', Decimal('0')) for w in self.state.wallets), Decimal('0'))
+            print(f"  {self.BOLD}{'ALL':<14} {self._format_currency(self.state.total_balance)} USDC | "
+                  f"Today: {self._format_currency(aggregate_pnl)}{self.RESET}")
+            print()
+        
         # Portfolio Performance
         print(f"{self.BOLD}[PORTFOLIO PERFORMANCE]{self.RESET}")
         win_rate_color = self.GREEN if self.state.win_rate >= Decimal('99.5') else self.YELLOW
@@ -378,6 +398,41 @@ class StatusDashboard:
             if hasattr(self.state, key):
                 setattr(self.state, key, value)
     
+    def update_wallets(self, wallets: List[Dict[str, Any]]):
+        """
+        Update per-wallet books and set the balances to their aggregate.
+        
+        Args:
+            wallets: Wallet snapshots (WalletRegistry.snapshot())
+        """
+        self.state.wallets = list(wallets)
+        self.state.eoa_balance = sum((w.get('eoa_balance', Decimal('0')) for w in wallets), Decimal('0'))
+        self.state.proxy_balance = sum((w.get('proxy_balance', Decimal('0')) for w in wallets), Decimal('0'))
+        self.state.total_balance = self.state.eoa_balance + self.state.proxy_balance
+    
+    def update_health_status(self, health: HealthStatus):
+        """
+        Update system, performance and network fields from a heartbeat.
+        
+        Args:
+            health: HealthStatus from the orchestrator's heartbeat check
+        """
+        self.state.uptime_seconds = int((datetime.now() - self.start_time).total_seconds())
+        self.state.last_heartbeat = health.timestamp
+        self.state.is_healthy = health.is_healthy
+        self.state.circuit_breaker_open = health.circuit_breaker_open
+        self.state.total_trades = health.total_trades
+        self.state.win_rate = Decimal(str(health.win_rate))
+        self.state.total_profit = health.total_profit
+        self.state.avg_profit_per_trade = health.avg_profit_per_trade
+        self.state.total_gas_cost = health.total_gas_cost
+        self.state.net_profit = health.net_profit
+        self.state.gas_price_gwei = health.gas_price_gwei
+        self.state.pending_tx_count = health.pending_tx_count
+        self.state.rpc_latency_ms = health.rpc_latency_ms
+        self.state.block_number = health.block_number
+        self.state.ai_safety_active = health.ai_safety_active
+    
     def add_trade(self, trade: Dict[str, Any]):
         """
         Add a trade to recent trades list.
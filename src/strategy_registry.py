@@ -0,0 +1,675 @@
+"""
+Strategy Registry for Polymarket Arbitrage Bot.
+
+Defines a common strategy interface (scan, decide, size, execute, exit hooks)
+and a registry that the MainOrchestrator uses to load strategies from config.
+
+Strategies are enabled, disabled and reordered per deployment through
+``Config.enabled_strategies`` instead of editing the orchestrator. Third-party
+strategies can be registered with ``StrategyRegistry.register`` or referenced
+directly in config as ``"package.module:factory"``.
+"""
+
+import importlib
+import logging
+import uuid
+from abc import ABC, abstractmethod
+from dataclasses import dataclass, field
+from datetime import datetime
+from decimal import Decimal, ROUND_DOWN
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from src.models import Market, Opportunity, TradeResult
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass
+class StrategyContext:
+    """Shared components handed to strategy factories."""
+    config: Any
+    clob_client: Any
+    order_manager: Any
+    ai_safety_guard: Any = None
+    llm_decision_engine: Any = None
+    initial_capital: float = 0.0
+    trade_size: float = 5.0
+    price_feed: Any = None  # Optional shared BinancePriceFeed
+    fair_value_pricer: Any = None  # Optional shared FairValuePricer (on price_feed)
+    correlation_analyzer: Any = None  # Optional shared CorrelationAnalyzer (on price_feed)
+    ledger: Any = None  # Optional shared PositionLedger
+    redemption_service: Any = None  # Optional RedemptionService for resolved positions
+    metrics: Any = None  # Optional MonitoringSystem exporting strategy metrics
+    wallet: str = "primary"  # Wallet (sub-account) whose book the strategies trade
+    extras: Dict[str, Any] = field(default_factory=dict)
+
+
+class TradingStrategy(ABC):
+    """
+    Common interface for all pluggable strategies.
+
+    A cycle runs the hooks in order:
+    1. check_exits() - manage open positions
+    2. scan(markets) - find candidates
+    3. decide(candidate) - accept or reject each candidate
+    4. size(candidate, bankroll) - compute the USDC amount to commit
+    5. execute(candidate, size) - place orders, return a TradeResult
+
+    Strategies that own their full loop (e.g. FifteenMinuteCryptoStrategy)
+    may override run_cycle() directly. Such strategies record their own fills
+    (position ledger, own risk manager) and may return no TradeResults.
+    """
+
+    name: str = "strategy"
+
+    async def start(self) -> None:
+        """Start feeds or background tasks required by the strategy."""
+        return None
+
+    async def stop(self) -> None:
+        """Stop feeds or background tasks started by the strategy."""
+        return None
+
+    @abstractmethod
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        """Return candidate opportunities found in the given markets."""
+
+    async def decide(self, candidate: Any) -> bool:
+        """Return True if the candidate should be traded."""
+        return True
+
+    def size(self, candidate: Any, bankroll: Decimal) -> Decimal:
+        """Return the USDC amount to commit to the candidate."""
+        return Decimal(str(getattr(candidate, "position_size", 0) or 0))
+
+    @abstractmethod
+    async def execute(self, candidate: Any, size: Decimal) -> Optional[TradeResult]:
+        """Execute the candidate and return the trade result."""
+
+    async def check_exits(self) -> None:
+        """Check open positions for exit conditions."""
+        return None
+
+    def open_positions(self) -> List[Dict[str, Any]]:
+        """Positions and resting orders the strategy holds across cycles."""
+        return []
+
+    async def flatten(self) -> int:
+        """Close held positions and cancel resting orders; returns the number closed."""
+        return 0
+
+    def apply_config(self, config: Any) -> None:
+        """Take over hot-reloaded settings (docs/CONFIG_RELOAD.md); must not await."""
+        return None
+
+    def feed_staleness(self) -> Dict[Tuple[str, str], float]:
+        """Seconds since the last update of each market data feed the strategy reads, by (feed, stream)."""
+        return {}
+
+    def risk_statistics(self) -> Optional[Dict[str, Any]]:
+        """Statistics of the strategy's own risk manager, if it has one."""
+        return None
+
+    async def run_cycle(self, markets: List[Market], bankroll: Decimal) -> List[TradeResult]:
+        """
+        Run one full strategy cycle.
+
+        Args:
+            markets: Parsed markets for this scan
+            bankroll: Current capital available for sizing
+
+        Returns:
+            List of trade results produced this cycle
+        """
+        await self.check_exits()
+
+        results: List[TradeResult] = []
+        for candidate in await self.scan(markets):
+            if not await self.decide(candidate):
+                continue
+
+            amount = self.size(candidate, bankroll)
+            if amount <= 0:
+                logger.debug(f"[{self.name}] Skipping candidate with non-positive size")
+                continue
+
+            result = await self.execute(candidate, amount)
+            if result is not None:
+                results.append(result)
+
+        return results
+
+
+StrategyFactory = Callable[[StrategyContext], TradingStrategy]
+
+
+class StrategyRegistry:
+    """
+    Registry mapping strategy names to factories.
+
+    Example:
+        registry = build_default_registry()
+        registry.register("my_strategy", lambda ctx: MyStrategy(ctx.clob_client))
+        strategies = registry.build(["fifteen_min_crypto", "my_strategy"], context)
+    """
+
+    def __init__(self):
+        self._factories: Dict[str, StrategyFactory] = {}
+
+    def register(self, name: str, factory: StrategyFactory, replace: bool = False) -> None:
+        """
+        Register a strategy factory.
+
+        Args:
+            name: Strategy name used in config
+            factory: Callable taking a StrategyContext and returning a TradingStrategy
+            replace: Allow overriding an existing registration
+
+        Raises:
+            ValueError: If the name is already registered and replace is False
+        """
+        if name in self._factories and not replace:
+            raise ValueError(f"Strategy already registered: {name}")
+        self._factories[name] = factory
+
+    def unregister(self, name: str) -> None:
+        """Remove a strategy registration if present."""
+        self._factories.pop(name, None)
+
+    def names(self) -> List[str]:
+        """Return registered strategy names in registration order."""
+        return list(self._factories.keys())
+
+    def is_registered(self, name: str) -> bool:
+        """Check whether a strategy name is registered."""
+        return name in self._factories
+
+    def _resolve(self, name: str) -> StrategyFactory:
+        """Resolve a name to a factory, importing "module:attr" references."""
+        if name in self._factories:
+            return self._factories[name]
+
+        if ":" in name:
+            module_name, attr = name.split(":", 1)
+            try:
+                module = importlib.import_module(module_name)
+                return getattr(module, attr)
+            except (ImportError, AttributeError) as e:
+                raise ValueError(f"Cannot import strategy factory '{name}': {e}")
+
+        raise ValueError(
+            f"Unknown strategy: {name} (registered: {', '.join(self.names()) or 'none'})"
+        )
+
+    def create(self, name: str, context: StrategyContext) -> TradingStrategy:
+        """Create a single strategy instance."""
+        strategy = self._resolve(name)(context)
+        if not isinstance(strategy, TradingStrategy):
+            raise TypeError(f"Factory for '{name}' did not return a TradingStrategy")
+        return strategy
+
+    def build(self, names: List[str], context: StrategyContext) -> List[TradingStrategy]:
+        """
+        Create strategies in the configured order.
+
+        Args:
+            names: Ordered strategy names from config
+            context: Shared components
+
+        Returns:
+            List of strategy instances, in priority order
+
+        Raises:
+            ValueError: On unknown or duplicate strategy names
+        """
+        seen = set()
+        strategies = []
+        for name in names:
+            if name in seen:
+                raise ValueError(f"Strategy listed twice: {name}")
+            seen.add(name)
+            strategies.append(self.create(name, context))
+            logger.info(f"✅ Strategy loaded: {name}")
+        return strategies
+
+
+# ============================================================
+# BUILT-IN ADAPTERS
+# ============================================================
+
+class FifteenMinuteCryptoAdapter(TradingStrategy):
+    """
+    Adapter for FifteenMinuteCryptoStrategy.
+
+    The 15-minute strategy fetches its own markets and runs its own
+    entry/exit chain (ordered by ``Config.fifteen_min_entry_order``),
+    so this adapter delegates the whole cycle. The strategy records its own
+    trades (position ledger, its risk manager, the trade outcome store), so
+    the cycle returns no TradeResults for the orchestrator to book again.
+    """
+
+    name = "fifteen_min_crypto"
+
+    def __init__(self, strategy):
+        self.strategy = strategy
+
+    async def start(self) -> None:
+        await self.strategy.start()
+
+    async def stop(self) -> None:
+        await self.strategy.stop()
+
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        return []
+
+    async def execute(self, candidate: Any, size: Decimal) -> Optional[TradeResult]:
+        return None
+
+    async def run_cycle(self, markets: List[Market], bankroll: Decimal) -> List[TradeResult]:
+        await self.strategy.run_cycle()
+        return []  # Trades are recorded by the strategy itself
+
+    def open_positions(self) -> List[Dict[str, Any]]:
+        return self.strategy.get_open_positions()
+
+    async def flatten(self) -> int:
+        return await self.strategy.close_all_positions()
+
+    def feed_staleness(self) -> Dict[Tuple[str, str], float]:
+        staleness = {("binance", asset): age for asset, age in self.strategy.binance_feed.staleness_seconds().items()}
+        polymarket_age = self.strategy.polymarket_ws_feed.staleness_seconds()
+        if polymarket_age is not None:
+            staleness[("polymarket_ws", "all")] = polymarket_age
+        return staleness
+
+    def risk_statistics(self) -> Optional[Dict[str, Any]]:
+        return self.strategy.risk_manager.get_statistics()
+
+    def apply_config(self, config: Any) -> None:
+        self.strategy.maker_entries = config.fifteen_min_maker_entries and self.strategy.order_manager is not None
+        self.strategy.maker_ttl_seconds = config.fifteen_min_maker_ttl_seconds
+        self.strategy.flash_crash_drop_threshold = config.flash_crash_drop_threshold
+        self.strategy.flash_crash_lookback_seconds = config.flash_crash_lookback_seconds
+        self.strategy.rl_engine.configure_exploration(config.rl_exploration_rate, config.rl_max_daily_explorations)
+
+
+class NegRiskArbitrageAdapter(TradingStrategy):
+    """Adapter for NegRiskArbitrageEngine (fetches its own multi-outcome markets)."""
+
+    name = "negrisk_arbitrage"
+
+    def __init__(self, engine):
+        self.engine = engine
+
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        return await self.engine.scan_opportunities()
+
+    async def decide(self, candidate: Any) -> bool:
+        return not self.engine.was_executed(candidate.opportunity_id)
+
+    def size(self, candidate: Any, bankroll: Decimal) -> Decimal:
+        return min(candidate.max_position_size, bankroll)
+
+    async def execute(self, candidate: Any, size: Decimal) -> Optional[TradeResult]:
+        return await self.engine.execute(candidate, position_size=size)
+
+    def apply_config(self, config: Any) -> None:
+        self.engine.min_profit_threshold = config.min_profit_threshold
+        self.engine.max_position_size = config.max_position_size
+
+
+class _BinanceAssetFeed:
+    """Exposes one asset of a BinancePriceFeed through get_latest_price()."""
+
+    def __init__(self, feed, asset: str):
+        self.feed = feed
+        self.asset = asset
+
+    def get_latest_price(self) -> Optional[Decimal]:
+        price = self.feed.prices.get(self.asset)
+        return price if price else None
+
+
+class ResolutionFarmingAdapter(TradingStrategy):
+    """
+    Adapter for ResolutionFarmingEngine, executing single-sided FOK buys.
+
+    The shares are held to resolution, so a fill realizes no P&L. Fills are
+    recorded in the ledger, and redemption realizes the P&L against that cost.
+    """
+
+    name = "resolution_farming"
+
+    def __init__(self, engine, order_manager, price_feed=None, owns_feed: bool = False, ledger=None):
+        self.engine = engine
+        self.order_manager = order_manager
+        self.price_feed = price_feed
+        self.owns_feed = owns_feed
+        self.ledger = ledger
+        self._markets: Dict[str, Market] = {}
+
+    async def start(self) -> None:
+        if self.owns_feed and self.price_feed:
+            await self.price_feed.start()
+
+    async def stop(self) -> None:
+        if self.owns_feed and self.price_feed:
+            await self.price_feed.stop()
+
+    def feed_staleness(self) -> Dict[Tuple[str, str], float]:
+        if self.price_feed is None:
+            return {}
+        return {("binance", asset): age for asset, age in self.price_feed.staleness_seconds().items()}
+
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        self._markets = {m.market_id: m for m in markets}
+        return await self.engine.scan_closing_markets(markets)
+
+    def size(self, candidate: Opportunity, bankroll: Decimal) -> Decimal:
+        return self.engine.calculate_position_size(bankroll)
+
+    async def execute(self, candidate: Opportunity, size: Decimal) -> Optional[TradeResult]:
+        market = self._markets.get(candidate.market_id)
+        if market is None:
+            logger.warning(f"[{self.name}] Market {candidate.market_id} no longer available")
+            return None
+
+        side = candidate.side
+        if side not in ("YES", "NO"):
+            logger.warning(f"[{self.name}] Opportunity {candidate.opportunity_id} has no side")
+            return None
+        token_id = market.yes_token_id if side == "YES" else market.no_token_id
+        price = candidate.total_cost
+        if price <= 0:
+            logger.warning(f"[{self.name}] Opportunity {candidate.opportunity_id} has no price ({price})")
+            return None
+        shares = (size / price).quantize(Decimal("0.01"), rounding=ROUND_DOWN)
+        candidate.position_size = size
+
+        trade_id = f"trade_{uuid.uuid4().hex[:12]}"
+        timestamp = datetime.now()
+
+        try:
+            order = self.order_manager.create_fok_order(
+                market_id=token_id,
+                side=side,
+                price=price,
+                size=shares
+            )
+            filled = await self.order_manager.submit_order(order)
+        except Exception as e:
+            logger.error(f"[{self.name}] Order failed: {e}")
+            filled = False
+            order = None
+
+        fill_price = order.fill_price if order and filled else None
+        actual_cost = (fill_price or price) * shares if filled else Decimal("0")
+        if filled:
+            self._record_ledger_fill(candidate, token_id, side, shares, fill_price or price, order.order_id)
+
+        return TradeResult(
+            trade_id=trade_id,
+            opportunity=candidate,
+            timestamp=timestamp,
+            status="success" if filled else "failed",
+            yes_order_id=order.order_id if order and side == "YES" else None,
+            no_order_id=order.order_id if order and side == "NO" else None,
+            yes_filled=filled and side == "YES",
+            no_filled=filled and side == "NO",
+            yes_fill_price=fill_price if side == "YES" else None,
+            no_fill_price=fill_price if side == "NO" else None,
+            actual_cost=actual_cost,
+            actual_profit=Decimal("0"),  # Realized on redemption
+            gas_cost=Decimal("0"),
+            net_profit=Decimal("0"),
+            yes_tx_hash=order.tx_hash if order and side == "YES" else None,
+            no_tx_hash=order.tx_hash if order and side == "NO" else None,
+            error_message=None if filled else "FOK order failed to fill"
+        )
+
+    def _record_ledger_fill(self, candidate: Opportunity, token_id: str, side: str, shares: Decimal,
+                            price: Decimal, order_id: Optional[str]) -> None:
+        """Record a live fill in the position ledger (if one is attached)."""
+        if self.ledger is None or getattr(self.order_manager, "dry_run", False):
+            return
+        try:
+            self.ledger.record_fill(
+                token_id=token_id, side="BUY", size=shares, price=price, market_id=candidate.market_id,
+                outcome=side, strategy=self.name, order_id=order_id
+            )
+        except Exception as e:
+            logger.error(f"[{self.name}] Failed to record fill in ledger: {e}")
+
+
+class MarketMakingAdapter(TradingStrategy):
+    """
+    Adapter for MarketMakingStrategy.
+
+    The market maker fetches its own up/down markets and manages resting
+    quotes across cycles, so this adapter delegates the whole cycle. Fills
+    are recorded by the strategy (position ledger, its risk manager) as they
+    happen, so the cycle returns no TradeResults.
+    """
+
+    name = "market_making"
+
+    def __init__(self, strategy):
+        self.strategy = strategy
+
+    async def start(self) -> None:
+        await self.strategy.start()
+
+    async def stop(self) -> None:
+        await self.strategy.stop()
+
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        return []
+
+    async def execute(self, candidate: Any, size: Decimal) -> Optional[TradeResult]:
+        return None
+
+    async def run_cycle(self, markets: List[Market], bankroll: Decimal) -> List[TradeResult]:
+        await self.strategy.run_cycle()
+        return []  # Trades are recorded by the strategy itself
+
+    def open_positions(self) -> List[Dict[str, Any]]:
+        return self.strategy.get_open_positions()
+
+    async def flatten(self) -> int:
+        return await self.strategy.close_inventory(reason="flatten")
+
+    def feed_staleness(self) -> Dict[Tuple[str, str], float]:
+        return {("binance", asset): age for asset, age in self.strategy.price_feed.staleness_seconds().items()}
+
+    def apply_config(self, config: Any) -> None:
+        strategy = self.strategy
+        strategy.half_spread = Decimal(str(config.market_making_half_spread))
+        strategy.max_skew = strategy.half_spread  # Built without an explicit max_skew
+        strategy.quote_size = Decimal(str(config.market_making_quote_size))
+        strategy.max_inventory = Decimal(str(config.market_making_max_inventory))
+        strategy.pull_minutes = config.market_making_pull_minutes
+
+
+class CrossPlatformArbitrageAdapter(TradingStrategy):
+    """
+    Adapter for CrossPlatformArbitrageEngine (Polymarket vs Kalshi).
+
+    Scans the orchestrator's Polymarket markets against Kalshi; the engine
+    sizes each trade with its Kelly sizer, so size() hands over the bankroll.
+    """
+
+    name = "cross_platform_arbitrage"
+
+    def __init__(self, engine):
+        self.engine = engine
+
+    async def stop(self) -> None:
+        await self.engine.kalshi_client.close()
+
+    async def scan(self, markets: List[Market]) -> List[Any]:
+        return await self.engine.scan_opportunities(markets)
+
+    def size(self, candidate: Any, bankroll: Decimal) -> Decimal:
+        return bankroll
+
+    async def execute(self, candidate: Any, size: Decimal) -> Optional[TradeResult]:
+        legs = self.engine.get_legs(candidate.opportunity_id)
+        if legs is None:
+            logger.warning(f"[{self.name}] Markets for {candidate.opportunity_id} no longer available")
+            return None
+        pm_market, kalshi_market, _, _ = legs
+        return await self.engine.execute(candidate, pm_market, kalshi_market, bankroll=size)
+
+    def apply_config(self, config: Any) -> None:
+        self.engine.min_profit_threshold = config.min_profit_threshold
+
+
+# ============================================================
+# BUILT-IN FACTORIES
+# ============================================================
+
+def _create_fifteen_min_crypto(context: StrategyContext) -> TradingStrategy:
+    from src.continuous_rl_engine import ContinuousRLEngine
+    from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+    from src.trade_outcome_store import TradeOutcomeStore
+    from src.wallet_accounts import PRIMARY_WALLET, wallet_file
+
+    # Sub-accounts keep their own positions, experience and trade outcomes
+    config = context.config
+    wallet = context.wallet
+    learning_store_path = wallet_file(getattr(config, "learning_store_path", "data/learning_store.db"), wallet)
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=context.clob_client,
+        trade_size=context.trade_size,  # DYNAMIC: Will be adjusted by risk manager
+        take_profit_pct=0.02,  # 2% profit target
+        stop_loss_pct=0.02,  # 2% stop loss
+        max_positions=5,
+        sum_to_one_threshold=1.02,
+        dry_run=config.dry_run,
+        llm_decision_engine=context.llm_decision_engine,
+        enable_adaptive_learning=False,  # Breaks dynamic take profit
+        initial_capital=context.initial_capital,
+        entry_order=getattr(config, "fifteen_min_entry_order", None),
+        order_manager=context.order_manager,
+        maker_entries=getattr(config, "fifteen_min_maker_entries", False),
+        maker_ttl_seconds=getattr(config, "fifteen_min_maker_ttl_seconds", 120),
+        flash_crash_drop_threshold=getattr(config, "flash_crash_drop_threshold", 0.15),
+        flash_crash_lookback_seconds=getattr(config, "flash_crash_lookback_seconds", 3),
+        binance_feed=context.price_feed,
+        fair_value_pricer=context.fair_value_pricer,
+        correlation_analyzer=context.correlation_analyzer,
+        ledger=context.ledger,
+        redemption_service=context.redemption_service,
+        metrics=context.metrics,
+        rl_engine=ContinuousRLEngine(
+            policy_dir=getattr(config, "rl_policy_dir", "data/rl_policies"),
+            policy_version=getattr(config, "rl_policy_version", None),
+            experience_path=wallet_file(getattr(config, "rl_experience_path", "data/rl_experience.jsonl"), wallet),
+            exploration_rate=getattr(config, "rl_exploration_rate", 0.0),
+            exploration_margin=getattr(config, "rl_exploration_margin", 0.005),
+            max_daily_explorations=getattr(config, "rl_max_daily_explorations", 10),
+            source="dry_run" if config.dry_run else "live"
+        ),
+        outcome_store=TradeOutcomeStore(
+            learning_store_path,
+            source="dry_run" if config.dry_run else "live",
+            # Legacy engine JSON files sit next to the store; they hold the primary wallet's history
+            legacy_dir=str(Path(learning_store_path).parent) if wallet == PRIMARY_WALLET else None
+        ),
+        positions_file=wallet_file("data/active_positions.json", wallet)
+    )
+    return FifteenMinuteCryptoAdapter(strategy)
+
+
+def _create_negrisk_arbitrage(context: StrategyContext) -> TradingStrategy:
+    from src.negrisk_arbitrage_engine import NegRiskArbitrageEngine
+
+    engine = NegRiskArbitrageEngine(
+        clob_client=context.clob_client,
+        order_manager=context.order_manager,
+        ai_safety_guard=context.ai_safety_guard,
+        min_profit_threshold=context.config.min_profit_threshold,
+        max_position_size=context.config.max_position_size
+    )
+    return NegRiskArbitrageAdapter(engine)
+
+
+def _create_resolution_farming(context: StrategyContext) -> TradingStrategy:
+    from src.fair_value_pricer import FairValuePricer
+    from src.resolution_farming_engine import ResolutionFarmingEngine
+
+    feed = context.price_feed
+    owns_feed = feed is None
+    if owns_feed:
+        from src.fifteen_min_crypto_strategy import BinancePriceFeed
+        feed = BinancePriceFeed()
+
+    engine = ResolutionFarmingEngine(
+        cex_feeds={asset: _BinanceAssetFeed(feed, asset) for asset in feed.prices},
+        ai_safety_guard=context.ai_safety_guard,
+        fair_value_pricer=context.fair_value_pricer or FairValuePricer(price_feed=feed)
+    )
+    return ResolutionFarmingAdapter(engine, context.order_manager, feed, owns_feed, ledger=context.ledger)
+
+
+def _create_market_making(context: StrategyContext) -> TradingStrategy:
+    from src.market_making_strategy import MarketMakingStrategy
+
+    config = context.config
+    strategy = MarketMakingStrategy(
+        clob_client=context.clob_client,
+        order_manager=context.order_manager,
+        price_feed=context.price_feed,
+        fair_value_pricer=context.fair_value_pricer,
+        initial_capital=context.initial_capital,
+        half_spread=getattr(config, "market_making_half_spread", 0.02),
+        quote_size=getattr(config, "market_making_quote_size", 10.0),
+        max_inventory=getattr(config, "market_making_max_inventory", 50.0),
+        pull_minutes=getattr(config, "market_making_pull_minutes", 2.0),
+        dry_run=config.dry_run,
+        ledger=context.ledger,
+        redemption_service=context.redemption_service
+    )
+    return MarketMakingAdapter(strategy)
+
+
+def _create_cross_platform_arbitrage(context: StrategyContext) -> TradingStrategy:
+    from src.cross_platform_arbitrage_engine import CrossPlatformArbitrageEngine
+    from src.kalshi_client import KalshiClient, KalshiOrderManager
+    from src.kalshi_simulator import FixtureKalshiClient
+    from src.kelly_position_sizer import KellyPositionSizer
+
+    config = context.config
+    fixture_path = getattr(config, "kalshi_fixture_path", None)
+    if fixture_path:
+        kalshi_client = FixtureKalshiClient.from_fixture(fixture_path)
+    else:
+        kalshi_client = KalshiClient(
+            key_id=config.kalshi_api_key,
+            private_key_path=config.kalshi_private_key_path,
+            base_url=config.kalshi_api_url
+        )
+
+    engine = CrossPlatformArbitrageEngine(
+        polymarket_client=context.clob_client,
+        kalshi_client=kalshi_client,
+        polymarket_order_manager=context.order_manager,
+        kalshi_order_manager=KalshiOrderManager(kalshi_client, dry_run=config.dry_run),
+        ai_safety_guard=context.ai_safety_guard,
+        kelly_sizer=KellyPositionSizer(),
+        min_profit_threshold=config.min_profit_threshold,
+        kalshi_series=getattr(config, "kalshi_series_tickers", None)
+    )
+    return CrossPlatformArbitrageAdapter(engine)
+
+
+def build_default_registry() -> StrategyRegistry:
+    """Create a registry with all built-in strategies registered."""
+    registry = StrategyRegistry()
+    registry.register("fifteen_min_crypto", _create_fifteen_min_crypto)
+    registry.register("negrisk_arbitrage", _create_negrisk_arbitrage)
+    registry.register("resolution_farming", _create_resolution_farming)
+    registry.register("market_making", _create_market_making)
+    registry.register("cross_platform_arbitrage", _create_cross_platform_arbitrage)
+    return registry
@@ -0,0 +1,217 @@
+"""
+Structured LLM output for Polymarket Arbitrage Bot.
+
+LLM answers are requested as JSON matching a schema (JSON mode or
+grammar-constrained decoding where the backend supports it) and validated
+strictly before use. An invalid answer is sent back to the model with the
+validation errors, a bounded number of times; every invalid answer is
+appended to a JSONL file for review.
+
+The validator covers the JSON Schema subset the bot's schemas use: type,
+enum, properties, required, additionalProperties (false), minimum, maximum,
+minLength and maxLength. Numbers must be finite (no NaN or Infinity).
+
+Validates Requirements:
+- Schema-enforced LLM responses with strict validation
+- Bounded repair retries
+- Persisted raw responses of rejected answers
+"""
+
+import json
+import logging
+import math
+import os
+from datetime import datetime, timezone
+from typing import Any, Awaitable, Callable, Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+JSON_TYPES = {
+    "object": dict,
+    "array": list,
+    "string": str,
+    "boolean": bool,
+    "null": type(None),
+}
+
+
+class SchemaValidationError(ValueError):
+    """An LLM response that is not valid JSON or does not match its schema."""
+
+    def __init__(self, errors: List[str]):
+        super().__init__("; ".join(errors))
+        self.errors = errors
+
+
+def _is_type(value: Any, kind: str) -> bool:
+    if kind == "number":
+        # NaN and Infinity parse from JSON but compare false against minimum/maximum
+        return isinstance(value, (int, float)) and not isinstance(value, bool) and math.isfinite(value)
+    if kind == "integer":
+        return isinstance(value, int) and not isinstance(value, bool)
+    return isinstance(value, JSON_TYPES[kind])
+
+
+def schema_errors(value: Any, schema: Dict[str, Any], path: str = "$") -> List[str]:
+    """All violations of `schema` by `value` (empty if valid)."""
+    kinds = schema.get("type")
+    if kinds is not None:
+        kinds = [kinds] if isinstance(kinds, str) else kinds
+        if not any(_is_type(value, kind) for kind in kinds):
+            return [f"{path}: expected {' or '.join(kinds)}, got {json.dumps(value)}"]
+
+    errors = []
+    if "enum" in schema and value not in schema["enum"]:
+        errors.append(f"{path}: must be one of {schema['enum']}, got {json.dumps(value)}")
+
+    if _is_type(value, "number"):
+        if "minimum" in schema and value < schema["minimum"]:
+            errors.append(f"{path}: {value} is below the minimum {schema['minimum']}")
+        if "maximum" in schema and value > schema["maximum"]:
+            errors.append(f"{path}: {value} is above the maximum {schema['maximum']}")
+
+    if isinstance(value, str):
+        if len(value) < schema.get("minLength", 0):
+            errors.append(f"{path}: must be at least {schema['minLength']} characters")
+        if "maxLength" in schema and len(value) > schema["maxLength"]:
+            errors.append(f"{path}: must be at most {schema['maxLength']} characters")
+
+    if isinstance(value, dict):
+        properties = schema.get("properties", {})
+        for key in schema.get("required", []):
+            if key not in value:
+                errors.append(f"{path}.{key}: is required")
+        if schema.get("additionalProperties") is False:
+            for key in value:
+                if key not in properties:
+                    errors.append(f"{path}.{key}: is not allowed")
+        for key, subschema in properties.items():
+            if key in value:
+                errors.extend(schema_errors(value[key], subschema, f"{path}.{key}"))
+
+    return errors
+
+
+def parse_json_object(text: str) -> Dict[str, Any]:
+    """
+    The JSON object a response consists of.
+
+    A response wrapped in a single ```json fence is accepted for backends
+    without JSON mode; anything else around the object is not.
+
+    Raises:
+        SchemaValidationError: If the response is not exactly one JSON object
+    """
+    body = (text or "").strip()
+    if "```" in body:
+        parts = body.split("```")
+        if len(parts) != 3:
+            raise SchemaValidationError(["response must be a single JSON object"])
+        body = parts[1].strip()
+        if body.startswith("json"):
+            body = body[4:].strip()
+
+    try:
+        data = json.loads(body)
+    except json.JSONDecodeError as e:
+        raise SchemaValidationError([f"response is not valid JSON: {e}"])
+    if not isinstance(data, dict):
+        raise SchemaValidationError(["response must be a JSON object"])
+    return data
+
+
+def validate_response(
+    text: str,
+    schema: Dict[str, Any],
+    check: Optional[Callable[[Dict[str, Any]], List[str]]] = None
+) -> Dict[str, Any]:
+    """
+    Parse and validate a response against `schema` (and `check`, for rules
+    across fields that the schema cannot express).
+
+    Raises:
+        SchemaValidationError: With every violation found
+    """
+    data = parse_json_object(text)
+    errors = schema_errors(data, schema)
+    if not errors and check:
+        errors = check(data)
+    if errors:
+        raise SchemaValidationError(errors)
+    return data
+
+
+def repair_prompt(user_prompt: str, response: str, errors: List[str]) -> str:
+    """The original prompt plus the rejected answer and why it was rejected."""
+    problems = "\n".join(f"- {error}" for error in errors)
+    return (
+        f"{user_prompt}\n\n"
+        f"Your previous answer was rejected:\n{response.strip()[:2000]}\n\n"
+        f"Problems:\n{problems}\n\n"
+        "Answer again with one JSON object that matches the schema exactly. No other text."
+    )
+
+
+async def request_structured(
+    complete: Callable[[str], Awaitable[str]],
+    user_prompt: str,
+    schema: Dict[str, Any],
+    max_repair_attempts: int = 2,
+    check: Optional[Callable[[Dict[str, Any]], List[str]]] = None,
+    on_invalid: Optional[Callable[[str, List[str], int], None]] = None
+) -> Dict[str, Any]:
+    """
+    Ask for a schema-valid answer, sending invalid answers back for repair.
+
+    Args:
+        complete: Sends a user prompt to the model and returns the raw answer
+        user_prompt: The original prompt
+        schema: JSON schema the answer must match
+        max_repair_attempts: Repair requests after the first answer
+        check: Extra validation across fields; returns error messages
+        on_invalid: Called with (raw answer, errors, attempt) for every rejected answer
+
+    Raises:
+        SchemaValidationError: If the last allowed answer is still invalid
+    """
+    prompt = user_prompt
+    for attempt in range(max_repair_attempts + 1):
+        response = await complete(prompt)
+        try:
+            return validate_response(response, schema, check)
+        except SchemaValidationError as e:
+            logger.warning(f"Invalid LLM response (attempt {attempt + 1}/{max_repair_attempts + 1}): {e}")
+            if on_invalid:
+                on_invalid(response, e.errors, attempt)
+            if attempt == max_repair_attempts:
+                raise
+            prompt = repair_prompt(user_prompt, response, e.errors)
+
+
+class RejectedResponseLog:
+    """Appends rejected LLM answers to a JSONL file for review (no file if path is None)."""
+
+    def __init__(self, path: Optional[str] = "data/llm_rejected_responses.jsonl"):
+        self.path = path
+        self.count = 0
+
+    def record(self, source: str, response: str, errors: List[str], **context: Any) -> None:
+        self.count += 1
+        if not self.path:
+            return
+        entry = {
+            "timestamp": datetime.now(timezone.utc).isoformat(),
+            "source": source,
+            "errors": errors,
+            "raw_response": response,
+            **{key: value if isinstance(value, (int, float, bool, type(None))) else str(value)
+               for key, value in context.items()},
+        }
+        try:
+            directory = os.path.dirname(self.path)
+            if directory:
+                os.makedirs(directory, exist_ok=True)
+            with open(self.path, "a") as f:
+                f.write(json.dumps(entry) + "\n")
+        except OSError as e:
+            logger.error(f"Failed to persist rejected LLM response: {e}")
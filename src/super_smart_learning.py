@@ -14,7 +14,7 @@ import json
 import logging
 from decimal import Decimal
 from datetime import datetime, timezone
-from typing import Dict, List, Optional
+from typing import Any, Dict, List, Optional
 from pathlib import Path
 import statistics
 
@@ -34,10 +34,16 @@ class SuperSmartLearning:
     6. Mistake Avoidance - Never repeats losing patterns
     7. Dynamic Parameter Tuning - Auto-adjusts ALL parameters
     8. Performance Prediction - Predicts trade success before entering
+    
+    With a TradeOutcomeStore, learned state is kept in the store (engine
+    "super_smart") instead of data_file; subscribe on_trade_outcome to it.
     """
     
-    def __init__(self, data_file: str = "data/super_smart_learning.json"):
+    STATE_NAME = "super_smart"
+    
+    def __init__(self, data_file: str = "data/super_smart_learning.json", store: Optional[Any] = None):
         self.data_file = Path(data_file)
+        self.store = store
         
         # Strategy performance tracking
         self.strategy_stats = {
@@ -159,6 +165,19 @@ class SuperSmartLearning:
         # Save data
         self._save_data()
     
+    def on_trade_outcome(self, outcome: Any) -> None:
+        """Learn from a trade recorded in the TradeOutcomeStore."""
+        self.record_trade(
+            asset=outcome.asset,
+            side=outcome.side,
+            entry_price=Decimal(str(outcome.entry_price)),
+            exit_price=Decimal(str(outcome.exit_price)),
+            profit_pct=Decimal(str(outcome.profit_pct)),
+            hold_time_minutes=outcome.hold_time_minutes,
+            exit_reason=outcome.exit_reason,
+            strategy_used=outcome.strategy
+        )
+    
     def _learn_from_trade(
         self,
         asset: str,
@@ -383,10 +402,8 @@ class SuperSmartLearning:
         return "\n".join(report)
     
     def _save_data(self) -> None:
-        """Save learning data to disk."""
+        """Save learning data to the store, or to disk without one."""
         try:
-            self.data_file.parent.mkdir(parents=True, exist_ok=True)
-            
             data = {
                 "total_trades": self.total_trades,
                 "total_wins": self.total_wins,
@@ -422,6 +439,11 @@ class SuperSmartLearning:
                 "losing_patterns": self.losing_patterns[-50:]
             }
             
+            if self.store is not None:
+                self.store.save_state(self.STATE_NAME, data)
+                return
+            
+            self.data_file.parent.mkdir(parents=True, exist_ok=True)
             with open(self.data_file, 'w') as f:
                 json.dump(data, f, indent=2)
             
@@ -429,15 +451,29 @@ class SuperSmartLearning:
             logger.error(f"Failed to save learning data: {e}")
     
     def _load_data(self) -> None:
-        """Load learning data from disk."""
+        """Load learning data from the store, or from disk without one."""
         try:
-            if not self.data_file.exists():
+            if self.store is not None:
+                data = self.store.load_state(self.STATE_NAME)
+            elif self.data_file.exists():
+                with open(self.data_file, 'r') as f:
+                    data = json.load(f)
+            else:
+                data = None
+            
+            # Hourly performance is not part of the saved state; the store has it for every trade
+            if self.store is not None:
+                for hour, stats in self.store.get_stats_by("hour").items():
+                    self.hourly_performance[hour] = {
+                        "trades": stats.trades,
+                        "wins": stats.wins,
+                        "profit": Decimal(str(stats.total_profit_pct))
+                    }
+            
+            if data is None:
                 logger.info("🔄 No existing learning data, starting fresh")
                 return
             
-            with open(self.data_file, 'r') as f:
-                data = json.load(f)
-            
             self.total_trades = data.get("total_trades", 0)
             self.total_wins = data.get("total_wins", 0)
             self.total_profit = Decimal(data.get("total_profit", "0"))
@@ -11,6 +11,8 @@ from typing import Dict, List
 from web3 import Web3
 from web3.contract import Contract
 
+from src.tx_journal import CONFIRMED, REVERTED
+
 logger = logging.getLogger(__name__)
 
 # Polygon Mainnet Addresses
@@ -79,16 +81,20 @@ class TokenAllowanceManager:
     Email/Magic wallets have allowances set automatically.
     """
     
-    def __init__(self, web3: Web3, account):
+    def __init__(self, web3: Web3, account, fee_estimator=None, journal=None):
         """
         Initialize allowance manager.
         
         Args:
             web3: Web3 instance connected to Polygon
-            account: Account object with address and private key
+            account: Signer (src/signer.py) or eth_account LocalAccount - address and sign_transaction()
+            fee_estimator: Eip1559FeeEstimator for approval fees (optional; legacy gas price otherwise)
+            journal: TransactionJournal that records approvals (optional)
         """
         self.web3 = web3
         self.account = account
+        self.fee_estimator = fee_estimator
+        self.journal = journal
         
         # Initialize contracts
         self.usdc = web3.eth.contract(
@@ -182,19 +188,12 @@ class TokenAllowanceManager:
             # Build transaction
             tx = self.usdc.functions.approve(spender, amount).build_transaction({
                 'from': self.account.address,
-                'nonce': self.web3.eth.get_transaction_count(self.account.address),
+                'nonce': self.web3.eth.get_transaction_count(self.account.address, 'pending'),
                 'gas': 100000,
-                'gasPrice': self.web3.eth.gas_price
+                **self._fee_params()
             })
             
-            # Sign and send
-            signed_tx = self.web3.eth.account.sign_transaction(tx, self.account.key)
-            tx_hash = self.web3.eth.send_raw_transaction(signed_tx.rawTransaction)
-            
-            logger.info(f"USDC approval sent: {tx_hash.hex()}")
-            
-            # Wait for confirmation
-            receipt = self.web3.eth.wait_for_transaction_receipt(tx_hash, timeout=120)
+            receipt = self._send_and_wait(tx, "USDC approval")
             
             if receipt['status'] == 1:
                 logger.info(f"✅ USDC approved for {spender}")
@@ -207,6 +206,40 @@ class TokenAllowanceManager:
             logger.error(f"Failed to approve USDC for {spender}: {e}")
             return False
     
+    def _send_and_wait(self, tx: Dict, label: str):
+        """Sign, send (journaled before broadcast) and wait for the receipt."""
+        signed_tx = self.account.sign_transaction(tx)
+        journal_seq = None
+        if self.journal is not None:
+            journal_seq = self.journal.record_signed(
+                self.account.address, tx['nonce'], tx, signed_tx.raw_transaction, label=label
+            )
+        try:
+            tx_hash = self.web3.eth.send_raw_transaction(signed_tx.raw_transaction)
+        except Exception as e:
+            if journal_seq is not None:
+                self.journal.mark_failed(journal_seq, str(e))
+            raise
+        
+        logger.info(f"{label} sent: {tx_hash.hex()}")
+        if journal_seq is not None:
+            self.journal.mark_broadcast(journal_seq, tx_hash.hex())
+        
+        # Wait for confirmation
+        receipt = self.web3.eth.wait_for_transaction_receipt(tx_hash, timeout=120)
+        if journal_seq is not None:
+            self.journal.resolve_nonce(
+                self.account.address, tx['nonce'], tx_hash.hex(),
+                CONFIRMED if receipt['status'] == 1 else REVERTED, receipt.get('blockNumber')
+            )
+        return receipt
+    
+    def _fee_params(self) -> Dict[str, int]:
+        """EIP-1559 fees if an estimator is set, else the legacy gas price."""
+        if self.fee_estimator is not None:
+            return self.fee_estimator.estimate().tx_params()
+        return {'gasPrice': self.web3.eth.gas_price}
+    
     def approve_conditional_token(self, spender: str, dry_run: bool = False) -> bool:
         """
         Approve Conditional Token spending.
@@ -231,19 +264,12 @@ class TokenAllowanceManager:
                 True
             ).build_transaction({
                 'from': self.account.address,
-                'nonce': self.web3.eth.get_transaction_count(self.account.address),
+                'nonce': self.web3.eth.get_transaction_count(self.account.address, 'pending'),
                 'gas': 100000,
-                'gasPrice': self.web3.eth.gas_price
+                **self._fee_params()
             })
             
-            # Sign and send
-            signed_tx = self.web3.eth.account.sign_transaction(tx, self.account.key)
-            tx_hash = self.web3.eth.send_raw_transaction(signed_tx.rawTransaction)
-            
-            logger.info(f"Conditional Token approval sent: {tx_hash.hex()}")
-            
-            # Wait for confirmation
-            receipt = self.web3.eth.wait_for_transaction_receipt(tx_hash, timeout=120)
+            receipt = self._send_and_wait(tx, "Conditional Token approval")
             
             if receipt['status'] == 1:
                 logger.info(f"✅ Conditional Token approved for {spender}")
@@ -106,10 +106,19 @@ class TradeHistoryDB:
                     
                     -- Cross-platform specific
                     platform_a TEXT,
-                    platform_b TEXT
+                    platform_b TEXT,
+                    
+                    -- Wallet (sub-account) that placed the trade
+                    wallet TEXT NOT NULL DEFAULT 'primary'
                 )
             """)
             
+            # Databases created before multi-wallet support: their trades belong to the primary wallet
+            columns = [row["name"] for row in cursor.execute("PRAGMA table_info(trades)").fetchall()]
+            if "wallet" not in columns:
+                cursor.execute("ALTER TABLE trades ADD COLUMN wallet TEXT NOT NULL DEFAULT 'primary'")
+                self.logger.info("Added wallet column to trades table")
+            
             # Create indexes for common queries
             cursor.execute("""
                 CREATE INDEX IF NOT EXISTS idx_timestamp 
@@ -131,6 +140,11 @@ class TradeHistoryDB:
                 ON trades(market_id)
             """)
             
+            cursor.execute("""
+                CREATE INDEX IF NOT EXISTS idx_wallet 
+                ON trades(wallet)
+            """)
+            
             self.logger.info("Database schema initialized")
     
     def insert_trade(self, trade: TradeResult) -> bool:
@@ -158,8 +172,8 @@ class TradeHistoryDB:
                         yes_fill_price, no_fill_price,
                         actual_cost, actual_profit, gas_cost, net_profit,
                         yes_tx_hash, no_tx_hash, merge_tx_hash,
-                        error_message, platform_a, platform_b
-                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        error_message, platform_a, platform_b, wallet
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 """, (
                     trade.trade_id,
                     trade.timestamp.isoformat(),
@@ -190,6 +204,7 @@ class TradeHistoryDB:
                     trade.error_message,
                     trade.opportunity.platform_a,
                     trade.opportunity.platform_b,
+                    trade.wallet,
                 ))
                 
                 self.logger.debug(f"Trade inserted: {trade.trade_id}")
@@ -291,6 +306,41 @@ class TradeHistoryDB:
             self.logger.error(f"Failed to retrieve trades by strategy: {e}")
             return []
     
+    def get_trades_by_wallet(
+        self,
+        wallet: str,
+        limit: Optional[int] = None,
+    ) -> List[Dict[str, Any]]:
+        """
+        Retrieve trades placed by one wallet (sub-account).
+        
+        Args:
+            wallet: Wallet name ("primary" or a sub-account name)
+            limit: Optional limit on number of results
+            
+        Returns:
+            List[Dict]: List of trade records
+        """
+        try:
+            with self._get_connection() as conn:
+                cursor = conn.cursor()
+                
+                query = """
+                    SELECT * FROM trades 
+                    WHERE wallet = ?
+                    ORDER BY timestamp DESC
+                """
+                
+                if limit:
+                    query += f" LIMIT {limit}"
+                
+                cursor.execute(query, (wallet,))
+                return [dict(row) for row in cursor.fetchall()]
+                
+        except Exception as e:
+            self.logger.error(f"Failed to retrieve trades by wallet: {e}")
+            return []
+    
     def get_recent_trades(self, limit: int = 100) -> List[Dict[str, Any]]:
         """
         Retrieve most recent trades.
@@ -0,0 +1,644 @@
+"""
+Trade Outcome Store for the learning engines.
+
+One SQLite store of closed trades and their entry features, shared by the
+learning engines (AdaptiveLearningEngine, SuperSmartLearning,
+HistoricalSuccessTracker, ReinforcementLearningEngine, DynamicParameterSystem
+and ContinuousRLEngine). The strategy records each closed trade once and every
+subscribed engine learns from it; engines keep their learned parameters as
+engine state in the same store. The per-engine JSON files the engines used to
+write are imported once, the first time a store is opened on their directory.
+
+    python -m src.trade_outcome_store --by strategy asset
+    python -m src.trade_outcome_store --by hour --strategy latency --days 7
+
+Validates Requirements:
+- Single trade-outcome/feature store with schema versioning
+- Migration from the per-engine JSON files
+- Win rate by strategy, asset and hour
+- Read-only store check for startup validation
+"""
+
+import argparse
+import json
+import logging
+import sqlite3
+import sys
+import time
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+SCHEMA_VERSION = 1
+
+# Statements taking a store from the previous version to each version (PRAGMA user_version)
+MIGRATIONS: Dict[int, List[str]] = {
+    1: [
+        """
+        CREATE TABLE trade_outcomes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            closed_at REAL NOT NULL,
+            hour INTEGER NOT NULL,
+            strategy TEXT NOT NULL,
+            asset TEXT NOT NULL,
+            side TEXT NOT NULL,
+            market_id TEXT NOT NULL,
+            entry_price REAL NOT NULL,
+            exit_price REAL NOT NULL,
+            profit_pct REAL NOT NULL,
+            hold_time_minutes REAL NOT NULL,
+            exit_reason TEXT NOT NULL,
+            size REAL,
+            features TEXT NOT NULL,
+            source TEXT NOT NULL
+        )
+        """,
+        "CREATE INDEX idx_outcomes_closed ON trade_outcomes(closed_at)",
+        "CREATE INDEX idx_outcomes_strategy_asset ON trade_outcomes(strategy, asset)",
+        """
+        CREATE TABLE engine_state (
+            engine TEXT PRIMARY KEY,
+            state TEXT NOT NULL,
+            updated_at REAL NOT NULL
+        )
+        """,
+        """
+        CREATE TABLE legacy_imports (
+            file TEXT PRIMARY KEY,
+            imported_at REAL NOT NULL,
+            trades INTEGER NOT NULL,
+            engine TEXT
+        )
+        """,
+    ],
+}
+
+GROUP_COLUMNS = ("strategy", "asset", "hour")
+
+# Engine state kept in each legacy JSON file (historical_success.json holds trades only)
+LEGACY_STATE_FILES: Dict[str, Tuple[str, ...]] = {
+    "adaptive_learning": ("adaptive_learning.json",),
+    # The same engine state was read under two names; the file with more trades wins
+    "super_smart": ("super_smart_learning.json", "supersmart_learning.json"),
+    "reinforcement_learning": ("rl_q_table.json",),
+}
+LEGACY_DUPLICATE_SECONDS = 5.0  # Adaptive and historical records of one trade are written together
+
+
+@dataclass
+class TradeOutcomeRecord:
+    """A closed trade with the features it was entered on."""
+    closed_at: datetime  # UTC
+    strategy: str
+    asset: str
+    side: str  # "UP"/"DOWN"; empty for migrated trades that did not record it
+    entry_price: float
+    exit_price: float
+    profit_pct: float  # Realized return (0.05 = +5%)
+    hold_time_minutes: float
+    exit_reason: str
+    market_id: str = ""
+    size: Optional[float] = None  # Position size in USDC, when known
+    features: Dict[str, Any] = field(default_factory=dict)  # Entry MarketFeatures or market conditions
+    source: str = "live"  # live, dry_run, backtest or migrated
+    outcome_id: Optional[int] = None
+
+    @property
+    def won(self) -> bool:
+        return self.profit_pct > 0
+
+    @property
+    def hour(self) -> int:
+        return self.closed_at.hour
+
+    @property
+    def profit_usd(self) -> Optional[float]:
+        return self.size * self.profit_pct if self.size is not None else None
+
+
+@dataclass
+class OutcomeStats:
+    """Win rate and returns of a set of trades."""
+    trades: int = 0
+    wins: int = 0
+    total_profit_pct: float = 0.0
+
+    @property
+    def win_rate(self) -> Optional[float]:
+        return self.wins / self.trades if self.trades else None
+
+    @property
+    def avg_profit_pct(self) -> Optional[float]:
+        return self.total_profit_pct / self.trades if self.trades else None
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "trades": self.trades,
+            "wins": self.wins,
+            "win_rate": self.win_rate,
+            "avg_profit_pct": self.avg_profit_pct,
+            "total_profit_pct": self.total_profit_pct,
+        }
+
+
+def _utc(timestamp: str) -> datetime:
+    """Parse a legacy ISO timestamp; naive ones were written in local time."""
+    parsed = datetime.fromisoformat(timestamp)
+    return parsed.astimezone(timezone.utc)
+
+
+class TradeOutcomeStore:
+    """
+    SQLite store of closed trades shared by the learning engines.
+
+    Features:
+    - One record per closed trade, with its entry features
+    - Subscribers called with every recorded trade (errors are logged, never raised)
+    - Win rate and returns by strategy, asset and UTC hour of the close
+    - Engine state (learned parameters) kept next to the trades
+    - Schema versioned with PRAGMA user_version and migrated on open
+    - One-time import of the legacy per-engine JSON files
+    """
+
+    def __init__(
+        self,
+        db_path: str = "data/learning_store.db",
+        source: str = "live",
+        legacy_dir: Optional[str] = None
+    ):
+        """
+        Initialize the store.
+
+        Args:
+            db_path: Path to the SQLite database file (":memory:" keeps it in memory)
+            source: Source tag of recorded trades (live, dry_run or backtest)
+            legacy_dir: Directory of legacy engine JSON files to import (None: no import)
+
+        Raises:
+            ValueError: If the database was written by a newer schema version
+        """
+        self.db_path = db_path
+        self.source = source
+        # An in-memory database lives as long as its connection, so that one is kept open
+        self._memory_conn: Optional[sqlite3.Connection] = None
+        if db_path == ":memory:":
+            self._memory_conn = sqlite3.connect(db_path, check_same_thread=False)
+        else:
+            Path(db_path).parent.mkdir(parents=True, exist_ok=True)
+        self._init_schema()
+
+        # Called with every recorded TradeOutcomeRecord
+        self.subscribers: List[Callable[[TradeOutcomeRecord], None]] = []
+
+        if legacy_dir:
+            self.migrate_legacy_files(legacy_dir)
+
+        logger.info(f"📚 Trade outcome store initialized: {db_path}")
+
+    @contextmanager
+    def _get_connection(self):
+        """Context manager for database connections."""
+        conn = self._memory_conn or sqlite3.connect(self.db_path)
+        conn.row_factory = sqlite3.Row
+        try:
+            yield conn
+            conn.commit()
+        except Exception:
+            conn.rollback()
+            raise
+        finally:
+            if conn is not self._memory_conn:
+                conn.close()
+
+    @property
+    def schema_version(self) -> int:
+        with self._get_connection() as conn:
+            return conn.execute("PRAGMA user_version").fetchone()[0]
+
+    def _init_schema(self) -> None:
+        """Bring the database to SCHEMA_VERSION, one migration at a time."""
+        with self._get_connection() as conn:
+            version = conn.execute("PRAGMA user_version").fetchone()[0]
+            if version > SCHEMA_VERSION:
+                raise ValueError(
+                    f"{self.db_path} has trade outcome store schema version {version}, "
+                    f"this version supports up to {SCHEMA_VERSION}"
+                )
+            for target in range(version + 1, SCHEMA_VERSION + 1):
+                for statement in MIGRATIONS[target]:
+                    conn.execute(statement)
+                conn.execute(f"PRAGMA user_version = {target}")
+                logger.info(f"Migrated trade outcome store {self.db_path} to schema version {target}")
+
+    # ============================================================
+    # RECORDING
+    # ============================================================
+
+    def subscribe(self, callback: Callable[[TradeOutcomeRecord], None]) -> None:
+        """Call `callback` with every trade recorded from now on."""
+        self.subscribers.append(callback)
+
+    def _insert(self, conn: sqlite3.Connection, record: TradeOutcomeRecord) -> int:
+        cursor = conn.execute("""
+            INSERT INTO trade_outcomes (
+                closed_at, hour, strategy, asset, side, market_id, entry_price, exit_price,
+                profit_pct, hold_time_minutes, exit_reason, size, features, source
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        """, (
+            record.closed_at.timestamp(),
+            record.hour,
+            record.strategy,
+            record.asset,
+            record.side,
+            record.market_id,
+            record.entry_price,
+            record.exit_price,
+            record.profit_pct,
+            record.hold_time_minutes,
+            record.exit_reason,
+            record.size,
+            json.dumps(record.features, default=float),
+            record.source,
+        ))
+        return cursor.lastrowid
+
+    def record(
+        self,
+        strategy: str,
+        asset: str,
+        side: str,
+        entry_price: Any,
+        exit_price: Any,
+        profit_pct: Any,
+        hold_time_minutes: float,
+        exit_reason: str,
+        market_id: str = "",
+        size: Optional[Any] = None,
+        features: Optional[Dict[str, Any]] = None,
+        closed_at: Optional[datetime] = None
+    ) -> TradeOutcomeRecord:
+        """
+        Store a closed trade and pass it to the subscribers.
+
+        Subscribers are called even if the write fails, so the engines keep
+        learning in memory.
+
+        Returns:
+            TradeOutcomeRecord: The stored trade (outcome_id None if the write failed)
+        """
+        record = TradeOutcomeRecord(
+            closed_at=closed_at or datetime.now(timezone.utc),
+            strategy=strategy,
+            asset=asset,
+            side=side,
+            entry_price=float(entry_price),
+            exit_price=float(exit_price),
+            profit_pct=float(profit_pct),
+            hold_time_minutes=float(hold_time_minutes),
+            exit_reason=exit_reason,
+            market_id=market_id or "",
+            size=float(size) if size is not None else None,
+            features=dict(features or {}),
+            source=self.source,
+        )
+        try:
+            with self._get_connection() as conn:
+                record.outcome_id = self._insert(conn, record)
+        except sqlite3.Error as e:
+            logger.error(f"❌ Failed to store trade outcome {strategy}/{asset}: {e}")
+
+        for callback in self.subscribers:
+            try:
+                callback(record)
+            except Exception as e:
+                logger.warning(f"Trade outcome subscriber {getattr(callback, '__qualname__', callback)} failed: {e}")
+        return record
+
+    # ============================================================
+    # QUERIES
+    # ============================================================
+
+    @staticmethod
+    def _filters(
+        strategy: Optional[str],
+        asset: Optional[str],
+        hour: Optional[int],
+        since: Optional[datetime],
+        source: Optional[str]
+    ) -> Tuple[str, List[Any]]:
+        clause = " WHERE 1=1"
+        params: List[Any] = []
+        for column, value in (("strategy", strategy), ("asset", asset), ("hour", hour), ("source", source)):
+            if value is not None:
+                clause += f" AND {column} = ?"
+                params.append(value)
+        if since is not None:
+            clause += " AND closed_at >= ?"
+            params.append(since.timestamp())
+        return clause, params
+
+    @staticmethod
+    def _row_to_record(row: sqlite3.Row) -> TradeOutcomeRecord:
+        return TradeOutcomeRecord(
+            closed_at=datetime.fromtimestamp(row["closed_at"], tz=timezone.utc),
+            strategy=row["strategy"],
+            asset=row["asset"],
+            side=row["side"],
+            entry_price=row["entry_price"],
+            exit_price=row["exit_price"],
+            profit_pct=row["profit_pct"],
+            hold_time_minutes=row["hold_time_minutes"],
+            exit_reason=row["exit_reason"],
+            market_id=row["market_id"],
+            size=row["size"],
+            features=json.loads(row["features"]),
+            source=row["source"],
+            outcome_id=row["id"],
+        )
+
+    def get_outcomes(
+        self,
+        strategy: Optional[str] = None,
+        asset: Optional[str] = None,
+        since: Optional[datetime] = None,
+        source: Optional[str] = None,
+        limit: Optional[int] = None
+    ) -> List[TradeOutcomeRecord]:
+        """Stored trades, oldest first (the newest `limit` of them when given)."""
+        clause, params = self._filters(strategy, asset, None, since, source)
+        query = f"SELECT * FROM trade_outcomes{clause} ORDER BY closed_at DESC, id DESC"
+        if limit:
+            query += " LIMIT ?"
+            params.append(limit)
+        with self._get_connection() as conn:
+            rows = conn.execute(query, params).fetchall()
+        return [self._row_to_record(row) for row in reversed(rows)]
+
+    def get_stats(
+        self,
+        strategy: Optional[str] = None,
+        asset: Optional[str] = None,
+        hour: Optional[int] = None,
+        since: Optional[datetime] = None,
+        source: Optional[str] = None
+    ) -> OutcomeStats:
+        """Win rate and returns of the trades matching every given filter (hour: UTC hour of the close)."""
+        clause, params = self._filters(strategy, asset, hour, since, source)
+        with self._get_connection() as conn:
+            row = conn.execute(f"""
+                SELECT COUNT(*) AS trades, COALESCE(SUM(profit_pct > 0), 0) AS wins,
+                       COALESCE(SUM(profit_pct), 0.0) AS total
+                FROM trade_outcomes{clause}
+            """, params).fetchone()
+        return OutcomeStats(row["trades"], row["wins"], row["total"])
+
+    def get_stats_by(
+        self,
+        group_by: Sequence[str] = ("strategy",),
+        strategy: Optional[str] = None,
+        asset: Optional[str] = None,
+        hour: Optional[int] = None,
+        since: Optional[datetime] = None,
+        source: Optional[str] = None
+    ) -> Dict[Any, OutcomeStats]:
+        """
+        Win rate and returns per group, e.g. group_by=("strategy", "asset").
+
+        Returns:
+            Dict keyed by the group value, or by a tuple of values when grouping by several columns
+
+        Raises:
+            ValueError: If a group column is not strategy, asset or hour
+        """
+        if isinstance(group_by, str):
+            group_by = (group_by,)
+        unknown = [column for column in group_by if column not in GROUP_COLUMNS]
+        if unknown or not group_by:
+            raise ValueError(f"Cannot group trade outcomes by {unknown or group_by}; use {', '.join(GROUP_COLUMNS)}")
+        columns = ", ".join(group_by)
+        clause, params = self._filters(strategy, asset, hour, since, source)
+        with self._get_connection() as conn:
+            rows = conn.execute(f"""
+                SELECT {columns}, COUNT(*) AS trades, SUM(profit_pct > 0) AS wins, SUM(profit_pct) AS total
+                FROM trade_outcomes{clause}
+                GROUP BY {columns} ORDER BY {columns}
+            """, params).fetchall()
+        stats = {}
+        for row in rows:
+            key = tuple(row[column] for column in group_by)
+            stats[key[0] if len(key) == 1 else key] = OutcomeStats(row["trades"], row["wins"], row["total"])
+        return stats
+
+    # ============================================================
+    # ENGINE STATE
+    # ============================================================
+
+    def load_state(self, engine: str) -> Optional[Dict[str, Any]]:
+        """Learned state an engine saved (None if it never did)."""
+        with self._get_connection() as conn:
+            row = conn.execute("SELECT state FROM engine_state WHERE engine = ?", (engine,)).fetchone()
+        return json.loads(row["state"]) if row else None
+
+    def save_state(self, engine: str, state: Dict[str, Any]) -> None:
+        """Replace an engine's learned state."""
+        with self._get_connection() as conn:
+            self._save_state(conn, engine, state)
+
+    @staticmethod
+    def _save_state(conn: sqlite3.Connection, engine: str, state: Dict[str, Any]) -> None:
+        conn.execute(
+            "INSERT OR REPLACE INTO engine_state (engine, state, updated_at) VALUES (?, ?, ?)",
+            (engine, json.dumps(state), time.time())
+        )
+
+    # ============================================================
+    # LEGACY FILES
+    # ============================================================
+
+    def migrate_legacy_files(self, data_dir: str) -> Dict[str, Any]:
+        """
+        Import the per-engine JSON files in `data_dir` (each file once).
+
+        - historical_success.json: every trade
+        - adaptive_learning.json: its last 100 trades (skipping those already
+          imported from historical_success.json) and the engine's parameters
+        - super_smart_learning.json / supersmart_learning.json: the engine's
+          parameters, from whichever file has more trades
+        - rl_q_table.json: the Q-table
+
+        Returns:
+            Dict with the imported files, trade count and engine states
+        """
+        data_dir_path = Path(data_dir)
+        report: Dict[str, Any] = {"files": [], "trades": 0, "engines": []}
+        with self._get_connection() as conn:
+            done = {row["file"] for row in conn.execute("SELECT file FROM legacy_imports").fetchall()}
+
+            def pending(name: str) -> Optional[Dict[str, Any]]:
+                path = data_dir_path / name
+                if name in done or not path.exists():
+                    return None
+                try:
+                    with open(path) as f:
+                        return json.load(f)
+                except (OSError, ValueError) as e:
+                    logger.warning(f"⚠️ Not importing {path}: {e}")
+                    return None
+
+            def mark(name: str, trades: int, engine: Optional[str]) -> None:
+                conn.execute(
+                    "INSERT INTO legacy_imports (file, imported_at, trades, engine) VALUES (?, ?, ?, ?)",
+                    (name, time.time(), trades, engine)
+                )
+                report["files"].append(name)
+                report["trades"] += trades
+
+            historical = pending("historical_success.json")
+            imported: List[TradeOutcomeRecord] = []
+            if historical is not None:
+                for trade in historical.get("trades", []):
+                    record = TradeOutcomeRecord(
+                        closed_at=_utc(trade["timestamp"]),
+                        strategy=trade["strategy"],
+                        asset=trade["asset"],
+                        side="",
+                        entry_price=trade["entry_price"],
+                        exit_price=trade["exit_price"],
+                        profit_pct=trade["profit_pct"],
+                        hold_time_minutes=trade["hold_time_minutes"],
+                        exit_reason=trade["exit_reason"],
+                        market_id=trade.get("market_id", ""),
+                        features=trade.get("conditions") or {},
+                        source="migrated",
+                    )
+                    self._insert(conn, record)
+                    imported.append(record)
+                mark("historical_success.json", len(imported), None)
+
+            for engine, names in LEGACY_STATE_FILES.items():
+                candidates = [(name, data) for name in names for data in [pending(name)] if data is not None]
+                if not candidates:
+                    continue
+                name, state = max(candidates, key=lambda candidate: candidate[1].get("total_trades", 0))
+                for other, _ in candidates:
+                    if other != name:
+                        logger.warning(f"⚠️ {other} ignored: {name} holds more {engine} trades")
+                        mark(other, 0, None)
+
+                trades = 0
+                if engine == "adaptive_learning":
+                    for outcome in state.pop("trade_outcomes", []):
+                        closed_at = _utc(outcome["timestamp"])
+                        if any(
+                            r.asset == outcome["asset"]
+                            and abs((r.closed_at - closed_at).total_seconds()) <= LEGACY_DUPLICATE_SECONDS
+                            for r in imported
+                        ):
+                            continue
+                        self._insert(conn, TradeOutcomeRecord(
+                            closed_at=closed_at,
+                            strategy=outcome.get("strategy_used", "unknown"),
+                            asset=outcome["asset"],
+                            side=outcome["side"],
+                            entry_price=float(outcome["entry_price"]),
+                            exit_price=float(outcome["exit_price"]),
+                            profit_pct=float(outcome["profit_pct"]),
+                            hold_time_minutes=outcome["hold_time_minutes"],
+                            exit_reason=outcome["exit_reason"],
+                            source="migrated",
+                        ))
+                        trades += 1
+
+                existing = conn.execute("SELECT 1 FROM engine_state WHERE engine = ?", (engine,)).fetchone()
+                if existing is None:
+                    self._save_state(conn, engine, state)
+                    report["engines"].append(engine)
+                mark(name, trades, engine)
+
+        if report["files"]:
+            logger.info(
+                f"📦 Imported {', '.join(report['files'])} into {self.db_path}: "
+                f"{report['trades']} trades, state of {', '.join(report['engines']) or 'no engines'}"
+            )
+        return report
+
+
+def verify_store(db_path: str) -> Dict[str, int]:
+    """
+    Check an existing store without opening it for writing (startup validation).
+
+    Args:
+        db_path: Path to the SQLite database file
+
+    Returns:
+        dict: schema_version and number of recorded trades
+
+    Raises:
+        ValueError: If the file is not a store of a supported schema version or fails the integrity check
+        sqlite3.Error: If the file cannot be read as an SQLite database
+    """
+    conn = sqlite3.connect(f"{Path(db_path).resolve().as_uri()}?mode=ro", uri=True)
+    try:
+        version = conn.execute("PRAGMA user_version").fetchone()[0]
+        if not 1 <= version <= SCHEMA_VERSION:
+            raise ValueError(f"{db_path} has schema version {version}, expected 1 to {SCHEMA_VERSION}")
+        integrity = conn.execute("PRAGMA quick_check").fetchone()[0]
+        if integrity != "ok":
+            raise ValueError(f"{db_path} failed the integrity check: {integrity}")
+        trades = conn.execute("SELECT COUNT(*) FROM trade_outcomes").fetchone()[0]
+    finally:
+        conn.close()
+    return {"schema_version": version, "trades": trades}
+
+
+# ============================================================================
+# Command line
+# ============================================================================
+
+def main(argv: Optional[List[str]] = None) -> int:
+    """Command-line entry point: win rates from a trade outcome store."""
+    parser = argparse.ArgumentParser(description="Win rate by strategy, asset and hour from the trade outcome store")
+    parser.add_argument("--db", type=Path, default=Path("data/learning_store.db"), help="Trade outcome store")
+    parser.add_argument("--by", nargs="+", choices=GROUP_COLUMNS, default=["strategy"], help="Group columns")
+    parser.add_argument("--strategy", help="Only this strategy")
+    parser.add_argument("--asset", help="Only this asset")
+    parser.add_argument("--hour", type=int, help="Only trades closed in this UTC hour")
+    parser.add_argument("--source", help="Only this source (live, dry_run, backtest, migrated)")
+    parser.add_argument("--days", type=float, help="Only trades closed in the last DAYS days")
+    parser.add_argument("--migrate", type=Path, metavar="DATA_DIR", help="Import legacy engine JSON files first")
+    parser.add_argument("--output", type=Path, help="Write the JSON report to this file")
+    args = parser.parse_args(argv)
+
+    logging.basicConfig(level=logging.WARNING, format="%(asctime)s - %(name)s - %(levelname)s - %(message)s")
+
+    if not args.db.exists() and not args.migrate:
+        parser.error(f"No trade outcome store at {args.db}")
+    try:
+        store = TradeOutcomeStore(str(args.db))
+    except ValueError as e:
+        parser.error(str(e))
+    report: Dict[str, Any] = {}
+    if args.migrate:
+        report["migration"] = store.migrate_legacy_files(str(args.migrate))
+
+    since = datetime.fromtimestamp(time.time() - args.days * 86400, tz=timezone.utc) if args.days else None
+    filters = {"strategy": args.strategy, "asset": args.asset, "hour": args.hour, "since": since, "source": args.source}
+    report["total"] = store.get_stats(**filters).to_dict()
+    report["groups"] = [
+        {**dict(zip(args.by, key if isinstance(key, tuple) else (key,))), **stats.to_dict()}
+        for key, stats in store.get_stats_by(args.by, **filters).items()
+    ]
+
+    output = json.dumps(report, indent=2, default=str)
+    if args.output:
+        args.output.write_text(output)
+    print(output)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
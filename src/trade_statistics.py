@@ -49,6 +49,9 @@ class TradeStatistics:
     # Strategy breakdown
     strategy_stats: dict = field(default_factory=dict)
     
+    # Wallet (sub-account) breakdown
+    wallet_stats: dict = field(default_factory=dict)
+    
     # Time period
     start_date: Optional[datetime] = None
     end_date: Optional[datetime] = None
@@ -312,16 +315,40 @@ class TradeStatisticsTracker:
         Returns:
             dict: Strategy statistics
         """
+        return self._breakdown('strategy', trades)
+    
+    def get_wallet_breakdown(
+        self,
+        trades: Optional[List[dict]] = None,
+    ) -> dict:
+        """
+        Get statistics breakdown by wallet (sub-account).
+        
+        Args:
+            trades: Optional list of trade records (uses recent trades if None)
+            
+        Returns:
+            dict: Wallet statistics
+        """
+        return self._breakdown('wallet', trades, default='primary')
+    
+    def _breakdown(
+        self,
+        key: str,
+        trades: Optional[List[dict]] = None,
+        default: Optional[str] = None,
+    ) -> dict:
+        """Group trade totals, win rate and net profit by a trade column (`default` for rows without it)."""
         if trades is None:
             trades = self.db.get_recent_trades(limit=1000)
         
-        strategy_stats = {}
+        breakdown = {}
         
         for trade in trades:
-            strategy = trade['strategy']
+            group = (trade.get(key) or default) if default else trade[key]
             
-            if strategy not in strategy_stats:
-                strategy_stats[strategy] = {
+            if group not in breakdown:
+                breakdown[group] = {
                     'total': 0,
                     'successful': 0,
                     'failed': 0,
@@ -329,7 +356,7 @@ class TradeStatisticsTracker:
                     'gas_cost': Decimal('0'),
                 }
             
-            stats = strategy_stats[strategy]
+            stats = breakdown[group]
             stats['total'] += 1
             
             if trade['status'] == 'success':
@@ -341,7 +368,7 @@ class TradeStatisticsTracker:
             stats['gas_cost'] += Decimal(trade['gas_cost'])
         
         # Calculate win rates
-        for strategy, stats in strategy_stats.items():
+        for group, stats in breakdown.items():
             if stats['total'] > 0:
                 stats['win_rate'] = (Decimal(stats['successful']) / Decimal(stats['total'])) * Decimal('100')
             else:
@@ -349,7 +376,7 @@ class TradeStatisticsTracker:
             
             stats['net_profit'] = stats['profit'] - stats['gas_cost']
         
-        return strategy_stats
+        return breakdown
     
     def get_statistics(
         self,
@@ -386,6 +413,7 @@ class TradeStatisticsTracker:
                 sharpe_ratio=Decimal('0'),
                 max_drawdown=Decimal('0'),
                 strategy_stats={},
+                wallet_stats={},
                 start_date=start_date,
                 end_date=end_date,
             )
@@ -406,6 +434,7 @@ class TradeStatisticsTracker:
         sharpe_ratio = self.calculate_sharpe_ratio(trades)
         max_drawdown = self.calculate_max_drawdown(trades)
         strategy_stats = self.get_strategy_breakdown(trades)
+        wallet_stats = self.get_wallet_breakdown(trades)
         
         return TradeStatistics(
             total_trades=total_trades,
@@ -420,6 +449,7 @@ class TradeStatisticsTracker:
             sharpe_ratio=sharpe_ratio,
             max_drawdown=max_drawdown,
             strategy_stats=strategy_stats,
+            wallet_stats=wallet_stats,
             start_date=start_date,
             end_date=end_date,
         )
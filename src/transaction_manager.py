@@ -2,12 +2,16 @@
 Transaction Manager for Polymarket Arbitrage Bot.
 
 Manages transaction submission, nonce tracking, and retry logic.
+Transactions use EIP-1559 fees (src/fee_strategy.py) unless the caller sets a
+legacy gasPrice; stuck transactions are replaced by fee (bounded) or cancelled
+with a self-send at the same nonce. With a TransactionJournal every signed
+transaction is persisted before broadcast and resumed after a restart.
 Validates Requirements 18.1, 18.2, 18.4, 18.5, 16.4, 18.3.
 """
 
 import asyncio
 import time
-from typing import Dict, Optional, Set
+from typing import Dict, List, Optional, Set, Tuple
 from dataclasses import dataclass, field
 from datetime import datetime
 from web3 import Web3
@@ -15,6 +19,10 @@ from web3.types import TxReceipt, TxParams
 from eth_account.signers.local import LocalAccount
 import logging
 
+from src.error_recovery import GasPriceEscalator
+from src.fee_strategy import GWEI, Eip1559FeeEstimator, FeeCapExceededError
+from src.tx_journal import CANCELLED, CONFIRMED, REVERTED, SIGNED, JournalEntry, TransactionJournal
+
 logger = logging.getLogger(__name__)
 
 
@@ -24,8 +32,22 @@ class PendingTransaction:
     tx_hash: str
     nonce: int
     submitted_at: float
-    gas_price: int
+    gas_price: int  # Legacy gasPrice, or maxFeePerGas for EIP-1559
     tx_params: Dict
+    max_priority_fee: Optional[int] = None  # EIP-1559 only
+    replaced_hashes: List[str] = field(default_factory=list)  # Earlier txs at this nonce
+    is_cancellation: bool = False
+    label: str = ""
+    journal_seq: Optional[int] = None  # TransactionJournal entry
+    
+    @property
+    def is_eip1559(self) -> bool:
+        return self.max_priority_fee is not None
+    
+    @property
+    def all_hashes(self) -> List[str]:
+        """This transaction and every one it replaced (any of them may get mined)."""
+        return [self.tx_hash] + self.replaced_hashes
 
 
 class TransactionError(Exception):
@@ -48,6 +70,11 @@ class TransactionTimeoutError(TransactionError):
     pass
 
 
+class TransactionCancelledError(TransactionError):
+    """Raised when a cancellation (self-send) was mined instead of the transaction."""
+    pass
+
+
 class TransactionManager:
     """
     Manages transaction submission with nonce tracking and retry logic.
@@ -55,9 +82,12 @@ class TransactionManager:
     Features:
     - Nonce tracking with pending queue
     - Automatic nonce conflict resolution
-    - Stuck transaction resubmission with gas escalation
+    - EIP-1559 fees from recent blocks (legacy gasPrice if the caller sets one)
+    - Stuck transaction replacement with bounded fee escalation
+    - Cancellation by 0-value self-send at the same nonce
     - Pending transaction limit enforcement (max 5)
     - Exponential backoff for network errors
+    - Optional write-ahead journal, reconciled with the chain on startup
     
     Validates Requirements:
     - 18.1: Fetch current nonce from blockchain
@@ -74,7 +104,12 @@ class TransactionManager:
         wallet: LocalAccount,
         max_pending_tx: int = 5,
         stuck_tx_timeout: int = 60,
-        confirmation_timeout: int = 120
+        confirmation_timeout: int = 120,
+        fee_estimator: Optional[Eip1559FeeEstimator] = None,
+        max_replacements: int = 5,
+        escalation_factor: float = 1.1,
+        auto_replace: bool = False,
+        journal: Optional[TransactionJournal] = None
     ):
         """
         Initialize Transaction Manager.
@@ -85,12 +120,21 @@ class TransactionManager:
             max_pending_tx: Maximum number of pending transactions (default 5)
             stuck_tx_timeout: Seconds before considering transaction stuck (default 60)
             confirmation_timeout: Seconds to wait for confirmation (default 120)
+            fee_estimator: EIP-1559 fee source (default: estimator on `web3`, no cap)
+            max_replacements: Fee replacements allowed per nonce
+            escalation_factor: Fee multiplier per replacement (nodes require >= 1.1)
+            auto_replace: If True, wait_for_confirmation() replaces stuck transactions
+            journal: Persists signed transactions across restarts (see recover_from_journal)
         """
         self.web3 = web3
         self.wallet = wallet
         self.max_pending_tx = max_pending_tx
         self.stuck_tx_timeout = stuck_tx_timeout
         self.confirmation_timeout = confirmation_timeout
+        self.fee_estimator = fee_estimator or Eip1559FeeEstimator(web3)
+        self.escalator = GasPriceEscalator(escalation_factor=escalation_factor, max_escalations=max_replacements)
+        self.auto_replace = auto_replace
+        self.journal = journal
         
         # Nonce tracking
         self._current_nonce: Optional[int] = None
@@ -161,7 +205,23 @@ class TransactionManager:
         """
         return len(self._pending_transactions)
     
-    async def send_transaction(self, tx_params: TxParams) -> str:
+    async def estimate_fees(self, urgency: str = "normal") -> Dict[str, int]:
+        """
+        EIP-1559 fee fields for a transaction built by the caller.
+        
+        Pass them to build_transaction(); without fee fields web3 fills in its
+        own defaults, which send_transaction() would then keep.
+        
+        Raises:
+            TransactionError: If current fees exceed the fee cap
+        """
+        try:
+            fees = await asyncio.to_thread(self.fee_estimator.estimate, urgency)
+        except FeeCapExceededError as e:
+            raise TransactionError(f"Transaction not sent: {e}")
+        return fees.tx_params()
+    
+    async def send_transaction(self, tx_params: TxParams, urgency: str = "normal", label: str = "") -> str:
         """
         Send transaction with nonce management.
         
@@ -171,13 +231,16 @@ class TransactionManager:
         - Tracks pending transactions
         
         Args:
-            tx_params: Transaction parameters (without nonce)
+            tx_params: Transaction parameters (without nonce). Without fee fields
+                the transaction gets EIP-1559 fees; a gasPrice keeps it legacy.
+            urgency: Priority fee level for EIP-1559 fees (low, normal, high)
+            label: What the transaction does, for the journal and logs ("merge", "redeem")
             
         Returns:
             str: Transaction hash (hex string with 0x prefix)
             
         Raises:
-            TransactionError: If pending transaction limit exceeded
+            TransactionError: If pending transaction limit exceeded or fees exceed the cap
             NonceConflictError: If nonce conflict detected
         """
         # Check pending transaction limit (Requirement 18.4)
@@ -187,47 +250,27 @@ class TransactionManager:
                 f"{self.get_pending_count()}/{self.max_pending_tx}"
             )
         
+        # Fees before the nonce, so a fee-cap refusal does not leave a gap
+        tx_params_with_nonce = dict(tx_params)
+        if 'gasPrice' not in tx_params_with_nonce and 'maxFeePerGas' not in tx_params_with_nonce:
+            tx_params_with_nonce.update(await self.estimate_fees(urgency))
+        
         # Get next available nonce
         nonce = await self.get_next_nonce()
         
         # Add nonce to transaction params
-        tx_params_with_nonce = dict(tx_params)
         tx_params_with_nonce['nonce'] = nonce
         tx_params_with_nonce['from'] = self.wallet.address
         
-        # Ensure gas price is set
-        if 'gasPrice' not in tx_params_with_nonce:
-            gas_price = await asyncio.to_thread(self.web3.eth.gas_price)
-            tx_params_with_nonce['gasPrice'] = gas_price
-        
         try:
-            # Sign transaction
-            signed_tx = await asyncio.to_thread(
-                self.wallet.sign_transaction,
-                tx_params_with_nonce
-            )
-            
-            # Send raw transaction
-            tx_hash = await asyncio.to_thread(
-                self.web3.eth.send_raw_transaction,
-                signed_tx.raw_transaction
-            )
-            
-            tx_hash_hex = tx_hash.hex()
+            tx_hash_hex, journal_seq = await self._sign_and_send(tx_params_with_nonce, label)
             
             # Track pending transaction
-            pending_tx = PendingTransaction(
-                tx_hash=tx_hash_hex,
-                nonce=nonce,
-                submitted_at=time.time(),
-                gas_price=tx_params_with_nonce['gasPrice'],
-                tx_params=tx_params_with_nonce
-            )
-            self._pending_transactions[tx_hash_hex] = pending_tx
+            pending_tx = self._track(tx_hash_hex, tx_params_with_nonce, label, journal_seq)
             
             logger.info(
                 f"Transaction sent: hash={tx_hash_hex}, nonce={nonce}, "
-                f"gas_price={tx_params_with_nonce['gasPrice']}"
+                f"{self._describe_fees(pending_tx)}"
             )
             
             return tx_hash_hex
@@ -250,10 +293,13 @@ class TransactionManager:
         
         Validates Requirements 18.5, 16.4:
         - Updates nonce tracker when transaction confirms
-        - Handles stuck transactions
+        - Handles stuck transactions (replaced automatically if auto_replace)
+        
+        Replacements of `tx_hash` are followed: whichever transaction at its
+        nonce is mined confirms it.
         
         Args:
-            tx_hash: Transaction hash to wait for
+            tx_hash: Transaction hash to wait for (original or a replacement)
             timeout: Timeout in seconds (uses default if None)
             
         Returns:
@@ -261,6 +307,7 @@ class TransactionManager:
             
         Raises:
             TransactionTimeoutError: If confirmation times out
+            TransactionCancelledError: If the transaction was cancelled and the cancellation mined
         """
         if timeout is None:
             timeout = self.confirmation_timeout
@@ -268,23 +315,22 @@ class TransactionManager:
         start_time = time.time()
         
         while time.time() - start_time < timeout:
+            pending_tx = self._find_pending(tx_hash)
             try:
-                # Check if transaction is mined
-                receipt = await asyncio.to_thread(
-                    self.web3.eth.get_transaction_receipt,
-                    tx_hash
-                )
+                # Check if transaction (or one of its replacements) is mined
+                mined_hash, receipt = await self._find_receipt(pending_tx.all_hashes if pending_tx else [tx_hash])
                 
                 if receipt is not None:
                     # Transaction confirmed
-                    await self._on_transaction_confirmed(tx_hash, receipt)
+                    await self._on_transaction_confirmed(mined_hash, receipt)
                     
-                    if receipt['status'] == 1:
-                        logger.info(f"Transaction confirmed: {tx_hash}")
-                        return receipt
-                    else:
-                        logger.error(f"Transaction reverted: {tx_hash}")
-                        raise TransactionError(f"Transaction reverted: {tx_hash}")
+                    if receipt['status'] != 1:
+                        logger.error(f"Transaction reverted: {mined_hash}")
+                        raise TransactionError(f"Transaction reverted: {mined_hash}")
+                    if pending_tx is not None and pending_tx.is_cancellation and mined_hash == pending_tx.tx_hash:
+                        raise TransactionCancelledError(f"Transaction cancelled at nonce {pending_tx.nonce}: {tx_hash}")
+                    logger.info(f"Transaction confirmed: {mined_hash}")
+                    return receipt
                 
             except TransactionError:
                 # Re-raise transaction errors (like reverts)
@@ -294,15 +340,19 @@ class TransactionManager:
                     logger.warning(f"Error checking transaction: {e}")
             
             # Check if transaction is stuck (Requirement 16.4)
-            if tx_hash in self._pending_transactions:
-                pending_tx = self._pending_transactions[tx_hash]
+            if pending_tx is not None:
                 elapsed = time.time() - pending_tx.submitted_at
                 
                 if elapsed > self.stuck_tx_timeout:
                     logger.warning(
-                        f"Transaction stuck for {elapsed:.0f}s: {tx_hash}"
+                        f"Transaction stuck for {elapsed:.0f}s: {pending_tx.tx_hash}"
                     )
-                    # Note: Caller should handle resubmission
+                    if self.auto_replace and not pending_tx.is_cancellation:
+                        try:
+                            await self.resubmit_stuck_transaction(pending_tx.tx_hash)
+                        except TransactionError as e:
+                            logger.warning(f"Not replacing stuck transaction: {e}")
+                    # Otherwise: caller should handle resubmission
             
             await asyncio.sleep(2)
         
@@ -310,88 +360,333 @@ class TransactionManager:
             f"Transaction confirmation timeout after {timeout}s: {tx_hash}"
         )
     
-    async def resubmit_stuck_transaction(self, tx_hash: str) -> str:
+    async def resubmit_stuck_transaction(self, tx_hash: str, urgency: str = "high") -> str:
         """
-        Resubmit stuck transaction with 10% higher gas price.
+        Replace a stuck transaction with higher fees at the same nonce.
+        
+        Legacy transactions get a 10% higher gas price. EIP-1559 transactions
+        get both fees raised by the escalation factor, or to the current
+        network estimate if that is higher. Replacements per nonce are bounded
+        by max_replacements and the fee cap.
         
         Validates Requirements 16.4, 18.3:
         - Checks if transaction was mined before resubmitting
         - Increases gas price by 10% and resubmits with same nonce
         
         Args:
-            tx_hash: Hash of stuck transaction
+            tx_hash: Hash of stuck transaction (original or latest replacement)
+            urgency: Priority fee level the replacement must at least match
             
         Returns:
-            str: New transaction hash
+            str: New transaction hash (or the mined one, if already mined)
             
         Raises:
-            TransactionError: If transaction not found or already mined
+            TransactionError: If transaction not found or replacement limit/fee cap reached
         """
-        if tx_hash not in self._pending_transactions:
+        pending_tx = self._find_pending(tx_hash)
+        if pending_tx is None:
             raise TransactionError(f"Transaction not found: {tx_hash}")
         
-        pending_tx = self._pending_transactions[tx_hash]
-        
         # Check if transaction was already mined (Requirement 18.3)
-        try:
-            receipt = await asyncio.to_thread(
-                self.web3.eth.get_transaction_receipt,
-                tx_hash
-            )
-            if receipt is not None:
-                logger.info(f"Transaction already mined: {tx_hash}")
-                await self._on_transaction_confirmed(tx_hash, receipt)
-                return tx_hash
-        except Exception:
-            pass  # Transaction not mined yet
-        
-        # Increase gas price by 10% (Requirement 16.4)
-        new_gas_price = int(pending_tx.gas_price * 1.1)
+        mined_hash = await self._check_mined(pending_tx)
+        if mined_hash is not None:
+            return mined_hash
         
-        # Create new transaction with same nonce but higher gas
         new_tx_params = dict(pending_tx.tx_params)
-        new_tx_params['gasPrice'] = new_gas_price
+        new_tx_params.update(await self._replacement_fees(pending_tx, urgency))
         
         logger.info(
-            f"Resubmitting transaction with 10% higher gas: "
-            f"nonce={pending_tx.nonce}, "
-            f"old_gas={pending_tx.gas_price}, new_gas={new_gas_price}"
+            f"Replacing stuck transaction: nonce={pending_tx.nonce}, "
+            f"{self._describe_fees(pending_tx)} -> {self._describe_fee_params(new_tx_params)}"
         )
         
-        try:
-            # Sign and send new transaction
-            signed_tx = await asyncio.to_thread(
-                self.wallet.sign_transaction,
-                new_tx_params
-            )
+        new_tx_hash_hex = await self._replace(pending_tx, new_tx_params, is_cancellation=pending_tx.is_cancellation)
+        logger.info(f"Transaction resubmitted: {new_tx_hash_hex}")
+        return new_tx_hash_hex
+    
+    async def cancel_transaction(self, tx_hash: str) -> str:
+        """
+        Cancel a pending transaction with a 0-value self-send at its nonce.
+        
+        The self-send carries replacement fees, so it displaces the original
+        once mined. wait_for_confirmation() on the original then raises
+        TransactionCancelledError (unless the original was mined first).
+        
+        Args:
+            tx_hash: Hash of the transaction to cancel (original or a replacement)
+            
+        Returns:
+            str: Hash of the cancellation transaction
             
-            new_tx_hash = await asyncio.to_thread(
+        Raises:
+            TransactionError: If not found, already mined, or fee limits reached
+        """
+        pending_tx = self._find_pending(tx_hash)
+        if pending_tx is None:
+            raise TransactionError(f"Transaction not found: {tx_hash}")
+        
+        mined_hash = await self._check_mined(pending_tx)
+        if mined_hash is not None:
+            raise TransactionError(f"Transaction already mined, cannot cancel: {mined_hash}")
+        
+        chain_id = pending_tx.tx_params.get('chainId')
+        if chain_id is None:
+            chain_id = await asyncio.to_thread(lambda: self.web3.eth.chain_id)
+        cancel_params = {
+            'from': self.wallet.address,
+            'to': self.wallet.address,
+            'value': 0,
+            'gas': 21000,
+            'nonce': pending_tx.nonce,
+            'chainId': chain_id,
+        }
+        cancel_params.update(await self._replacement_fees(pending_tx, "high"))
+        
+        cancel_hash = await self._replace(pending_tx, cancel_params, is_cancellation=True)
+        logger.warning(f"Cancelling transaction at nonce {pending_tx.nonce}: {pending_tx.tx_hash} -> {cancel_hash}")
+        return cancel_hash
+    
+    async def recover_from_journal(self) -> List[str]:
+        """
+        Reconcile open journal entries with the chain after a restart.
+        
+        For each unresolved nonce of this wallet:
+        - One of its transactions was mined: the journal is updated
+        - The nonce was used by a transaction not in the journal: entries are dropped
+        - Still pending: tracked again (nonce reserved, original submit time kept),
+          after rebroadcasting the newest transaction if it was only signed
+        
+        Returns:
+            list: Hashes of the transactions still pending; pass them to
+            wait_for_confirmation() to resume waiting (and replacement)
+        """
+        if self.journal is None:
+            return []
+        
+        address = self.wallet.address
+        chains = await asyncio.to_thread(self.journal.open_nonces, address)
+        if not chains:
+            return []
+        chain_nonce = await asyncio.to_thread(self.web3.eth.get_transaction_count, address, 'latest')
+        
+        resumed = []
+        for nonce, entries in sorted(chains.items()):
+            hashes = [entry.tx_hash for entry in reversed(entries) if entry.tx_hash]
+            try:
+                mined_hash, receipt = await self._find_receipt(hashes)
+            except Exception as e:
+                logger.warning(f"Cannot check journaled transactions at nonce {nonce}: {e}")
+                mined_hash, receipt = None, None
+        
+            if receipt is not None:
+                mined = next(entry for entry in entries if entry.tx_hash == mined_hash)
+                if receipt['status'] != 1:
+                    status = REVERTED
+                elif mined.is_cancellation:
+                    status = CANCELLED
+                else:
+                    status = CONFIRMED
+                await asyncio.to_thread(
+                    self.journal.resolve_nonce, address, nonce, mined_hash, status, receipt.get('blockNumber')
+                )
+                logger.info(f"📓 Journaled {mined.label or 'transaction'} at nonce {nonce} was mined while offline: {status}")
+                continue
+        
+            if nonce < chain_nonce:
+                await asyncio.to_thread(
+                    self.journal.mark_dropped, address, nonce, "nonce used by a transaction not in the journal"
+                )
+                logger.warning(f"📓 Nonce {nonce} was used by another transaction; journaled {entries[-1].label or 'transaction'} dropped")
+                continue
+        
+            head = entries[-1]
+            if head.status == SIGNED:
+                head = await self._rebroadcast(head, entries)
+                if head is None:
+                    continue
+        
+            pending_tx = self._track(head.tx_hash, head.tx_params, head.label, head.seq, submitted_at=head.created_at)
+            pending_tx.replaced_hashes = [entry.tx_hash for entry in reversed(entries) if entry.tx_hash and entry is not head]
+            pending_tx.is_cancellation = head.is_cancellation
+            resumed.append(head.tx_hash)
+            logger.info(f"📓 Resuming {head.label or 'transaction'} at nonce {nonce}: {head.tx_hash}")
+        
+        if resumed:
+            async with self._nonce_lock:
+                for tx_hash in resumed:
+                    self._pending_nonces.add(self._pending_transactions[tx_hash].nonce)
+                highest = max(self._pending_nonces)
+                self._current_nonce = max(self._current_nonce or 0, highest + 1)
+        
+        return resumed
+    
+    async def _rebroadcast(self, entry: JournalEntry, entries: List[JournalEntry]) -> Optional[JournalEntry]:
+        """Send a journaled transaction that may not have reached the node; returns the entry to track."""
+        try:
+            tx_hash = await asyncio.to_thread(
                 self.web3.eth.send_raw_transaction,
-                signed_tx.raw_transaction
+                bytes.fromhex(entry.raw_transaction[2:])
             )
+            tx_hash_hex = tx_hash.hex()
+        except Exception as e:
+            if "already known" not in str(e).lower():
+                await asyncio.to_thread(self.journal.mark_failed, entry.seq, str(e))
+                logger.warning(f"📓 Rebroadcast of journaled transaction at nonce {entry.nonce} failed: {e}")
+                # An earlier broadcast at this nonce may still be pending
+                earlier = [other for other in entries if other.tx_hash and other is not entry]
+                return earlier[-1] if earlier else None
+            tx_hash_hex = Web3.keccak(hexstr=entry.raw_transaction).hex()
+        
+        await asyncio.to_thread(self.journal.mark_broadcast, entry.seq, tx_hash_hex)
+        entry.tx_hash = tx_hash_hex
+        return entry
+    
+    async def _replacement_fees(self, pending_tx: PendingTransaction, urgency: str) -> Dict[str, int]:
+        """Escalated fee fields for replacing `pending_tx` (bounded per nonce)."""
+        key = f"nonce:{pending_tx.nonce}"
+        cap = self.fee_estimator.max_fee_cap
+        try:
+            if not pending_tx.is_eip1559:
+                return {'gasPrice': self.escalator.escalate_gas_price(pending_tx.gas_price, key)}
             
-            new_tx_hash_hex = new_tx_hash.hex()
-            
-            # Remove old transaction from pending
-            del self._pending_transactions[tx_hash]
-            
-            # Add new transaction to pending
-            new_pending_tx = PendingTransaction(
-                tx_hash=new_tx_hash_hex,
-                nonce=pending_tx.nonce,  # Same nonce
-                submitted_at=time.time(),
-                gas_price=new_gas_price,
-                tx_params=new_tx_params
+            max_fee, priority_fee = self.escalator.escalate_fees(
+                pending_tx.gas_price, pending_tx.max_priority_fee, key, max_fee_cap=cap
+            )
+        except ValueError as e:
+            raise TransactionError(f"Cannot replace transaction at nonce {pending_tx.nonce}: {e}")
+        
+        # Follow the network if it moved further than the bump
+        try:
+            current = await asyncio.to_thread(self.fee_estimator.estimate, urgency)
+        except FeeCapExceededError:
+            current = None
+        if current is not None and not current.legacy:
+            priority_fee = max(priority_fee, current.max_priority_fee)
+            max_fee = max(max_fee, current.max_fee - current.max_priority_fee + priority_fee)
+            if cap is not None:
+                max_fee = min(max_fee, cap)
+            max_fee = max(max_fee, priority_fee)
+        return {'maxFeePerGas': max_fee, 'maxPriorityFeePerGas': priority_fee}
+    
+    async def _replace(self, pending_tx: PendingTransaction, new_tx_params: Dict, is_cancellation: bool) -> str:
+        """Sign and send a replacement; it takes over the pending entry of `pending_tx`."""
+        try:
+            new_tx_hash_hex, journal_seq = await self._sign_and_send(
+                new_tx_params, pending_tx.label, is_cancellation, replaces=pending_tx.journal_seq
             )
-            self._pending_transactions[new_tx_hash_hex] = new_pending_tx
-            
-            logger.info(f"Transaction resubmitted: {new_tx_hash_hex}")
-            
-            return new_tx_hash_hex
-            
         except Exception as e:
             logger.error(f"Failed to resubmit transaction: {e}")
             raise TransactionError(f"Transaction resubmission failed: {e}")
+        
+        # Remove old transaction from pending
+        del self._pending_transactions[pending_tx.tx_hash]
+        
+        # Add new transaction to pending (same nonce)
+        new_pending_tx = self._track(new_tx_hash_hex, new_tx_params, pending_tx.label, journal_seq)
+        new_pending_tx.replaced_hashes = pending_tx.all_hashes
+        new_pending_tx.is_cancellation = is_cancellation
+        return new_tx_hash_hex
+    
+    async def _sign_and_send(
+        self,
+        tx_params: Dict,
+        label: str = "",
+        is_cancellation: bool = False,
+        replaces: Optional[int] = None
+    ) -> Tuple[str, Optional[int]]:
+        """Sign, journal (before broadcast) and broadcast; returns (hash hex, journal seq)."""
+        signed_tx = await asyncio.to_thread(
+            self.wallet.sign_transaction,
+            tx_params
+        )
+        journal_seq = None
+        if self.journal is not None:
+            journal_seq = await asyncio.to_thread(
+                self.journal.record_signed,
+                self.wallet.address, tx_params['nonce'], tx_params, signed_tx.raw_transaction,
+                label, is_cancellation, replaces
+            )
+        try:
+            tx_hash = await asyncio.to_thread(
+                self.web3.eth.send_raw_transaction,
+                signed_tx.raw_transaction
+            )
+        except Exception as e:
+            if journal_seq is not None:
+                await asyncio.to_thread(self.journal.mark_failed, journal_seq, str(e))
+            raise
+        tx_hash_hex = tx_hash.hex()
+        if journal_seq is not None:
+            await asyncio.to_thread(self.journal.mark_broadcast, journal_seq, tx_hash_hex)
+        return tx_hash_hex, journal_seq
+    
+    def _track(
+        self,
+        tx_hash: str,
+        tx_params: Dict,
+        label: str = "",
+        journal_seq: Optional[int] = None,
+        submitted_at: Optional[float] = None
+    ) -> PendingTransaction:
+        pending_tx = PendingTransaction(
+            tx_hash=tx_hash,
+            nonce=tx_params['nonce'],
+            submitted_at=submitted_at or time.time(),
+            gas_price=tx_params.get('maxFeePerGas', tx_params.get('gasPrice')),
+            tx_params=tx_params,
+            max_priority_fee=tx_params.get('maxPriorityFeePerGas'),
+            label=label,
+            journal_seq=journal_seq
+        )
+        self._pending_transactions[tx_hash] = pending_tx
+        return pending_tx
+    
+    def _find_pending(self, tx_hash: str) -> Optional[PendingTransaction]:
+        """Pending entry for `tx_hash` or for the transaction that replaced it."""
+        if tx_hash in self._pending_transactions:
+            return self._pending_transactions[tx_hash]
+        for pending_tx in self._pending_transactions.values():
+            if tx_hash in pending_tx.replaced_hashes:
+                return pending_tx
+        return None
+    
+    async def _find_receipt(self, tx_hashes: List[str]) -> Tuple[Optional[str], Optional[TxReceipt]]:
+        """First mined transaction among `tx_hashes` (a nonce's replacement chain)."""
+        for tx_hash in tx_hashes:
+            try:
+                receipt = await asyncio.to_thread(
+                    self.web3.eth.get_transaction_receipt,
+                    tx_hash
+                )
+            except Exception as e:
+                if "not found" not in str(e).lower() and len(tx_hashes) == 1:
+                    raise
+                continue
+            if receipt is not None:
+                return tx_hash, receipt
+        return None, None
+    
+    async def _check_mined(self, pending_tx: PendingTransaction) -> Optional[str]:
+        """Hash of the mined transaction at this nonce, confirming it; None if none mined."""
+        try:
+            mined_hash, receipt = await self._find_receipt(pending_tx.all_hashes)
+        except Exception:
+            return None  # Transaction not mined yet
+        if receipt is None:
+            return None
+        logger.info(f"Transaction already mined: {mined_hash}")
+        await self._on_transaction_confirmed(mined_hash, receipt)
+        return mined_hash
+    
+    @staticmethod
+    def _describe_fee_params(tx_params: Dict) -> str:
+        if 'maxFeePerGas' in tx_params:
+            return (f"max_fee={tx_params['maxFeePerGas'] / GWEI:.1f} gwei, "
+                    f"priority_fee={tx_params['maxPriorityFeePerGas'] / GWEI:.1f} gwei")
+        return f"gas_price={tx_params.get('gasPrice')}"
+    
+    def _describe_fees(self, pending_tx: PendingTransaction) -> str:
+        return self._describe_fee_params(pending_tx.tx_params)
     
     async def _on_transaction_confirmed(
         self,
@@ -404,18 +699,33 @@ class TransactionManager:
         Validates Requirement 18.5: Update nonce tracker when transactions confirm
         
         Args:
-            tx_hash: Confirmed transaction hash
+            tx_hash: Confirmed transaction hash (may be a replaced one)
             receipt: Transaction receipt
         """
-        if tx_hash in self._pending_transactions:
-            pending_tx = self._pending_transactions[tx_hash]
+        pending_tx = self._find_pending(tx_hash)
+        if pending_tx is not None:
+            if self.journal is not None:
+                if receipt['status'] != 1:
+                    status = REVERTED
+                elif pending_tx.is_cancellation and tx_hash == pending_tx.tx_hash:
+                    status = CANCELLED
+                else:
+                    status = CONFIRMED
+                try:
+                    await asyncio.to_thread(
+                        self.journal.resolve_nonce,
+                        self.wallet.address, pending_tx.nonce, tx_hash, status, receipt.get('blockNumber')
+                    )
+                except Exception as e:
+                    logger.error(f"Failed to journal confirmation of {tx_hash}: {e}")
             
             # Remove from pending
-            del self._pending_transactions[tx_hash]
+            del self._pending_transactions[pending_tx.tx_hash]
             
             # Release nonce
             async with self._nonce_lock:
                 self._pending_nonces.discard(pending_tx.nonce)
+            self.escalator.reset_escalation(f"nonce:{pending_tx.nonce}")
             
             logger.debug(
                 f"Transaction confirmed and removed from pending: "
@@ -450,19 +760,10 @@ class TransactionManager:
             int: Number of transactions cleaned up
         """
         cleaned = 0
-        tx_hashes = list(self._pending_transactions.keys())
         
-        for tx_hash in tx_hashes:
-            try:
-                receipt = await asyncio.to_thread(
-                    self.web3.eth.get_transaction_receipt,
-                    tx_hash
-                )
-                if receipt is not None:
-                    await self._on_transaction_confirmed(tx_hash, receipt)
-                    cleaned += 1
-            except Exception:
-                pass  # Transaction not found or error
+        for pending_tx in list(self._pending_transactions.values()):
+            if await self._check_mined(pending_tx) is not None:
+                cleaned += 1
         
         if cleaned > 0:
             logger.info(f"Cleaned up {cleaned} confirmed transactions")
@@ -0,0 +1,303 @@
+"""
+Transaction Journal for Polymarket Arbitrage Bot.
+
+Write-ahead SQLite journal of every transaction the bot signs. An entry is
+written before the transaction is broadcast and updated when it is sent,
+replaced, mined or dropped, so a crash or restart never loses track of an
+in-flight merge, redemption or approval. On startup TransactionManager
+reconciles the open entries against the chain and resumes waiting for (or
+replacing) the ones still pending.
+
+Validates Requirements:
+- Persistent journal of submitted transactions and their nonces
+- Startup reconciliation against chain state
+- Resume confirmation waiting / replacement after a restart
+"""
+
+import json
+import logging
+import sqlite3
+import time
+from contextlib import contextmanager
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Union
+
+logger = logging.getLogger(__name__)
+
+# Entry statuses
+SIGNED = "signed"  # Written before broadcast; may or may not have reached the node
+PENDING = "pending"  # Broadcast, not mined
+REPLACED = "replaced"  # Superseded by a later entry at the same nonce
+CONFIRMED = "confirmed"
+REVERTED = "reverted"
+CANCELLED = "cancelled"  # Cancellation self-send was mined
+FAILED = "failed"  # Broadcast rejected
+DROPPED = "dropped"  # Nonce was used by a transaction not in the journal
+
+OPEN_STATUSES = (SIGNED, PENDING)
+
+
+@dataclass
+class JournalEntry:
+    """One signed transaction."""
+    seq: int
+    sender: str
+    nonce: int
+    status: str
+    tx_params: Dict[str, Any]
+    raw_transaction: str  # Hex with 0x prefix
+    tx_hash: Optional[str] = None  # Known once broadcast
+    label: str = ""  # What the transaction does, e.g. "merge", "redeem"
+    is_cancellation: bool = False
+    replaces: Optional[int] = None  # seq of the entry this one replaced
+    created_at: float = 0.0
+    updated_at: float = 0.0
+    block_number: Optional[int] = None
+    error: Optional[str] = None
+
+
+def _to_hex(value: Union[bytes, str]) -> str:
+    if isinstance(value, str):
+        return value if value.startswith("0x") else "0x" + value
+    return "0x" + bytes(value).hex()
+
+
+def _json_default(value: Any) -> Any:
+    if isinstance(value, (bytes, bytearray)):
+        return _to_hex(value)
+    raise TypeError(f"Cannot store {type(value).__name__} in the transaction journal")
+
+
+class TransactionJournal:
+    """
+    SQLite write-ahead journal of signed transactions.
+
+    Features:
+    - Entry per signed transaction (raw bytes kept for rebroadcast)
+    - Replacement chains per nonce (replace-by-fee, cancellation)
+    - Open nonces per sender for startup reconciliation
+    """
+
+    def __init__(self, db_path: str = "data/tx_journal.db"):
+        """
+        Initialize the journal.
+
+        Args:
+            db_path: Path to the SQLite database file
+        """
+        self.db_path = db_path
+        Path(db_path).parent.mkdir(parents=True, exist_ok=True)
+        self._init_schema()
+        logger.info(f"📓 Transaction journal initialized: {db_path}")
+
+    @contextmanager
+    def _get_connection(self):
+        """Context manager for database connections."""
+        conn = sqlite3.connect(self.db_path)
+        conn.row_factory = sqlite3.Row
+        try:
+            yield conn
+            conn.commit()
+        except Exception:
+            conn.rollback()
+            raise
+        finally:
+            conn.close()
+
+    def _init_schema(self) -> None:
+        """Create the journal table."""
+        with self._get_connection() as conn:
+            conn.execute("""
+                CREATE TABLE IF NOT EXISTS tx_journal (
+                    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                    sender TEXT NOT NULL,
+                    nonce INTEGER NOT NULL,
+                    status TEXT NOT NULL,
+                    tx_params TEXT NOT NULL,
+                    raw_transaction TEXT NOT NULL,
+                    tx_hash TEXT,
+                    label TEXT NOT NULL,
+                    is_cancellation INTEGER NOT NULL,
+                    replaces INTEGER,
+                    created_at REAL NOT NULL,
+                    updated_at REAL NOT NULL,
+                    block_number INTEGER,
+                    error TEXT
+                )
+            """)
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_tx_journal_nonce ON tx_journal(sender, nonce)")
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_tx_journal_status ON tx_journal(status)")
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_tx_journal_hash ON tx_journal(tx_hash)")
+
+    # ============================================================
+    # RECORDING
+    # ============================================================
+
+    def record_signed(
+        self,
+        sender: str,
+        nonce: int,
+        tx_params: Dict[str, Any],
+        raw_transaction: Union[bytes, str],
+        label: str = "",
+        is_cancellation: bool = False,
+        replaces: Optional[int] = None
+    ) -> int:
+        """
+        Journal a signed transaction before it is broadcast.
+
+        Args:
+            sender: Signing address
+            nonce: Transaction nonce
+            tx_params: Parameters that were signed
+            raw_transaction: Signed transaction bytes
+            label: What the transaction does ("merge", "redeem", "USDC approval", ...)
+            is_cancellation: True for a cancellation self-send
+            replaces: seq of the entry this transaction replaces (same nonce)
+
+        Returns:
+            int: Entry seq
+        """
+        now = time.time()
+        with self._get_connection() as conn:
+            cursor = conn.execute("""
+                INSERT INTO tx_journal (
+                    sender, nonce, status, tx_params, raw_transaction, label,
+                    is_cancellation, replaces, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            """, (
+                sender.lower(),
+                int(nonce),
+                SIGNED,
+                json.dumps(tx_params, default=_json_default),
+                _to_hex(raw_transaction),
+                label,
+                int(is_cancellation),
+                replaces,
+                now,
+                now,
+            ))
+            return cursor.lastrowid
+
+    def mark_broadcast(self, seq: int, tx_hash: str) -> None:
+        """The node accepted the transaction; an entry it replaces is superseded."""
+        now = time.time()
+        with self._get_connection() as conn:
+            conn.execute(
+                "UPDATE tx_journal SET status = ?, tx_hash = ?, updated_at = ? WHERE seq = ?",
+                (PENDING, tx_hash, now, seq)
+            )
+            conn.execute("""
+                UPDATE tx_journal SET status = ?, updated_at = ?
+                WHERE seq = (SELECT replaces FROM tx_journal WHERE seq = ?) AND status IN (?, ?)
+            """, (REPLACED, now, seq, *OPEN_STATUSES))
+
+    def mark_failed(self, seq: int, error: str) -> None:
+        """The broadcast was rejected; the nonce was not used by this entry."""
+        with self._get_connection() as conn:
+            conn.execute(
+                "UPDATE tx_journal SET status = ?, error = ?, updated_at = ? WHERE seq = ?",
+                (FAILED, error[:500], time.time(), seq)
+            )
+
+    def resolve_nonce(
+        self,
+        sender: str,
+        nonce: int,
+        tx_hash: str,
+        status: str,
+        block_number: Optional[int] = None
+    ) -> None:
+        """
+        Record which transaction was mined at a nonce.
+
+        The mined entry gets `status` (confirmed, reverted or cancelled); every
+        other open entry at the nonce is marked replaced.
+        """
+        if status not in (CONFIRMED, REVERTED, CANCELLED):
+            raise ValueError(f"Not a mined status: {status}")
+        now = time.time()
+        with self._get_connection() as conn:
+            conn.execute("""
+                UPDATE tx_journal SET status = ?, block_number = ?, updated_at = ?
+                WHERE sender = ? AND nonce = ? AND tx_hash = ?
+            """, (status, block_number, now, sender.lower(), int(nonce), tx_hash))
+            conn.execute("""
+                UPDATE tx_journal SET status = ?, updated_at = ?
+                WHERE sender = ? AND nonce = ? AND status IN (?, ?) AND (tx_hash IS NULL OR tx_hash != ?)
+            """, (REPLACED, now, sender.lower(), int(nonce), *OPEN_STATUSES, tx_hash))
+
+    def mark_dropped(self, sender: str, nonce: int, reason: str) -> None:
+        """The nonce was consumed by a transaction that is not in the journal."""
+        with self._get_connection() as conn:
+            conn.execute("""
+                UPDATE tx_journal SET status = ?, error = ?, updated_at = ?
+                WHERE sender = ? AND nonce = ? AND status IN (?, ?, ?)
+            """, (DROPPED, reason[:500], time.time(), sender.lower(), int(nonce), *OPEN_STATUSES, REPLACED))
+
+    # ============================================================
+    # QUERIES
+    # ============================================================
+
+    def open_nonces(self, sender: str) -> Dict[int, List[JournalEntry]]:
+        """
+        Unresolved nonces of `sender` with their replacement chains.
+
+        Returns:
+            Dict of nonce -> entries at that nonce, oldest first (the last one
+            is the newest replacement)
+        """
+        with self._get_connection() as conn:
+            rows = conn.execute("""
+                SELECT * FROM tx_journal
+                WHERE sender = ? AND nonce IN (
+                    SELECT nonce FROM tx_journal WHERE sender = ? AND status IN (?, ?)
+                ) AND status IN (?, ?, ?)
+                ORDER BY nonce, seq
+            """, (sender.lower(), sender.lower(), *OPEN_STATUSES, *OPEN_STATUSES, REPLACED)).fetchall()
+
+        chains: Dict[int, List[JournalEntry]] = {}
+        for row in rows:
+            entry = self._row_to_entry(row)
+            chains.setdefault(entry.nonce, []).append(entry)
+        return chains
+
+    def get_entries(
+        self,
+        sender: Optional[str] = None,
+        status: Optional[str] = None,
+        limit: int = 100
+    ) -> List[JournalEntry]:
+        """Most recent entries, optionally filtered by sender and status."""
+        query = "SELECT * FROM tx_journal WHERE 1=1"
+        params: List[Any] = []
+        if sender:
+            query += " AND sender = ?"
+            params.append(sender.lower())
+        if status:
+            query += " AND status = ?"
+            params.append(status)
+        query += " ORDER BY seq DESC LIMIT ?"
+        params.append(limit)
+        with self._get_connection() as conn:
+            return [self._row_to_entry(row) for row in conn.execute(query, params).fetchall()]
+
+    @staticmethod
+    def _row_to_entry(row: sqlite3.Row) -> JournalEntry:
+        return JournalEntry(
+            seq=row["seq"],
+            sender=row["sender"],
+            nonce=row["nonce"],
+            status=row["status"],
+            tx_params=json.loads(row["tx_params"]),
+            raw_transaction=row["raw_transaction"],
+            tx_hash=row["tx_hash"],
+            label=row["label"],
+            is_cancellation=bool(row["is_cancellation"]),
+            replaces=row["replaces"],
+            created_at=row["created_at"],
+            updated_at=row["updated_at"],
+            block_number=row["block_number"],
+            error=row["error"],
+        )
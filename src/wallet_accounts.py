@@ -0,0 +1,357 @@
+"""
+Wallet sub-accounts for Polymarket Arbitrage Bot.
+
+Runs several strategy books from separate (proxy) wallets in one process.
+The primary wallet is the one configured by the top-level wallet settings;
+every entry of `Config.wallets` adds a sub-account with its own signer,
+CLOB client, transaction manager, balances, risk limits and strategies.
+Trades are tagged with the wallet name, so trade history, statistics and
+reports can be split per wallet and aggregated.
+
+Validates Requirements:
+- Several wallets with separate risk limits and balances in one process
+- Per-wallet trade tags in trade history
+- Aggregate view over all wallets
+"""
+
+import asyncio
+import logging
+import os
+from dataclasses import dataclass, field
+from decimal import Decimal
+from pathlib import Path
+from typing import Any, Dict, Iterator, List, Optional, Tuple
+
+from web3 import Web3
+
+from src.fund_manager import FundManager
+from src.order_manager import OrderManager
+from src.portfolio_risk_manager import PortfolioRiskManager
+from src.transaction_manager import TransactionManager
+
+logger = logging.getLogger(__name__)
+
+PRIMARY_WALLET = "primary"
+
+SIGNATURE_TYPE_NAMES = {0: "EOA", 1: "POLY_PROXY", 2: "GNOSIS_SAFE"}
+
+
+@dataclass
+class WalletAccountConfig:
+    """Settings of one sub-account (an entry of Config.wallets)."""
+    name: str
+    wallet_address: str  # Signing EOA
+    private_key_env: Optional[str] = None  # Environment variable holding the key (local signer)
+    signer_socket_path: Optional[str] = None  # Signing daemon holding the key (remote signer)
+    funder_address: Optional[str] = None  # Proxy wallet holding the funds (default: the EOA)
+    signature_type: int = 0  # 0 EOA, 1 POLY_PROXY, 2 GNOSIS_SAFE
+    strategies: List[str] = field(default_factory=list)
+    capital: Optional[Decimal] = None  # Risk capital (default: the wallet's balance)
+
+    # Risk limits (defaults match the primary wallet)
+    max_portfolio_heat: Decimal = Decimal("1.50")
+    max_daily_drawdown: Decimal = Decimal("0.15")
+    max_position_size_pct: Decimal = Decimal("0.80")
+    consecutive_loss_limit: int = 5
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any], default_strategies: List[str]) -> "WalletAccountConfig":
+        """
+        Parse a validated Config.wallets entry.
+
+        Args:
+            data: Wallet entry
+            default_strategies: Strategies used when the entry lists none
+        """
+        funder = data.get("funder_address")
+        wallet = cls(
+            name=data["name"],
+            wallet_address=Web3.to_checksum_address(data["wallet_address"]),
+            private_key_env=data.get("private_key_env"),
+            signer_socket_path=data.get("signer_socket_path"),
+            funder_address=Web3.to_checksum_address(funder) if funder else None,
+            signature_type=int(data.get("signature_type", 2 if funder else 0)),
+            strategies=list(data.get("strategies") or default_strategies),
+            capital=Decimal(str(data["capital"])) if data.get("capital") is not None else None,
+            consecutive_loss_limit=int(data.get("consecutive_loss_limit", 5)),
+        )
+        for key in ("max_portfolio_heat", "max_daily_drawdown", "max_position_size_pct"):
+            if key in data:
+                setattr(wallet, key, Decimal(str(data[key])))
+        return wallet
+
+    def create_risk_manager(self, capital: Decimal) -> PortfolioRiskManager:
+        """Risk manager with this wallet's limits."""
+        return PortfolioRiskManager(
+            initial_capital=capital,
+            max_portfolio_heat=self.max_portfolio_heat,
+            max_daily_drawdown=self.max_daily_drawdown,
+            max_position_size_pct=self.max_position_size_pct,
+            consecutive_loss_limit=self.consecutive_loss_limit
+        )
+
+
+def book_label(wallet_name: str, strategy_name: str) -> str:
+    """Strategy name, qualified with the wallet for sub-accounts (e.g. market_making@book_b)."""
+    return strategy_name if wallet_name == PRIMARY_WALLET else f"{strategy_name}@{wallet_name}"
+
+
+def wallet_file(path: Optional[str], wallet_name: str) -> Optional[str]:
+    """Per-wallet copy of a data file (data/x.db -> data/x_<wallet>.db); the primary wallet keeps the path."""
+    if not path or wallet_name == PRIMARY_WALLET:
+        return path
+    file = Path(path)
+    return str(file.with_name(f"{file.stem}_{wallet_name}{file.suffix}"))
+
+
+def load_wallet_configs(config: Any) -> List[WalletAccountConfig]:
+    """Sub-account settings from Config.wallets, in configured order."""
+    return [WalletAccountConfig.from_dict(entry, config.enabled_strategies) for entry in config.wallets]
+
+
+@dataclass
+class WalletAccount:
+    """One wallet's trading book: signer, clients, balances, risk and strategies."""
+    name: str
+    account: Any  # Signer: eth_account LocalAccount, LocalSigner or RemoteSigner
+    signature_type: int
+    funder_address: str  # Address holding the funds
+    clob_client: Any
+    transaction_manager: Any
+    order_manager: Any
+    fund_manager: Any
+    risk_manager: Any = None
+    strategies: List[Any] = field(default_factory=list)
+    redemption_service: Any = None
+    ledger: Any = None  # PositionLedger of this wallet's fills (live trading only)
+    ledger_reconciler: Any = None
+
+    # Last balance check
+    eoa_balance: Decimal = Decimal("0")
+    proxy_balance: Decimal = Decimal("0")
+
+    @property
+    def address(self) -> str:
+        return self.account.address
+
+    @property
+    def total_balance(self) -> Decimal:
+        return self.eoa_balance + self.proxy_balance
+
+    async def refresh_balance(self) -> Decimal:
+        """Fetch EOA and Polymarket balances; returns their total."""
+        self.eoa_balance, self.proxy_balance = await self.fund_manager.check_balance()
+        return self.total_balance
+
+    def trading_allowed(self) -> Tuple[bool, str]:
+        """Whether the wallet's risk limits (halt, drawdown, losses, capital) allow new trades."""
+        if self.risk_manager is None:
+            return True, ""
+        metrics = self.risk_manager.check_can_trade(Decimal("0"), "")
+        return metrics.can_trade, metrics.reason
+
+    def snapshot(self) -> Dict[str, Any]:
+        """Balances, risk state and strategies of the wallet (dashboard row)."""
+        state = self.risk_manager.get_portfolio_state() if self.risk_manager is not None else {}
+        return {
+            "name": self.name,
+            "address": self.address,
+            "funder_address": self.funder_address,
+            "eoa_balance": self.eoa_balance,
+            "proxy_balance": self.proxy_balance,
+            "total_balance": self.total_balance,
+            "capital": Decimal(str(state.get("total_balance", 0))),
+            "daily_pnl": Decimal(str(state.get("daily_pnl", 0))),
+            "trades_today": state.get("trades_today", 0),
+            "open_positions": len(state.get("open_positions", [])),
+            "halted": bool(getattr(self.risk_manager, "_trading_halted", False)),
+            "strategies": [strategy.name for strategy in self.strategies],
+        }
+
+
+class WalletRegistry:
+    """
+    Wallets trading in this process, primary first.
+
+    Features:
+    - Lookup by name, iteration in configured order
+    - Balance refresh across all wallets
+    - Per-wallet snapshots and their aggregate
+    - Routing of trade results to the owning wallet's risk manager
+    """
+
+    def __init__(self):
+        self._accounts: Dict[str, WalletAccount] = {}
+
+    def add(self, account: WalletAccount) -> None:
+        if account.name in self._accounts:
+            raise ValueError(f"Wallet registered twice: {account.name}")
+        self._accounts[account.name] = account
+
+    def get(self, name: str) -> WalletAccount:
+        if name not in self._accounts:
+            raise KeyError(f"Unknown wallet '{name}' (available: {', '.join(self._accounts)})")
+        return self._accounts[name]
+
+    @property
+    def primary(self) -> WalletAccount:
+        return self._accounts[PRIMARY_WALLET]
+
+    @property
+    def names(self) -> List[str]:
+        return list(self._accounts)
+
+    def __iter__(self) -> Iterator[WalletAccount]:
+        return iter(list(self._accounts.values()))
+
+    def __len__(self) -> int:
+        return len(self._accounts)
+
+    def all_strategies(self) -> List[Any]:
+        """Strategies of every wallet."""
+        return [strategy for account in self for strategy in account.strategies]
+
+    async def refresh_balances(self) -> None:
+        """Refresh every wallet's balances; a failed check keeps the last values."""
+        accounts = list(self)
+        results = await asyncio.gather(
+            *(account.refresh_balance() for account in accounts),
+            return_exceptions=True
+        )
+        for account, result in zip(accounts, results):
+            if isinstance(result, Exception):
+                logger.error(f"Balance check failed for wallet {account.name}: {result}")
+
+    def record_trade(self, result: Any) -> None:
+        """Book an executed trade's P&L on the risk manager of the wallet that placed it."""
+        account = self._accounts.get(result.wallet)
+        if account is None or account.risk_manager is None or result.status != "success":
+            return
+        account.risk_manager.record_trade_result(
+            result.net_profit,
+            result.opportunity.market_id,
+            result.opportunity.position_size
+        )
+
+    def snapshot(self) -> List[Dict[str, Any]]:
+        """One snapshot per wallet, primary first."""
+        return [account.snapshot() for account in self]
+
+    def aggregate(self) -> Dict[str, Any]:
+        """Totals over all wallets."""
+        rows = self.snapshot()
+        zero = Decimal("0")
+        return {
+            "wallets": len(rows),
+            "eoa_balance": sum((row["eoa_balance"] for row in rows), zero),
+            "proxy_balance": sum((row["proxy_balance"] for row in rows), zero),
+            "total_balance": sum((row["total_balance"] for row in rows), zero),
+            "capital": sum((row["capital"] for row in rows), zero),
+            "daily_pnl": sum((row["daily_pnl"] for row in rows), zero),
+            "trades_today": sum(row["trades_today"] for row in rows),
+            "open_positions": sum(row["open_positions"] for row in rows),
+            "halted": [row["name"] for row in rows if row["halted"]],
+        }
+
+
+# ============================================================
+# SUB-ACCOUNT CONSTRUCTION
+# ============================================================
+
+def create_wallet_signer(wallet: WalletAccountConfig, signer_backend: str) -> Any:
+    """
+    Signer for a sub-account, checked against its configured address.
+
+    Raises:
+        ValueError: If the key is missing or belongs to another address
+    """
+    from src.signer import create_signer
+
+    if signer_backend == "remote":
+        signer = create_signer("remote", socket_path=wallet.signer_socket_path)
+    else:
+        private_key = os.getenv(wallet.private_key_env or "", "")
+        if not private_key:
+            raise ValueError(f"Wallet {wallet.name}: environment variable {wallet.private_key_env} is not set")
+        signer = create_signer("local", private_key=private_key)
+
+    if signer.address.lower() != wallet.wallet_address.lower():
+        raise ValueError(
+            f"SECURITY ERROR: Key of wallet {wallet.name} does not match its wallet address. "
+            f"Expected: {wallet.wallet_address}, Got: {signer.address}"
+        )
+    return signer
+
+
+def open_wallet_account(
+    wallet: WalletAccountConfig,
+    config: Any,
+    web3: Web3,
+    fee_estimator: Optional[Any] = None,
+    journal: Optional[Any] = None
+) -> WalletAccount:
+    """
+    Build a sub-account's signer, CLOB client, transaction, order and fund managers.
+
+    The risk manager and strategies are attached by the caller once the
+    wallet's capital is known.
+
+    Args:
+        wallet: Sub-account settings
+        config: System configuration (endpoints, contracts, fee settings)
+        web3: Web3 instance shared by all wallets
+        fee_estimator: Shared EIP-1559 fee estimator
+        journal: Shared transaction journal (entries are keyed by sender)
+    """
+    from py_clob_client.client import ClobClient
+    from src.signer import attach_signer, derive_api_creds
+
+    signer = create_wallet_signer(wallet, config.signer_backend)
+    funder = wallet.funder_address or signer.address
+    logger.info(
+        f"👛 Wallet {wallet.name}: {signer.address} "
+        f"({SIGNATURE_TYPE_NAMES.get(wallet.signature_type, wallet.signature_type)}, funds in {funder})"
+    )
+
+    # Orders and L1 auth are signed through the Signer, whichever backend holds the key
+    clob_client = ClobClient(
+        host=config.polymarket_api_url,
+        chain_id=config.chain_id,
+        signature_type=wallet.signature_type,
+        funder=funder
+    )
+    attach_signer(clob_client, signer, config.chain_id, wallet.signature_type, funder)
+    clob_client.set_api_creds(derive_api_creds(config.polymarket_api_url, signer, config.chain_id))
+
+    transaction_manager = TransactionManager(
+        web3,
+        signer,
+        fee_estimator=fee_estimator,
+        max_replacements=config.max_fee_replacements,
+        auto_replace=config.auto_replace_stuck_tx,
+        journal=journal
+    )
+    order_manager = OrderManager(clob_client, transaction_manager, dry_run=config.dry_run)
+    fund_manager = FundManager(
+        web3=web3,
+        wallet=signer,
+        usdc_address=config.usdc_address,
+        ctf_exchange_address=config.ctf_exchange_address,
+        min_balance=config.min_balance,
+        target_balance=config.target_balance,
+        withdraw_limit=config.withdraw_limit,
+        dry_run=config.dry_run,
+        oneinch_api_key=None,
+        clob_client=clob_client
+    )
+
+    return WalletAccount(
+        name=wallet.name,
+        account=signer,
+        signature_type=wallet.signature_type,
+        funder_address=funder,
+        clob_client=clob_client,
+        transaction_manager=transaction_manager,
+        order_manager=order_manager,
+        fund_manager=fund_manager
+    )
@@ -0,0 +1,519 @@
+"""
+Web Dashboard and Control API for Polymarket Arbitrage Bot.
+
+Serves the StatusDashboard state, open positions, recent trades and risk
+state as JSON and as a small auto-refreshing HTML page, from an aiohttp
+server running in the orchestrator's event loop. The terminal dashboard
+redraws the console, which nobody watches on a headless host.
+
+Control endpoints require the bearer token from `dashboard_api_token`:
+- Pause / resume trading
+- Flatten all positions
+- Enable / disable a strategy book
+- Debug mode (verbose logging, debug log tail in the status view)
+
+Validates Requirements:
+- Dashboard state, positions, recent trades and risk state over HTTP (JSON + HTML)
+- Authenticated control endpoints: pause/resume, flatten, strategy toggles, debug mode
+"""
+
+import hmac
+import logging
+from dataclasses import fields, is_dataclass
+from datetime import date, datetime
+from decimal import Decimal
+from enum import Enum
+from typing import Any, Awaitable, Callable, Dict, List, Optional
+
+from src.status_dashboard import DashboardState
+from src.wallet_accounts import book_label
+
+logger = logging.getLogger(__name__)
+
+
+def to_jsonable(value: Any) -> Any:
+    """Convert dashboard values to JSON types (Decimals as strings, datetimes as ISO 8601)."""
+    if value is None or isinstance(value, (str, bool, int, float)):
+        return value
+    if isinstance(value, Decimal):
+        return str(value)
+    if isinstance(value, (datetime, date)):
+        return value.isoformat()
+    if isinstance(value, Enum):
+        return to_jsonable(value.value)
+    if is_dataclass(value) and not isinstance(value, type):
+        return {f.name: to_jsonable(getattr(value, f.name)) for f in fields(value)}
+    if isinstance(value, dict):
+        return {str(key): to_jsonable(item) for key, item in value.items()}
+    if isinstance(value, (list, tuple, set, frozenset)):
+        return [to_jsonable(item) for item in value]
+    return str(value)
+
+
+def is_authorized(authorization: Optional[str], api_token: Optional[str]) -> bool:
+    """Whether an Authorization header carries the API token (constant-time compare)."""
+    if not api_token or not authorization:
+        return False
+    scheme, _, token = authorization.partition(" ")
+    return scheme.lower() == "bearer" and hmac.compare_digest(token.strip().encode(), api_token.encode())
+
+
+class _DashboardLogHandler(logging.Handler):
+    """Copies log records into the dashboard's debug log tail."""
+
+    def __init__(self, dashboard):
+        super().__init__(level=logging.DEBUG)
+        self.dashboard = dashboard
+
+    def emit(self, record: logging.LogRecord) -> None:
+        try:
+            self.dashboard.add_debug_log(f"{record.levelname} {record.name}: {record.getMessage()}")
+        except Exception:
+            self.handleError(record)
+
+
+class DashboardController:
+    """
+    Views of and controls over a running MainOrchestrator.
+
+    Trading state stays on the orchestrator (`trading_paused`, `pause_reason`,
+    `disabled_strategies`), which its loop checks every cycle; the controller
+    only reads and flips it.
+
+    Features:
+    - JSON-ready views: status, positions, recent trades, risk, strategy books
+    - Pause / resume, flatten, strategy toggles, debug mode
+    - Every control action logged with its source
+    """
+
+    def __init__(self, orchestrator: Any):
+        """
+        Initialize the controller.
+
+        Args:
+            orchestrator: MainOrchestrator (dashboard, wallets, trade_history,
+                circuit_breaker, config and the control flags)
+        """
+        self.orchestrator = orchestrator
+        self._debug_handler: Optional[_DashboardLogHandler] = None
+        self._log_levels: Dict[Any, int] = {}
+
+    # ============================================================
+    # VIEWS
+    # ============================================================
+
+    def controls(self) -> Dict[str, Any]:
+        """Current operator control state."""
+        orchestrator = self.orchestrator
+        return {
+            "trading_paused": orchestrator.trading_paused,
+            "pause_reason": orchestrator.pause_reason,
+            "disabled_strategies": sorted(orchestrator.disabled_strategies),
+            "debug_mode": orchestrator.dashboard.state.debug_mode,
+            "dry_run": orchestrator.config.dry_run,
+        }
+
+    def status(self) -> Dict[str, Any]:
+        """DashboardState (trades are served by trades()) and the control state."""
+        state = self.orchestrator.dashboard.state
+        return {
+            "dashboard": to_jsonable({
+                f.name: getattr(state, f.name) for f in fields(DashboardState) if f.name != "recent_trades"
+            }),
+            "controls": self.controls(),
+        }
+
+    def strategies(self) -> List[Dict[str, Any]]:
+        """Strategy books (strategy per wallet) and whether they trade."""
+        disabled = self.orchestrator.disabled_strategies
+        return [
+            {
+                "name": book_label(wallet.name, strategy.name),
+                "strategy": strategy.name,
+                "wallet": wallet.name,
+                "enabled": book_label(wallet.name, strategy.name) not in disabled,
+            }
+            for wallet in self.orchestrator.wallets
+            for strategy in wallet.strategies
+        ]
+
+    def positions(self) -> List[Dict[str, Any]]:
+        """Open positions and resting orders of every strategy book."""
+        positions = []
+        for wallet in self.orchestrator.wallets:
+            for strategy in wallet.strategies:
+                label = book_label(wallet.name, strategy.name)
+                try:
+                    held = strategy.open_positions()
+                except Exception as e:
+                    logger.error(f"Failed to read positions of {label}: {e}")
+                    continue
+                positions.extend({**position, "wallet": wallet.name, "book": label} for position in held)
+        return to_jsonable(positions)
+
+    def trades(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """Most recent trades from the trade history."""
+        return to_jsonable(self.orchestrator.trade_history.get_recent_trades(limit=limit))
+
+    def risk(self) -> Dict[str, Any]:
+        """Per-wallet risk state, their aggregate, circuit breaker and trading halts."""
+        orchestrator = self.orchestrator
+        breaker = orchestrator.circuit_breaker
+        return to_jsonable({
+            "wallets": orchestrator.wallets.snapshot(),
+            "aggregate": orchestrator.wallets.aggregate(),
+            "circuit_breaker": {
+                "open": breaker.is_open,
+                "consecutive_failures": breaker.consecutive_failures,
+                "threshold": breaker.failure_threshold,
+            },
+            "gas_price_halted": orchestrator.gas_price_halted,
+            **self.controls(),
+        })
+
+    # ============================================================
+    # CONTROLS
+    # ============================================================
+
+    def pause(self, reason: str = "", source: str = "") -> Dict[str, Any]:
+        """Stop scanning for and executing trades until resume()."""
+        self.orchestrator.trading_paused = True
+        self.orchestrator.pause_reason = reason or "paused by operator"
+        logger.warning(f"⏸️ Trading paused ({self.orchestrator.pause_reason}){self._from(source)}")
+        return self.controls()
+
+    def resume(self, source: str = "") -> Dict[str, Any]:
+        """Resume trading after pause() or flatten()."""
+        self.orchestrator.trading_paused = False
+        self.orchestrator.pause_reason = ""
+        logger.warning(f"▶️ Trading resumed{self._from(source)}")
+        return self.controls()
+
+    async def flatten(self, source: str = "") -> Dict[str, Any]:
+        """
+        Pause trading, then close every strategy's positions and cancel all resting orders.
+
+        Trading stays paused so the next cycle does not re-enter; resume() ends it.
+        Positions a book could not close (no bid, rejected sell) are reported
+        under "remaining".
+        """
+        self.pause("flatten", source=source)
+        closed: Dict[str, int] = {}
+        remaining: Dict[str, int] = {}
+        cancelled: Dict[str, int] = {}
+        errors: Dict[str, str] = {}
+
+        for wallet in self.orchestrator.wallets:
+            for strategy in wallet.strategies:
+                label = book_label(wallet.name, strategy.name)
+                try:
+                    closed[label] = await strategy.flatten()
+                except Exception as e:
+                    logger.error(f"Failed to flatten {label}: {e}")
+                    errors[label] = str(e)
+                try:
+                    left = [p for p in strategy.open_positions() if p.get("status") != "resting"]
+                except Exception as e:
+                    logger.error(f"Failed to read positions of {label}: {e}")
+                    continue
+                if left:
+                    remaining[label] = len(left)
+            try:
+                cancelled[wallet.name] = await wallet.order_manager.cancel_resting_orders()
+            except Exception as e:
+                logger.error(f"Failed to cancel resting orders of wallet {wallet.name}: {e}")
+                errors[wallet.name] = str(e)
+
+        logger.warning(
+            f"🧯 Flattened: {sum(closed.values())} position(s) closed, "
+            f"{sum(remaining.values())} still open, "
+            f"{sum(cancelled.values())} order(s) cancelled" + (f", {len(errors)} error(s)" if errors else "")
+        )
+        return {
+            "closed": closed,
+            "remaining": remaining,
+            "orders_cancelled": cancelled,
+            "errors": errors,
+            **self.controls(),
+        }
+
+    def set_strategy_enabled(self, name: str, enabled: bool, source: str = "") -> Dict[str, Any]:
+        """
+        Enable or disable a strategy book (strategy, or strategy@wallet for sub-accounts).
+
+        A disabled book is skipped by the scan cycle; its open positions are
+        left alone (flatten closes them).
+
+        Raises:
+            KeyError: If no wallet runs the strategy book
+        """
+        names = [book["name"] for book in self.strategies()]
+        if name not in names:
+            raise KeyError(f"Unknown strategy '{name}' (available: {', '.join(names)})")
+        if enabled:
+            self.orchestrator.disabled_strategies.discard(name)
+        else:
+            self.orchestrator.disabled_strategies.add(name)
+        logger.warning(f"🎛️ Strategy {name} {'enabled' if enabled else 'disabled'}{self._from(source)}")
+        return self.controls()
+
+    def set_debug_mode(self, enabled: bool, source: str = "") -> Dict[str, Any]:
+        """
+        Switch verbose logging and the dashboard's debug log tail on or off.
+
+        Enabling lowers the root logger and its handlers to DEBUG; disabling
+        restores their previous levels.
+        """
+        root = logging.getLogger()
+        dashboard = self.orchestrator.dashboard
+        if enabled and self._debug_handler is None:
+            self._log_levels = {root: root.level, **{handler: handler.level for handler in root.handlers}}
+            for target in self._log_levels:
+                target.setLevel(logging.DEBUG)
+            self._debug_handler = _DashboardLogHandler(dashboard)
+            root.addHandler(self._debug_handler)
+        elif not enabled and self._debug_handler is not None:
+            root.removeHandler(self._debug_handler)
+            self._debug_handler = None
+            for target, level in self._log_levels.items():
+                target.setLevel(level)
+            self._log_levels = {}
+        dashboard.state.debug_mode = enabled
+        logger.warning(f"🐞 Debug mode {'on' if enabled else 'off'}{self._from(source)}")
+        return self.controls()
+
+    @staticmethod
+    def _from(source: str) -> str:
+        return f" by {source}" if source else ""
+
+
+# ============================================================
+# HTTP SERVER
+# ============================================================
+
+def create_app(controller: DashboardController, api_token: Optional[str] = None):
+    """
+    aiohttp application serving the dashboard.
+
+    Routes:
+        GET  /                               HTML dashboard
+        GET  /api/status                     DashboardState and control state
+        GET  /api/positions                  Open positions and resting orders
+        GET  /api/trades?limit=50            Recent trades
+        GET  /api/risk                       Per-wallet risk, circuit breaker, halts
+        GET  /api/strategies                 Strategy books
+        POST /api/control/pause              {"reason": "..."}
+        POST /api/control/resume
+        POST /api/control/flatten
+        POST /api/control/strategies/{name}  {"enabled": true|false}
+        POST /api/control/debug              {"enabled": true|false}
+
+    With an API token, every /api route requires `Authorization: Bearer <token>`.
+    Without one, the views are open (the server binds to loopback) and the
+    control routes are disabled.
+    """
+    from aiohttp import web
+
+    def respond(data: Any, status: int = 200):
+        return web.json_response(to_jsonable(data), status=status)
+
+    def view(handler: Callable[[Any], Any]):
+        async def wrapped(request):
+            if api_token and not is_authorized(request.headers.get("Authorization"), api_token):
+                return respond({"error": "unauthorized"}, 401)
+            return respond(handler(request))
+        return wrapped
+
+    def control(handler: Callable[[Any, Dict[str, Any], str], Awaitable[Any]]):
+        async def wrapped(request):
+            source = request.remote or "unknown"
+            if not api_token:
+                return respond({"error": "control API disabled: dashboard_api_token is not set"}, 403)
+            if not is_authorized(request.headers.get("Authorization"), api_token):
+                logger.warning(f"🚫 Rejected control request {request.method} {request.path} from {source}")
+                return respond({"error": "unauthorized"}, 401)
+            try:
+                body = await request.json() if request.can_read_body else {}
+            except ValueError:
+                return respond({"error": "invalid JSON body"}, 400)
+            if not isinstance(body, dict):
+                return respond({"error": "JSON body must be an object"}, 400)
+            try:
+                return respond(await handler(request, body, source))
+            except KeyError as e:
+                return respond({"error": e.args[0] if e.args else str(e)}, 404)
+            except ValueError as e:
+                return respond({"error": str(e)}, 400)
+        return wrapped
+
+    def enabled_flag(body: Dict[str, Any]) -> bool:
+        enabled = body.get("enabled")
+        if not isinstance(enabled, bool):
+            raise ValueError("'enabled' must be true or false")
+        return enabled
+
+    async def index(request):
+        return web.Response(text=DASHBOARD_HTML, content_type="text/html")
+
+    def trades(request):
+        try:
+            limit = min(max(int(request.query.get("limit", "50")), 1), 500)
+        except ValueError:
+            limit = 50
+        return controller.trades(limit=limit)
+
+    async def pause(request, body, source):
+        return controller.pause(str(body.get("reason", "")), source=source)
+
+    async def resume(request, body, source):
+        return controller.resume(source=source)
+
+    async def flatten(request, body, source):
+        return await controller.flatten(source=source)
+
+    async def toggle_strategy(request, body, source):
+        return controller.set_strategy_enabled(request.match_info["name"], enabled_flag(body), source=source)
+
+    async def debug(request, body, source):
+        return controller.set_debug_mode(enabled_flag(body), source=source)
+
+    app = web.Application()
+    app.router.add_get("/", index)
+    app.router.add_get("/api/status", view(lambda request: controller.status()))
+    app.router.add_get("/api/positions", view(lambda request: controller.positions()))
+    app.router.add_get("/api/trades", view(trades))
+    app.router.add_get("/api/risk", view(lambda request: controller.risk()))
+    app.router.add_get("/api/strategies", view(lambda request: controller.strategies()))
+    app.router.add_post("/api/control/pause", control(pause))
+    app.router.add_post("/api/control/resume", control(resume))
+    app.router.add_post("/api/control/flatten", control(flatten))
+    app.router.add_post("/api/control/strategies/{name}", control(toggle_strategy))
+    app.router.add_post("/api/control/debug", control(debug))
+    return app
+
+
+class WebDashboardServer:
+    """Runs the dashboard application inside the orchestrator's event loop."""
+
+    def __init__(
+        self,
+        controller: DashboardController,
+        host: str = "127.0.0.1",
+        port: int = 8080,
+        api_token: Optional[str] = None
+    ):
+        self.controller = controller
+        self.host = host
+        self.port = port
+        self.api_token = api_token
+        self._runner = None
+
+    async def start(self) -> None:
+        from aiohttp import web
+
+        self._runner = web.AppRunner(create_app(self.controller, self.api_token), access_log=None)
+        await self._runner.setup()
+        await web.TCPSite(self._runner, self.host, self.port).start()
+        logger.info(
+            f"🌐 Web dashboard on http://{self.host}:{self.port}"
+            + ("" if self.api_token else " (control API disabled: dashboard_api_token is not set)")
+        )
+
+    async def stop(self) -> None:
+        if self._runner is not None:
+            await self._runner.cleanup()
+            self._runner = None
+
+
+DASHBOARD_HTML = """<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Polymarket Bot</title>
+<style>
+body { font-family: monospace; background: #111; color: #ddd; margin: 1.5em; }
+h1 { font-size: 1.2em; } h2 { font-size: 1em; color: #6cf; margin-top: 1.5em; }
+table { border-collapse: collapse; } td, th { padding: 2px 10px; text-align: left; border-bottom: 1px solid #333; }
+.bad { color: #f66; } .good { color: #6f6; }
+button { margin-right: 6px; } #error { color: #f66; }
+</style>
+</head>
+<body>
+<h1>Polymarket Bot <span id="state"></span></h1>
+<div>
+  Token <input id="token" type="password" size="30">
+  <button onclick="control('pause', {reason: 'paused from dashboard'})">Pause</button>
+  <button onclick="control('resume')">Resume</button>
+  <button onclick="confirm('Close all positions and pause trading?') && control('flatten')">Flatten all</button>
+  <button onclick="control('debug', {enabled: !debugMode})">Toggle debug</button>
+  <span id="error"></span>
+</div>
+<h2>Status</h2><table id="status"></table>
+<h2>Risk</h2><table id="risk"></table>
+<h2>Strategies</h2><table id="strategies"></table>
+<h2>Positions</h2><table id="positions"></table>
+<h2>Recent trades</h2><table id="trades"></table>
+<h2>Debug log</h2><pre id="debug"></pre>
+<script>
+let debugMode = false;
+const tokenInput = document.getElementById('token');
+tokenInput.value = sessionStorage.getItem('dashboardToken') || '';
+tokenInput.onchange = () => sessionStorage.setItem('dashboardToken', tokenInput.value);
+function headers() {
+  return tokenInput.value ? {'Authorization': 'Bearer ' + tokenInput.value, 'Content-Type': 'application/json'}
+                          : {'Content-Type': 'application/json'};
+}
+async function api(path, options) {
+  const response = await fetch(path, Object.assign({headers: headers()}, options || {}));
+  const data = await response.json();
+  if (!response.ok) throw new Error(data.error || response.status);
+  return data;
+}
+async function control(action, body) {
+  try { await api('/api/control/' + action, {method: 'POST', body: JSON.stringify(body || {})}); refresh(); }
+  catch (e) { document.getElementById('error').textContent = e.message; }
+}
+function esc(value) {
+  return String(value === null || value === undefined ? '' : value).replace(/[&<>"]/g, c => ({'&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;'})[c]);
+}
+function table(id, rows, columns) {
+  const head = '<tr>' + columns.map(c => '<th>' + esc(c) + '</th>').join('') + '</tr>';
+  document.getElementById(id).innerHTML = head + rows.map(
+    row => '<tr>' + columns.map(c => '<td>' + esc(row[c]) + '</td>').join('') + '</tr>').join('');
+}
+function pairs(id, object) {
+  document.getElementById(id).innerHTML = Object.entries(object).map(
+    ([k, v]) => '<tr><th>' + esc(k) + '</th><td>' + esc(typeof v === 'object' ? JSON.stringify(v) : v) + '</td></tr>').join('');
+}
+async function refresh() {
+  try {
+    const [status, risk, strategies, positions, trades] = await Promise.all(
+      ['status', 'risk', 'strategies', 'positions', 'trades?limit=20'].map(p => api('/api/' + p)));
+    const d = status.dashboard, c = status.controls;
+    debugMode = c.debug_mode;
+    document.getElementById('state').innerHTML = c.trading_paused
+      ? '<span class="bad">PAUSED (' + esc(c.pause_reason) + ')</span>' : '<span class="good">' + esc(d.status) + '</span>';
+    pairs('status', {mode: c.dry_run ? 'DRY_RUN' : 'LIVE', healthy: d.is_healthy, balance: d.total_balance,
+      trades: d.total_trades, win_rate: d.win_rate, net_profit: d.net_profit, scan_cycle: d.scan_cycle,
+      gas_gwei: d.gas_price_gwei, pending_tx: d.pending_tx_count, last_heartbeat: d.last_heartbeat});
+    pairs('risk', {circuit_breaker: risk.circuit_breaker, gas_price_halted: risk.gas_price_halted, aggregate: risk.aggregate});
+    table('strategies', strategies.map(s => Object.assign({}, s, {toggle:
+      s.enabled ? 'enabled' : 'disabled'})), ['name', 'wallet', 'toggle']);
+    document.querySelectorAll('#strategies tr').forEach((tr, i) => {
+      if (i === 0) return;
+      const s = strategies[i - 1];
+      tr.lastChild.innerHTML = '<button>' + (s.enabled ? 'Disable' : 'Enable') + '</button>';
+      tr.lastChild.firstChild.onclick = () => control('strategies/' + encodeURIComponent(s.name), {enabled: !s.enabled});
+    });
+    table('positions', positions, ['book', 'asset', 'side', 'status', 'size', 'entry_price', 'unrealized_pnl', 'market_id']);
+    table('trades', trades, ['timestamp', 'wallet', 'strategy', 'status', 'net_profit', 'market_id']);
+    document.getElementById('debug').textContent = d.debug_mode ? d.debug_logs.slice(-20).join('\\n') : '';
+    document.getElementById('error').textContent = '';
+  } catch (e) { document.getElementById('error').textContent = e.message; }
+}
+refresh();
+setInterval(refresh, 2000);
+</script>
+</body>
+</html>
+"""
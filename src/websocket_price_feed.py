@@ -10,7 +10,7 @@ import logging
 import json
 from datetime import datetime
 from decimal import Decimal
-from typing import Dict, Callable, Optional, List, Any
+from typing import Dict, Callable, Optional, List, Any, Tuple
 from dataclasses import dataclass, field
 import websockets
 from websockets.exceptions import ConnectionClosed
@@ -90,7 +90,8 @@ class WebSocketPriceFeed:
         api_key: Optional[str] = None,
         on_price_update: Optional[Callable[[PriceUpdate], None]] = None,
         reconnect_delay: float = 5.0,
-        heartbeat_interval: float = 30.0
+        heartbeat_interval: float = 30.0,
+        recorder: Optional[Any] = None
     ):
         """
         Initialize WebSocket Price Feed.
@@ -100,8 +101,10 @@ class WebSocketPriceFeed:
             on_price_update: Callback for price updates
             reconnect_delay: Delay before reconnection attempts
             heartbeat_interval: Interval for heartbeat checks
+            recorder: Optional MarketDataRecorder for snapshots, updates and trades
         """
         self.api_key = api_key
+        self.recorder = recorder
         self.on_price_update = on_price_update
         self.reconnect_delay = reconnect_delay
         self.heartbeat_interval = heartbeat_interval
@@ -282,6 +285,9 @@ class WebSocketPriceFeed:
             data = json.loads(message)
             msg_type = data.get("type", "")
             
+            if self.recorder is not None:
+                self._record_message(msg_type, data)
+            
             if msg_type == "orderbook_snapshot":
                 await self._handle_orderbook_snapshot(data)
                 
@@ -300,6 +306,34 @@ class WebSocketPriceFeed:
         except json.JSONDecodeError as e:
             logger.error(f"Failed to parse WebSocket message: {e}")
     
+    def _record_message(self, msg_type: str, data: Dict):
+        """
+        Pass orderbook snapshots, updates and trades to the recorder.
+
+        This feed is keyed by market, not token, so records carry market_id.
+        """
+        market_id = data.get("market", "")
+        if not market_id:
+            return
+        exchange_ts = data.get("timestamp")
+        
+        if msg_type == "orderbook_snapshot":
+            self.recorder.record_book(
+                market_id, data.get("bids", []), data.get("asks", []),
+                source="gamma_ws", exchange_ts=exchange_ts, id_field="market_id"
+            )
+        elif msg_type == "orderbook_update":
+            self.recorder.record_book_delta(
+                market_id, data.get("changes", []), source="gamma_ws",
+                exchange_ts=exchange_ts, id_field="market_id"
+            )
+        elif msg_type == "trade":
+            self.recorder.record_trade(
+                market_id, data.get("price"), source="gamma_ws",
+                size=data.get("size"), side=data.get("side"), exchange_ts=exchange_ts,
+                id_field="market_id"
+            )
+    
     async def _handle_orderbook_snapshot(self, data: Dict):
         """Handle full orderbook snapshot."""
         market_id = data.get("market", "")
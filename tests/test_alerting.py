@@ -0,0 +1,338 @@
+"""
+Tests for alert routing and sinks.
+
+Tests:
+- Config validation of alert channels and routes
+- Webhook, Telegram, Discord and SMTP sinks against local stand-in servers
+- Routing by event and minimum severity, deduplication, rate limits
+- MonitoringSystem.send_alert/notify through the router
+- Alert sources: circuit breaker trips, volatility halts, ledger fills, low balances
+"""
+
+import asyncio
+import json
+import re
+import socketserver
+import threading
+from decimal import Decimal
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, Mock
+
+import pytest
+
+from config.config import Config
+from src.ai_safety_guard import AISafetyGuard
+from src.alerting import (
+    CIRCUIT_BREAKER, LOW_BALANCE, TRADE_FILL, Alert, AlertRouter, AlertSink, SmtpSink, WebhookSink,
+    create_alert_sink,
+)
+from src.error_recovery import CircuitBreaker
+from src.main_orchestrator import MainOrchestrator
+from src.monitoring_system import MonitoringSystem
+from src.position_ledger import PositionLedger
+
+
+class FakeSink(AlertSink):
+    """Records alerts; optionally fails."""
+
+    def __init__(self, name, fail=False, **kwargs):
+        super().__init__(name, **kwargs)
+        self.fail = fail
+        self.alerts = []
+
+    async def send(self, alert):
+        if self.fail:
+            raise ConnectionError("channel down")
+        self.alerts.append(alert)
+        return True
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+@pytest.fixture
+def http_server():
+    """Local HTTP stand-in: records (path, JSON body), answers with `status`."""
+    requests = []
+
+    class Handler(BaseHTTPRequestHandler):
+        def do_POST(self):
+            body = self.rfile.read(int(self.headers.get("Content-Length", 0)))
+            requests.append((self.path, json.loads(body), dict(self.headers)))
+            self.send_response(server.status)
+            self.end_headers()
+            if server.status >= 300:
+                self.wfile.write(b"bad request")
+
+        def log_message(self, *args):
+            pass
+
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    server.status = 200
+    server.requests = requests
+    server.url = f"http://127.0.0.1:{server.server_address[1]}"
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    yield server
+    server.shutdown()
+    server.server_close()
+
+
+@pytest.fixture
+def smtp_server():
+    """Local SMTP stand-in (no TLS, no auth): records each message's envelope and data."""
+    messages = []
+
+    class Handler(socketserver.StreamRequestHandler):
+        def reply(self, line):
+            self.wfile.write(line.encode() + b"\r\n")
+
+        def handle(self):
+            envelope = {"rcpt": []}
+            self.reply("220 localhost ready")
+            while True:
+                line = self.rfile.readline().decode().strip()
+                command = line.upper()
+                if command.startswith(("EHLO", "HELO")):
+                    self.reply("250 localhost")
+                elif command.startswith("MAIL FROM"):
+                    envelope["from"] = line[10:]
+                    self.reply("250 OK")
+                elif command.startswith("RCPT TO"):
+                    envelope["rcpt"].append(line[8:])
+                    self.reply("250 OK")
+                elif command == "DATA":
+                    self.reply("354 End data with <CR><LF>.<CR><LF>")
+                    data = []
+                    while (row := self.rfile.readline().decode()) not in (".\r\n", ""):
+                        data.append(row)
+                    messages.append(dict(envelope, data="".join(data)))
+                    self.reply("250 OK")
+                elif command == "QUIT" or not line:
+                    self.reply("221 Bye")
+                    return
+                else:
+                    self.reply("250 OK")
+
+    server = socketserver.ThreadingTCPServer(("127.0.0.1", 0), Handler)
+    server.daemon_threads = True
+    server.messages = messages
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    yield server
+    server.shutdown()
+    server.server_close()
+
+
+def make_config(**overrides):
+    return Config(
+        private_key="0x" + "11" * 32,
+        wallet_address="0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+        polygon_rpc_url="https://polygon-rpc.com",
+        **overrides
+    )
+
+
+# ============================================================================
+# Configuration
+# ============================================================================
+
+def test_config_accepts_channels_and_routes():
+    config = make_config(
+        alert_channels={
+            "ops": {"type": "webhook", "url": "https://hooks.example/alerts", "min_severity": "warning"},
+            "phone": {"type": "telegram", "chat_id": "42", "bot_token_env": "OPS_BOT_TOKEN"},
+        },
+        alert_routes={"trade_fill": ["phone"], "default": ["ops", "phone"]},
+    )
+    assert config.to_dict()["alert_channels"]["ops"] == {"type": "webhook", "min_severity": "warning"}
+
+
+@pytest.mark.parametrize("channels, routes, error", [
+    ({"x": {"type": "pager"}}, {}, "type must be one of"),
+    ({"x": {"type": "discord"}}, {}, "url or url_env is required"),
+    ({"x": {"type": "telegram", "chat_id": "1", "token": "abc"}}, {}, "unknown options"),
+    ({"x": {"type": "smtp", "host": "mail"}}, {}, "host, sender and recipients"),
+    ({"x": {"type": "webhook", "url": "u", "min_severity": "loud"}}, {}, "min_severity"),
+    ({"x": {"type": "webhook", "url": "u"}}, {"fills": ["x"]}, "unknown event 'fills'"),
+    ({"x": {"type": "webhook", "url": "u"}}, {"trade_fill": ["y"]}, "unknown channels: ['y']"),
+])
+def test_config_rejects_invalid_alerting(channels, routes, error):
+    with pytest.raises(ValueError, match=re.escape(error)):
+        make_config(alert_channels=channels, alert_routes=routes)
+
+
+# ============================================================================
+# Sinks against local stand-ins
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_webhook_sink_posts_json(http_server):
+    sink = WebhookSink("ops", http_server.url + "/hook", headers={"X-Token": "t"})
+
+    assert await sink.send(Alert("WARNING", "gas high", event="gas_price", context={"gwei": 900}))
+
+    path, body, headers = http_server.requests[0]
+    assert path == "/hook"
+    assert (body["severity"], body["event"], body["message"], body["context"]) == (
+        "warning", "gas_price", "gas high", {"gwei": 900}
+    )
+    assert headers["X-Token"] == "t"
+
+    http_server.status = 500
+    assert await sink.send(Alert("warning", "again")) is False
+
+
+@pytest.mark.asyncio
+async def test_telegram_and_discord_sinks(http_server, monkeypatch):
+    monkeypatch.setenv("TELEGRAM_BOT_TOKEN", "123:abc")
+    telegram = create_alert_sink("phone", {"type": "telegram", "chat_id": 42, "api_url": http_server.url})
+    discord = create_alert_sink("chat", {"type": "discord", "url": http_server.url + "/api/webhooks/1/x"})
+
+    http_server.status = 204  # Discord answers No Content
+    assert await telegram.send(Alert("critical", "circuit open", event=CIRCUIT_BREAKER))
+    assert await discord.send(Alert("info", "filled"))
+
+    (tg_path, tg_body, _), (dc_path, dc_body, _) = http_server.requests
+    assert tg_path == "/bot123:abc/sendMessage"
+    assert tg_body["chat_id"] == "42"
+    assert tg_body["text"].startswith("[CRITICAL] Polymarket Arbitrage Bot: circuit_breaker\ncircuit open")
+    assert dc_path == "/api/webhooks/1/x"
+    assert "filled" in dc_body["content"]
+
+    monkeypatch.delenv("TELEGRAM_BOT_TOKEN")
+    with pytest.raises(ValueError, match="TELEGRAM_BOT_TOKEN is not set"):
+        create_alert_sink("phone", {"type": "telegram", "chat_id": 42})
+
+
+@pytest.mark.asyncio
+async def test_smtp_sink_sends_email(smtp_server):
+    sink = SmtpSink("mail", "127.0.0.1", "bot@example.com", ["ops@example.com", "me@example.com"],
+                    port=smtp_server.server_address[1], use_tls=False)
+
+    assert await sink.send(Alert("error", "redemption failed", context={"wallet": "primary"}))
+
+    message = smtp_server.messages[0]
+    assert message["rcpt"] == ["<ops@example.com>", "<me@example.com>"]
+    assert "Subject: [ERROR] Polymarket Arbitrage Bot: system" in message["data"]
+    assert "wallet: primary" in message["data"]
+
+
+# ============================================================================
+# Router
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_router_routes_by_event_and_severity():
+    ops, phone, pager = FakeSink("ops"), FakeSink("phone"), FakeSink("pager", min_severity="error")
+    router = AlertRouter([ops, phone, pager], routes={
+        TRADE_FILL: ["phone"], CIRCUIT_BREAKER: ["pager", "phone"], "default": ["ops"],
+    })
+
+    assert await router.send(Alert("info", "filled", event=TRADE_FILL)) == {"phone": True}
+    assert await router.send(Alert("critical", "tripped", event=CIRCUIT_BREAKER)) == {"pager": True, "phone": True}
+    assert await router.send(Alert("warning", "low", event=LOW_BALANCE)) == {"ops": True}
+    assert [len(s.alerts) for s in (ops, phone, pager)] == [1, 2, 1]
+
+    unrouted = AlertRouter([ops, pager])
+    assert await unrouted.send(Alert("info", "hello")) == {"ops": True}  # pager needs error or worse
+
+
+@pytest.mark.asyncio
+async def test_router_deduplicates_and_rate_limits():
+    clock = FakeClock()
+    sink = FakeSink("ops")
+    router = AlertRouter([sink], dedup_seconds=300, rate_limit_per_minute=2, clock=clock)
+
+    assert await router.send(Alert("warning", "gas high")) == {"ops": True}
+    assert await router.send(Alert("warning", "gas high")) == {}
+    assert await router.send(Alert("warning", "gas high")) == {}
+    clock.now += 301
+    await router.send(Alert("warning", "gas high"))
+    assert sink.alerts[-1].context == {"suppressed_duplicates": 2}
+
+    assert await router.send(Alert("warning", "other")) == {"ops": True}
+    assert await router.send(Alert("warning", "third")) == {}  # Two sent within the minute
+    assert await router.send(Alert("critical", "tripped")) == {"ops": True}  # Critical bypasses the limit
+    clock.now += 60
+    assert await router.send(Alert("warning", "fourth")) == {"ops": True}
+
+
+@pytest.mark.asyncio
+async def test_failing_channel_does_not_block_others():
+    good = FakeSink("good")
+    router = AlertRouter([FakeSink("bad", fail=True), good])
+
+    assert await router.send(Alert("error", "boom")) == {"bad": False, "good": True}
+
+    monitoring = MonitoringSystem(enable_prometheus=False, alert_router=router)
+    assert await monitoring.send_alert("error", "boom again") is True
+    assert await MonitoringSystem(enable_prometheus=False).send_alert("error", "nowhere") is False
+
+    monitoring.notify("info", "from sync code", event=TRADE_FILL)
+    await asyncio.gather(*monitoring._alert_tasks)
+    assert good.alerts[-1].event == TRADE_FILL
+
+
+# ============================================================================
+# Alert sources
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_safety_events_and_fills_call_listeners(tmp_path):
+    breaker = CircuitBreaker(failure_threshold=2)
+    breaker.on_open = Mock()
+    breaker.record_failure("rejected")
+    breaker.record_failure("rejected")
+    assert breaker.on_open.call_args[0][0]["consecutive_failures"] == 2
+
+    guard = AISafetyGuard(nvidia_api_key="test_key")
+    guard.on_volatility_halt = Mock()
+    guard._trigger_volatility_halt()
+    guard.on_volatility_halt.assert_called_once_with(guard._volatility_halt_until)
+
+    ledger = PositionLedger(str(tmp_path / "ledger.db"))
+    fills = []
+    ledger.fill_listeners.append(fills.append)
+    ledger.fill_listeners.insert(0, Mock(side_effect=RuntimeError("listener bug")))  # Never breaks recording
+    ledger.record_fill("tok", "BUY", Decimal("10"), Decimal("0.45"), outcome="UP", strategy="fifteen_min_crypto")
+    assert fills[0].shares == Decimal("10")
+
+    monitoring = Mock()
+    MainOrchestrator._on_ledger_fill(SimpleNamespace(monitoring=monitoring), fills[0])
+    args, kwargs = monitoring.notify.call_args
+    assert args[1].startswith("Fill: BUY 10 UP @ $0.45 (fifteen_min_crypto")
+    assert kwargs["event"] == TRADE_FILL
+
+    # Recording the trade result does not alert the same fill a second time
+    result = Mock()
+    MainOrchestrator._record_trade_result(SimpleNamespace(
+        trade_history=Mock(), trade_statistics=Mock(), monitoring=monitoring,
+        dashboard=Mock(), circuit_breaker=Mock(), wallets=Mock()
+    ), result)
+    monitoring.notify.assert_called_once()
+
+
+@pytest.mark.asyncio
+async def test_low_balance_alerts_once_per_drop():
+    wallet = SimpleNamespace(name="primary", eoa_balance=Decimal("0"), proxy_balance=Decimal("0.05"))
+    wallet.total_balance = wallet.proxy_balance
+    orchestrator = SimpleNamespace(
+        wallets=[wallet], config=SimpleNamespace(min_balance=Decimal("1")),
+        monitoring=SimpleNamespace(send_alert=AsyncMock()), low_balance_wallets=set(),
+    )
+
+    await MainOrchestrator._check_low_balances(orchestrator)
+    await MainOrchestrator._check_low_balances(orchestrator)
+    assert orchestrator.monitoring.send_alert.await_count == 1
+    assert orchestrator.monitoring.send_alert.call_args.kwargs["event"] == LOW_BALANCE
+
+    wallet.total_balance = Decimal("5")  # Topped up, then drops again
+    await MainOrchestrator._check_low_balances(orchestrator)
+    wallet.total_balance = Decimal("0.5")
+    await MainOrchestrator._check_low_balances(orchestrator)
+    assert orchestrator.monitoring.send_alert.await_count == 2
@@ -0,0 +1,251 @@
+"""
+Unit tests for the event-driven replay backtester.
+
+Tests:
+- Event parsing and streaming from compressed JSON lines
+- Simulated clock patching of datetime.now() and time.time()
+- ReplayExchange matching, balance checks and settlement
+- End-to-end exits (take profit, time exit, resolution) through the real strategy
+- Strategy state kept in the replay's directory, without changing the working directory
+- --set values typed like the ReplayConfig field or strategy attribute they override
+"""
+
+import argparse
+import gzip
+import json
+import os
+import time
+
+import pytest
+from decimal import Decimal
+from datetime import datetime, timedelta, timezone
+
+from py_clob_client.clob_types import OrderArgs
+
+import src.fifteen_min_crypto_strategy as strategy_module
+from src.backtest_replay import (
+    ReplayBacktester,
+    ReplayConfig,
+    ReplayEvent,
+    ReplayExchange,
+    SimulatedClock,
+    _parse_override,
+    default_strategy_factory,
+    load_replay_events,
+)
+from src.fifteen_min_crypto_strategy import Position
+
+
+T0 = datetime(2026, 1, 1, 12, 0, tzinfo=timezone.utc)
+
+
+def _event(seconds, event_type, **data):
+    return ReplayEvent(T0 + timedelta(seconds=seconds), event_type, data)
+
+
+def _book(seconds, token_id, bid, ask, size="100"):
+    return _event(seconds, "book", token_id=token_id, bids=[[bid, size]], asks=[[ask, size]])
+
+
+def _market_events(end_minutes=60):
+    return [
+        _event(0, "market", market_id="m1", asset="BTC", up_token_id="111", down_token_id="222",
+               end_time=(T0 + timedelta(minutes=end_minutes)).isoformat()),
+        _event(0, "binance_tick", asset="BTC", price="95000"),
+        _book(0, "111", "0.45", "0.46"),
+        _book(0, "222", "0.54", "0.55"),
+    ]
+
+
+def _seeded_factory(entry_price="0.45", shares="10"):
+    """Build the real strategy holding an UP position bought on the replay exchange."""
+    def factory(exchange, config, workdir):
+        strategy = default_strategy_factory(exchange, config, workdir)
+        exchange.apply_book("111", [["0.44", "100"]], [[entry_price, "100"]])
+        exchange.post_order(exchange.create_order(
+            OrderArgs(token_id="111", price=float(entry_price), size=float(shares), side="BUY")
+        ))
+        strategy.positions["111"] = Position(
+            token_id="111", side="UP", entry_price=Decimal(entry_price), size=Decimal(shares),
+            entry_time=exchange.clock.now(), market_id="m1", asset="BTC", strategy="test",
+            highest_price=Decimal(entry_price),
+        )
+        return strategy
+    return factory
+
+
+@pytest.fixture
+def exchange():
+    """Replay exchange with one book and $100 collateral."""
+    exchange = ReplayExchange(SimulatedClock(T0), Decimal("100"))
+    exchange.apply_book("tok", [["0.40", "10"], ["0.39", "20"]], [["0.42", "10"], ["0.43", "20"]])
+    return exchange
+
+
+def test_event_round_trip_and_gzip_stream(tmp_path):
+    """Events serialize to JSON lines and stream back from .gz files."""
+    path = tmp_path / "events.jsonl.gz"
+    events = _market_events()
+    with gzip.open(path, "wt", encoding="utf-8") as f:
+        for event in events:
+            f.write(json.dumps(event.to_dict()) + "\n")
+
+    loaded = list(load_replay_events(path))
+
+    assert [e.event_type for e in loaded] == [e.event_type for e in events]
+    assert loaded[2].data["bids"] == [["0.45", "100"]]
+    assert loaded[0].timestamp == T0
+
+
+def test_unknown_event_type_rejected():
+    """Malformed events fail loudly rather than being skipped."""
+    with pytest.raises(ValueError, match="Unknown replay event type"):
+        ReplayEvent.from_dict({"ts": T0.isoformat(), "type": "weather"})
+
+
+def test_clock_patches_strategy_module_time():
+    """datetime.now() and time.time() follow the simulated clock while patched."""
+    clock = SimulatedClock(T0)
+
+    with clock.patched():
+        assert strategy_module.datetime.now(timezone.utc) == T0
+        clock.advance_to(T0 + timedelta(minutes=5))
+        assert time.time() == (T0 + timedelta(minutes=5)).timestamp()
+
+    assert strategy_module.datetime is datetime
+    assert strategy_module.datetime.now(timezone.utc) > T0 + timedelta(days=30)
+
+
+def test_clock_cannot_go_backwards():
+    """Out-of-order streams are rejected."""
+    clock = SimulatedClock(T0)
+
+    with pytest.raises(ValueError, match="back in time"):
+        clock.advance_to(T0 - timedelta(seconds=1))
+
+
+def test_buy_walks_book_and_consumes_depth(exchange):
+    """Buys fill across levels up to the limit and remove matched depth."""
+    order = exchange.create_order(OrderArgs(token_id="tok", price=0.43, size=15, side="BUY"))
+
+    response = exchange.post_order(order)
+
+    assert response["success"] is True
+    fill = exchange.fills[-1]
+    assert fill.size == Decimal("15")
+    assert fill.price == (Decimal("0.42") * 10 + Decimal("0.43") * 5) / 15
+    assert exchange.best_ask("tok") == Decimal("0.43")
+    assert exchange.token_balances["tok"] == Decimal("15")
+
+
+def test_buy_without_enough_depth_is_rejected(exchange):
+    """Orders that cannot fully fill at the limit are killed."""
+    order = exchange.create_order(OrderArgs(token_id="tok", price=0.42, size=15, side="BUY"))
+
+    response = exchange.post_order(order)
+
+    assert response["success"] is False
+    assert exchange.fills == []
+    assert exchange.cash == Decimal("100")
+
+
+def test_sell_requires_token_balance(exchange):
+    """Selling more than held returns the balance error the strategy retries on."""
+    order = exchange.create_order(OrderArgs(token_id="tok", price=0.40, size=5, side="SELL"))
+
+    response = exchange.post_order(order)
+
+    assert "balance" in response["errorMsg"]
+
+
+def test_sell_realizes_pnl_against_cost_basis(exchange):
+    """Realized PnL uses average cost of the shares sold."""
+    exchange.post_order(exchange.create_order(OrderArgs(token_id="tok", price=0.42, size=10, side="BUY")))
+    exchange.apply_book("tok", [["0.50", "50"]], [["0.52", "50"]])
+
+    exchange.post_order(exchange.create_order(OrderArgs(token_id="tok", price=0.50, size=10, side="SELL")))
+
+    assert exchange.fills[-1].realized_pnl == Decimal("0.80")
+    assert exchange.cash == Decimal("100.80")
+    assert "tok" not in exchange.token_balances
+
+
+@pytest.mark.asyncio
+async def test_take_profit_exit_fills_against_replayed_book(tmp_path):
+    """A price jump triggers the strategy's take-profit and sells into the bid."""
+    events = _market_events() + [_book(5, "111", "0.60", "0.61"), _event(10, "binance_tick", asset="BTC", price="95010")]
+    config = ReplayConfig(cycle_interval_seconds=1.0, entry_order=[])
+
+    result = await ReplayBacktester(events, config, _seeded_factory(), workdir=tmp_path).run()
+
+    assert len(result.closed_trades) == 1
+    trade = result.closed_trades[0]
+    assert trade.exit_reason == "take_profit"
+    assert trade.exit_price == Decimal("0.60")
+    assert trade.exit_time == T0 + timedelta(seconds=5)
+    assert result.net_pnl == Decimal("1.50")
+
+
+@pytest.mark.asyncio
+async def test_time_exit_uses_simulated_position_age(tmp_path):
+    """Positions held past 13 simulated minutes are force-exited."""
+    events = _market_events() + [_event(15 * 60, "binance_tick", asset="BTC", price="95000")]
+    config = ReplayConfig(cycle_interval_seconds=30.0, entry_order=[])
+
+    result = await ReplayBacktester(events, config, _seeded_factory(), workdir=tmp_path).run()
+
+    assert [t.exit_reason for t in result.closed_trades] == ["time_exit_13min"]
+    assert 13 < result.closed_trades[0].hold_minutes <= 13.5
+
+
+@pytest.mark.asyncio
+async def test_resolution_settles_held_tokens(tmp_path):
+    """Held winning tokens redeem at $1 and leave the strategy's positions."""
+    events = _market_events() + [_event(30, "resolution", market_id="m1", winner="UP")]
+    config = ReplayConfig(cycle_interval_seconds=10.0, entry_order=[])
+    backtester = ReplayBacktester(events, config, _seeded_factory(), workdir=tmp_path)
+
+    result = await backtester.run()
+
+    assert result.closed_trades[0].exit_reason == "resolution"
+    assert result.closed_trades[0].pnl == Decimal("5.50")
+    assert backtester.strategy.positions == {}
+    assert result.exit_reason_breakdown()["resolution"]["wins"] == 1
+
+
+@pytest.mark.asyncio
+async def test_strategy_state_stays_in_workdir(tmp_path):
+    """Positions persist to the replay's directory; the process working directory is untouched."""
+    cwd = os.getcwd()
+    config = ReplayConfig(cycle_interval_seconds=10.0, entry_order=[])
+    backtester = ReplayBacktester(_market_events(), config, _seeded_factory(), workdir=tmp_path / "state")
+
+    await backtester.run()
+
+    assert backtester.strategy.positions_file == str(tmp_path / "state" / "active_positions.json")
+    assert os.getcwd() == cwd
+
+
+def test_set_overrides_take_the_replay_config_field_type():
+    assert _parse_override("max_positions=3") == ("max_positions", 3)
+    assert isinstance(_parse_override("fee_rate=0.01")[1], Decimal)
+    assert _parse_override("take_profit_pct=0.03") == ("take_profit_pct", 0.03)
+    assert _parse_override("trailing_stop_pct=0.015") == ("trailing_stop_pct", "0.015")  # Typed on attach
+
+    with pytest.raises(argparse.ArgumentTypeError, match="max_positions expects int"):
+        _parse_override("max_positions=1.5")
+
+
+@pytest.mark.asyncio
+async def test_set_overrides_take_the_strategy_attribute_type(tmp_path):
+    config = ReplayConfig(entry_order=[], strategy_overrides={"trailing_stop_pct": "0.015", "maker_entries": "false"})
+    backtester = ReplayBacktester(_market_events(), config, workdir=tmp_path)
+
+    await backtester.run()
+
+    assert backtester.strategy.trailing_stop_pct == Decimal("0.015")
+    assert backtester.strategy.maker_entries is False
+
+    config = ReplayConfig(entry_order=[], strategy_overrides={"maker_entries": "maybe"})
+    with pytest.raises(ValueError, match="maker_entries expects true or false"):
+        await ReplayBacktester(_market_events(), config, workdir=tmp_path).run()
@@ -0,0 +1,404 @@
+"""
+Unit tests for the simulated Polymarket CLOB.
+
+Tests:
+- Matching engine: price-time priority, partial fills, GTC/GTD/FOK/FAK, post-only, cancels
+- Balance, reservation and allowance checks
+- SimulatedClobClient response shapes (books, balances, orders, trades)
+- Market channel events consumed by PolymarketWebSocketFeed
+- OrderManager and a full MainOrchestrator scan running offline against scenarios
+"""
+
+import contextlib
+import json
+import uuid
+
+import pytest
+from decimal import Decimal
+from datetime import datetime
+from types import SimpleNamespace
+from unittest.mock import Mock, patch
+
+from py_clob_client.clob_types import BalanceAllowanceParams, OrderArgs, OrderType
+
+from config.config import Config
+from src.clob_simulator import (
+    ERR_BALANCE,
+    ERR_FOK,
+    ERR_POST_ONLY_CROSSES,
+    ERR_POST_ONLY_TYPE,
+    SimulatedClobClient,
+    SimulatedClobExchange,
+    _order_from_payload,
+)
+from src.models import Opportunity
+from src.order_manager import OrderManager
+from src.polymarket_websocket_feed import PolymarketWebSocketFeed
+from src.strategy_registry import ResolutionFarmingAdapter
+
+
+T0 = 1767268800  # 2026-01-01T12:00:00Z
+
+ALICE = "0x00000000000000000000000000000000000A11CE"
+BOB = "0x0000000000000000000000000000000000000B0B"
+
+SCENARIO = {
+    "start_time": T0,
+    "markets": [{
+        "condition_id": "0xabc", "question": "Will BTC close above $100k?",
+        "tokens": {"111": "Yes", "222": "No"}, "end_date_iso": "2099-01-01T00:00:00Z",
+    }],
+    "accounts": {ALICE: {"collateral": "100"}, BOB: {"collateral": "50", "tokens": {"111": "40"}}},
+    "books": {"111": {"bids": [["0.46", "100"]], "asks": [["0.48", "10"], ["0.50", "100"]]}},
+}
+
+
+@pytest.fixture
+def exchange():
+    """Exchange loaded from the default scenario."""
+    return SimulatedClobExchange.from_scenario(json.loads(json.dumps(SCENARIO)))
+
+
+@pytest.fixture
+def client(exchange):
+    """Client trading for ALICE."""
+    return SimulatedClobClient(exchange, address=ALICE)
+
+
+def _buy(client, price, size, order_type=OrderType.GTC, token_id="111", **kwargs):
+    order = client.create_order(OrderArgs(token_id=token_id, price=price, size=size, side="BUY", **kwargs))
+    return client.post_order(order, order_type)
+
+
+# ============================================================================
+# Matching engine
+# ============================================================================
+
+def test_taker_walks_book_at_maker_prices(exchange, client):
+    """A marketable buy fills across levels at resting prices and reports amounts."""
+    response = _buy(client, 0.50, 15, OrderType.FOK)
+
+    assert response["success"] is True and response["status"] == "matched"
+    assert response["takingAmount"] == "15"
+    assert response["makingAmount"] == "7.3"  # 10 @ 0.48 + 5 @ 0.50
+    assert exchange.balance(ALICE) == Decimal("92.7")
+    assert exchange.balance(ALICE, "111") == Decimal("15")
+    assert exchange.book("111")[1][0] == (Decimal("0.50"), Decimal("95"))
+
+
+def test_resting_order_partially_filled_with_time_priority(exchange, client):
+    """Resting bids fill oldest-first at the same price and keep their remainder live."""
+    first = _buy(client, 0.47, 10)["orderID"]
+    bob = SimulatedClobClient(exchange, address=BOB)
+    second = bob.post_order(bob.create_order(OrderArgs(token_id="111", price=0.47, size=10, side="BUY")))["orderID"]
+
+    sell = bob.post_order(bob.create_order(OrderArgs(token_id="111", price=0.47, size=6, side="SELL")), "FAK")
+
+    assert sell["status"] == "matched" and sell["takingAmount"] == "2.82"
+    assert client.get_order(first)["size_matched"] == "6"
+    assert client.get_order(first)["status"] == "LIVE"
+    assert exchange.orders[second].size_matched == 0
+    assert client.get_trades()[0]["trader_side"] == "MAKER"
+
+
+def test_fok_without_depth_is_rejected_without_side_effects(exchange, client):
+    """FOK orders that cannot fully fill are killed and nothing changes."""
+    response = _buy(client, 0.48, 15, OrderType.FOK)
+
+    assert response == {"success": False, "errorMsg": ERR_FOK, "orderID": "", "status": "",
+                        "takingAmount": "0", "makingAmount": "0", "transactionsHashes": []}
+    assert exchange.trades == [] and exchange.balance(ALICE) == Decimal("100")
+
+
+def test_fak_fills_available_and_cancels_rest(exchange, client):
+    """FAK takes what is available at the limit and does not rest."""
+    response = _buy(client, 0.48, 15, OrderType.FAK)
+
+    assert response["takingAmount"] == "10"
+    assert client.get_orders() == []
+    assert exchange.orders[response["orderID"]].status == "CANCELED"
+
+
+def test_post_only_orders_never_take(exchange, client):
+    """Post-only orders are rejected if they cross the book and only rest as GTC/GTD."""
+    crossing = client.create_order(OrderArgs(token_id="111", price=0.48, size=5, side="BUY"))
+    assert client.post_order(crossing, OrderType.GTC, post_only=True)["errorMsg"] == ERR_POST_ONLY_CROSSES
+    assert client.post_order(crossing, OrderType.FOK, post_only=True)["errorMsg"] == ERR_POST_ONLY_TYPE
+    assert exchange.trades == []
+
+    passive = client.create_order(OrderArgs(token_id="111", price=0.47, size=5, side="BUY"))
+    response = client.post_order(passive, OrderType.GTC, post_only=True)
+    assert response["success"] is True and response["status"] == "live"
+
+
+def test_invalid_tick_and_size_rejected(exchange):
+    """Prices must sit on the tick grid and sizes meet the market minimum."""
+    assert "tick size" in exchange.place_order(ALICE, "111", "BUY", "0.475", "10")["errorMsg"]
+    assert "minimum" in exchange.place_order(ALICE, "111", "BUY", "0.40", "1")["errorMsg"]
+    assert "does not exist" in exchange.place_order(ALICE, "999", "BUY", "0.40", "10")["errorMsg"]
+
+
+# ============================================================================
+# Balances and allowances
+# ============================================================================
+
+def test_resting_buys_reserve_collateral(exchange, client):
+    """Open bids lock collateral; a cancel releases it."""
+    resting = _buy(client, 0.40, 200)["orderID"]
+
+    assert _buy(client, 0.40, 60)["errorMsg"] == ERR_BALANCE
+
+    assert client.cancel(resting) == {"canceled": [resting], "not_canceled": {}}
+    assert _buy(client, 0.40, 60)["success"] is True
+
+
+def test_allowance_and_token_approval_checked(exchange, client):
+    """USDC allowance and conditional token approval gate orders."""
+    exchange.fund(ALICE, allowance="2")
+    assert _buy(client, 0.48, 10)["errorMsg"] == ERR_BALANCE
+
+    exchange.fund(BOB, tokens_approved=False)
+    assert exchange.place_order(BOB, "111", "SELL", "0.46", "10")["errorMsg"] == ERR_BALANCE
+    assert exchange.place_order(ALICE, "111", "SELL", "0.46", "10")["errorMsg"] == ERR_BALANCE
+
+
+def test_trade_settles_between_accounts(exchange, client):
+    """Cash and tokens move between maker and taker at the maker price."""
+    exchange.place_order(BOB, "111", "SELL", "0.47", "10")
+
+    _buy(client, 0.47, 10)
+
+    assert exchange.balance(BOB) == Decimal("54.7")
+    assert exchange.balance(BOB, "111") == Decimal("30")
+    assert client.get_balance_allowance(
+        BalanceAllowanceParams(asset_type="CONDITIONAL", token_id="111")
+    )["balance"] == "10000000"
+    assert client.get_balance_allowance(BalanceAllowanceParams(asset_type="COLLATERAL"))["balance"] == "95300000"
+
+
+# ============================================================================
+# Cancels and expiry
+# ============================================================================
+
+def test_cancel_rules(exchange, client):
+    """Only the owner's live orders can be cancelled."""
+    live = _buy(client, 0.40, 10)["orderID"]
+    matched = _buy(client, 0.48, 10)["orderID"]
+    bob = SimulatedClobClient(exchange, address=BOB)
+
+    assert bob.cancel(live)["not_canceled"] == {live: "order not found"}
+    result = client.cancel_orders([live, matched])
+
+    assert result["canceled"] == [live]
+    assert "MATCHED" in result["not_canceled"][matched]
+
+
+def test_gtd_orders_expire_on_the_exchange_clock(exchange, client):
+    """GTD orders leave the book once the clock passes their expiration."""
+    order_id = _buy(client, 0.40, 10, OrderType.GTD, expiration=T0 + 60)["orderID"]
+    assert client.get_orders()[0]["id"] == order_id
+
+    exchange.advance(60)
+
+    assert client.get_orders() == []
+    assert exchange.orders[order_id].status == "CANCELED"
+    assert "expiration" in _buy(client, 0.40, 10, OrderType.GTD, expiration=T0)["errorMsg"]
+
+
+# ============================================================================
+# Client surface and feeds
+# ============================================================================
+
+def test_client_market_data_shapes(client):
+    """Books are best-first and markets parse like the CLOB API."""
+    book = client.get_order_book("111")
+
+    assert [(lvl.price, lvl.size) for lvl in book.asks] == [("0.48", "10"), ("0.5", "100")]
+    assert client.get_midpoint("111") == {"mid": "0.47"}
+    assert client.get_price("111", "BUY") == {"price": "0.48"}
+    assert client.get_markets()["data"][0]["tokens"][0] == {"token_id": "111", "outcome": "Yes", "price": 0.47}
+    assert client.create_or_derive_api_creds().api_key == client.create_or_derive_api_creds().api_key
+
+
+def test_create_order_rejects_price_outside_tick_range(client):
+    """Like ClobClient, out-of-range prices fail before posting."""
+    with pytest.raises(ValueError, match="price"):
+        client.create_order(OrderArgs(token_id="111", price=1.0, size=10, side="BUY"))
+
+
+def test_signed_order_amounts_decode_to_price_and_size():
+    """REST order payloads use 6-decimal maker/taker amounts."""
+    buy = {"tokenId": "111", "side": "BUY", "makerAmount": "4800000", "takerAmount": "10000000", "expiration": "0"}
+    sell = {"tokenId": "111", "side": 1, "makerAmount": "10000000", "takerAmount": "4600000"}
+
+    assert _order_from_payload(buy) == ("111", "BUY", Decimal("0.48"), Decimal("10"), 0)
+    assert _order_from_payload(sell) == ("111", "SELL", Decimal("0.46"), Decimal("10"), 0)
+
+
+@pytest.mark.asyncio
+async def test_market_events_drive_polymarket_feed(exchange, client):
+    """Book and last_trade_price events are readable by PolymarketWebSocketFeed."""
+    feed = PolymarketWebSocketFeed()
+    events = []
+    exchange.add_listener(events.append)
+
+    _buy(client, 0.48, 10)
+    for event in events:
+        await feed._process_message(json.dumps(event))
+
+    assert [e["event_type"] for e in events if e["event_type"] != "order"] == ["trade", "last_trade_price", "book"]
+    assert (await feed.get_price("111")).price == Decimal("0.50")
+
+
+# ============================================================================
+# Offline OrderManager / MainOrchestrator
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_order_manager_reads_fill_from_clob_response(exchange, client):
+    """Real (non dry-run) FOK submission records the matched price and rejections."""
+    order_manager = OrderManager(client, Mock(), default_slippage=Decimal("0.05"))
+
+    order = order_manager.create_fok_order("111", "YES", Decimal("0.50"), Decimal("15"))
+    assert await order_manager.submit_order(order) is True
+    assert order.fill_price == Decimal("7.3") / Decimal("15")
+    assert order.order_id in exchange.orders
+
+    too_big = order_manager.create_fok_order("111", "YES", Decimal("0.48"), Decimal("50"))
+    assert await order_manager.submit_order(too_big) is False
+    assert too_big.error_message == ERR_FOK
+
+
+class _BuyYesStrategy(ResolutionFarmingAdapter):
+    """Buys each market's YES token at the best ask through OrderManager."""
+
+    name = "buy_yes"
+
+    def __init__(self, order_manager, clob_client, amount, ledger=None):
+        super().__init__(engine=None, order_manager=order_manager, ledger=ledger)
+        self.clob_client = clob_client
+        self.amount = amount
+
+    async def scan(self, markets):
+        self._markets = {m.market_id: m for m in markets}
+        candidates = []
+        for market in markets:
+            ask = Decimal(self.clob_client.get_price(market.yes_token_id, "BUY")["price"])
+            candidates.append(Opportunity(
+                opportunity_id=f"opp_{uuid.uuid4().hex[:8]}", market_id=market.market_id,
+                strategy=self.name, timestamp=datetime.now(), yes_price=ask, no_price=1 - ask,
+                yes_fee=Decimal("0"), no_fee=Decimal("0"), total_cost=ask, expected_profit=Decimal("0"),
+                profit_percentage=Decimal("0"), position_size=self.amount, gas_estimate=0, side="YES",
+            ))
+        return candidates
+
+    def size(self, candidate, bankroll):
+        return self.amount
+
+
+def buy_yes_strategy(context):
+    """Strategy factory referenced from config as tests.test_clob_simulator:buy_yes_strategy."""
+    return _BuyYesStrategy(context.order_manager, context.clob_client, Decimal("4.8"), context.ledger)
+
+
+@contextlib.contextmanager
+def _offline_orchestrator(exchange, tmp_path, monkeypatch):
+    """MainOrchestrator with the simulated CLOB and real OrderManager/MarketParser."""
+    monkeypatch.chdir(tmp_path)
+    config = Mock(spec=Config)
+    config.private_key = "0x" + "1" * 64
+    config.wallet_address = ALICE
+    config.polygon_rpc_url = "http://localhost:8545"
+    config.polymarket_api_url = "sim://clob"
+    config.chain_id = 137
+    config.dry_run = False
+    config.nvidia_api_key = "test_key"
+    config.llm_backends = {}
+    config.llm_backend_order = []
+    config.llm_max_repair_attempts = 2
+    config.llm_rejected_responses_path = None
+    config.llm_decision_audit_path = None
+    config.target_balance = Decimal("100")
+    config.min_balance = Decimal("1")
+    config.max_gas_price_gwei = 800
+    config.max_pending_tx = 5
+    config.circuit_breaker_threshold = 10
+    config.prometheus_port = 9090
+    config.sns_alert_topic = ""
+    config.scan_interval_seconds = 2
+    config.enabled_strategies = ["tests.test_clob_simulator:buy_yes_strategy"]
+    config.market_data_recording = False
+    config.ledger_db_path = str(tmp_path / "ledger.db")
+    config.redemption_interval_seconds = 0
+    config.ledger_reconcile_interval_seconds = 0
+    config.min_priority_fee_gwei = 30
+    config.fee_blocks_ahead = 3
+    config.max_fee_replacements = 5
+    config.auto_replace_stuck_tx = False
+    config.tx_journal_db_path = str(tmp_path / "tx_journal.db")
+    config.wallets = []
+    config.dashboard_enabled = False
+    config.config_file = None
+    config.alert_channels = {}
+    config.alert_routes = {}
+    config.alert_dedup_seconds = 300
+    config.alert_rate_limit_per_minute = 20
+
+    web3 = Mock()
+    web3.eth.account.from_key.return_value = SimpleNamespace(address=ALICE)
+    mocked = [
+        "Web3", "TransactionManager", "PositionMerger", "AISafetyGuard", "FundManager", "AutoBridgeManager",
+        "InternalArbitrageEngine", "MonitoringSystem", "TradeHistoryDB", "TradeStatisticsTracker",
+        "StatusDashboard", "CircuitBreaker", "FlashCrashStrategy", "LLMDecisionEngineV2",
+        "NegRiskArbitrageEngine", "PortfolioRiskManager",
+    ]
+    with contextlib.ExitStack() as stack:
+        mocks = {name: stack.enter_context(patch(f"src.main_orchestrator.{name}")) for name in mocked}
+        mocks["Web3"].return_value = web3
+        stack.enter_context(patch("src.main_orchestrator.ClobClient", exchange.client_factory(address=ALICE)))
+        stack.enter_context(patch("src.wallet_verifier.WalletVerifier.verify_wallet_address", return_value=True))
+        stack.enter_context(patch("src.wallet_type_detector.WalletTypeDetector"))
+        # Gamma API unreachable offline -> falls back to the (simulated) CLOB markets
+        stack.enter_context(patch("requests.get", side_effect=ConnectionError("offline")))
+
+        from src.main_orchestrator import MainOrchestrator
+        orchestrator = MainOrchestrator(config)
+        orchestrator.circuit_breaker.is_open = False
+        exchange.fund(orchestrator.funder_address, collateral="20")
+        yield orchestrator
+
+
+@pytest.mark.asyncio
+async def test_orchestrator_scan_trades_against_simulated_clob(exchange, tmp_path, monkeypatch):
+    """A full scan places a real FOK order on the simulated CLOB and records the fill."""
+    with _offline_orchestrator(exchange, tmp_path, monkeypatch) as orchestrator:
+        await orchestrator._scan_and_execute()
+
+        result = orchestrator.trade_history.insert_trade.call_args[0][0]
+        funder = orchestrator.funder_address
+        held = orchestrator.position_ledger.position("111")
+
+    assert result.status == "success"
+    assert result.yes_fill_price == Decimal("0.48")
+    assert result.net_profit == Decimal("0")  # Realized on redemption
+    assert (held.shares, held.avg_price) == (Decimal("10"), Decimal("0.48"))
+    assert exchange.orders[result.yes_order_id].status == "MATCHED"
+    assert exchange.balance(funder, "111") == Decimal("10")
+    assert exchange.balance(funder) == Decimal("15.2")
+
+
+@pytest.mark.asyncio
+async def test_orchestrator_scan_records_killed_fok(exchange, tmp_path, monkeypatch):
+    """When depth disappears, the FOK order is killed and the trade fails cleanly."""
+    exchange.cancel(None, [o.order_id for o in exchange.open_orders(token_id="111") if o.side == "SELL"])
+    exchange.seed_book("111", asks=[["0.48", "5"]])
+
+    with _offline_orchestrator(exchange, tmp_path, monkeypatch) as orchestrator:
+        await orchestrator._scan_and_execute()
+        result = orchestrator.trade_history.insert_trade.call_args[0][0]
+        funder = orchestrator.funder_address
+
+    assert result.status == "failed"
+    assert exchange.trades == []
+    assert exchange.balance(funder) == Decimal("20")
+    orchestrator.circuit_breaker.record_failure.assert_called_once()
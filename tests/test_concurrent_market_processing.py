@@ -34,7 +34,7 @@ def strategy(mock_clob_client):
          patch('src.multi_timeframe_analyzer.MultiTimeframeAnalyzer'), \
          patch('src.order_book_analyzer.OrderBookAnalyzer'), \
          patch('src.historical_success_tracker.HistoricalSuccessTracker'), \
-         patch('src.reinforcement_learning_engine.ReinforcementLearningEngine'), \
+         patch('src.continuous_rl_engine.ContinuousRLEngine'), \
          patch('src.ensemble_decision_engine.EnsembleDecisionEngine'), \
          patch('src.context_optimizer.ContextOptimizer'):
         
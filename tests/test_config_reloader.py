@@ -0,0 +1,220 @@
+"""
+Tests for configuration hot-reload.
+
+Tests:
+- Config.load: CONFIG_FILE selects the YAML file, which is remembered for reloads
+- Hot-reloadable edits applied and audited; check() only reloads changed files
+- Invalid edits rejected through Config._validate, running config kept
+- Startup-only edits reported as restart required, secret values kept out of the audit log
+- Rollback when applying fails
+- Orchestrator and strategy adapters take over reloaded settings
+"""
+
+import dataclasses
+import json
+import os
+from decimal import Decimal
+from types import SimpleNamespace
+from unittest.mock import Mock
+
+import pytest
+import yaml
+
+from config.config import Config
+from src.config_reloader import APPLIED, REJECTED, RESTART_REQUIRED, ConfigReloader
+from src.error_recovery import CircuitBreaker
+from src.main_orchestrator import MainOrchestrator
+from src.strategy_registry import FifteenMinuteCryptoAdapter, MarketMakingAdapter, NegRiskArbitrageAdapter
+
+SETTINGS = {
+    "private_key": "0x" + "11" * 32,
+    "wallet_address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+    "polygon_rpc_url": "https://polygon-rpc.com",
+    "min_profit_threshold": "0.005",
+    "scan_interval_seconds": 2,
+    "min_balance": 1.0,
+    "target_balance": 10.0,
+    "withdraw_limit": 100.0,
+}
+
+
+def write_config(path, **overrides):
+    settings = dict(SETTINGS, **overrides)
+    path.write_text(yaml.safe_dump(settings))
+    stat = os.stat(path)
+    os.utime(path, ns=(stat.st_atime_ns, stat.st_mtime_ns + 1_000_000_000))  # Visible to check()
+
+
+def audit_entries(path):
+    return [json.loads(line) for line in path.read_text().splitlines()]
+
+
+@pytest.fixture
+def config_path(tmp_path):
+    path = tmp_path / "config.yaml"
+    write_config(path)
+    return path
+
+
+@pytest.fixture
+def reloader(tmp_path, config_path):
+    applied = []
+    reloader = ConfigReloader(
+        Config.from_yaml(str(config_path)), applied.append, audit_log_path=str(tmp_path / "audit.jsonl")
+    )
+    reloader.applied = applied
+    return reloader
+
+
+def test_config_file_env_selects_yaml(monkeypatch, config_path):
+    monkeypatch.setenv("CONFIG_FILE", str(config_path))
+
+    config = Config.load()
+
+    assert config.config_file == str(config_path)
+    assert config.min_profit_threshold == Decimal("0.005")
+
+
+def test_hot_edit_is_applied_and_audited(reloader, config_path, tmp_path):
+    assert reloader.check() is None  # Unchanged since load
+
+    write_config(config_path, scan_interval_seconds=5, min_profit_threshold=0.01)
+    entry = reloader.check()
+
+    assert entry["status"] == APPLIED
+    assert reloader.config.scan_interval_seconds == 5
+    assert reloader.config.min_profit_threshold == Decimal("0.01")
+    assert reloader.applied == [reloader.config]
+    assert audit_entries(tmp_path / "audit.jsonl")[0]["changes"] == {
+        "min_profit_threshold": {"old": "0.005", "new": "0.01"},
+        "scan_interval_seconds": {"old": 2, "new": 5},
+    }
+    assert reloader.check() is None
+
+
+@pytest.mark.parametrize("overrides, error", [
+    ({"target_balance": 0.5}, "target_balance"),
+    ({"scan_interval_seconds": 0}, "scan_interval_seconds must be positive"),
+    ({"min_profit_threshold": "lots"}, "min_profit_threshold must be a number"),
+    ({"scan_intervall_seconds": 5}, "Unknown configuration options: scan_intervall_seconds"),
+])
+def test_invalid_edit_keeps_running_config(reloader, config_path, tmp_path, overrides, error):
+    previous = reloader.config
+    write_config(config_path, **overrides)
+
+    entry = reloader.check()
+
+    assert entry["status"] == REJECTED
+    assert error in entry["error"]
+    assert reloader.config is previous
+    assert reloader.applied == []
+    assert audit_entries(tmp_path / "audit.jsonl")[0]["status"] == REJECTED
+
+
+def test_startup_only_edits_need_restart(reloader, config_path, tmp_path):
+    write_config(config_path, dashboard_port=8181, private_key="0x" + "22" * 32)
+    entry = reloader.check()
+
+    assert entry["status"] == RESTART_REQUIRED
+    assert entry["restart_required"] == ["private_key", "dashboard_port"]
+    assert reloader.applied == []
+    assert reloader.config.dashboard_port == 8080
+
+    write_config(config_path, dashboard_port=8181, private_key="0x" + "22" * 32, scan_interval_seconds=3)
+    entry = reloader.check()
+
+    assert entry["status"] == APPLIED
+    assert list(entry["changes"]) == ["scan_interval_seconds"]
+    assert reloader.config.private_key == "0x" + "11" * 32
+    assert "22" * 32 not in (tmp_path / "audit.jsonl").read_text()
+
+
+def test_unapplied_sizes_need_restart(reloader, config_path):
+    """Sizes no running component reads are not reported as applied."""
+    write_config(config_path, stake_amount="20", min_position_size="0.5", flash_crash_trade_size="3.0")
+    entry = reloader.check()
+
+    assert entry["status"] == RESTART_REQUIRED
+    assert entry["restart_required"] == ["stake_amount", "min_position_size", "flash_crash_trade_size"]
+    assert reloader.applied == []
+
+
+def test_failed_apply_rolls_back(reloader, config_path):
+    previous = reloader.config
+    calls = []
+
+    def apply(config):
+        calls.append(config)
+        if len(calls) == 1:
+            raise RuntimeError("component refused")
+
+    reloader.apply = apply
+    write_config(config_path, scan_interval_seconds=5)
+    entry = reloader.check()
+
+    assert entry["status"] == REJECTED
+    assert "component refused" in entry["error"]
+    assert calls[-1] is previous
+    assert reloader.config is previous
+
+
+# ============================================================================
+# Components
+# ============================================================================
+
+def test_orchestrator_applies_reloaded_settings(config_path):
+    config = dataclasses.replace(
+        Config.from_yaml(str(config_path)), scan_interval_seconds=7, circuit_breaker_threshold=4,
+        max_fee_replacements=2, min_priority_fee_gwei=40, max_position_size=Decimal("8")
+    )
+    strategy = Mock()
+    wallet = SimpleNamespace(
+        transaction_manager=SimpleNamespace(auto_replace=True, escalator=SimpleNamespace(max_escalations=5)),
+        fund_manager=SimpleNamespace(min_balance=None, target_balance=None, withdraw_limit=None),
+        strategies=[strategy],
+    )
+    orchestrator = SimpleNamespace(
+        config=None, _base_scan_interval=2, _current_scan_interval=1,
+        circuit_breaker=CircuitBreaker(failure_threshold=10),
+        ai_safety_guard=SimpleNamespace(),
+        fee_estimator=SimpleNamespace(),
+        internal_arbitrage=SimpleNamespace(),
+        negrisk_arbitrage=SimpleNamespace(),
+        wallets=[wallet],
+    )
+
+    MainOrchestrator._apply_config(orchestrator, config)
+
+    assert orchestrator.config is config
+    assert (orchestrator._base_scan_interval, orchestrator._current_scan_interval) == (7, 7)
+    assert orchestrator.circuit_breaker.failure_threshold == 4
+    assert orchestrator.fee_estimator.min_priority_fee == 40 * 10 ** 9
+    assert orchestrator.internal_arbitrage.max_position_size == Decimal("8")
+    assert orchestrator.negrisk_arbitrage.max_position_size == Decimal("8")
+    assert wallet.transaction_manager.escalator.max_escalations == 2
+    assert wallet.fund_manager.target_balance == Decimal("10.0")
+    strategy.apply_config.assert_called_once_with(config)
+
+
+def test_strategy_adapters_apply_config():
+    config = SimpleNamespace(
+        min_profit_threshold=Decimal("0.02"), max_position_size=Decimal("8"),
+        market_making_half_spread=0.03, market_making_quote_size=5.0,
+        market_making_max_inventory=20.0, market_making_pull_minutes=1.0,
+        fifteen_min_maker_entries=False, fifteen_min_maker_ttl_seconds=60,
+        flash_crash_drop_threshold=0.1, flash_crash_lookback_seconds=5,
+        rl_exploration_rate=0.0, rl_max_daily_explorations=0,
+    )
+    negrisk = NegRiskArbitrageAdapter(SimpleNamespace())
+    negrisk.apply_config(config)
+    assert (negrisk.engine.min_profit_threshold, negrisk.engine.max_position_size) == (Decimal("0.02"), Decimal("8"))
+
+    fifteen_min = FifteenMinuteCryptoAdapter(SimpleNamespace(order_manager=None, rl_engine=Mock()))
+    fifteen_min.apply_config(config)
+    assert (fifteen_min.strategy.flash_crash_drop_threshold, fifteen_min.strategy.flash_crash_lookback_seconds) == (0.1, 5)
+
+    market_making = MarketMakingAdapter(SimpleNamespace())
+    market_making.apply_config(config)
+    assert market_making.strategy.half_spread == market_making.strategy.max_skew == Decimal("0.03")
+    assert market_making.strategy.max_inventory == Decimal("20.0")
+    assert market_making.strategy.pull_minutes == 1.0
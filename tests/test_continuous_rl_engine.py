@@ -0,0 +1,322 @@
+"""
+Tests for the continuous-state RL engine and offline policy training.
+
+Tests:
+- Features oriented to the token being bought
+- Chronological train/evaluation split
+- Ridge-regression policy picks the strategy with the best predicted return, or skips
+- Held-out evaluation: agreed, skipped and unscored trades
+- Frozen versions are numbered, never overwritten and loaded by version
+- Exploration: rate cap, value margin and daily budget
+- Experience logged by the 15-minute strategy with the entry features of the position
+- Training CLI: freeze, blocked freeze, evaluation of a frozen version
+"""
+
+import json
+import random
+from datetime import datetime, timezone
+from decimal import Decimal
+from unittest.mock import Mock
+
+import pytest
+
+from src.continuous_rl_engine import (
+    SKIP,
+    ContinuousRLEngine,
+    Experience,
+    ExperienceLog,
+    MarketFeatures,
+    PolicyStore,
+    evaluate_policy,
+    load_experiences,
+    split_experiences,
+    train_policy,
+)
+from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy, Position
+from src.rl_training import main
+from src.trade_outcome_store import TradeOutcomeStore
+
+
+def features(spot_move=0.0, minutes_to_close=8.0, book_imbalance=0.0, spread=0.01):
+    return MarketFeatures(spot_move, minutes_to_close, book_imbalance, spread)
+
+
+def synthetic_experiences(count=200, seed=7, start=1_790_000_000.0):
+    """latency earns 10x the spot move; directional loses 1% whatever the state."""
+    rng = random.Random(seed)
+    experiences = []
+    for i in range(count):
+        move = rng.uniform(-0.004, 0.004)
+        state = features(move, rng.uniform(1, 14), rng.uniform(-1, 1), rng.uniform(0.01, 0.05))
+        strategy = "latency" if i % 2 else "directional"
+        reward = 10 * move if strategy == "latency" else -0.01
+        experiences.append(Experience(start + i * 60, "BTC", strategy, state, reward + rng.gauss(0, 0.001)))
+    return experiences
+
+
+@pytest.fixture
+def policy():
+    return train_policy(synthetic_experiences(), l2=0.1)
+
+
+# ============================================================================
+# Features and data
+# ============================================================================
+
+def test_features_are_oriented_to_the_token_bought():
+    up = MarketFeatures.observe("UP", Decimal("0.002"), 7.5, Decimal("300"), Decimal("100"), Decimal("0.02"))
+    down = MarketFeatures.observe("DOWN", Decimal("0.002"), -1, 0, 0, "0.03")
+
+    assert up.to_dict() == pytest.approx(
+        {"spot_move": 0.002, "minutes_to_close": 7.5, "book_imbalance": 0.5, "spread": 0.02}
+    )
+    assert (down.spot_move, down.minutes_to_close, down.book_imbalance) == (-0.002, 0.0, 0.0)
+    assert MarketFeatures.observe("UP", None, 5, 1, 1, 0).spot_move == 0.0
+    assert MarketFeatures.from_dict(up.to_dict()) == up
+
+
+def test_split_holds_out_the_newest_trades():
+    experiences = synthetic_experiences(10)
+    shuffled = list(reversed(experiences))
+
+    train, held_out = split_experiences(shuffled, 0.25)
+
+    assert train == experiences[:7] and held_out == experiences[7:]
+    with pytest.raises(ValueError):
+        split_experiences(experiences, 1.0)
+
+
+def test_experience_logs_merge_in_time_order(tmp_path):
+    live, backtest = ExperienceLog(str(tmp_path / "live.jsonl")), ExperienceLog(str(tmp_path / "bt.jsonl"))
+    for experience in synthetic_experiences(6):
+        (live if experience.timestamp % 120 else backtest).append(experience)
+    with open(live.path, "a") as f:
+        f.write("not json\n")
+        f.write(json.dumps({**synthetic_experiences(1)[0].to_dict(), "schema_version": 99}) + "\n")
+
+    merged = load_experiences([str(live.path), str(backtest.path), str(tmp_path / "missing.jsonl")])
+
+    assert merged == synthetic_experiences(6)
+
+
+# ============================================================================
+# Training and evaluation
+# ============================================================================
+
+def test_policy_values_strategies_by_state(policy):
+    assert set(policy.strategies) == {"latency", "directional"}
+    assert policy.value(features(0.003), "latency") == pytest.approx(0.03, abs=0.005)
+    assert policy.value(features(0.003), "directional") == pytest.approx(-0.01, abs=0.003)
+
+    assert policy.greedy(features(0.003))[0] == "latency"
+    strategy, value = policy.greedy(features(-0.003))
+    assert strategy == SKIP and value < 0  # Nothing is expected to profit
+    assert policy.greedy(features(0.003), ["directional"])[0] == SKIP
+    assert policy.confidence(0.03) > policy.confidence(0.001) >= 50
+
+
+def test_strategies_without_enough_trades_are_left_out():
+    experiences = synthetic_experiences(60)
+    experiences += [Experience(2e9, "ETH", "sum_to_one", features(), 0.5)] * 5
+
+    policy = train_policy(experiences, min_samples=20)
+
+    assert "sum_to_one" not in policy.strategies
+    assert policy.metadata["training"]["strategies"]["sum_to_one"] == {
+        "trades": 5, "mean_reward": 0.5, "trained": False
+    }
+    with pytest.raises(ValueError):
+        train_policy(experiences[:10], min_samples=20)
+
+
+def test_evaluation_scores_agreed_and_skipped_trades(policy):
+    held_out = [
+        Experience(1, "BTC", "latency", features(0.003), 0.02),  # Agreed
+        Experience(2, "BTC", "latency", features(-0.003), -0.03),  # Policy skips
+        Experience(3, "BTC", "directional", features(0.003), 0.05),  # Policy would pick latency
+    ]
+
+    evaluation = evaluate_policy(policy, held_out)
+
+    assert evaluation["policy"] == pytest.approx({
+        "agreed": 1, "skipped": 1, "unscored": 1, "mean_reward": 0.01, "total_reward": 0.02, "win_rate": 1.0,
+    })
+    assert evaluation["logged"]["mean_reward"] == pytest.approx(0.04 / 3)
+    latency = evaluation["strategies"]["latency"]
+    assert latency["trades"] == 2 and latency["mse"] < latency["baseline_mse"]
+
+
+# ============================================================================
+# Frozen versions
+# ============================================================================
+
+def test_frozen_versions_are_numbered_and_immutable(tmp_path, policy):
+    store = PolicyStore(str(tmp_path / "policies"))
+    assert store.load() is None
+
+    assert store.freeze(policy) == 1
+    assert store.freeze(train_policy(synthetic_experiences(seed=8))) == 2
+    assert store.versions() == [1, 2]
+
+    first = store.load(1)
+    assert first.version == 1 and first.weights == policy.weights and "frozen_at" in first.metadata
+    assert store.load().version == 2
+    with pytest.raises(FileNotFoundError):
+        store.load(3)
+    with pytest.raises(FileExistsError):
+        with open(tmp_path / "policies" / "policy_v0001.json", "x"):
+            pass
+
+
+def test_engine_without_a_policy_casts_a_neutral_vote(tmp_path):
+    engine = ContinuousRLEngine(policy_dir=str(tmp_path / "none"), experience_path=None)
+
+    assert engine.select_strategy("BTC", features(0.003)) == (SKIP, 0.0)
+
+
+def test_engine_with_a_missing_pinned_version_has_no_policy(tmp_path, policy):
+    PolicyStore(str(tmp_path)).freeze(policy)
+
+    assert ContinuousRLEngine(policy_dir=str(tmp_path), policy_version=4).policy is None
+    assert ContinuousRLEngine(policy_dir=str(tmp_path), policy_version=1).policy_version == 1
+
+
+def test_engine_selects_greedily_from_the_frozen_policy(tmp_path, policy):
+    PolicyStore(str(tmp_path)).freeze(policy)
+    engine = ContinuousRLEngine(policy_dir=str(tmp_path), experience_path=None)
+
+    strategy, confidence = engine.select_strategy("BTC", features(0.003).to_dict())
+    assert strategy == "latency" and confidence > 50
+    assert engine.select_strategy("BTC", features(0.003), available_strategies=["directional"])[0] == SKIP
+    assert engine.select_strategy("BTC", None) == (SKIP, 0.0)
+
+
+# ============================================================================
+# Exploration
+# ============================================================================
+
+def test_exploration_rate_is_capped():
+    engine = ContinuousRLEngine(policy_dir=None, experience_path=None, exploration_rate=0.5)
+    assert engine.exploration_rate == ContinuousRLEngine.MAX_EXPLORATION_RATE
+
+    engine.configure_exploration(-1, -3)
+    assert (engine.exploration_rate, engine.max_daily_explorations) == (0.0, 0)
+
+
+def test_exploration_stays_near_the_best_choice_and_within_budget(tmp_path, policy):
+    PolicyStore(str(tmp_path)).freeze(policy)
+    engine = ContinuousRLEngine(
+        policy_dir=str(tmp_path), experience_path=None, exploration_rate=0.05,
+        exploration_margin=0.02, max_daily_explorations=2, seed=1
+    )
+    engine._rng.random = lambda: 0.0  # Every selection rolls for exploration
+
+    # Latency is far above directional (-1%): nothing is close enough to explore
+    assert engine.select_strategy("BTC", features(0.004))[0] == "latency"
+    assert engine.total_explorations == 0
+
+    # Directional alone loses 1%, within the 2% margin of skipping
+    flat = {"asset": "BTC", "features": features(0.0), "available_strategies": ["directional"]}
+    assert engine.select_strategy(**flat) == ("directional", 50.0)
+    assert engine.select_strategy(**flat) == ("directional", 50.0)
+    assert engine.select_strategy(**flat)[0] == SKIP  # Daily budget spent
+    assert (engine.explorations_today, engine.total_explorations) == (2, 2)
+
+    engine._exploration_day = datetime(2020, 1, 1, tzinfo=timezone.utc).date()
+    assert engine.select_strategy(**flat)[0] == "directional"  # New day, new budget
+
+
+# ============================================================================
+# Experience from the strategy
+# ============================================================================
+
+def test_engine_logs_experience_with_the_policy_version(tmp_path, policy):
+    PolicyStore(str(tmp_path / "policies")).freeze(policy)
+    log_path = tmp_path / "experience.jsonl"
+    engine = ContinuousRLEngine(policy_dir=str(tmp_path / "policies"), experience_path=str(log_path), source="dry_run")
+
+    engine.record_outcome("BTC", "latency", 0.02, features(0.001).to_dict(), market_id="m1")
+    engine.record_outcome("BTC", "latency", 0.02, None)  # No entry features: nothing to learn from
+
+    [experience] = ExperienceLog(str(log_path)).load()
+    assert (experience.strategy, experience.reward, experience.market_id) == ("latency", 0.02, "m1")
+    assert (experience.source, experience.policy_version) == ("dry_run", 1)
+    assert experience.features == features(0.001)
+
+
+def test_strategy_logs_closed_trades_with_entry_features(tmp_path):
+    log_path = tmp_path / "experience.jsonl"
+    positions_file = str(tmp_path / "positions.json")
+    store_path = str(tmp_path / "learning_store.db")
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, enable_adaptive_learning=False,
+        rl_engine=ContinuousRLEngine(policy_dir=None, experience_path=str(log_path)),
+        positions_file=positions_file, outcome_store=TradeOutcomeStore(store_path)
+    )
+    entry = features(0.002, 9.0, 0.25, 0.02).to_dict()
+    strategy.positions["tok"] = Position(
+        token_id="tok", side="UP", entry_price=Decimal("0.40"), size=Decimal("10"),
+        entry_time=datetime.now(timezone.utc), market_id="m1", asset="BTC", strategy="latency", features=entry
+    )
+    strategy._save_positions()
+
+    restored = FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, enable_adaptive_learning=False,
+        rl_engine=strategy.rl_engine, positions_file=positions_file, outcome_store=TradeOutcomeStore(store_path)
+    )
+    position = restored.positions["tok"]
+    assert position.features == entry
+
+    restored._record_trade_outcome(
+        asset="BTC", side="UP", strategy="latency", entry_price=Decimal("0.40"), exit_price=Decimal("0.44"),
+        profit_pct=Decimal("0.10"), hold_time_minutes=3.0, exit_reason="take_profit",
+        market_id=position.market_id, features=position.features
+    )
+
+    [experience] = ExperienceLog(str(log_path)).load()
+    assert (experience.strategy, experience.reward, experience.source) == ("latency", 0.10, "live")
+    assert experience.features.to_dict() == entry
+
+
+# ============================================================================
+# Training CLI
+# ============================================================================
+
+@pytest.fixture
+def experience_file(tmp_path):
+    log = ExperienceLog(str(tmp_path / "experience.jsonl"))
+    for experience in synthetic_experiences(300):
+        log.append(experience)
+    return log.path
+
+
+def test_cli_trains_evaluates_and_freezes(tmp_path, experience_file, capsys):
+    policy_dir = tmp_path / "policies"
+
+    assert main(["--experience", str(experience_file), "--policy-dir", str(policy_dir), "--freeze",
+                 "--require-improvement", "--output", str(tmp_path / "report.json")]) == 0
+
+    report = json.loads((tmp_path / "report.json").read_text())
+    assert report["split"]["train"] == 240 and report["split"]["eval"] == 60
+    assert report["frozen_version"] == 1
+    assert report["evaluation"]["policy"]["mean_reward"] > report["evaluation"]["logged"]["mean_reward"]
+    assert PolicyStore(str(policy_dir)).load(1).metadata["training"]["sources"] == {"live": 240}
+
+    capsys.readouterr()
+    assert main(["--policy-dir", str(policy_dir), "--list"]) == 0
+    [listed] = json.loads(capsys.readouterr().out)["versions"]
+    assert listed["version"] == 1 and listed["trades"] == 240
+
+    assert main(["--experience", str(experience_file), "--policy-dir", str(policy_dir), "--evaluate", "1"]) == 0
+    assert json.loads(capsys.readouterr().out)["evaluation"]["trades"] == 300
+
+
+def test_cli_blocks_freezing_without_enough_held_out_trades(tmp_path, experience_file, capsys):
+    policy_dir = tmp_path / "policies"
+
+    assert main(["--experience", str(experience_file), "--policy-dir", str(policy_dir), "--freeze",
+                 "--eval-fraction", "0.05", "--min-eval-trades", "50"]) == 1
+
+    report = json.loads(capsys.readouterr().out)
+    assert report["frozen_version"] is None and report["freeze_blocked"] == ["15 held-out trades, 50 required"]
+    assert PolicyStore(str(policy_dir)).versions() == []
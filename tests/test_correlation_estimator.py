@@ -0,0 +1,130 @@
+"""
+Unit tests for rolling empirical correlations.
+
+Tests:
+- Correlations estimated from price history (coupled and independent assets)
+- Shrinkage toward the prior with little data, prior fallback without data
+- Regime-change detection when a pair decouples
+- New assets picked up automatically, bars kept beyond the tick buffer
+- CorrelationAnalyzer limits driven by the estimated matrix
+"""
+
+import math
+import random
+from collections import deque
+from datetime import datetime, timedelta
+from decimal import Decimal
+
+import pytest
+
+from src.correlation_analyzer import CorrelationAnalyzer, Position
+from src.correlation_estimator import RollingCorrelationEstimator
+
+NOW = datetime(2026, 10, 16, 12, 0, 0)
+
+
+def make_history(bars: int, decouple_last: int = 0, seed: int = 7):
+    """
+    BTC random walk; ETH follows BTC closely, SOL is independent.
+
+    ETH moves on its own for the last `decouple_last` bars.
+    """
+    rng = random.Random(seed)
+    prices = {"BTC": 60000.0, "ETH": 3000.0, "SOL": 150.0}
+    history = {asset: deque() for asset in prices}
+    for i in range(bars):
+        timestamp = NOW - timedelta(seconds=5 * (bars - i)) + timedelta(seconds=1)
+        btc = rng.gauss(0, 0.001)
+        eth = rng.gauss(0, 0.001) if i >= bars - decouple_last else btc + rng.gauss(0, 0.0002)
+        moves = {"BTC": btc, "ETH": eth, "SOL": rng.gauss(0, 0.001)}
+        for asset, move in moves.items():
+            prices[asset] *= math.exp(move)
+            history[asset].append((timestamp, Decimal(str(round(prices[asset], 6)))))
+    return history
+
+
+def make_estimator(history, **kwargs):
+    kwargs.setdefault("clock", lambda: NOW)
+    return RollingCorrelationEstimator(history, **kwargs)
+
+
+# ============================================================================
+# Estimation
+# ============================================================================
+
+def test_estimates_follow_price_history():
+    estimator = make_estimator(make_history(720))
+
+    assert estimator.get_correlation("BTC", "ETH") > 0.85
+    assert abs(estimator.get_correlation("BTC", "SOL")) < 0.3
+    assert estimator.get_correlation("ETH", "BTC") == estimator.get_correlation("BTC", "ETH")
+    assert estimator.regime_changes() == []
+
+
+def test_shrinkage_pulls_small_samples_toward_prior():
+    """With few observations the estimate sits between the sample and the prior."""
+    history = make_history(30)
+    prior = {("BTC", "SOL"): 0.9}
+    raw = make_estimator(history, prior=prior, shrinkage_strength=0).get_estimate("BTC", "SOL")
+    shrunk = make_estimator(history, prior=prior, shrinkage_strength=50).get_estimate("BTC", "SOL")
+
+    sample = raw.window_correlations[300]
+    n = raw.observations
+    assert shrunk.correlation == pytest.approx((n * sample + 50 * 0.9) / (n + 50))
+    assert abs(shrunk.correlation - 0.9) < abs(sample - 0.9)
+
+
+def test_prior_used_without_enough_data():
+    estimator = make_estimator(make_history(5), prior={("BTC", "ETH"): 0.85})
+
+    assert estimator.get_estimate("BTC", "ETH").is_prior
+    assert estimator.get_correlation("BTC", "ETH") == 0.85
+    assert estimator.get_correlation("BTC", "DOGE") == 0.5  # Unknown asset: default prior
+
+
+def test_regime_change_detected_when_pair_decouples():
+    """ETH stops following BTC for the last 5 minutes; the short window is trusted."""
+    estimator = make_estimator(make_history(720, decouple_last=60))
+
+    estimate = estimator.get_estimate("BTC", "ETH")
+    assert estimate.regime_change
+    assert estimator.regime_changes() == [("BTC", "ETH")]
+    assert estimate.window_correlations[3600] > 0.7
+    assert estimate.correlation < 0.5
+
+
+def test_new_assets_and_bars_beyond_tick_buffer():
+    """Assets added to the feed are covered; bars survive the feed's deque eviction."""
+    history = make_history(720)
+    history["DOGE"] = deque([(NOW - timedelta(seconds=30), Decimal("0.1"))])
+    estimator = make_estimator(history)
+    before = estimator.get_correlation("BTC", "ETH")
+
+    assert "DOGE" in estimator.assets
+    assert ("BTC", "DOGE") in estimator.matrix()
+
+    for ticks in history.values():
+        while len(ticks) > 10:
+            ticks.popleft()
+    assert estimator.update(NOW)[("BTC", "ETH")].correlation == pytest.approx(before)
+
+
+# ============================================================================
+# CorrelationAnalyzer integration
+# ============================================================================
+
+def test_analyzer_limits_use_estimated_matrix():
+    """An independent asset no longer counts as 75% correlated with BTC."""
+    feed = type("Feed", (), {"price_history": make_history(720)})()
+    live = CorrelationAnalyzer.from_price_feed(feed, clock=lambda: NOW)
+    static = CorrelationAnalyzer()
+    positions = [Position("1", "BTC", "UP", Decimal("260"), "m1")]
+
+    assert live.get_correlation("BTC", "SOL") < 0.5 < static.get_correlation("BTC", "SOL")
+    assert live.calculate_correlated_exposure(positions, Decimal("500"), "SOL") < \
+        static.calculate_correlated_exposure(positions, Decimal("500"), "SOL")
+
+    can_add_live, _ = live.check_can_add_position(positions, Decimal("500"), "SOL", Decimal("150"))
+    can_add_static, reason = static.check_can_add_position(positions, Decimal("500"), "SOL", Decimal("150"))
+    assert can_add_live and not can_add_static
+    assert "Correlated exposure" in reason
@@ -924,7 +924,7 @@ class TestCrossPlatformProfitAccounting:
         if opportunity is not None:
             # Calculate expected costs manually
             pm_trading_fee = engine._calculate_fee(pm_price)
-            kalshi_trading_fee = engine._calculate_fee(kalshi_price)
+            kalshi_trading_fee = engine.kalshi_fee_model.fee_rate(kalshi_price)
             
             # Total cost should include:
             # 1. Base prices
@@ -32,7 +32,7 @@ def mock_strategy():
          patch('src.fifteen_min_crypto_strategy.MultiTimeframeAnalyzer'), \
          patch('src.fifteen_min_crypto_strategy.OrderBookAnalyzer'), \
          patch('src.fifteen_min_crypto_strategy.HistoricalSuccessTracker'), \
-         patch('src.fifteen_min_crypto_strategy.ReinforcementLearningEngine'), \
+         patch('src.fifteen_min_crypto_strategy.ContinuousRLEngine'), \
          patch('src.fast_execution_engine.FastExecutionEngine'), \
          patch('src.polymarket_websocket_feed.PolymarketWebSocketFeed'), \
          patch('src.fifteen_min_crypto_strategy.EnsembleDecisionEngine'), \
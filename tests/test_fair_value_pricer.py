@@ -0,0 +1,251 @@
+"""
+Unit tests for the fair-value pricer for binary strike markets.
+
+Tests:
+- Digital-option probabilities (moneyness, time decay, above/below, expiry)
+- Confidence bands and edge helpers
+- Market pricing from the question, end time and Binance feed
+- Volatility sources (MultiTimeframeAnalyzer, Binance ticks, defaults)
+- Implied strike round trip and the per-market up/down strike
+- LatencyArbitrageEngine and ResolutionFarmingEngine using the model
+"""
+
+import math
+
+import pytest
+from datetime import datetime, timedelta
+from decimal import Decimal
+from unittest.mock import Mock
+
+from src.fair_value_pricer import (
+    DEFAULT_ANNUAL_VOLATILITY,
+    SECONDS_PER_YEAR,
+    FairValuePricer,
+    realized_volatility,
+    strike_direction,
+)
+from src.fifteen_min_crypto_strategy import BinancePriceFeed
+from src.latency_arbitrage_engine import LatencyArbitrageEngine, PriceMovement
+from src.models import Market
+from src.multi_timeframe_analyzer import MultiTimeframeAnalyzer
+from src.resolution_farming_engine import ResolutionFarmingEngine
+
+
+@pytest.fixture
+def pricer():
+    """Pricer without feeds (given volatility only)."""
+    return FairValuePricer()
+
+
+def create_market(question, seconds_to_close=900, yes_price="0.50", asset="BTC"):
+    """Strike market closing in seconds_to_close."""
+    yes_price = Decimal(yes_price)
+    return Market(
+        market_id="0xstrike",
+        question=question,
+        asset=asset,
+        outcomes=["YES", "NO"],
+        yes_price=yes_price,
+        no_price=Decimal("1") - yes_price,
+        yes_token_id="yes",
+        no_token_id="no",
+        volume=Decimal("10000"),
+        liquidity=Decimal("5000"),
+        end_time=datetime.now() + timedelta(seconds=seconds_to_close),
+        resolution_source="CEX",
+    )
+
+
+# ============================================================================
+# Pricing model
+# ============================================================================
+
+def test_at_the_money_is_a_coin_flip(pricer):
+    """Spot at the strike prices close to 50%."""
+    fair = pricer.price(Decimal("100000"), Decimal("100000"), 900, 0.5)
+
+    assert abs(fair.probability - Decimal("0.5")) < Decimal("0.001")
+
+
+def test_probability_follows_moneyness_and_time(pricer):
+    """Deep in/out of the money is near certain; more time pulls towards 50%."""
+    itm = pricer.price(Decimal("101000"), Decimal("100000"), 900, 0.5)
+    otm = pricer.price(Decimal("99000"), Decimal("100000"), 900, 0.5)
+    itm_long = pricer.price(Decimal("101000"), Decimal("100000"), 24 * 3600, 0.5)
+
+    assert itm.probability > Decimal("0.99")
+    assert otm.probability < Decimal("0.01")
+    assert Decimal("0.5") < itm_long.probability < itm.probability
+
+
+def test_below_market_is_complement(pricer):
+    """A "below" market's YES is the "above" market's NO, band included."""
+    above = pricer.price(Decimal("100100"), Decimal("100000"), 900, 0.5)
+    below = pricer.price(Decimal("100100"), Decimal("100000"), 900, 0.5, direction="below")
+
+    assert below.probability == Decimal("1") - above.probability
+    assert below.lower == Decimal("1") - above.upper
+    assert below.upper == Decimal("1") - above.lower
+
+
+def test_expired_market_is_settled(pricer):
+    """With no time left the outcome is known."""
+    assert pricer.price(Decimal("100001"), Decimal("100000"), 0, 0.5).probability == Decimal("1")
+    assert pricer.price(Decimal("99999"), Decimal("100000"), -5, 0.5).probability == Decimal("0")
+
+
+def test_invalid_inputs_rejected(pricer):
+    """Non-positive prices and unknown directions are errors."""
+    with pytest.raises(ValueError):
+        pricer.price(Decimal("0"), Decimal("100000"), 900, 0.5)
+    with pytest.raises(ValueError):
+        pricer.price(Decimal("100000"), Decimal("100000"), 900, 0.5, direction="sideways")
+
+
+# ============================================================================
+# Confidence bands and edge
+# ============================================================================
+
+def test_band_contains_probability_and_widens_on_default_volatility(pricer):
+    """The band brackets the estimate and is wider when volatility is a guess."""
+    given = pricer.price(Decimal("100100"), Decimal("100000"), 900, 0.5)
+    default = pricer.price(Decimal("100100"), Decimal("100000"), 900, 0.5, volatility_source="default")
+
+    assert given.lower < given.probability < given.upper
+    assert default.band_width > given.band_width
+
+
+def test_edge_for_both_sides(pricer):
+    """Edge is model value minus price; the conservative edge uses the band."""
+    fair = pricer.price(Decimal("100100"), Decimal("100000"), 900, 0.5)
+
+    assert fair.edge(Decimal("0.60"), "YES") == fair.probability - Decimal("0.60")
+    assert fair.edge(Decimal("0.20"), "NO") == Decimal("1") - fair.probability - Decimal("0.20")
+    assert fair.conservative_edge(Decimal("0.60"), "YES") == fair.lower - Decimal("0.60")
+    assert fair.conservative_edge(Decimal("0.20"), "DOWN") == Decimal("1") - fair.upper - Decimal("0.20")
+
+
+# ============================================================================
+# Markets, feeds and volatility
+# ============================================================================
+
+def test_strike_direction_parsing():
+    """Above/below keywords decide which side of the strike pays."""
+    assert strike_direction("Will BTC be above $95,000 at 3pm?") == "above"
+    assert strike_direction("Will ETH trade under $3,000?") == "below"
+    assert strike_direction("Bitcoin Up or Down?") is None
+
+
+def test_price_market_uses_question_and_feed():
+    """Strike, direction and spot come from the market and the Binance feed."""
+    feed = BinancePriceFeed()
+    feed._update_price("BTC", Decimal("96000"))
+    pricer = FairValuePricer(price_feed=feed)
+
+    fair = pricer.price_market(create_market("Will BTC be above $95,000 in 15 minutes?"))
+
+    assert fair.strike == Decimal("95000") and fair.spot == Decimal("96000")
+    assert fair.direction == "above" and fair.volatility_source == "default"
+    assert 880 < fair.seconds_to_close <= 900
+    assert fair.probability > Decimal("0.99")
+
+    assert pricer.price_market(create_market("Will BTC be up at 3pm?")) is None
+    assert FairValuePricer().price_market(create_market("Will BTC be above $95,000?")) is None
+
+
+def test_volatility_sources_in_order():
+    """Analyzer volatility beats Binance ticks, which beat the per-asset default."""
+    feed = BinancePriceFeed()
+    start = datetime.now() - timedelta(seconds=30)
+    for i in range(30):
+        feed.price_history["BTC"].append((start + timedelta(seconds=i), Decimal("100000") + (i % 2) * 50))
+
+    assert FairValuePricer().volatility("BTC") == (DEFAULT_ANNUAL_VOLATILITY["BTC"], "default")
+    assert FairValuePricer(price_feed=feed).volatility("BTC")[1] == "ticks"
+
+    analyzer = Mock(get_realized_volatility=Mock(return_value=0.42))
+    assert FairValuePricer(price_feed=feed, volatility_analyzer=analyzer).volatility("BTC") == (0.42, "realized")
+
+
+def test_multi_timeframe_realized_volatility():
+    """MultiTimeframeAnalyzer annualizes squared log returns over elapsed time."""
+    analyzer = MultiTimeframeAnalyzer()
+    start = datetime(2026, 1, 1, 12, 0, 0)
+    prices = [Decimal("100"), Decimal("101")] * 10
+    for i, price in enumerate(prices):
+        analyzer.update_price("BTC", price, timestamp=start + timedelta(seconds=i))
+
+    expected = math.sqrt(19 * math.log(1.01) ** 2 / 19 * SECONDS_PER_YEAR)
+    assert analyzer.get_realized_volatility("BTC") == pytest.approx(expected)
+    assert analyzer.get_realized_volatility("ETH") is None
+    assert realized_volatility([(start, Decimal("100"))] * 20) is None
+
+
+def test_implied_strike_round_trip(pricer):
+    """Pricing at the implied strike gives back the observed probability."""
+    strike = pricer.implied_strike(Decimal("100000"), Decimal("0.70"), 600, 0.5)
+
+    assert strike < Decimal("100000")
+    assert pricer.price(Decimal("100000"), strike, 600, 0.5).probability == Decimal("0.7")
+
+
+def test_up_down_strike_is_kept_until_close():
+    """The first observed UP price sets the strike; later calls follow the spot and expired strikes are dropped."""
+    feed = BinancePriceFeed()
+    feed._update_price("BTC", Decimal("100000"))
+    pricer = FairValuePricer(price_feed=feed)
+    end_time = datetime.now() + timedelta(minutes=10)
+
+    assert pricer.price_up_down("m1", "BTC", end_time, Decimal("0.50")).probability == Decimal("0.5")
+    feed._update_price("BTC", Decimal("100200"))
+    assert pricer.price_up_down("m1", "BTC", end_time, Decimal("0.30")).probability > Decimal("0.7")
+
+    pricer._up_down_strikes["old"] = (Decimal("90000"), datetime.now() - timedelta(minutes=1))
+    pricer.price_up_down("m2", "BTC", end_time, Decimal("0.50"))
+    assert set(pricer._up_down_strikes) == {"m1", "m2"}
+
+    pricer.forget_strike("m1")
+    assert pricer.price_up_down("m1", "BTC", end_time, Decimal("0.40")).probability == Decimal("0.4")
+    assert pricer.price_up_down("m3", "BTC", datetime.now() - timedelta(seconds=1), Decimal("0.50")) is None
+
+
+# ============================================================================
+# Strategies using the model
+# ============================================================================
+
+def _latency_engine():
+    return LatencyArbitrageEngine(
+        cex_feeds={}, clob_client=None, order_manager=None, ai_safety_guard=None, kelly_sizer=None
+    )
+
+
+def _movement(new_price):
+    return PriceMovement(
+        asset="BTC", old_price=Decimal(new_price) - 500, new_price=Decimal(new_price),
+        timestamp=datetime.now(), exchange="Binance"
+    )
+
+
+def test_latency_expected_price_from_model():
+    """Latency arbitrage expects the model price only once the model is decisive."""
+    engine = _latency_engine()
+    market = create_market("Will BTC be above $95,000 in 15 minutes?")
+
+    assert engine._calculate_expected_price(_movement("97000"), market) > Decimal("0.99")
+    assert engine._calculate_expected_price(_movement("93000"), market) < Decimal("0.01")
+    assert engine._calculate_expected_price(_movement("95050"), market) is None
+
+
+def test_resolution_farming_requires_model_edge():
+    """With a pricer, a spot barely past the strike is no longer treated as certain."""
+    def engine(spot):
+        feeds = {"BTC": Mock(get_latest_price=Mock(return_value=spot))}
+        return ResolutionFarmingEngine(
+            cex_feeds=feeds, ai_safety_guard=Mock(_has_ambiguous_keywords=Mock(return_value=False)),
+            fair_value_pricer=FairValuePricer()
+        )
+
+    market = create_market("Will BTC be above $95,000 at close?", seconds_to_close=60, yes_price="0.97")
+
+    assert engine(95010)._has_model_edge(market, "YES", Decimal("0.97")) is False
+    assert engine(96000)._has_model_edge(market, "YES", Decimal("0.97")) is True
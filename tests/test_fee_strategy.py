@@ -0,0 +1,244 @@
+"""
+Tests for EIP-1559 fees and transaction replacement.
+
+Tests:
+- Priority fee percentiles per urgency, ignoring empty blocks, with a floor
+- Base fee prediction (headroom for blocks_ahead) and next-block derivation
+- Fee cap: clamp headroom, refuse if the next block needs more
+- Legacy gasPrice fallback when the node has no fee history
+- GasPriceEscalator.escalate_fees bounds
+- TransactionManager: EIP-1559 send, bounded replacement, cancellation self-send,
+  confirmation through the replacement chain
+"""
+
+import pytest
+from unittest.mock import Mock
+
+from src.error_recovery import GasPriceEscalator
+from src.fee_strategy import GWEI, Eip1559FeeEstimator, FeeCapExceededError, next_base_fee
+from src.transaction_manager import (
+    TransactionCancelledError,
+    TransactionError,
+    TransactionManager,
+)
+
+WALLET = "0x1234567890123456789012345678901234567890"
+
+
+class FakeEth:
+    """eth interface with a scripted fee history and receipts."""
+
+    def __init__(self, base_fees, ratios, rewards):
+        self.history = {"baseFeePerGas": base_fees, "gasUsedRatio": ratios, "reward": rewards}
+        self.gas_price = 80 * GWEI
+        self.chain_id = 137
+        self.sent = []
+        self.receipts = {}
+        self.fee_history_calls = 0
+
+    def fee_history(self, block_count, newest, percentiles):
+        self.fee_history_calls += 1
+        if self.history is None:
+            raise ValueError("method eth_feeHistory not supported")
+        return self.history
+
+    def get_transaction_count(self, address, block="latest"):
+        return 7
+
+    def send_raw_transaction(self, raw_tx):
+        self.sent.append(raw_tx)
+        return bytes([len(self.sent)]) * 32
+
+    def get_transaction_receipt(self, tx_hash):
+        return self.receipts.get(tx_hash)
+
+
+class FakeAccount:
+    address = WALLET
+
+    def __init__(self):
+        self.signed = []
+
+    def sign_transaction(self, tx):
+        self.signed.append(dict(tx))
+        return Mock(raw_transaction=bytes(len(self.signed)))
+
+
+def make_web3(base_fees=None, ratios=None, rewards=None):
+    # Three blocks; the node also reports the next block's base fee (100 gwei)
+    base_fees = base_fees or [90 * GWEI, 95 * GWEI, 98 * GWEI, 100 * GWEI]
+    ratios = ratios or [0.6, 0.0, 0.7]
+    rewards = rewards or [
+        [30 * GWEI, 40 * GWEI, 80 * GWEI],
+        [0, 0, 0],  # Empty block
+        [32 * GWEI, 50 * GWEI, 120 * GWEI],
+    ]
+    web3 = Mock()
+    web3.eth = FakeEth(base_fees, ratios, rewards)
+    return web3
+
+
+@pytest.fixture
+def web3():
+    return make_web3()
+
+
+@pytest.fixture
+def estimator(web3):
+    return Eip1559FeeEstimator(web3, blocks_ahead=3, min_priority_fee_gwei=30, cache_seconds=0)
+
+
+@pytest.fixture
+def manager(web3, estimator):
+    return TransactionManager(web3, FakeAccount(), fee_estimator=estimator, max_replacements=2)
+
+
+TX = {"to": "0x" + "ab" * 20, "value": 0, "gas": 100000, "chainId": 137}
+
+
+# ============================================================================
+# Estimator
+# ============================================================================
+
+def test_priority_fee_percentiles_skip_empty_blocks(estimator):
+    assert estimator.estimate("low").max_priority_fee == 31 * GWEI  # median(30, 32)
+    assert estimator.estimate("normal").max_priority_fee == 45 * GWEI
+    assert estimator.estimate("high").max_priority_fee == 100 * GWEI
+    with pytest.raises(ValueError, match="Unknown urgency"):
+        estimator.estimate("urgent")
+
+
+def test_priority_fee_floor(web3):
+    estimator = Eip1559FeeEstimator(web3, min_priority_fee_gwei=60, cache_seconds=0)
+    assert estimator.estimate("normal").max_priority_fee == 60 * GWEI
+
+
+def test_max_fee_covers_base_fee_growth(estimator):
+    fees = estimator.estimate("normal")
+    assert fees.base_fee == 100 * GWEI
+    # Two more blocks of +12.5% after the next one
+    assert fees.max_fee == int(100 * GWEI * 1.125 ** 2) + 45 * GWEI
+    assert fees.tx_params() == {"maxFeePerGas": fees.max_fee, "maxPriorityFeePerGas": 45 * GWEI}
+
+
+def test_next_base_fee_derived_when_missing():
+    assert next_base_fee(100 * GWEI, 1.0) == int(112.5 * GWEI)
+    assert next_base_fee(100 * GWEI, 0.0) == int(87.5 * GWEI)
+    assert next_base_fee(100 * GWEI, 0.5) == 100 * GWEI
+
+    web3 = make_web3(base_fees=[90 * GWEI, 95 * GWEI, 100 * GWEI])  # No next-block entry
+    estimator = Eip1559FeeEstimator(web3, cache_seconds=0)
+    assert estimator.estimate().base_fee == next_base_fee(100 * GWEI, 0.7)
+
+
+def test_fee_cap_clamps_headroom_or_refuses(web3):
+    clamped = Eip1559FeeEstimator(web3, max_fee_cap_gwei=150, cache_seconds=0).estimate("normal")
+    assert clamped.max_fee == 150 * GWEI
+    assert clamped.max_priority_fee == 45 * GWEI
+
+    with pytest.raises(FeeCapExceededError):
+        Eip1559FeeEstimator(web3, max_fee_cap_gwei=120, cache_seconds=0).estimate("normal")
+
+
+def test_legacy_fallback_without_fee_history(web3):
+    web3.eth.history = None
+    fees = Eip1559FeeEstimator(web3, cache_seconds=0).estimate()
+    assert fees.legacy
+    assert fees.tx_params() == {"gasPrice": 80 * GWEI}
+
+
+def test_fee_history_cached(web3):
+    now = [0.0]
+    estimator = Eip1559FeeEstimator(web3, cache_seconds=2, clock=lambda: now[0])
+    estimator.estimate("low")
+    estimator.estimate("high")
+    assert web3.eth.fee_history_calls == 1
+    now[0] = 3.0
+    estimator.estimate()
+    assert web3.eth.fee_history_calls == 2
+
+
+def test_escalate_fees_bounded():
+    escalator = GasPriceEscalator(escalation_factor=1.1, max_escalations=2)
+    assert escalator.escalate_fees(100, 31, "nonce:1") == (110, 35)  # Rounded up
+    escalator.escalate_fees(110, 35, "nonce:1")
+    with pytest.raises(ValueError, match="Maximum"):
+        escalator.escalate_fees(121, 39, "nonce:1")
+    with pytest.raises(ValueError, match="cap"):
+        escalator.escalate_fees(100 * GWEI, 30 * GWEI, max_fee_cap=105 * GWEI)
+
+
+# ============================================================================
+# TransactionManager
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_send_uses_eip1559_fees(manager):
+    tx_hash = await manager.send_transaction(dict(TX), urgency="high")
+    signed = manager.wallet.signed[-1]
+    assert signed["maxPriorityFeePerGas"] == 100 * GWEI
+    assert "gasPrice" not in signed
+    assert signed["nonce"] == 7
+    assert manager._pending_transactions[tx_hash].is_eip1559
+
+
+@pytest.mark.asyncio
+async def test_send_refused_above_fee_cap_keeps_nonce_free(web3):
+    estimator = Eip1559FeeEstimator(web3, max_fee_cap_gwei=120, cache_seconds=0)
+    manager = TransactionManager(web3, FakeAccount(), fee_estimator=estimator)
+    with pytest.raises(TransactionError, match="exceed cap"):
+        await manager.send_transaction(dict(TX))
+    assert manager._pending_nonces == set()
+
+
+@pytest.mark.asyncio
+async def test_replacement_bumps_fees_and_is_bounded(manager, web3):
+    original = await manager.send_transaction(dict(TX), urgency="low")
+    first = manager.wallet.signed[-1]
+
+    replacement = await manager.resubmit_stuck_transaction(original, urgency="low")
+    bumped = manager.wallet.signed[-1]
+    assert bumped["nonce"] == first["nonce"]
+    assert bumped["maxPriorityFeePerGas"] >= first["maxPriorityFeePerGas"] * 11 // 10
+    assert bumped["maxFeePerGas"] >= first["maxFeePerGas"] * 11 // 10
+
+    # Network tips jumped: the replacement follows them
+    web3.eth.history["reward"] = [[200 * GWEI] * 3, [0, 0, 0], [200 * GWEI] * 3]
+    await manager.resubmit_stuck_transaction(replacement, urgency="low")
+    assert manager.wallet.signed[-1]["maxPriorityFeePerGas"] == 200 * GWEI
+
+    with pytest.raises(TransactionError, match="Maximum"):
+        await manager.resubmit_stuck_transaction(original)
+
+
+@pytest.mark.asyncio
+async def test_confirmation_follows_replacements(manager, web3):
+    original = await manager.send_transaction(dict(TX))
+    await manager.resubmit_stuck_transaction(original)
+
+    web3.eth.receipts[original] = {"status": 1, "blockNumber": 1}  # The original won the race
+    receipt = await manager.wait_for_confirmation(original, timeout=5)
+    assert receipt["blockNumber"] == 1
+    assert manager.get_pending_count() == 0
+    assert manager._pending_nonces == set()
+
+
+@pytest.mark.asyncio
+async def test_cancel_sends_empty_self_transfer(manager, web3):
+    original = await manager.send_transaction(dict(TX))
+    cancel_hash = await manager.cancel_transaction(original)
+
+    cancel = manager.wallet.signed[-1]
+    assert cancel["to"] == WALLET
+    assert cancel["value"] == 0
+    assert cancel["gas"] == 21000
+    assert cancel["nonce"] == 7
+    assert "data" not in cancel
+
+    web3.eth.receipts[cancel_hash] = {"status": 1, "blockNumber": 2}
+    with pytest.raises(TransactionCancelledError):
+        await manager.wait_for_confirmation(original, timeout=5)
+    assert manager.get_pending_count() == 0
+
+    with pytest.raises(TransactionError, match="not found"):
+        await manager.cancel_transaction(original)
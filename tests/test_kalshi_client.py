@@ -0,0 +1,342 @@
+"""
+Unit tests for the Kalshi client and its recorded-fixture double.
+
+Tests:
+- Fee model (per-order rounding up to the cent)
+- RSA-PSS request signing
+- REST parsing: markets, order books, order placement body
+- WebSocket snapshots, deltas and sequence gaps
+- FixtureKalshiClient FOK fills, rejects and balance
+- KalshiOrderManager and CrossPlatformArbitrageEngine end to end on the fixture
+- Market pairing on asset, strike, direction and close time
+- Registry factory and config validation
+"""
+
+import base64
+import json
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from pathlib import Path
+from unittest.mock import AsyncMock, Mock
+
+import pytest
+
+from src.kalshi_client import (
+    KalshiClient,
+    KalshiFeeModel,
+    KalshiMarket,
+    KalshiOrderBook,
+    KalshiOrderError,
+    KalshiOrderManager,
+    KalshiSigner,
+    KalshiWebSocketFeed,
+    dollars_to_cents,
+)
+from src.kalshi_simulator import FixtureKalshiClient
+from src.models import Market, SafetyDecision
+
+FIXTURE = Path(__file__).parent / "fixtures" / "kalshi_markets.json"
+BTC = "KXBTCD-26OCT1617-T99999.99"
+
+
+@pytest.fixture
+def fixture_client():
+    return FixtureKalshiClient.from_fixture(str(FIXTURE))
+
+
+@pytest.fixture
+def pem():
+    from cryptography.hazmat.primitives import serialization
+    from cryptography.hazmat.primitives.asymmetric import rsa
+
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    return key.private_bytes(
+        serialization.Encoding.PEM, serialization.PrivateFormat.PKCS8, serialization.NoEncryption()
+    ).decode()
+
+
+# ============================================================================
+# Fees and signing
+# ============================================================================
+
+def test_fee_rounds_up_per_order():
+    """Taker fee is 7% of C * P * (1 - P), rounded up to the next cent."""
+    fees = KalshiFeeModel()
+    assert fees.fee(10, Decimal("0.50")) == Decimal("0.18")  # 0.175
+    assert fees.fee(1, Decimal("0.99")) == Decimal("0.01")  # 0.000693
+    assert fees.fee(100, Decimal("0.50"), maker=True) == Decimal("0")
+    assert fees.fee_rate(Decimal("0.40")) == Decimal("0.042")
+
+
+def test_prices_must_be_whole_cents():
+    assert dollars_to_cents(Decimal("0.48")) == 48
+    for bad in (Decimal("0.485"), Decimal("0"), Decimal("1")):
+        with pytest.raises(ValueError):
+            dollars_to_cents(bad)
+
+
+def test_signer_signs_timestamp_method_and_path(pem):
+    """The signature covers timestamp + METHOD + path without the query string."""
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.asymmetric import padding
+
+    signer = KalshiSigner("key-1", pem)
+    headers = signer.headers("get", "/trade-api/v2/portfolio/balance?x=1", timestamp_ms=1700000000000)
+
+    assert headers["KALSHI-ACCESS-KEY"] == "key-1"
+    assert headers["KALSHI-ACCESS-TIMESTAMP"] == "1700000000000"
+    signer._private_key.public_key().verify(
+        base64.b64decode(headers["KALSHI-ACCESS-SIGNATURE"]),
+        b"1700000000000GET/trade-api/v2/portfolio/balance",
+        padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=padding.PSS.DIGEST_LENGTH),
+        hashes.SHA256()
+    )
+
+
+# ============================================================================
+# REST client
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_get_markets_follows_cursor():
+    client = KalshiClient()
+    markets = json.loads(FIXTURE.read_text())["markets"]
+    client._request = AsyncMock(side_effect=[
+        {"markets": markets[:1], "cursor": "next"},
+        {"markets": markets[1:], "cursor": ""},
+    ])
+
+    result = await client.get_markets(series_ticker="KXBTCD")
+
+    assert [m.ticker for m in result] == [m["ticker"] for m in markets]
+    assert client._request.call_args_list[1].kwargs["params"]["cursor"] == "next"
+    assert result[0].yes_ask == Decimal("0.55") and result[0].no_ask == Decimal("0.48")
+
+
+@pytest.mark.asyncio
+async def test_place_order_sends_side_price_in_cents():
+    client = KalshiClient()
+    client._request = AsyncMock(return_value={"order": {
+        "order_id": "o1", "ticker": BTC, "side": "no", "action": "buy", "status": "executed",
+        "no_price": 48, "initial_count": 10, "fill_count": 10, "remaining_count": 0,
+        "taker_fill_cost": 480, "taker_fees": 18,
+    }})
+
+    order = await client.place_order(BTC, "NO", 10, Decimal("0.48"), client_order_id="c1")
+
+    method, path = client._request.call_args.args
+    body = client._request.call_args.kwargs["body"]
+    assert (method, path) == ("POST", "/portfolio/orders")
+    assert body["no_price"] == 48 and body["side"] == "no" and body["count"] == 10
+    assert body["time_in_force"] == "fill_or_kill" and body["client_order_id"] == "c1"
+    assert order.fill_count == 10 and order.fees == Decimal("0.18")
+    assert order.average_fill_price == Decimal("0.48")
+
+
+def test_market_conversion_skips_unsupported_markets():
+    """Only crypto markets with two-sided asks become bot Markets."""
+    markets = [KalshiMarket.from_api(m) for m in json.loads(FIXTURE.read_text())["markets"]]
+    converted = [m.to_market() for m in markets]
+
+    assert converted[0].asset == "BTC"
+    assert converted[0].yes_token_id == converted[0].no_token_id == BTC
+    assert converted[1] is None  # No quotes
+    assert converted[2] is None  # Weather
+
+
+def test_order_book_asks_mirror_opposite_bids():
+    book = KalshiOrderBook.from_api(BTC, json.loads(FIXTURE.read_text())["orderbooks"][BTC])
+
+    assert book.best_bid("yes") == Decimal("0.52")
+    assert book.best_ask("no") == Decimal("0.48")
+    assert book.best_ask("yes") == Decimal("0.55")
+    assert book.fillable("no", Decimal("0.49")) == 65
+
+
+def test_websocket_snapshot_delta_and_gap():
+    updates = []
+    feed = KalshiWebSocketFeed(Mock(), on_book_update=updates.append)
+
+    assert feed.handle_message({"type": "orderbook_snapshot", "sid": 1, "seq": 1, "msg": {
+        "market_ticker": BTC, "yes": [[52, 25]], "no": [[45, 12]],
+    }})
+    assert feed.handle_message({"type": "orderbook_delta", "sid": 1, "seq": 2, "msg": {
+        "market_ticker": BTC, "side": "yes", "price": 52, "delta": -25,
+    }})
+    assert feed.get_orderbook(BTC).best_bid("yes") is None
+    assert len(updates) == 2
+
+    assert feed.handle_message({"type": "orderbook_delta", "sid": 1, "seq": 4, "msg": {
+        "market_ticker": BTC, "side": "no", "price": 45, "delta": 1,
+    }}) is False
+
+
+# ============================================================================
+# Fixture client
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_fixture_fok_fill_walks_book_and_debits_balance(fixture_client):
+    order = await fixture_client.place_order(BTC, "no", 30, Decimal("0.49"))
+
+    assert order.status == "executed" and order.fill_count == 30
+    assert [(f.price, f.count) for f in fixture_client.fills] == [(Decimal("0.48"), 25), (Decimal("0.49"), 5)]
+    expected_fees = KalshiFeeModel().fee(25, Decimal("0.48")) + KalshiFeeModel().fee(5, Decimal("0.49"))
+    assert order.fees == expected_fees
+    assert fixture_client.balance == Decimal("100") - Decimal("12.00") - Decimal("2.45") - expected_fees
+    assert fixture_client.positions[BTC]["no"] == 30
+    assert (await fixture_client.get_orderbook(BTC)).best_ask("no") == Decimal("0.49")
+
+
+@pytest.mark.asyncio
+async def test_fixture_fok_rejects_without_depth(fixture_client):
+    with pytest.raises(KalshiOrderError) as excinfo:
+        await fixture_client.place_order(BTC, "no", 26, Decimal("0.48"))
+
+    assert excinfo.value.code == "fill_or_kill_insufficient_resting_volume"
+    assert fixture_client.balance == Decimal("100") and fixture_client.fills == []
+
+
+@pytest.mark.asyncio
+async def test_order_manager_records_fill_and_fee(fixture_client):
+    manager = KalshiOrderManager(fixture_client)
+    order = manager.create_fok_order(BTC, "NO", Decimal("0.48"), Decimal("10.7"))
+
+    assert order.size == Decimal("10")
+    assert await manager.submit_order(order) is True
+    assert order.fill_price == Decimal("0.48") and order.order_id.startswith("fixture-")
+    assert manager.fees[order.order_id] == Decimal("0.18")
+
+    too_big = manager.create_fok_order(BTC, "NO", Decimal("0.48"), Decimal("50"))
+    assert await manager.submit_order(too_big) is False
+    assert "fill or kill" in too_big.error_message
+
+
+# ============================================================================
+# Cross-platform arbitrage on the fixture
+# ============================================================================
+
+def _pm_btc_market(yes_price: Decimal, question="Will BTC be above $100,000 on Oct 16 at 5pm ET?",
+                   end_time=datetime(2099, 10, 16, 21, 0, tzinfo=timezone.utc)) -> Market:
+    """Polymarket twin of the fixture's KXBTCD $100,000-or-above contract."""
+    return Market(
+        market_id="pm-btc-100k",
+        question=question,
+        asset="BTC",
+        outcomes=["YES", "NO"],
+        yes_price=yes_price,
+        no_price=1 - yes_price,
+        yes_token_id="pm_yes",
+        no_token_id="pm_no",
+        volume=Decimal("50000"),
+        liquidity=Decimal("20000"),
+        end_time=end_time,
+        resolution_source="chainlink",
+    )
+
+
+def _pm_order_manager():
+    from src.order_manager import OrderManager
+
+    manager = OrderManager(Mock(), Mock())
+
+    async def fill(order):
+        order.filled = True
+        order.fill_price = order.price
+        return True
+
+    manager.submit_order = AsyncMock(side_effect=fill)
+    return manager
+
+
+@pytest.mark.asyncio
+async def test_cross_platform_cycle_trades_against_fixture(fixture_client):
+    """PM YES at 0.40 plus Kalshi NO at 0.48 is arbitrage; both legs fill."""
+    from src.cross_platform_arbitrage_engine import CrossPlatformArbitrageEngine
+    from src.strategy_registry import CrossPlatformArbitrageAdapter
+
+    guard = Mock()
+    guard.validate_trade = AsyncMock(return_value=SafetyDecision(
+        approved=True, reason="ok", timestamp=datetime.now(), checks_performed={}
+    ))
+    kelly = Mock()
+    kelly.calculate_position_size.return_value = Decimal("10")  # USDC: 10 pairs at ~$0.91 each
+    pm_manager = _pm_order_manager()
+    engine = CrossPlatformArbitrageEngine(
+        polymarket_client=Mock(),
+        kalshi_client=fixture_client,
+        polymarket_order_manager=pm_manager,
+        kalshi_order_manager=KalshiOrderManager(fixture_client),
+        ai_safety_guard=guard,
+        kelly_sizer=kelly,
+        kalshi_series=["KXBTCD", "KXETHD"],
+    )
+    adapter = CrossPlatformArbitrageAdapter(engine)
+
+    [result] = await adapter.run_cycle([_pm_btc_market(Decimal("0.40"))], Decimal("100"))
+
+    assert result.status == "success"
+    pm_order = pm_manager.submit_order.call_args.args[0]
+    assert pm_order.market_id == "pm_yes" and pm_order.side == "YES" and pm_order.size == Decimal("10")
+    [fill] = fixture_client.fills
+    assert (fill.side, fill.price, fill.count) == ("no", Decimal("0.48"), 10)
+    assert result.no_fill_price == Decimal("0.48") and result.net_profit > 0
+    assert engine.get_legs(result.opportunity.opportunity_id) is None
+
+    # No edge once Polymarket reprices
+    assert await adapter.run_cycle([_pm_btc_market(Decimal("0.55"))], Decimal("100")) == []
+
+
+@pytest.mark.asyncio
+async def test_cross_platform_pairs_need_same_strike_direction_and_close(fixture_client):
+    """Markets on the same asset with another strike, direction or expiry are not arbitrage."""
+    from src.cross_platform_arbitrage_engine import CrossPlatformArbitrageEngine
+
+    engine = CrossPlatformArbitrageEngine(
+        polymarket_client=Mock(), kalshi_client=fixture_client, polymarket_order_manager=Mock(),
+        kalshi_order_manager=Mock(), ai_safety_guard=Mock(), kelly_sizer=Mock(), kalshi_series=["KXBTCD"],
+    )
+    kalshi_markets = await engine._fetch_kalshi_markets()
+
+    assert len(engine._find_equivalent_markets([_pm_btc_market(Decimal("0.40"))], kalshi_markets)) == 1
+    for market in (
+        _pm_btc_market(Decimal("0.40"), question="Will BTC be above $105,000 on Oct 16 at 5pm ET?"),
+        _pm_btc_market(Decimal("0.40"), question="Will BTC be below $100,000 on Oct 16 at 5pm ET?"),
+        _pm_btc_market(Decimal("0.40"), question="Bitcoin price on Oct 16?"),
+        _pm_btc_market(Decimal("0.40"), end_time=datetime(2099, 10, 17, 21, 0, tzinfo=timezone.utc)),
+    ):
+        assert engine._find_equivalent_markets([market], kalshi_markets) == [], market.question
+
+
+def test_registry_builds_fixture_backed_strategy():
+    from src.strategy_registry import StrategyContext, build_default_registry
+
+    config = Mock(
+        dry_run=True, min_profit_threshold=Decimal("0.005"),
+        kalshi_fixture_path=str(FIXTURE), kalshi_series_tickers=["KXBTCD"]
+    )
+    context = StrategyContext(
+        config=config, clob_client=Mock(), order_manager=Mock(), ai_safety_guard=Mock(),
+        llm_decision_engine=None, initial_capital=Decimal("100"), trade_size=Decimal("5")
+    )
+
+    [strategy] = build_default_registry().build(["cross_platform_arbitrage"], context)
+
+    assert isinstance(strategy.engine.kalshi_client, FixtureKalshiClient)
+    assert strategy.engine.kalshi_order_manager.dry_run is True
+    assert strategy.engine.kalshi_series == ["KXBTCD"]
+
+
+def test_config_requires_kalshi_credentials_or_fixture():
+    from config.config import Config
+
+    base = dict(
+        private_key="0x" + "1" * 64,
+        wallet_address="0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0",
+        polygon_rpc_url="https://polygon-rpc.com",
+        enabled_strategies=["cross_platform_arbitrage"],
+    )
+    with pytest.raises(ValueError, match="kalshi"):
+        Config(**base)
+    Config(**base, kalshi_fixture_path=str(FIXTURE))
+    Config(**base, kalshi_api_key="key", kalshi_private_key_path="/keys/kalshi.pem")
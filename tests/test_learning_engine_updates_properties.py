@@ -1,8 +1,8 @@
 """
 Property-based tests for learning engine updates.
 
-Tests that trade outcomes are recorded to SuperSmart, RL, and Adaptive engines
-and that data persists to disk.
+Tests that trade outcomes recorded in the strategy's TradeOutcomeStore reach the
+SuperSmart, RL, and Adaptive engines and that data persists to disk.
 
 **Validates: Requirements 2.8**
 """
@@ -17,6 +17,10 @@ import os
 import json
 
 from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+from src.trade_outcome_store import TradeOutcomeStore
+
+# One trade outcome store shared by every generated example
+STORE_DIR = tempfile.mkdtemp()
 
 
 # ============================================================================
@@ -66,26 +70,29 @@ def create_mock_strategy_with_learning_engines():
         clob_client=mock_clob,
         trade_size=10.0,
         max_positions=5,
-        enable_adaptive_learning=True  # Enable learning engines
+        enable_adaptive_learning=True,  # Enable learning engines
+        outcome_store=TradeOutcomeStore(os.path.join(STORE_DIR, "learning_store.db"))
     )
     
     # Create mock learning engines
     mock_super_smart = Mock()
-    mock_super_smart.record_trade = Mock()
-    mock_super_smart._save_data = Mock()
+    mock_super_smart.on_trade_outcome = Mock()
     
     mock_rl_engine = Mock()
-    mock_rl_engine.update_q_value = Mock()
-    mock_rl_engine._save_q_table = Mock()
+    mock_rl_engine.on_trade_outcome = Mock()
     
     mock_adaptive = Mock()
-    mock_adaptive.record_trade = Mock()
-    mock_adaptive._save_data = Mock()
+    mock_adaptive.on_trade_outcome = Mock()
     
-    # Inject mocked learning engines
+    # Inject mocked learning engines and subscribe them in place of the real ones
     strategy.super_smart = mock_super_smart
     strategy.rl_engine = mock_rl_engine
     strategy.adaptive_learning = mock_adaptive
+    strategy.outcome_store.subscribers = [
+        mock_super_smart.on_trade_outcome,
+        mock_rl_engine.on_trade_outcome,
+        mock_adaptive.on_trade_outcome
+    ]
     
     return strategy, mock_super_smart, mock_rl_engine, mock_adaptive
 
@@ -123,23 +130,23 @@ def test_property_all_learning_engines_receive_updates(outcome):
     )
     
     # Verify: SuperSmart received update
-    assert mock_super_smart.record_trade.called, \
+    assert mock_super_smart.on_trade_outcome.called, \
         "SuperSmart learning engine should receive trade outcome"
     
     # Verify: RL Engine received update
-    assert mock_rl_engine.update_q_value.called, \
+    assert mock_rl_engine.on_trade_outcome.called, \
         "RL engine should receive trade outcome"
     
     # Verify: Adaptive Learning received update
-    assert mock_adaptive.record_trade.called, \
+    assert mock_adaptive.on_trade_outcome.called, \
         "Adaptive learning engine should receive trade outcome"
     
     # Verify: All three engines were called exactly once
-    assert mock_super_smart.record_trade.call_count == 1, \
+    assert mock_super_smart.on_trade_outcome.call_count == 1, \
         "SuperSmart should be called exactly once"
-    assert mock_rl_engine.update_q_value.call_count == 1, \
+    assert mock_rl_engine.on_trade_outcome.call_count == 1, \
         "RL engine should be called exactly once"
-    assert mock_adaptive.record_trade.call_count == 1, \
+    assert mock_adaptive.on_trade_outcome.call_count == 1, \
         "Adaptive learning should be called exactly once"
 
 
@@ -168,43 +175,23 @@ def test_property_learning_engines_receive_correct_data(outcome):
         exit_reason=outcome["exit_reason"]
     )
     
-    # Verify: SuperSmart received correct parameters
-    super_smart_call = mock_super_smart.record_trade.call_args
+    # Verify: Every engine received the same stored outcome
+    super_smart_call = mock_super_smart.on_trade_outcome.call_args
     assert super_smart_call is not None, "SuperSmart should be called"
-    
-    # Check keyword arguments
-    kwargs = super_smart_call[1]
-    assert kwargs["asset"] == outcome["asset"], "SuperSmart should receive correct asset"
-    assert kwargs["side"] == outcome["side"], "SuperSmart should receive correct side"
-    assert kwargs["entry_price"] == outcome["entry_price"], "SuperSmart should receive correct entry_price"
-    assert kwargs["exit_price"] == outcome["exit_price"], "SuperSmart should receive correct exit_price"
-    assert kwargs["profit_pct"] == outcome["profit_pct"], "SuperSmart should receive correct profit_pct"
-    assert kwargs["strategy_used"] == outcome["strategy"], "SuperSmart should receive correct strategy"
-    
-    # Verify: RL Engine received correct parameters
-    rl_call = mock_rl_engine.update_q_value.call_args
-    assert rl_call is not None, "RL engine should be called"
-    
-    rl_kwargs = rl_call[1]
-    assert rl_kwargs["asset"] == outcome["asset"], "RL engine should receive correct asset"
-    assert rl_kwargs["strategy"] == outcome["strategy"], "RL engine should receive correct strategy"
-    assert rl_kwargs["reward"] == float(outcome["profit_pct"]), "RL engine should receive profit as reward"
-    
-    # Verify: Adaptive Learning received correct parameters
-    adaptive_call = mock_adaptive.record_trade.call_args
-    assert adaptive_call is not None, "Adaptive learning should be called"
-    
-    # Adaptive receives a TradeOutcome object
-    adaptive_args = adaptive_call[0]
-    assert len(adaptive_args) > 0, "Adaptive learning should receive TradeOutcome object"
-    
-    trade_outcome_obj = adaptive_args[0]
-    assert trade_outcome_obj.asset == outcome["asset"], "Adaptive should receive correct asset"
-    assert trade_outcome_obj.side == outcome["side"], "Adaptive should receive correct side"
-    assert trade_outcome_obj.entry_price == outcome["entry_price"], "Adaptive should receive correct entry_price"
-    assert trade_outcome_obj.exit_price == outcome["exit_price"], "Adaptive should receive correct exit_price"
-    assert trade_outcome_obj.profit_pct == outcome["profit_pct"], "Adaptive should receive correct profit_pct"
-    assert trade_outcome_obj.strategy_used == outcome["strategy"], "Adaptive should receive correct strategy"
+    assert mock_rl_engine.on_trade_outcome.call_args == super_smart_call, \
+        "RL engine should receive the same trade outcome"
+    assert mock_adaptive.on_trade_outcome.call_args == super_smart_call, \
+        "Adaptive learning should receive the same trade outcome"
+    
+    # Verify: The outcome carries the trade's data
+    record = super_smart_call[0][0]
+    assert record.asset == outcome["asset"], "Engines should receive correct asset"
+    assert record.side == outcome["side"], "Engines should receive correct side"
+    assert record.entry_price == float(outcome["entry_price"]), "Engines should receive correct entry_price"
+    assert record.exit_price == float(outcome["exit_price"]), "Engines should receive correct exit_price"
+    assert record.profit_pct == float(outcome["profit_pct"]), "Engines should receive correct profit_pct"
+    assert record.strategy == outcome["strategy"], "Engines should receive correct strategy"
+    assert record.exit_reason == outcome["exit_reason"], "Engines should receive correct exit_reason"
 
 
 @given(outcomes=st.lists(trade_outcome_strategy(), min_size=1, max_size=10))
@@ -236,13 +223,13 @@ def test_property_multiple_outcomes_recorded_sequentially(outcomes):
     # Verify: Each engine was called once per outcome
     expected_calls = len(outcomes)
     
-    assert mock_super_smart.record_trade.call_count == expected_calls, \
+    assert mock_super_smart.on_trade_outcome.call_count == expected_calls, \
         f"SuperSmart should be called {expected_calls} times for {expected_calls} outcomes"
     
-    assert mock_rl_engine.update_q_value.call_count == expected_calls, \
+    assert mock_rl_engine.on_trade_outcome.call_count == expected_calls, \
         f"RL engine should be called {expected_calls} times for {expected_calls} outcomes"
     
-    assert mock_adaptive.record_trade.call_count == expected_calls, \
+    assert mock_adaptive.on_trade_outcome.call_count == expected_calls, \
         f"Adaptive learning should be called {expected_calls} times for {expected_calls} outcomes"
 
 
@@ -355,7 +342,7 @@ def test_property_learning_engines_handle_errors_gracefully(outcome):
     strategy, mock_super_smart, mock_rl_engine, mock_adaptive = create_mock_strategy_with_learning_engines()
     
     # Make SuperSmart raise an exception
-    mock_super_smart.record_trade.side_effect = Exception("SuperSmart error")
+    mock_super_smart.on_trade_outcome.side_effect = Exception("SuperSmart error")
     
     # Record trade outcome (should not raise exception)
     try:
@@ -373,12 +360,12 @@ def test_property_learning_engines_handle_errors_gracefully(outcome):
         pytest.fail(f"_record_trade_outcome should not raise exception even if one engine fails: {e}")
     
     # Verify: SuperSmart was called (but failed)
-    assert mock_super_smart.record_trade.called, "SuperSmart should be called"
+    assert mock_super_smart.on_trade_outcome.called, "SuperSmart should be called"
     
     # Verify: RL Engine and Adaptive still received updates
-    assert mock_rl_engine.update_q_value.called, \
+    assert mock_rl_engine.on_trade_outcome.called, \
         "RL engine should still receive update even if SuperSmart fails"
-    assert mock_adaptive.record_trade.called, \
+    assert mock_adaptive.on_trade_outcome.called, \
         "Adaptive learning should still receive update even if SuperSmart fails"
 
 
@@ -0,0 +1,319 @@
+"""
+Tests for the LLM backend layer.
+
+Tests:
+- Config validation of llm_backends and llm_backend_order
+- OpenAI-compatible and Ollama backends against a local stand-in server
+- Per-backend timeouts, token cost accounting and failover order
+- Building backends from config (API keys from environment variables)
+- Decision engine and AI safety guard running on the mock backend, offline
+"""
+
+import json
+import threading
+from datetime import datetime
+from decimal import Decimal
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from types import SimpleNamespace
+
+import pytest
+
+from config.config import Config
+from src.ai_safety_guard import AISafetyGuard
+from src.llm_backends import (
+    LLMBackendChain, LLMBackendError, MockBackend, OllamaBackend, OpenAICompatibleBackend,
+    create_llm_backend, create_llm_backend_chain,
+)
+from src.llm_decision_engine_v2 import LLMDecisionEngineV2, MarketContext, PortfolioState, TradeAction
+from src.models import Market, Opportunity
+
+BUY_YES = json.dumps({
+    "action": "buy_yes", "confidence": 80, "position_size_pct": 3, "order_type": "market",
+    "limit_price": None, "reasoning": "Binance bullish", "risk_assessment": "low", "expected_profit_pct": 2,
+})
+
+
+@pytest.fixture
+def llm_server():
+    """Local chat server stand-in: records (path, JSON body, headers), answers server.reply or server.status."""
+    requests = []
+
+    class Handler(BaseHTTPRequestHandler):
+        def do_POST(self):
+            body = self.rfile.read(int(self.headers.get("Content-Length", 0)))
+            requests.append((self.path, json.loads(body), dict(self.headers)))
+            payload = json.dumps(server.reply).encode() if server.status < 300 else b"overloaded"
+            self.send_response(server.status)
+            self.send_header("Content-Type", "application/json")
+            self.send_header("Content-Length", str(len(payload)))
+            self.end_headers()
+            self.wfile.write(payload)
+
+        def log_message(self, *args):
+            pass
+
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    server.status = 200
+    server.reply = {}
+    server.requests = requests
+    server.url = f"http://127.0.0.1:{server.server_address[1]}"
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    yield server
+    server.shutdown()
+    server.server_close()
+
+
+def make_config(**overrides):
+    return Config(
+        private_key="0x" + "11" * 32,
+        wallet_address="0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+        polygon_rpc_url="https://polygon-rpc.com",
+        **overrides
+    )
+
+
+def openai_reply(text, prompt_tokens=100, completion_tokens=20):
+    return {
+        "choices": [{"message": {"role": "assistant", "content": text}}],
+        "usage": {"prompt_tokens": prompt_tokens, "completion_tokens": completion_tokens},
+    }
+
+
+# ============================================================================
+# Configuration
+# ============================================================================
+
+def test_config_accepts_backends():
+    config = make_config(
+        llm_backends={
+            "local": {"type": "ollama", "model": "llama3.1:8b", "timeout_seconds": 4},
+            "nvidia": {"type": "openai", "base_url": "https://integrate.api.nvidia.com/v1",
+                       "model": "meta/llama-3.1-70b-instruct", "api_key_env": "NVIDIA_API_KEY",
+                       "input_cost_per_1k_tokens": 0.0004},
+            "offline": {"type": "mock"},
+        },
+        llm_backend_order=["local", "nvidia"],
+    )
+    assert config.to_dict()["llm_backends"]["nvidia"] == {"type": "openai", "model": "meta/llama-3.1-70b-instruct"}
+    assert config.to_dict()["llm_backend_order"] == ["local", "nvidia"]
+
+
+@pytest.mark.parametrize("backends, order, error", [
+    ({"a": {"type": "grpc"}}, [], "llm_backends.a: type must be one of"),
+    ({"a": {"type": "openai", "model": "m"}}, [], "llm_backends.a: base_url and model are required"),
+    ({"a": {"type": "ollama"}}, [], "llm_backends.a: model is required"),
+    ({"a": {"type": "mock", "api_key": "secret"}}, [], "unknown options for type mock: ['api_key']"),
+    ({"a": {"type": "mock", "timeout_seconds": 0}}, [], "timeout_seconds must be positive"),
+    ({"a": {"type": "mock", "output_cost_per_1k_tokens": -1}}, [], "output_cost_per_1k_tokens must be non-negative"),
+    ({"a": {"type": "mock"}}, ["a", "b"], "llm_backend_order names unknown backends: ['b']"),
+    ({"a": {"type": "mock"}}, ["a", "a"], "llm_backend_order lists a backend more than once"),
+    ({"a": {"type": "mock", "structured_output": "grammar"}}, [], "structured_output must be one of"),
+])
+def test_config_rejects_invalid_backends(backends, order, error):
+    with pytest.raises(ValueError) as exc_info:
+        make_config(llm_backends=backends, llm_backend_order=order)
+    assert error in str(exc_info.value)
+
+
+# ============================================================================
+# Backends
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_openai_compatible_backend_prices_usage(llm_server):
+    llm_server.reply = openai_reply("YES", prompt_tokens=1500, completion_tokens=500)
+    backend = OpenAICompatibleBackend(
+        "nvidia", "deepseek", f"{llm_server.url}/v1", api_key="key-1",
+        extra_body={"chat_template_kwargs": {"thinking": True}},
+        input_cost_per_1k_tokens=Decimal("0.002"), output_cost_per_1k_tokens=Decimal("0.01"),
+    )
+
+    response = await backend.complete("system", "user", max_tokens=64, top_p=0.95)
+
+    path, body, headers = llm_server.requests[0]
+    assert path == "/v1/chat/completions"
+    assert headers["Authorization"] == "Bearer key-1"
+    assert body["model"] == "deepseek" and body["max_tokens"] == 64 and body["top_p"] == 0.95
+    assert body["chat_template_kwargs"] == {"thinking": True}
+    assert body["messages"] == [{"role": "system", "content": "system"}, {"role": "user", "content": "user"}]
+    assert response.text == "YES"
+    assert response.cost_usd == Decimal("0.008")  # 1.5 * 0.002 + 0.5 * 0.01
+    assert backend.get_statistics()["cost_usd"] == Decimal("0.008")
+
+
+@pytest.mark.asyncio
+async def test_local_backends_need_no_api_key(llm_server):
+    llm_server.reply = {"message": {"role": "assistant", "content": "NO"}, "prompt_eval_count": 42, "eval_count": 2}
+    ollama = OllamaBackend("ollama", "llama3.1:8b", llm_server.url)
+
+    response = await ollama.complete("system", "user", temperature=0.0)
+
+    path, body, headers = llm_server.requests[0]
+    assert path == "/api/chat"
+    assert body["stream"] is False and body["options"] == {"num_predict": 500, "temperature": 0.0}
+    assert "Authorization" not in headers
+    assert (response.text, response.prompt_tokens, response.completion_tokens) == ("NO", 42, 2)
+    assert response.cost_usd == 0
+
+    llm_server.reply = {"choices": [{"message": {"content": "YES"}}]}  # llama.cpp without usage
+    llamacpp = create_llm_backend("cpp", {"type": "llamacpp", "base_url": f"{llm_server.url}/v1"})
+    response = await llamacpp.complete("s" * 400, "u" * 400)
+    assert llm_server.requests[1][0] == "/v1/chat/completions"
+    assert "Authorization" not in llm_server.requests[1][2]
+    assert response.prompt_tokens == 200  # Estimated from the prompt length
+
+
+@pytest.mark.asyncio
+async def test_chain_fails_over_in_order(llm_server):
+    llm_server.status = 503
+    slow = MockBackend("slow", response="YES", delay_seconds=1.0, timeout_seconds=0.05)
+    down = OpenAICompatibleBackend("down", "m", llm_server.url)
+    local = MockBackend("local", response=lambda system, user: f"echo {user}")
+    chain = LLMBackendChain([slow, down, local])
+
+    response = await chain.complete("system", "ping")
+
+    assert (response.backend, response.text) == ("local", "echo ping")
+    stats = chain.get_statistics()
+    assert stats["order"] == ["slow", "down", "local"]
+    assert stats["backends"]["slow"]["timeouts"] == 1
+    assert stats["backends"]["down"]["failures"] == 1
+    assert stats["backends"]["local"]["calls"] == 1 and stats["backends"]["local"]["failures"] == 0
+
+
+@pytest.mark.asyncio
+async def test_chain_raises_when_every_backend_fails():
+    with pytest.raises(LLMBackendError, match="No LLM backends configured"):
+        await LLMBackendChain().complete("system", "user")
+
+    chain = LLMBackendChain([MockBackend("a", error="model not loaded"), MockBackend("b", error="out of memory")])
+    with pytest.raises(LLMBackendError, match="All LLM backends failed \\(a, b\\).*out of memory"):
+        await chain.complete("system", "user")
+
+
+def test_chain_from_config(monkeypatch):
+    monkeypatch.delenv("LLM_TEST_KEY", raising=False)
+    config = SimpleNamespace(
+        llm_backends={
+            "remote": {"type": "openai", "base_url": "https://api.example.com/v1", "model": "m",
+                       "api_key_env": "LLM_TEST_KEY"},
+            "offline": {"type": "mock"},
+            "local": {"type": "ollama", "model": "llama3.1:8b"},
+        },
+        llm_backend_order=["local", "remote", "offline"],
+    )
+    assert create_llm_backend_chain(config).names == ["local", "offline"]  # remote: key not set
+
+    monkeypatch.setenv("LLM_TEST_KEY", "sk-test")
+    chain = create_llm_backend_chain(config)
+    assert chain.names == ["local", "remote", "offline"]
+    assert chain.backends[1].api_key == "sk-test"
+
+    assert create_llm_backend_chain(SimpleNamespace(llm_backends={}, llm_backend_order=[])) is None
+
+
+@pytest.mark.asyncio
+async def test_backends_request_structured_output(llm_server):
+    schema = {"title": "Verdict", "type": "object", "properties": {"safe": {"type": "boolean"}}}
+    llm_server.reply = openai_reply('{"safe": true}')
+    await OpenAICompatibleBackend("a", "m", llm_server.url).complete("s", "u", response_schema=schema)
+    await OpenAICompatibleBackend("b", "m", llm_server.url, structured_output="json_object").complete(
+        "s", "u", response_schema=schema
+    )
+    await OpenAICompatibleBackend("c", "m", llm_server.url, structured_output="none").complete(
+        "s", "u", response_schema=schema
+    )
+    llm_server.reply = {"message": {"content": '{"safe": true}'}}
+    await OllamaBackend("d", "m", llm_server.url).complete("s", "u", response_schema=schema)
+
+    bodies = [body for _, body, _ in llm_server.requests]
+    assert bodies[0]["response_format"] == {
+        "type": "json_schema", "json_schema": {"name": "Verdict", "schema": schema, "strict": True}
+    }
+    assert bodies[1]["response_format"] == {"type": "json_object"}
+    assert "response_format" not in bodies[2]
+    assert bodies[3]["format"] == schema
+
+
+# ============================================================================
+# Consumers
+# ============================================================================
+
+@pytest.fixture
+def market_context():
+    return MarketContext(
+        market_id="m1", question="BTC up in 15 minutes?", asset="BTC",
+        yes_price=Decimal("0.48"), no_price=Decimal("0.50"), yes_liquidity=Decimal("500"),
+        no_liquidity=Decimal("500"), volume_24h=Decimal("10000"), time_to_resolution=10.0,
+        spread=Decimal("0.02"), binance_momentum="bullish",
+    )
+
+
+@pytest.fixture
+def portfolio_state():
+    return PortfolioState(
+        available_balance=Decimal("100"), total_balance=Decimal("100"), open_positions=[],
+        daily_pnl=Decimal("0"), win_rate_today=0.5, trades_today=0, max_position_size=Decimal("5"),
+    )
+
+
+@pytest.mark.asyncio
+async def test_decision_engine_runs_on_mock_backend(market_context, portfolio_state):
+    mock = MockBackend("offline", response=BUY_YES)
+    engine = LLMDecisionEngineV2(backends=LLMBackendChain([mock]), rejected_responses_path=None)
+
+    decision = await engine.make_decision(market_context, portfolio_state, "latency_arbitrage")
+
+    assert decision.action == TradeAction.BUY_YES
+    assert decision.position_size == Decimal("3")
+    assert "LATENCY ARBITRAGE ANALYSIS" in mock.prompts[0][1]
+    assert engine.get_performance_stats()["llm_backends"]["backends"]["offline"]["calls"] == 1
+
+
+@pytest.mark.asyncio
+async def test_decision_engine_without_backends_skips(market_context, portfolio_state):
+    engine = LLMDecisionEngineV2(nvidia_api_key=None, rejected_responses_path=None)
+
+    decision = await engine.make_decision(market_context, portfolio_state, "directional_trend")
+
+    assert engine.backends.names == []
+    assert decision.action == TradeAction.SKIP
+    assert "No LLM backends configured" in decision.reasoning
+
+
+@pytest.fixture
+def market():
+    return Market(
+        market_id="m1", question="Will BTC be above $90,000 at 3:15 PM?", asset="BTC",
+        outcomes=["Yes", "No"], yes_price=Decimal("0.48"), no_price=Decimal("0.49"),
+        yes_token_id="yes", no_token_id="no", volume=Decimal("1000"), liquidity=Decimal("1000"),
+        end_time=datetime(2030, 1, 1), resolution_source="binance",
+    )
+
+
+@pytest.fixture
+def opportunity():
+    return Opportunity(
+        opportunity_id="o1", market_id="m1", strategy="internal", timestamp=datetime.now(),
+        yes_price=Decimal("0.48"), no_price=Decimal("0.49"), yes_fee=Decimal("0"), no_fee=Decimal("0"),
+        total_cost=Decimal("0.97"), expected_profit=Decimal("0.03"), profit_percentage=Decimal("0.03"),
+        position_size=Decimal("1"), gas_estimate=100000,
+    )
+
+
+@pytest.mark.asyncio
+async def test_safety_guard_uses_backends_within_budget(market, opportunity):
+    unsafe = '{"safe": false, "reason": "thin book"}'
+    guard = AISafetyGuard(llm_backends=LLMBackendChain([MockBackend("offline", response=unsafe)]))
+    decision = await guard.validate_trade(opportunity, market, Decimal("100"), 50, 0)
+    assert decision.approved is False and decision.fallback_used is False
+    assert decision.checks_performed["ai_check"] is True
+
+    slow = AISafetyGuard(
+        llm_backends=LLMBackendChain([MockBackend("slow", response=unsafe, delay_seconds=1.0)]), ai_timeout_seconds=0.05
+    )
+    decision = await slow.validate_trade(opportunity, market, Decimal("100"), 50, 0)
+    assert decision.approved is True and decision.fallback_used is True  # Heuristics approve good conditions
+
+    assert AISafetyGuard().llm_backends.names == []
+    assert AISafetyGuard(nvidia_api_key="key").llm_backends.names == ["nvidia:deepseek-ai/deepseek-v3.2"]
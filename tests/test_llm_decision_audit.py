@@ -0,0 +1,227 @@
+"""
+Tests for the LLM decision audit trail and the offline replay harness.
+
+Tests:
+- Decision engine logs prompts, raw answers (rejected ones too), decisions and fallbacks
+- Stored contexts rebuild the exact prompt they were logged with
+- Trade outcomes from the 15-minute strategy are linked to decisions by market
+- Scoring against resolutions and realized trades (outcomes before a decision ignored)
+- Replay CLI: new model and new system prompt, decision diff and scores
+"""
+
+import asyncio
+import json
+from decimal import Decimal
+from unittest.mock import Mock
+
+import pytest
+
+from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+from src.llm_backends import LLMBackendChain, MockBackend
+from src.llm_decision_audit import DecisionAuditLog, TradeOutcome, decision_return, score_decisions
+from src.llm_decision_engine_v2 import LLMDecisionEngineV2, MarketContext, PortfolioState
+from src.llm_replay import load_backends, main
+
+
+def answer(action="buy_yes", **overrides):
+    data = {
+        "action": action, "confidence": 80, "position_size_pct": 3 if action.startswith("buy") else None,
+        "order_type": "market", "limit_price": None, "reasoning": f"{action} reasoning", "risk_assessment": "low",
+        "expected_profit_pct": 2,
+    }
+    data.update(overrides)
+    return json.dumps(data)
+
+
+def market_context(market_id="m1", asset="BTC", yes_price="0.40"):
+    return MarketContext(
+        market_id=market_id, question=f"{asset} up in 15 minutes?", asset=asset,
+        yes_price=Decimal(yes_price), no_price=Decimal("1") - Decimal(yes_price), yes_liquidity=Decimal("500"),
+        no_liquidity=Decimal("500"), volume_24h=Decimal("10000"), time_to_resolution=10.0,
+        spread=Decimal("0.01"), recent_price_changes=[Decimal("0.0012")], binance_price=Decimal("65000.5"),
+        binance_momentum="bullish", price_history_5min=[(5.0, Decimal("64900")), (0.0, Decimal("65000.5"))],
+        volatility_5min=Decimal("0.012"), price_velocity=Decimal("0.0031"),
+    )
+
+
+@pytest.fixture
+def portfolio_state():
+    return PortfolioState(
+        available_balance=Decimal("100"), total_balance=Decimal("100"), open_positions=[{"asset": "ETH"}],
+        daily_pnl=Decimal("0"), win_rate_today=0.5, trades_today=0, max_position_size=Decimal("5"),
+    )
+
+
+@pytest.fixture
+def audit_log(tmp_path):
+    return DecisionAuditLog(str(tmp_path / "decisions.db"))
+
+
+def make_engine(audit_log, responses):
+    mock = MockBackend("offline", model="small-model", response=responses)
+    return LLMDecisionEngineV2(backends=LLMBackendChain([mock]), rejected_responses_path=None, audit_log=audit_log)
+
+
+# ============================================================================
+# Recording
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_engine_logs_prompts_answers_and_decision(audit_log, portfolio_state):
+    engine = make_engine(audit_log, [answer(confidence=150), answer()])
+    context = market_context()
+
+    decision = await engine.make_decision(context, portfolio_state, "directional_trend")
+
+    [entry] = audit_log.get_decisions()
+    assert entry.market_id == "m1" and entry.asset == "BTC" and entry.opportunity_type == "directional_trend"
+    assert entry.system_prompt == engine.DIRECTIONAL_SYSTEM_PROMPT
+    assert entry.raw_responses == [answer(confidence=150), answer()]
+    assert (entry.backend, entry.model, entry.fallback_reason) == ("offline", "small-model", None)
+    assert entry.action == "buy_yes" and entry.confidence == decision.confidence
+    assert Decimal(entry.decision["position_size"]) == decision.position_size
+    # The stored context rebuilds the prompt the model saw
+    assert entry.to_market_context() == context
+    assert engine._build_decision_prompt(
+        entry.to_market_context(), entry.to_portfolio_state(), "directional_trend"
+    ) == entry.user_prompt
+
+
+@pytest.mark.asyncio
+async def test_fallback_decisions_are_logged(audit_log, portfolio_state):
+    engine = make_engine(audit_log, ["not json"])
+    engine.max_repair_attempts = 0
+
+    await engine.make_decision(market_context(), portfolio_state, "latency_arbitrage")
+
+    [entry] = audit_log.get_decisions()
+    assert entry.action == "skip" and entry.fallback_reason == "invalid_response"
+    assert entry.raw_responses == ["not json"]
+
+
+@pytest.mark.asyncio
+async def test_decisions_are_queryable(audit_log, portfolio_state):
+    engine = make_engine(audit_log, answer(action="skip"))
+    for market_id, asset, yes_price in (("m1", "BTC", "0.40"), ("m2", "ETH", "0.40"), ("m3", "BTC", "0.55")):
+        await engine.make_decision(market_context(market_id, asset, yes_price), portfolio_state, "directional_trend")
+
+    assert [e.market_id for e in audit_log.get_decisions(asset="BTC")] == ["m1", "m3"]
+    assert [e.market_id for e in audit_log.get_decisions(limit=2)] == ["m1", "m2"]
+    assert audit_log.get_decisions(opportunity_type="arbitrage") == []
+    first = audit_log.get_decisions()[0]
+    assert [e.market_id for e in audit_log.get_decisions(since=first.created_at + 1e-6)] == ["m2", "m3"]
+
+
+def test_strategy_links_trade_outcomes_to_decisions(tmp_path, audit_log):
+    engine = make_engine(audit_log, answer())
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, enable_adaptive_learning=False,
+        llm_decision_engine=engine, positions_file=str(tmp_path / "positions.json")
+    )
+
+    strategy._record_trade_outcome(
+        asset="BTC", side="UP", strategy="directional", entry_price=Decimal("0.40"), exit_price=Decimal("0.44"),
+        profit_pct=Decimal("0.10"), hold_time_minutes=3.0, exit_reason="take_profit", market_id="m1"
+    )
+
+    [outcome] = audit_log.get_outcomes()["m1"]
+    assert (outcome.side, outcome.profit_pct, outcome.exit_reason) == ("yes", 0.10, "take_profit")
+
+
+# ============================================================================
+# Scoring
+# ============================================================================
+
+def test_decision_return_prefers_resolution_over_realized_trades():
+    context = {"yes_price": "0.40", "no_price": "0.60"}
+    traded = [TradeOutcome("m1", 10.0, side="yes", profit_pct=0.10)]
+    resolved = traded + [TradeOutcome("m1", 20.0, resolved_side="no")]
+
+    assert decision_return("buy_yes", context, []) is None
+    assert decision_return("buy_yes", context, traded) == pytest.approx(0.10)
+    assert decision_return("buy_no", context, traded) is None  # No trade on that side to learn from
+    assert decision_return("skip", context, traded) == 0.0
+    assert decision_return("buy_yes", context, resolved) == -1.0
+    assert decision_return("buy_no", context, resolved) == pytest.approx(0.4 / 0.6)
+
+
+def test_outcomes_before_a_decision_are_ignored():
+    context = {"yes_price": "0.50", "no_price": "0.50"}
+    outcomes = {"m1": [TradeOutcome("m1", 5.0, resolved_side="yes")]}
+    decisions = [
+        {"created_at": 1.0, "market_id": "m1", "market_context": context, "action": "buy_yes", "confidence": 80.0},
+        {"created_at": 9.0, "market_id": "m1", "market_context": context, "action": "buy_yes", "confidence": 60.0},
+    ]
+
+    score = score_decisions(decisions, outcomes)
+
+    assert (score["scored"], score["wins"], score["win_rate"]) == (1, 1, 1.0)
+    assert score["total_return"] == pytest.approx(1.0)
+    assert score["avg_confidence"] == 70.0
+
+
+# ============================================================================
+# Replay
+# ============================================================================
+
+def write_config(tmp_path, backends):
+    path = tmp_path / "config.yaml"
+    path.write_text(json.dumps({"llm_backends": backends}))  # JSON is valid YAML
+    return path
+
+
+def record_corpus(audit_log, portfolio_state, responses, market_ids, opportunity_type="directional_trend"):
+    engine = make_engine(audit_log, responses)
+    for index, market_id in enumerate(market_ids):  # Distinct prices, so no decision comes from the cache
+        context = market_context(market_id, yes_price=str(Decimal("0.40") + Decimal("0.05") * index))
+        asyncio.run(engine.make_decision(context, portfolio_state, opportunity_type))
+
+
+def test_replay_with_a_new_model_diffs_and_scores(tmp_path, audit_log, portfolio_state):
+    record_corpus(audit_log, portfolio_state, [answer("buy_yes"), answer("buy_yes"), answer("skip")], ["m1", "m2", "m3"])
+    audit_log.record_outcome("m1", side="UP", profit_pct=-0.2, exit_reason="stop_loss")
+    audit_log.record_outcome("m2", resolved_side="YES")
+    config = write_config(tmp_path, {
+        "cautious": {"type": "mock", "model": "big-model", "response": answer("skip")},
+        "unused": {"type": "mock", "error": "down"},
+    })
+    output = tmp_path / "report.json"
+
+    assert main(["--db", audit_log.db_path, "--config", str(config), "--backend", "cautious",
+                 "--output", str(output)]) == 0
+
+    report = json.loads(output.read_text())
+    assert report["corpus"] == {"decisions": 3, "markets": 3, "markets_with_outcomes": 2}
+    assert report["baseline"]["scored_trades"] == 2 and report["baseline"]["wins"] == 1
+    assert report["baseline"]["total_return"] == pytest.approx(-0.2 + 0.55 / 0.45)
+    assert report["candidate"]["actions"] == {"skip": 3}
+    assert report["candidate"]["total_return"] == 0.0
+    assert report["changed"] == 2
+    assert report["transitions"] == {"buy_yes -> skip": 2, "skip -> skip": 1}
+    assert [change["market_id"] for change in report["changes"]] == ["m1", "m2"]
+    assert report["changes"][0]["baseline"]["return"] == pytest.approx(-0.2)
+    assert len(audit_log.get_decisions()) == 3  # Replays are not logged
+
+
+def test_replay_with_a_new_system_prompt(tmp_path, audit_log, portfolio_state):
+    record_corpus(audit_log, portfolio_state, answer("skip"), ["m1"], "latency_arbitrage")
+    prompt_file = tmp_path / "prompt.txt"
+    prompt_file.write_text("You are a careful trader.")
+    config = write_config(tmp_path, {"local": {"type": "mock", "response": answer("buy_no")}})
+    output = tmp_path / "report.json"
+
+    main(["--db", audit_log.db_path, "--config", str(config), "--system-prompt", str(prompt_file),
+          "--stored-prompts", "--output", str(output)])
+
+    report = json.loads(output.read_text())
+    assert report["transitions"] == {"skip -> buy_no": 1}
+    assert report["changes"][0]["candidate"]["reasoning"] == "buy_no reasoning"
+
+
+def test_load_backends_rejects_unknown_names(tmp_path):
+    config = write_config(tmp_path, {"local": {"type": "mock"}})
+
+    assert load_backends(None, []) is None
+    assert load_backends(config, []).names == ["local"]
+    with pytest.raises(ValueError, match="Backends not in .*: remote"):
+        load_backends(config, ["remote"])
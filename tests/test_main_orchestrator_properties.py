@@ -54,7 +54,7 @@ def _orchestrator_patches(mock_web3):
         'auto_bridge': patch('src.main_orchestrator.AutoBridgeManager'),
         'negrisk': patch('src.main_orchestrator.NegRiskArbitrageEngine'),
         'risk_mgr': patch('src.main_orchestrator.PortfolioRiskManager'),
-        'fifteen_min': patch('src.main_orchestrator.FifteenMinuteCryptoStrategy'),
+        'fifteen_min': patch('src.fifteen_min_crypto_strategy.FifteenMinuteCryptoStrategy'),
         'llm_engine': patch('src.main_orchestrator.LLMDecisionEngineV2'),
     }
     with contextlib.ExitStack() as stack:
@@ -64,7 +64,7 @@ def _orchestrator_patches(mock_web3):
 
 # Test fixtures
 @pytest.fixture
-def mock_config():
+def mock_config(tmp_path):
     """Create a mock configuration for testing."""
     config = Mock(spec=Config)
     config.private_key = "0x" + "1" * 64
@@ -74,6 +74,11 @@ def mock_config():
     config.polymarket_api_url = "https://clob.polymarket.com"
     config.kalshi_api_key = None
     config.nvidia_api_key = "test_key"
+    config.llm_backends = {}
+    config.llm_backend_order = []
+    config.llm_max_repair_attempts = 2
+    config.llm_rejected_responses_path = None
+    config.llm_decision_audit_path = None
     config.usdc_address = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
     config.ctf_exchange_address = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"
     config.conditional_token_address = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"
@@ -94,6 +99,33 @@ def mock_config():
     config.scan_interval_seconds = 2
     config.heartbeat_interval_seconds = 60
     config.chain_id = 137
+    config.enabled_strategies = ["fifteen_min_crypto"]
+    config.fifteen_min_entry_order = ["flash_crash", "latency", "directional", "sum_to_one"]
+    config.fifteen_min_maker_entries = False
+    config.fifteen_min_maker_ttl_seconds = 120
+    config.learning_store_path = str(tmp_path / "learning_store.db")
+    config.rl_policy_dir = str(tmp_path / "rl_policies")
+    config.rl_policy_version = None
+    config.rl_experience_path = None
+    config.rl_exploration_rate = 0.0
+    config.rl_exploration_margin = 0.005
+    config.rl_max_daily_explorations = 10
+    config.market_data_recording = False
+    config.ledger_db_path = str(tmp_path / "ledger.db")
+    config.redemption_interval_seconds = 0
+    config.ledger_reconcile_interval_seconds = 0
+    config.min_priority_fee_gwei = 30
+    config.fee_blocks_ahead = 3
+    config.max_fee_replacements = 5
+    config.auto_replace_stuck_tx = False
+    config.tx_journal_db_path = str(tmp_path / "tx_journal.db")
+    config.wallets = []
+    config.dashboard_enabled = False
+    config.config_file = None
+    config.alert_channels = {}
+    config.alert_routes = {}
+    config.alert_dedup_seconds = 300
+    config.alert_rate_limit_per_minute = 20
     return config
 
 
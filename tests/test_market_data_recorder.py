@@ -0,0 +1,228 @@
+"""
+Unit tests for the market data recorder.
+
+Tests:
+- Record schema and round trip through the replay loader
+- Rotation by size and age, with market definitions repeated per file
+- Markets awaiting a resolution record
+- Feed integration (Polymarket WebSocket, gamma WebSocket, Binance)
+- Recording failures never propagate to the feed
+"""
+
+import gzip
+import json
+
+import pytest
+from decimal import Decimal
+from datetime import datetime, timedelta, timezone
+from types import SimpleNamespace
+
+from src.backtest_replay import ReplayExchange, SimulatedClock, load_replay_events
+from src.fifteen_min_crypto_strategy import BinancePriceFeed
+from src.market_data_recorder import MarketDataRecorder
+from src.polymarket_websocket_feed import PolymarketWebSocketFeed
+from src.websocket_price_feed import WebSocketPriceFeed
+
+
+T0 = datetime(2026, 1, 1, 12, 0, tzinfo=timezone.utc)
+
+
+def _market(market_id="m1", end=T0 + timedelta(minutes=15)):
+    return SimpleNamespace(
+        market_id=market_id, question="BTC Up or Down", asset="BTC",
+        up_token_id="111", down_token_id="222", end_time=end, neg_risk=True, tick_size="0.01",
+    )
+
+
+def _read(path):
+    with gzip.open(path, "rt", encoding="utf-8") as f:
+        return [json.loads(line) for line in f]
+
+
+@pytest.fixture
+def recorder(tmp_path):
+    """Recorder writing to a temporary directory."""
+    recorder = MarketDataRecorder(output_dir=str(tmp_path / "rec"))
+    yield recorder
+    recorder.close()
+
+
+def test_records_carry_receive_timestamp_and_schema_version(recorder):
+    """Every record has ts, type and v; prices stay decimal strings."""
+    with SimulatedClock(T0).patched():
+        recorder.record_binance_tick("BTC", Decimal("95000.50"), Decimal("0.01"), exchange_ts=123)
+    recorder.close()
+
+    record = _read(recorder.files_written[0])[0]
+
+    assert record == {
+        "ts": T0.isoformat(), "type": "binance_tick", "v": 1,
+        "asset": "BTC", "price": "95000.50", "volume": "0.01", "exchange_ts": 123,
+    }
+
+
+def test_recording_round_trips_through_replay_loader(recorder):
+    """Recorded books and deltas rebuild the same book in the replay exchange."""
+    with SimulatedClock(T0).patched():
+        recorder.record_market(_market())
+        recorder.record_book("111", [{"price": "0.48", "size": "100"}], [{"price": "0.50", "size": "80"}],
+                             source="polymarket_ws")
+        recorder.record_book_delta("111", [{"side": "BUY", "price": "0.49", "size": "40"},
+                                           {"side": "SELL", "price": "0.50", "size": "0"},
+                                           {"side": "SELL", "price": "0.52", "size": "10"}],
+                                   source="polymarket_ws")
+        recorder.record_trade("111", "0.50", source="polymarket_ws", size="25", side="buy")
+        recorder.record_resolution("m1", "up")
+    recorder.close()
+
+    events = list(load_replay_events(recorder.output_dir))
+    exchange = ReplayExchange(SimulatedClock(T0), Decimal("100"))
+    for event in events:
+        if event.event_type == "book":
+            exchange.apply_book(event.data["token_id"], event.data["bids"], event.data["asks"])
+        elif event.event_type == "book_delta":
+            exchange.apply_book_delta(event.data["token_id"], event.data["changes"])
+
+    assert [e.event_type for e in events] == ["market", "book", "book_delta", "trade", "resolution"]
+    assert events[3].data["side"] == "BUY"
+    assert events[4].data["winner"] == "UP"
+    assert exchange.best_bid("111") == Decimal("0.49")
+    assert exchange.best_ask("111") == Decimal("0.52")
+
+
+def test_duplicate_market_definitions_written_once(recorder):
+    """Markets seen on every fetch are only recorded the first time."""
+    recorder.record_markets([_market(), _market()])
+
+    assert recorder.records_written == 1
+
+
+def test_rotation_by_age_repeats_open_markets(tmp_path):
+    """A rotated file starts with every market that is still open."""
+    clock = SimulatedClock(T0)
+    recorder = MarketDataRecorder(output_dir=str(tmp_path), rotate_interval_seconds=60)
+    with clock.patched():
+        recorder.record_market(_market("open", end=T0 + timedelta(minutes=15)))
+        recorder.record_market(_market("closing", end=T0 + timedelta(seconds=30)))
+        clock.advance_to(T0 + timedelta(seconds=61))
+        recorder.record_binance_tick("BTC", Decimal("1"))
+    recorder.close()
+
+    assert len(recorder.files_written) == 2
+    second = _read(recorder.files_written[1])
+    assert [(r["type"], r.get("market_id")) for r in second] == [("market", "open"), ("binance_tick", None)]
+    assert sorted(recorder.files_written) == recorder.files_written
+
+
+def test_rotation_by_size(tmp_path):
+    """Files rotate once the uncompressed size limit is reached."""
+    clock = SimulatedClock(T0)
+    recorder = MarketDataRecorder(output_dir=str(tmp_path), max_file_bytes=200)
+    with clock.patched():
+        for i in range(6):
+            clock.advance_to(T0 + timedelta(milliseconds=i))
+            recorder.record_binance_tick("BTC", Decimal(95000 + i))
+    recorder.close()
+
+    assert len(recorder.files_written) > 1
+    assert [e.data["price"] for e in load_replay_events(tmp_path)] == [str(95000 + i) for i in range(6)]
+
+
+def test_receive_timestamps_never_decrease(recorder):
+    """A wall-clock step backwards does not reorder the recording."""
+    future = datetime.now(timezone.utc) + timedelta(hours=1)
+    recorder._last_ts = future
+
+    recorder.record_binance_tick("BTC", Decimal("1"))
+    recorder.close()
+
+    assert _read(recorder.files_written[0])[0]["ts"] == future.isoformat()
+
+
+def test_write_failure_does_not_raise(tmp_path):
+    """Recording errors are logged, never raised into the feed."""
+    blocker = tmp_path / "not_a_dir"
+    blocker.write_text("")
+    recorder = MarketDataRecorder(output_dir=str(blocker))
+
+    recorder.record_binance_tick("BTC", Decimal("1"))
+
+    assert recorder.records_written == 0
+
+
+def test_ended_markets_await_resolution_until_recorded(tmp_path):
+    """Ended markets stay pending across rotation until their resolution is written."""
+    recorder = MarketDataRecorder(output_dir=str(tmp_path), rotate_interval_seconds=60)
+    with SimulatedClock(T0).patched():
+        recorder.record_market(_market("m1", end=T0 + timedelta(minutes=1)))
+        recorder.record_market(_market("m2", end=T0 + timedelta(minutes=15)))
+    with SimulatedClock(T0 + timedelta(minutes=2)).patched():
+        recorder.record_binance_tick("BTC", Decimal("95000"), Decimal("0.01"))  # rotates, drops m1's definition
+
+    assert recorder.awaiting_resolution(T0 + timedelta(minutes=2)) == ["m1"]
+
+    recorder.record_resolution("m1", "up")
+    recorder.close()
+
+    assert recorder.awaiting_resolution(T0 + timedelta(minutes=20)) == ["m2"]
+    assert _read(recorder.files_written[-1])[-1]["winner"] == "UP"
+
+
+def test_invalid_rotation_settings_rejected(tmp_path):
+    """Rotation limits must be positive."""
+    with pytest.raises(ValueError):
+        MarketDataRecorder(output_dir=str(tmp_path), rotate_interval_seconds=0)
+
+
+@pytest.mark.asyncio
+async def test_polymarket_feed_records_books_deltas_and_trades(recorder):
+    """The Polymarket WebSocket feed passes book, price_change and trade messages through."""
+    feed = PolymarketWebSocketFeed(recorder=recorder)
+
+    await feed._process_message(json.dumps({
+        "type": "book", "asset_id": "111", "timestamp": "1767268800000",
+        "bids": [{"price": "0.48", "size": "10"}], "asks": [{"price": "0.50", "size": "5"}],
+    }))
+    await feed._process_message(json.dumps({
+        "type": "price_change", "asset_id": "111", "changes": [{"side": "BUY", "price": "0.49", "size": "3"}],
+    }))
+    await feed._process_message(json.dumps({"type": "last_trade_price", "asset_id": "111", "price": "0.50"}))
+    recorder.close()
+
+    records = _read(recorder.files_written[0])
+    assert [r["type"] for r in records] == ["book", "book_delta", "trade"]
+    assert records[0]["exchange_ts"] == "1767268800000"
+    assert records[1]["changes"] == [["bid", "0.49", "3"]]
+    assert (await feed.get_price("111")).price == Decimal("0.50")
+
+
+@pytest.mark.asyncio
+async def test_gamma_feed_records_market_ids(recorder):
+    """WebSocketPriceFeed is keyed by market, so its records say market_id, not token_id."""
+    feed = WebSocketPriceFeed(recorder=recorder)
+
+    await feed._process_message(json.dumps({
+        "type": "orderbook_snapshot", "market": "0xabc", "bids": [], "asks": [{"price": "0.50", "size": "5"}],
+    }))
+    await feed._process_message(json.dumps({"type": "trade", "market": "0xabc", "price": "0.50"}))
+    recorder.close()
+
+    records = _read(recorder.files_written[0])
+    assert [r["market_id"] for r in records] == ["0xabc", "0xabc"]
+    assert all("token_id" not in r for r in records)
+
+
+@pytest.mark.asyncio
+async def test_binance_feed_records_ticks(recorder):
+    """Binance trades are recorded with Binance's trade time."""
+    feed = BinancePriceFeed()
+    feed.recorder = recorder
+
+    await feed._process_message(json.dumps({"s": "ETHUSDT", "p": "3500.10", "q": "0.5", "T": 1767268800248}))
+    await feed._process_message(json.dumps({"s": "DOGEUSDT", "p": "0.1", "q": "1"}))
+    recorder.close()
+
+    records = _read(recorder.files_written[0])
+    assert len(records) == 1
+    assert records[0]["asset"] == "ETH" and records[0]["exchange_ts"] == 1767268800248
+    assert feed.prices["ETH"] == Decimal("3500.10")
@@ -0,0 +1,394 @@
+"""
+Unit tests for the 15-minute up/down market maker.
+
+Runs against the simulated CLOB (src/clob_simulator.py).
+
+Tests:
+- Quote prices and inventory skew
+- CEX-derived fair UP probability and stale-feed handling
+- Two-sided post-only quoting and requotes, each side independent of the other
+- Fills becoming inventory and risk-manager exposure
+- Net inventory limits
+- Pulling quotes near close and on flash crashes
+- Finished markets released from the risk limits and handed to redemption
+- Flatten: inventory sold into the best bids, unsold shares kept
+- Registry factory
+"""
+
+import json
+
+import pytest
+from dataclasses import replace
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from unittest.mock import ANY, AsyncMock, Mock
+
+from src.clob_simulator import LIQUIDITY_PROVIDER, SimulatedClobClient, SimulatedClobExchange
+from src.fifteen_min_crypto_strategy import BinancePriceFeed, CryptoMarket
+from src.flash_crash_detector import FlashCrash
+from src.market_making_strategy import MarketMakingStrategy
+from src.order_book_analyzer import OrderBookDepth, OrderBookLevel
+from src.order_manager import OrderManager
+from src.strategy_registry import MarketMakingAdapter, StrategyContext, build_default_registry
+
+
+T0 = 1767268800  # 2026-01-01T12:00:00Z
+
+ALICE = "0x00000000000000000000000000000000000A11CE"
+BOB = "0x0000000000000000000000000000000000000B0B"
+
+SCENARIO = {
+    "start_time": T0,
+    "markets": [{
+        "condition_id": "0xabc", "question": "Bitcoin Up or Down?",
+        "tokens": {"111": "Up", "222": "Down"}, "end_date_iso": "2099-01-01T00:00:00Z",
+    }],
+    "accounts": {
+        ALICE: {"collateral": "100"},
+        BOB: {"collateral": "50", "tokens": {"111": "100", "222": "100"}},
+    },
+    "books": {
+        "111": {"bids": [["0.45", "100"]], "asks": [["0.55", "100"]]},
+        "222": {"bids": [["0.45", "100"]], "asks": [["0.55", "100"]]},
+    },
+}
+
+
+@pytest.fixture
+def exchange():
+    """Exchange loaded from the scenario."""
+    return SimulatedClobExchange.from_scenario(json.loads(json.dumps(SCENARIO)))
+
+
+@pytest.fixture
+def market():
+    """15-minute BTC market on the simulated token pair."""
+    return CryptoMarket(
+        market_id="0xabc",
+        question="Bitcoin Up or Down?",
+        asset="BTC",
+        up_token_id="111",
+        down_token_id="222",
+        up_price=Decimal("0.50"),
+        down_price=Decimal("0.50"),
+        end_time=datetime.now(timezone.utc) + timedelta(minutes=10),
+        neg_risk=False,
+    )
+
+
+@pytest.fixture
+def feed():
+    """Binance feed with a fresh BTC tick."""
+    feed = BinancePriceFeed()
+    feed._update_price("BTC", Decimal("100000"))
+    return feed
+
+
+@pytest.fixture
+def strategy(exchange, market, feed):
+    """Live market maker quoting the scenario market."""
+    client = SimulatedClobClient(exchange, address=ALICE)
+    order_manager = OrderManager(client, Mock(), clock=lambda: exchange.now())
+    return MarketMakingStrategy(
+        clob_client=client,
+        order_manager=order_manager,
+        price_feed=feed,
+        initial_capital=1000.0,
+        half_spread=0.02,
+        quote_size=10.0,
+        max_inventory=50.0,
+        market_source=AsyncMock(return_value=[market]),
+    )
+
+
+def _alice_orders(exchange):
+    """Orders the market maker placed on the exchange."""
+    return [o for o in exchange.orders.values() if o.owner == ALICE]
+
+
+def _bob_sells(exchange, token_id, size, price):
+    """BOB hits resting bids at or above price."""
+    return exchange.place_order(BOB, token_id, "SELL", price, size, order_type="FAK")
+
+
+# ============================================================================
+# Pricing
+# ============================================================================
+
+def test_quote_prices_skew_against_inventory(strategy):
+    """Long UP lowers the UP bid and raises the DOWN bid; the quoted spread stays constant."""
+    assert strategy.quote_prices(Decimal("0.50"), Decimal("0")) == (Decimal("0.48"), Decimal("0.48"))
+
+    up_bid, down_bid = strategy.quote_prices(Decimal("0.50"), Decimal("25"))
+    assert (up_bid, down_bid) == (Decimal("0.47"), Decimal("0.49"))
+
+    up_bid, down_bid = strategy.quote_prices(Decimal("0.50"), Decimal("-500"))
+    assert (up_bid, down_bid) == (Decimal("0.50"), Decimal("0.46"))
+    assert up_bid + down_bid == Decimal("0.96")
+
+
+def test_fair_value_follows_cex_price(strategy, market, feed):
+    """The strike is implied from the first mid; afterwards fair value tracks Binance."""
+    assert strategy.fair_up_probability(market, Decimal("0.50")) == Decimal("0.5")
+
+    feed._update_price("BTC", Decimal("100200"))
+    assert strategy.fair_up_probability(market, Decimal("0.50")) > Decimal("0.7")
+
+    feed._update_price("BTC", Decimal("99800"))
+    assert strategy.fair_up_probability(market, Decimal("0.50")) < Decimal("0.3")
+
+
+def test_stale_feed_has_no_fair_value(strategy, market, feed):
+    """Without a recent Binance tick there is no fair value to quote around."""
+    feed.price_history["BTC"].append((datetime.now() - timedelta(seconds=30), Decimal("100000")))
+
+    assert strategy.fair_up_probability(market, Decimal("0.50")) is None
+
+
+# ============================================================================
+# Quoting and inventory
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_run_cycle_quotes_both_sides(strategy, exchange):
+    """Each cycle keeps one post-only bid on UP and one on DOWN."""
+    await strategy.run_cycle()
+
+    up, down = strategy.quotes["111"].order, strategy.quotes["222"].order
+    assert (up.price, down.price) == (Decimal("0.48"), Decimal("0.48"))
+    assert up.post_only and up.order_type == "GTD"
+    assert exchange.orders[up.order_id].status == "LIVE"
+    assert exchange.orders[down.order_id].status == "LIVE"
+
+    await strategy.run_cycle()
+    assert strategy.quotes["111"].order is up
+    assert strategy.stats["quotes_posted"] == 2 and strategy.stats["requotes"] == 0
+
+
+@pytest.mark.asyncio
+async def test_empty_ask_side_keeps_target(strategy, market):
+    """With no asks to stay under, the bid goes in at the target price."""
+    book = OrderBookDepth(
+        bids=[OrderBookLevel(price=Decimal("0.45"), size=Decimal("100"))], asks=[],
+        bid_depth=Decimal("100"), ask_depth=Decimal("0"), spread=Decimal("0"),
+        mid_price=Decimal("0.45"), liquidity_score=0.0
+    )
+
+    await strategy._update_quote(market, "UP", Decimal("0.48"), book)
+
+    assert strategy.quotes["111"].order.price == Decimal("0.48")
+
+
+@pytest.mark.asyncio
+async def test_failed_side_still_quotes_other_side(strategy):
+    """An error quoting UP does not stop the DOWN quote."""
+    check_can_trade = strategy.risk_manager.check_can_trade
+    strategy.risk_manager.check_can_trade = Mock(
+        side_effect=[RuntimeError("risk check failed"), check_can_trade(Decimal("4.8"), "0xabc")]
+    )
+
+    await strategy.run_cycle()
+
+    assert "111" not in strategy.quotes
+    assert strategy.quotes["222"].order.price == Decimal("0.48")
+
+
+@pytest.mark.asyncio
+async def test_fill_becomes_inventory_and_skews_quotes(strategy, exchange):
+    """A filled UP bid adds UP inventory, counts as exposure and lowers the next UP bid."""
+    await strategy.run_cycle()
+    _bob_sells(exchange, "111", "10", "0.48")
+
+    await strategy.run_cycle()
+
+    inventory = strategy.inventory["0xabc"]
+    assert inventory.up_shares == Decimal("10") and inventory.net == Decimal("10")
+    assert inventory.cost == Decimal("4.8")
+    assert strategy.risk_manager._get_market_exposure("0xabc") == Decimal("10")
+    assert strategy.risk_manager._positions["0xabc"].entry_price == Decimal("0.48")
+    assert strategy.quotes["111"].order.price == Decimal("0.47")
+    assert strategy.quotes["222"].order.price == Decimal("0.48")
+
+
+@pytest.mark.asyncio
+async def test_inventory_limit_stops_adding_to_side(strategy, exchange):
+    """At max net inventory only the side that reduces it is quoted."""
+    strategy.max_inventory = Decimal("10")
+    await strategy.run_cycle()
+    _bob_sells(exchange, "111", "10", "0.48")
+
+    await strategy.run_cycle()
+
+    assert "111" not in strategy.quotes
+    assert "222" in strategy.quotes
+
+
+@pytest.mark.asyncio
+async def test_risk_manager_blocks_new_quotes(strategy, exchange):
+    """Quotes are not posted when PortfolioRiskManager refuses the exposure."""
+    strategy.risk_manager.check_can_trade = Mock(return_value=Mock(can_trade=False, reason="heat"))
+
+    await strategy.run_cycle()
+
+    assert strategy.quotes == {} and _alice_orders(exchange) == []
+
+
+@pytest.mark.asyncio
+async def test_dry_run_posts_nothing(strategy, exchange):
+    """Dry run logs quotes without creating orders."""
+    strategy.dry_run = True
+
+    await strategy.run_cycle()
+
+    assert strategy.quotes == {} and _alice_orders(exchange) == []
+    assert strategy.order_manager.get_active_orders() == []
+
+
+# ============================================================================
+# Pulling quotes
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_quotes_pulled_near_close(strategy, market, exchange):
+    """Both quotes are cancelled once the market is within pull_minutes of close."""
+    await strategy.run_cycle()
+    order_ids = [q.order.order_id for q in strategy.quotes.values()]
+
+    closing = replace(market, end_time=datetime.now(timezone.utc) + timedelta(minutes=1))
+    strategy.market_source.return_value = [closing]
+    await strategy.run_cycle()
+
+    assert strategy.quotes == {}
+    assert all(exchange.orders[order_id].status == "CANCELED" for order_id in order_ids)
+
+
+@pytest.mark.asyncio
+async def test_flash_crash_pulls_quotes_and_pauses(strategy, exchange):
+    """A flash crash pulls the market's quotes and keeps it dark for the cooldown."""
+    await strategy.run_cycle()
+
+    strategy.flash_detector.update_price = Mock(return_value=FlashCrash(
+        market_id="0xabc", side="YES", crash_pct=Decimal("0.20"),
+        pre_crash_price=Decimal("0.50"), post_crash_price=Decimal("0.40"), timestamp=0.0
+    ))
+    await strategy.run_cycle()
+    assert strategy.quotes == {} and strategy.stats["flash_crash_pulls"] == 1
+
+    strategy.flash_detector.update_price = Mock(return_value=None)
+    await strategy.run_cycle()
+    assert strategy.quotes == {}
+
+
+@pytest.mark.asyncio
+async def test_stale_feed_pulls_quotes(strategy, feed):
+    """Quotes come down when the CEX price goes stale."""
+    await strategy.run_cycle()
+
+    feed.price_history["BTC"].append((datetime.now() - timedelta(seconds=30), Decimal("100000")))
+    await strategy.run_cycle()
+
+    assert strategy.quotes == {}
+
+
+@pytest.mark.asyncio
+async def test_finished_markets_release_exposure_and_go_to_redemption(strategy, market, exchange):
+    """Inventory of closed windows leaves the risk limits, so quoting continues window after window."""
+    strategy.redemption_service = Mock()
+    strategy.risk_manager.max_portfolio_heat = Decimal("0.02")  # $20: room for about two windows of fills
+
+    for window in range(4):
+        current = replace(market, market_id=f"0xabc{window}")
+        strategy.market_source.return_value = [current]
+        await strategy.run_cycle()
+        assert {"111", "222"} <= set(strategy.quotes), f"window {window} not quoted"
+        _bob_sells(exchange, "111", "10", "0.48")
+        _bob_sells(exchange, "222", "10", "0.48")
+
+        # The window closes and the next one is listed
+        strategy.market_source.return_value = []
+        await strategy.run_cycle()
+
+        assert f"0xabc{window}" not in strategy.inventory
+        assert strategy.risk_manager._calculate_total_exposure() == Decimal("0")
+        strategy.redemption_service.watch.assert_any_call(f"0xabc{window}", "111", outcome_index=0, neg_risk=False)
+        strategy.redemption_service.watch.assert_any_call(f"0xabc{window}", "222", outcome_index=1, neg_risk=False)
+
+    assert strategy.stats["markets_retired"] == 4
+
+
+@pytest.mark.asyncio
+async def test_failed_market_fetch_keeps_markets(strategy):
+    """A Gamma API error is not a market closing."""
+    await strategy.run_cycle()
+    strategy.market_source.side_effect = RuntimeError("gamma down")
+
+    await strategy.run_cycle()
+
+    assert "0xabc" in strategy.markets and {"111", "222"} <= set(strategy.quotes)
+
+
+# ============================================================================
+# Flatten
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_flatten_pulls_quotes_and_sells_inventory(strategy, exchange):
+    """Flatten sells both sides into the best bids and books the result."""
+    strategy.ledger = Mock()
+    await strategy.run_cycle()
+    _bob_sells(exchange, "111", "10", "0.48")
+    _bob_sells(exchange, "222", "10", "0.48")
+    await strategy.run_cycle()
+
+    assert await MarketMakingAdapter(strategy).flatten() == 1
+
+    assert strategy.quotes == {} and strategy.inventory == {}
+    assert exchange.balance(ALICE, "111") == 0 and exchange.balance(ALICE, "222") == 0
+    assert strategy.risk_manager._calculate_total_exposure() == Decimal("0")
+    assert strategy.risk_manager._daily_pnl == Decimal("-0.6")  # 20 shares bought at 0.48, sold at 0.45
+    strategy.ledger.record_fill.assert_any_call(
+        token_id="111", side="SELL", size=Decimal("10"), price=Decimal("0.45"), market_id="0xabc",
+        outcome="UP", strategy="market_making", order_id=ANY
+    )
+
+
+@pytest.mark.asyncio
+async def test_flatten_keeps_inventory_without_bids(strategy, exchange):
+    """Shares with no bid to sell into stay in inventory and keep counting as exposure."""
+    await strategy.run_cycle()
+    _bob_sells(exchange, "111", "10", "0.48")
+    await strategy.run_cycle()
+    exchange.cancel_all(LIQUIDITY_PROVIDER, token_id="111")
+
+    assert await strategy.close_inventory() == 0
+
+    assert strategy.inventory["0xabc"].up_shares == Decimal("10")
+    assert strategy.risk_manager._get_market_exposure("0xabc") == Decimal("10")
+    assert strategy.get_open_positions()[0]["status"] == "open"
+
+
+# ============================================================================
+# Registry
+# ============================================================================
+
+def test_registry_builds_market_maker_from_config():
+    """The market_making factory wires config settings and the shared feed."""
+    feed = BinancePriceFeed()
+    config = Mock(
+        dry_run=True,
+        market_making_half_spread=0.03,
+        market_making_quote_size=20.0,
+        market_making_max_inventory=40.0,
+        market_making_pull_minutes=3.0,
+    )
+    context = StrategyContext(
+        config=config, clob_client=Mock(), order_manager=Mock(), initial_capital=500.0, price_feed=feed
+    )
+
+    adapter = build_default_registry().create("market_making", context)
+
+    assert isinstance(adapter, MarketMakingAdapter)
+    strategy = adapter.strategy
+    assert strategy.price_feed is feed and not strategy.owns_feed
+    assert strategy.half_spread == Decimal("0.03") and strategy.quote_size == Decimal("20.0")
+    assert strategy.max_inventory == Decimal("40.0") and strategy.pull_minutes == 3.0
@@ -0,0 +1,218 @@
+"""
+Tests for Prometheus metric definitions, strategy instrumentation and the Grafana dashboard.
+
+Tests:
+- Metric definitions: unique names, SLO bounds on histogram buckets
+- MonitoringSystem strategy counters, tick-to-order histogram, position PnL gauges
+- 15-minute strategy: opportunities, entries, exits, latency and unrealized PnL
+- Latency arbitrage engine: tick-to-order latency from the CEX tick
+- Orchestrator refresh of position, feed staleness and risk state gauges
+- Bundled Grafana dashboard generated from the definitions
+"""
+
+import json
+from datetime import datetime, timedelta
+from decimal import Decimal
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, Mock
+
+import pytest
+
+from src.error_recovery import CircuitBreaker
+from src.fifteen_min_crypto_strategy import CryptoMarket, FifteenMinuteCryptoStrategy
+from src.latency_arbitrage_engine import LatencyArbitrageEngine
+from src.main_orchestrator import MainOrchestrator
+from src.metrics import (
+    DASHBOARD_PATH, HISTOGRAM, METRIC_DEFINITIONS, grafana_dashboard, main, render_dashboard,
+)
+from src.models import Opportunity
+from src.monitoring_system import MonitoringSystem
+from src.polymarket_websocket_feed import TokenPrice
+from src.strategy_registry import FifteenMinuteCryptoAdapter
+
+
+@pytest.fixture
+def monitoring():
+    return MonitoringSystem(enable_prometheus=False)
+
+
+def sample(monitoring, name, **labels):
+    return monitoring.registry.get_sample_value(name, labels)
+
+
+@pytest.fixture
+def strategy(tmp_path, monitoring):
+    return FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, enable_adaptive_learning=False,
+        metrics=monitoring, positions_file=str(tmp_path / "positions.json")
+    )
+
+
+@pytest.fixture
+def market():
+    return CryptoMarket(
+        market_id="m1", question="BTC up or down?", asset="BTC",
+        up_token_id="up-1", down_token_id="down-1",
+        up_price=Decimal("0.50"), down_price=Decimal("0.50"),
+        end_time=datetime.now() + timedelta(minutes=10),
+    )
+
+
+def test_definitions_are_consistent():
+    names = [definition.name for definition in METRIC_DEFINITIONS]
+    assert len(names) == len(set(names))
+    for definition in METRIC_DEFINITIONS:
+        if definition.kind == HISTOGRAM and definition.slo is not None:
+            assert definition.slo in definition.buckets, definition.name
+
+
+def test_monitoring_exports_strategy_metrics(monitoring):
+    monitoring.record_strategy_opportunity("latency", "BTC")
+    monitoring.record_strategy_opportunity("latency", "BTC")
+    monitoring.record_strategy_entry("latency", "BTC")
+    monitoring.record_strategy_exit("latency", "BTC", "take_profit")
+    monitoring.record_tick_to_order("latency", "BTC", 120.0)
+    monitoring.record_tick_to_order("latency", "BTC", 1500.0)
+    monitoring.update_risk_state("primary", trading_halted=False, conservative_mode=True)
+
+    assert sample(monitoring, "strategy_opportunities_total", strategy="latency", asset="BTC") == 2
+    assert sample(monitoring, "strategy_entries_total", strategy="latency", asset="BTC") == 1
+    assert sample(monitoring, "strategy_exits_total", strategy="latency", asset="BTC", reason="take_profit") == 1
+    assert sample(monitoring, "tick_to_order_latency_ms_count", strategy="latency", asset="BTC") == 2
+    assert sample(monitoring, "tick_to_order_latency_ms_bucket", strategy="latency", asset="BTC", le="1000.0") == 1
+    assert sample(monitoring, "conservative_mode_active", book="primary") == 1
+    assert sample(monitoring, "risk_trading_halted", book="primary") == 0
+
+
+def test_position_pnl_gauges_removed_when_closed(monitoring):
+    monitoring.update_position_pnl({
+        ("primary", "latency", "BTC", "up-1"): 0.25,
+        ("primary", "market_making", "ETH", "m2"): -0.5,
+    })
+    monitoring.update_position_pnl({("primary", "latency", "BTC", "up-1"): 0.75})
+
+    labels = dict(wallet="primary", strategy="latency", asset="BTC", position="up-1")
+    assert sample(monitoring, "position_unrealized_pnl_usd", **labels) == 0.75
+    assert sample(
+        monitoring, "position_unrealized_pnl_usd", wallet="primary", strategy="market_making", asset="ETH", position="m2"
+    ) is None
+
+
+# ============================================================================
+# Strategies
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_fifteen_min_strategy_records_metrics(strategy, monitoring, market):
+    strategy.dynamic_params.analyze_cost_benefit = Mock(return_value=(True, {}))
+    strategy._record_opportunity("directional", "BTC")
+    assert await strategy._place_order(market, "UP", Decimal("0.50"), 4.0, strategy="directional")
+    strategy._track_execution_time(5.0, "directional", "BTC", tick_time=datetime.now() - timedelta(milliseconds=80))
+    strategy._track_execution_time(5.0, "directional", "ETH")  # No tick behind the signal
+    strategy._record_trade_outcome(
+        asset="BTC", side="UP", strategy="directional", entry_price=Decimal("0.50"), exit_price=Decimal("0.55"),
+        profit_pct=Decimal("0.10"), hold_time_minutes=3.0, exit_reason="take_profit"
+    )
+
+    assert sample(monitoring, "strategy_opportunities_total", strategy="directional", asset="BTC") == 1
+    assert sample(monitoring, "strategy_entries_total", strategy="directional", asset="BTC") == 1
+    assert sample(monitoring, "strategy_exits_total", strategy="directional", asset="BTC", reason="take_profit") == 1
+    assert sample(monitoring, "tick_to_order_latency_ms_sum", strategy="directional", asset="BTC") >= 80.0
+    assert sample(monitoring, "tick_to_order_latency_ms_count", strategy="directional", asset="ETH") is None
+
+
+@pytest.mark.asyncio
+async def test_open_positions_carry_unrealized_pnl_and_feed_staleness(strategy, market):
+    strategy.dynamic_params.analyze_cost_benefit = Mock(return_value=(True, {}))
+    await strategy._place_order(market, "UP", Decimal("0.50"), 4.0, strategy="latency")
+    adapter = FifteenMinuteCryptoAdapter(strategy)
+
+    assert adapter.open_positions()[0]["unrealized_pnl"] is None  # No price yet
+    strategy.polymarket_ws_feed._price_cache["up-1"] = TokenPrice("up-1", Decimal("0.60"), datetime.now())
+    assert adapter.open_positions()[0]["unrealized_pnl"] == Decimal("0.40")
+
+    assert adapter.feed_staleness() == {}
+    strategy.binance_feed._update_price("BTC", Decimal("60000"))
+    strategy.polymarket_ws_feed._last_message_time = datetime.now() - timedelta(seconds=30)
+    staleness = adapter.feed_staleness()
+    assert staleness[("binance", "BTC")] < 5
+    assert 30 <= staleness[("polymarket_ws", "all")] < 35
+    assert adapter.risk_statistics()["conservative_mode_active"] is False
+
+
+@pytest.mark.asyncio
+async def test_latency_engine_measures_from_cex_tick(monitoring):
+    order = SimpleNamespace(order_id="o1", tx_hash="0x1", fill_price=Decimal("0.40"))
+    engine = LatencyArbitrageEngine(
+        cex_feeds={}, clob_client=Mock(),
+        order_manager=Mock(create_fok_order=Mock(return_value=order), submit_order=AsyncMock(return_value=True)),
+        ai_safety_guard=Mock(validate_trade=AsyncMock(return_value=SimpleNamespace(approved=True, reason=""))),
+        kelly_sizer=Mock(calculate_position_size=Mock(return_value=Decimal("2"))),
+        metrics=monitoring,
+    )
+    opportunity = Opportunity(
+        opportunity_id="latency_1", market_id="m1", strategy="latency_arbitrage", timestamp=datetime.now(),
+        yes_price=Decimal("0.40"), no_price=Decimal("0"), yes_fee=Decimal("0.01"), no_fee=Decimal("0"),
+        total_cost=Decimal("0.40"), expected_profit=Decimal("0.05"), profit_percentage=Decimal("0.12"),
+        position_size=Decimal("0"), gas_estimate=100000,
+    )
+    engine._signal_ticks["latency_1"] = ("ETH", datetime.now() - timedelta(milliseconds=300))
+
+    result = await engine.execute(opportunity, SimpleNamespace(market_id="m1"), Decimal("100"))
+
+    assert result.status == "success"
+    assert sample(monitoring, "tick_to_order_latency_ms_sum", strategy="latency_arbitrage", asset="ETH") >= 300
+    assert sample(monitoring, "strategy_entries_total", strategy="latency_arbitrage", asset="ETH") == 1
+    assert engine._signal_ticks == {}
+
+
+def test_orchestrator_refreshes_strategy_gauges(monitoring):
+    strategy = SimpleNamespace(
+        name="fifteen_min_crypto",
+        open_positions=lambda: [
+            {"token_id": "up-1", "market_id": "m1", "asset": "BTC", "strategy": "latency", "unrealized_pnl": Decimal("1.5")},
+            {"token_id": "up-2", "market_id": "m2", "asset": "ETH", "strategy": "latency", "unrealized_pnl": None},
+        ],
+        feed_staleness=lambda: {("binance", "BTC"): 2.5},
+        risk_statistics=lambda: {"trading_halted": True, "conservative_mode_active": False},
+    )
+    wallet = SimpleNamespace(
+        name="book_b", strategies=[strategy],
+        risk_manager=Mock(get_statistics=Mock(return_value={"trading_halted": False, "conservative_mode_active": True})),
+    )
+    circuit_breaker = CircuitBreaker(failure_threshold=1)
+    circuit_breaker.record_failure("boom")
+    orchestrator = SimpleNamespace(wallets=[wallet], monitoring=monitoring, circuit_breaker=circuit_breaker)
+
+    MainOrchestrator._update_strategy_metrics(orchestrator)
+
+    assert sample(
+        monitoring, "position_unrealized_pnl_usd", wallet="book_b", strategy="latency", asset="BTC", position="up-1"
+    ) == 1.5
+    assert sample(monitoring, "feed_staleness_seconds", feed="binance", stream="BTC") == 2.5
+    assert sample(monitoring, "conservative_mode_active", book="book_b") == 1
+    assert sample(monitoring, "risk_trading_halted", book="fifteen_min_crypto@book_b") == 1
+    assert sample(monitoring, "circuit_breaker_status") == 1
+
+
+# ============================================================================
+# Grafana dashboard
+# ============================================================================
+
+def test_bundled_dashboard_is_generated_from_definitions():
+    with open(DASHBOARD_PATH) as f:
+        assert f.read() == render_dashboard(), "Regenerate with: python -m src.metrics"
+
+    dashboard = grafana_dashboard()
+    exprs = " ".join(target["expr"] for panel in dashboard["panels"] for target in panel.get("targets", []))
+    for definition in METRIC_DEFINITIONS:
+        assert definition.sample_name in exprs or f"{definition.name}_bucket" in exprs, definition.name
+    ids = [panel["id"] for panel in dashboard["panels"]]
+    assert len(ids) == len(set(ids))
+    assert any("within 1000ms SLO" in panel["title"] for panel in dashboard["panels"])
+
+
+def test_main_writes_dashboard(tmp_path):
+    path = tmp_path / "dashboard.json"
+    assert main([str(path)]) == 0
+    assert json.loads(path.read_text())["uid"] == "polymarket-bot"
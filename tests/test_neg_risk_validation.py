@@ -28,7 +28,7 @@ def strategy():
         with patch('src.fifteen_min_crypto_strategy.MultiTimeframeAnalyzer'):
             with patch('src.fifteen_min_crypto_strategy.OrderBookAnalyzer'):
                 with patch('src.fifteen_min_crypto_strategy.HistoricalSuccessTracker'):
-                    with patch('src.fifteen_min_crypto_strategy.ReinforcementLearningEngine'):
+                    with patch('src.fifteen_min_crypto_strategy.ContinuousRLEngine'):
                         with patch('src.fast_execution_engine.FastExecutionEngine'):
                             with patch('src.polymarket_websocket_feed.PolymarketWebSocketFeed'):
                                 with patch('src.fifteen_min_crypto_strategy.EnsembleDecisionEngine'):
@@ -0,0 +1,380 @@
+"""
+Unit tests for resting (maker) limit orders in OrderManager.
+
+Runs against the simulated CLOB (src/clob_simulator.py).
+
+Tests:
+- GTC/GTD post-only order creation and validation
+- Post-only crossing rejection
+- Partial-fill tracking via refresh_resting_orders()
+- Cancel, cancel-replace and requote
+- GTD expiry handling
+- FifteenMinuteCryptoStrategy maker entries (resting, fills become positions, pulled near close)
+- Sum-to-one maker pairs (one leg rests, the other is taken as it fills)
+"""
+
+import json
+
+import pytest
+from dataclasses import replace
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from unittest.mock import AsyncMock, MagicMock, Mock
+
+from src.clob_simulator import SimulatedClobClient, SimulatedClobExchange
+from src.order_manager import (
+    ERR_POST_ONLY_CROSSES,
+    ORDER_STATUS_CANCELED,
+    ORDER_STATUS_EXPIRED,
+    ORDER_STATUS_LIVE,
+    ORDER_STATUS_MATCHED,
+    ORDER_STATUS_REJECTED,
+    OrderManager,
+)
+
+
+T0 = 1767268800  # 2026-01-01T12:00:00Z
+
+ALICE = "0x00000000000000000000000000000000000A11CE"
+BOB = "0x0000000000000000000000000000000000000B0B"
+
+SCENARIO = {
+    "start_time": T0,
+    "markets": [{
+        "condition_id": "0xabc", "question": "Will BTC close above $100k?",
+        "tokens": {"111": "Yes", "222": "No"}, "end_date_iso": "2099-01-01T00:00:00Z",
+    }],
+    "accounts": {ALICE: {"collateral": "100"}, BOB: {"collateral": "50", "tokens": {"111": "40"}}},
+    "books": {"111": {"bids": [["0.46", "100"]], "asks": [["0.48", "10"], ["0.50", "100"]]}},
+}
+
+
+@pytest.fixture
+def exchange():
+    """Exchange loaded from the scenario."""
+    return SimulatedClobExchange.from_scenario(json.loads(json.dumps(SCENARIO)))
+
+
+@pytest.fixture
+def client(exchange):
+    """Client trading for ALICE."""
+    return SimulatedClobClient(exchange, address=ALICE)
+
+
+@pytest.fixture
+def order_manager(exchange, client):
+    """Live (non dry-run) OrderManager on the exchange clock."""
+    return OrderManager(client, Mock(), clock=lambda: exchange.now())
+
+
+def _bob_sells(exchange, size, price="0.47"):
+    """BOB hits resting bids at or above price."""
+    return exchange.place_order(BOB, "111", "SELL", price, size, order_type="FAK")
+
+
+async def _resting_bid(order_manager, price="0.47", size="20", **kwargs):
+    order = order_manager.create_limit_order("111", "YES", Decimal(price), Decimal(size), **kwargs)
+    assert await order_manager.post_limit_order(order) is True
+    return order
+
+
+# ============================================================================
+# Creation and posting
+# ============================================================================
+
+def test_create_limit_order_validation(order_manager, exchange):
+    """Resting orders need a resting type; GTD needs a lifetime past the security threshold."""
+    with pytest.raises(ValueError):
+        order_manager.create_limit_order("111", "YES", Decimal("0.47"), Decimal("10"), order_type="FOK")
+    with pytest.raises(ValueError):
+        order_manager.create_limit_order("111", "YES", Decimal("0.47"), Decimal("10"), order_type="GTD")
+
+    order = order_manager.create_limit_order(
+        "111", "YES", Decimal("0.47"), Decimal("10"), order_type="GTD", ttl_seconds=30
+    )
+    assert order.expiration == T0 + 60 + 30
+    assert order.post_only is True and order.is_resting
+
+
+@pytest.mark.asyncio
+async def test_post_only_order_rests_on_book(order_manager, exchange):
+    """An accepted post-only bid rests under the exchange order ID and reserves collateral."""
+    order = await _resting_bid(order_manager)
+
+    assert order.status == ORDER_STATUS_LIVE
+    assert order.order_id in exchange.orders
+    assert order_manager.get_order(order.order_id) is order
+    assert exchange.reserved(ALICE) == Decimal("9.4")
+
+
+@pytest.mark.asyncio
+async def test_post_only_crossing_order_rejected(order_manager, exchange):
+    """A post-only bid at the best ask is rejected before reaching the book."""
+    order = order_manager.create_limit_order("111", "YES", Decimal("0.48"), Decimal("10"))
+
+    assert await order_manager.post_limit_order(order) is False
+    assert order.status == ORDER_STATUS_REJECTED
+    assert order.error_message.startswith(ERR_POST_ONLY_CROSSES)
+    assert exchange.trades == [] and order_manager.get_active_orders() == []
+
+
+@pytest.mark.asyncio
+async def test_dry_run_limit_order_fills_at_limit(client):
+    """Dry run never posts and treats the order as filled at its limit price."""
+    order_manager = OrderManager(client, Mock(), dry_run=True)
+    order = order_manager.create_limit_order("111", "YES", Decimal("0.47"), Decimal("10"))
+
+    assert await order_manager.post_limit_order(order) is True
+    assert order.filled and order.fill_price == Decimal("0.47")
+    assert order.status == ORDER_STATUS_MATCHED
+
+
+# ============================================================================
+# Fills, cancels and requotes
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_partial_fills_tracked_until_matched(order_manager, exchange):
+    """Refreshing picks up partial fills; a full fill completes and untracks the order."""
+    order = await _resting_bid(order_manager)
+
+    _bob_sells(exchange, "8")
+    assert await order_manager.refresh_resting_orders() == [order]
+    assert order.size_matched == Decimal("8") and order.remaining_size == Decimal("12")
+    assert order.status == ORDER_STATUS_LIVE and not order.filled
+    assert order.fill_price == Decimal("0.47")
+
+    assert await order_manager.refresh_resting_orders() == []
+
+    _bob_sells(exchange, "12")
+    await order_manager.refresh_resting_orders()
+    assert order.filled and order.status == ORDER_STATUS_MATCHED
+    assert order_manager.get_active_orders() == []
+
+
+@pytest.mark.asyncio
+async def test_cancel_order_cancels_on_clob(order_manager, exchange):
+    """Cancelling a resting order removes it from the book and releases collateral."""
+    order = await _resting_bid(order_manager)
+
+    assert await order_manager.cancel_order(order.order_id) is True
+    assert order.status == ORDER_STATUS_CANCELED
+    assert exchange.orders[order.order_id].status == "CANCELED"
+    assert exchange.reserved(ALICE) == Decimal("0")
+
+
+@pytest.mark.asyncio
+async def test_cancel_replace_keeps_filled_size(order_manager, exchange):
+    """Cancel-replace reposts only the unfilled remainder at the new price."""
+    order = await _resting_bid(order_manager)
+    _bob_sells(exchange, "8")
+
+    new = await order_manager.cancel_replace(order.order_id, Decimal("0.45"))
+
+    assert order.size_matched == Decimal("8") and order.status == ORDER_STATUS_CANCELED
+    assert new.replaces == order.order_id
+    assert new.size == Decimal("12") and new.price == Decimal("0.45")
+    assert exchange.orders[new.order_id].status == "LIVE"
+    assert [o.order_id for o in order_manager.get_active_orders()] == [new.order_id]
+
+
+@pytest.mark.asyncio
+async def test_requote_ignores_sub_tick_moves(order_manager):
+    """Requote keeps the order unless the price moves by at least one tick."""
+    order = await _resting_bid(order_manager)
+
+    assert await order_manager.requote(order.order_id, Decimal("0.475")) is order
+
+    moved = await order_manager.requote(order.order_id, Decimal("0.45"))
+    assert moved is not order and moved.price == Decimal("0.45")
+
+
+@pytest.mark.asyncio
+async def test_gtd_order_expires(order_manager, exchange):
+    """Past its lifetime a GTD order is cancelled and marked expired."""
+    order = await _resting_bid(order_manager, order_type="GTD", ttl_seconds=30)
+
+    assert await order_manager.expire_orders() == []
+
+    exchange.advance(31)
+    assert await order_manager.expire_orders() == [order]
+    assert order.status == ORDER_STATUS_EXPIRED
+    assert exchange.orders[order.order_id].status == "CANCELED"
+    assert order_manager.get_active_orders() == []
+
+
+# ============================================================================
+# FifteenMinuteCryptoStrategy maker entries
+# ============================================================================
+
+@pytest.fixture
+def strategy(client, order_manager, tmp_path, monkeypatch):
+    """Strategy posting maker entries through the simulated CLOB."""
+    from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+
+    monkeypatch.chdir(tmp_path)
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=client,
+        dry_run=False,
+        order_manager=order_manager,
+        maker_entries=True,
+    )
+    strategy.risk_manager.check_can_trade = MagicMock(
+        return_value=Mock(can_trade=True, max_position_size=Decimal("100.0"))
+    )
+    strategy.dynamic_params.analyze_cost_benefit = MagicMock(
+        return_value=(True, {"net_profit": Decimal("0.5"), "net_profit_pct": 50.0})
+    )
+    strategy.polymarket_ws_feed = AsyncMock()
+    return strategy
+
+
+@pytest.fixture
+def market():
+    """15-minute market on the simulated token pair."""
+    from src.fifteen_min_crypto_strategy import CryptoMarket
+
+    return CryptoMarket(
+        market_id="0xabc",
+        question="Will BTC be up in 15 minutes?",
+        asset="BTC",
+        up_token_id="111",
+        down_token_id="222",
+        up_price=Decimal("0.48"),
+        down_price=Decimal("0.52"),
+        end_time=datetime.now(timezone.utc) + timedelta(minutes=10),
+        neg_risk=False,
+    )
+
+
+@pytest.mark.asyncio
+async def test_maker_entry_rests_then_becomes_position(strategy, market, exchange):
+    """A directional entry rests one tick under the ask and turns into a position as it fills."""
+    assert await strategy._place_order(market, "UP", Decimal("0.48"), 20, strategy="directional") is True
+
+    [entry] = strategy.maker_orders.values()
+    assert entry.order.price == Decimal("0.47") and entry.order.order_type == "GTD"
+    assert strategy.positions == {} and exchange.trades == []
+
+    _bob_sells(exchange, "8")
+    await strategy._sync_maker_orders([market])
+    assert strategy.positions["111"].size == Decimal("8")
+
+    _bob_sells(exchange, "12")
+    await strategy._sync_maker_orders([market])
+    position = strategy.positions["111"]
+    assert position.size == Decimal("20") and position.entry_price == Decimal("0.47")
+    assert position.strategy == "directional"
+    assert strategy.maker_orders == {}
+    assert strategy.stats["maker_fills"] == 2
+
+
+@pytest.mark.asyncio
+async def test_maker_entry_pulled_near_close(strategy, market, exchange):
+    """Resting entries are cancelled once the market is about to close."""
+    await strategy._place_order(market, "UP", Decimal("0.48"), 10, strategy="directional")
+    [order_id] = strategy.maker_orders
+
+    closing = replace(market, end_time=datetime.now(timezone.utc) + timedelta(minutes=1))
+    await strategy._sync_maker_orders([closing])
+
+    assert strategy.maker_orders == {}
+    assert exchange.orders[order_id].status == "CANCELED"
+    assert strategy.positions == {}
+
+
+@pytest.mark.asyncio
+async def test_taker_strategies_unaffected_by_maker_entries(strategy, market, exchange):
+    """Latency entries and sum-to-one legs without a hedge price still take."""
+    assert await strategy._place_order(market, "UP", Decimal("0.48"), 10, strategy="latency") is True
+    assert await strategy._place_order(market, "UP", Decimal("0.50"), 10, strategy="sum_to_one") is True
+
+    assert strategy.maker_orders == {} and len(exchange.trades) == 2
+
+
+@pytest.mark.asyncio
+async def test_sum_to_one_maker_leg_hedged_as_it_fills(strategy, market, exchange):
+    """The resting UP leg's fills are matched by DOWN taker buys once enough shares are unhedged."""
+    exchange.seed_book("222", asks=[("0.50", "100")])
+    exchange.seed_book("111", bids=[("0.47", "5")])  # Ahead of the maker leg in the queue
+    assert await strategy._place_order(
+        market, "UP", Decimal("0.48"), 20, strategy="sum_to_one", hedge_price=Decimal("0.50")
+    ) is True
+    [entry] = strategy.maker_orders.values()
+    assert entry.order.price == Decimal("0.47") and entry.hedge_side == "DOWN"
+
+    _bob_sells(exchange, "8")
+    await strategy._sync_maker_orders([market])
+    assert strategy.positions["111"].size == Decimal("3") and "222" not in strategy.positions
+
+    _bob_sells(exchange, "5")
+    await strategy._sync_maker_orders([market])
+    hedge = strategy.positions["222"]
+    assert hedge.size == Decimal("8") and hedge.entry_price == Decimal("0.50")
+    assert hedge.side == "DOWN" and hedge.strategy == "sum_to_one"
+
+    _bob_sells(exchange, "12")
+    await strategy._sync_maker_orders([market])
+    assert strategy.positions["111"].size == strategy.positions["222"].size == Decimal("20")
+    assert strategy.maker_orders == {} and strategy.stats["maker_hedges"] == 2
+
+
+@pytest.mark.asyncio
+async def test_sum_to_one_remainder_hedged_when_leg_pulled(strategy, market, exchange):
+    """A remainder below the CLOB minimum is hedged with a minimum-size order when the leg is pulled."""
+    exchange.seed_book("222", asks=[("0.50", "100")])
+    exchange.seed_book("111", bids=[("0.47", "5")])
+    await strategy._place_order(market, "UP", Decimal("0.48"), 10, strategy="sum_to_one", hedge_price=Decimal("0.50"))
+    _bob_sells(exchange, "8")
+    await strategy._sync_maker_orders([market])
+    assert "222" not in strategy.positions
+
+    closing = replace(market, end_time=datetime.now(timezone.utc) + timedelta(minutes=1))
+    await strategy._sync_maker_orders([closing])
+
+    assert strategy.positions["111"].size == Decimal("3") and strategy.positions["222"].size == Decimal("5")
+    assert strategy.maker_orders == {}
+
+
+@pytest.mark.asyncio
+async def test_sum_to_one_failed_hedge_pulls_resting_leg(strategy, market, exchange):
+    """If the other leg costs more than the hedge price, the resting leg is cancelled and its fills kept."""
+    exchange.seed_book("222", asks=[("0.55", "100")])
+    await strategy._place_order(market, "UP", Decimal("0.48"), 20, strategy="sum_to_one", hedge_price=Decimal("0.50"))
+    [order_id] = strategy.maker_orders
+
+    _bob_sells(exchange, "8")
+    await strategy._sync_maker_orders([market])
+
+    assert strategy.positions["111"].size == Decimal("8") and "222" not in strategy.positions
+    assert exchange.orders[order_id].status == "CANCELED" and strategy.maker_orders == {}
+    assert strategy.stats["unhedged_maker_fills"] == 1
+
+
+@pytest.mark.asyncio
+async def test_sum_to_one_maker_pair_in_dry_run(strategy, market):
+    """In dry run both legs of a sum-to-one maker pair fill at once."""
+    strategy.dry_run = True
+
+    await strategy._place_order(market, "UP", Decimal("0.48"), 20, strategy="sum_to_one", hedge_price=Decimal("0.50"))
+
+    assert strategy.positions["111"].size == strategy.positions["222"].size == Decimal("20")
+    assert strategy.positions["222"].entry_price == Decimal("0.50") and strategy.maker_orders == {}
+
+
+@pytest.mark.asyncio
+async def test_sum_to_one_rests_leg_nearest_even_odds(strategy, market):
+    """With maker entries, sum-to-one rests only the leg with the higher taker fee, hedged by the other."""
+    books = {"111": Decimal("0.40"), "222": Decimal("0.52")}
+    strategy.order_book_analyzer.get_order_book = AsyncMock(
+        side_effect=lambda token_id, **_: Mock(asks=[Mock(price=books[token_id])])
+    )
+    strategy._verify_liquidity_before_entry = AsyncMock(return_value=(True, "OK"))
+    strategy._should_take_trade = Mock(return_value=(True, 80.0, "Test approved"))
+    strategy._place_order = AsyncMock(return_value=True)
+
+    assert await strategy.check_sum_to_one_arbitrage(market) is True
+
+    [call] = strategy._place_order.call_args_list
+    assert call.args[1:3] == ("DOWN", Decimal("0.52"))
+    assert call.kwargs["strategy"] == "sum_to_one" and call.kwargs["hedge_price"] == Decimal("0.40")
@@ -0,0 +1,333 @@
+"""
+Unit tests for the position/PnL ledger and its on-chain reconciliation.
+
+Tests:
+- Average-cost fills, fees and realized PnL
+- Merges, redemptions and transfers
+- Unrealized PnL, cash and equity summary
+- Persistence across ledger instances
+- Reconciliation: drift, orphans, untracked balances, cash drift and repair
+- Settlement grace window for recent fills
+- FifteenMinuteCryptoStrategy and MarketMakingStrategy fills, orphan cleanup
+"""
+
+import json
+
+import pytest
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from unittest.mock import AsyncMock, MagicMock, Mock
+
+from src.clob_simulator import SimulatedClobClient, SimulatedClobExchange
+from src.order_manager import OrderManager
+from src.position_ledger import (
+    ADJUSTMENT,
+    FILL,
+    LedgerReconciler,
+    PositionLedger,
+)
+
+
+@pytest.fixture
+def ledger(tmp_path):
+    """Empty ledger in a temporary database."""
+    return PositionLedger(str(tmp_path / "ledger.db"))
+
+
+def balances(values):
+    """Async token balance getter backed by a dict (missing tokens hold 0)."""
+    async def getter(token_id):
+        value = values.get(token_id, Decimal("0"))
+        if isinstance(value, Exception):
+            raise value
+        return value
+    return getter
+
+
+def usdc(value):
+    """Async USDC balance getter."""
+    return AsyncMock(return_value=value)
+
+
+# ============================================================================
+# Positions and PnL
+# ============================================================================
+
+def test_fills_use_average_cost(ledger):
+    """Buys average into the cost basis; sells realize against it."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"), market_id="0xabc", outcome="UP", strategy="latency")
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.50"))
+    position = ledger.position("111")
+    assert position.shares == Decimal("20") and position.avg_price == Decimal("0.45")
+    assert (position.market_id, position.outcome, position.strategy) == ("0xabc", "UP", "latency")
+
+    ledger.record_fill("111", "SELL", Decimal("5"), Decimal("0.60"))
+    position = ledger.position("111")
+    assert position.shares == Decimal("15") and position.cost_basis == Decimal("6.75")
+    assert position.realized_pnl == Decimal("0.75")
+    assert ledger.cash_balance() == Decimal("-6.0")
+
+
+def test_fees_are_part_of_pnl(ledger):
+    """Fill fees raise cost or cut proceeds; standalone fees are charged too."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.50"), fee=Decimal("0.10"))
+    ledger.record_fill("111", "SELL", Decimal("10"), Decimal("0.60"), fee=Decimal("0.10"))
+    ledger.record_fee(Decimal("0.05"), note="relayer")
+
+    assert ledger.position("111").realized_pnl == Decimal("0.80")
+    assert ledger.realized_pnl() == Decimal("0.75")
+    assert ledger.realized_pnl(strategy="") == Decimal("0.80")
+    assert ledger.summary().fees == Decimal("0.25")
+    assert ledger.positions() == {}
+
+
+def test_invalid_fills_rejected(ledger):
+    """Unknown sides and non-positive sizes are errors."""
+    with pytest.raises(ValueError):
+        ledger.record_fill("111", "HOLD", Decimal("1"), Decimal("0.5"))
+    with pytest.raises(ValueError):
+        ledger.record_fill("111", "BUY", Decimal("0"), Decimal("0.5"))
+
+
+def test_merge_and_redemption_close_positions(ledger):
+    """Merging a pair pays $1; redemption pays the winner and writes off the loser."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.45"))
+    ledger.record_fill("222", "BUY", Decimal("10"), Decimal("0.50"))
+    ledger.record_merge("0xabc", ["111", "222"], Decimal("6"), Decimal("6"), tx_hash="0xmerge")
+
+    assert ledger.position("111").shares == Decimal("4")
+    assert ledger.realized_pnl() == Decimal("0.30")
+
+    ledger.record_redemption("111", Decimal("4"), Decimal("4"), tx_hash="0xredeem")
+    ledger.record_redemption("222", Decimal("4"), Decimal("0"))
+
+    assert ledger.positions() == {}
+    assert ledger.realized_pnl() == Decimal("0.50")
+    assert ledger.cash_balance() == Decimal("0.50")
+
+
+def test_transfers_move_cash_not_pnl(ledger):
+    """Deposits change cash; shares transferred in carry no cost and no PnL."""
+    ledger.record_transfer(usdc=Decimal("100"), note="deposit")
+    ledger.record_transfer(token_id="111", shares=Decimal("5"))
+
+    assert ledger.cash_balance() == Decimal("100")
+    assert ledger.position("111").cost_basis == Decimal("0")
+    assert ledger.realized_pnl() == Decimal("0")
+
+
+def test_summary_marks_open_positions(ledger):
+    """Unrealized PnL and equity use the supplied marks; unmarked positions count at cost."""
+    ledger.record_transfer(usdc=Decimal("100"))
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"))
+    ledger.record_fill("222", "BUY", Decimal("10"), Decimal("0.50"))
+
+    summary = ledger.summary({"111": Decimal("0.55")})
+
+    assert summary.unrealized_pnl == Decimal("1.50")
+    assert summary.cash == Decimal("91.00")
+    assert summary.equity == Decimal("101.50")
+    assert summary.open_positions == 2
+
+
+def test_ledger_persists(ledger):
+    """A new instance on the same database sees the same entries and positions."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"), order_id="order-1")
+
+    reopened = PositionLedger(ledger.db_path)
+
+    [entry] = reopened.entries(kind=FILL)
+    assert entry.reference == "order-1" and entry.shares == Decimal("10")
+    assert reopened.position("111").cost_basis == Decimal("4.00")
+
+
+# ============================================================================
+# Reconciliation
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_reconcile_clean_ledger(ledger):
+    """Matching balances (within tolerance) report no drift."""
+    ledger.record_transfer(usdc=Decimal("10"))
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"))
+    reconciler = LedgerReconciler(ledger, balances({"111": Decimal("9.995")}), usdc(Decimal("6")))
+
+    report = await reconciler.reconcile({"111": Decimal("10")})
+
+    assert not report.has_drift and not report.repaired
+    assert ledger.entries(kind=ADJUSTMENT) == []
+
+
+@pytest.mark.asyncio
+async def test_reconcile_flags_and_repairs_drift(ledger):
+    """Drift, orphans, untracked balances and cash are detected and corrected from the chain."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"))
+    ledger.record_fill("222", "BUY", Decimal("10"), Decimal("0.50"))
+    chain = {"111": Decimal("9.5"), "333": Decimal("3")}
+    reconciler = LedgerReconciler(ledger, balances(chain), usdc(Decimal("20")))
+
+    report = await reconciler.reconcile({"222": Decimal("10"), "333": Decimal("0")})
+
+    kinds = {d.token_id: d.kind for d in report.drifts}
+    assert kinds == {"111": "drift", "222": "orphan", "333": "untracked"}
+    assert report.orphans == ["222"]
+    assert report.cash_drift == Decimal("29.00") and report.repaired
+
+    assert ledger.position("111").shares == Decimal("9.5")
+    assert ledger.position("333").shares == Decimal("3")
+    assert "222" not in ledger.positions()
+    assert ledger.position("222").realized_pnl == Decimal("-5.00")
+    assert ledger.cash_balance() == Decimal("20")
+
+    assert not (await reconciler.reconcile()).has_drift
+
+
+@pytest.mark.asyncio
+async def test_reconcile_without_repair_only_reports(ledger):
+    """With repair disabled the ledger is left untouched."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"))
+    reconciler = LedgerReconciler(ledger, balances({}), repair=False)
+
+    report = await reconciler.reconcile()
+
+    assert report.drifts[0].kind == "orphan" and not report.repaired
+    assert ledger.position("111").shares == Decimal("10")
+
+
+@pytest.mark.asyncio
+async def test_failed_balance_queries_are_skipped(ledger):
+    """Tokens whose balance cannot be read are neither flagged nor adjusted."""
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"))
+    getter = balances({"111": RuntimeError("rpc down")})
+    reconciler = LedgerReconciler(ledger, getter, usdc(None))
+
+    report = await reconciler.reconcile()
+
+    assert report.unchecked == ["111"] and not report.has_drift
+    assert ledger.entries(kind=ADJUSTMENT) == []
+
+
+@pytest.mark.asyncio
+async def test_recent_fills_wait_for_settlement(ledger):
+    """Tokens filled within the grace window are neither repaired nor dropped, and cash waits too."""
+    old = datetime.now(timezone.utc) - timedelta(minutes=5)
+    ledger.record_fill("111", "BUY", Decimal("10"), Decimal("0.40"), timestamp=old)
+    ledger.record_fill("222", "BUY", Decimal("10"), Decimal("0.50"))
+    reconciler = LedgerReconciler(ledger, balances({"111": Decimal("9")}), usdc(Decimal("0")),
+                                  settlement_grace_seconds=60)
+
+    report = await reconciler.reconcile({"222": Decimal("10")})
+
+    assert report.settling == ["222"] and report.orphans == []
+    assert [d.token_id for d in report.drifts] == ["111"]
+    assert report.chain_cash is None and not report.cash_flagged
+    assert ledger.position("111").shares == Decimal("9")
+    assert ledger.position("222").shares == Decimal("10")
+
+
+# ============================================================================
+# Strategy integration
+# ============================================================================
+
+T0 = 1767268800  # 2026-01-01T12:00:00Z
+
+ALICE = "0x00000000000000000000000000000000000A11CE"
+
+SCENARIO = {
+    "start_time": T0,
+    "markets": [{
+        "condition_id": "0xabc", "question": "Will BTC be up in 15 minutes?",
+        "tokens": {"111": "Up", "222": "Down"}, "end_date_iso": "2099-01-01T00:00:00Z",
+    }],
+    "accounts": {ALICE: {"collateral": "100"}},
+    "books": {"111": {"bids": [["0.46", "100"]], "asks": [["0.48", "100"]]}},
+}
+
+
+@pytest.fixture
+def strategy(ledger, tmp_path, monkeypatch):
+    """Live 15-minute strategy on the simulated CLOB recording into the ledger."""
+    from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+
+    monkeypatch.chdir(tmp_path)
+    exchange = SimulatedClobExchange.from_scenario(json.loads(json.dumps(SCENARIO)))
+    client = SimulatedClobClient(exchange, address=ALICE)
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=client,
+        dry_run=False,
+        order_manager=OrderManager(client, Mock(), clock=lambda: exchange.now()),
+        ledger=ledger,
+    )
+    strategy.risk_manager.check_can_trade = MagicMock(
+        return_value=Mock(can_trade=True, max_position_size=Decimal("100.0"))
+    )
+    strategy.dynamic_params.analyze_cost_benefit = MagicMock(
+        return_value=(True, {"net_profit": Decimal("0.5"), "net_profit_pct": 50.0})
+    )
+    strategy.polymarket_ws_feed = AsyncMock()
+    return strategy
+
+
+@pytest.fixture
+def market():
+    """15-minute market on the simulated token pair."""
+    from src.fifteen_min_crypto_strategy import CryptoMarket
+
+    return CryptoMarket(
+        market_id="0xabc",
+        question="Will BTC be up in 15 minutes?",
+        asset="BTC",
+        up_token_id="111",
+        down_token_id="222",
+        up_price=Decimal("0.48"),
+        down_price=Decimal("0.52"),
+        end_time=datetime.now(timezone.utc) + timedelta(minutes=10),
+        neg_risk=False,
+    )
+
+
+@pytest.mark.asyncio
+async def test_strategy_entry_recorded_in_ledger(strategy, market, ledger):
+    """A live taker entry is recorded as a BUY fill with strategy and outcome."""
+    assert await strategy._place_order(market, "UP", Decimal("0.48"), 10, strategy="latency") is True
+
+    [entry] = ledger.entries(kind=FILL)
+    assert entry.token_id == "111" and entry.shares == Decimal("10")
+    assert (entry.outcome, entry.strategy, entry.market_id) == ("UP", "latency", "0xabc")
+    assert entry.reference
+    assert ledger.position("111").shares == strategy.positions["111"].size
+
+
+@pytest.mark.asyncio
+async def test_reconciliation_repairs_strategy_positions(strategy, market, ledger):
+    """Orphans are dropped from the strategy and drifted sizes follow the chain."""
+    await strategy._place_order(market, "UP", Decimal("0.48"), 10, strategy="latency")
+    await strategy._place_order(market, "DOWN", Decimal("0.52"), 10, strategy="latency")
+    reconciler = LedgerReconciler(ledger, balances({"111": Decimal("9.9")}))
+
+    report = await reconciler.reconcile(strategy.tracked_shares())
+    strategy.apply_reconciliation(report)
+
+    assert "222" in report.orphans
+    assert set(strategy.positions) == {"111"}
+    assert strategy.positions["111"].size == Decimal("9.9")
+
+
+def test_market_maker_fills_recorded(ledger):
+    """Market-making fills are recorded under the market_making strategy."""
+    from src.fifteen_min_crypto_strategy import CryptoMarket
+    from src.market_making_strategy import MarketMakingStrategy, Quote
+
+    market = CryptoMarket(
+        market_id="0xabc", question="Bitcoin Up or Down?", asset="BTC", up_token_id="111",
+        down_token_id="222", up_price=Decimal("0.5"), down_price=Decimal("0.5"),
+        end_time=datetime.now(timezone.utc) + timedelta(minutes=10), neg_risk=False
+    )
+    strategy = MarketMakingStrategy(clob_client=Mock(), order_manager=Mock(), ledger=ledger)
+    order = Mock(market_id="222", order_id="q1", size_matched=Decimal("4"), fill_price=None, price=Decimal("0.47"))
+
+    strategy._apply_fills(Quote(order=order, market=market, side="DOWN"))
+
+    [entry] = ledger.entries(kind=FILL)
+    assert (entry.token_id, entry.outcome, entry.strategy) == ("222", "DOWN", "market_making")
+    assert entry.usdc == Decimal("-1.88")
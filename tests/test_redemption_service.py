@@ -0,0 +1,335 @@
+"""
+Unit tests for automatic redemption of resolved positions.
+
+Tests:
+- Discovery: on-chain resolution and balance checks, data API and watched positions
+- CTF and NegRiskAdapter redeem paths through TransactionManager
+- Gnosis Safe wallets (execTransaction with the owner's pre-validated signature)
+- Proceeds recorded in trade history and the position ledger
+- Losing-only conditions (skipped and written off), failed transactions and dry run
+- Resolutions of recorded markets written to the market data recorder
+- FifteenMinuteCryptoStrategy handing orphan positions to the service
+"""
+
+import pytest
+from datetime import datetime, timezone
+from decimal import Decimal
+from unittest.mock import AsyncMock, Mock
+
+from src.position_ledger import REDEMPTION, PositionLedger
+from src.redemption_service import RedeemablePosition, RedemptionService
+from src.trade_history import TradeHistoryDB
+
+CTF = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"
+USDC = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
+ADAPTER = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"
+OWNER = "0x1111111111111111111111111111111111111111"
+SAFE = "0x2222222222222222222222222222222222222222"
+WON = "0x" + "a" * 64
+LOST = "0x" + "b" * 64
+OPEN = "0x" + "c" * 64
+
+
+class FakeCall:
+    """A bound contract function: call(), estimate_gas() and build_transaction()."""
+
+    def __init__(self, address, name, args, result=None):
+        self.address = address
+        self.name = name
+        self.args = args
+        self.result = result
+
+    def call(self):
+        return self.result
+
+    def estimate_gas(self, params):
+        return 100000
+
+    def build_transaction(self, params):
+        return {**params, "to": self.address, "call": self}
+
+    def _encode_transaction_data(self):
+        return f"{self.name}{self.args}"
+
+
+class FakeContract:
+    """Contract whose functions are created on attribute access."""
+
+    def __init__(self, address, chain=None):
+        self.address = address
+        self.chain = chain
+        self.functions = self
+
+    def __getattr__(self, name):
+        def bind(*args):
+            result = self.chain.view(name, args) if self.chain else None
+            return FakeCall(self.address, name, args, result)
+        return bind
+
+
+class FakeChain:
+    """CTF state: payouts per condition and share balances per (holder, token)."""
+
+    def __init__(self):
+        self.payouts = {WON: [1, 0], LOST: [0, 1], OPEN: None}
+        self.balances = {}
+
+    def view(self, name, args):
+        if name == "payoutDenominator":
+            return 0 if self.payouts.get(args[0]) is None else sum(self.payouts[args[0]])
+        if name == "payoutNumerators":
+            return self.payouts[args[0]][args[1]]
+        if name == "balanceOf":
+            return self.balances.get((args[0], args[1]), 0)
+        return None
+
+
+@pytest.fixture
+def chain():
+    return FakeChain()
+
+
+@pytest.fixture
+def web3(chain):
+    web3 = Mock()
+    web3.eth.gas_price = 30 * 10 ** 9
+    web3.eth.contract.side_effect = lambda address, abi: FakeContract(
+        address, chain if address == CTF else None
+    )
+    return web3
+
+
+@pytest.fixture
+def transaction_manager():
+    tm = Mock()
+    tm.wallet.address = OWNER
+    tm.send_transaction = AsyncMock(return_value="0xredeem")
+    tm.estimate_fees = AsyncMock(return_value={"maxFeePerGas": 120 * 10 ** 9, "maxPriorityFeePerGas": 30 * 10 ** 9})
+    tm.wait_for_confirmation = AsyncMock(return_value={"status": 1, "gasUsed": 90000})
+    return tm
+
+
+@pytest.fixture
+def ledger(tmp_path):
+    return PositionLedger(str(tmp_path / "ledger.db"))
+
+
+@pytest.fixture
+def history(tmp_path):
+    return TradeHistoryDB(str(tmp_path / "trades.db"))
+
+
+def make_service(web3, tm, positions=(), **kwargs):
+    async def source():
+        return [RedeemablePosition(**p) for p in positions]
+    return RedemptionService(web3, tm, ctf_address=CTF, usdc_address=USDC, position_source=source, **kwargs)
+
+
+def hold(chain, token_id, shares, holder=OWNER):
+    chain.balances[(holder, int(token_id))] = int(Decimal(shares) * 10 ** 6)
+
+
+# ============================================================================
+# Discovery
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_discover_keeps_resolved_held_positions(web3, transaction_manager, chain):
+    """Only resolved conditions with an on-chain balance are returned, with their payouts."""
+    hold(chain, "1", "10")
+    hold(chain, "3", "4")
+    service = make_service(web3, transaction_manager, [
+        dict(condition_id=WON, token_id="1", outcome_index=0),
+        dict(condition_id=WON, token_id="2", outcome_index=1),  # No balance: already redeemed
+        dict(condition_id=OPEN, token_id="3", outcome_index=0),  # Not resolved yet
+    ])
+
+    resolved = await service.discover()
+
+    assert list(resolved) == [WON]
+    [position] = resolved[WON]
+    assert position.shares == Decimal("10")
+    assert position.payout_per_share == Decimal("1") and position.payout == Decimal("10")
+
+
+@pytest.mark.asyncio
+async def test_watched_positions_are_discovered(web3, transaction_manager, chain):
+    """Positions handed over with watch() are checked even when the API does not list them."""
+    hold(chain, "7", "5")
+    service = make_service(web3, transaction_manager)
+    service.watch(LOST, "7", outcome_index=1, neg_risk=True)
+
+    resolved = await service.discover()
+
+    [position] = resolved[LOST]
+    assert position.neg_risk is True and position.payout_per_share == Decimal("1")
+
+
+# ============================================================================
+# Redemption
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_ctf_redemption_records_proceeds(web3, transaction_manager, chain, ledger, history):
+    """A standard market redeems both index sets and records proceeds and PnL."""
+    ledger.record_fill("1", "BUY", Decimal("10"), Decimal("0.60"), market_id=WON, outcome="UP", strategy="latency")
+    hold(chain, "1", "10")
+    service = make_service(
+        web3, transaction_manager, [dict(condition_id=WON, token_id="1", outcome_index=0)],
+        ledger=ledger, trade_history=history
+    )
+
+    [result] = await service.redeem_all()
+
+    tx = transaction_manager.send_transaction.call_args.args[0]
+    assert tx["to"] == CTF and tx["from"] == OWNER
+    assert tx["maxFeePerGas"] == 120 * 10 ** 9 and "gasPrice" not in tx
+    assert tx["call"].name == "redeemPositions"
+    assert tx["call"].args == (USDC, RedemptionService.ZERO_BYTES32, WON, [1, 2])
+    assert result.success and result.tx_hash == "0xredeem"
+    assert result.payout == Decimal("10") and result.profit == Decimal("4")
+
+    [entry] = ledger.entries(kind=REDEMPTION)
+    assert entry.shares == Decimal("-10") and entry.usdc == Decimal("10") and entry.reference == "0xredeem"
+    assert not ledger.position("1").is_open
+
+    [trade] = history.get_trades_by_strategy("redemption")
+    assert trade["market_id"] == WON and trade["status"] == "success"
+    assert Decimal(trade["net_profit"]) == Decimal("4") and trade["merge_tx_hash"] == "0xredeem"
+
+
+@pytest.mark.asyncio
+async def test_neg_risk_redemption_uses_adapter_amounts(web3, transaction_manager, chain):
+    """Neg-risk markets redeem through the adapter with amounts per outcome index."""
+    hold(chain, "1", "2.5")
+    hold(chain, "2", "1")
+    service = make_service(web3, transaction_manager, [
+        dict(condition_id=WON, token_id="1", outcome_index=0, neg_risk=True),
+        dict(condition_id=WON, token_id="2", outcome_index=1, neg_risk=True),
+    ])
+
+    [result] = await service.redeem_all()
+
+    tx = transaction_manager.send_transaction.call_args.args[0]
+    assert tx["to"] == ADAPTER
+    assert tx["call"].args == (WON, [2500000, 1000000])
+    assert result.neg_risk and result.payout == Decimal("2.5")
+
+
+@pytest.mark.asyncio
+async def test_safe_wallet_wraps_redeem_in_exec_transaction(web3, transaction_manager, chain):
+    """Shares in a Safe are redeemed by the owner through execTransaction."""
+    hold(chain, "1", "3", holder=SAFE)
+    service = make_service(
+        web3, transaction_manager, [dict(condition_id=WON, token_id="1", outcome_index=0)],
+        safe_address=SAFE
+    )
+
+    [result] = await service.redeem_all()
+
+    tx = transaction_manager.send_transaction.call_args.args[0]
+    assert tx["to"] == SAFE and tx["from"] == OWNER
+    assert tx["call"].name == "execTransaction"
+    to, value, data, operation = tx["call"].args[:4]
+    assert (to, value, operation) == (CTF, 0, 0) and data.startswith("redeemPositions")
+    signature = tx["call"].args[-1]
+    assert signature == bytes(12) + bytes.fromhex(OWNER[2:]) + bytes(32) + b"\x01"
+    assert result.success and result.payout == Decimal("3")
+
+
+@pytest.mark.asyncio
+async def test_losing_only_conditions_skipped(web3, transaction_manager, chain):
+    """Redeeming shares that pay $0 costs gas, so it is opt-in."""
+    hold(chain, "2", "10")
+    positions = [dict(condition_id=WON, token_id="2", outcome_index=1)]
+
+    assert await make_service(web3, transaction_manager, positions).redeem_all() == []
+
+    [result] = await make_service(web3, transaction_manager, positions, redeem_losers=True).redeem_all()
+    assert result.success and result.payout == Decimal("0")
+
+
+@pytest.mark.asyncio
+async def test_losing_only_conditions_written_off(web3, transaction_manager, chain, ledger):
+    """Unredeemed losers stop being watched and their ledger position closes at $0."""
+    ledger.record_fill("2", "BUY", Decimal("10"), Decimal("0.40"), market_id=WON, outcome="DOWN", strategy="latency")
+    hold(chain, "2", "10")
+    service = make_service(web3, transaction_manager, ledger=ledger)
+    service.watch(WON, "2", outcome_index=1)
+
+    assert await service.redeem_all() == []
+    assert await service.redeem_all() == []
+
+    assert service.watched == {}
+    [entry] = ledger.entries(kind=REDEMPTION)
+    assert entry.shares == Decimal("-10") and entry.usdc == Decimal("0") and entry.reference is None
+    assert not ledger.position("2").is_open
+    assert ledger.realized_pnl() == Decimal("-4")
+    transaction_manager.send_transaction.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_failed_redemption_recorded_and_retried(web3, transaction_manager, chain, ledger, history):
+    """A reverted redemption is recorded as failed and stays watched for the next run."""
+    hold(chain, "1", "10")
+    transaction_manager.wait_for_confirmation.side_effect = Exception("Transaction reverted: 0xredeem")
+    service = make_service(web3, transaction_manager, ledger=ledger, trade_history=history)
+    service.watch(WON, "1", outcome_index=0)
+
+    [result] = await service.redeem_all()
+
+    assert not result.success and "reverted" in result.error
+    assert "1" in service.watched
+    assert ledger.entries(kind=REDEMPTION) == []
+    [trade] = history.get_trades_by_strategy("redemption")
+    assert trade["status"] == "failed"
+
+
+@pytest.mark.asyncio
+async def test_dry_run_sends_nothing(web3, transaction_manager, chain):
+    """In dry run the redemption is only logged."""
+    hold(chain, "1", "10")
+    service = make_service(
+        web3, transaction_manager, [dict(condition_id=WON, token_id="1", outcome_index=0)], dry_run=True
+    )
+
+    [result] = await service.redeem_all()
+
+    assert not result.success and result.payout == Decimal("10")
+    transaction_manager.send_transaction.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_resolutions_written_to_recorder(web3, transaction_manager, chain):
+    """Each ended recorded market gets its winner once resolved; open ones stay pending."""
+    recorder = Mock()
+    recorder.awaiting_resolution.return_value = [WON, LOST, OPEN]
+    service = make_service(web3, transaction_manager, recorder=recorder)
+
+    assert await service.redeem_all() == []
+
+    assert [c.args for c in recorder.record_resolution.call_args_list] == [(WON, "UP"), (LOST, "DOWN")]
+
+
+# ============================================================================
+# Strategy integration
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_orphan_position_handed_to_redemption(tmp_path, monkeypatch):
+    """An orphaned 15-minute position is watched for redemption instead of only logged."""
+    from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy, Position
+
+    monkeypatch.chdir(tmp_path)
+    service = Mock()
+    strategy = FifteenMinuteCryptoStrategy(clob_client=Mock(), dry_run=False, redemption_service=service)
+    position = Position(
+        token_id="222", side="DOWN", entry_price=Decimal("0.52"), size=Decimal("10"),
+        entry_time=datetime.now(timezone.utc), market_id=WON, asset="BTC", neg_risk=False
+    )
+    to_close = []
+
+    await strategy._handle_orphan_position(position, "222", 20.0, to_close)
+
+    assert to_close == ["222"]
+    service.watch.assert_called_once_with(WON, "222", outcome_index=1, neg_risk=False)
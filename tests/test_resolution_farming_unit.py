@@ -79,6 +79,7 @@ class TestResolutionFarmingEngine:
         assert len(opportunities) == 1
         assert opportunities[0].total_cost == Decimal('0.97')
         assert opportunities[0].expected_profit == Decimal('0.03')
+        assert opportunities[0].side == "YES"
     
     @pytest.mark.asyncio
     async def test_scan_finds_opportunity_at_99_cents(self, engine):
@@ -0,0 +1,245 @@
+"""
+Unit tests for pluggable secrets backends.
+
+Tests:
+- SecretsManager defaults (environment, AWS) and get_private_key with any backend
+- Encrypted keystore: passphrase from env, file or prompt; wrong passphrase
+- Vault KV v1/v2 and error handling
+- systemd credentials directory and file descriptor
+- Backend selection through Config.from_env / from_yaml and validation
+"""
+
+import json
+import os
+from unittest.mock import Mock
+
+import pytest
+import yaml
+
+from src.secrets_backends import (
+    AwsSecretsBackend,
+    CredentialsSecretsBackend,
+    EnvSecretsBackend,
+    KeystoreSecretsBackend,
+    VaultSecretsBackend,
+    create_secrets_backend,
+)
+from src.secrets_manager import SecretsManager, get_secrets_manager
+
+PRIVATE_KEY = "0x" + "4c" * 32
+WALLET = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+
+
+@pytest.fixture(autouse=True)
+def clean_env(monkeypatch):
+    for var in ("PRIVATE_KEY", "WALLET_ADDRESS", "NVIDIA_API_KEY", "KALSHI_API_KEY", "KEYSTORE_PASSPHRASE",
+                "VAULT_ADDR", "VAULT_TOKEN", "VAULT_NAMESPACE", "CREDENTIALS_DIRECTORY", "SECRETS_BACKEND",
+                "USE_AWS_SECRETS", "SECRETS_FD", "KEYSTORE_PATH", "SECRET_NAME"):
+        monkeypatch.delenv(var, raising=False)
+
+
+@pytest.fixture
+def keystore(tmp_path):
+    from eth_account import Account
+
+    path = tmp_path / "keystore.json"
+    path.write_text(json.dumps(Account.encrypt(PRIVATE_KEY, "correct horse")))
+    return str(path)
+
+
+class FakeResponse:
+    def __init__(self, status_code, body):
+        self.status_code = status_code
+        self._body = body
+
+    def json(self):
+        return self._body
+
+
+# ============================================================================
+# SecretsManager
+# ============================================================================
+
+def test_manager_without_aws_reads_environment(monkeypatch):
+    """The existing env fallback and get_private_key keep working."""
+    monkeypatch.setenv("PRIVATE_KEY", PRIVATE_KEY)
+    monkeypatch.setenv("WALLET_ADDRESS", WALLET)
+
+    manager = get_secrets_manager(use_aws=False)
+
+    assert isinstance(manager.backend, EnvSecretsBackend) and not manager.use_aws
+    assert manager.get_private_key() == PRIVATE_KEY
+    assert manager.get_secret("ignored")["wallet_address"] == WALLET
+
+
+def test_aws_backend_reads_secret_string():
+    client = Mock()
+    client.get_secret_value.return_value = {"SecretString": json.dumps({"private_key": PRIVATE_KEY})}
+
+    manager = SecretsManager(backend=AwsSecretsBackend(client=client))
+
+    assert manager.use_aws
+    assert manager.get_private_key("bot-creds") == PRIVATE_KEY
+    client.get_secret_value.assert_called_once_with(SecretId="bot-creds")
+
+
+def test_unknown_backend_rejected():
+    with pytest.raises(ValueError, match="Unknown secrets backend"):
+        create_secrets_backend("pastebin")
+
+
+# ============================================================================
+# Keystore
+# ============================================================================
+
+def test_keystore_unlocked_with_env_passphrase(keystore, monkeypatch):
+    monkeypatch.setenv("KEYSTORE_PASSPHRASE", "correct horse")
+    monkeypatch.setenv("NVIDIA_API_KEY", "nv-key")
+
+    manager = get_secrets_manager(backend=create_secrets_backend("keystore", path=keystore))
+    secret = manager.get_secret("polymarket-bot-credentials")
+
+    assert manager.get_private_key() == PRIVATE_KEY
+    assert secret["wallet_address"].lower() == WALLET.lower()
+    assert secret["nvidia_api_key"] == "nv-key"
+
+
+def test_keystore_passphrase_file_and_prompt(keystore, tmp_path):
+    passphrase_file = tmp_path / "passphrase"
+    passphrase_file.write_text("correct horse\n")
+    assert KeystoreSecretsBackend(keystore, passphrase_file=str(passphrase_file)).get_secret("")["private_key"] == PRIVATE_KEY
+
+    prompt = Mock(return_value="correct horse")
+    backend = KeystoreSecretsBackend(keystore, prompt=prompt)
+    backend.get_secret("")
+    backend.get_secret("")
+    prompt.assert_called_once()  # Unlocked once per process
+
+
+def test_keystore_wrong_passphrase(keystore):
+    with pytest.raises(ValueError, match="Cannot decrypt keystore"):
+        KeystoreSecretsBackend(keystore, passphrase="battery staple").get_secret("")
+
+
+# ============================================================================
+# Vault
+# ============================================================================
+
+def test_vault_kv2_reads_nested_data(monkeypatch):
+    monkeypatch.setenv("VAULT_TOKEN", "s.token")
+    session = Mock()
+    session.get.return_value = FakeResponse(200, {"data": {"data": {"private_key": PRIVATE_KEY}, "metadata": {}}})
+
+    backend = VaultSecretsBackend("https://vault:8200/", mount="kv", namespace="trading", session=session)
+    secret = backend.get_secret("bots/polymarket")
+
+    url = session.get.call_args.args[0]
+    headers = session.get.call_args.kwargs["headers"]
+    assert url == "https://vault:8200/v1/kv/data/bots/polymarket"
+    assert headers == {"X-Vault-Token": "s.token", "X-Vault-Namespace": "trading"}
+    assert secret["private_key"] == PRIVATE_KEY
+
+
+def test_vault_kv1_and_token_file(tmp_path):
+    token_file = tmp_path / "token"
+    token_file.write_text("s.agent\n")
+    session = Mock()
+    session.get.return_value = FakeResponse(200, {"data": {"private_key": PRIVATE_KEY}})
+
+    backend = VaultSecretsBackend("https://vault", kv_version=1, secret_path="bot", token_file=str(token_file), session=session)
+
+    assert backend.get_secret("ignored")["private_key"] == PRIVATE_KEY
+    assert session.get.call_args.args[0] == "https://vault/v1/secret/bot"
+    assert session.get.call_args.kwargs["headers"]["X-Vault-Token"] == "s.agent"
+
+
+def test_vault_errors_are_reported():
+    session = Mock()
+    session.get.return_value = FakeResponse(403, {"errors": ["permission denied"]})
+    backend = VaultSecretsBackend("https://vault", token="bad", session=session)
+
+    with pytest.raises(ValueError, match="HTTP 403 \\(permission denied\\)"):
+        backend.get_secret("bot")
+
+    session.get.return_value = FakeResponse(200, {"data": {"data": {"api_key": "x"}}})
+    with pytest.raises(ValueError, match="private_key"):
+        backend.get_secret("bot")
+
+
+# ============================================================================
+# systemd credentials / file descriptor
+# ============================================================================
+
+def test_credentials_directory_per_field_and_bundle(tmp_path, monkeypatch):
+    (tmp_path / "private_key").write_text(PRIVATE_KEY + "\n")
+    (tmp_path / "wallet_address").write_text(WALLET)
+    monkeypatch.setenv("CREDENTIALS_DIRECTORY", str(tmp_path))
+
+    secret = CredentialsSecretsBackend().get_secret("polymarket-bot-credentials")
+    assert secret == {"private_key": PRIVATE_KEY, "wallet_address": WALLET}
+
+    (tmp_path / "polymarket-bot-credentials").write_text(json.dumps({"private_key": "0x" + "11" * 32}))
+    assert CredentialsSecretsBackend().get_secret("polymarket-bot-credentials")["private_key"] == "0x" + "11" * 32
+
+
+def test_credentials_from_file_descriptor():
+    read_fd, write_fd = os.pipe()
+    os.write(write_fd, PRIVATE_KEY.encode())
+    os.close(write_fd)
+
+    backend = CredentialsSecretsBackend(fd=read_fd)
+
+    assert backend.get_secret("")["private_key"] == PRIVATE_KEY
+    assert backend.get_secret("")["private_key"] == PRIVATE_KEY  # Cached; the fd is read once
+    with pytest.raises(OSError):
+        os.fstat(read_fd)
+
+
+# ============================================================================
+# Config selection
+# ============================================================================
+
+def test_config_from_env_uses_selected_backend(tmp_path, monkeypatch, keystore):
+    from config.config import Config
+
+    monkeypatch.chdir(tmp_path)  # No stray .env
+    monkeypatch.setenv("SECRETS_BACKEND", "keystore")
+    monkeypatch.setenv("KEYSTORE_PATH", keystore)
+    monkeypatch.setenv("KEYSTORE_PASSPHRASE", "correct horse")
+    monkeypatch.setenv("PRIVATE_KEY", "0x" + "99" * 32)  # Ignored in favour of the keystore
+
+    config = Config.from_env()
+
+    assert config.private_key == PRIVATE_KEY
+    assert config.wallet_address.lower() == WALLET.lower()
+    assert config.secrets_backend == "keystore" and config.keystore_path == keystore
+    assert config.to_dict()["secrets_backend"] == "keystore"
+    assert PRIVATE_KEY not in json.dumps(config.to_dict())
+
+
+def test_config_from_yaml_fills_missing_key(tmp_path, monkeypatch):
+    from config.config import Config
+
+    (tmp_path / "private_key").write_text(PRIVATE_KEY)
+    path = tmp_path / "config.yaml"
+    path.write_text(yaml.safe_dump({
+        "private_key": "",
+        "wallet_address": WALLET,
+        "polygon_rpc_url": "https://polygon-rpc.com",
+        "secrets_backend": "credentials",
+        "credentials_directory": str(tmp_path),
+    }))
+
+    assert Config.from_yaml(str(path)).private_key == PRIVATE_KEY
+
+
+def test_config_validates_backend_options():
+    from config.config import Config
+
+    base = dict(private_key=PRIVATE_KEY, wallet_address=WALLET, polygon_rpc_url="https://polygon-rpc.com")
+    with pytest.raises(ValueError, match="keystore_path"):
+        Config(**base, secrets_backend="keystore")
+    with pytest.raises(ValueError, match="secrets_backend must be one of"):
+        Config(**base, secrets_backend="pastebin")
+    with pytest.raises(ValueError, match="vault_kv_version"):
+        Config(**base, secrets_backend="vault", vault_addr="https://vault", vault_kv_version=3)
@@ -0,0 +1,340 @@
+"""
+Unit tests for the signer abstraction and the signing daemon.
+
+Tests:
+- RemoteSigner <-> SignerDaemon round trip over a Unix socket
+- Order policy: per-order and daily notional caps, exchange, maker, taker
+- Transaction policy: allowed contracts, approvals, transfers, Safe-wrapped calls, value and chain
+- ClobAuth freshness
+- py_clob_client integration: order builder signing through a Signer, API credential derivation
+- Config validation for the remote signer
+"""
+
+import asyncio
+import sys
+from decimal import Decimal
+from types import SimpleNamespace
+from unittest.mock import Mock
+
+import pytest
+
+from src.signer import (
+    ZERO_ADDRESS,
+    RemoteSigner,
+    SignedTransaction,
+    Signer,
+    SignerError,
+    attach_signer,
+    derive_api_creds,
+    order_typed_data,
+)
+from src.signer_daemon import PolicyEngine, PolicyViolation, SignerDaemon, SigningPolicy
+
+EOA = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+SAFE = "0x93e65c1419AB8147cbd16d440Bb7FC178b3b2F35"
+EXCHANGE = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"
+USDC = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
+CTF = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"
+ATTACKER = "0x000000000000000000000000000000000000dEaD"
+NOW = 1_790_000_000.0
+
+
+class FakeSigner(Signer):
+    """Deterministic in-process signer (records what it signed)."""
+
+    def __init__(self):
+        self.signed = []
+
+    @property
+    def address(self):
+        return EOA
+
+    def sign_transaction(self, tx):
+        self.signed.append(("tx", tx))
+        return SignedTransaction(b"\x02raw", b"\xab" * 32)
+
+    def sign_order(self, order, exchange_address, chain_id):
+        self.signed.append(("order", order_typed_data(order, exchange_address, chain_id)))
+        return "0x" + "11" * 65
+
+    def sign_clob_auth(self, timestamp, nonce, chain_id):
+        self.signed.append(("auth", timestamp, nonce))
+        return "0x" + "22" * 65
+
+
+def make_policy(**overrides):
+    settings = dict(
+        max_order_notional=Decimal("50"),
+        max_daily_notional=Decimal("120"),
+        allowed_makers={SAFE},
+        allowed_contracts={USDC, CTF, SAFE},
+        allowed_spenders={EXCHANGE},
+    )
+    settings.update(overrides)
+    return SigningPolicy(**settings)
+
+
+def make_order(usdc="10", side="BUY", maker=SAFE, taker=ZERO_ADDRESS):
+    return {
+        "salt": 1, "maker": maker, "signer": EOA, "taker": taker, "tokenId": "123",
+        "makerAmount": str(int(Decimal(usdc) * 1_000_000)), "takerAmount": "20000000",
+        "expiration": "0", "nonce": "0", "feeRateBps": "0", "side": side, "signatureType": 2,
+    }
+
+
+def calldata(selector, *words, tail=b""):
+    """ABI calldata from a selector and 32-byte head words (addresses or ints)."""
+    out = bytes.fromhex(selector)
+    for word in words:
+        value = int(word, 16) if isinstance(word, str) else word
+        out += value.to_bytes(32, "big")
+    return "0x" + (out + tail).hex()
+
+
+def safe_exec(to, data_hex, operation=0, refund=(0, 0, 0, 0, 0)):
+    """Gnosis Safe execTransaction(to, value, data, operation, safeTxGas, baseGas, gasPrice, gasToken, refundReceiver, ...) calldata."""
+    inner = bytes.fromhex(data_hex[2:])
+    padded = inner + b"\x00" * (-len(inner) % 32)
+    return calldata("6a761202", to, 0, 320, operation, *refund, 352 + len(padded),
+                    tail=len(inner).to_bytes(32, "big") + padded + (0).to_bytes(32, "big"))
+
+
+def tx(to, data="0x", value=0, chain_id=137, **extra):
+    return {"to": to, "data": data, "value": value, "chainId": chain_id, "nonce": 3, "gas": 100000,
+            "maxFeePerGas": 100 * 10 ** 9, "maxPriorityFeePerGas": 30 * 10 ** 9, **extra}
+
+
+@pytest.fixture
+def engine():
+    return PolicyEngine(make_policy(), EOA, clock=lambda: NOW)
+
+
+# ============================================================================
+# Daemon round trip
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_remote_signer_round_trip(tmp_path):
+    """The bot signs through the socket; refusals come back as SignerError."""
+    fake = FakeSigner()
+    daemon = SignerDaemon(fake, make_policy(), str(tmp_path / "signer.sock"), clock=lambda: NOW)
+    await daemon.start()
+    remote = RemoteSigner(daemon.socket_path)
+    loop = asyncio.get_running_loop()
+    try:
+        assert await loop.run_in_executor(None, lambda: remote.address) == EOA
+
+        signed = await loop.run_in_executor(None, remote.sign_transaction,
+                                            tx(USDC, calldata("095ea7b3", EXCHANGE, 2 ** 256 - 1)))
+        assert signed.raw_transaction == b"\x02raw" and signed.rawTransaction == b"\x02raw"
+
+        signature = await loop.run_in_executor(None, remote.sign_order, make_order("25"), EXCHANGE, 137)
+        assert signature == "0x" + "11" * 65
+        assert daemon.policy_engine.daily_notional == Decimal("25")
+
+        with pytest.raises(SignerError, match="policy: transfer to .* not allowed"):
+            await loop.run_in_executor(None, remote.sign_transaction,
+                                       tx(USDC, calldata("a9059cbb", ATTACKER, 10 ** 12)))
+    finally:
+        await daemon.stop()
+
+    assert [entry[0] for entry in fake.signed] == ["tx", "order"]  # The refused transfer never reached the key
+    with pytest.raises(SignerError, match="unreachable"):
+        RemoteSigner(daemon.socket_path).sign_clob_auth(int(NOW), 0, 137)
+
+
+def test_daemon_rejects_malformed_requests():
+    daemon = SignerDaemon(FakeSigner(), make_policy(), "/nonexistent.sock")
+
+    assert "unknown method" in daemon.handle_request(b'{"id": "1", "method": "sign_hash", "params": {}}')["error"]
+    assert daemon.handle_request(b"not json")["error"]
+
+
+# ============================================================================
+# Order policy
+# ============================================================================
+
+def test_order_notional_caps(engine):
+    engine.record_order(engine.check_order(make_order("50"), EXCHANGE, 137))
+    engine.record_order(engine.check_order(make_order("50", side="SELL"), EXCHANGE, 137))
+
+    with pytest.raises(PolicyViolation, match="exceeds max \\$50"):
+        engine.check_order(make_order("50.01"), EXCHANGE, 137)
+    with pytest.raises(PolicyViolation, match="daily notional"):
+        engine.check_order(make_order("30"), EXCHANGE, 137)
+
+    engine._clock = lambda: NOW + 86400  # Next UTC day
+    assert engine.check_order(make_order("30"), EXCHANGE, 137) == Decimal("30")
+
+
+def test_order_addresses_checked(engine):
+    with pytest.raises(PolicyViolation, match="exchange"):
+        engine.check_order(make_order(), ATTACKER, 137)
+    with pytest.raises(PolicyViolation, match="maker"):
+        engine.check_order(make_order(maker=ATTACKER), EXCHANGE, 137)
+    with pytest.raises(PolicyViolation, match="non-zero taker"):
+        engine.check_order(make_order(taker=ATTACKER), EXCHANGE, 137)
+    with pytest.raises(PolicyViolation, match="chain 80002"):
+        engine.check_order(make_order(), EXCHANGE, 80002)
+    assert engine.check_order(make_order(maker=EOA.lower()), EXCHANGE.lower(), 137) == Decimal("10")
+
+
+# ============================================================================
+# Transaction policy
+# ============================================================================
+
+def test_transaction_targets_and_approvals(engine):
+    engine.check_transaction(tx(CTF, calldata("a22cb465", EXCHANGE, 1)))
+    engine.check_transaction(tx(CTF, calldata("9e7212ad", USDC, 0)))  # mergePositions: not token-moving
+
+    with pytest.raises(PolicyViolation, match="contract .* not allowed"):
+        engine.check_transaction(tx(ATTACKER))
+    with pytest.raises(PolicyViolation, match="contract creation"):
+        engine.check_transaction(tx(None, "0x6080"))
+    with pytest.raises(PolicyViolation, match="spender"):
+        engine.check_transaction(tx(USDC, calldata("095ea7b3", ATTACKER, 2 ** 256 - 1)))
+    with pytest.raises(PolicyViolation, match="value"):
+        engine.check_transaction(tx(USDC, value=1))
+    with pytest.raises(PolicyViolation, match="gas price"):
+        engine.check_transaction(tx(USDC, maxFeePerGas=2000 * 10 ** 9))
+    with pytest.raises(PolicyViolation, match="malformed"):
+        engine.check_transaction(tx(USDC, "0x095ea7b3" + "00" * 8))
+
+
+def test_transfers_only_to_own_wallets(engine):
+    engine.check_transaction(tx(USDC, calldata("a9059cbb", EOA, 10 ** 6)))
+    with pytest.raises(PolicyViolation, match="transfer to"):
+        engine.check_transaction(tx(CTF, calldata("f242432a", SAFE, ATTACKER, 123, 10 ** 6, 160)))
+    with pytest.raises(PolicyViolation, match="transfer to"):
+        engine.check_transaction(tx(USDC, calldata("23b872dd", SAFE, ATTACKER, 10 ** 6)))
+
+
+def test_cancellation_self_send_allowed(engine):
+    engine.check_transaction(tx(EOA))
+    with pytest.raises(PolicyViolation, match="contract .* not allowed"):
+        engine.check_transaction(tx(EOA, value=1))
+    with pytest.raises(PolicyViolation, match="contract .* not allowed"):
+        engine.check_transaction(tx(EOA, calldata("a9059cbb", ATTACKER, 10 ** 6)))
+
+
+def test_safe_wrapped_calls_checked(engine):
+    """A Safe execTransaction is judged by the call it wraps."""
+    engine.check_transaction(tx(SAFE, safe_exec(CTF, calldata("01b7037c", USDC, 0, 0, 128))))
+
+    with pytest.raises(PolicyViolation, match="transfer to"):
+        engine.check_transaction(tx(SAFE, safe_exec(USDC, calldata("a9059cbb", ATTACKER, 10 ** 12))))
+    with pytest.raises(PolicyViolation, match="DELEGATECALL"):
+        engine.check_transaction(tx(SAFE, safe_exec(CTF, "0x", operation=1)))
+    with pytest.raises(PolicyViolation, match="contract .* not allowed"):
+        engine.check_transaction(tx(SAFE, safe_exec(ATTACKER, "0x")))
+
+
+def test_safe_cannot_be_reconfigured_through_exec_transaction(engine):
+    """Module, owner and threshold changes wrapped in execTransaction would hand over the Safe."""
+    with pytest.raises(PolicyViolation, match="Safe itself"):
+        engine.check_transaction(tx(SAFE, safe_exec(SAFE, calldata("610b5925", ATTACKER))))  # enableModule
+    with pytest.raises(PolicyViolation, match="Safe itself"):
+        engine.check_transaction(tx(SAFE, safe_exec(SAFE, calldata("0d582f13", ATTACKER, 1))))  # addOwnerWithThreshold
+    with pytest.raises(PolicyViolation, match="function 0x610b5925 not allowed"):
+        engine.check_transaction(tx(SAFE, calldata("610b5925", ATTACKER)))
+
+
+def test_safe_gas_refunds_rejected(engine):
+    """A refund would pay the Safe's USDC to refundReceiver around any allowed call."""
+    redeem = calldata("01b7037c", USDC, 0, 0, 128)
+    for refund in ((0, 0, 10 ** 6, USDC, ATTACKER), (0, 10 ** 6, 0, 0, 0), (10 ** 6, 0, 0, 0, 0),
+                   (0, 0, 0, USDC, 0), (0, 0, 0, 0, ATTACKER)):
+        with pytest.raises(PolicyViolation, match="gas refunds"):
+            engine.check_transaction(tx(SAFE, safe_exec(CTF, redeem, refund=refund)))
+
+
+def test_only_allowed_functions_on_allowed_contracts(engine):
+    engine.check_transaction(tx(SAFE, safe_exec(CTF, calldata("9e7212ad", USDC, 0, 0, 160, 10 ** 6))))
+    with pytest.raises(PolicyViolation, match="function 0x72ce4275 not allowed"):  # splitPosition
+        engine.check_transaction(tx(CTF, calldata("72ce4275", USDC, 0, 0, 160, 10 ** 6)))
+    with pytest.raises(PolicyViolation, match="function 0x40c10f19 not allowed"):  # mint on USDC via the Safe
+        engine.check_transaction(tx(SAFE, safe_exec(USDC, calldata("40c10f19", ATTACKER, 10 ** 12))))
+
+    configured = PolicyEngine(make_policy(allowed_calls={CTF: ["0x72CE4275"]}), EOA, clock=lambda: NOW)
+    configured.check_transaction(tx(CTF, calldata("72ce4275", USDC, 0, 0, 160, 10 ** 6)))
+    with pytest.raises(PolicyViolation, match="function 0x9e7212ad not allowed"):
+        configured.check_transaction(tx(CTF, calldata("9e7212ad", USDC, 0)))
+
+
+def test_clob_auth_must_be_fresh(engine):
+    engine.check_clob_auth(int(NOW) - 10, 0, 137)
+    with pytest.raises(PolicyViolation, match="timestamp"):
+        engine.check_clob_auth(int(NOW) - 3600, 0, 137)
+
+
+# ============================================================================
+# py_clob_client integration
+# ============================================================================
+
+class FakeBaseBuilder:
+    def __init__(self, signer, sig_type, funder):
+        self.signer, self.sig_type, self.funder = signer, sig_type, funder
+
+    def get_order_amounts(self, side, size, price, round_config):
+        return (0 if side == "BUY" else 1), int(size * price * 1_000_000), int(size * 1_000_000)
+
+    def calculate_market_price(self, *args):
+        return 0.5
+
+
+def test_clob_client_orders_signed_through_signer(monkeypatch):
+    monkeypatch.setitem(sys.modules, "py_clob_client.order_builder.builder",
+                        SimpleNamespace(ROUNDING_CONFIG={"0.01": None}, OrderBuilder=FakeBaseBuilder))
+    monkeypatch.setitem(sys.modules, "py_clob_client.config",
+                        SimpleNamespace(get_contract_config=lambda chain, neg_risk: SimpleNamespace(exchange=EXCHANGE)))
+    client = SimpleNamespace(_get_client_mode=lambda: 1)
+    fake = FakeSigner()
+
+    attach_signer(client, fake, 137, 2, SAFE)
+    order_args = SimpleNamespace(token_id="123", price=0.4, size=10, side="BUY", fee_rate_bps=0,
+                                 nonce=0, expiration=0, taker=ZERO_ADDRESS)
+    signed = client.builder.create_order(order_args, SimpleNamespace(tick_size="0.01", neg_risk=False))
+
+    body = signed.dict()
+    assert body["signature"] == "0x" + "11" * 65
+    assert body["maker"] == SAFE and body["signer"] == EOA and body["side"] == "BUY"
+    assert body["makerAmount"] == "4000000"
+    _, typed = fake.signed[0]
+    assert typed["domain"]["verifyingContract"] == EXCHANGE and typed["message"]["side"] == 0
+    assert client.signer.address() == EOA and client.mode == 1
+    assert client.builder.calculate_market_price() == 0.5  # Helpers still come from py_clob_client
+    with pytest.raises(SignerError, match="Raw hash signing"):
+        client.signer.sign("0x" + "00" * 32)
+
+
+def test_api_creds_derived_with_signer_headers():
+    session = Mock()
+    session.post.return_value = SimpleNamespace(status_code=400, json=lambda: {})
+    session.get.return_value = SimpleNamespace(
+        status_code=200, json=lambda: {"apiKey": "k", "secret": "s", "passphrase": "p"})
+    fake = FakeSigner()
+
+    creds = derive_api_creds("https://clob.polymarket.com/", fake, 137, session=session)
+
+    assert (creds.api_key, creds.api_secret, creds.api_passphrase) == ("k", "s", "p")
+    assert session.get.call_args.args[0] == "https://clob.polymarket.com/auth/derive-api-key"
+    headers = session.get.call_args.kwargs["headers"]
+    assert headers["POLY_ADDRESS"] == EOA and headers["POLY_SIGNATURE"] == "0x" + "22" * 65
+    assert headers["POLY_NONCE"] == "0"
+
+
+# ============================================================================
+# Config
+# ============================================================================
+
+def test_config_remote_signer_needs_no_private_key():
+    from config.config import Config
+
+    base = dict(wallet_address=EOA, polygon_rpc_url="https://polygon-rpc.com")
+    config = Config(private_key="", signer_backend="remote", **base)
+    assert config.to_dict()["signer_backend"] == "remote"
+
+    with pytest.raises(ValueError, match="private_key is required"):
+        Config(private_key="", **base)
+    with pytest.raises(ValueError, match="signer_backend must be"):
+        Config(private_key="0x" + "4c" * 32, signer_backend="hsm", **base)
@@ -0,0 +1,354 @@
+"""
+Unit tests for the strategy registry and pluggable strategy interface.
+
+Tests:
+- Registry registration, ordering and error handling
+- TradingStrategy cycle hooks (scan, decide, size, execute, exits)
+- Built-in adapters (NegRisk sizing and executed opportunities, unpriced resolution farming
+  opportunities, self-recording strategies)
+- Configurable entry order in FifteenMinuteCryptoStrategy
+- One FairValuePricer shared through the context; fair-value gate on 15-minute entries
+- Correlation limits on 15-minute entries
+"""
+
+import pytest
+from dataclasses import replace
+from decimal import Decimal
+from datetime import datetime, timedelta, timezone
+from types import SimpleNamespace
+from unittest.mock import Mock, AsyncMock, patch
+
+from src.strategy_registry import (
+    StrategyRegistry,
+    StrategyContext,
+    TradingStrategy,
+    FifteenMinuteCryptoAdapter,
+    MarketMakingAdapter,
+    NegRiskArbitrageAdapter,
+    ResolutionFarmingAdapter,
+    build_default_registry,
+)
+from src.negrisk_arbitrage_engine import NegRiskArbitrageEngine
+from src.correlation_analyzer import CorrelationAnalyzer
+from src.fair_value_pricer import FairValuePricer
+from src.fifteen_min_crypto_strategy import BinancePriceFeed, FifteenMinuteCryptoStrategy, CryptoMarket, Position
+from src.multi_timeframe_analyzer import MultiTimeframeAnalyzer
+
+
+class RecordingStrategy(TradingStrategy):
+    """Strategy that records hook calls for assertions."""
+
+    name = "recording"
+
+    def __init__(self, candidates, approve=True, amount=Decimal("2")):
+        self.candidates = candidates
+        self.approve = approve
+        self.amount = amount
+        self.calls = []
+
+    async def check_exits(self):
+        self.calls.append("exits")
+
+    async def scan(self, markets):
+        self.calls.append("scan")
+        return list(self.candidates)
+
+    async def decide(self, candidate):
+        self.calls.append(f"decide:{candidate}")
+        return self.approve
+
+    def size(self, candidate, bankroll):
+        self.calls.append(f"size:{candidate}")
+        return self.amount
+
+    async def execute(self, candidate, size):
+        self.calls.append(f"execute:{candidate}:{size}")
+        return Mock(trade_id=f"trade_{candidate}")
+
+
+@pytest.fixture
+def context():
+    """Create a strategy context with mocked components."""
+    return StrategyContext(
+        config=Mock(),
+        clob_client=Mock(),
+        order_manager=Mock(),
+    )
+
+
+def test_default_registry_has_builtin_strategies():
+    """Built-in strategies are registered in the default registry."""
+    registry = build_default_registry()
+
+    assert registry.names() == [
+        "fifteen_min_crypto", "negrisk_arbitrage", "resolution_farming", "market_making",
+        "cross_platform_arbitrage"
+    ]
+
+
+def test_register_duplicate_name_rejected():
+    """Registering the same name twice raises unless replace=True."""
+    registry = StrategyRegistry()
+    registry.register("a", lambda ctx: RecordingStrategy([]))
+
+    with pytest.raises(ValueError, match="already registered"):
+        registry.register("a", lambda ctx: RecordingStrategy([]))
+
+    registry.register("a", lambda ctx: RecordingStrategy(["x"]), replace=True)
+    assert registry.is_registered("a")
+
+
+def test_build_preserves_config_order(context):
+    """Strategies are built in the order listed in config."""
+    registry = StrategyRegistry()
+    registry.register("first", lambda ctx: RecordingStrategy(["1"]))
+    registry.register("second", lambda ctx: RecordingStrategy(["2"]))
+
+    strategies = registry.build(["second", "first"], context)
+
+    assert [s.candidates for s in strategies] == [["2"], ["1"]]
+
+
+def test_build_unknown_strategy_raises(context):
+    """Unknown strategy names fail fast at startup."""
+    registry = StrategyRegistry()
+
+    with pytest.raises(ValueError, match="Unknown strategy"):
+        registry.build(["missing"], context)
+
+
+def test_build_duplicate_strategy_raises(context):
+    """A strategy cannot be listed twice."""
+    registry = StrategyRegistry()
+    registry.register("a", lambda ctx: RecordingStrategy([]))
+
+    with pytest.raises(ValueError, match="listed twice"):
+        registry.build(["a", "a"], context)
+
+
+def test_build_imports_module_factory(context):
+    """Strategies can be referenced as module:factory without registering."""
+    registry = StrategyRegistry()
+
+    strategies = registry.build(["tests.test_strategy_registry:_module_factory"], context)
+
+    assert strategies[0].candidates == ["module"]
+
+
+def _module_factory(ctx):
+    """Factory referenced by test_build_imports_module_factory."""
+    return RecordingStrategy(["module"])
+
+
+def test_factory_must_return_strategy(context):
+    """Factories returning the wrong type are rejected."""
+    registry = StrategyRegistry()
+    registry.register("bad", lambda ctx: object())
+
+    with pytest.raises(TypeError):
+        registry.create("bad", context)
+
+
+@pytest.mark.asyncio
+async def test_run_cycle_calls_hooks_in_order():
+    """run_cycle runs exits, scan, decide, size and execute."""
+    strategy = RecordingStrategy(["a"])
+
+    results = await strategy.run_cycle([], Decimal("100"))
+
+    assert len(results) == 1
+    assert strategy.calls == ["exits", "scan", "decide:a", "size:a", "execute:a:2"]
+
+
+@pytest.mark.asyncio
+async def test_run_cycle_skips_rejected_and_zero_size():
+    """Rejected or zero-sized candidates are not executed."""
+    rejected = RecordingStrategy(["a"], approve=False)
+    zero = RecordingStrategy(["b"], amount=Decimal("0"))
+
+    assert await rejected.run_cycle([], Decimal("100")) == []
+    assert await zero.run_cycle([], Decimal("100")) == []
+    assert not any(c.startswith("execute") for c in rejected.calls + zero.calls)
+
+
+@pytest.mark.asyncio
+async def test_negrisk_adapter_caps_size_by_bankroll():
+    """NegRisk adapter sizes by liquidity cap and bankroll."""
+    engine = Mock()
+    engine.was_executed = Mock(return_value=False)
+    engine.scan_opportunities = AsyncMock(return_value=[
+        Mock(opportunity_id="opp1", max_position_size=Decimal("5"))
+    ])
+    engine.execute = AsyncMock(return_value=Mock())
+    adapter = NegRiskArbitrageAdapter(engine)
+
+    await adapter.run_cycle([], Decimal("3"))
+
+    engine.execute.assert_awaited_once()
+    assert engine.execute.await_args.kwargs["position_size"] == Decimal("3")
+
+
+@pytest.mark.asyncio
+async def test_negrisk_adapter_skips_executed_opportunities():
+    """Opportunities the engine already executed are not traded twice."""
+    engine = NegRiskArbitrageEngine(clob_client=Mock(), order_manager=Mock(), ai_safety_guard=Mock())
+    engine._executed_opportunities.append("opp1")
+    adapter = NegRiskArbitrageAdapter(engine)
+
+    assert engine.was_executed("opp1") and not engine.was_executed("opp2")
+    assert await adapter.decide(Mock(opportunity_id="opp1")) is False
+    assert await adapter.decide(Mock(opportunity_id="opp2")) is True
+
+
+@pytest.mark.asyncio
+async def test_resolution_farming_adapter_skips_unpriced_opportunities():
+    """An opportunity without a positive price is not traded (and does not divide by zero)."""
+    order_manager = Mock()
+    adapter = ResolutionFarmingAdapter(Mock(), order_manager)
+    adapter._markets = {"m1": Mock(yes_token_id="111", no_token_id="222")}
+
+    for price in (Decimal("0"), Decimal("-0.10")):
+        candidate = Mock(market_id="m1", side="YES", total_cost=price, opportunity_id="opp1")
+        assert await adapter.execute(candidate, Decimal("10")) is None
+
+    order_manager.create_fok_order.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_self_recording_strategies_return_no_trade_results():
+    """The 15-minute strategy and the market maker record their own trades; the orchestrator must not book them again."""
+    for adapter in (FifteenMinuteCryptoAdapter(Mock(run_cycle=AsyncMock())), MarketMakingAdapter(Mock(run_cycle=AsyncMock()))):
+        assert await adapter.run_cycle([], Decimal("100")) == []
+        adapter.strategy.run_cycle.assert_awaited_once()
+
+
+def _create_strategy(entry_order=None, **kwargs):
+    """Create a 15-minute strategy with heavy dependencies patched out."""
+    with patch('src.portfolio_risk_manager.PortfolioRiskManager'), \
+         patch('src.dynamic_parameter_system.DynamicParameterSystem'), \
+         patch('src.fast_execution_engine.FastExecutionEngine'), \
+         patch('src.multi_timeframe_analyzer.MultiTimeframeAnalyzer'), \
+         patch('src.order_book_analyzer.OrderBookAnalyzer'), \
+         patch('src.historical_success_tracker.HistoricalSuccessTracker'), \
+         patch('src.continuous_rl_engine.ContinuousRLEngine'), \
+         patch('src.ensemble_decision_engine.EnsembleDecisionEngine'), \
+         patch('src.context_optimizer.ContextOptimizer'):
+        strategy = FifteenMinuteCryptoStrategy(
+            clob_client=Mock(),
+            trade_size=5.0,
+            dry_run=True,
+            enable_adaptive_learning=False,
+            entry_order=entry_order,
+            **kwargs
+        )
+
+    strategy.positions = {}
+    strategy.check_exit_conditions = AsyncMock()
+    strategy.check_flash_crash = AsyncMock(return_value=False)
+    strategy.check_latency_arbitrage = AsyncMock(return_value=False)
+    strategy.check_directional_trade = AsyncMock(return_value=False)
+    strategy.check_sum_to_one_arbitrage = AsyncMock(return_value=False)
+    return strategy
+
+
+def _market():
+    return CryptoMarket(
+        market_id="m1",
+        question="Will BTC go up?",
+        asset="BTC",
+        up_token_id="up",
+        down_token_id="down",
+        up_price=Decimal("0.50"),
+        down_price=Decimal("0.50"),
+        end_time=datetime.now(timezone.utc) + timedelta(minutes=15),
+    )
+
+
+@pytest.mark.asyncio
+async def test_entry_order_default_runs_all_checks():
+    """Default entry order runs every check when none trades."""
+    strategy = _create_strategy()
+
+    await strategy._process_single_market(_market())
+
+    assert strategy.entry_order == FifteenMinuteCryptoStrategy.DEFAULT_ENTRY_ORDER
+    strategy.check_flash_crash.assert_awaited_once()
+    strategy.check_sum_to_one_arbitrage.assert_awaited_once()
+
+
+@pytest.mark.asyncio
+async def test_entry_order_custom_order_and_disable():
+    """Configured order is respected and omitted checks never run."""
+    strategy = _create_strategy(entry_order=["sum_to_one", "latency"])
+    strategy.check_sum_to_one_arbitrage = AsyncMock(return_value=True)
+
+    await strategy._process_single_market(_market())
+
+    strategy.check_sum_to_one_arbitrage.assert_awaited_once()
+    strategy.check_latency_arbitrage.assert_not_awaited()
+    strategy.check_flash_crash.assert_not_awaited()
+    strategy.check_directional_trade.assert_not_awaited()
+
+
+def _shared_pricer():
+    feed = BinancePriceFeed()
+    feed._update_price("BTC", Decimal("100000"))
+    return FairValuePricer(price_feed=feed, volatility_analyzer=MultiTimeframeAnalyzer())
+
+
+def test_strategies_share_the_context_pricer(context):
+    """Market making, resolution farming and the 15-minute strategy price with the one pricer in the context."""
+    pricer = _shared_pricer()
+    context.config = SimpleNamespace(dry_run=True)
+    context.price_feed = pricer.price_feed
+    context.fair_value_pricer = pricer
+    registry = build_default_registry()
+
+    market_making = registry.create("market_making", context)
+    resolution_farming = registry.create("resolution_farming", context)
+    fifteen_min = _create_strategy(binance_feed=pricer.price_feed, fair_value_pricer=pricer)
+
+    assert market_making.strategy.fair_value_pricer is pricer and not market_making.strategy.owns_feed
+    assert resolution_farming.engine.fair_value_pricer is pricer and not resolution_farming.owns_feed
+    assert fifteen_min.fair_value_pricer is pricer and not fifteen_min.owns_feed
+    assert fifteen_min.multi_tf_analyzer is pricer.volatility_analyzer
+
+
+def test_fifteen_min_entries_need_fair_value_edge():
+    """Once Binance moves past the implied strike, the side the model values below its price is skipped."""
+    pricer = _shared_pricer()
+    strategy = _create_strategy(binance_feed=pricer.price_feed, fair_value_pricer=pricer)
+    market = _market()
+
+    assert strategy._has_fair_value_edge(market, "UP", market.up_price, "latency")
+    pricer.price_feed._update_price("BTC", Decimal("100300"))
+
+    assert strategy._has_fair_value_edge(market, "UP", market.up_price, "latency")
+    assert not strategy._has_fair_value_edge(market, "DOWN", market.down_price, "latency")
+    assert _create_strategy()._has_fair_value_edge(market, "DOWN", market.down_price, "latency")
+
+
+@pytest.mark.asyncio
+async def test_fifteen_min_entries_respect_correlation_limits():
+    """An ETH entry on top of a large BTC position breaches the correlated exposure limit and is not placed."""
+    strategy = _create_strategy(correlation_analyzer=CorrelationAnalyzer())
+    strategy.risk_manager.current_capital = Decimal("100")
+    strategy.positions["btc-up"] = Position(
+        token_id="btc-up", side="UP", entry_price=Decimal("0.50"), size=Decimal("40"),
+        entry_time=datetime.now(timezone.utc), market_id="m0", asset="BTC"
+    )
+    market = replace(_market(), market_id="m1", asset="ETH")
+
+    can_add, reason = strategy._check_correlation(market, Decimal("30"))
+    assert not can_add and "Correlated exposure" in reason
+    assert strategy._check_correlation(market, Decimal("10"))[0]
+
+    assert not await strategy._place_order(market, "UP", Decimal("0.50"), 30.0, strategy="latency")
+    assert "up" not in strategy.positions
+    assert _create_strategy()._check_correlation(market, Decimal("30"))[0]
+
+
+def test_entry_order_unknown_check_rejected():
+    """Unknown entry check names raise at construction."""
+    with pytest.raises(ValueError, match="Unknown entry checks"):
+        _create_strategy(entry_order=["flash_crash", "moonshot"])
@@ -0,0 +1,244 @@
+"""
+Tests for schema-enforced LLM decisions.
+
+Tests:
+- Schema validation: types, enums, ranges, required and unknown fields, non-finite numbers
+- Schemas sent with strict: true require every property
+- Strict JSON extraction (a single fenced block at most)
+- Decision engine: schema sent with every request, repair retries, rejection of
+  out-of-range confidence and size, persisted raw responses
+- AI safety guard: JSON verdicts, invalid answers fall back to heuristics
+"""
+
+import json
+import math
+from datetime import datetime
+from decimal import Decimal
+
+import pytest
+
+from config.config import Config
+from src.ai_safety_guard import SAFETY_VERDICT_SCHEMA, AISafetyGuard
+from src.llm_backends import LLMBackendChain, MockBackend
+from src.llm_decision_engine_v2 import (
+    LLMDecisionEngineV2, MarketContext, PortfolioState, TradeAction, trade_decision_schema,
+)
+from src.models import Market, Opportunity
+from src.structured_output import SchemaValidationError, parse_json_object, schema_errors
+
+
+def answer(**overrides):
+    data = {
+        "action": "buy_yes", "confidence": 80, "position_size_pct": 3, "order_type": "market",
+        "limit_price": None, "reasoning": "Binance bullish", "risk_assessment": "low", "expected_profit_pct": 2,
+    }
+    data.update(overrides)
+    return json.dumps(data)
+
+
+def read_rejected(path):
+    with open(path) as f:
+        return [json.loads(line) for line in f]
+
+
+@pytest.fixture
+def market_context():
+    return MarketContext(
+        market_id="m1", question="BTC up in 15 minutes?", asset="BTC",
+        yes_price=Decimal("0.48"), no_price=Decimal("0.50"), yes_liquidity=Decimal("500"),
+        no_liquidity=Decimal("500"), volume_24h=Decimal("10000"), time_to_resolution=10.0,
+        spread=Decimal("0.02"), binance_momentum="bullish",
+    )
+
+
+@pytest.fixture
+def portfolio_state():
+    return PortfolioState(
+        available_balance=Decimal("100"), total_balance=Decimal("100"), open_positions=[],
+        daily_pnl=Decimal("0"), win_rate_today=0.5, trades_today=0, max_position_size=Decimal("5"),
+    )
+
+
+def make_engine(tmp_path, responses, **kwargs):
+    mock = MockBackend("offline", response=responses)
+    engine = LLMDecisionEngineV2(
+        backends=LLMBackendChain([mock]), rejected_responses_path=str(tmp_path / "rejected.jsonl"), **kwargs
+    )
+    return engine, mock
+
+
+# ============================================================================
+# Validation
+# ============================================================================
+
+def test_schema_errors_are_strict():
+    schema = trade_decision_schema(max_position_pct=5)
+
+    assert schema_errors(json.loads(answer()), schema) == []
+    errors = schema_errors({
+        "action": "yolo", "confidence": True, "position_size_pct": 7.5, "order_type": "market",
+        "reasoning": "", "risk_assessment": "low", "stop_loss": 0.1,
+    }, schema)
+    assert errors == [
+        "$.limit_price: is required",
+        "$.expected_profit_pct: is required",
+        "$.stop_loss: is not allowed",
+        "$.action: must be one of ['buy_yes', 'buy_no', 'buy_both', 'sell_yes', 'sell_no', 'hold', 'skip'], "
+        'got "yolo"',
+        "$.confidence: expected number, got true",
+        "$.position_size_pct: 7.5 is above the maximum 5",
+        "$.reasoning: must be at least 1 characters",
+    ]
+
+
+def test_strict_schemas_list_every_property():
+    """Backends send these with strict: true, which needs every property required and no extras."""
+    def check(schema, path="$"):
+        if schema.get("type") == "object":
+            assert sorted(schema["required"]) == sorted(schema["properties"]), path
+            assert schema["additionalProperties"] is False, path
+        for name, child in schema.get("properties", {}).items():
+            check(child, f"{path}.{name}")
+        if "items" in schema:
+            check(schema["items"], f"{path}[]")
+
+    for schema in (trade_decision_schema(), trade_decision_schema(max_position_pct=5), SAFETY_VERDICT_SCHEMA):
+        check(schema)
+
+
+def test_non_finite_numbers_are_rejected():
+    schema = trade_decision_schema(max_position_pct=5)
+    for value, text in ((math.nan, "NaN"), (math.inf, "Infinity"), (-math.inf, "-Infinity")):
+        data = parse_json_object(answer(confidence=value))
+        assert schema_errors(data, schema) == [f"$.confidence: expected number, got {text}"]
+
+
+def test_parse_json_object_accepts_one_fenced_block_only():
+    assert parse_json_object('```json\n{"safe": true, "reason": "ok"}\n```') == {"safe": True, "reason": "ok"}
+    for text in ("Sure! YES", '{"safe": true} and more', "[1, 2]", "```{}``` ```{}```", ""):
+        with pytest.raises(SchemaValidationError):
+            parse_json_object(text)
+
+
+def test_config_rejects_negative_repair_attempts():
+    with pytest.raises(ValueError, match="llm_max_repair_attempts must be non-negative"):
+        Config(
+            private_key="0x" + "11" * 32, wallet_address="0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            polygon_rpc_url="https://polygon-rpc.com", llm_max_repair_attempts=-1,
+        )
+
+
+# ============================================================================
+# Decision engine
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_invalid_answer_is_repaired(tmp_path, market_context, portfolio_state):
+    engine, mock = make_engine(tmp_path, [answer(confidence=150), answer(confidence=70)])
+
+    decision = await engine.make_decision(market_context, portfolio_state, "latency_arbitrage")
+
+    assert decision.action == TradeAction.BUY_YES and decision.confidence == 70
+    assert mock.schemas[0] == engine.decision_schema
+    assert '"title": "TradeDecision"' in mock.prompts[0][1]
+    repair = mock.prompts[1][1]
+    assert "$.confidence: 150 is above the maximum 100" in repair and '"confidence": 150' in repair
+    [rejected] = read_rejected(tmp_path / "rejected.jsonl")
+    assert rejected["source"] == "decision_engine" and rejected["attempt"] == 0
+    assert rejected["raw_response"] == answer(confidence=150)
+    assert rejected["market_id"] == "m1" and rejected["opportunity_type"] == "latency_arbitrage"
+    stats = engine.get_performance_stats()
+    assert (stats["rejected_responses"], stats["repaired_responses"]) == (1, 1)
+
+
+@pytest.mark.asyncio
+async def test_repairs_are_bounded(tmp_path, market_context, portfolio_state):
+    engine, mock = make_engine(tmp_path, ["I would buy YES here."], max_repair_attempts=2)
+
+    decision = await engine.make_decision(market_context, portfolio_state, "directional_trend")
+
+    assert decision.action == TradeAction.SKIP
+    assert "invalid_response" in decision.reasoning
+    assert len(mock.prompts) == 3
+    assert [entry["attempt"] for entry in read_rejected(tmp_path / "rejected.jsonl")] == [0, 1, 2]
+
+
+@pytest.mark.parametrize("response, error", [
+    (answer(position_size_pct=8), "$.position_size_pct: 8 is above the maximum 5.0"),
+    (answer(position_size_pct=None), "$.position_size_pct: must be above 0 for action buy_yes"),
+    (answer(order_type="limit"), "$.limit_price: is required for limit orders"),
+    (answer(confidence="high"), '$.confidence: expected number, got "high"'),
+])
+def test_out_of_range_fields_are_rejected_not_clamped(tmp_path, market_context, portfolio_state, response, error):
+    engine, _ = make_engine(tmp_path, [], max_position_pct=5.0)
+
+    decision = engine._parse_llm_response(response, market_context, portfolio_state)
+
+    assert decision.action == TradeAction.SKIP and decision.position_size == 0
+    assert error in read_rejected(tmp_path / "rejected.jsonl")[0]["errors"]
+
+
+def test_valid_answer_maps_to_decision(tmp_path, market_context, portfolio_state):
+    engine, _ = make_engine(tmp_path, [])
+
+    decision = engine._parse_llm_response(
+        answer(action="skip", position_size_pct=None, expected_profit_pct=None, order_type="limit", limit_price=0.45),
+        market_context, portfolio_state
+    )
+
+    assert decision.action == TradeAction.SKIP
+    assert decision.position_size == 0 and decision.expected_profit == 0
+    assert decision.limit_price == Decimal("0.45")
+
+
+# ============================================================================
+# AI safety guard
+# ============================================================================
+
+@pytest.fixture
+def market():
+    return Market(
+        market_id="m1", question="Will BTC be above $90,000 at 3:15 PM?", asset="BTC",
+        outcomes=["Yes", "No"], yes_price=Decimal("0.48"), no_price=Decimal("0.49"),
+        yes_token_id="yes", no_token_id="no", volume=Decimal("1000"), liquidity=Decimal("1000"),
+        end_time=datetime(2030, 1, 1), resolution_source="binance",
+    )
+
+
+@pytest.fixture
+def opportunity():
+    return Opportunity(
+        opportunity_id="o1", market_id="m1", strategy="internal", timestamp=datetime.now(),
+        yes_price=Decimal("0.48"), no_price=Decimal("0.49"), yes_fee=Decimal("0"), no_fee=Decimal("0"),
+        total_cost=Decimal("0.97"), expected_profit=Decimal("0.03"), profit_percentage=Decimal("0.03"),
+        position_size=Decimal("1"), gas_estimate=100000,
+    )
+
+
+@pytest.mark.asyncio
+async def test_safety_guard_requires_json_verdict(tmp_path, market, opportunity):
+    mock = MockBackend("offline", response=["YES", '{"safe": false, "reason": "thin book"}'])
+    guard = AISafetyGuard(llm_backends=LLMBackendChain([mock]), rejected_responses_path=str(tmp_path / "r.jsonl"))
+
+    decision = await guard.validate_trade(opportunity, market, Decimal("100"), 50, 0)
+
+    assert decision.approved is False and decision.fallback_used is False
+    assert mock.schemas[0] == SAFETY_VERDICT_SCHEMA
+    [rejected] = read_rejected(tmp_path / "r.jsonl")
+    assert rejected["source"] == "safety_guard" and rejected["raw_response"] == "YES"
+
+
+@pytest.mark.asyncio
+async def test_safety_guard_falls_back_on_invalid_verdicts(tmp_path, market, opportunity):
+    mock = MockBackend("offline", response='{"safe": "yes"}')
+    guard = AISafetyGuard(
+        llm_backends=LLMBackendChain([mock]), max_repair_attempts=0, rejected_responses_path=str(tmp_path / "r.jsonl")
+    )
+
+    decision = await guard.validate_trade(opportunity, market, Decimal("100"), 50, 0)
+
+    assert decision.fallback_used is True and decision.approved is True
+    assert len(mock.prompts) == 1
+    assert read_rejected(tmp_path / "r.jsonl")[0]["errors"] == [
+        "$.reason: is required", '$.safe: expected boolean, got "yes"'
+    ]
@@ -0,0 +1,291 @@
+"""
+Tests for the trade outcome store shared by the learning engines.
+
+Tests:
+- Recorded trades reach every subscriber, even when one of them fails
+- Win rate and returns by strategy, asset and UTC hour
+- Schema version stamped on creation, newer versions rejected
+- Read-only store check for startup validation
+- Engine state round trip
+- Import of the legacy per-engine JSON files (duplicates, the two SuperSmart files, once only)
+- Learning engines subscribed to the store learn from and persist to it
+- The 15-minute strategy records each closed trade once; its default store stays in memory
+- Command line report
+"""
+
+import json
+import sqlite3
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from unittest.mock import Mock
+
+import pytest
+
+from src.adaptive_learning_engine import AdaptiveLearningEngine
+from src.dynamic_parameter_system import DynamicParameterSystem
+from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy
+from src.historical_success_tracker import HistoricalSuccessTracker
+from src.reinforcement_learning_engine import ReinforcementLearningEngine
+from src.super_smart_learning import SuperSmartLearning
+from src.trade_outcome_store import SCHEMA_VERSION, TradeOutcomeStore, main, verify_store
+
+T0 = datetime(2026, 10, 1, 9, 30, tzinfo=timezone.utc)
+
+
+@pytest.fixture
+def store(tmp_path):
+    return TradeOutcomeStore(str(tmp_path / "learning_store.db"))
+
+
+def record(store, strategy="latency", asset="BTC", profit_pct=0.05, closed_at=T0, **overrides):
+    trade = {
+        "strategy": strategy, "asset": asset, "side": "UP", "entry_price": Decimal("0.40"),
+        "exit_price": Decimal("0.42"), "profit_pct": profit_pct, "hold_time_minutes": 4.0,
+        "exit_reason": "take_profit" if profit_pct > 0 else "stop_loss", "closed_at": closed_at,
+    }
+    trade.update(overrides)
+    return store.record(**trade)
+
+
+# ============================================================================
+# Recording and queries
+# ============================================================================
+
+def test_record_stores_trade_and_notifies_subscribers(store):
+    received = []
+    store.subscribe(Mock(side_effect=RuntimeError("engine down")))
+    store.subscribe(received.append)
+
+    stored = record(store, market_id="m1", size=Decimal("5"), features={"spot_move": 0.001})
+
+    assert received == [stored]
+    assert stored.outcome_id is not None and stored.profit_usd == pytest.approx(0.25)
+    [loaded] = store.get_outcomes()
+    assert (loaded.closed_at, loaded.market_id, loaded.size, loaded.source) == (T0, "m1", 5.0, "live")
+    assert loaded.features == {"spot_move": 0.001} and loaded.entry_price == 0.40
+
+
+def test_get_outcomes_keeps_newest_in_order(store):
+    for minutes in (20, 0, 10):
+        record(store, closed_at=T0 + timedelta(minutes=minutes))
+
+    assert [o.closed_at for o in store.get_outcomes(limit=2)] == [T0 + timedelta(minutes=10), T0 + timedelta(minutes=20)]
+    assert len(store.get_outcomes(since=T0 + timedelta(minutes=5))) == 2
+
+
+def test_win_rate_by_strategy_asset_and_hour(store):
+    record(store, "latency", "BTC", 0.05)
+    record(store, "latency", "BTC", -0.02)
+    record(store, "latency", "ETH", 0.03, closed_at=T0 + timedelta(hours=2))
+    record(store, "directional", "BTC", -0.01)
+
+    assert store.get_stats().win_rate == pytest.approx(0.5)
+    latency_btc = store.get_stats(strategy="latency", asset="BTC")
+    assert (latency_btc.trades, latency_btc.wins, latency_btc.avg_profit_pct) == (2, 1, pytest.approx(0.015))
+    assert store.get_stats(strategy="sum_to_one").win_rate is None
+
+    by_strategy = store.get_stats_by("strategy")
+    assert by_strategy["latency"].win_rate == pytest.approx(2 / 3) and by_strategy["directional"].wins == 0
+    assert {hour: s.trades for hour, s in store.get_stats_by(["hour"], strategy="latency").items()} == {9: 2, 11: 1}
+    by_pair = store.get_stats_by(("strategy", "asset"))
+    assert set(by_pair) == {("directional", "BTC"), ("latency", "BTC"), ("latency", "ETH")}
+
+    with pytest.raises(ValueError):
+        store.get_stats_by(("strategy", "market_id"))
+
+
+def test_schema_version_and_newer_store_rejected(tmp_path):
+    path = tmp_path / "learning_store.db"
+    assert TradeOutcomeStore(str(path)).schema_version == SCHEMA_VERSION
+
+    conn = sqlite3.connect(path)
+    conn.execute(f"PRAGMA user_version = {SCHEMA_VERSION + 1}")
+    conn.close()
+    with pytest.raises(ValueError):
+        TradeOutcomeStore(str(path))
+
+
+def test_verify_store_reads_without_changing(tmp_path, store):
+    record(store)
+    assert verify_store(store.db_path) == {"schema_version": SCHEMA_VERSION, "trades": 1}
+
+    not_a_store = tmp_path / "other.db"
+    sqlite3.connect(not_a_store).close()
+    with pytest.raises(ValueError, match="schema version 0"):
+        verify_store(str(not_a_store))
+    assert sqlite3.connect(not_a_store).execute("PRAGMA user_version").fetchone()[0] == 0  # Not migrated
+
+    garbage = tmp_path / "garbage.db"
+    garbage.write_bytes(b"not sqlite" * 100)
+    with pytest.raises(sqlite3.DatabaseError):
+        verify_store(str(garbage))
+
+
+def test_engine_state_round_trip(store):
+    assert store.load_state("super_smart") is None
+    store.save_state("super_smart", {"total_trades": 3})
+    store.save_state("super_smart", {"total_trades": 4})
+    assert store.load_state("super_smart") == {"total_trades": 4}
+
+
+# ============================================================================
+# Legacy files
+# ============================================================================
+
+def write_legacy_files(data_dir):
+    data_dir.mkdir()
+    local = T0.astimezone().replace(tzinfo=None)  # The tracker wrote naive local times
+    historical = [
+        {"timestamp": (local + timedelta(minutes=i)).isoformat(), "strategy": "latency", "asset": "BTC",
+         "market_id": f"m{i}", "entry_price": 0.4, "exit_price": 0.44, "size": 1.0, "profit_pct": 0.1,
+         "profit_usd": 0.04, "hold_time_minutes": 3.0, "exit_reason": "take_profit", "conditions": {}}
+        for i in range(3)
+    ]
+    (data_dir / "historical_success.json").write_text(json.dumps({"trades": historical}))
+    adaptive_trade = {"asset": "BTC", "side": "UP", "entry_price": "0.4", "exit_price": "0.44", "profit_pct": "0.1",
+                      "hold_time_minutes": 3.0, "exit_reason": "take_profit", "strategy_used": "latency",
+                      "time_of_day": 9}
+    (data_dir / "adaptive_learning.json").write_text(json.dumps({
+        "total_trades": 4, "winning_trades": 3,
+        "trade_outcomes": [
+            {**adaptive_trade, "timestamp": (T0 + timedelta(seconds=1)).isoformat()},  # Same trade as m0
+            {**adaptive_trade, "asset": "ETH", "profit_pct": "-0.05", "timestamp": T0.isoformat()},
+        ],
+    }))
+    (data_dir / "super_smart_learning.json").write_text(json.dumps({"total_trades": 2, "total_wins": 1}))
+    (data_dir / "supersmart_learning.json").write_text(json.dumps({"total_trades": 7, "total_wins": 5}))
+    (data_dir / "rl_q_table.json").write_text(json.dumps({"q_table": {"BTC_high": {"latency": 0.3}}, "total_episodes": 9}))
+
+
+def test_migrate_legacy_files(tmp_path, store):
+    write_legacy_files(tmp_path / "data")
+
+    report = store.migrate_legacy_files(str(tmp_path / "data"))
+
+    assert report["trades"] == 4
+    assert set(report["engines"]) == {"adaptive_learning", "super_smart", "reinforcement_learning"}
+    assert len(report["files"]) == 5
+    outcomes = store.get_outcomes()
+    assert outcomes[0].closed_at == T0 and {o.source for o in outcomes} == {"migrated"}
+    assert store.get_stats_by("asset")["ETH"].wins == 0
+    assert "trade_outcomes" not in store.load_state("adaptive_learning")
+    assert store.load_state("super_smart")["total_trades"] == 7
+    assert store.load_state("reinforcement_learning")["total_episodes"] == 9
+
+    assert store.migrate_legacy_files(str(tmp_path / "data")) == {"files": [], "trades": 0, "engines": []}
+    assert store.get_stats().trades == 4
+
+
+def test_migration_keeps_existing_engine_state(tmp_path):
+    write_legacy_files(tmp_path / "data")
+    store = TradeOutcomeStore(str(tmp_path / "learning_store.db"))
+    store.save_state("super_smart", {"total_trades": 50})
+
+    store.migrate_legacy_files(str(tmp_path / "data"))
+
+    assert store.load_state("super_smart") == {"total_trades": 50}
+
+
+# ============================================================================
+# Learning engines
+# ============================================================================
+
+def test_engines_learn_from_store_and_keep_state_in_it(tmp_path, store):
+    engines = [
+        SuperSmartLearning(data_file=str(tmp_path / "super_smart.json"), store=store),
+        AdaptiveLearningEngine(data_file=str(tmp_path / "adaptive.json"), store=store),
+        HistoricalSuccessTracker(data_file=str(tmp_path / "historical.json"), store=store),
+        ReinforcementLearningEngine(data_file=str(tmp_path / "rl.json"), store=store),
+    ]
+    for engine in engines:
+        store.subscribe(engine.on_trade_outcome)
+
+    record(store, "latency", "BTC", 0.05)
+    record(store, "latency", "ETH", -0.02)
+
+    super_smart, adaptive, tracker, rl = engines
+    assert super_smart.total_trades == 2 and adaptive.total_trades == 2 and len(tracker.trades) == 2
+    assert tracker.strategy_stats["latency"]["win_rate"] == pytest.approx(0.5)
+    assert rl.total_episodes == 2
+    assert list(tmp_path.glob("*.json")) == []
+
+    # Restarted engines pick up where they left off
+    assert SuperSmartLearning(data_file=str(tmp_path / "super_smart.json"), store=store).total_trades == 2
+    restarted = AdaptiveLearningEngine(data_file=str(tmp_path / "adaptive.json"), store=store)
+    assert restarted.total_trades == 2 and len(restarted.trade_outcomes) == 2
+    assert len(HistoricalSuccessTracker(data_file=str(tmp_path / "historical.json"), store=store).trades) == 2
+    assert ReinforcementLearningEngine(data_file=str(tmp_path / "rl.json"), store=store).total_episodes == 2
+
+
+def test_kelly_system_records_sized_trades_only(store):
+    kelly = DynamicParameterSystem()
+    store.subscribe(kelly.on_trade_outcome)
+
+    record(store, profit_pct=0.04, size=Decimal("10"))
+    record(store, profit_pct=0.04)
+
+    [trade] = kelly.trade_history
+    assert trade.position_size == Decimal("10") and trade.profit == Decimal("0.40") and trade.was_successful
+
+
+def test_strategy_records_each_closed_trade_once(tmp_path, store):
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, outcome_store=store,
+        positions_file=str(tmp_path / "positions.json")
+    )
+
+    strategy._record_trade_outcome(
+        asset="BTC", side="UP", strategy="latency", entry_price=Decimal("0.40"), exit_price=Decimal("0.44"),
+        profit_pct=Decimal("0.10"), hold_time_minutes=3.0, exit_reason="take_profit",
+        position_size=Decimal("5"), market_id="m1"
+    )
+
+    [stored] = store.get_outcomes()
+    assert (stored.strategy, stored.market_id, stored.size) == ("latency", "m1", 5.0)
+    assert len(strategy.success_tracker.trades) == 1
+    assert strategy.super_smart.total_trades == 1
+    assert len(strategy.kelly_system.trade_history) == 1
+
+
+def test_strategy_default_store_stays_in_memory(tmp_path, monkeypatch):
+    """Without an injected store nothing is written to data/ and no legacy files are imported."""
+    monkeypatch.chdir(tmp_path)
+    write_legacy_files(tmp_path / "data")
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=Mock(), trade_size=5.0, dry_run=True, positions_file=str(tmp_path / "positions.json")
+    )
+
+    strategy._record_trade_outcome(
+        asset="BTC", side="UP", strategy="latency", entry_price=Decimal("0.40"), exit_price=Decimal("0.44"),
+        profit_pct=Decimal("0.10"), hold_time_minutes=3.0, exit_reason="take_profit",
+        position_size=Decimal("5"), market_id="m1"
+    )
+
+    assert [o.market_id for o in strategy.outcome_store.get_outcomes()] == ["m1"]
+    assert not (tmp_path / "data" / "learning_store.db").exists()
+
+
+# ============================================================================
+# Command line
+# ============================================================================
+
+def test_cli_migrates_and_reports_win_rates(tmp_path, capsys):
+    write_legacy_files(tmp_path / "data")
+    db = tmp_path / "learning_store.db"
+
+    assert main(["--db", str(db), "--migrate", str(tmp_path / "data"), "--by", "strategy", "asset",
+                 "--output", str(tmp_path / "report.json")]) == 0
+
+    report = json.loads((tmp_path / "report.json").read_text())
+    assert report["migration"]["trades"] == 4
+    assert report["total"]["trades"] == 4
+    assert report["groups"][0] == {
+        "strategy": "latency", "asset": "BTC", "trades": 3, "wins": 3, "win_rate": 1.0,
+        "avg_profit_pct": pytest.approx(0.1), "total_profit_pct": pytest.approx(0.3),
+    }
+
+    capsys.readouterr()
+    assert main(["--db", str(db), "--by", "hour", "--asset", "ETH"]) == 0
+    assert json.loads(capsys.readouterr().out)["groups"] == [
+        {"hour": 9, "trades": 1, "wins": 0, "win_rate": 0.0, "avg_profit_pct": -0.05, "total_profit_pct": -0.05}
+    ]
@@ -0,0 +1,203 @@
+"""
+Tests for the transaction journal and restart recovery.
+
+Tests:
+- Entry lifecycle: signed -> pending -> replaced / confirmed / failed
+- Open nonces with their replacement chains
+- TransactionManager journals every transaction before broadcast
+- Recovery: mined while offline, nonce used elsewhere, still pending
+  (nonce reserved, replacements kept), signed but never broadcast
+"""
+
+import pytest
+from unittest.mock import Mock
+
+from src.transaction_manager import TransactionManager
+from src.tx_journal import (
+    CANCELLED,
+    CONFIRMED,
+    DROPPED,
+    FAILED,
+    PENDING,
+    REPLACED,
+    SIGNED,
+    TransactionJournal,
+)
+
+WALLET = "0x1234567890123456789012345678901234567890"
+TX = {"to": "0x" + "ab" * 20, "value": 0, "gas": 100000, "gasPrice": 50 * 10 ** 9, "chainId": 137}
+
+
+class FakeEth:
+    """Chain with a settable nonce, receipts and a log of broadcast raw txs."""
+
+    def __init__(self):
+        self.nonce = 4  # Mined transactions ('latest')
+        self.gas_price = 50 * 10 ** 9
+        self.chain_id = 137
+        self.receipts = {}
+        self.broadcast = []
+        self.reject = None
+
+    def fee_history(self, *args):
+        raise ValueError("not supported")
+
+    def get_transaction_count(self, address, block="latest"):
+        return self.nonce
+
+    def send_raw_transaction(self, raw_tx):
+        if self.reject:
+            raise ValueError(self.reject)
+        self.broadcast.append(bytes(raw_tx))
+        return Mock(hex=Mock(return_value="0x" + bytes(raw_tx).hex().ljust(64, "0")))
+
+    def get_transaction_receipt(self, tx_hash):
+        return self.receipts.get(tx_hash)
+
+
+class FakeAccount:
+    address = WALLET
+
+    def __init__(self):
+        self.count = 0
+
+    def sign_transaction(self, tx):
+        self.count += 1
+        return Mock(raw_transaction=bytes([tx["nonce"], self.count]))
+
+
+@pytest.fixture
+def journal(tmp_path):
+    return TransactionJournal(str(tmp_path / "tx_journal.db"))
+
+
+@pytest.fixture
+def web3():
+    web3 = Mock()
+    web3.eth = FakeEth()
+    return web3
+
+
+def make_manager(web3, journal):
+    return TransactionManager(web3, FakeAccount(), journal=journal)
+
+
+def statuses(journal):
+    return [(entry.nonce, entry.status) for entry in reversed(journal.get_entries())]
+
+
+# ============================================================================
+# Journal
+# ============================================================================
+
+def test_entry_lifecycle(journal):
+    first = journal.record_signed(WALLET, 4, {"nonce": 4, "data": b"\x01"}, b"\xaa", label="merge")
+    assert statuses(journal) == [(4, SIGNED)]
+
+    journal.mark_broadcast(first, "0xaaa")
+    second = journal.record_signed(WALLET, 4, {"nonce": 4}, "bb", label="merge", replaces=first)
+    journal.mark_broadcast(second, "0xbbb")
+    assert statuses(journal) == [(4, REPLACED), (4, PENDING)]
+
+    chain = journal.open_nonces(WALLET.upper().replace("0X", "0x"))[4]
+    assert [entry.tx_hash for entry in chain] == ["0xaaa", "0xbbb"]
+    assert chain[0].tx_params == {"nonce": 4, "data": "0x01"}
+    assert chain[1].raw_transaction == "0xbb"
+
+    journal.resolve_nonce(WALLET, 4, "0xaaa", CONFIRMED, block_number=10)
+    assert statuses(journal) == [(4, CONFIRMED), (4, REPLACED)]
+    assert journal.open_nonces(WALLET) == {}
+
+    failed = journal.record_signed(WALLET, 5, {"nonce": 5}, b"\xcc")
+    journal.mark_failed(failed, "nonce too low")
+    assert journal.get_entries(status=FAILED)[0].error == "nonce too low"
+    assert journal.open_nonces(WALLET) == {}
+
+
+# ============================================================================
+# TransactionManager
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_manager_journals_before_broadcast(web3, journal):
+    manager = make_manager(web3, journal)
+    tx_hash = await manager.send_transaction(dict(TX), label="redeem")
+    entry = journal.get_entries()[0]
+    assert (entry.status, entry.tx_hash, entry.label, entry.nonce) == (PENDING, tx_hash, "redeem", 4)
+
+    replacement = await manager.resubmit_stuck_transaction(tx_hash)
+    assert statuses(journal) == [(4, REPLACED), (4, PENDING)]
+
+    web3.eth.receipts[replacement] = {"status": 1, "blockNumber": 99}
+    await manager.wait_for_confirmation(tx_hash, timeout=5)
+    assert statuses(journal) == [(4, REPLACED), (4, CONFIRMED)]
+    assert journal.get_entries()[0].block_number == 99
+
+    web3.eth.reject = "insufficient funds"
+    with pytest.raises(Exception):
+        await manager.send_transaction(dict(TX))
+    assert journal.get_entries()[0].status == FAILED
+
+
+@pytest.mark.asyncio
+async def test_cancellation_journaled(web3, journal):
+    manager = make_manager(web3, journal)
+    tx_hash = await manager.send_transaction(dict(TX))
+    cancel_hash = await manager.cancel_transaction(tx_hash)
+    web3.eth.receipts[cancel_hash] = {"status": 1, "blockNumber": 5}
+    await manager.cleanup_confirmed_transactions()
+    assert statuses(journal) == [(4, REPLACED), (4, CANCELLED)]
+
+
+# ============================================================================
+# Recovery
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_recovery_reconciles_with_chain(web3, journal):
+    before_crash = make_manager(web3, journal)
+    mined = await before_crash.send_transaction(dict(TX), label="merge")  # nonce 4
+    await before_crash.send_transaction(dict(TX), label="approval")  # nonce 5
+    stuck = await before_crash.send_transaction(dict(TX), label="redeem")  # nonce 6
+    stuck_replacement = await before_crash.resubmit_stuck_transaction(stuck)
+    submitted_at = before_crash._pending_transactions[stuck_replacement].submitted_at
+
+    # Offline: nonce 4 mined; nonce 5 taken by a transaction from elsewhere
+    web3.eth.receipts[mined] = {"status": 1, "blockNumber": 7}
+    web3.eth.nonce = 6
+
+    manager = make_manager(web3, journal)
+    resumed = await manager.recover_from_journal()
+
+    assert resumed == [stuck_replacement]
+    assert statuses(journal) == [(4, CONFIRMED), (5, DROPPED), (6, REPLACED), (6, PENDING)]
+
+    pending_tx = manager._pending_transactions[stuck_replacement]
+    assert pending_tx.label == "redeem"
+    assert pending_tx.replaced_hashes == [stuck]
+    assert pending_tx.submitted_at == pytest.approx(submitted_at, abs=1)
+    assert await manager.get_next_nonce() == 7  # Recovered nonce stays reserved
+
+    # Either transaction at the nonce confirms it
+    web3.eth.receipts[stuck] = {"status": 1, "blockNumber": 8}
+    await manager.wait_for_confirmation(stuck_replacement, timeout=5)
+    assert statuses(journal)[2:] == [(6, CONFIRMED), (6, REPLACED)]
+
+
+@pytest.mark.asyncio
+async def test_recovery_rebroadcasts_signed_entry(web3, journal):
+    # Crash between journaling and broadcast
+    journal.record_signed(WALLET, 4, dict(TX, nonce=4), b"\x04\x09", label="merge")
+
+    manager = make_manager(web3, journal)
+    resumed = await manager.recover_from_journal()
+
+    assert web3.eth.broadcast == [b"\x04\x09"]
+    assert len(resumed) == 1
+    assert journal.get_entries()[0].status == PENDING
+    assert manager.get_pending_count() == 1
+
+
+@pytest.mark.asyncio
+async def test_recovery_without_journal_is_noop(web3):
+    assert await TransactionManager(web3, FakeAccount()).recover_from_journal() == []
@@ -0,0 +1,316 @@
+"""
+Tests for wallet sub-accounts.
+
+Tests:
+- Config validation of `wallets` entries
+- Sub-account settings: defaults and risk limits
+- Trade history wallet tag, migration of older databases, per-wallet queries
+- Per-wallet statistics and the WALLET BREAKDOWN report section
+- Registry: P&L booked on the trading wallet, per-wallet halts, aggregate view
+- Signer checked against the configured wallet address
+- Sub-accounts opened with their awaited balance, their own ledger and per-wallet strategy files
+- Ledger reconciliation of every wallet
+"""
+
+import sqlite3
+import uuid
+from datetime import datetime
+from decimal import Decimal
+from unittest.mock import AsyncMock, Mock, patch
+
+import pytest
+
+from config.config import Config
+from src import signer as signer_module
+from src.models import Opportunity, TradeResult
+from src.report_generator import ReportGenerator
+from src.status_dashboard import StatusDashboard
+from src.trade_history import TradeHistoryDB
+from src.trade_statistics import TradeStatisticsTracker
+from src.wallet_accounts import (
+    PRIMARY_WALLET,
+    WalletAccount,
+    WalletAccountConfig,
+    WalletRegistry,
+    create_wallet_signer,
+    load_wallet_configs,
+    wallet_file,
+)
+
+PRIMARY_ADDRESS = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+BOOK_B_KEY = "0x" + "22" * 32
+BOOK_B_ADDRESS = "0x" + "bb" * 20
+SAFE = "0x" + "ab" * 20
+
+
+class FakeLocalSigner:
+    """LocalSigner stand-in: address looked up from the key."""
+    ADDRESSES = {BOOK_B_KEY: BOOK_B_ADDRESS}
+
+    def __init__(self, private_key):
+        self.address = self.ADDRESSES.get(private_key, "0x" + "99" * 20)
+
+
+def make_config(wallets, **overrides):
+    return Config(
+        private_key="0x" + "11" * 32,
+        wallet_address=PRIMARY_ADDRESS,
+        polygon_rpc_url="https://polygon-rpc.com",
+        wallets=wallets,
+        **overrides
+    )
+
+
+def book_b(**overrides):
+    entry = {"name": "book_b", "wallet_address": BOOK_B_ADDRESS, "private_key_env": "BOOK_B_KEY"}
+    entry.update(overrides)
+    return entry
+
+
+def make_trade(wallet=PRIMARY_WALLET, net_profit="1.0", status="success", strategy="negrisk_arbitrage"):
+    trade_id = uuid.uuid4().hex[:12]
+    opportunity = Opportunity(
+        opportunity_id=f"opp_{trade_id}",
+        market_id=f"market_{trade_id}",
+        strategy=strategy,
+        timestamp=datetime.now(),
+        yes_price=Decimal("0.48"),
+        no_price=Decimal("0.50"),
+        yes_fee=Decimal("0"),
+        no_fee=Decimal("0"),
+        total_cost=Decimal("0.98"),
+        expected_profit=Decimal("0.02"),
+        profit_percentage=Decimal("0.02"),
+        position_size=Decimal("5"),
+        gas_estimate=100000,
+    )
+    return TradeResult(
+        trade_id=trade_id,
+        opportunity=opportunity,
+        timestamp=datetime.now(),
+        status=status,
+        yes_order_id="yes",
+        no_order_id="no",
+        yes_filled=status == "success",
+        no_filled=status == "success",
+        yes_fill_price=None,
+        no_fill_price=None,
+        actual_cost=Decimal("4.90"),
+        actual_profit=Decimal(net_profit),
+        gas_cost=Decimal("0"),
+        net_profit=Decimal(net_profit),
+        wallet=wallet,
+    )
+
+
+def make_account(name, capital="100", balances=("1", "99"), **limits):
+    settings = WalletAccountConfig(name=name, wallet_address=PRIMARY_ADDRESS, **limits)
+    fund_manager = Mock()
+    fund_manager.check_balance = AsyncMock(return_value=tuple(Decimal(b) for b in balances))
+    return WalletAccount(
+        name=name,
+        account=Mock(address=PRIMARY_ADDRESS),
+        signature_type=2,
+        funder_address=SAFE,
+        clob_client=Mock(),
+        transaction_manager=Mock(),
+        order_manager=Mock(),
+        fund_manager=fund_manager,
+        risk_manager=settings.create_risk_manager(Decimal(capital)),
+    )
+
+
+# ============================================================================
+# Configuration
+# ============================================================================
+
+def test_config_accepts_sub_accounts():
+    config = make_config([book_b(funder_address=SAFE, strategies=["market_making"], capital=50)])
+    wallet = WalletAccountConfig.from_dict(config.wallets[0], config.enabled_strategies)
+
+    assert wallet.signature_type == 2  # Funds in a proxy wallet
+    assert wallet.strategies == ["market_making"]
+    assert wallet.capital == Decimal("50")
+    assert wallet.max_daily_drawdown == Decimal("0.15")
+    assert config.to_dict()["wallets"][0]["capital"] == 50
+
+    default = WalletAccountConfig.from_dict(book_b(max_daily_drawdown="0.05"), ["fifteen_min_crypto"])
+    assert (default.signature_type, default.funder_address) == (0, None)
+    assert default.strategies == ["fifteen_min_crypto"]
+    assert default.create_risk_manager(Decimal("20")).max_daily_drawdown == Decimal("0.05")
+
+
+@pytest.mark.parametrize("entry, error", [
+    (book_b(name="primary"), "used twice"),
+    (book_b(wallet_address=PRIMARY_ADDRESS), "already configured"),
+    (book_b(private_key_env=None), "private_key_env"),
+    (book_b(signature_type=2), "requires funder_address"),
+    (book_b(max_daily_drawdown=1.5), "at most 1"),
+    (book_b(capital="lots"), "capital must be positive"),
+    (book_b(leverage=3), "unknown options"),
+])
+def test_config_rejects_invalid_wallets(entry, error):
+    with pytest.raises(ValueError, match=error):
+        make_config([entry])
+
+
+def test_remote_signer_needs_socket_per_wallet():
+    with pytest.raises(ValueError, match="signer_socket_path"):
+        make_config([book_b()], signer_backend="remote")
+    make_config([book_b(private_key_env=None, signer_socket_path="/run/b.sock")], signer_backend="remote")
+
+
+def test_signer_must_match_wallet_address(monkeypatch):
+    monkeypatch.setattr(signer_module, "LocalSigner", FakeLocalSigner)
+    wallet = WalletAccountConfig.from_dict(book_b(), [])
+    monkeypatch.setenv("BOOK_B_KEY", BOOK_B_KEY)
+    assert create_wallet_signer(wallet, "local").address == BOOK_B_ADDRESS
+
+    monkeypatch.setenv("BOOK_B_KEY", "0x" + "33" * 32)
+    with pytest.raises(ValueError, match="does not match"):
+        create_wallet_signer(wallet, "local")
+
+    monkeypatch.delenv("BOOK_B_KEY")
+    with pytest.raises(ValueError, match="not set"):
+        create_wallet_signer(wallet, "local")
+
+
+# ============================================================================
+# Trade history, statistics and reports
+# ============================================================================
+
+def test_trade_history_tags_wallet(tmp_path):
+    db = TradeHistoryDB(str(tmp_path / "trades.db"))
+    db.insert_trade(make_trade())
+    db.insert_trade(make_trade(wallet="book_b"))
+
+    assert [t["wallet"] for t in db.get_trades_by_wallet("book_b")] == ["book_b"]
+    assert len(db.get_trades_by_wallet(PRIMARY_WALLET)) == 1
+
+
+def test_trade_history_migrates_single_wallet_database(tmp_path):
+    path = str(tmp_path / "trades.db")
+    with sqlite3.connect(path) as conn:
+        conn.execute("CREATE TABLE trades (trade_id TEXT PRIMARY KEY, timestamp TEXT, market_id TEXT, "
+                     "strategy TEXT, status TEXT)")
+        conn.execute("INSERT INTO trades VALUES ('old', '2026-01-01T00:00:00', 'm', 'negrisk_arbitrage', 'success')")
+
+    db = TradeHistoryDB(path)
+
+    assert db.get_trade("old")["wallet"] == PRIMARY_WALLET
+
+
+def test_statistics_and_report_split_by_wallet(tmp_path):
+    db = TradeHistoryDB(str(tmp_path / "trades.db"))
+    for trade in (make_trade(net_profit="2"), make_trade(status="failed", net_profit="0"),
+                  make_trade(wallet="book_b", net_profit="-1")):
+        db.insert_trade(trade)
+    tracker = TradeStatisticsTracker(db)
+
+    stats = tracker.get_statistics()
+    assert stats.wallet_stats[PRIMARY_WALLET]["total"] == 2
+    assert stats.wallet_stats[PRIMARY_WALLET]["win_rate"] == Decimal("50")
+    assert stats.wallet_stats["book_b"]["net_profit"] == Decimal("-1")
+
+    report = ReportGenerator(db, tracker, output_dir=str(tmp_path / "reports")).generate_console_report("all")
+    assert "WALLET BREAKDOWN" in report
+    assert "book_b: 1 trades" in report
+    assert "ALL WALLETS: 3 trades" in report
+
+
+# ============================================================================
+# Registry
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_registry_books_and_aggregates_per_wallet():
+    registry = WalletRegistry()
+    registry.add(make_account(PRIMARY_WALLET))
+    registry.add(make_account("book_b", capital="50", balances=("0", "50"), consecutive_loss_limit=2))
+    with pytest.raises(ValueError):
+        registry.add(make_account("book_b"))
+
+    registry.record_trade(make_trade(wallet="book_b", net_profit="-1"))
+    registry.record_trade(make_trade(wallet="book_b", net_profit="-1"))
+    registry.record_trade(make_trade(wallet="book_b", net_profit="-1", status="failed"))  # Not executed
+
+    book = registry.get("book_b")
+    assert book.risk_manager.current_capital == Decimal("48")
+    assert book.trading_allowed()[0] is False  # Its own consecutive loss limit
+    assert registry.primary.trading_allowed() == (True, "")
+
+    await registry.refresh_balances()
+    aggregate = registry.aggregate()
+    assert aggregate["total_balance"] == Decimal("150")
+    assert aggregate["capital"] == Decimal("148")
+    assert aggregate["daily_pnl"] == Decimal("-2")
+    assert aggregate["halted"] == ["book_b"]
+
+    dashboard = StatusDashboard()
+    dashboard.update_wallets(registry.snapshot())
+    assert dashboard.state.total_balance == Decimal("150")
+    assert [w["name"] for w in dashboard.state.wallets] == [PRIMARY_WALLET, "book_b"]
+
+
+# ============================================================================
+# Orchestrator
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_sub_account_opened_with_balance_capital_and_own_ledger(tmp_path):
+    from src.main_orchestrator import MainOrchestrator
+
+    config = make_config([book_b()], ledger_db_path=str(tmp_path / "ledger.db"))
+    orchestrator = Mock(config=config)
+    account = make_account("book_b")
+    account.risk_manager = None
+    with patch("src.main_orchestrator.open_wallet_account", return_value=account), \
+            patch("src.main_orchestrator.RedemptionService") as redemption_service:
+        opened = await MainOrchestrator._open_sub_account(orchestrator, load_wallet_configs(config)[0])
+
+    assert opened.risk_manager.initial_capital == Decimal("100")  # Awaited balance, not a fallback
+    assert opened.ledger.db_path == str(tmp_path / "ledger_book_b.db")
+    assert opened.ledger_reconciler.ledger is opened.ledger
+    assert redemption_service.call_args.kwargs["ledger"] is opened.ledger
+    context = orchestrator.strategy_registry.build.call_args[0][1]
+    assert (context.wallet, context.ledger, context.initial_capital) == ("book_b", opened.ledger, 100.0)
+
+
+@pytest.mark.asyncio
+async def test_ledgers_reconciled_for_every_wallet():
+    from src.main_orchestrator import MainOrchestrator
+    from src.strategy_registry import FifteenMinuteCryptoAdapter
+
+    wallets = []
+    for name, shares in ((PRIMARY_WALLET, {"111": Decimal("5")}), ("book_b", {"222": Decimal("7")})):
+        wallet = make_account(name)
+        wallet.strategies = [FifteenMinuteCryptoAdapter(Mock(tracked_shares=Mock(return_value=shares)))]
+        wallet.ledger_reconciler = Mock(reconcile=AsyncMock(return_value=Mock(repaired=False, has_drift=False)))
+        wallets.append(wallet)
+
+    await MainOrchestrator._reconcile_ledger(Mock(wallets=wallets))
+
+    wallets[0].ledger_reconciler.reconcile.assert_awaited_once_with({"111": Decimal("5")})
+    wallets[1].ledger_reconciler.reconcile.assert_awaited_once_with({"222": Decimal("7")})
+
+
+def test_sub_account_strategy_files_are_per_wallet(tmp_path, monkeypatch):
+    from src.strategy_registry import StrategyContext, build_default_registry
+
+    monkeypatch.chdir(tmp_path)
+    (tmp_path / "data").mkdir()
+    (tmp_path / "data" / "historical_success.json").write_text('{"trades": []}')
+    config = make_config([book_b()], dry_run=True, learning_store_path="data/learning_store.db",
+                         rl_experience_path="data/rl_experience.jsonl")
+    context = StrategyContext(config=config, clob_client=Mock(), order_manager=None, wallet="book_b")
+
+    strategy = build_default_registry().create("fifteen_min_crypto", context).strategy
+
+    assert wallet_file("data/learning_store.db", PRIMARY_WALLET) == "data/learning_store.db"
+    assert strategy.positions_file == "data/active_positions_book_b.json"
+    assert strategy.outcome_store.db_path == "data/learning_store_book_b.db"
+    assert str(strategy.rl_engine.experience_log.path) == "data/rl_experience_book_b.jsonl"
+    legacy_imports = sqlite3.connect(tmp_path / "data" / "learning_store_book_b.db").execute(
+        "SELECT COUNT(*) FROM legacy_imports").fetchone()[0]
+    assert legacy_imports == 0  # The legacy files hold the primary wallet's history
+    assert not (tmp_path / "data" / "learning_store.db").exists()
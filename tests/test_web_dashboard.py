@@ -0,0 +1,241 @@
+"""
+Tests for the web dashboard and control API.
+
+Tests:
+- JSON conversion of dashboard values, bearer token check
+- Config: a non-loopback dashboard needs an API token
+- Views: status, strategy books, positions, trades, risk
+- Controls: pause/resume, strategy toggles, flatten, debug mode
+- FifteenMinuteCryptoStrategy.close_all_positions (flatten)
+"""
+
+import json
+import logging
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, MagicMock, Mock
+
+import pytest
+
+from config.config import Config
+from src.error_recovery import CircuitBreaker
+from src.fifteen_min_crypto_strategy import FifteenMinuteCryptoStrategy, Position
+from src.status_dashboard import StatusDashboard
+from src.trade_history import TradeHistoryDB
+from src.wallet_accounts import PRIMARY_WALLET, WalletAccount, WalletAccountConfig, WalletRegistry
+from src.web_dashboard import DashboardController, is_authorized, to_jsonable
+
+ADDRESS = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+
+
+class FakeStrategy:
+    """Strategy book with fixed positions; flatten() closes all but the stuck ones."""
+
+    def __init__(self, name, positions=(), fail=False):
+        self.name = name
+        self.held = list(positions)
+        self.stuck = []
+        self.fail = fail
+
+    def open_positions(self):
+        return list(self.held)
+
+    async def flatten(self):
+        if self.fail:
+            raise RuntimeError("exchange down")
+        closed, self.held = len(self.held) - len(self.stuck), list(self.stuck)
+        return closed
+
+
+def make_wallet(name, strategies):
+    order_manager = Mock()
+    order_manager.cancel_resting_orders = AsyncMock(return_value=1)
+    return WalletAccount(
+        name=name,
+        account=Mock(address=ADDRESS),
+        signature_type=0,
+        funder_address=ADDRESS,
+        clob_client=Mock(),
+        transaction_manager=Mock(),
+        order_manager=order_manager,
+        fund_manager=Mock(),
+        risk_manager=WalletAccountConfig(name=name, wallet_address=ADDRESS).create_risk_manager(Decimal("100")),
+        strategies=strategies,
+    )
+
+
+@pytest.fixture
+def orchestrator(tmp_path):
+    wallets = WalletRegistry()
+    wallets.add(make_wallet(PRIMARY_WALLET, [FakeStrategy("fifteen_min_crypto", [
+        {"token_id": "t1", "asset": "BTC", "side": "UP", "size": Decimal("5"), "status": "open"}
+    ])]))
+    wallets.add(make_wallet("book_b", [FakeStrategy("market_making", fail=True)]))
+    return SimpleNamespace(
+        config=SimpleNamespace(dry_run=True),
+        dashboard=StatusDashboard(),
+        wallets=wallets,
+        trade_history=TradeHistoryDB(str(tmp_path / "trades.db")),
+        circuit_breaker=CircuitBreaker(failure_threshold=3),
+        gas_price_halted=False,
+        trading_paused=False,
+        pause_reason="",
+        disabled_strategies=set(),
+    )
+
+
+@pytest.fixture
+def controller(orchestrator):
+    return DashboardController(orchestrator)
+
+
+# ============================================================================
+# Helpers
+# ============================================================================
+
+def test_to_jsonable_and_token_check():
+    value = to_jsonable({"amount": Decimal("1.50"), "at": datetime(2026, 1, 1), "tags": ("a",)})
+    assert value == {"amount": "1.50", "at": "2026-01-01T00:00:00", "tags": ["a"]}
+
+    assert is_authorized("Bearer s3cret", "s3cret")
+    assert is_authorized("bearer s3cret", "s3cret")
+    assert not is_authorized("Bearer wrong", "s3cret")
+    assert not is_authorized("s3cret", "s3cret")
+    assert not is_authorized(None, "s3cret")
+    assert not is_authorized("Bearer ", None)  # No token configured: nothing is authorized
+
+
+def test_public_dashboard_requires_token():
+    settings = dict(private_key="0x" + "11" * 32, wallet_address=ADDRESS, polygon_rpc_url="https://polygon-rpc.com",
+                    dashboard_enabled=True, dashboard_host="0.0.0.0")
+    with pytest.raises(ValueError, match="dashboard_api_token is required"):
+        Config(**settings)
+    assert Config(**settings, dashboard_api_token="s3cret").to_dict()["has_dashboard_api_token"] is True
+
+
+# ============================================================================
+# Views
+# ============================================================================
+
+def test_views_are_json(controller):
+    status = controller.status()
+    assert "recent_trades" not in status["dashboard"]
+    assert status["controls"] == {
+        "trading_paused": False, "pause_reason": "", "disabled_strategies": [], "debug_mode": False, "dry_run": True
+    }
+
+    assert [book["name"] for book in controller.strategies()] == ["fifteen_min_crypto", "market_making@book_b"]
+    assert controller.positions() == [{
+        "token_id": "t1", "asset": "BTC", "side": "UP", "size": "5", "status": "open",
+        "wallet": PRIMARY_WALLET, "book": "fifteen_min_crypto",
+    }]
+
+    risk = controller.risk()
+    assert risk["circuit_breaker"] == {"open": False, "consecutive_failures": 0, "threshold": 3}
+    assert [wallet["name"] for wallet in risk["wallets"]] == [PRIMARY_WALLET, "book_b"]
+    assert controller.trades() == []
+
+    for view in (controller.status(), controller.positions(), risk, controller.strategies()):
+        json.dumps(view)
+
+
+# ============================================================================
+# Controls
+# ============================================================================
+
+def test_pause_resume_and_strategy_toggle(controller, orchestrator):
+    controller.pause("maintenance", source="10.0.0.5")
+    assert (orchestrator.trading_paused, orchestrator.pause_reason) == (True, "maintenance")
+    controller.resume()
+    assert (orchestrator.trading_paused, orchestrator.pause_reason) == (False, "")
+
+    controller.set_strategy_enabled("market_making@book_b", False)
+    assert orchestrator.disabled_strategies == {"market_making@book_b"}
+    assert [book["enabled"] for book in controller.strategies()] == [True, False]
+    controller.set_strategy_enabled("market_making@book_b", True)
+    assert orchestrator.disabled_strategies == set()
+
+    with pytest.raises(KeyError):
+        controller.set_strategy_enabled("market_making", False)  # Only book_b runs it
+
+
+@pytest.mark.asyncio
+async def test_flatten_pauses_and_closes_every_book(controller, orchestrator):
+    result = await controller.flatten(source="10.0.0.5")
+
+    assert result["closed"] == {"fifteen_min_crypto": 1}
+    assert result["remaining"] == {}
+    assert result["orders_cancelled"] == {PRIMARY_WALLET: 1, "book_b": 1}
+    assert result["errors"] == {"market_making@book_b": "exchange down"}
+    assert result["trading_paused"] is True  # No re-entry until resumed
+    assert controller.positions() == []
+
+
+@pytest.mark.asyncio
+async def test_flatten_reports_positions_left_open(controller, orchestrator):
+    strategy = orchestrator.wallets.get(PRIMARY_WALLET).strategies[0]
+    stuck = {"token_id": "t2", "asset": "ETH", "side": "DOWN", "size": Decimal("5"), "status": "open"}
+    strategy.held.append(stuck)
+    strategy.stuck.append(stuck)
+
+    result = await controller.flatten()
+
+    assert result["closed"] == {"fifteen_min_crypto": 1}
+    assert result["remaining"] == {"fifteen_min_crypto": 1}
+    assert [p["token_id"] for p in controller.positions()] == ["t2"]
+
+
+def test_debug_mode_restores_log_levels(controller, orchestrator):
+    root = logging.getLogger()
+    level = root.level
+    root.setLevel(logging.INFO)
+    try:
+        controller.set_debug_mode(True)
+        assert root.level == logging.DEBUG
+        logging.getLogger("src.test").debug("verbose detail")
+        assert orchestrator.dashboard.state.debug_logs[-1].endswith("DEBUG src.test: verbose detail")
+
+        controller.set_debug_mode(False)
+        assert root.level == logging.INFO
+        assert orchestrator.dashboard.state.debug_mode is False
+        count = len(orchestrator.dashboard.state.debug_logs)
+        logging.getLogger("src.test").warning("after")
+        assert len(orchestrator.dashboard.state.debug_logs) == count
+    finally:
+        controller.set_debug_mode(False)
+        root.setLevel(level)
+
+
+# ============================================================================
+# Flatten in the 15-minute strategy
+# ============================================================================
+
+@pytest.mark.asyncio
+async def test_close_all_positions_keeps_unpriced_positions(tmp_path):
+    strategy = FifteenMinuteCryptoStrategy(
+        clob_client=MagicMock(),
+        trade_size=5.0,
+        dry_run=True,
+        enable_adaptive_learning=False,
+        positions_file=str(tmp_path / "positions.json")
+    )
+    strategy.positions.clear()
+    strategy._close_position = AsyncMock(return_value=True)
+    strategy._save_positions = MagicMock()
+    strategy._record_trade_outcome = MagicMock()
+    strategy.polymarket_ws_feed = Mock(unsubscribe=AsyncMock())
+    for token_id, asset in (("priced", "BTC"), ("unpriced", "ETH")):
+        strategy.positions[token_id] = Position(
+            token_id=token_id, side="UP", entry_price=Decimal("0.50"), size=Decimal("10"),
+            entry_time=datetime.now(timezone.utc) - timedelta(minutes=3), market_id=f"m_{asset}", asset=asset
+        )
+    prices = {"priced": (Decimal("0.55"), True), "unpriced": (None, False)}
+    strategy._get_exit_price = AsyncMock(side_effect=lambda position: prices[position.token_id])
+
+    assert await strategy.close_all_positions() == 1
+
+    assert list(strategy.positions) == ["unpriced"]
+    strategy._close_position.assert_awaited_once()
+    assert strategy._record_trade_outcome.call_args.kwargs["exit_reason"] == "manual_flatten"
+    strategy._save_positions.assert_called_once()